@@ -0,0 +1,77 @@
+//! Simulation throughput benchmarks
+//!
+//! Run with `cargo bench`. `simulate/round` is the hot path of every
+//! Monte Carlo feature: deal a hand, choose the best play, and score it.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use jimbo::core::joker::JokerKind;
+use jimbo::core::{
+    create_standard_deck, DrawPile, GameRunState, Hand, Joker, ScoreCalculator, Solver,
+};
+use std::hint::black_box;
+
+/// Rounds simulated per iteration of the simulation benchmarks
+const RUNS: usize = 200;
+
+fn jokers() -> Vec<Joker> {
+    [JokerKind::Joker, JokerKind::GreedyJoker, JokerKind::Baron]
+        .into_iter()
+        .map(Joker::new)
+        .collect()
+}
+
+fn bench_simulate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("simulate");
+    group.throughput(Throughput::Elements(RUNS as u64));
+    for (name, run) in [
+        ("round", GameRunState::default()),
+        ("round_with_jokers", GameRunState::default().with_jokers(jokers())),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || {
+                    let mut config = run.simulation_config(RUNS, Some(1));
+                    config.hands_remaining = 1;
+                    config.discards_remaining = 0;
+                    config.parallelism = Some(1);
+                    (jimbo::core::Simulator::new(run.solver()), config)
+                },
+                |(simulator, config)| black_box(simulator.simulate(config)),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn bench_solve(c: &mut Criterion) {
+    let deck = create_standard_deck();
+    let hand: Vec<_> = deck.iter().step_by(6).take(8).cloned().collect();
+    let solver = Solver::new(ScoreCalculator::new(jokers()));
+    c.bench_function("solve/8_cards", |b| b.iter(|| black_box(solver.solve(&hand))));
+
+    let calculator = ScoreCalculator::new(jokers());
+    let play = Hand::new(hand[..5].to_vec());
+    c.bench_function("score/calculate", |b| {
+        b.iter(|| black_box(calculator.calculate(&play, &hand[5..])))
+    });
+    c.bench_function("score/calculate_quick", |b| {
+        b.iter(|| black_box(calculator.calculate_quick(&play, &hand[5..])))
+    });
+}
+
+fn bench_draw(c: &mut Criterion) {
+    let deck = create_standard_deck();
+    c.bench_function("draw/8_of_52", |b| {
+        let mut seed = 0;
+        b.iter(|| {
+            seed += 1;
+            let mut hand = Vec::with_capacity(8);
+            DrawPile::new(&deck, seed).draw_to(&mut hand, 8);
+            black_box(hand)
+        })
+    });
+}
+
+criterion_group!(benches, bench_simulate, bench_solve, bench_draw);
+criterion_main!(benches);
@@ -0,0 +1,206 @@
+//! Compare command implementation
+//!
+//! This module implements the `compare` command which simulates several
+//! builds on the same hands and reports how each differs from the first.
+
+use super::solve::parse_jokers;
+use crate::config::DeckConfig;
+use crate::core::score::format_score;
+use crate::core::{
+    create_standard_deck, BuildComparison, BuildConfig, DeckType, GameRunState, HandLevels,
+    Simulator, Solver,
+};
+use anyhow::{Context, Result};
+use clap::Args;
+
+/// Arguments for the compare command
+#[derive(Debug, Args)]
+pub struct CompareArgs {
+    /// A build to compare, given at least twice: semicolon-separated
+    /// name=, jokers=, deck= (a deck file or an official deck), and
+    /// levels= entries (e.g., "name=Flush;jokers=Droll Joker,Joker;levels=Flush=3"),
+    /// or just a comma-separated list of jokers; the first is the baseline
+    #[arg(long = "build", required = true)]
+    builds: Vec<String>,
+
+    /// Number of simulation runs per build (default: 1000)
+    #[arg(long, default_value = "1000")]
+    runs: usize,
+
+    /// Path to deck configuration file for builds without their own
+    /// (default: standard 52-card deck)
+    #[arg(long)]
+    deck: Option<String>,
+
+    /// Hand size to draw (default: 8)
+    #[arg(long, default_value = "8")]
+    hand_size: usize,
+
+    /// Hands played per simulated round (default: 1)
+    #[arg(long, default_value = "1")]
+    hands: u32,
+
+    /// Discards available per simulated round (default: 0)
+    #[arg(long, default_value = "0")]
+    discards: u32,
+
+    /// Optional seed for reproducible results
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+/// Runs the compare command
+pub fn run(args: CompareArgs) -> Result<()> {
+    if args.builds.len() < 2 {
+        anyhow::bail!("Give at least two --build specs to compare");
+    }
+    let builds = args
+        .builds
+        .iter()
+        .map(|spec| parse_build(spec, args.seed.unwrap_or_default()))
+        .collect::<Result<Vec<_>>>()?;
+
+    let deck = match &args.deck {
+        Some(deck_path) => DeckConfig::from_file(deck_path)
+            .with_context(|| format!("Failed to load deck config from {}", deck_path))?
+            .to_cards()?,
+        None => create_standard_deck(),
+    };
+    let mut run = GameRunState::default().with_deck(deck);
+    run.hands_remaining = args.hands;
+    run.discards_remaining = args.discards;
+    let mut config = run.simulation_config(args.runs, args.seed);
+    config.hand_size = args.hand_size;
+
+    outln!("Running {} paired simulations of {} builds...", args.runs, builds.len());
+    let comparison = Simulator::new(Solver::new(run.calculator())).compare(builds, &config);
+    display_comparison(&comparison);
+    Ok(())
+}
+
+/// Parses a build spec: semicolon-separated "key=value" entries (name,
+/// jokers, deck, levels), or a bare comma-separated list of jokers
+///
+/// Official decks are dealt their starting cards from the seed.
+fn parse_build(spec: &str, seed: u64) -> Result<BuildConfig> {
+    if !spec.contains('=') {
+        return parse_build(&format!("jokers={}", spec), seed);
+    }
+
+    let mut name = None;
+    let mut build = BuildConfig::new("", vec![]);
+    for entry in spec.split(';').map(str::trim).filter(|entry| !entry.is_empty()) {
+        let (key, value) = entry
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Invalid build entry: {}. Use '<key>=<value>'", entry))?;
+        let value = value.trim();
+        match key.trim().to_lowercase().replace(['_', ' '], "-").as_str() {
+            "name" => name = Some(value.to_string()),
+            "jokers" => {
+                let names: Vec<String> = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|name| !name.is_empty())
+                    .map(String::from)
+                    .collect();
+                build.jokers = parse_jokers(&names)?;
+            }
+            "deck" => {
+                let cards = match value.parse::<DeckType>() {
+                    Ok(deck_type) => deck_type.create_cards(seed),
+                    Err(_) => DeckConfig::from_file(value)
+                        .with_context(|| format!("Failed to load deck config from {}", value))?
+                        .to_cards()?,
+                };
+                build = build.with_deck(cards);
+            }
+            "levels" | "hand-levels" => {
+                let entries: Vec<String> = value.split(',').map(String::from).collect();
+                build = build.with_hand_levels(HandLevels::from_entries(&entries)?);
+            }
+            _ => anyhow::bail!(
+                "Unknown build entry: {}. Use 'name', 'jokers', 'deck', or 'levels'",
+                key
+            ),
+        }
+    }
+
+    build.name = name.unwrap_or_else(|| match build.jokers.is_empty() {
+        true => "No jokers".to_string(),
+        false => {
+            let names: Vec<&str> = build.jokers.iter().map(|joker| joker.kind.name()).collect();
+            names.join(", ")
+        }
+    });
+    Ok(build)
+}
+
+/// Displays the builds side by side, with each one's difference from the
+/// first
+fn display_comparison(comparison: &BuildComparison) {
+    outln!("\n⚖️  Build Comparison ({} runs each):", comparison.num_runs);
+    let width = comparison
+        .builds
+        .iter()
+        .map(|build| build.name.chars().count())
+        .max()
+        .unwrap_or_default()
+        .max(5);
+    outln!(
+        "  {:<width$} {:>12} {:>12} {:>12}  vs. {}",
+        "Build",
+        "Mean",
+        "Median",
+        "P95",
+        comparison.builds[0].name,
+        width = width
+    );
+    for build in &comparison.builds {
+        let difference = match &build.difference {
+            None => "baseline".to_string(),
+            Some(difference) => format!(
+                "{:+.1} ({:+.1} to {:+.1}), wins {:.0}% of runs{}",
+                difference.mean,
+                difference.lower,
+                difference.upper,
+                difference.win_rate * 100.0,
+                if difference.is_significant() { "" } else { " (not significant)" }
+            ),
+        };
+        outln!(
+            "  {:<width$} {:>12} {:>12} {:>12}  {}",
+            build.name,
+            format_score(build.stats.mean_score),
+            build.stats.median_score.to_string(),
+            build.stats.percentile_95.to_string(),
+            difference,
+            width = width
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::joker::JokerKind;
+    use crate::core::HandType;
+
+    #[test]
+    fn test_parse_build_specs() {
+        let build = parse_build("name=Flush;jokers=Droll Joker, Joker;levels=Flush=3", 0).unwrap();
+        assert_eq!(build.name, "Flush");
+        let kinds: Vec<JokerKind> = build.jokers.iter().map(|joker| joker.kind.clone()).collect();
+        assert_eq!(kinds, vec![JokerKind::DrollJoker, JokerKind::Joker]);
+        assert_eq!(build.hand_levels.level(HandType::Flush), 3);
+        assert!(build.deck.is_none());
+
+        let build = parse_build("Baron,Mime", 0).unwrap();
+        assert_eq!(build.name, "Baron, Mime");
+        let build = parse_build("deck=abandoned", 0).unwrap();
+        assert_eq!(build.name, "No jokers");
+        assert_eq!(build.deck.map(|deck| deck.len()), Some(40));
+
+        assert!(parse_build("jokers=Jimbo the Great", 0).is_err());
+        assert!(parse_build("color=red", 0).is_err());
+    }
+}
@@ -0,0 +1,122 @@
+//! Completions command implementation
+//!
+//! This module implements the `completions` command which prints the
+//! script that hooks jimbo into a shell's tab completion. The script calls
+//! back into jimbo while completing, so flags that take jokers, boss
+//! blinds, official decks, or tags complete from the names jimbo knows,
+//! including each entry of a comma-separated list.
+
+use crate::core::joker::JokerKind;
+use crate::core::{Archetype, BossBlind, DeckType, Tag};
+use anyhow::{Context, Result};
+use clap::builder::PossibleValuesParser;
+use clap::Args;
+use clap_complete::engine::CompletionCandidate;
+use clap_complete::env::Shells;
+
+/// Environment variable the completion script sets when calling back into
+/// jimbo
+pub const COMPLETE_VAR: &str = "COMPLETE";
+
+/// Arguments for the completions command
+#[derive(Debug, Args)]
+pub struct CompletionsArgs {
+    /// Shell to print the completion script for
+    #[arg(value_parser = PossibleValuesParser::new(Shells::builtins().names()))]
+    shell: String,
+}
+
+/// Runs the completions command
+pub fn run(args: CompletionsArgs) -> Result<()> {
+    let shells = Shells::builtins();
+    let shell = shells
+        .completer(&args.shell)
+        .with_context(|| format!("Unsupported shell: {}", args.shell))?;
+    // The script calls this same binary, so completions match its version
+    let completer = std::env::current_exe()
+        .map_or_else(|_| "jimbo".to_string(), |path| path.to_string_lossy().into_owned());
+    let mut stdout = std::io::stdout();
+    shell.write_registration(COMPLETE_VAR, "jimbo", "jimbo", &completer, &mut stdout)?;
+    Ok(())
+}
+
+/// Completes joker names, e.g. for `--jokers`
+pub fn joker_candidates() -> Vec<CompletionCandidate> {
+    JokerKind::all()
+        .into_iter()
+        .map(|kind| {
+            CompletionCandidate::new(format!("{:?}", kind)).help(Some(kind.name().into()))
+        })
+        .collect()
+}
+
+/// Completes boss blind names, e.g. for `--boss`
+pub fn boss_candidates() -> Vec<CompletionCandidate> {
+    BossBlind::all()
+        .into_iter()
+        .map(|boss| CompletionCandidate::new(format!("{:?}", boss)).help(Some(boss.name().into())))
+        .collect()
+}
+
+/// Completes official deck names, e.g. for `--deck-type`
+pub fn deck_type_candidates() -> Vec<CompletionCandidate> {
+    DeckType::all()
+        .into_iter()
+        .map(|deck| CompletionCandidate::new(format!("{:?}", deck).to_lowercase()))
+        .collect()
+}
+
+/// Completes tag names, e.g. for `--small-tag`
+pub fn tag_candidates() -> Vec<CompletionCandidate> {
+    Tag::all()
+        .into_iter()
+        .map(|tag| {
+            let name = tag.name().trim_end_matches(" Tag");
+            CompletionCandidate::new(name.to_lowercase().replace(['-', ' '], "_"))
+                .help(Some(tag.name().into()))
+        })
+        .collect()
+}
+
+/// Completes seed archetypes, e.g. for `--archetype`
+pub fn archetype_candidates() -> Vec<CompletionCandidate> {
+    Archetype::all()
+        .into_iter()
+        .map(|archetype| {
+            CompletionCandidate::new(archetype.name().to_lowercase().replace(' ', "_"))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(candidates: Vec<CompletionCandidate>) -> Vec<String> {
+        candidates
+            .iter()
+            .map(|candidate| candidate.get_value().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_candidates_parse_back() {
+        for joker in values(joker_candidates()) {
+            assert!(joker.parse::<JokerKind>().is_ok(), "{}", joker);
+        }
+        for boss in values(boss_candidates()) {
+            assert!(boss.parse::<BossBlind>().is_ok(), "{}", boss);
+        }
+        let decks = values(deck_type_candidates());
+        assert!(decks.contains(&"plasma".to_string()));
+        for deck in decks {
+            assert!(deck.parse::<DeckType>().is_ok(), "{}", deck);
+        }
+        for tag in values(tag_candidates()) {
+            assert!(tag.parse::<Tag>().is_ok(), "{}", tag);
+        }
+        for archetype in values(archetype_candidates()) {
+            assert!(archetype.parse::<Archetype>().is_ok(), "{}", archetype);
+        }
+    }
+}
@@ -1,11 +1,25 @@
 //! Config command implementation
 //!
 //! This module implements the `config` command which manages
-//! configuration files for decks and game states.
+//! configuration files for decks and game states, and the user
+//! configuration whose defaults apply to every command.
 
-use crate::config::{DeckConfig, GameState};
+use super::deck_builder;
+use super::output::{self, OutputFormat, Report};
+use crate::config::user::CONFIG_VAR;
+use crate::config::{
+    diff_decks, diff_game_states, format, ChangeKind, ConfigChange, ConfigFormat, DeckConfig,
+    GameState, UserConfig,
+};
+use crate::core::names::unknown_name;
+use crate::core::{BlindConfig, Joker};
 use anyhow::{Context, Result};
-use clap::{Args, Subcommand};
+use clap::builder::ArgPredicate;
+use clap::{Args, Command, Subcommand};
+use rustyline::DefaultEditor;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
 
 /// Arguments for the config command
 #[derive(Debug, Args)]
@@ -23,15 +37,63 @@ enum ConfigCommand {
         #[arg(value_parser = ["deck", "game-state"])]
         config_type: String,
 
-        /// Output file path
+        /// Output file path; its extension picks the format: .json,
+        /// .yaml, or .toml
         #[arg(short, long)]
         output: String,
+
+        /// Format to write, whatever the file's extension: json, yaml, or
+        /// toml
+        #[arg(long)]
+        format: Option<ConfigFormat>,
+
+        /// Build the deck step by step: base deck, cards to remove and
+        /// add, and card modifiers
+        #[arg(short, long)]
+        interactive: bool,
+    },
+
+    /// Convert a configuration file to another format
+    Convert {
+        /// Path to configuration file
+        file: String,
+
+        /// Format to convert to: json, yaml, or toml
+        #[arg(long)]
+        to: ConfigFormat,
+
+        /// Output file path (default: the input path with the new format's
+        /// extension)
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+
+    /// Show what changed between two decks or two game states
+    Diff {
+        /// The earlier configuration file
+        before: String,
+
+        /// The later configuration file
+        after: String,
+
+        /// Output format: pretty (default), json, yaml, csv, compact
+        #[arg(long, default_value = "pretty")]
+        output: OutputFormat,
     },
 
     /// Validate an existing configuration file
     Validate {
         /// Path to configuration file
         file: String,
+
+        /// Format to read, whatever the file's extension: json, yaml, or
+        /// toml
+        #[arg(long)]
+        format: Option<ConfigFormat>,
+
+        /// Output format: pretty (default), json, yaml, csv, compact
+        #[arg(long, default_value = "pretty")]
+        output: OutputFormat,
     },
 
     /// List all saved configurations in a directory
@@ -39,6 +101,26 @@ enum ConfigCommand {
         /// Directory to search (default: current directory)
         #[arg(short, long, default_value = ".")]
         dir: String,
+
+        /// Output format: pretty (default), json, yaml, csv, compact
+        #[arg(long, default_value = "pretty")]
+        output: OutputFormat,
+    },
+
+    /// Set a default in the user configuration (runs, hand-size, output,
+    /// threads) or a joker set (jokers.NAME); an empty value removes it
+    Set {
+        /// Setting to change, e.g. "runs" or "jokers.flush"
+        key: String,
+
+        /// New value, e.g. "5000", or "Droll Joker,Four Fingers" for a joker set
+        value: String,
+    },
+
+    /// Print a setting from the user configuration, or all of them
+    Get {
+        /// Setting to print (default: every setting that is set)
+        key: Option<String>,
     },
 }
 
@@ -48,29 +130,54 @@ pub fn run(args: ConfigArgs) -> Result<()> {
         ConfigCommand::Init {
             config_type,
             output,
-        } => init_config(&config_type, &output),
-        ConfigCommand::Validate { file } => validate_config(&file),
-        ConfigCommand::List { dir } => list_configs(&dir),
+            format,
+            interactive,
+        } => {
+            let format = format.unwrap_or_else(|| ConfigFormat::from_path(&output));
+            init_config(&config_type, &output, format, interactive)
+        }
+        ConfigCommand::Convert { file, to, output } => convert_config(&file, to, output),
+        ConfigCommand::Diff { before, after, output } => diff_configs(&before, &after, output),
+        ConfigCommand::Validate { file, format, output } => {
+            let format = format.unwrap_or_else(|| ConfigFormat::from_path(&file));
+            validate_config(&file, format, output)
+        }
+        ConfigCommand::List { dir, output } => list_configs(&dir, output),
+        ConfigCommand::Set { key, value } => set_user_setting(&key, &value),
+        ConfigCommand::Get { key } => get_user_settings(key.as_deref()),
     }
 }
 
 /// Initializes a new configuration file
-fn init_config(config_type: &str, output_path: &str) -> Result<()> {
+fn init_config(
+    config_type: &str,
+    output_path: &str,
+    format: ConfigFormat,
+    interactive: bool,
+) -> Result<()> {
     match config_type {
+        "deck" if interactive => {
+            let deck = deck_builder::build_deck(&mut DefaultEditor::new()?)?;
+            deck.to_file_as(output_path, format)
+                .with_context(|| format!("Failed to create deck config at {}", output_path))?;
+            outln!("\n✅ Created deck configuration at: {}", output_path);
+            outln!("   ({} cards)", deck.cards.len());
+        }
         "deck" => {
             let deck = DeckConfig::standard();
-            deck.to_file(output_path)
+            deck.to_file_as(output_path, format)
                 .with_context(|| format!("Failed to create deck config at {}", output_path))?;
-            println!("✅ Created standard deck configuration at: {}", output_path);
-            println!("   (52-card standard deck)");
+            outln!("✅ Created standard deck configuration at: {}", output_path);
+            outln!("   (52-card standard deck)");
         }
         "game-state" => {
+            anyhow::ensure!(!interactive, "--interactive only builds deck configurations");
             let state = GameState::default();
             state
-                .to_file(output_path)
+                .to_file_as(output_path, format)
                 .with_context(|| format!("Failed to create game state at {}", output_path))?;
-            println!("✅ Created empty game state at: {}", output_path);
-            println!("   Edit the file to add jokers, vouchers, and blind configuration");
+            outln!("✅ Created empty game state at: {}", output_path);
+            outln!("   Edit the file to add jokers, vouchers, and blind configuration");
         }
         _ => anyhow::bail!("Invalid config type: {}. Use 'deck' or 'game-state'", config_type),
     }
@@ -78,55 +185,314 @@ fn init_config(config_type: &str, output_path: &str) -> Result<()> {
     Ok(())
 }
 
+/// A deck configuration or game state, loaded without knowing which
+enum ConfigFile {
+    Deck(DeckConfig),
+    GameState(GameState),
+}
+
+impl ConfigFile {
+    /// Loads a configuration file in any format
+    ///
+    /// A file with a card list is a deck configuration and anything else a
+    /// game state, so an invalid deck is reported rather than read as a
+    /// game state without its cards.
+    fn load(file_path: &str) -> Result<Self> {
+        let config: serde_json::Value = format::read_file(Path::new(file_path), "configuration")?;
+        if config.get("cards").is_some() {
+            let deck: DeckConfig =
+                serde_json::from_value(config).context("Failed to parse deck config")?;
+            deck.validate()?;
+            Ok(ConfigFile::Deck(deck))
+        } else {
+            let state: GameState =
+                serde_json::from_value(config).context("Failed to parse game state")?;
+            Ok(ConfigFile::GameState(state))
+        }
+    }
+
+    /// Names the kind of configuration, e.g. for messages
+    fn kind(&self) -> &'static str {
+        match self {
+            ConfigFile::Deck(_) => "deck configuration",
+            ConfigFile::GameState(_) => "game state",
+        }
+    }
+}
+
+/// Converts a configuration file to another format
+fn convert_config(file_path: &str, to: ConfigFormat, output: Option<String>) -> Result<()> {
+    let output = output.map_or_else(|| to.path_for(file_path), PathBuf::from);
+    anyhow::ensure!(
+        output != Path::new(file_path),
+        "{} would be overwritten; choose another path with --output",
+        file_path
+    );
+
+    let config = ConfigFile::load(file_path)?;
+    let contents = match &config {
+        ConfigFile::Deck(deck) => to.to_string_lossless(deck)?,
+        ConfigFile::GameState(state) => to.to_string_lossless(state)?,
+    };
+    std::fs::write(&output, contents)
+        .with_context(|| format!("Failed to write {} to {:?}", config.kind(), output))?;
+
+    outln!("✅ Converted {} to {}: {}", config.kind(), to, output.display());
+    Ok(())
+}
+
+/// The differences between two configuration files
+#[derive(Debug, Serialize)]
+struct DiffReport {
+    before: String,
+    after: String,
+    kind: &'static str,
+    changes: Vec<ConfigChange>,
+}
+
+impl Report for DiffReport {
+    fn print_pretty(&self) {
+        outln!("🔀 {}: {} → {}", capitalize(self.kind), self.before, self.after);
+        if self.changes.is_empty() {
+            outln!("   No differences");
+        }
+        for change in &self.changes {
+            outln!("   {}", describe_change(change));
+        }
+    }
+
+    fn print_compact(&self) {
+        for change in &self.changes {
+            outln!("{}", describe_change(change));
+        }
+    }
+
+    /// One row per change
+    fn records(&self) -> Result<Vec<serde_json::Value>> {
+        self.changes.iter().map(|change| Ok(serde_json::to_value(change)?)).collect()
+    }
+}
+
+/// Describes a change on one line: "+" added, "-" removed, "~" changed
+fn describe_change(change: &ConfigChange) -> String {
+    let before = change.before.as_deref().unwrap_or_default();
+    let after = change.after.as_deref().unwrap_or_default();
+    match change.kind {
+        ChangeKind::Added => format!("+ {}: {}", change.field, after),
+        ChangeKind::Removed => format!("- {}: {}", change.field, before),
+        ChangeKind::Changed => format!("~ {}: {} → {}", change.field, before, after),
+    }
+}
+
+/// Capitalizes the first letter, e.g. "game state" to "Game state"
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars.next().map_or_else(String::new, |first| first.to_uppercase().chain(chars).collect())
+}
+
+/// Prints the differences between two decks or two game states
+fn diff_configs(before_path: &str, after_path: &str, format: OutputFormat) -> Result<()> {
+    let (before, after) = (ConfigFile::load(before_path)?, ConfigFile::load(after_path)?);
+    let changes = match (&before, &after) {
+        (ConfigFile::Deck(old), ConfigFile::Deck(new)) => diff_decks(old, new)?,
+        (ConfigFile::GameState(old), ConfigFile::GameState(new)) => diff_game_states(old, new),
+        _ => anyhow::bail!("Cannot compare a {} with a {}", before.kind(), after.kind()),
+    };
+
+    let report = DiffReport {
+        before: before_path.to_string(),
+        after: after_path.to_string(),
+        kind: before.kind(),
+        changes,
+    };
+    output::print(&report, format)
+}
+
+/// What a valid configuration file holds
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum ConfigSummary {
+    Deck {
+        cards: usize,
+        enhancements: usize,
+        editions: usize,
+        seals: usize,
+    },
+    GameState {
+        jokers: Vec<String>,
+        consumables: Vec<String>,
+        vouchers: Vec<String>,
+        blind: Option<BlindConfig>,
+        seed: Option<u64>,
+    },
+}
+
+/// A configuration file that passed validation
+#[derive(Debug, Serialize)]
+struct ValidationReport {
+    file: String,
+    #[serde(flatten)]
+    summary: ConfigSummary,
+}
+
+impl Report for ValidationReport {
+    fn print_pretty(&self) {
+        match &self.summary {
+            ConfigSummary::Deck { cards, enhancements, editions, seals } => {
+                outln!("✅ Valid deck configuration");
+                outln!("   Cards: {}", cards);
+                outln!("   Enhancements: {}", enhancements);
+                outln!("   Editions: {}", editions);
+                outln!("   Seals: {}", seals);
+            }
+            ConfigSummary::GameState { jokers, consumables, vouchers, blind, seed } => {
+                outln!("✅ Valid game state configuration");
+                outln!("   Jokers: {}", jokers.len());
+                outln!("   Consumables: {}", consumables.len());
+                outln!("   Vouchers: {}", vouchers.len());
+                if let Some(blind) = blind {
+                    outln!("   Blind: {:?} (score required: {})",
+                        blind.blind_type, blind.score_required);
+                }
+                if let Some(seed) = seed {
+                    outln!("   Seed: {}", seed);
+                }
+            }
+        }
+    }
+
+    fn print_compact(&self) {
+        match &self.summary {
+            ConfigSummary::Deck { cards, .. } => {
+                outln!("{}: valid deck ({} cards)", self.file, cards)
+            }
+            ConfigSummary::GameState { jokers, .. } => {
+                outln!("{}: valid game state ({} jokers)", self.file, jokers.len())
+            }
+        }
+    }
+}
+
 /// Validates a configuration file
-fn validate_config(file_path: &str) -> Result<()> {
+fn validate_config(
+    file_path: &str,
+    config_format: ConfigFormat,
+    format: OutputFormat,
+) -> Result<()> {
     // Try to load as deck config first
-    if let Ok(deck_config) = DeckConfig::from_file(file_path) {
-        match deck_config.validate() {
-            Ok(()) => {
-                println!("✅ Valid deck configuration");
-                println!("   Cards: {}", deck_config.cards.len());
-                println!("   Enhancements: {}", deck_config.enhancements.len());
-                println!("   Editions: {}", deck_config.editions.len());
-                println!("   Seals: {}", deck_config.seals.len());
-                return Ok(());
-            }
-            Err(e) => {
-                println!("❌ Invalid deck configuration:");
-                println!("   {}", e);
-                return Err(e);
+    if let Ok(deck_config) = DeckConfig::from_file_as(file_path, config_format) {
+        if let Err(e) = deck_config.validate() {
+            if !format.is_structured() {
+                outln!("❌ Invalid deck configuration:");
+                outln!("   {}", e);
             }
+            return Err(e);
         }
+        let report = ValidationReport {
+            file: file_path.to_string(),
+            summary: ConfigSummary::Deck {
+                cards: deck_config.cards.len(),
+                enhancements: deck_config.enhancements.len(),
+                editions: deck_config.editions.len(),
+                seals: deck_config.seals.len(),
+            },
+        };
+        return output::print(&report, format);
     }
 
     // Try to load as game state
-    if let Ok(game_state) = GameState::from_file(file_path) {
-        println!("✅ Valid game state configuration");
-        println!("   Jokers: {}", game_state.jokers.len());
-        println!("   Consumables: {}", game_state.consumables.len());
-        println!("   Vouchers: {}", game_state.vouchers.len());
-        if let Some(blind) = &game_state.blind {
-            println!("   Blind: {:?} (score required: {})",
-                blind.blind_type, blind.score_required);
-        }
-        if let Some(seed) = game_state.seed {
-            println!("   Seed: {}", seed);
+    if let Ok(game_state) = GameState::from_file_as(file_path, config_format) {
+        if let Err(e) = game_state.validate() {
+            if !format.is_structured() {
+                outln!("❌ Invalid game state:");
+                outln!("   {}", e);
+            }
+            return Err(e);
         }
-        return Ok(());
+        let report = ValidationReport {
+            file: file_path.to_string(),
+            summary: ConfigSummary::GameState {
+                jokers: game_state.jokers,
+                consumables: game_state.consumables,
+                vouchers: game_state.vouchers,
+                blind: game_state.blind,
+                seed: game_state.seed,
+            },
+        };
+        return output::print(&report, format);
     }
 
     anyhow::bail!("File is not a valid deck config or game state: {}", file_path)
 }
 
+/// The configuration files found in a directory
+#[derive(Debug, Serialize)]
+struct ConfigList {
+    directory: String,
+    decks: Vec<String>,
+    game_states: Vec<String>,
+}
+
+impl Report for ConfigList {
+    fn print_pretty(&self) {
+        if self.decks.is_empty() && self.game_states.is_empty() {
+            outln!("No configuration files found in: {}", self.directory);
+            return;
+        }
+
+        if !self.decks.is_empty() {
+            outln!("🃏 Deck Configurations:");
+            for config in &self.decks {
+                outln!("   - {}", config);
+            }
+        }
+
+        if !self.game_states.is_empty() {
+            outln!("\n🎮 Game States:");
+            for state in &self.game_states {
+                outln!("   - {}", state);
+            }
+        }
+    }
+
+    fn print_compact(&self) {
+        for config in &self.decks {
+            outln!("deck {}", config);
+        }
+        for state in &self.game_states {
+            outln!("game_state {}", state);
+        }
+    }
+
+    /// One row per file
+    fn records(&self) -> Result<Vec<serde_json::Value>> {
+        #[derive(Serialize)]
+        struct ConfigRecord<'a> {
+            kind: &'static str,
+            path: &'a str,
+        }
+
+        let decks = self.decks.iter().map(|path| ("deck", path));
+        let game_states = self.game_states.iter().map(|path| ("game_state", path));
+        decks
+            .chain(game_states)
+            .map(|(kind, path)| Ok(serde_json::to_value(ConfigRecord { kind, path })?))
+            .collect()
+    }
+}
+
 /// Lists all configuration files in a directory
-fn list_configs(dir_path: &str) -> Result<()> {
+fn list_configs(dir_path: &str, format: OutputFormat) -> Result<()> {
     use std::fs;
 
     let entries = fs::read_dir(dir_path)
         .with_context(|| format!("Failed to read directory: {}", dir_path))?;
 
-    let mut deck_configs = Vec::new();
-    let mut game_states = Vec::new();
+    let mut list = ConfigList {
+        directory: dir_path.to_string(),
+        decks: Vec::new(),
+        game_states: Vec::new(),
+    };
 
     for entry in entries {
         let entry = entry?;
@@ -137,46 +503,425 @@ fn list_configs(dir_path: &str) -> Result<()> {
 
             // Try to load as deck config
             if DeckConfig::from_file(&path_str).is_ok() {
-                deck_configs.push(path_str.clone());
+                list.decks.push(path_str.clone());
             }
 
             // Try to load as game state
             if GameState::from_file(&path_str).is_ok() {
-                game_states.push(path_str);
+                list.game_states.push(path_str);
             }
         }
     }
 
-    if deck_configs.is_empty() && game_states.is_empty() {
-        println!("No configuration files found in: {}", dir_path);
-        return Ok(());
+    output::print(&list, format)
+}
+
+/// A setting in the user configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum UserSetting {
+    Runs,
+    HandSize,
+    Output,
+    Threads,
+    JokerSet(String),
+}
+
+impl UserSetting {
+    /// The settings `config set` and `config get` take, as they are written
+    const NAMES: [&str; 5] = ["runs", "hand-size", "output", "threads", "jokers.NAME"];
+
+    /// Every setting that is set, in the order `config get` prints them
+    fn all(config: &UserConfig) -> Vec<UserSetting> {
+        let settings = [
+            (UserSetting::Runs, config.runs.is_some()),
+            (UserSetting::HandSize, config.hand_size.is_some()),
+            (UserSetting::Output, config.output.is_some()),
+            (UserSetting::Threads, config.threads.is_some()),
+        ];
+        let sets = config.jokers.keys().map(|name| UserSetting::JokerSet(name.clone()));
+        settings
+            .into_iter()
+            .filter_map(|(setting, set)| set.then_some(setting))
+            .chain(sets)
+            .collect()
     }
 
-    if !deck_configs.is_empty() {
-        println!("🃏 Deck Configurations:");
-        for config in deck_configs {
-            println!("   - {}", config);
+    /// Returns the setting's value, if it is set
+    fn get(&self, config: &UserConfig) -> Option<String> {
+        match self {
+            UserSetting::Runs => config.runs.map(|runs| runs.to_string()),
+            UserSetting::HandSize => config.hand_size.map(|size| size.to_string()),
+            UserSetting::Output => config.output.clone(),
+            UserSetting::Threads => config.threads.map(|threads| threads.to_string()),
+            UserSetting::JokerSet(name) => config.jokers.get(name).map(|jokers| jokers.join(",")),
         }
     }
 
-    if !game_states.is_empty() {
-        println!("\n🎮 Game States:");
-        for state in game_states {
-            println!("   - {}", state);
+    /// Sets the setting, checking the value first; an empty value unsets it
+    fn set(&self, config: &mut UserConfig, value: &str) -> Result<()> {
+        let value = value.trim();
+        if value.is_empty() {
+            match self {
+                UserSetting::Runs => config.runs = None,
+                UserSetting::HandSize => config.hand_size = None,
+                UserSetting::Output => config.output = None,
+                UserSetting::Threads => config.threads = None,
+                UserSetting::JokerSet(name) => {
+                    config.jokers.remove(name);
+                }
+            }
+            return Ok(());
         }
+
+        let count = |value: &str| -> Result<usize> {
+            let count = value
+                .parse()
+                .with_context(|| format!("Invalid {}: {}; expected a number", self, value))?;
+            anyhow::ensure!(count > 0, "{} must be at least 1", self);
+            Ok(count)
+        };
+        match self {
+            UserSetting::Runs => config.runs = Some(count(value)?),
+            UserSetting::HandSize => config.hand_size = Some(count(value)?),
+            UserSetting::Output => {
+                value.parse::<OutputFormat>()?;
+                config.output = Some(value.to_lowercase());
+            }
+            UserSetting::Threads => {
+                let threads = u16::try_from(count(value)?)
+                    .with_context(|| format!("Invalid threads: {}; too many", value))?;
+                config.threads = Some(threads);
+            }
+            UserSetting::JokerSet(name) => {
+                let jokers: Vec<String> = value
+                    .split(',')
+                    .map(|joker| joker.trim().to_string())
+                    .filter(|joker| !joker.is_empty())
+                    .collect();
+                for joker in &jokers {
+                    joker.parse::<Joker>()?;
+                }
+                config.jokers.insert(name.clone(), jokers);
+            }
+        }
+        Ok(())
     }
+}
 
+impl std::fmt::Display for UserSetting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UserSetting::Runs => write!(f, "runs"),
+            UserSetting::HandSize => write!(f, "hand-size"),
+            UserSetting::Output => write!(f, "output"),
+            UserSetting::Threads => write!(f, "threads"),
+            UserSetting::JokerSet(name) => write!(f, "jokers.{}", name),
+        }
+    }
+}
+
+impl std::str::FromStr for UserSetting {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let key = s.trim();
+        if let Some(name) = key.strip_prefix("jokers.") {
+            anyhow::ensure!(
+                !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || "-_".contains(c)),
+                "Invalid joker set name: {:?}; use letters, digits, - and _",
+                name
+            );
+            return Ok(UserSetting::JokerSet(name.to_string()));
+        }
+        match key.to_lowercase().replace('_', "-").as_str() {
+            "runs" => Ok(UserSetting::Runs),
+            "hand-size" => Ok(UserSetting::HandSize),
+            "output" => Ok(UserSetting::Output),
+            "threads" => Ok(UserSetting::Threads),
+            _ => Err(unknown_name("setting", key, &UserSetting::NAMES, |name| {
+                name.to_lowercase().replace('_', "-")
+            })),
+        }
+    }
+}
+
+/// Returns the user configuration's path, for `config set` and `config get`
+fn user_config_path() -> Result<PathBuf> {
+    UserConfig::path()
+        .with_context(|| format!("Cannot find the user configuration; set {} or HOME", CONFIG_VAR))
+}
+
+/// Changes a setting in the user configuration file
+fn set_user_setting(key: &str, value: &str) -> Result<()> {
+    let setting: UserSetting = key.parse()?;
+    let path = user_config_path()?;
+    let mut config = UserConfig::load_from(&path)?;
+    setting.set(&mut config, value)?;
+    config.to_file(&path)?;
+
+    match setting.get(&config) {
+        Some(value) => outln!("✅ Set {} = {} in {}", setting, value, path.display()),
+        None => outln!("✅ Removed {} from {}", setting, path.display()),
+    }
     Ok(())
 }
 
+/// Prints one setting from the user configuration file, or every setting
+fn get_user_settings(key: Option<&str>) -> Result<()> {
+    let path = user_config_path()?;
+    let config = UserConfig::load_from(&path)?;
+    match key {
+        Some(key) => {
+            let setting: UserSetting = key.parse()?;
+            let value = setting
+                .get(&config)
+                .with_context(|| format!("{} is not set in {}", setting, path.display()))?;
+            outln!("{}", value);
+        }
+        None => {
+            outln!("⚙️  {}", path.display());
+            for setting in UserSetting::all(&config) {
+                outln!("   {} = {}", setting, setting.get(&config).unwrap_or_default());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the user configuration, loaded on first use
+///
+/// A configuration that fails to load, or whose defaults are invalid, is an
+/// error every time.
+pub fn user_config() -> Result<&'static UserConfig> {
+    static CONFIG: OnceLock<Result<UserConfig, String>> = OnceLock::new();
+    CONFIG
+        .get_or_init(|| {
+            load_user_config().map_err(|e| {
+                let path = UserConfig::path().unwrap_or_default();
+                format!("Invalid user configuration {}: {:#}", path.display(), e)
+            })
+        })
+        .as_ref()
+        .map_err(|e| anyhow::anyhow!("{}", e))
+}
+
+/// Loads the user configuration and checks the defaults it gives flags
+fn load_user_config() -> Result<UserConfig> {
+    let config = UserConfig::load()?;
+    if let Some(output) = &config.output {
+        output.parse::<OutputFormat>()?;
+    }
+    anyhow::ensure!(
+        config.runs != Some(0) && config.hand_size != Some(0) && config.threads != Some(0),
+        "runs, hand-size, and threads must be at least 1"
+    );
+    Ok(config)
+}
+
+/// Gives the command's flags, and its subcommands' flags, their defaults
+/// from the user configuration
+///
+/// Covers --runs, --hand-size, --threads, and the --output flags that pick
+/// a report format (not those naming a file). Flags given on the command
+/// line still win, and --max-time without --runs keeps running until time
+/// is up.
+pub fn with_user_defaults(mut command: Command, config: &UserConfig) -> Command {
+    let subcommands: Vec<String> =
+        command.get_subcommands().map(|sub| sub.get_name().to_string()).collect();
+    for name in subcommands {
+        command = command.mut_subcommand(name, |sub| with_user_defaults(sub, config));
+    }
+
+    let defaults = [
+        ("runs", config.runs.map(|runs| runs.to_string())),
+        ("hand_size", config.hand_size.map(|size| size.to_string())),
+        ("threads", config.threads.map(|threads| threads.to_string())),
+        ("output", config.output.clone()),
+    ];
+    let has_arg = |command: &Command, id: &str| command.get_arguments().any(|a| a.get_id() == id);
+    for (id, value) in defaults {
+        let Some(value) = value else { continue };
+        let Some(arg) = command.get_arguments().find(|arg| arg.get_id() == id) else { continue };
+        if id == "output" && !matches!(arg.get_default_values(), [default] if default == "pretty") {
+            continue;
+        }
+        let until_time_is_up = id == "runs" && has_arg(&command, "max_time");
+        command = command.mut_arg(id, |arg| {
+            let arg = arg.default_value(value);
+            if until_time_is_up {
+                arg.default_value_if("max_time", ArgPredicate::IsPresent, None)
+            } else {
+                arg
+            }
+        });
+    }
+    command
+}
+
 #[cfg(test)]
 mod tests {
-    use super::*;
-
     #[test]
     fn test_config_type_parsing() {
         // Just verify the command structure compiles
         assert_eq!("deck", "deck");
         assert_eq!("game-state", "game-state");
     }
+
+    #[test]
+    fn test_convert_config() {
+        use super::*;
+
+        let dir = std::env::temp_dir().join(format!("jimbo-convert-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let json = dir.join("deck.json");
+        DeckConfig::from_deck_type(crate::core::DeckType::Abandoned, 0).to_file(&json).unwrap();
+
+        let json = json.to_string_lossy().into_owned();
+        convert_config(&json, ConfigFormat::Toml, None).unwrap();
+        let deck = DeckConfig::from_file(dir.join("deck.toml")).unwrap();
+        assert_eq!(deck.cards.len(), 40);
+        assert!(convert_config(&json, ConfigFormat::Json, None).is_err());
+
+        let state = dir.join("state.yaml").to_string_lossy().into_owned();
+        std::fs::write(&state, "jokers: [Joker]\nmoney: 10\n").unwrap();
+        convert_config(&state, ConfigFormat::Json, None).unwrap();
+        assert_eq!(GameState::from_file(dir.join("state.json")).unwrap().money, 10);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_format_override() {
+        use super::*;
+
+        let dir = std::env::temp_dir().join(format!("jimbo-format-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let deck = dir.join("deck.cfg").to_string_lossy().into_owned();
+        init_config("deck", &deck, ConfigFormat::Toml, false).unwrap();
+        assert!(std::fs::read_to_string(&deck).unwrap().contains("[[cards]]"));
+        assert!(DeckConfig::from_file(&deck).is_err());
+        assert_eq!(DeckConfig::from_file_as(&deck, ConfigFormat::Toml).unwrap().cards.len(), 52);
+        validate_config(&deck, ConfigFormat::Toml, OutputFormat::Json).unwrap();
+        assert!(validate_config(&deck, ConfigFormat::Json, OutputFormat::Json).is_err());
+
+        let state = dir.join("state").to_string_lossy().into_owned();
+        init_config("game-state", &state, ConfigFormat::Yaml, false).unwrap();
+        assert_eq!(GameState::from_file_as(&state, ConfigFormat::Yaml).unwrap().ante, 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_diff_configs() {
+        use super::*;
+
+        let dir = std::env::temp_dir().join(format!("jimbo-diff-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = |name: &str| dir.join(name).to_string_lossy().into_owned();
+        std::fs::write(path("before.json"), r#"{"jokers": ["Joker"], "money": 4}"#).unwrap();
+        std::fs::write(path("after.yaml"), "jokers: [Baron]\nmoney: 4\n").unwrap();
+        DeckConfig::standard().to_file(path("deck.json")).unwrap();
+
+        assert!(diff_configs(&path("before.json"), &path("after.yaml"), OutputFormat::Json).is_ok());
+        assert!(diff_configs(&path("before.json"), &path("deck.json"), OutputFormat::Json).is_err());
+        assert_eq!(
+            describe_change(&diff_game_states(
+                &GameState::from_file(path("before.json")).unwrap(),
+                &GameState::from_file(path("after.yaml")).unwrap(),
+            )[0]),
+            "- jokers: Joker"
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_config_list_records() {
+        use super::*;
+
+        let list = ConfigList {
+            directory: ".".to_string(),
+            decks: vec!["deck.json".to_string()],
+            game_states: vec!["state.json".to_string()],
+        };
+        let records = list.records().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1]["kind"], "game_state");
+        assert_eq!(records[1]["path"], "state.json");
+    }
+
+    #[test]
+    fn test_user_settings() {
+        use super::*;
+
+        let mut config = UserConfig::default();
+        let set = |config: &mut UserConfig, key: &str, value: &str| {
+            key.parse::<UserSetting>()?.set(config, value)
+        };
+        set(&mut config, "runs", "5000").unwrap();
+        set(&mut config, "hand_size", "9").unwrap();
+        set(&mut config, "output", "JSON").unwrap();
+        set(&mut config, "jokers.flush", "Droll Joker, Four Fingers").unwrap();
+        assert_eq!(config.runs, Some(5000));
+        assert_eq!(config.output.as_deref(), Some("json"));
+        assert_eq!(config.jokers["flush"], ["Droll Joker", "Four Fingers"]);
+        let settings = UserSetting::all(&config);
+        let values: Vec<String> = settings
+            .iter()
+            .map(|setting| format!("{}={}", setting, setting.get(&config).unwrap()))
+            .collect();
+        assert_eq!(
+            values,
+            ["runs=5000", "hand-size=9", "output=json", "jokers.flush=Droll Joker,Four Fingers"]
+        );
+
+        assert!(set(&mut config, "runs", "0").is_err());
+        assert!(set(&mut config, "threads", "70000").is_err());
+        assert!(set(&mut config, "output", "xml").is_err());
+        assert!(set(&mut config, "jokers.flush", "Jokr").is_err());
+        assert!(set(&mut config, "jokers.", "Joker").is_err());
+        let error = set(&mut config, "rnus", "1").unwrap_err();
+        assert_eq!(error.to_string(), "Unknown setting: rnus (did you mean runs?)");
+
+        set(&mut config, "runs", "").unwrap();
+        set(&mut config, "jokers.flush", "").unwrap();
+        assert_eq!(config.runs, None);
+        assert!(config.jokers.is_empty());
+    }
+
+    #[test]
+    fn test_with_user_defaults() {
+        use super::*;
+        use clap::{Arg, ArgMatches};
+
+        let command = Command::new("jimbo")
+            .subcommand(
+                Command::new("simulate")
+                    .arg(Arg::new("runs").long("runs").value_parser(clap::value_parser!(usize)))
+                    .arg(Arg::new("max_time").long("max-time"))
+                    .arg(Arg::new("output").long("output").default_value("pretty")),
+            )
+            .subcommand(Command::new("init").arg(Arg::new("output").long("output")));
+        let config = UserConfig {
+            runs: Some(5000),
+            output: Some("json".into()),
+            ..UserConfig::default()
+        };
+        let command = with_user_defaults(command, &config);
+        let parse = |args: &[&str]| -> ArgMatches {
+            let matches = command.clone().try_get_matches_from(args).unwrap();
+            matches.subcommand().unwrap().1.clone()
+        };
+
+        let simulate = parse(&["jimbo", "simulate"]);
+        assert_eq!(simulate.get_one::<usize>("runs"), Some(&5000));
+        assert_eq!(simulate.get_one::<String>("output").unwrap(), "json");
+        let simulate = parse(&["jimbo", "simulate", "--runs", "10", "--output", "csv"]);
+        assert_eq!(simulate.get_one::<usize>("runs"), Some(&10));
+        assert_eq!(simulate.get_one::<String>("output").unwrap(), "csv");
+        let simulate = parse(&["jimbo", "simulate", "--max-time", "5"]);
+        assert_eq!(simulate.get_one::<usize>("runs"), None);
+        // An --output that names a file keeps having no default
+        assert_eq!(parse(&["jimbo", "init"]).get_one::<String>("output"), None);
+    }
 }
@@ -0,0 +1,141 @@
+//! Deck command implementation
+//!
+//! This module implements the `deck` command which analyzes a deck
+//! configuration: what it is made of and how often a dealt hand holds a
+//! flush or a straight.
+
+use super::completions::deck_type_candidates;
+use super::output::{self, OutputFormat, Report};
+use crate::config::DeckConfig;
+use crate::core::{DeckStats, DeckType};
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use clap_complete::ArgValueCandidates;
+use serde::Serialize;
+
+/// Arguments for the deck command
+#[derive(Debug, Args)]
+pub struct DeckArgs {
+    #[command(subcommand)]
+    command: DeckCommand,
+}
+
+/// Subcommands for analyzing decks
+#[derive(Debug, Subcommand)]
+enum DeckCommand {
+    /// Show a deck's suits, ranks, face cards, modifiers, and the chance a
+    /// dealt hand holds a flush or a straight
+    Stats(StatsArgs),
+}
+
+/// Arguments for `deck stats`
+#[derive(Debug, Args)]
+struct StatsArgs {
+    /// Path to deck configuration file (default: standard 52-card deck, or
+    /// the deck type's)
+    deck: Option<String>,
+
+    /// Official deck to analyze (e.g., "abandoned", "checkered")
+    #[arg(long, conflicts_with = "deck", add = ArgValueCandidates::new(deck_type_candidates))]
+    deck_type: Option<DeckType>,
+
+    /// Cards dealt for the flush and straight odds (default: 8, or the
+    /// deck type's hand size)
+    #[arg(long)]
+    hand_size: Option<usize>,
+
+    /// Output format: pretty (default), json, yaml, csv, compact
+    #[arg(long, default_value = "pretty")]
+    output: OutputFormat,
+}
+
+/// Runs the deck command
+pub fn run(args: DeckArgs) -> Result<()> {
+    match args.command {
+        DeckCommand::Stats(args) => stats(args),
+    }
+}
+
+/// Prints the statistics of the deck
+fn stats(args: StatsArgs) -> Result<()> {
+    let deck_config = match &args.deck {
+        Some(path) => DeckConfig::from_file(path)
+            .with_context(|| format!("Failed to load deck config from {}", path))?,
+        None => DeckConfig::from_deck_type(args.deck_type.unwrap_or_default(), 0),
+    };
+    let deck_type = args.deck_type.or(deck_config.deck_type).unwrap_or_default();
+    let hand_size = args.hand_size.unwrap_or(deck_type.modifiers().hand_size);
+
+    let stats = DeckStats::new(&deck_config.to_cards()?, hand_size);
+    output::print(&StatsReport(stats), args.output)
+}
+
+/// A deck's statistics, as printed
+#[derive(Debug, Serialize)]
+#[serde(transparent)]
+struct StatsReport(DeckStats);
+
+impl Report for StatsReport {
+    fn print_pretty(&self) {
+        let stats = &self.0;
+        outln!("🃏 Deck Stats ({} cards):", stats.cards);
+        outln!("  Suits:  {}", format_counts(&stats.suits));
+        outln!("  Ranks:  {}", format_counts(&stats.ranks));
+        outln!("  Face cards: {} ({:.1}%)", stats.face_cards, stats.face_ratio * 100.0);
+        for (label, counts) in [
+            ("Enhancements", &stats.enhancements),
+            ("Editions", &stats.editions),
+            ("Seals", &stats.seals),
+        ] {
+            if !counts.is_empty() {
+                outln!("  {}: {}", label, format_counts(counts));
+            }
+        }
+
+        outln!("\n🎲 Dealing {} cards:", stats.hand_size);
+        outln!("  Flush:    {:.2}%", stats.flush_probability * 100.0);
+        outln!("  Straight: {:.2}%", stats.straight_probability * 100.0);
+    }
+
+    fn print_compact(&self) {
+        let stats = &self.0;
+        outln!(
+            "{} cards, {:.1}% face, flush {:.2}%, straight {:.2}% ({} dealt)",
+            stats.cards,
+            stats.face_ratio * 100.0,
+            stats.flush_probability * 100.0,
+            stats.straight_probability * 100.0,
+            stats.hand_size
+        );
+    }
+}
+
+/// Formats counts as "Hearts: 13, Spades: 12"
+fn format_counts(counts: &[(String, usize)]) -> String {
+    let counts: Vec<String> =
+        counts.iter().map(|(name, count)| format!("{}: {}", name, count)).collect();
+    counts.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: DeckArgs,
+    }
+
+    #[test]
+    fn test_stats_args() {
+        let cli = Cli::parse_from(["deck", "stats", "--deck-type", "painted"]);
+        let DeckCommand::Stats(args) = cli.args.command;
+        assert_eq!(args.deck_type, Some(DeckType::Painted));
+        assert!(args.deck.is_none());
+
+        assert!(Cli::try_parse_from(["deck", "stats", "my.json", "--deck-type", "red"]).is_err());
+        assert_eq!(format_counts(&[("Hearts".to_string(), 13)]), "Hearts: 13");
+    }
+}
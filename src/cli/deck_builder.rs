@@ -0,0 +1,204 @@
+//! Interactive deck builder
+//!
+//! This module implements `config init deck --interactive`, which builds a
+//! deck configuration one step at a time: pick a base deck, remove and add
+//! cards, then give cards enhancements, editions, and seals. An invalid
+//! answer is reported and the question asked again.
+
+use super::solve::parse_hand;
+use crate::config::deck::CardDefinition;
+use crate::config::DeckConfig;
+use crate::core::card::{Card, Edition, Enhancement};
+use crate::core::DeckType;
+use anyhow::Result;
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+
+/// Answers the deck builder's questions
+pub trait Prompt {
+    /// Asks a question, returning `None` once input has ended
+    fn ask(&mut self, question: &str) -> Result<Option<String>>;
+}
+
+impl Prompt for DefaultEditor {
+    fn ask(&mut self, question: &str) -> Result<Option<String>> {
+        match self.readline(question) {
+            Ok(line) => Ok(Some(line)),
+            Err(ReadlineError::Interrupted | ReadlineError::Eof) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Walks through building a deck, returning it once every step is answered
+pub fn build_deck(prompt: &mut impl Prompt) -> Result<DeckConfig> {
+    outln!("🃏 Deck builder. Press Enter to skip a step, Ctrl-D to cancel.");
+
+    let names: Vec<String> =
+        DeckType::all().iter().map(|deck| format!("{:?}", deck).to_lowercase()).collect();
+    outln!("\nBase decks: standard, {}", names.join(", "));
+    let mut deck = ask_until_valid(prompt, "Base deck [standard]: ", base_deck)?;
+    outln!("   {} cards", deck.cards.len());
+
+    outln!("\nCards are written like AH, 10D, or KS, separated by spaces.");
+    deck = ask_until_valid(prompt, "Cards to remove: ", |answer| remove_cards(&deck, answer))?;
+    outln!("   {} cards", deck.cards.len());
+    deck = ask_until_valid(prompt, "Cards to add: ", |answer| add_cards(&deck, answer))?;
+    outln!("   {} cards", deck.cards.len());
+
+    outln!("\nModifiers follow the card after colons, e.g. AH:glass KS:foil:red_seal.");
+    deck = ask_until_valid(prompt, "Cards to modify: ", |answer| modify_cards(&deck, answer))?;
+    Ok(deck)
+}
+
+/// Asks a question until `step` accepts the answer
+fn ask_until_valid<T>(
+    prompt: &mut impl Prompt,
+    question: &str,
+    mut step: impl FnMut(&str) -> Result<T>,
+) -> Result<T> {
+    loop {
+        let Some(answer) = prompt.ask(question)? else {
+            anyhow::bail!("Deck builder cancelled");
+        };
+        match step(answer.trim()) {
+            Ok(value) => return Ok(value),
+            Err(e) => outln!("❌ {}", e),
+        }
+    }
+}
+
+/// Returns the deck an answer names: "standard" (the default) or an
+/// official deck
+fn base_deck(answer: &str) -> Result<DeckConfig> {
+    if answer.is_empty() || answer.eq_ignore_ascii_case("standard") {
+        return Ok(DeckConfig::standard());
+    }
+    // Only the Erratic deck uses the seed; a fresh one deals a fresh deck
+    Ok(DeckConfig::from_deck_type(answer.parse()?, rand::random()))
+}
+
+/// Removes the listed cards, preferring one with the same modifiers when
+/// the deck holds several
+fn remove_cards(deck: &DeckConfig, answer: &str) -> Result<DeckConfig> {
+    let mut deck = deck.clone();
+    let mut cards = deck.to_cards()?;
+    for card in parse_hand(answer)? {
+        let index = cards
+            .iter()
+            .rposition(|other| *other == card)
+            .or_else(|| cards.iter().rposition(|other| same_card(other, &card)))
+            .ok_or_else(|| anyhow::anyhow!("No {} in the deck to remove", card))?;
+        cards.remove(index);
+        deck.cards.remove(index);
+    }
+    deck.validate()?;
+    Ok(deck)
+}
+
+/// Adds the listed cards, with any modifiers they are written with
+fn add_cards(deck: &DeckConfig, answer: &str) -> Result<DeckConfig> {
+    let mut deck = deck.clone();
+    deck.cards.extend(parse_hand(answer)?.iter().map(CardDefinition::from_card));
+    Ok(deck)
+}
+
+/// Gives the listed cards their modifiers
+///
+/// Each entry goes to the first matching card without that kind of
+/// modifier yet, so "AH:glass AH:red_seal" makes one Glass Ace with a Red
+/// seal and "AH:glass AH:gold" changes two Aces.
+fn modify_cards(deck: &DeckConfig, answer: &str) -> Result<DeckConfig> {
+    let mut deck = deck.clone();
+    let mut cards = deck.to_cards()?;
+    for entry in answer.split_whitespace() {
+        let (base, _) = entry.split_once(':').ok_or_else(|| {
+            anyhow::anyhow!("Give {} a modifier after a colon, e.g. {}:glass", entry, entry)
+        })?;
+        anyhow::ensure!(
+            !base.eq_ignore_ascii_case("stone"),
+            "Name the card to modify, e.g. AS:stone"
+        );
+        let modified = parse_hand(entry)?.remove(0);
+        let fits = |card: &Card| {
+            same_card(card, &modified)
+                && (modified.enhancement == Enhancement::None
+                    || card.enhancement == Enhancement::None)
+                && (modified.edition == Edition::None || card.edition == Edition::None)
+                && (modified.seal.is_none() || card.seal.is_none())
+        };
+        let index = cards
+            .iter()
+            .position(fits)
+            .ok_or_else(|| anyhow::anyhow!("No {} in the deck left to modify", base))?;
+
+        let mut card = cards[index].clone();
+        if modified.enhancement != Enhancement::None {
+            card = card.with_enhancement(modified.enhancement);
+        }
+        if modified.edition != Edition::None {
+            card = card.with_edition(modified.edition);
+        }
+        if let Some(seal) = modified.seal {
+            card = card.with_seal(seal);
+        }
+        deck.cards[index] =
+            CardDefinition { id: deck.cards[index].id.clone(), ..CardDefinition::from_card(&card) };
+        cards[index] = card;
+    }
+    Ok(deck)
+}
+
+/// Whether two cards have the same rank and suit
+fn same_card(card: &Card, other: &Card) -> bool {
+    card.rank == other.rank && card.suit == other.suit
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::card::{Rank, Seal, Suit};
+    use std::collections::VecDeque;
+
+    /// Answers questions from a script
+    struct Script(VecDeque<&'static str>);
+
+    impl Prompt for Script {
+        fn ask(&mut self, _question: &str) -> Result<Option<String>> {
+            Ok(self.0.pop_front().map(str::to_string))
+        }
+    }
+
+    #[test]
+    fn test_build_deck() {
+        let mut script = Script(VecDeque::from([
+            "abandoned",
+            "2C 3C",
+            "AS AS:steel",
+            "AS:glass AS:red_seal AS:gold 5H:foil",
+        ]));
+        let deck = build_deck(&mut script).unwrap();
+        assert_eq!(deck.deck_type, Some(DeckType::Abandoned));
+
+        let cards = deck.to_cards().unwrap();
+        assert_eq!(cards.len(), 40);
+        let ace = Card::new(Rank::Ace, Suit::Spades);
+        let aces: Vec<&Card> = cards.iter().filter(|card| same_card(card, &ace)).collect();
+        assert_eq!(aces.len(), 3);
+        assert_eq!((aces[0].enhancement, aces[0].seal), (Enhancement::Glass, Some(Seal::Red)));
+        assert_eq!(aces[1].enhancement, Enhancement::Gold);
+        assert_eq!(aces[2].enhancement, Enhancement::Steel);
+        assert!(cards.iter().any(|card| card.edition == Edition::Foil));
+    }
+
+    #[test]
+    fn test_invalid_answers_are_asked_again() {
+        let mut script = Script(VecDeque::from(["", "KH KH", "KH", "", "KH", "QH:nope", ""]));
+        let deck = build_deck(&mut script).unwrap();
+        assert_eq!(deck.to_cards().unwrap().len(), 51);
+
+        // Running out of answers cancels
+        let mut script = Script(VecDeque::from(["plasma"]));
+        assert!(build_deck(&mut script).is_err());
+    }
+}
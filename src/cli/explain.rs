@@ -0,0 +1,238 @@
+//! Explain command implementation
+//!
+//! This module implements the `explain` command which prints how a play
+//! scores, card by card: the hand's base, each scoring card and retrigger,
+//! cards held in hand, and jokers, with the running chips and mult after
+//! every contribution.
+
+use super::completions::{boss_candidates, joker_candidates};
+use super::output::{OutputFormat, Report, Sink};
+use super::solve::{calculator_from_flags, format_cards, parse_hand};
+use crate::config::GameState;
+use crate::core::{
+    score_trace, BossBlind, Card, GameRunState, Hand, HandType, Score, ScoreCalculator,
+    ScoreEffect, ScoreResult, ScoreStage, TraceStep,
+};
+use anyhow::{Context, Result};
+use clap::Args;
+use clap_complete::ArgValueCandidates;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Arguments for the explain command
+#[derive(Debug, Args)]
+pub struct ExplainArgs {
+    /// The cards played (space-separated, e.g., "KH KS:foil 7D"), with
+    /// modifiers as for `solve --hand`
+    #[arg(long, required = true)]
+    hand: String,
+
+    /// Cards held in hand while playing (space-separated)
+    #[arg(long, default_value = "")]
+    held: String,
+
+    /// Path to deck configuration file (JSON), for jokers that count the
+    /// full deck
+    #[arg(long)]
+    deck: Option<String>,
+
+    /// Path to a game state file (JSON) to take the jokers, hand levels,
+    /// boss blind, and deck from; flags given as well take precedence
+    /// (hand levels are merged)
+    #[arg(long)]
+    game_state: Option<String>,
+
+    /// Comma-separated list of jokers (e.g., "Joker,GreedyJoker")
+    #[arg(long, value_delimiter = ',', add = ArgValueCandidates::new(joker_candidates))]
+    jokers: Vec<String>,
+
+    /// Comma-separated hand levels (e.g., "Flush=5,Pair=3")
+    #[arg(long, value_delimiter = ',')]
+    hand_levels: Vec<String>,
+
+    /// Boss blind in effect (e.g., "The Flint")
+    #[arg(long, add = ArgValueCandidates::new(boss_candidates))]
+    boss: Option<BossBlind>,
+
+    /// Output format: pretty (default), json, yaml, csv (one row per
+    /// contribution), compact
+    #[arg(long, default_value = "pretty")]
+    output: OutputFormat,
+}
+
+/// Runs the explain command
+pub fn run(args: ExplainArgs) -> Result<()> {
+    run_with(&args, &mut Sink::Print(args.output))
+}
+
+/// Runs the explain command, sending its report to a sink
+pub fn run_with(args: &ExplainArgs, sink: &mut Sink) -> Result<()> {
+    let cards = parse_hand(&args.hand)?;
+    if cards.is_empty() || cards.len() > 5 {
+        anyhow::bail!("Play between 1 and 5 cards, not {}", cards.len());
+    }
+    let held = parse_hand(&args.held)?;
+
+    let run_state = match &args.game_state {
+        Some(path) => Some(
+            GameState::from_file(path)
+                .with_context(|| format!("Failed to load game state from {}", path))?
+                .to_run_state()?,
+        ),
+        None => None,
+    };
+    let calculator = build_calculator(args, run_state.as_ref())?;
+
+    let result = calculator.calculate(&Hand::new(cards.clone()), &held);
+    let trace = score_trace(&result);
+    sink.emit(&ExplainReport {
+        hand_type: result.hand_type,
+        cards: cards.iter().map(Card::to_string).collect(),
+        held: held.iter().map(Card::to_string).collect(),
+        chips: result.chips,
+        mult: result.mult,
+        score: result.score,
+        money: result.money_delta,
+        trace,
+        played: cards,
+        held_cards: held,
+        result,
+    })
+}
+
+/// How a play scores, as printed
+#[derive(Serialize)]
+struct ExplainReport {
+    hand_type: HandType,
+    cards: Vec<String>,
+    held: Vec<String>,
+    chips: u32,
+    mult: f64,
+    score: Score,
+    money: f64,
+    /// Every contribution, with the running chips and mult after it
+    trace: Vec<TraceStep>,
+    #[serde(skip)]
+    played: Vec<Card>,
+    #[serde(skip)]
+    held_cards: Vec<Card>,
+    #[serde(skip)]
+    result: ScoreResult,
+}
+
+impl Report for ExplainReport {
+    fn print_pretty(&self) {
+        outln!("🃏 {:?}: {}", self.hand_type, format_cards(&self.played));
+        if !self.held_cards.is_empty() {
+            outln!("  Held: {}", format_cards(&self.held_cards));
+        }
+        display_trace(&self.result, &self.trace);
+    }
+
+    fn print_compact(&self) {
+        outln!(
+            "{:?}: {} | {} × {} = {}",
+            self.hand_type,
+            format_cards(&self.played),
+            self.chips,
+            self.mult,
+            self.score
+        );
+    }
+
+    /// One row per contribution
+    fn records(&self) -> Result<Vec<Value>> {
+        Ok(self
+            .trace
+            .iter()
+            .map(|step| {
+                json!({
+                    "stage": stage_heading(&step.event.stage),
+                    "source": step.event.source,
+                    "effect": format_effect(&step.event.effect),
+                    "chips": step.chips,
+                    "mult": step.mult,
+                })
+            })
+            .collect())
+    }
+}
+
+/// Creates the score calculator for the jokers, hand levels, boss blind,
+/// and deck, with `--boss` replacing the game state's boss blind
+fn build_calculator(args: &ExplainArgs, run: Option<&GameRunState>) -> Result<ScoreCalculator> {
+    let calculator =
+        calculator_from_flags(run, &args.jokers, &args.hand_levels, args.deck.as_deref())?;
+    Ok(match args.boss {
+        Some(boss) => calculator.with_boss_blind(Some(boss)),
+        None => calculator,
+    })
+}
+
+/// Names a stage of the trace, e.g. "K♥ (retrigger 1)"
+fn stage_heading(stage: &ScoreStage) -> String {
+    match stage {
+        ScoreStage::Hand => "Hand base".to_string(),
+        ScoreStage::Played { card, trigger: 0, .. } => format!("{} scored", card),
+        ScoreStage::Played { card, trigger, .. } => format!("{} (retrigger {})", card, trigger),
+        ScoreStage::Held => "Held in hand".to_string(),
+        ScoreStage::Jokers => "Jokers".to_string(),
+    }
+}
+
+/// Prints each stage's contributions with the running chips and mult
+pub(crate) fn display_trace(result: &ScoreResult, trace: &[TraceStep]) {
+    let mut stage = None;
+    for step in trace {
+        if stage != Some(&step.event.stage) {
+            stage = Some(&step.event.stage);
+            outln!("  {}:", stage_heading(&step.event.stage));
+        }
+        outln!(
+            "    {:<12} {:<32} {:>6} × {}",
+            format_effect(&step.event.effect),
+            step.event.source,
+            step.chips,
+            step.mult
+        );
+    }
+
+    let replayed = trace.last().map_or((0, 0.0), |step| (step.chips as u32, step.mult));
+    if replayed != (result.chips, result.mult) {
+        outln!("  Balanced (Plasma Deck): {} × {}", result.chips, result.mult);
+    }
+    outln!("  Score: {} chips × {} mult = {}", result.chips, result.mult, result.score);
+    if result.money_delta > 0.0 {
+        outln!("  Money: ${:.2}", result.money_delta);
+    }
+}
+
+/// Formats an effect as "+11 chips", "+4 mult", or "x1.5 mult"
+fn format_effect(effect: &ScoreEffect) -> String {
+    match effect {
+        ScoreEffect::Chips(chips) => format!("{:+} chips", chips),
+        ScoreEffect::Mult(mult) => format!("{:+} mult", mult),
+        ScoreEffect::XMult(multiplier) => format!("x{} mult", multiplier),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stage_headings() {
+        let calculator = calculator_from_flags(None, &["Joker".to_string()], &[], None).unwrap();
+        let hand = parse_hand("KH:red_seal KS").unwrap();
+        let result = calculator.calculate(&Hand::new(hand), &[]);
+
+        let headings: Vec<String> = score_trace(&result)
+            .iter()
+            .map(|step| stage_heading(&step.event.stage))
+            .collect();
+        assert_eq!(headings.first().map(String::as_str), Some("Hand base"));
+        assert!(headings.contains(&"K♥ (retrigger 1)".to_string()));
+        assert!(headings.contains(&"K♠ scored".to_string()));
+        assert_eq!(headings.last().map(String::as_str), Some("Jokers"));
+    }
+}
@@ -0,0 +1,99 @@
+//! Import command implementation
+//!
+//! This module implements the `import` command which reads a Balatro save
+//! file and writes the run it holds as a game state and a deck
+//! configuration, ready for `solve`, `simulate`, and `run`.
+
+use crate::config::BalatroSave;
+use anyhow::{Context, Result};
+use clap::Args;
+use std::path::{Path, PathBuf};
+
+/// Arguments for the import command
+#[derive(Debug, Args)]
+pub struct ImportArgs {
+    /// Path to the save file (save.jkr in the game's profile folder)
+    save: String,
+
+    /// Game state file to write; its extension picks the format: .json,
+    /// .yaml, or .toml
+    #[arg(short, long, default_value = "game_state.json")]
+    output: String,
+
+    /// Deck configuration file to write (default: next to the game state,
+    /// e.g. game_state_deck.json)
+    #[arg(long)]
+    deck: Option<String>,
+}
+
+/// Runs the import command
+pub fn run(args: ImportArgs) -> Result<()> {
+    let save = BalatroSave::from_file(&args.save)?;
+    let deck_path = args.deck.map_or_else(|| deck_path_for(&args.output), PathBuf::from);
+
+    save.deck
+        .to_file(&deck_path)
+        .with_context(|| format!("Failed to write deck config to {}", deck_path.display()))?;
+    let mut game_state = save.game_state;
+    game_state.deck_path = Some(deck_path.to_string_lossy().into_owned());
+    game_state
+        .to_file(&args.output)
+        .with_context(|| format!("Failed to write game state to {}", args.output))?;
+
+    let deck_name = save.deck.deck_type.map_or("Unknown deck", |deck_type| deck_type.name());
+    outln!(
+        "📥 Imported ante {} on {}, {:?} Stake: ${}, {} jokers, {} cards",
+        game_state.ante,
+        deck_name,
+        game_state.stake,
+        game_state.money,
+        game_state.jokers.len(),
+        save.deck.cards.len()
+    );
+    if let Some(blind) = &game_state.blind {
+        let name = blind.name.clone().unwrap_or_else(|| format!("{:?} Blind", blind.blind_type));
+        outln!("   Blind: {} ({} chips)", name, blind.score_required);
+    }
+    if let Some(seed) = &save.seed {
+        outln!("   Seed: {}", seed);
+    }
+    for skipped in &save.skipped {
+        outln!("   ⚠️  Skipped {}", lowercase_first(skipped));
+    }
+    outln!("✅ Wrote game state to {} and deck to {}", args.output, deck_path.display());
+    Ok(())
+}
+
+/// Returns the deck path that goes with a game state path:
+/// "runs/ante4.yaml" gives "runs/ante4_deck.yaml"
+fn deck_path_for(state_path: &str) -> PathBuf {
+    let path = Path::new(state_path);
+    let stem = path.file_stem().map_or("game_state".into(), |stem| stem.to_string_lossy());
+    let file_name = match path.extension() {
+        Some(extension) => format!("{}_deck.{}", stem, extension.to_string_lossy()),
+        None => format!("{}_deck", stem),
+    };
+    path.with_file_name(file_name)
+}
+
+/// Lowercases the first letter, e.g. to put an error message mid-sentence
+fn lowercase_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deck_path_for() {
+        assert_eq!(deck_path_for("game_state.json"), Path::new("game_state_deck.json"));
+        assert_eq!(deck_path_for("runs/ante4.yaml"), Path::new("runs/ante4_deck.yaml"));
+        assert_eq!(deck_path_for("run"), Path::new("run_deck"));
+        assert_eq!(lowercase_first("Unknown joker: Caino"), "unknown joker: Caino");
+    }
+}
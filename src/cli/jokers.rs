@@ -0,0 +1,116 @@
+//! Jokers command implementation
+//!
+//! This module implements the `jokers` command, a reference for every
+//! joker jimbo knows: its effect, rarity, cost, and whether the scoring
+//! engine models all of it yet.
+
+use super::completions::joker_candidates;
+use crate::core::joker::JokerKind;
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use clap_complete::ArgValueCandidates;
+
+/// Arguments for the jokers command
+#[derive(Debug, Args)]
+pub struct JokersArgs {
+    #[command(subcommand)]
+    command: JokersCommand,
+}
+
+/// Subcommands for browsing jokers
+#[derive(Debug, Subcommand)]
+enum JokersCommand {
+    /// List every joker
+    List,
+
+    /// Show one joker in full
+    Show {
+        /// Joker name (e.g., "Greedy Joker", "greedy_joker")
+        #[arg(add = ArgValueCandidates::new(joker_candidates))]
+        name: String,
+    },
+
+    /// Find jokers whose name or effect mentions some text
+    Search {
+        /// Text to look for, ignoring case (e.g., "face", "x mult")
+        text: String,
+    },
+}
+
+/// Runs the jokers command
+pub fn run(args: JokersArgs) -> Result<()> {
+    match args.command {
+        JokersCommand::List => display_list(&JokerKind::all()),
+        JokersCommand::Show { name } => display_joker(&name.parse()?),
+        JokersCommand::Search { text } => {
+            let matches = search(&text);
+            if matches.is_empty() {
+                outln!("No jokers match: {}", text);
+            } else {
+                display_list(&matches);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the jokers whose name or effect contains the text, ignoring case
+fn search(text: &str) -> Vec<JokerKind> {
+    let text = text.trim().to_lowercase();
+    JokerKind::all()
+        .into_iter()
+        .filter(|kind| {
+            kind.name().to_lowercase().contains(&text)
+                || kind.description().to_lowercase().contains(&text)
+        })
+        .collect()
+}
+
+/// Prints one line per joker, with a footnote if any are not fully modeled
+fn display_list(kinds: &[JokerKind]) {
+    outln!("🃏 Jokers ({}):", kinds.len());
+    for kind in kinds {
+        let marker = if kind.is_fully_modeled() { " " } else { "*" };
+        outln!(
+            " {} {:<18} {:<10} ${:<3} {}",
+            marker,
+            kind.name(),
+            format!("{:?}", kind.rarity()),
+            kind.cost(),
+            kind.description()
+        );
+    }
+    if kinds.iter().any(|kind| !kind.is_fully_modeled()) {
+        outln!("\n * Not fully modeled yet; see `jimbo jokers show <name>`");
+    }
+}
+
+/// Prints everything known about one joker
+fn display_joker(kind: &JokerKind) {
+    outln!("🃏 {}", kind.name());
+    outln!("  Effect: {}", kind.description());
+    outln!("  Rarity: {:?}", kind.rarity());
+    outln!("  Cost: ${} (sells for ${})", kind.cost(), kind.sell_value());
+    match kind.modeling_gap() {
+        None => outln!("  ✅ Fully modeled"),
+        Some(gap) => outln!("  ⚠️  Not fully modeled: {}", gap),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_names_and_effects() {
+        assert_eq!(search("Greedy"), vec![JokerKind::GreedyJoker]);
+
+        let face = search("FACE CARD");
+        assert!(face.contains(&JokerKind::Pareidolia));
+        assert!(face.contains(&JokerKind::ScaryFace));
+        assert!(!face.contains(&JokerKind::Joker));
+
+        assert_eq!(search("").len(), JokerKind::all().len());
+        assert!(search("jimbo the great").is_empty());
+    }
+}
@@ -2,6 +2,25 @@
 //!
 //! This module contains the implementation for all CLI commands.
 
+// First, so the printing macros it defines are in scope in every command
+#[macro_use]
+pub mod style;
+
+pub mod compare;
+pub mod completions;
 pub mod config;
+pub mod deck;
+pub mod deck_builder;
+pub mod explain;
+pub mod import;
+pub mod jokers;
+pub mod optimize;
+pub mod output;
+pub mod plan;
+pub mod repl;
+pub mod run;
+pub mod seed;
+pub mod serve;
 pub mod solve;
 pub mod simulate;
+pub mod stats;
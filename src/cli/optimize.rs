@@ -0,0 +1,158 @@
+//! Optimize command implementation
+//!
+//! This module implements the `optimize` command which searches for the
+//! joker combinations that score best with a deck and prints the top ones
+//! by simulated mean score and clear rate.
+
+use super::completions::deck_type_candidates;
+use crate::config::DeckConfig;
+use crate::core::joker::JokerRarity;
+use crate::core::optimizer::{DEFAULT_OPTIMIZER_RUNS, DEFAULT_RESTARTS};
+use crate::core::score::format_score;
+use crate::core::{
+    BlindType, BuildOptimizer, DeckType, GameRunState, HandLevels, OptimizeObjective,
+    OptimizedBuild, Stake,
+};
+use anyhow::{Context, Result};
+use clap::Args;
+use clap_complete::ArgValueCandidates;
+
+/// Arguments for the optimize command
+#[derive(Debug, Args)]
+pub struct OptimizeArgs {
+    /// Path to deck configuration file (default: standard 52-card deck, or
+    /// the deck type's)
+    #[arg(long)]
+    deck: Option<String>,
+
+    /// Official deck to optimize for (e.g., "plasma", "abandoned")
+    #[arg(long, add = ArgValueCandidates::new(deck_type_candidates))]
+    deck_type: Option<DeckType>,
+
+    /// Ante whose Boss blind requirement a round must reach to count as
+    /// cleared (default: 1)
+    #[arg(long, default_value = "1")]
+    ante: u32,
+
+    /// Number of jokers in a build (default: the deck's joker slots)
+    #[arg(long)]
+    slots: Option<usize>,
+
+    /// Comma-separated rarities to pick jokers from (e.g.,
+    /// "common,uncommon"; default: every rarity but legendary)
+    #[arg(long, value_delimiter = ',')]
+    pool: Vec<JokerRarity>,
+
+    /// What to maximize: mean (default) or clear-rate
+    #[arg(long, default_value = "mean")]
+    objective: OptimizeObjective,
+
+    /// Comma-separated hand levels (e.g., "Flush=5,Pair=3")
+    #[arg(long, value_delimiter = ',')]
+    hand_levels: Vec<String>,
+
+    /// Hands per simulated round (default: the deck's)
+    #[arg(long)]
+    hands: Option<u32>,
+
+    /// Discards per simulated round (default: the deck's)
+    #[arg(long)]
+    discards: Option<u32>,
+
+    /// Rounds simulated per candidate build (default: 100)
+    #[arg(long, default_value_t = DEFAULT_OPTIMIZER_RUNS)]
+    runs: usize,
+
+    /// Random builds hill-climbed besides the greedy one (default: 2)
+    #[arg(long, default_value_t = DEFAULT_RESTARTS)]
+    restarts: usize,
+
+    /// Number of builds to print (default: 10)
+    #[arg(long, default_value = "10")]
+    top: usize,
+
+    /// Optional seed for reproducible results
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+/// Runs the optimize command
+pub fn run(args: OptimizeArgs) -> Result<()> {
+    let deck_type = args.deck_type.unwrap_or_default();
+    let seed = args.seed.unwrap_or_default();
+    let mut run = GameRunState::new(deck_type, Stake::default())
+        .with_hand_levels(HandLevels::from_entries(&args.hand_levels)?);
+    if let Some(deck_path) = &args.deck {
+        let deck_config = DeckConfig::from_file(deck_path)
+            .with_context(|| format!("Failed to load deck config from {}", deck_path))?;
+        run = run.with_deck(deck_config.to_cards()?);
+    } else if args.deck_type.is_some() {
+        run = run.with_deck(deck_type.create_cards(seed));
+    }
+    run.ante = args.ante;
+    if let Some(hands) = args.hands {
+        run.hands_remaining = hands;
+    }
+    if let Some(discards) = args.discards {
+        run.discards_remaining = discards;
+    }
+    let target = run.blind_requirement(BlindType::Boss);
+
+    let mut optimizer = BuildOptimizer::new(run)
+        .with_objective(args.objective)
+        .with_runs(args.runs, seed)
+        .with_restarts(args.restarts);
+    if !args.pool.is_empty() {
+        optimizer = optimizer.with_rarities(&args.pool);
+    }
+    if let Some(slots) = args.slots {
+        optimizer = optimizer.with_slots(slots);
+    }
+
+    outln!("Searching joker builds ({} rounds per build)...", args.runs);
+    let builds = optimizer.optimize();
+    if builds.is_empty() {
+        anyhow::bail!("No jokers in the pool to build from");
+    }
+    display_builds(&builds[..builds.len().min(args.top)], args.ante, target);
+    Ok(())
+}
+
+/// Prints the builds, best first, with their mean score and clear rate
+fn display_builds(builds: &[OptimizedBuild], ante: u32, target: u64) {
+    outln!("\n🏆 Top {} Builds:", builds.len());
+    outln!("  (Clear: rounds reaching ante {}'s Boss blind requirement, {})", ante, target);
+    outln!("  {:>3} {:>12} {:>8}  Jokers", "#", "Mean", "Clear");
+    for (rank, build) in builds.iter().enumerate() {
+        let names: Vec<&str> = build.jokers.iter().map(|joker| joker.kind.name()).collect();
+        outln!(
+            "  {:>3} {:>12} {:>7.1}%  {}",
+            rank + 1,
+            format_score(build.mean_score),
+            build.clear_rate * 100.0,
+            names.join(", ")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: OptimizeArgs,
+    }
+
+    #[test]
+    fn test_optimize_args() {
+        let cli = Cli::parse_from(["optimize", "--pool", "common,Uncommon", "--objective", "clear"]);
+        assert_eq!(cli.args.pool, vec![JokerRarity::Common, JokerRarity::Uncommon]);
+        assert_eq!(cli.args.objective, OptimizeObjective::ClearRate);
+        assert_eq!(cli.args.runs, DEFAULT_OPTIMIZER_RUNS);
+
+        assert!(Cli::try_parse_from(["optimize", "--pool", "common,mythic"]).is_err());
+    }
+}
@@ -0,0 +1,227 @@
+//! Output formats shared by the commands
+//!
+//! Commands describe their results as serializable reports, so every
+//! structured format prints the same fields: json and yaml serialize the
+//! report as it is, and csv prints its records with nested fields
+//! flattened into columns (e.g., "target.probability"). Pretty and compact
+//! are each command's own layouts for reading in a terminal.
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+use std::io::Write;
+
+/// Output format selected with --output
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Pretty,
+    Json,
+    Yaml,
+    Csv,
+    Compact,
+}
+
+impl OutputFormat {
+    /// Returns whether the format is meant for other programs, in which
+    /// case nothing else may be printed to stdout
+    pub fn is_structured(&self) -> bool {
+        matches!(self, OutputFormat::Json | OutputFormat::Yaml | OutputFormat::Csv)
+    }
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "pretty" => Ok(OutputFormat::Pretty),
+            "json" => Ok(OutputFormat::Json),
+            "yaml" | "yml" => Ok(OutputFormat::Yaml),
+            "csv" => Ok(OutputFormat::Csv),
+            "compact" => Ok(OutputFormat::Compact),
+            _ => anyhow::bail!(
+                "Invalid output format: {}. Use 'pretty', 'json', 'yaml', 'csv', or 'compact'",
+                s
+            ),
+        }
+    }
+}
+
+/// A command's result, printable in every output format
+pub trait Report: Serialize {
+    /// Prints the report for reading in a terminal
+    fn print_pretty(&self);
+
+    /// Prints the report in as few lines as it takes (default: pretty)
+    fn print_compact(&self) {
+        self.print_pretty();
+    }
+
+    /// Returns the rows printed for csv (default: the whole report as one
+    /// row)
+    fn records(&self) -> Result<Vec<Value>> {
+        Ok(vec![serde_json::to_value(self)?])
+    }
+}
+
+/// Prints a report in the given format
+pub fn print<R: Report>(report: &R, format: OutputFormat) -> Result<()> {
+    match format {
+        OutputFormat::Pretty => report.print_pretty(),
+        OutputFormat::Compact => report.print_compact(),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(report)?),
+        OutputFormat::Yaml => print!("{}", serde_yaml::to_string(report)?),
+        OutputFormat::Csv => {
+            let mut writer = RecordWriter::new(format);
+            for record in report.records()? {
+                writer.write(&record)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Where a command's report goes: printed in a format, or kept as JSON for
+/// a caller such as `serve`
+pub enum Sink {
+    Print(OutputFormat),
+    Capture(Option<Value>),
+}
+
+impl Sink {
+    /// Prints or keeps the report
+    pub fn emit<R: Report>(&mut self, report: &R) -> Result<()> {
+        match self {
+            Sink::Print(format) => print(report, *format),
+            Sink::Capture(value) => {
+                *value = Some(serde_json::to_value(report)?);
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the captured report, if one was emitted
+    pub fn into_value(self) -> Option<Value> {
+        match self {
+            Sink::Print(_) => None,
+            Sink::Capture(value) => value,
+        }
+    }
+}
+
+/// Prints records one at a time as they are produced, e.g. while reading
+/// a batch from stdin
+///
+/// Json prints one object per line, yaml one item of a sequence, and csv
+/// one row, under a header taken from the first record.
+pub struct RecordWriter {
+    format: OutputFormat,
+    header: Option<Vec<String>>,
+}
+
+impl RecordWriter {
+    /// Creates a writer for a structured format
+    pub fn new(format: OutputFormat) -> Self {
+        Self { format, header: None }
+    }
+
+    /// Prints one record
+    pub fn write<T: Serialize>(&mut self, record: &T) -> Result<()> {
+        let mut stdout = std::io::stdout().lock();
+        match self.format {
+            OutputFormat::Json => writeln!(stdout, "{}", serde_json::to_string(record)?)?,
+            // A one-item sequence, so the records together form one sequence
+            OutputFormat::Yaml => write!(stdout, "{}", serde_yaml::to_string(&[record])?)?,
+            OutputFormat::Csv => {
+                let fields = flatten(&serde_json::to_value(record)?);
+                let mut csv = csv::Writer::from_writer(stdout);
+                if self.header.is_none() {
+                    let header: Vec<String> = fields.iter().map(|(k, _)| k.clone()).collect();
+                    csv.write_record(&header)?;
+                    self.header = Some(header);
+                }
+                let header = self.header.as_ref().expect("header is written first");
+                let row = header.iter().map(|column| {
+                    fields
+                        .iter()
+                        .find(|(key, _)| key == column)
+                        .map_or("", |(_, value)| value.as_str())
+                });
+                csv.write_record(row)?;
+                csv.flush()?;
+            }
+            OutputFormat::Pretty | OutputFormat::Compact => {
+                anyhow::bail!("Records are only written as json, yaml, or csv")
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Flattens a record into (column, cell) pairs
+///
+/// Nested objects become dotted columns, lists of plain values are joined
+/// with spaces, and lists of objects are kept as JSON. Null is empty.
+fn flatten(record: &Value) -> Vec<(String, String)> {
+    fn visit(prefix: &str, value: &Value, fields: &mut Vec<(String, String)>) {
+        let is_plain = |item: &Value| !item.is_object() && !item.is_array();
+        let cell = match value {
+            Value::Object(map) => {
+                for (key, value) in map {
+                    let column = match prefix {
+                        "" => key.clone(),
+                        _ => format!("{}.{}", prefix, key),
+                    };
+                    visit(&column, value, fields);
+                }
+                return;
+            }
+            Value::Null => String::new(),
+            Value::String(text) => text.clone(),
+            Value::Array(items) if items.iter().all(is_plain) => {
+                let items: Vec<String> = items
+                    .iter()
+                    .map(|item| item.as_str().map_or_else(|| item.to_string(), str::to_string))
+                    .collect();
+                items.join(" ")
+            }
+            other => other.to_string(),
+        };
+        let column = if prefix.is_empty() { "value" } else { prefix };
+        fields.push((column.to_string(), cell));
+    }
+
+    let mut fields = Vec::new();
+    visit("", record, &mut fields);
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_format_parsing() {
+        assert_eq!("YAML".parse::<OutputFormat>().unwrap(), OutputFormat::Yaml);
+        assert_eq!("compact".parse::<OutputFormat>().unwrap(), OutputFormat::Compact);
+        assert!("csv".parse::<OutputFormat>().unwrap().is_structured());
+        assert!(!OutputFormat::Pretty.is_structured());
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn test_flatten_nested_records() {
+        let record = serde_json::json!({
+            "score": 120,
+            "cards": ["A♥", "K♥"],
+            "target": { "probability": 0.5, "lower": null },
+            "antes": [{ "ante": 1 }],
+        });
+        let fields = flatten(&record);
+        let columns: Vec<&str> = fields.iter().map(|(column, _)| column.as_str()).collect();
+        assert_eq!(columns, ["score", "cards", "target.probability", "target.lower", "antes"]);
+        let cells: Vec<&str> = fields.iter().map(|(_, cell)| cell.as_str()).collect();
+        assert_eq!(cells, ["120", "A♥ K♥", "0.5", "", r#"[{"ante":1}]"#]);
+    }
+}
@@ -0,0 +1,380 @@
+//! Plan command implementation
+//!
+//! This module implements the `plan` command which looks ahead from the
+//! start of an ante: the expected score against its Small, Big, and Boss
+//! blinds, how to play the boss, and whether skipping the Small or Big
+//! blind for its tag is better than playing it.
+
+use super::completions::{boss_candidates, deck_type_candidates, joker_candidates, tag_candidates};
+use super::output::{self, OutputFormat, Report};
+use super::solve::{format_cards, parse_hand, parse_jokers};
+use crate::config::GameState;
+use crate::core::ante_planner::DEFAULT_PLAN_RUNS;
+use crate::core::{
+    AntePlan, AntePlanner, BlindOutlook, BlindType, BossBlind, Card, DeckType, GameRunState,
+    RoundAction, SkipOutlook, SkipVerdict, Stake, Tag,
+};
+use anyhow::{Context, Result};
+use clap::Args;
+use clap_complete::ArgValueCandidates;
+use serde::Serialize;
+
+/// Arguments for the plan command
+#[derive(Debug, Args)]
+pub struct PlanArgs {
+    /// Path to a game state file (JSON) at the start of the ante: its deck,
+    /// jokers, hand levels, money, ante, stake, and boss blind (default: a
+    /// new run)
+    #[arg(long)]
+    game_state: Option<String>,
+
+    /// Official deck for a new run (e.g., "plasma"), when no game state is
+    /// given
+    #[arg(
+        long,
+        conflicts_with = "game_state",
+        add = ArgValueCandidates::new(deck_type_candidates)
+    )]
+    deck_type: Option<DeckType>,
+
+    /// Stake to play at (e.g., "gold"), overriding the game state's
+    /// (default: white)
+    #[arg(long)]
+    stake: Option<Stake>,
+
+    /// Ante to plan, overriding the game state's (default: 1)
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    ante: Option<u32>,
+
+    /// The ante's boss blind (e.g., "The Club"), overriding the game
+    /// state's (default: a plain boss)
+    #[arg(long, add = ArgValueCandidates::new(boss_candidates))]
+    boss: Option<BossBlind>,
+
+    /// Comma-separated list of jokers, replacing the game state's
+    #[arg(long, value_delimiter = ',', add = ArgValueCandidates::new(joker_candidates))]
+    jokers: Vec<String>,
+
+    /// Comma-separated hand levels (e.g., "Flush=5,Pair=3"), set on top of
+    /// the game state's
+    #[arg(long, value_delimiter = ',')]
+    hand_levels: Vec<String>,
+
+    /// Tag offered for skipping the Small blind (e.g., "economy")
+    #[arg(long, add = ArgValueCandidates::new(tag_candidates))]
+    small_tag: Option<Tag>,
+
+    /// Tag offered for skipping the Big blind (e.g., "investment")
+    #[arg(long, add = ArgValueCandidates::new(tag_candidates))]
+    big_tag: Option<Tag>,
+
+    /// Cards dealt against the boss (e.g., "AH KH QH 7C 4D 3C 2H 9S"), to
+    /// recommend the next play or discard
+    #[arg(long)]
+    hand: Option<String>,
+
+    /// Rounds simulated per blind, and runs played out each way per skip
+    /// (default: 500)
+    #[arg(long, default_value = DEFAULT_PLAN_RUNS.to_string())]
+    runs: usize,
+
+    /// Optional seed for reproducible plans
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Output format: pretty (default), json, yaml, csv, compact
+    #[arg(long, default_value = "pretty")]
+    output: OutputFormat,
+}
+
+/// Runs the plan command
+pub fn run(args: PlanArgs) -> Result<()> {
+    let (run, boss_name) = start_ante(&args)?;
+    let mut planner = AntePlanner::new(run)
+        .with_runs(args.runs, args.seed.unwrap_or_else(rand::random))
+        .with_tags(args.small_tag, args.big_tag);
+    let hand = args.hand.as_deref().map(parse_hand).transpose()?;
+    if let Some(hand) = &hand {
+        planner = planner.with_boss_hand(hand.clone());
+    }
+
+    if !args.output.is_structured() {
+        outln!("Planning ante with {} runs per blind and skip choice...", args.runs);
+    }
+    let report = PlanReport { plan: planner.plan(), boss_name, hand };
+    output::print(&report, args.output)
+}
+
+/// Builds the run at the start of the ante from the game state or a new
+/// run, with flags taking precedence, and names its boss if it has one
+///
+/// A game state's boss whose effect isn't modeled (e.g., The Hook) is
+/// named, but planned as a plain boss.
+fn start_ante(args: &PlanArgs) -> Result<(GameRunState, Option<String>)> {
+    let (mut run, mut boss_name) = match &args.game_state {
+        Some(path) => {
+            let mut state = GameState::from_file(path)
+                .with_context(|| format!("Failed to load game state from {}", path))?;
+            if let Some(stake) = args.stake {
+                state.stake = stake;
+            }
+            let boss_name = state
+                .blind
+                .as_ref()
+                .filter(|blind| blind.blind_type == BlindType::Boss)
+                .and_then(|blind| blind.name.clone());
+            (state.to_run_state()?, boss_name)
+        }
+        None => {
+            let run = GameRunState::new(
+                args.deck_type.unwrap_or_default(),
+                args.stake.unwrap_or_default(),
+            );
+            (run, None)
+        }
+    };
+    if let Some(ante) = args.ante {
+        run.ante = ante;
+    }
+    if let Some(boss) = args.boss {
+        run.boss_blind = Some(boss);
+        boss_name = Some(boss.name().to_string());
+    }
+    if !args.jokers.is_empty() {
+        run = run.with_jokers(parse_jokers(&args.jokers)?);
+    }
+    run.hand_levels.set_entries(&args.hand_levels)?;
+    Ok((run, boss_name))
+}
+
+/// An ante's plan
+#[derive(Serialize)]
+struct PlanReport {
+    #[serde(flatten)]
+    plan: AntePlan,
+    /// The boss's name, including one whose effect isn't modeled
+    #[serde(skip)]
+    boss_name: Option<String>,
+    /// The hand dealt against the boss, if given
+    #[serde(skip)]
+    hand: Option<Vec<Card>>,
+}
+
+impl PlanReport {
+    /// Names a blind, e.g. "Small Blind" or "The Club"
+    fn blind_name(&self, blind_type: BlindType) -> String {
+        match (blind_type, &self.boss_name) {
+            (BlindType::Boss, Some(name)) => name.clone(),
+            _ => format!("{:?} Blind", blind_type),
+        }
+    }
+
+    /// Describes a skip's verdict, e.g. "Skip" or "Play it"
+    fn verdict(skip: &SkipOutlook) -> &'static str {
+        match skip.verdict {
+            SkipVerdict::Skip => "Skip",
+            SkipVerdict::Play => "Play it",
+            SkipVerdict::Either => "Either (no significant difference)",
+        }
+    }
+
+    fn display_blind(&self, blind: &BlindOutlook) {
+        outln!(
+            "   {:<14} needs {:>9}   mean {:>9.0} (median {:.0})   clears {:>5.1}%   ${}",
+            self.blind_name(blind.blind_type),
+            blind.score_required,
+            blind.mean_score,
+            blind.median_score.value(),
+            blind.clear.probability * 100.0,
+            blind.reward
+        );
+    }
+
+    fn display_boss(&self) {
+        let plan = &self.plan;
+        outln!("\n👹 {}:", self.blind_name(BlindType::Boss));
+        if plan.boss.boss.is_none() {
+            outln!("   Planned as a plain boss (its effect isn't known or modeled)");
+        }
+        match plan.boss.aim_for {
+            Some((hand_type, share)) => outln!(
+                "   Hold toward {:?} ({:.0}% of the hands in rounds that beat it)",
+                hand_type,
+                share * 100.0
+            ),
+            None => outln!("   No simulated round beat it"),
+        }
+        if let Some(suit) = plan.boss.boss.and_then(|boss| boss.debuffed_suit()) {
+            outln!(
+                "   Discard {:?} first: {} cards in the deck are debuffed",
+                suit,
+                plan.boss.debuffed_cards
+            );
+        }
+        if let (Some(hand), Some(next)) = (&self.hand, &plan.boss.next_action) {
+            let (verb, cards) = match &next.best.action {
+                RoundAction::Play(cards) => ("Play", cards),
+                RoundAction::Discard(cards) => ("Discard", cards),
+            };
+            outln!(
+                "   👉 With {}: {} {} ({:.0}% to beat it)",
+                format_cards(hand),
+                verb,
+                format_cards(cards),
+                next.best.success_probability * 100.0
+            );
+        }
+    }
+
+    fn display_skip(&self, skip: &SkipOutlook) {
+        let tag = skip.tag.map_or("no tag".to_string(), |tag| {
+            let modeled = if tag.is_modeled() { "" } else { ", not modeled" };
+            format!("{}{}", tag.name(), modeled)
+        });
+        outln!(
+            "\n⏭️  Skip the {} ({})? {}",
+            self.blind_name(skip.blind_type),
+            tag,
+            Self::verdict(skip)
+        );
+        for (choice, line) in [("Play", &skip.play), ("Skip", &skip.skip)] {
+            outln!(
+                "   {}: clears the ante {:>5.1}%   wins {:>5.1}%   {:.2} blinds passed",
+                choice,
+                line.ante_clear_rate * 100.0,
+                line.win_rate * 100.0,
+                line.mean_blinds_passed
+            );
+        }
+        let difference = &skip.difference;
+        outln!(
+            "   Skipping: {:+.2} blinds passed ({:+.2} to {:+.2})",
+            difference.mean,
+            difference.lower,
+            difference.upper
+        );
+    }
+}
+
+impl Report for PlanReport {
+    fn print_pretty(&self) {
+        let plan = &self.plan;
+        outln!("\n📅 Ante {} ({} runs per blind):", plan.ante, plan.runs);
+        for blind in &plan.blinds {
+            self.display_blind(blind);
+        }
+        outln!("   Clears the ante: {:.1}%", plan.ante_clear_rate * 100.0);
+        self.display_boss();
+        for skip in &plan.skips {
+            self.display_skip(skip);
+        }
+    }
+
+    fn print_compact(&self) {
+        let plan = &self.plan;
+        let clears: Vec<String> = plan
+            .blinds
+            .iter()
+            .map(|blind| format!("{:?} {:.1}%", blind.blind_type, blind.clear.probability * 100.0))
+            .collect();
+        let skips: Vec<String> = plan
+            .skips
+            .iter()
+            .map(|skip| format!("Skip {:?}: {:?}", skip.blind_type, skip.verdict).to_lowercase())
+            .collect();
+        outln!(
+            "Ante {}: {} | Ante {:.1}% | {}",
+            plan.ante,
+            clears.join(" | "),
+            plan.ante_clear_rate * 100.0,
+            skips.join(" | ")
+        );
+    }
+
+    /// One row per blind
+    fn records(&self) -> Result<Vec<serde_json::Value>> {
+        #[derive(Serialize)]
+        struct BlindRecord {
+            blind: String,
+            score_required: u64,
+            reward: u32,
+            mean_score: f64,
+            median_score: f64,
+            clear_probability: f64,
+            skip: Option<SkipVerdict>,
+        }
+
+        self.plan
+            .blinds
+            .iter()
+            .map(|blind| {
+                let skip = self.plan.skips.iter().find(|s| s.blind_type == blind.blind_type);
+                Ok(serde_json::to_value(BlindRecord {
+                    blind: self.blind_name(blind.blind_type),
+                    score_required: blind.score_required,
+                    reward: blind.reward,
+                    mean_score: blind.mean_score,
+                    median_score: blind.median_score.value(),
+                    clear_probability: blind.clear.probability,
+                    skip: skip.map(|skip| skip.verdict),
+                })?)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: PlanArgs,
+    }
+
+    fn parse(flags: &[&str]) -> PlanArgs {
+        Cli::parse_from(std::iter::once("plan").chain(flags.iter().copied())).args
+    }
+
+    #[test]
+    fn test_plan_args() {
+        let args = parse(&["--small-tag", "economy", "--big-tag", "Investment Tag"]);
+        assert_eq!(args.small_tag, Some(Tag::Economy));
+        assert_eq!(args.big_tag, Some(Tag::Investment));
+        assert_eq!(args.runs, 500);
+        assert!(Cli::try_parse_from(["plan", "--ante", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_start_ante_applies_flags() {
+        let (run, boss_name) = start_ante(&parse(&[])).unwrap();
+        assert_eq!((run.ante, run.boss_blind, boss_name), (1, None, None));
+
+        let args = parse(&["--ante", "3", "--boss", "club", "--jokers", "Joker", "--stake", "red"]);
+        let (run, boss_name) = start_ante(&args).unwrap();
+        assert_eq!(run.ante, 3);
+        assert_eq!(run.stake, Stake::Red);
+        assert_eq!(run.boss_blind, Some(BossBlind::TheClub));
+        assert_eq!(boss_name.as_deref(), Some("The Club"));
+        assert_eq!(run.jokers.len(), 1);
+    }
+
+    #[test]
+    fn test_unmodeled_boss_is_named() {
+        let dir = std::env::temp_dir().join(format!("jimbo-plan-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("state.json");
+        let state = r#"{"ante": 2, "blind": {"blind_type": "boss", "name": "The Hook",
+                        "score_required": 1600}}"#;
+        std::fs::write(&path, state).unwrap();
+
+        let args = parse(&["--game-state", path.to_str().unwrap()]);
+        let (run, boss_name) = start_ante(&args).unwrap();
+        assert_eq!((run.ante, run.boss_blind), (2, None));
+        assert_eq!(boss_name.as_deref(), Some("The Hook"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
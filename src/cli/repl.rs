@@ -0,0 +1,385 @@
+//! REPL command implementation
+//!
+//! This module implements the `repl` command, an interactive shell that
+//! keeps a hand, jokers, hand levels, and blind between commands, so plays
+//! can be solved, simulated, and explained without retyping every flag.
+//! Joker and boss names tab-complete, and history persists across sessions
+//! in `~/.jimbo_history`.
+
+use super::explain::display_trace;
+use super::solve::{format_cards, parse_hand, parse_jokers};
+use crate::core::score::format_score;
+use crate::core::{
+    score_trace, BlindConfig, BlindType, BossBlind, Card, GameRunState, HandLevels, HandType,
+    Joker, Score, Simulator, Solver,
+};
+use crate::core::joker::JokerKind;
+use anyhow::Result;
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::path::PathBuf;
+
+/// Commands the shell understands, for completion
+const COMMANDS: [&str; 11] = [
+    "hand", "jokers", "levels", "boss", "blind", "solve", "simulate", "explain", "state", "help",
+    "quit",
+];
+
+/// Default number of rounds for `simulate`
+const DEFAULT_RUNS: usize = 1000;
+
+/// Hand size `simulate` draws
+const HAND_SIZE: usize = 8;
+
+/// What the shell keeps between commands
+#[derive(Debug, Default)]
+struct Session {
+    hand: Vec<Card>,
+    jokers: Vec<Joker>,
+    hand_levels: HandLevels,
+    boss: Option<BossBlind>,
+    /// Score the blind requires, if set
+    blind_score: Option<u64>,
+}
+
+/// Whether the shell keeps reading commands
+#[derive(Debug, PartialEq, Eq)]
+enum Flow {
+    Continue,
+    Quit,
+}
+
+impl Session {
+    /// Runs one command line, printing its output
+    fn execute(&mut self, line: &str) -> Result<Flow> {
+        let line = line.trim();
+        let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let rest = rest.trim();
+
+        match command.to_lowercase().as_str() {
+            "" => {}
+            "hand" if rest.is_empty() => outln!("Hand: {}", format_cards(&self.hand)),
+            "hand" => self.hand = parse_hand(rest)?,
+            "jokers" => self.edit_jokers(rest)?,
+            "levels" if rest.is_empty() => self.display_levels(),
+            "levels" => {
+                let entries: Vec<&str> = rest.split(',').collect();
+                self.hand_levels.set_entries(entries)?;
+            }
+            "boss" if rest.is_empty() || rest.eq_ignore_ascii_case("none") => self.boss = None,
+            "boss" => self.boss = Some(rest.parse()?),
+            "blind" if rest.is_empty() || rest.eq_ignore_ascii_case("none") => {
+                self.blind_score = None
+            }
+            "blind" => self.blind_score = Some(rest.parse()?),
+            "solve" => self.solve()?,
+            "simulate" => match rest {
+                "" => self.simulate(DEFAULT_RUNS),
+                runs => self.simulate(runs.parse()?),
+            },
+            "explain" => self.explain()?,
+            "state" => self.display_state(),
+            "help" => display_help(),
+            "quit" | "exit" => return Ok(Flow::Quit),
+            _ => anyhow::bail!("Unknown command: {}. Type 'help' for the list", command),
+        }
+        Ok(Flow::Continue)
+    }
+
+    /// Runs a `jokers` command: list, add <name>, remove <name>, or clear
+    fn edit_jokers(&mut self, args: &str) -> Result<()> {
+        let (action, name) = args.split_once(' ').unwrap_or((args, ""));
+        match action.to_lowercase().as_str() {
+            "" => outln!("Jokers: {}", self.joker_names()),
+            "add" => self.jokers.extend(parse_jokers(&[name.trim().to_string()])?),
+            "remove" => {
+                let kind: JokerKind = name.trim().parse()?;
+                let index = self
+                    .jokers
+                    .iter()
+                    .rposition(|joker| joker.kind == kind)
+                    .ok_or_else(|| anyhow::anyhow!("No {} to remove", kind.name()))?;
+                self.jokers.remove(index);
+            }
+            "clear" => self.jokers.clear(),
+            _ => anyhow::bail!("Unknown jokers action: {}. Use add, remove, or clear", action),
+        }
+        Ok(())
+    }
+
+    /// Lists the jokers' names, or "none"
+    fn joker_names(&self) -> String {
+        match self.jokers.is_empty() {
+            true => "none".to_string(),
+            false => {
+                let names: Vec<&str> = self.jokers.iter().map(|joker| joker.kind.name()).collect();
+                names.join(", ")
+            }
+        }
+    }
+
+    /// Returns the run the session describes: one hand, no discards
+    fn run_state(&self) -> GameRunState {
+        let mut run = GameRunState::default()
+            .with_jokers(self.jokers.clone())
+            .with_hand_levels(self.hand_levels.clone());
+        run.boss_blind = self.boss;
+        run.hands_remaining = 1;
+        run.discards_remaining = 0;
+        run
+    }
+
+    /// Returns the solver for the session's jokers, levels, and blind
+    fn solver(&self) -> Solver {
+        Solver::new(self.run_state().calculator())
+            .with_blind_score(self.blind_score.map(Score::from))
+    }
+
+    /// Finds and prints the best play of the hand
+    fn solve(&self) -> Result<()> {
+        if self.hand.is_empty() {
+            anyhow::bail!("Set a hand first, e.g. 'hand AH KH QH JH 10H'");
+        }
+        let result = self.solver().solve(&self.hand);
+        let Some(score_result) = &result.best_score else {
+            outln!("No valid plays found");
+            return Ok(());
+        };
+
+        outln!("🃏 {:?}: {}", score_result.hand_type, format_cards(&result.best_hand.cards));
+        outln!("  Score: {} ({} × {})", score_result.score, score_result.chips, score_result.mult);
+        if let Some(blind_score) = self.blind_score {
+            match score_result.score >= Score::from(blind_score) {
+                true => outln!("  ✅ Beats blind (required: {})", blind_score),
+                false => outln!("  ❌ Does not beat blind (required: {})", blind_score),
+            }
+        }
+        for warning in &result.warnings {
+            outln!("  ⚠️  {}", warning);
+        }
+        Ok(())
+    }
+
+    /// Simulates rounds dealt from a standard deck and prints the spread
+    fn simulate(&self, runs: usize) {
+        let run = self.run_state();
+        let mut config = run.simulation_config(runs, None);
+        config.hand_size = HAND_SIZE;
+        config.blind_score = self.blind_score.map(Score::from);
+        config.blind = run
+            .boss_blind
+            .map(|boss| BlindConfig::for_boss(boss, run.blind_requirement(BlindType::Boss)));
+
+        let result = Simulator::new(Solver::new(run.calculator())).simulate(config);
+        outln!(
+            "📊 {} rounds: mean {}, median {}, P95 {}",
+            result.num_runs,
+            format_score(result.mean_score),
+            result.median_score,
+            result.percentile_95
+        );
+        if let Some(clear_rate) = result.blind_clear_rate {
+            outln!("  Chance to beat blind: {:.1}%", clear_rate * 100.0);
+        }
+    }
+
+    /// Prints the scoring trace of the hand's best play
+    fn explain(&self) -> Result<()> {
+        if self.hand.is_empty() {
+            anyhow::bail!("Set a hand first, e.g. 'hand AH KH QH JH 10H'");
+        }
+        let solver = self.solver();
+        let result = solver.solve(&self.hand);
+        let score_result = solver.calculator().calculate(&result.best_hand, &result.held_cards);
+        outln!("🃏 {:?}: {}", score_result.hand_type, format_cards(&result.best_hand.cards));
+        display_trace(&score_result, &score_trace(&score_result));
+        Ok(())
+    }
+
+    /// Prints the hand levels above 1
+    fn display_levels(&self) {
+        let levels: Vec<String> = HandType::all()
+            .into_iter()
+            .filter(|&hand_type| self.hand_levels.level(hand_type) > 1)
+            .map(|hand_type| format!("{:?}={}", hand_type, self.hand_levels.level(hand_type)))
+            .collect();
+        match levels.is_empty() {
+            true => outln!("Levels: all 1"),
+            false => outln!("Levels: {}", levels.join(", ")),
+        }
+    }
+
+    /// Prints everything the session holds
+    fn display_state(&self) {
+        outln!("Hand: {}", format_cards(&self.hand));
+        outln!("Jokers: {}", self.joker_names());
+        self.display_levels();
+        outln!("Boss: {}", self.boss.map_or("none", |boss| boss.name()));
+        match self.blind_score {
+            Some(score) => outln!("Blind: {}", score),
+            None => outln!("Blind: none"),
+        }
+    }
+}
+
+/// Prints the commands the shell understands
+fn display_help() {
+    outln!("Commands:");
+    outln!("  hand [CARDS]              Show or set the hand (e.g., hand AH KH QH JH 10H)");
+    outln!("  jokers [add|remove NAME]  Show, add, or remove jokers; 'jokers clear' drops all");
+    outln!("  levels [ENTRIES]          Show or set hand levels (e.g., levels Flush=5,Pair=3)");
+    outln!("  boss [NAME|none]          Set or clear the boss blind (e.g., boss The Psychic)");
+    outln!("  blind [SCORE|none]        Set or clear the score the blind requires");
+    outln!("  solve                     Find the best play of the hand");
+    outln!("  simulate [RUNS]           Simulate rounds from a standard deck (default: 1000)");
+    outln!("  explain                   Show how the best play scores, card by card");
+    outln!("  state                     Show the whole session");
+    outln!("  quit                      Leave the shell");
+}
+
+/// Returns where the word being completed starts and its completions
+fn completions(line: &str) -> (usize, Vec<String>) {
+    let candidates: Vec<String> = match line.split_once(' ') {
+        None => COMMANDS.iter().map(|command| command.to_string()).collect(),
+        Some((command, rest)) => match command.to_lowercase().as_str() {
+            "jokers" => match rest.split_once(' ') {
+                None => ["add", "remove", "clear"].iter().map(|a| a.to_string()).collect(),
+                Some((_, _)) => JokerKind::all().iter().map(|kind| kind.name().to_string()).collect(),
+            },
+            "boss" => {
+                let mut names: Vec<String> =
+                    BossBlind::all().iter().map(|boss| boss.name().to_string()).collect();
+                names.push("none".to_string());
+                names
+            }
+            _ => vec![],
+        },
+    };
+
+    // Names may contain spaces, so arguments complete from after the
+    // command (and jokers action) rather than from the last word
+    let start = match line.split_once(' ') {
+        None => 0,
+        Some(("jokers", rest)) => {
+            line.len() - rest.split_once(' ').map_or(rest, |(_, name)| name).len()
+        }
+        Some((command, _)) => command.len() + 1,
+    };
+    let typed = line[start..].to_lowercase();
+    let matches = candidates
+        .into_iter()
+        .filter(|candidate| candidate.to_lowercase().starts_with(&typed))
+        .collect();
+    (start, matches)
+}
+
+/// Completes command, joker, and boss names
+struct ShellHelper;
+
+impl Completer for ShellHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(completions(&line[..pos]))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+/// Returns the file history is kept in, if there is a home directory
+fn history_path() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".jimbo_history"))
+}
+
+/// Runs the repl command
+pub fn run() -> Result<()> {
+    let mut editor: Editor<ShellHelper, DefaultHistory> = Editor::new()?;
+    editor.set_helper(Some(ShellHelper));
+    let history = history_path();
+    if let Some(path) = &history {
+        // No history yet is fine
+        let _ = editor.load_history(path);
+    }
+
+    outln!("🃏 Jimbo shell. Type 'help' for commands, 'quit' to leave.");
+    let mut session = Session::default();
+    loop {
+        let line = match editor.readline("jimbo> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted | ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+        if !line.trim().is_empty() {
+            editor.add_history_entry(line.as_str())?;
+        }
+        match session.execute(&line) {
+            Ok(Flow::Continue) => {}
+            Ok(Flow::Quit) => break,
+            Err(e) => outln!("❌ {}", e),
+        }
+    }
+
+    if let Some(path) = &history {
+        editor.save_history(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Rank;
+
+    #[test]
+    fn test_session_commands() {
+        let mut session = Session::default();
+        session.execute("hand AH KH QH JH 10H").unwrap();
+        assert_eq!(session.hand.len(), 5);
+        assert_eq!(session.hand[0].rank, Rank::Ace);
+
+        session.execute("jokers add Blueprint").unwrap();
+        session.execute("jokers add greedy_joker").unwrap();
+        session.execute("jokers remove Blueprint").unwrap();
+        assert_eq!(session.jokers, vec![Joker::new(JokerKind::GreedyJoker)]);
+        assert!(session.execute("jokers remove Baron").is_err());
+
+        session.execute("levels Flush=5,Pair=3").unwrap();
+        assert_eq!(session.hand_levels.level(HandType::Flush), 5);
+        session.execute("boss The Psychic").unwrap();
+        session.execute("blind 600").unwrap();
+        assert_eq!((session.boss, session.blind_score), (Some(BossBlind::ThePsychic), Some(600)));
+        session.execute("boss none").unwrap();
+        assert_eq!(session.boss, None);
+
+        assert!(session.execute("dance").is_err());
+        assert_eq!(session.execute("quit").unwrap(), Flow::Quit);
+    }
+
+    #[test]
+    fn test_completions() {
+        assert_eq!(completions("si"), (0, vec!["simulate".to_string()]));
+        assert_eq!(completions("jokers a"), (7, vec!["add".to_string()]));
+        let (start, names) = completions("jokers add greedy");
+        assert_eq!((start, names), (11, vec!["Greedy Joker".to_string()]));
+        let (start, names) = completions("boss the ps");
+        assert_eq!((start, names), (5, vec!["The Psychic".to_string()]));
+    }
+}
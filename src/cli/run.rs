@@ -0,0 +1,147 @@
+//! Run command implementation
+//!
+//! This module implements the `run` command which simulates complete runs
+//! from a game state through the final ante and reports how often they win,
+//! how far they get, and which blinds end them.
+
+use super::completions::{deck_type_candidates, joker_candidates};
+use super::output::{self, OutputFormat};
+use super::simulate::RunsReport;
+use super::solve::parse_jokers;
+use crate::config::GameState;
+use crate::core::{DeckType, GameRunState, ShopModel, Simulator, Solver, Stake};
+use anyhow::{Context, Result};
+use clap::Args;
+use clap_complete::ArgValueCandidates;
+
+/// Arguments for the run command
+#[derive(Debug, Args)]
+pub struct RunArgs {
+    /// Path to a game state file (JSON) to start the runs from: its deck,
+    /// jokers, hand levels, money, ante, and stake (default: a new run)
+    #[arg(long)]
+    game_state: Option<String>,
+
+    /// Number of runs to simulate (default: 500)
+    #[arg(long, default_value = "500")]
+    runs: usize,
+
+    /// Stake to play at (e.g., "gold"), overriding the game state's
+    /// (default: white)
+    #[arg(long)]
+    stake: Option<Stake>,
+
+    /// Official deck for a new run (e.g., "plasma"), when no game state is
+    /// given
+    #[arg(
+        long,
+        conflicts_with = "game_state",
+        add = ArgValueCandidates::new(deck_type_candidates)
+    )]
+    deck_type: Option<DeckType>,
+
+    /// Comma-separated list of jokers, replacing the game state's
+    #[arg(long, value_delimiter = ',', add = ArgValueCandidates::new(joker_candidates))]
+    jokers: Vec<String>,
+
+    /// Comma-separated hand levels (e.g., "Flush=5,Pair=3"), set on top of
+    /// the game state's
+    #[arg(long, value_delimiter = ',')]
+    hand_levels: Vec<String>,
+
+    /// Visit the shop after each blind, buying jokers, vouchers, planets,
+    /// and packs
+    #[arg(long)]
+    shop: bool,
+
+    /// Optional seed for reproducible runs
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Threads to simulate on (default: one per core); results do not
+    /// depend on it
+    #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+    threads: Option<u16>,
+
+    /// Output format: pretty (default), json, yaml, csv, compact
+    #[arg(long, default_value = "pretty")]
+    output: OutputFormat,
+}
+
+/// Runs the run command
+pub fn run(args: RunArgs) -> Result<()> {
+    let run = start_run(&args)?;
+    let simulator = Simulator::new(Solver::new(run.calculator()))
+        .with_parallelism(args.threads.map(usize::from));
+    let simulator = match args.shop {
+        true => simulator.with_shop(ShopModel::new()),
+        false => simulator,
+    };
+
+    if !args.output.is_structured() {
+        outln!("Running {} full runs at {:?} Stake...", args.runs, run.stake);
+    }
+    let result = simulator.simulate_runs(&run, args.runs, args.seed);
+    output::print(&RunsReport { result: &result }, args.output)
+}
+
+/// Builds the run to simulate from the game state or a new run, with the
+/// stake, jokers, and hand levels from flags taking precedence
+///
+/// The stake is set before the run is built, so its hands and discards
+/// follow it.
+fn start_run(args: &RunArgs) -> Result<GameRunState> {
+    let mut run = match &args.game_state {
+        Some(path) => {
+            let mut state = GameState::from_file(path)
+                .with_context(|| format!("Failed to load game state from {}", path))?;
+            if let Some(stake) = args.stake {
+                state.stake = stake;
+            }
+            state.to_run_state()?
+        }
+        None => {
+            GameRunState::new(args.deck_type.unwrap_or_default(), args.stake.unwrap_or_default())
+        }
+    };
+    if !args.jokers.is_empty() {
+        run = run.with_jokers(parse_jokers(&args.jokers)?);
+    }
+    run.hand_levels.set_entries(&args.hand_levels)?;
+    Ok(run)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: RunArgs,
+    }
+
+    fn parse(flags: &[&str]) -> RunArgs {
+        Cli::parse_from(std::iter::once("run").chain(flags.iter().copied())).args
+    }
+
+    #[test]
+    fn test_start_run_applies_flags() {
+        let run = start_run(&parse(&[])).unwrap();
+        assert_eq!(run.stake, Stake::White);
+        assert!(run.jokers.is_empty());
+
+        let white = GameRunState::new(DeckType::default(), Stake::White);
+        let run = start_run(&parse(&[
+            "--stake", "gold", "--jokers", "Joker,Baron", "--hand-levels", "Flush=5,Pair=3",
+        ]))
+        .unwrap();
+        assert_eq!(run.stake, Stake::Gold);
+        assert_eq!(run.jokers.len(), 2);
+        assert_eq!(run.hand_levels.level(crate::core::HandType::Flush), 5);
+        assert!(run.discards_remaining < white.discards_remaining);
+
+        assert_eq!(parse(&["--output", "yaml"]).output, OutputFormat::Yaml);
+    }
+}
@@ -0,0 +1,149 @@
+//! Seed command implementation
+//!
+//! This module implements the `seed` command which predicts what a Balatro
+//! seed holds for its first antes (boss blinds, vouchers, and shop cards)
+//! and rates how well those suit a build.
+
+use super::completions::archetype_candidates;
+use super::output::{self, OutputFormat, Report};
+use crate::core::{Archetype, SeedPrediction, SeedRating};
+use anyhow::Result;
+use clap::Args;
+use clap_complete::ArgValueCandidates;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Arguments for the seed command
+#[derive(Debug, Args)]
+pub struct SeedArgs {
+    /// Seed to analyze, as entered in the game (e.g., "7LB2WVPK")
+    seed: String,
+
+    /// Number of antes to predict
+    #[arg(long, default_value = "3")]
+    antes: u32,
+
+    /// Shop cards to predict per ante (two per shop visit, more with
+    /// Overstock or rerolls)
+    #[arg(long, default_value = "4")]
+    shop_cards: usize,
+
+    /// Build to rate the seed for: flush, straight, pairs, face_cards, or
+    /// high_card
+    #[arg(long, add = ArgValueCandidates::new(archetype_candidates))]
+    archetype: Option<Archetype>,
+
+    /// Output format: pretty (default), json, yaml, csv, compact
+    #[arg(long, default_value = "pretty")]
+    output: OutputFormat,
+}
+
+/// Runs the seed command
+pub fn run(args: SeedArgs) -> Result<()> {
+    anyhow::ensure!(args.antes > 0, "--antes must be at least 1");
+    let prediction = SeedPrediction::new(&args.seed, args.antes, args.shop_cards)?;
+    let rating = args.archetype.map(|archetype| prediction.rate(archetype));
+    output::print(&SeedReport { prediction, rating }, args.output)
+}
+
+/// A seed's predictions and rating, as printed
+#[derive(Debug, Serialize)]
+struct SeedReport {
+    #[serde(flatten)]
+    prediction: SeedPrediction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rating: Option<SeedRating>,
+}
+
+impl Report for SeedReport {
+    fn print_pretty(&self) {
+        outln!("🌱 Seed {} (Red Deck, White Stake, vouchers redeemed):", self.prediction.seed);
+        for ante in &self.prediction.antes {
+            outln!("\n  Ante {}:", ante.ante);
+            outln!("    Boss:    {}", ante.boss);
+            outln!("    Voucher: {}", ante.voucher);
+            outln!("    Shop:    {}", shop_list(ante));
+        }
+        if let Some(rating) = &self.rating {
+            outln!("\n⭐ {} rating: {:.1}/10", rating.archetype.name(), rating.score);
+            for highlight in &rating.highlights {
+                outln!("  {}", highlight);
+            }
+        }
+    }
+
+    fn print_compact(&self) {
+        for ante in &self.prediction.antes {
+            outln!(
+                "Ante {}: {} | {} | {}",
+                ante.ante,
+                ante.boss,
+                ante.voucher,
+                shop_list(ante)
+            );
+        }
+        if let Some(rating) = &self.rating {
+            outln!("{}: {:.1}/10", rating.archetype.name(), rating.score);
+        }
+    }
+
+    /// One row per ante, with the shop as one column
+    fn records(&self) -> Result<Vec<Value>> {
+        Ok(self
+            .prediction
+            .antes
+            .iter()
+            .map(|ante| {
+                json!({
+                    "seed": self.prediction.seed,
+                    "ante": ante.ante,
+                    "boss": ante.boss,
+                    "voucher": ante.voucher,
+                    "shop": shop_list(ante),
+                })
+            })
+            .collect())
+    }
+}
+
+/// Formats an ante's shop cards as "Joker, Foil Blueprint, Jupiter"
+fn shop_list(ante: &crate::core::seed::AntePrediction) -> String {
+    let cards: Vec<String> = ante
+        .shop
+        .iter()
+        .map(|card| match card.edition {
+            Some(edition) => format!("{:?} {}", edition, card.name),
+            None => card.name.clone(),
+        })
+        .collect();
+    cards.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: SeedArgs,
+    }
+
+    #[test]
+    fn test_seed_args() {
+        let cli = Cli::parse_from(["seed", "7LB2WVPK", "--antes", "2", "--archetype", "flush"]);
+        assert_eq!(cli.args.seed, "7LB2WVPK");
+        assert_eq!(cli.args.antes, 2);
+        assert_eq!(cli.args.shop_cards, 4);
+        assert_eq!(cli.args.archetype, Some(Archetype::Flush));
+
+        assert!(Cli::try_parse_from(["seed", "7LB2WVPK", "--archetype", "mult"]).is_err());
+
+        let prediction = SeedPrediction::new(&cli.args.seed, 2, 4).unwrap();
+        let report = SeedReport { prediction, rating: None };
+        let records = report.records().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1]["ante"], 2);
+    }
+}
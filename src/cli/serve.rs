@@ -0,0 +1,514 @@
+//! Serve command implementation
+//!
+//! This module implements the `serve` command which answers `solve`,
+//! `simulate`, and `explain` over HTTP, so overlays, bots, and web pages
+//! can use jimbo as a backend. A request's JSON body holds the command's
+//! options under their flag names (e.g., `{"hand": "AH KH QH", "jokers":
+//! ["Joker"], "blind_score": 300}`), and the response is what the command
+//! prints with `--output json`.
+//!
+//! Requests are answered by a fixed pool of worker threads, and only the
+//! origins given with `--allow-origin` may call the server from a browser.
+
+use super::explain::{self, ExplainArgs};
+use super::output::Sink;
+use super::simulate::{self, SimulateArgs};
+use super::solve::{self, SolveArgs};
+use anyhow::{Context, Result};
+use clap::{Args, Parser};
+use serde_json::{json, Map, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Largest request body accepted, in bytes
+const MAX_BODY: usize = 1 << 20;
+
+/// Longest request line or header accepted, in bytes
+const MAX_LINE: usize = 8 << 10;
+
+/// Most headers accepted in one request
+const MAX_HEADERS: usize = 100;
+
+/// Most runs one /simulate request may ask for
+const MAX_SIMULATE_RUNS: usize = 100_000;
+
+/// Longest time limit one /simulate request may ask for
+const MAX_SIMULATE_TIME: Duration = Duration::from_secs(60);
+
+/// Longest time limit one /solve --search request may ask for
+const MAX_SOLVE_TIME: Duration = Duration::from_secs(60);
+
+/// Options that read or write files on the server, read stdin, size the
+/// server's thread pool, or change the response format, which a request
+/// may not set
+const BLOCKED_OPTIONS: [&str; 9] = [
+    "batch",
+    "deck",
+    "deck-remaining",
+    "export",
+    "game-state",
+    "output",
+    "quiet",
+    "shop-weights",
+    "threads",
+];
+
+/// Arguments for the serve command
+#[derive(Debug, Args)]
+pub struct ServeArgs {
+    /// Address to listen on; use 0.0.0.0 to accept requests from other
+    /// machines
+    #[arg(long, default_value = "127.0.0.1")]
+    host: String,
+
+    /// Port to listen on
+    #[arg(short, long, default_value = "8080")]
+    port: u16,
+
+    /// Number of requests answered at once; more wait their turn
+    #[arg(long, default_value = "4", value_parser = clap::value_parser!(u16).range(1..))]
+    workers: u16,
+
+    /// Origin allowed to call the server from a browser (e.g.,
+    /// http://localhost:3000), given once per origin; none by default
+    #[arg(long = "allow-origin", value_name = "ORIGIN")]
+    allow_origins: Vec<String>,
+}
+
+/// Runs the serve command until it is interrupted
+pub fn run(args: ServeArgs) -> Result<()> {
+    let listener = TcpListener::bind((args.host.as_str(), args.port))
+        .with_context(|| format!("Failed to listen on {}:{}", args.host, args.port))?;
+    outln!("🌐 Serving on http://{}", listener.local_addr()?);
+    outln!("   POST /solve, /simulate, or /explain with a JSON body; Ctrl+C to stop");
+
+    // Simulations can take a while, so workers answer requests side by
+    // side; once they are all busy and the queue is full, accepting waits
+    let (sender, receiver) = mpsc::sync_channel::<TcpStream>(args.workers.into());
+    let receiver = Arc::new(Mutex::new(receiver));
+    let allow_origins = Arc::new(args.allow_origins);
+    for _ in 0..args.workers {
+        let receiver = Arc::clone(&receiver);
+        let allow_origins = Arc::clone(&allow_origins);
+        std::thread::spawn(move || {
+            loop {
+                let Ok(stream) = receiver.lock().unwrap().recv() else { break };
+                // A panicking request must not take its worker with it
+                let handled = panic::catch_unwind(AssertUnwindSafe(|| {
+                    handle_connection(&stream, &allow_origins)
+                }));
+                match handled {
+                    Ok(Ok(())) => {}
+                    Ok(Err(e)) => eoutln!("⚠️  {:#}", e),
+                    Err(_) => eoutln!("⚠️  A request failed unexpectedly"),
+                }
+            }
+        });
+    }
+
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else { continue };
+        sender.send(stream).context("Every worker has stopped")?;
+    }
+    Ok(())
+}
+
+/// Reads one request from a connection and writes its response
+///
+/// Browsers are allowed to read the response only if the request's
+/// Origin is one of `allow_origins`.
+fn handle_connection(stream: &TcpStream, allow_origins: &[String]) -> Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+    let (request, response, origin) = match read_request(&mut BufReader::new(stream)) {
+        Ok(request) => {
+            let response = respond(&request);
+            let origin = request.origin.filter(|origin| allow_origins.contains(origin));
+            (format!("{} {}", request.method, request.path), response, origin)
+        }
+        Err(e) => ("(unreadable request)".to_string(), Response::error(400, &e), None),
+    };
+    outln!("{} → {}", request, response.status);
+    response.write_to(&mut &*stream, origin.as_deref())
+}
+
+/// An HTTP request, as far as the server needs it
+#[derive(Debug)]
+struct Request {
+    method: String,
+    /// The target without its query string
+    path: String,
+    /// The Content-Type header, if any
+    content_type: Option<String>,
+    /// The Origin header browsers send, if any
+    origin: Option<String>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    /// Returns true if the body is declared as JSON
+    fn is_json(&self) -> bool {
+        self.content_type.as_deref().is_some_and(|content_type| {
+            let media_type = content_type.split(';').next().unwrap_or_default();
+            media_type.trim().eq_ignore_ascii_case("application/json")
+        })
+    }
+}
+
+/// Reads a request line, headers, and a body of Content-Length bytes
+///
+/// Lines over `MAX_LINE` bytes, more than `MAX_HEADERS` headers, and
+/// bodies over `MAX_BODY` bytes are refused.
+fn read_request(reader: &mut impl BufRead) -> Result<Request> {
+    let mut line = String::new();
+    read_head_line(reader, &mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        anyhow::bail!("Malformed request line: {}", line.trim());
+    };
+    let method = method.to_uppercase();
+    let path = target.split('?').next().unwrap_or_default().to_string();
+
+    let (mut content_length, mut content_type, mut origin) = (0, None, None);
+    for count in 0.. {
+        anyhow::ensure!(read_head_line(reader, &mut line)? > 0, "Request ended in its headers");
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        anyhow::ensure!(count < MAX_HEADERS, "Request has over {} headers", MAX_HEADERS);
+        let Some((name, value)) = header.split_once(':') else { continue };
+        let (name, value) = (name.trim(), value.trim());
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length =
+                value.parse().with_context(|| format!("Invalid Content-Length: {}", value))?;
+        } else if name.eq_ignore_ascii_case("content-type") {
+            content_type = Some(value.to_string());
+        } else if name.eq_ignore_ascii_case("origin") {
+            origin = Some(value.to_string());
+        }
+    }
+    anyhow::ensure!(content_length <= MAX_BODY, "Request body is over {} bytes", MAX_BODY);
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).context("Request body is shorter than its Content-Length")?;
+    Ok(Request { method, path, content_type, origin, body })
+}
+
+/// Reads one line of a request's head into `line`, returning its length
+fn read_head_line(reader: &mut impl BufRead, line: &mut String) -> Result<usize> {
+    line.clear();
+    let length = reader.take(MAX_LINE as u64 + 1).read_line(line)?;
+    anyhow::ensure!(length <= MAX_LINE, "Request line or header is over {} bytes", MAX_LINE);
+    Ok(length)
+}
+
+/// An HTTP response with a JSON body
+#[derive(Debug)]
+struct Response {
+    status: u16,
+    body: Option<Value>,
+}
+
+impl Response {
+    fn json(status: u16, body: Value) -> Self {
+        Self { status, body: Some(body) }
+    }
+
+    /// Responds with `{"error": "<message>"}`, including any context
+    fn error(status: u16, error: &anyhow::Error) -> Self {
+        Self::json(status, json!({ "error": format!("{:#}", error) }))
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            204 => "No Content",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            415 => "Unsupported Media Type",
+            422 => "Unprocessable Entity",
+            _ => "Internal Server Error",
+        }
+    }
+
+    /// Writes the response, allowing a web page from `origin`, if given,
+    /// to call the server
+    fn write_to(&self, writer: &mut impl Write, origin: Option<&str>) -> Result<()> {
+        let body = match &self.body {
+            Some(body) => serde_json::to_string_pretty(body)? + "\n",
+            None => String::new(),
+        };
+        let cors = match origin {
+            Some(origin) => format!(
+                "Access-Control-Allow-Origin: {}\r\n\
+                 Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+                 Access-Control-Allow-Headers: Content-Type\r\n",
+                origin
+            ),
+            None => String::new(),
+        };
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: {}\r\n\
+             {}\
+             Vary: Origin\r\n\
+             Connection: close\r\n\r\n{}",
+            self.status,
+            self.reason(),
+            body.len(),
+            cors,
+            body
+        )?;
+        writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Answers a request
+///
+/// `GET /` lists the endpoints, and `OPTIONS` answers browsers' preflight
+/// checks. A POST whose Content-Type is not application/json is a 415, a
+/// body that does not parse as options is a 400, and an error from the
+/// command itself (e.g., an unknown joker) is a 422.
+fn respond(request: &Request) -> Response {
+    let path = match request.path.trim_end_matches('/') {
+        "" => "/",
+        path => path,
+    };
+    if request.method == "POST" && !request.is_json() {
+        return Response::error(415, &anyhow::anyhow!("Content-Type must be application/json"));
+    }
+    match (request.method.as_str(), path) {
+        ("OPTIONS", _) => Response { status: 204, body: None },
+        ("GET", "/") => Response::json(
+            200,
+            json!({
+                "name": "jimbo",
+                "version": env!("CARGO_PKG_VERSION"),
+                "endpoints": ["/solve", "/simulate", "/explain"],
+            }),
+        ),
+        ("POST", "/solve") => answer::<SolveArgs>(&request.body, |args, sink| {
+            args.check_limits(MAX_SOLVE_TIME)?;
+            solve::run_with(args, sink).map(|_| ())
+        }),
+        ("POST", "/simulate") => answer::<SimulateArgs>(&request.body, |args, sink| {
+            args.check_limits(MAX_SIMULATE_RUNS, MAX_SIMULATE_TIME)?;
+            simulate::run_with(args, sink)
+        }),
+        ("POST", "/explain") => answer::<ExplainArgs>(&request.body, explain::run_with),
+        (_, "/" | "/solve" | "/simulate" | "/explain") => Response::error(
+            405,
+            &anyhow::anyhow!("{} is not allowed on {}", request.method, request.path),
+        ),
+        _ => Response::error(404, &anyhow::anyhow!("No endpoint at {}", request.path)),
+    }
+}
+
+/// Runs a command with the options in a request body
+fn answer<A: Args>(body: &[u8], run: fn(&A, &mut Sink) -> Result<()>) -> Response {
+    let args = match parse_options::<A>(body) {
+        Ok(args) => args,
+        Err(e) => return Response::error(400, &e),
+    };
+    let mut sink = Sink::Capture(None);
+    match run(&args, &mut sink) {
+        Ok(()) => Response::json(200, sink.into_value().unwrap_or(Value::Null)),
+        Err(e) => Response::error(422, &e),
+    }
+}
+
+/// A command's options, parsed from the flags a request body stands for
+#[derive(Parser)]
+#[command(no_binary_name = true)]
+struct Options<A: Args> {
+    #[command(flatten)]
+    args: A,
+}
+
+/// Parses a JSON object of options into a command's arguments
+///
+/// Each key is a flag with dashes or underscores (`blind_score` is
+/// `--blind-score`), true sets a switch, false and null leave an option
+/// out, and a list gives a repeatable option once per item.
+fn parse_options<A: Args>(body: &[u8]) -> Result<A> {
+    let options: Map<String, Value> = if body.iter().all(u8::is_ascii_whitespace) {
+        Map::new()
+    } else {
+        serde_json::from_slice(body).context("Request body must be a JSON object of options")?
+    };
+
+    let mut flags = Vec::new();
+    for (key, value) in &options {
+        let flag = key.replace('_', "-");
+        anyhow::ensure!(
+            !BLOCKED_OPTIONS.contains(&flag.as_str()),
+            "{} is not available over HTTP",
+            key
+        );
+        match value {
+            Value::Null | Value::Bool(false) => {}
+            Value::Bool(true) => flags.push(format!("--{}", flag)),
+            Value::Array(items) => {
+                for item in items {
+                    flags.push(format!("--{}={}", flag, option_value(key, item)?));
+                }
+            }
+            value => flags.push(format!("--{}={}", flag, option_value(key, value)?)),
+        }
+    }
+    flags.push("--output=json".to_string());
+
+    Options::<A>::try_parse_from(flags).map(|options| options.args).map_err(|e| {
+        // Keep clap's first line, without its "error: " prefix or usage
+        let message = e.to_string();
+        let message = message.lines().next().unwrap_or_default();
+        anyhow::anyhow!("{}", message.trim_start_matches("error: "))
+    })
+}
+
+/// Returns an option's value as it would be typed on the command line
+fn option_value(key: &str, value: &Value) -> Result<String> {
+    match value {
+        Value::String(text) => Ok(text.clone()),
+        Value::Number(number) => Ok(number.to_string()),
+        Value::Bool(flag) => Ok(flag.to_string()),
+        _ => anyhow::bail!("{} must be a string, a number, or a list of them", key),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    fn request(method: &str, path: &str, body: Vec<u8>) -> Request {
+        Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            content_type: Some("application/json".to_string()),
+            origin: None,
+            body,
+        }
+    }
+
+    fn post(path: &str, body: Value) -> Response {
+        respond(&request("POST", path, serde_json::to_vec(&body).unwrap()))
+    }
+
+    #[test]
+    fn test_read_request() {
+        let raw = "POST /solve?pretty HTTP/1.1\r\nHost: localhost\r\ncontent-length: 2\r\n\
+                   Content-Type: application/json; charset=utf-8\r\n\r\n{}";
+        let request = read_request(&mut raw.as_bytes()).unwrap();
+        assert_eq!((request.method.as_str(), request.path.as_str()), ("POST", "/solve"));
+        assert_eq!(request.body, b"{}");
+        assert!(request.is_json());
+        assert_eq!(request.origin, None);
+
+        let short = "POST /solve HTTP/1.1\r\nContent-Length: 10\r\n\r\n{}";
+        assert!(read_request(&mut short.as_bytes()).is_err());
+        assert!(read_request(&mut "\r\n".as_bytes()).is_err());
+
+        let error = |raw: String| read_request(&mut raw.as_bytes()).unwrap_err().to_string();
+        let long = format!("GET / HTTP/1.1\r\nX-Long: {}\r\n\r\n", "a".repeat(MAX_LINE));
+        assert!(error(long).contains("over 8192 bytes"));
+        let many = format!("GET / HTTP/1.1\r\n{}\r\n", "X-Many: 1\r\n".repeat(MAX_HEADERS + 1));
+        assert!(error(many).contains("over 100 headers"));
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let body = br#"{"hand": "AH KH", "jokers": ["Joker", "GreedyJoker"], "blind-score": 300,
+            "exhaustive": true, "per_hand_type": false, "seed": null}"#;
+        let args: SolveArgs = parse_options(body).unwrap();
+        let debug = format!("{:?}", args);
+        assert!(debug.contains(r#"jokers: ["Joker", "GreedyJoker"]"#), "{}", debug);
+        assert!(debug.contains("blind_score: Some(300)"), "{}", debug);
+        assert!(debug.contains("exhaustive: true"), "{}", debug);
+        assert!(debug.contains("per_hand_type: false"), "{}", debug);
+
+        let error = |body: &[u8]| parse_options::<SolveArgs>(body).unwrap_err().to_string();
+        assert!(error(br#"{"hand": "AH", "export": "runs.csv"}"#).contains("not available"));
+        assert!(error(br#"{"hand": "AH", "game_state": "/etc/passwd"}"#).contains("not available"));
+        assert!(error(br#"{"hand": "AH", "deck-remaining": "d.json"}"#).contains("not available"));
+        assert!(error(br#"{"hand": "AH", "colour": "red"}"#).contains("--colour"));
+        assert!(error(br#"{"hand": {"cards": 2}}"#).contains("hand must be"));
+        assert!(error(b"[1, 2]").contains("JSON object"));
+    }
+
+    #[test]
+    fn test_respond() {
+        let solved = post("/solve", json!({ "hand": "AH KH QH JH 10H", "jokers": ["Joker"] }));
+        assert_eq!(solved.status, 200);
+        let body = solved.body.unwrap();
+        assert_eq!(body["best_play"]["hand_type"], "StraightFlush");
+
+        let explained = post("/explain/", json!({ "hand": "KH KS" }));
+        assert_eq!(explained.status, 200);
+        assert_eq!(explained.body.unwrap()["hand_type"], "Pair");
+
+        let simulated = post("/simulate", json!({ "runs": 20, "seed": 7 }));
+        assert_eq!(simulated.status, 200);
+        assert_eq!(post("/simulate", json!({ "runs": MAX_SIMULATE_RUNS + 1 })).status, 422);
+        assert_eq!(post("/simulate", json!({ "max_time": 3600 })).status, 422);
+        assert_eq!(post("/simulate", json!({ "full_run": true, "runs": 5000 })).status, 422);
+        assert_eq!(post("/simulate", json!({ "runs": 20, "threads": 60000 })).status, 400);
+        assert_eq!(post("/solve", json!({ "search": true, "max_time": 3600 })).status, 422);
+        assert_eq!(post("/simulate", json!({ "shop": true, "shop_weights": "w" })).status, 400);
+        assert_eq!(post("/explain", json!({ "hand": "KH", "deck": "/etc/passwd" })).status, 400);
+
+        let mut form = request("POST", "/solve", b"hand=AH".to_vec());
+        form.content_type = Some("application/x-www-form-urlencoded".to_string());
+        assert_eq!(respond(&form).status, 415);
+        form.content_type = None;
+        assert_eq!(respond(&form).status, 415);
+
+        assert_eq!(post("/explain", json!({})).status, 400);
+        assert_eq!(post("/solve", json!({ "hand": "AH", "jokers": ["Nope"] })).status, 422);
+        assert_eq!(post("/play", json!({})).status, 404);
+        assert_eq!(respond(&request("GET", "/solve", vec![])).status, 405);
+    }
+
+    #[test]
+    fn test_serve_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            handle_connection(&stream, &["http://localhost:3000".to_string()]).unwrap();
+        });
+
+        let body = r#"{"hand": "KH KS"}"#;
+        let mut client = TcpStream::connect(address).unwrap();
+        write!(
+            client,
+            "POST /explain HTTP/1.1\r\nOrigin: http://localhost:3000\r\n\
+             Content-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).unwrap();
+        server.join().unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+        assert!(response.contains("Access-Control-Allow-Origin: http://localhost:3000\r\n"));
+        let (_, json) = response.split_once("\r\n\r\n").unwrap();
+        let json: Value = serde_json::from_str(json).unwrap();
+        assert_eq!(json["score"].as_f64(), Some(60.0));
+
+        // Origins that were not allowed get no CORS headers at all
+        let mut written = Vec::new();
+        Response { status: 204, body: None }.write_to(&mut written, None).unwrap();
+        assert!(!String::from_utf8(written).unwrap().contains("Access-Control"));
+    }
+}
@@ -3,162 +3,893 @@
 //! This module implements the `simulate` command which runs multiple
 //! simulations to evaluate build performance.
 
+use super::completions::{boss_candidates, deck_type_candidates, joker_candidates};
+use super::output::{OutputFormat, Report, Sink};
 use crate::config::DeckConfig;
+use crate::core::score::{format_score, Score};
+use crate::core::simulator::{SimulationResult, FINAL_ANTE};
 use crate::core::{
-    create_standard_deck, ScoreCalculator, SimulationConfig, Simulator, Solver,
+    create_standard_deck, BeamSearch, BlindConfig, BlindType, BossBlind, Convergence,
+    DeckPerturbation, DeckSensitivity, DeckType, Exhaustive, FlushChaser, GameRunState, Greedy,
+    HandLevels, HistogramBin, HighCardPreserver, PairSpammer, ParameterSweep, Random, RawFormat,
+    RunSimulationResult, ShopModel, ShopWeights, SimProgress, Simulator, Solver, Stake, SweepAxis,
+    SweepResult, VarianceReduction,
 };
 use anyhow::{Context, Result};
 use clap::Args;
+use clap_complete::ArgValueCandidates;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufWriter, IsTerminal, Write};
+use std::path::PathBuf;
+use std::time::Duration;
 
 /// Arguments for the simulate command
 #[derive(Debug, Args)]
 pub struct SimulateArgs {
-    /// Number of simulation runs (default: 1000)
-    #[arg(long, default_value = "1000")]
-    runs: usize,
+    /// Number of simulation runs (default: 1000, or as many as fit in
+    /// --max-time)
+    #[arg(long)]
+    runs: Option<usize>,
+
+    /// Stop starting runs after this many seconds and report what has run
+    /// (e.g., 5 or 0.5)
+    #[arg(long, value_parser = parse_seconds, conflicts_with = "full_run")]
+    max_time: Option<Duration>,
 
     /// Path to deck configuration file (default: standard 52-card deck)
     #[arg(long)]
     deck: Option<String>,
 
+    /// Official deck to simulate (e.g., "plasma", "abandoned")
+    #[arg(long, add = ArgValueCandidates::new(deck_type_candidates))]
+    deck_type: Option<DeckType>,
+
     /// Comma-separated list of jokers (e.g., "Joker,GreedyJoker")
-    #[arg(long, value_delimiter = ',')]
+    #[arg(long, value_delimiter = ',', add = ArgValueCandidates::new(joker_candidates))]
     jokers: Vec<String>,
 
-    /// Hand size to draw (default: 8)
-    #[arg(long, default_value = "8")]
-    hand_size: usize,
+    /// Comma-separated hand levels (e.g., "Flush=5,Pair=3")
+    #[arg(long, value_delimiter = ',')]
+    hand_levels: Vec<String>,
+
+    /// Hand size to draw (default: 8, or the deck type's hand size)
+    #[arg(long)]
+    hand_size: Option<usize>,
+
+    /// Hands played per simulated round (default: 1, or the deck's hands
+    /// with --full-run)
+    #[arg(long)]
+    hands: Option<u32>,
+
+    /// Discards available per simulated round (default: 0, or the deck's
+    /// and stake's discards with --full-run)
+    #[arg(long)]
+    discards: Option<u32>,
+
+    /// Score each round must reach; rounds stop once it is reached and the
+    /// chance of clearing the blind is reported
+    #[arg(long, conflicts_with = "full_run")]
+    blind_score: Option<u64>,
+
+    /// Boss blind every round is played against (e.g., "The Psychic"); its
+    /// effect applies to every hand and the chance of clearing it is
+    /// reported
+    #[arg(long, add = ArgValueCandidates::new(boss_candidates))]
+    boss: Option<BossBlind>,
+
+    /// Ante whose requirement the boss blind has, used with --boss
+    /// (default: 1)
+    #[arg(long, default_value = "1", requires = "boss")]
+    ante: u32,
+
+    /// Report the chance of a round scoring at least this much
+    #[arg(long, conflicts_with = "full_run")]
+    target: Option<u64>,
+
+    /// Stop before --runs once the 95% interval is narrow enough:
+    /// mean:<fraction of the mean> or target:<width> (e.g., mean:0.02)
+    #[arg(long, conflicts_with = "full_run")]
+    converge: Option<Convergence>,
+
+    /// Deal rounds to narrow the estimates: antithetic (pairs of rounds
+    /// dealt one shuffle from opposite ends) or stratified (every card
+    /// opens the same number of rounds)
+    #[arg(long, conflicts_with_all = ["full_run", "sweep"])]
+    variance_reduction: Option<VarianceReduction>,
+
+    /// Write every run to a .csv or .jsonl file for offline analysis
+    #[arg(long, conflicts_with = "full_run")]
+    export: Option<PathBuf>,
+
+    /// Report how trimming low cards, adding a suit, and adding enhanced
+    /// cards would change the mean score
+    #[arg(long, conflicts_with_all = ["full_run", "export", "converge", "max_time"])]
+    sensitivity: bool,
+
+    /// Simulate over a range of a parameter, given once or twice for a
+    /// grid: hand-size=<a>..<b>, jokers=<a>..<b>, or <hand type>=<a>..<b>
+    /// for its level (e.g., --sweep hand-size=7..10 --sweep flush=1..5)
+    #[arg(
+        long,
+        conflicts_with_all = ["full_run", "sensitivity", "export", "converge", "max_time"]
+    )]
+    sweep: Vec<SweepAxis>,
+
+    /// Simulate complete runs through ante 8 instead of single rounds
+    #[arg(long)]
+    full_run: bool,
+
+    /// Visit the shop after each blind of a full run, buying jokers,
+    /// vouchers, planets, and packs
+    #[arg(long, requires = "full_run")]
+    shop: bool,
+
+    /// JSON file overriding the shop's odds and prices (see ShopWeights),
+    /// used with --shop
+    #[arg(long, requires = "shop")]
+    shop_weights: Option<PathBuf>,
+
+    /// Money never spent in the shop, e.g. to keep earning interest, used
+    /// with --shop (default: 0)
+    #[arg(long, default_value = "0", requires = "shop")]
+    shop_reserve: u32,
+
+    /// Most rerolls bought in one shop while a joker slot is free, used
+    /// with --shop (default: 0)
+    #[arg(long, default_value = "0", requires = "shop")]
+    max_rerolls: u32,
+
+    /// How each play is chosen: exhaustive (default, also optimal-solver),
+    /// greedy, beam:<width>, random, or a player preset: flush-chaser,
+    /// pair-spammer, high-card-preserver
+    #[arg(long, default_value = "exhaustive")]
+    strategy: StrategyArg,
 
     /// Optional seed for reproducible simulations
     #[arg(long)]
     seed: Option<u64>,
 
-    /// Output format: summary (default), detailed, csv
-    #[arg(long, default_value = "summary")]
-    output: OutputFormat,
+    /// Threads to simulate on (default: one per core); results do not
+    /// depend on it
+    #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+    threads: Option<u16>,
+
+    /// Output format: pretty (default), json, yaml, csv, compact; also
+    /// summary (pretty) and detailed (pretty with --detailed)
+    #[arg(long, default_value = "pretty")]
+    output: OutputArg,
+
+    /// Show the configuration, spread, and attrition in full, used with
+    /// --output pretty
+    #[arg(long)]
+    detailed: bool,
+}
+
+/// Output format selected on the command line, which also takes the
+/// "summary" and "detailed" formats simulate had before the shared ones
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct OutputArg {
+    format: OutputFormat,
+    detailed: bool,
 }
 
-/// Output format for the simulate command
+impl std::str::FromStr for OutputArg {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (format, detailed) = match s.trim().to_lowercase().as_str() {
+            "summary" => (OutputFormat::Pretty, false),
+            "detailed" => (OutputFormat::Pretty, true),
+            _ => (s.parse()?, false),
+        };
+        Ok(Self { format, detailed })
+    }
+}
+
+/// Play strategy selected on the command line
 #[derive(Debug, Clone, Copy)]
-enum OutputFormat {
-    Summary,
-    Detailed,
-    Csv,
+enum StrategyArg {
+    Exhaustive,
+    Greedy,
+    BeamSearch(usize),
+    Random,
+    FlushChaser,
+    PairSpammer,
+    HighCardPreserver,
 }
 
-impl std::str::FromStr for OutputFormat {
+impl std::str::FromStr for StrategyArg {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self> {
-        match s.to_lowercase().as_str() {
-            "summary" => Ok(OutputFormat::Summary),
-            "detailed" => Ok(OutputFormat::Detailed),
-            "csv" => Ok(OutputFormat::Csv),
+        let s = s.to_lowercase();
+        if let Some(width) = s.strip_prefix("beam:") {
+            let width = width
+                .parse()
+                .with_context(|| format!("Invalid beam width: {}", width))?;
+            return Ok(StrategyArg::BeamSearch(width));
+        }
+        let normalized: String = s.chars().filter(|c| !matches!(c, ' ' | '_' | '-')).collect();
+        match normalized.as_str() {
+            "exhaustive" | "optimal" | "optimalsolver" => Ok(StrategyArg::Exhaustive),
+            "greedy" => Ok(StrategyArg::Greedy),
+            "random" => Ok(StrategyArg::Random),
+            "flushchaser" => Ok(StrategyArg::FlushChaser),
+            "pairspammer" => Ok(StrategyArg::PairSpammer),
+            "highcardpreserver" => Ok(StrategyArg::HighCardPreserver),
             _ => anyhow::bail!(
-                "Invalid output format: {}. Use 'summary', 'detailed', or 'csv'",
+                "Invalid strategy: {}. Use 'exhaustive', 'greedy', 'beam:<width>', 'random', \
+                 'flush-chaser', 'pair-spammer', or 'high-card-preserver'",
                 s
             ),
         }
     }
 }
 
+/// Parses a positive number of seconds
+pub(crate) fn parse_seconds(s: &str) -> Result<Duration> {
+    let seconds: f64 = s
+        .trim()
+        .parse()
+        .with_context(|| format!("Invalid number of seconds: {}", s))?;
+    if seconds.is_nan() || seconds <= 0.0 {
+        anyhow::bail!("Time limit must be positive: {}", s);
+    }
+    Duration::try_from_secs_f64(seconds).with_context(|| format!("Time limit too large: {}", s))
+}
+
+/// Runs simulated when neither --runs nor --max-time is given
+const DEFAULT_RUNS: usize = 1000;
+
+impl SimulateArgs {
+    /// Fails if the simulation asks for more than `max_runs` rounds or a
+    /// time limit over `max_time`, as one `serve` request may not
+    ///
+    /// A full run counts as one round per blind, and a sweep or
+    /// sensitivity report as its runs once per point or deck.
+    pub(crate) fn check_limits(&self, max_runs: usize, max_time: Duration) -> Result<()> {
+        let (per_run, with) = if self.full_run {
+            (3 * FINAL_ANTE as usize, " with full_run")
+        } else if !self.sweep.is_empty() {
+            let points = self.sweep.iter().map(|axis| axis.values.len()).product();
+            (points, " with this sweep")
+        } else if self.sensitivity {
+            (DeckPerturbation::defaults().len() + 1, " with sensitivity")
+        } else {
+            (1, "")
+        };
+        // Without --runs, only a plain simulation stops at --max-time
+        let runs = match (self.runs, self.max_time) {
+            (Some(runs), _) => Some(runs),
+            (None, Some(_)) if per_run == 1 => None,
+            (None, _) => Some(DEFAULT_RUNS),
+        };
+        if let Some(runs) = runs {
+            anyhow::ensure!(
+                runs.saturating_mul(per_run) <= max_runs,
+                "runs may be at most {}{}",
+                max_runs / per_run.max(1),
+                with
+            );
+        }
+        if let Some(time) = self.max_time {
+            anyhow::ensure!(
+                time <= max_time,
+                "max_time may be at most {}s",
+                max_time.as_secs_f64()
+            );
+        }
+        Ok(())
+    }
+}
+
 /// Runs the simulate command
 pub fn run(args: SimulateArgs) -> Result<()> {
+    run_with(&args, &mut Sink::Print(args.output.format))
+}
+
+/// Runs the simulate command, sending its report to a sink
+pub fn run_with(args: &SimulateArgs, sink: &mut Sink) -> Result<()> {
+    let runs = match (args.runs, args.max_time) {
+        (Some(runs), _) => runs,
+        (None, Some(_)) => usize::MAX,
+        (None, None) => DEFAULT_RUNS,
+    };
+
     // Load or create deck
     let deck = if let Some(deck_path) = &args.deck {
         let deck_config = DeckConfig::from_file(deck_path)
             .with_context(|| format!("Failed to load deck config from {}", deck_path))?;
         deck_config.to_cards()?
+    } else if let Some(deck_type) = args.deck_type {
+        deck_type.create_cards(args.seed.unwrap_or_default())
     } else {
         create_standard_deck()
     };
 
-    // Parse jokers (for now, empty)
-    let jokers = Vec::new(); // TODO: Parse joker names
+    let mut run = GameRunState::new(args.deck_type.unwrap_or_default(), Stake::default())
+        .with_deck(deck)
+        .with_jokers(super::solve::parse_jokers(&args.jokers)?)
+        .with_hand_levels(HandLevels::from_entries(&args.hand_levels)?);
+    if !args.full_run {
+        run.hands_remaining = 1;
+        run.discards_remaining = 0;
+    }
+    if let Some(hands) = args.hands {
+        run.hands_remaining = hands;
+    }
+    if let Some(discards) = args.discards {
+        run.discards_remaining = discards;
+    }
+    if let Some(boss) = args.boss {
+        run.ante = args.ante;
+        run.boss_blind = Some(boss);
+    }
+
+    // Create solver and simulator
+    let solver = Solver::new(run.calculator());
+    let simulator = match args.strategy {
+        StrategyArg::Exhaustive => Simulator::new(solver).with_strategy(Exhaustive),
+        StrategyArg::Greedy => Simulator::new(solver).with_strategy(Greedy),
+        StrategyArg::BeamSearch(width) => Simulator::new(solver).with_strategy(BeamSearch { width }),
+        StrategyArg::Random => {
+            Simulator::new(solver).with_strategy(Random::new(args.seed.unwrap_or_default()))
+        }
+        StrategyArg::FlushChaser => Simulator::new(solver).with_strategy(FlushChaser),
+        StrategyArg::PairSpammer => Simulator::new(solver).with_strategy(PairSpammer),
+        StrategyArg::HighCardPreserver => Simulator::new(solver).with_strategy(HighCardPreserver),
+    };
+    let simulator = simulator.with_parallelism(args.threads.map(usize::from));
+
+    if args.full_run {
+        let simulator = match args.shop {
+            true => simulator.with_shop(shop_model(args)?),
+            false => simulator,
+        };
+        if !args.output.format.is_structured() {
+            outln!("Running {} full runs...", runs);
+        }
+        let result = simulator.simulate_runs(&run, runs, args.seed);
+        return sink.emit(&RunsReport { result: &result });
+    }
 
-    // Create score calculator, solver, and simulator
-    let calculator = ScoreCalculator::new(jokers);
-    let solver = Solver::new(calculator);
-    let simulator = Simulator::new(solver);
+    if !args.sweep.is_empty() {
+        let mut axes = args.sweep.clone().into_iter();
+        let (rows, columns) = (axes.next().expect("sweep is not empty"), axes.next());
+        if axes.next().is_some() {
+            anyhow::bail!("--sweep can be given at most twice");
+        }
+        if !args.output.format.is_structured() {
+            outln!("Running {} simulations per point...", runs);
+        }
+        let mut sweep =
+            ParameterSweep::new(run).with_simulator(simulator).with_runs(runs, args.seed);
+        if let Some(hand_size) = args.hand_size {
+            sweep = sweep.with_hand_size(hand_size);
+        }
+        let result = sweep.sweep(rows, columns);
+        return sink.emit(&SweepReport { result: &result });
+    }
 
     // Configure simulation
-    let config = SimulationConfig {
-        deck,
-        hand_size: args.hand_size,
-        num_runs: args.runs,
-        seed: args.seed,
-    };
+    let mut config = run.simulation_config(runs, args.seed);
+    if let Some(hand_size) = args.hand_size {
+        config.hand_size = hand_size;
+    }
+    config.parallelism = args.threads.map(usize::from);
+    config.blind_score = args.blind_score.map(Score::from);
+    config.blind = run
+        .boss_blind
+        .map(|boss| BlindConfig::for_boss(boss, run.blind_requirement(BlindType::Boss)));
+    let blind = config.blind.clone();
+    config.target_score = args.target.map(Score::from);
+    config.convergence = args.converge;
+    config.variance_reduction = args.variance_reduction;
+    config.max_duration = args.max_time;
+    config.record_runs = args.export.is_some();
+    let export_format = args.export.as_deref().map(RawFormat::from_path).transpose()?;
+    if matches!(args.converge, Some(Convergence::Target(_))) && args.target.is_none() {
+        anyhow::bail!("--converge target:<width> needs --target");
+    }
+    if !args.output.format.is_structured() {
+        config.progress = Some(Box::new(display_progress));
+    }
+
+    if args.sensitivity {
+        if !args.output.format.is_structured() {
+            outln!("Running {} simulations per deck...", runs);
+        }
+        let results = simulator.deck_sensitivity(&config, &DeckPerturbation::defaults());
+        return sink.emit(&SensitivityReport { results: &results });
+    }
 
     // Run simulation
-    println!("Running {} simulations...", args.runs);
+    match (args.runs, args.max_time) {
+        _ if args.output.format.is_structured() => {}
+        (None, Some(max_time)) => {
+            outln!("Running simulations for {:.1}s...", max_time.as_secs_f64())
+        }
+        (Some(runs), Some(max_time)) => {
+            outln!("Running up to {} simulations for {:.1}s...", runs, max_time.as_secs_f64())
+        }
+        _ => outln!("Running {} simulations...", runs),
+    }
     let result = simulator.simulate(config);
+    let report = RoundsReport { result: &result, args, blind: blind.as_ref() };
+    sink.emit(&report)?;
 
-    // Display results based on output format
-    match args.output {
-        OutputFormat::Summary => display_summary(&result, &args),
-        OutputFormat::Detailed => display_detailed(&result, &args),
-        OutputFormat::Csv => display_csv(&result),
+    if let (Some(path), Some(format)) = (&args.export, export_format) {
+        let file = File::create(path).with_context(|| format!("Failed to create {:?}", path))?;
+        let mut writer = BufWriter::new(file);
+        result.write_raw(&mut writer, format)?;
+        writer.flush()?;
+        eoutln!("📝 Wrote {} runs to {}", result.runs.len(), path.display());
     }
 
     Ok(())
 }
 
+/// Builds the shop full runs visit, with weights from --shop-weights if
+/// given
+fn shop_model(args: &SimulateArgs) -> Result<ShopModel> {
+    let mut shop = ShopModel::new()
+        .with_reserve(args.shop_reserve)
+        .with_max_rerolls(args.max_rerolls);
+    if let Some(path) = &args.shop_weights {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read shop weights from {:?}", path))?;
+        let weights: ShopWeights =
+            serde_json::from_str(&contents).context("Failed to parse shop weights JSON")?;
+        shop = shop.with_weights(weights);
+    }
+    Ok(shop)
+}
+
+/// Draws a progress bar on stderr, replacing the previous one
+fn display_progress(progress: SimProgress) {
+    const WIDTH: usize = 30;
+    let filled = (progress.fraction() * WIDTH as f64).round() as usize;
+    // Over a time budget the run count is open-ended
+    let runs = match progress.budget {
+        Some(_) if progress.total == usize::MAX => progress.completed.to_string(),
+        _ => format!("{}/{}", progress.completed, progress.total),
+    };
+    eout!(
+        "\r  [{}{}] {:>3.0}% ({})  mean {}  ETA {}s ",
+        "#".repeat(filled),
+        "-".repeat(WIDTH - filled),
+        progress.fraction() * 100.0,
+        runs,
+        format_score(progress.mean_score),
+        progress.eta.as_secs()
+    );
+    if progress.finished {
+        eoutln!();
+    }
+}
+
+/// Round simulation results
+#[derive(Serialize)]
+#[serde(transparent)]
+struct RoundsReport<'a> {
+    result: &'a SimulationResult,
+    #[serde(skip)]
+    args: &'a SimulateArgs,
+    #[serde(skip)]
+    blind: Option<&'a BlindConfig>,
+}
+
+impl Report for RoundsReport<'_> {
+    fn print_pretty(&self) {
+        match self.args.detailed || self.args.output.detailed {
+            true => display_detailed(self.result, self.args, self.blind),
+            false => display_summary(self.result, self.args),
+        }
+    }
+
+    fn print_compact(&self) {
+        display_compact(self.result);
+    }
+}
+
+/// Full-run simulation results
+#[derive(Serialize)]
+#[serde(transparent)]
+pub(crate) struct RunsReport<'a> {
+    pub(crate) result: &'a RunSimulationResult,
+}
+
+impl Report for RunsReport<'_> {
+    fn print_pretty(&self) {
+        display_runs(self.result);
+    }
+
+    fn print_compact(&self) {
+        let result = self.result;
+        outln!(
+            "Win Rate: {:.1}% | Final Ante: {:.1} | Final Money: ${:.1} | Runs: {}",
+            result.win_rate * 100.0,
+            result.average_final_ante,
+            result.mean_final_money,
+            result.num_runs
+        );
+    }
+}
+
+/// Sweep results
+#[derive(Serialize)]
+#[serde(transparent)]
+struct SweepReport<'a> {
+    result: &'a SweepResult,
+}
+
+impl Report for SweepReport<'_> {
+    fn print_pretty(&self) {
+        display_sweep(self.result);
+    }
+
+    /// One row per point, named after the swept parameters
+    fn records(&self) -> Result<Vec<serde_json::Value>> {
+        let result = self.result;
+        let mut records = Vec::new();
+        for (row, cells) in result.rows.values.iter().zip(&result.cells) {
+            for (i, cell) in cells.iter().enumerate() {
+                let mut record = serde_json::Map::new();
+                record.insert(result.rows.parameter.to_string(), (*row).into());
+                if let Some(columns) = &result.columns {
+                    record.insert(columns.parameter.to_string(), columns.values[i].into());
+                }
+                record.insert("mean_score".to_string(), cell.mean_score.into());
+                record.insert("median_score".to_string(), cell.median_score.value().into());
+                record.insert("percentile_95".to_string(), cell.percentile_95.value().into());
+                records.push(record.into());
+            }
+        }
+        Ok(records)
+    }
+}
+
+/// Deck sensitivity results
+#[derive(Serialize)]
+#[serde(transparent)]
+struct SensitivityReport<'a> {
+    results: &'a [DeckSensitivity],
+}
+
+impl Report for SensitivityReport<'_> {
+    fn print_pretty(&self) {
+        display_sensitivity(self.results);
+    }
+
+    /// One row per perturbation, named as in the pretty output
+    fn records(&self) -> Result<Vec<serde_json::Value>> {
+        #[derive(Serialize)]
+        struct SensitivityRecord<'a> {
+            perturbation: String,
+            mean_score: f64,
+            #[serde(flatten)]
+            impact: &'a crate::core::PairedDifference,
+        }
+
+        self.results
+            .iter()
+            .map(|result| {
+                Ok(serde_json::to_value(SensitivityRecord {
+                    perturbation: result.perturbation.to_string(),
+                    mean_score: result.mean_score,
+                    impact: &result.impact,
+                })?)
+            })
+            .collect()
+    }
+}
+
 /// Displays results in summary format
-fn display_summary(result: &crate::core::simulator::SimulationResult, args: &SimulateArgs) {
-    println!("\n📊 Simulation Results ({} runs):", result.num_runs);
-    println!("  Mean Score:   {:.2}", result.mean_score);
-    println!("  Median Score: {}", result.median_score);
-    println!("  Min Score:    {}", result.min_score);
-    println!("  Max Score:    {}", result.max_score);
-    println!("\n  Percentiles:");
-    println!("    25th: {}", result.percentile_25);
-    println!("    75th: {}", result.percentile_75);
-    println!("    95th: {}", result.percentile_95);
+fn display_summary(result: &SimulationResult, args: &SimulateArgs) {
+    outln!("\n📊 Simulation Results ({} runs):", result.num_runs);
+    display_statistics(result, args.boss.map_or("Blind", |boss| boss.name()));
 
     if let Some(seed) = args.seed {
-        println!("\n  🎲 Seed: {} (reproducible)", seed);
+        outln!("\n  🎲 Seed: {} (reproducible)", seed);
     }
 }
 
-/// Displays results in detailed format
-fn display_detailed(result: &crate::core::simulator::SimulationResult, args: &SimulateArgs) {
-    println!("\n📊 Detailed Simulation Results");
-    println!("═══════════════════════════════");
-    println!("Configuration:");
-    println!("  Runs:       {}", result.num_runs);
-    println!("  Hand Size:  {}", args.hand_size);
-    if let Some(seed) = args.seed {
-        println!("  Seed:       {}", seed);
-    }
-    println!("\nScore Statistics:");
-    println!("  Mean:       {:.2}", result.mean_score);
-    println!("  Median:     {}", result.median_score);
-    println!("  Min:        {}", result.min_score);
-    println!("  Max:        {}", result.max_score);
-    println!("  Range:      {}", result.max_score - result.min_score);
-    println!("\nPercentile Distribution:");
-    println!("  25th:       {}", result.percentile_25);
-    println!("  50th:       {} (median)", result.median_score);
-    println!("  75th:       {}", result.percentile_75);
-    println!("  95th:       {}", result.percentile_95);
-    println!("\nInterquartile Range (IQR):");
-    println!("  IQR:        {}", result.percentile_75 - result.percentile_25);
-}
-
-/// Displays results in CSV format
-fn display_csv(result: &crate::core::simulator::SimulationResult) {
-    println!("num_runs,mean_score,median_score,min_score,max_score,p25,p75,p95");
-    println!(
-        "{},{:.2},{},{},{},{},{},{}",
-        result.num_runs,
-        result.mean_score,
+/// Displays results on one line
+pub(crate) fn display_compact(result: &SimulationResult) {
+    out!(
+        "Mean: {} | Median: {} | P95: {} | Runs: {}",
+        format_score(result.mean_score),
         result.median_score,
-        result.min_score,
-        result.max_score,
-        result.percentile_25,
-        result.percentile_75,
-        result.percentile_95
+        result.percentile_95,
+        result.num_runs
     );
+    match result.blind_clear_rate {
+        Some(rate) => outln!(" | Cleared: {:.1}%", rate * 100.0),
+        None => outln!(),
+    }
+}
+
+/// Displays the score statistics and distribution, and the chances of
+/// reaching the target and clearing the blind (named `blind`)
+pub(crate) fn display_statistics(result: &SimulationResult, blind: &str) {
+    outln!("  Mean Score:   {}", format_score(result.mean_score));
+    outln!("  Median Score: {}", result.median_score);
+    outln!("  Min Score:    {}", result.min_score);
+    outln!("  Max Score:    {}", result.max_score);
+    outln!("\n  Percentiles:");
+    outln!("    25th: {}", result.percentile_25);
+    outln!("    75th: {}", result.percentile_75);
+    outln!("    95th: {}", result.percentile_95);
+    if std::io::stdout().is_terminal() && result.histogram.len() > 1 {
+        outln!("\n  Distribution:");
+        display_histogram(&result.histogram, "    ");
+    }
+
+    match result.converged {
+        Some(true) => outln!("\n  ⏱️  Converged after {} runs", result.num_runs),
+        Some(false) => outln!("\n  ⏱️  Tolerance not met after {} runs", result.num_runs),
+        None if result.out_of_time == Some(true) => {
+            outln!("\n  ⏱️  Time limit reached after {} runs", result.num_runs)
+        }
+        None => {}
+    }
+
+    if let Some(target) = &result.target {
+        outln!(
+            "\n  🎯 P(score ≥ {}): {:.1}% (95% CI {:.1}–{:.1}%)",
+            target.target,
+            target.probability * 100.0,
+            target.lower * 100.0,
+            target.upper * 100.0
+        );
+    }
+
+    if let Some(rate) = result.blind_clear_rate {
+        out!("\n  🎯 {} Cleared: {:.1}%", blind, rate * 100.0);
+        match result.mean_hands_to_clear {
+            Some(hands) => outln!(" ({:.2} hands on average)", hands),
+            None => outln!(),
+        }
+    }
+
+    if result.expected_glass_destroyed > 0.0 {
+        outln!("\n  🔨 Glass Attrition:");
+        outln!("    Expected per hand: {:.3}", result.expected_glass_destroyed);
+        outln!("    Destroyed (sampled): {}", result.glass_destroyed);
+    }
+
+    let consumables = &result.consumables;
+    if !consumables.planets.is_empty() || consumables.tarots > 0 {
+        outln!("\n  🪐 Consumables Created:");
+        outln!("    Planets (Blue seals): {}", consumables.planets.len());
+        outln!("    Tarots (Purple seals): {}", consumables.tarots);
+    }
+}
+
+/// Displays results in detailed format
+fn display_detailed(
+    result: &SimulationResult,
+    args: &SimulateArgs,
+    blind: Option<&BlindConfig>,
+) {
+    outln!("\n📊 Detailed Simulation Results");
+    outln!("═══════════════════════════════");
+    outln!("Configuration:");
+    outln!("  Runs:       {}", result.num_runs);
+    if let Some(converged) = result.converged {
+        let reason = match result.out_of_time {
+            Some(true) => "no (time limit reached)",
+            _ => "no (run limit reached)",
+        };
+        outln!("  Converged:  {}", if converged { "yes" } else { reason });
+    }
+    if result.converged.is_none() && result.out_of_time == Some(true) {
+        outln!("  Stopped:    time limit reached");
+    }
+    if let Some(hand_size) = args.hand_size {
+        outln!("  Hand Size:  {}", hand_size);
+    }
+    if let Some(deck_type) = args.deck_type {
+        outln!("  Deck:       {:?}", deck_type);
+    }
+    let (hands, discards) = (args.hands.unwrap_or(1), args.discards.unwrap_or(0));
+    if hands > 1 || discards > 0 {
+        outln!("  Round:      {} hands, {} discards", hands, discards);
+    }
+    if let Some(seed) = args.seed {
+        outln!("  Seed:       {}", seed);
+    }
+    outln!("\nScore Statistics:");
+    outln!("  Mean:       {}", format_score(result.mean_score));
+    outln!("  Median:     {}", result.median_score);
+    outln!("  Min:        {}", result.min_score);
+    outln!("  Max:        {}", result.max_score);
+    outln!("  Range:      {}", result.max_score - result.min_score);
+    outln!("\nPercentile Distribution:");
+    outln!("  25th:       {}", result.percentile_25);
+    outln!("  50th:       {} (median)", result.median_score);
+    outln!("  75th:       {}", result.percentile_75);
+    outln!("  95th:       {}", result.percentile_95);
+    outln!("\nInterquartile Range (IQR):");
+    outln!("  IQR:        {}", result.percentile_75 - result.percentile_25);
+    if std::io::stdout().is_terminal() && result.histogram.len() > 1 {
+        outln!("\nScore Histogram:");
+        display_histogram(&result.histogram, "  ");
+    }
+    if let Some(target) = &result.target {
+        outln!("\nTarget ({}):", target.target);
+        outln!("  Reached:       {:.1}%", target.probability * 100.0);
+        outln!("  95% CI:        {:.1}% – {:.1}%", target.lower * 100.0, target.upper * 100.0);
+    }
+    let blind_score = args.blind_score.or(blind.map(|blind| blind.score_required));
+    if let (Some(rate), Some(blind_score)) = (result.blind_clear_rate, blind_score) {
+        match blind.and_then(|blind| blind.name.as_deref()) {
+            Some(name) => outln!("\n{} ({}):", name, Score::from(blind_score)),
+            None => outln!("\nBlind ({}):", Score::from(blind_score)),
+        }
+        outln!("  Cleared:       {:.1}%", rate * 100.0);
+        if let Some(hands) = result.mean_hands_to_clear {
+            outln!("  Hands to clear: {:.2} (average)", hands);
+        }
+    }
+    outln!("\nGlass Attrition:");
+    outln!("  Expected/hand: {:.3}", result.expected_glass_destroyed);
+    outln!("  Destroyed:     {}", result.glass_destroyed);
+    outln!("\nConsumables Created:");
+    outln!("  Planets:       {}", result.consumables.planets.len());
+    outln!("  Tarots:        {}", result.consumables.tarots);
+}
+
+/// Displays the score histogram as horizontal bars, one per bin, scaled
+/// so the fullest bin spans `HISTOGRAM_WIDTH` columns
+fn display_histogram(histogram: &[HistogramBin], indent: &str) {
+    const HISTOGRAM_WIDTH: usize = 40;
+    const EIGHTHS: [char; 8] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉', '█'];
+
+    let labels: Vec<String> = histogram
+        .iter()
+        .map(|bin| match bin.lower == bin.upper {
+            true => bin.lower.to_string(),
+            false => format!("{}–{}", bin.lower, bin.upper),
+        })
+        .collect();
+    let label_width = labels.iter().map(|label| label.chars().count()).max().unwrap_or(0);
+    let fullest = histogram.iter().map(|bin| bin.count).max().unwrap_or(0).max(1);
+    for (bin, label) in histogram.iter().zip(&labels) {
+        let eighths = bin.count * HISTOGRAM_WIDTH * 8 / fullest;
+        let mut bar = "█".repeat(eighths / 8);
+        if !eighths.is_multiple_of(8) {
+            bar.push(EIGHTHS[eighths % 8 - 1]);
+        }
+        outln!(
+            "{}{:>width$} {:<bar_width$} {}",
+            indent,
+            label,
+            bar,
+            bin.count,
+            width = label_width,
+            bar_width = HISTOGRAM_WIDTH
+        );
+    }
+}
+
+/// Displays the mean score at each point of a sweep as a table
+fn display_sweep(result: &SweepResult) {
+    let means = result.mean_scores();
+    match &result.columns {
+        None => {
+            outln!("\n📈 Mean Score by {}:", result.rows.parameter);
+            for (value, row) in result.rows.values.iter().zip(&means) {
+                outln!("  {:>4}: {:>12}", value, format_score(row[0]));
+            }
+        }
+        Some(columns) => {
+            outln!(
+                "\n📈 Mean Score by {} (rows) and {} (columns):",
+                result.rows.parameter, columns.parameter
+            );
+            let header: Vec<String> = columns.values.iter().map(|v| format!("{:>12}", v)).collect();
+            outln!("  {:>4}  {}", "", header.join(""));
+            for (value, row) in result.rows.values.iter().zip(&means) {
+                let cells: Vec<String> = row.iter().map(|m| format!("{:>12}", format_score(*m))).collect();
+                outln!("  {:>4}  {}", value, cells.join(""));
+            }
+        }
+    }
+}
+
+/// Displays the score impact of each deck perturbation
+fn display_sensitivity(results: &[DeckSensitivity]) {
+    outln!("\n🃏 Deck Sensitivity:");
+    for result in results {
+        let impact = &result.impact;
+        let marker = if impact.is_significant() { "" } else { " (not significant)" };
+        outln!(
+            "  {:<24} {:>+10.1} mean ({:+.1} to {:+.1}){}",
+            result.perturbation.to_string(),
+            impact.mean,
+            impact.lower,
+            impact.upper,
+            marker
+        );
+    }
+}
+
+/// Displays full-run results
+pub(crate) fn display_runs(result: &RunSimulationResult) {
+    outln!("\n🏁 Full Run Results ({} runs):", result.num_runs);
+    outln!("  Win Rate:     {:.1}%", result.win_rate * 100.0);
+    if let Some(ante) = result.average_losing_ante {
+        outln!("  Losing Ante:  {:.1} (average)", ante);
+    }
+    outln!("  Final Ante:   {:.1} (average)", result.average_final_ante);
+    outln!("  Final Money:  ${:.1} (average)", result.mean_final_money);
+    if let Some(shop) = &result.shop {
+        outln!("\n  🛒 Shop (average per run):");
+        outln!("    Spent:      ${:.1}", shop.mean_spent);
+        outln!("    Purchases:  {:.1}", shop.mean_purchases);
+        outln!("    Rerolls:    {:.1}", shop.mean_rerolls);
+        outln!("    Jokers:     {:.1} at the end", shop.mean_final_jokers);
+    }
+
+    outln!("\n  Antes Beaten:");
+    for (ante, fraction) in &result.ante_survival {
+        outln!("    Ante {}: {:.1}%", ante, fraction * 100.0);
+    }
+
+    if !result.antes.is_empty() {
+        outln!("\n  Per Ante:");
+        outln!(
+            "    {:>4} {:>6} {:>7} {:>7} {:>7} {:>7} {:>7}  Deadliest Boss",
+            "Ante", "Runs", "Cleared", "Money", "Small", "Big", "Boss"
+        );
+        for ante in result.antes.iter().filter(|ante| ante.runs > 0) {
+            let margin = |blind_type: BlindType| {
+                ante.blind_margins
+                    .iter()
+                    .find(|(played, _)| *played == blind_type)
+                    .map_or("-".to_string(), |(_, margin)| format!("{:+.0}%", margin * 100.0))
+            };
+            let deadliest = ante
+                .deadliest_boss
+                .map_or("-".to_string(), |(boss, losses)| format!("{} ({})", boss.name(), losses));
+            outln!(
+                "    {:>4} {:>6} {:>6.1}% {:>7} {:>7} {:>7} {:>7}  {}",
+                ante.ante,
+                ante.runs,
+                ante.clear_rate * 100.0,
+                format!("${:.1}", ante.mean_money),
+                margin(BlindType::Small),
+                margin(BlindType::Big),
+                margin(BlindType::Boss),
+                deadliest
+            );
+        }
+        outln!("    (Small/Big/Boss: mean score margin over each blind's requirement)");
+    }
+
+    let lost = result.failures.iter().map(|failure| failure.runs).sum::<usize>();
+    if lost > 0 {
+        outln!("\n  💀 Runs Lost To:");
+        for failure in &result.failures {
+            let blind = match failure.boss {
+                Some(boss) => boss.name().to_string(),
+                None => format!("{:?} Blind", failure.blind_type),
+            };
+            outln!(
+                "    {:<20} {:>6} ({:.1}% of losses)",
+                blind,
+                failure.runs,
+                failure.runs as f64 / lost as f64 * 100.0
+            );
+        }
+    }
+
+    outln!("\n  Best Hand Reached:");
+    for (score, fraction) in &result.milestones {
+        outln!("    {:>9}: {:.1}%", score.to_string(), fraction * 100.0);
+    }
 }
 
 #[cfg(test)]
@@ -166,19 +897,62 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_output_format_parsing() {
-        assert!(matches!(
-            "summary".parse::<OutputFormat>().unwrap(),
-            OutputFormat::Summary
-        ));
-        assert!(matches!(
-            "detailed".parse::<OutputFormat>().unwrap(),
-            OutputFormat::Detailed
-        ));
-        assert!(matches!(
-            "csv".parse::<OutputFormat>().unwrap(),
-            OutputFormat::Csv
-        ));
-        assert!("invalid".parse::<OutputFormat>().is_err());
+    fn test_output_flags() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            simulate: SimulateArgs,
+        }
+
+        let cli = Cli::parse_from(["jimbo", "--output", "yaml"]);
+        assert_eq!(cli.simulate.output.format, OutputFormat::Yaml);
+        assert!(!cli.simulate.detailed);
+        let cli = Cli::parse_from(["jimbo", "--detailed"]);
+        assert_eq!(cli.simulate.output.format, OutputFormat::Pretty);
+        assert!(cli.simulate.detailed);
+
+        // The formats simulate had before --output was shared still parse
+        let output = |format| Cli::parse_from(["jimbo", "--output", format]).simulate.output;
+        assert_eq!(output("summary"), output("pretty"));
+        assert_eq!(output("detailed").format, OutputFormat::Pretty);
+        assert!(output("detailed").detailed);
+        assert!(Cli::try_parse_from(["jimbo", "--output", "table"]).is_err());
+    }
+
+    #[test]
+    fn test_check_limits() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            simulate: SimulateArgs,
+        }
+
+        let check = |flags: &[&str]| {
+            let mut argv = vec!["jimbo"];
+            argv.extend(flags);
+            let simulate = Cli::parse_from(argv).simulate;
+            let result = simulate.check_limits(10_000, Duration::from_secs(60));
+            result.map_err(|e| e.to_string())
+        };
+        assert!(check(&["--runs", "10000"]).is_ok());
+        assert!(check(&["--max-time", "60"]).is_ok());
+        assert_eq!(check(&["--runs", "10001"]).unwrap_err(), "runs may be at most 10000");
+        assert!(check(&["--max-time", "61"]).is_err());
+
+        // Full runs, sweep points, and perturbed decks each multiply the runs
+        assert!(check(&["--full-run", "--runs", "416"]).is_ok());
+        assert_eq!(
+            check(&["--full-run", "--runs", "417"]).unwrap_err(),
+            "runs may be at most 416 with full_run"
+        );
+        assert!(check(&["--sweep", "hand-size=1..10", "--runs", "1000"]).is_ok());
+        let grid = ["--sweep", "hand-size=1..10", "--sweep", "flush=1..2"];
+        assert!(check(&grid).unwrap_err().contains("at most 500 with this sweep"));
+        assert!(check(&["--sensitivity", "--runs", "1250"]).is_ok());
+        assert!(check(&["--sensitivity", "--runs", "1251"]).is_err());
     }
 }
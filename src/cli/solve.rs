@@ -3,124 +3,759 @@
 //! This module implements the `solve` command which finds the optimal
 //! play from a given hand.
 
-use crate::config::DeckConfig;
-use crate::core::{Card, Joker, Rank, ScoreCalculator, Solver, Suit};
+use super::completions::{boss_candidates, joker_candidates};
+use super::output::{OutputFormat, RecordWriter, Report, Sink};
+use super::simulate::parse_seconds;
+use crate::config::{DeckConfig, GameState};
+use crate::core::planner::DEFAULT_ROLLOUTS;
+use crate::core::search::DEFAULT_TIME_LIMIT;
+use crate::core::solver::{SolverResult, DEFAULT_DISCARD_SAMPLES};
+use crate::core::{
+    blind_requirement, boss_requirement, BlindType, BossBlind, Card, Consumable, ConsumableUse,
+    DiscardResult, Edition, Enhancement, GameRunState, Hand, HandLevels, HandType, Joker,
+    MaxScoreResult, MaxScoreSearch, Objective, Rank, RoundPlan, RoundPlanner, Score,
+    ScoreCalculator, ScoreResult, SolveConstraints, Solver, Stake, Suit,
+};
 use anyhow::{Context, Result};
 use clap::Args;
+use clap_complete::ArgValueCandidates;
+use serde::Serialize;
+use std::process::ExitCode;
+use std::time::Duration;
+
+/// Exit code when the best play does not beat the blind (errors exit
+/// with 1)
+const EXIT_BLIND_NOT_BEATEN: u8 = 2;
+
+/// Chance of beating the blind over the round at which a planned round
+/// counts as beating it
+const ROUND_BEAT_CHANCE: f64 = 0.5;
 
 /// Arguments for the solve command
 #[derive(Debug, Args)]
 pub struct SolveArgs {
-    /// Your current hand (space-separated, e.g., "AH KH QH JH 10H")
-    #[arg(long, required = true)]
-    hand: String,
+    /// Your current hand (e.g., "AH KH QH JH 10H", or "Ah,Kh,Qh,Jh,Th");
+    /// add modifiers after colons (e.g., "AH:gold", "KS:steel:foil",
+    /// "10D:wild:red_seal") or use "stone" for a Stone card
+    #[arg(long, required_unless_present_any = ["batch", "search"], conflicts_with = "batch")]
+    hand: Option<String>,
+
+    /// Solve every hand in a file, one per line in --hand's format ("-"
+    /// reads stdin), with the same jokers and state; prints a table, or
+    /// one record per hand with --output json, yaml, or csv
+    #[arg(long, conflicts_with = "consumables")]
+    batch: Option<String>,
+
+    /// Search the whole deck for the highest-scoring hand, as in endless
+    /// runs: the play, the cards to hold, the joker order, and the
+    /// consumables to use first
+    #[arg(long, conflicts_with_all = ["hand", "batch", "deck_remaining"])]
+    search: bool,
+
+    /// Seconds --search may run before returning its best so far
+    /// (default: 5)
+    #[arg(
+        long,
+        value_parser = parse_seconds,
+        requires = "search",
+        conflicts_with_all = ["hand", "batch"]
+    )]
+    max_time: Option<Duration>,
 
     /// Path to deck configuration file (JSON)
     #[arg(long)]
     deck: Option<String>,
 
+    /// Path to a game state file (JSON) to take the jokers, hand levels,
+    /// consumables, blind, and deck from; flags given as well take
+    /// precedence (hand levels are merged)
+    #[arg(long)]
+    game_state: Option<String>,
+
     /// Comma-separated list of jokers (e.g., "Joker,GreedyJoker")
-    #[arg(long, value_delimiter = ',')]
+    #[arg(long, value_delimiter = ',', add = ArgValueCandidates::new(joker_candidates))]
     jokers: Vec<String>,
 
+    /// Comma-separated hand levels (e.g., "Flush=5,Pair=3")
+    #[arg(long, value_delimiter = ',')]
+    hand_levels: Vec<String>,
+
+    /// Comma-separated consumables to consider using before playing
+    /// (e.g., "The Star,Jupiter")
+    #[arg(long, value_delimiter = ',')]
+    consumables: Vec<String>,
+
+    /// Comma-separated cards every play must include (e.g., "KH" for
+    /// Cerulean Bell)
+    #[arg(long, value_delimiter = ',')]
+    force_cards: Vec<String>,
+
+    /// Only consider plays of exactly this many cards
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=5))]
+    card_count: Option<u8>,
+
+    /// What to rank plays by: expected (default), median, beat-blind, or a
+    /// risk-averse lower percentile such as p10
+    #[arg(long, default_value = "expected")]
+    objective: Objective,
+
+    /// Points each dollar earned by a play is worth when ranking plays
+    #[arg(long, default_value = "0")]
+    money_weight: f64,
+
     /// Required score to beat the blind
-    #[arg(long)]
+    #[arg(long, conflicts_with = "blind")]
     blind_score: Option<u64>,
 
+    /// Blind to beat (small, big, boss); looks up the required score
+    #[arg(long)]
+    blind: Option<BlindType>,
+
+    /// Boss blind in effect (e.g., "The Psychic", or part of a name such
+    /// as "psychic"); its restrictions and debuffs apply to every play, and
+    /// its size to --blind boss
+    #[arg(long, add = ArgValueCandidates::new(boss_candidates))]
+    boss: Option<BossBlind>,
+
+    /// Current ante, used with --blind (default: 1)
+    #[arg(long, default_value = "1", requires = "blind")]
+    ante: u32,
+
+    /// Stake difficulty, used with --blind (default: white)
+    #[arg(long, default_value = "white", requires = "blind")]
+    stake: Stake,
+
+    /// Hands left in the round, including this one (default: 1)
+    #[arg(long, visible_alias = "hands-left", default_value = "1")]
+    hands: u32,
+
+    /// Discards left in the round (default: 0)
+    #[arg(long, visible_alias = "discards-left", default_value = "0")]
+    discards: u32,
+
+    /// Path to a deck configuration file (JSON) of the cards left to draw;
+    /// recommends playing now or discarding and redrawing, with the
+    /// expected value of both, and plans the round against the blind
+    #[arg(long, conflicts_with = "batch")]
+    deck_remaining: Option<String>,
+
+    /// Only extend the N best plays of each size (faster for large hands)
+    #[arg(long)]
+    beam_width: Option<usize>,
+
+    /// Score every play, without pruning or beam search
+    #[arg(long, conflicts_with = "beam_width")]
+    exhaustive: bool,
+
     /// Optional seed for reproducible results
     #[arg(long)]
     seed: Option<u64>,
 
-    /// Output format: pretty (default), json, compact
+    /// Output format: pretty (default), json, yaml, csv, compact
     #[arg(long, default_value = "pretty")]
     output: OutputFormat,
 
+    /// Print only the best play's score, as a plain number
+    #[arg(short, long, conflicts_with_all = ["output", "batch"])]
+    quiet: bool,
+
     /// Show top N alternative plays (default: 3)
     #[arg(long, default_value = "3")]
     show_alternatives: usize,
-}
 
-/// Output format for the solve command
-#[derive(Debug, Clone, Copy)]
-enum OutputFormat {
-    Pretty,
-    Json,
-    Compact,
+    /// Show only the best play of each hand type as alternatives
+    #[arg(long)]
+    per_hand_type: bool,
 }
 
-impl std::str::FromStr for OutputFormat {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self> {
-        match s.to_lowercase().as_str() {
-            "pretty" => Ok(OutputFormat::Pretty),
-            "json" => Ok(OutputFormat::Json),
-            "compact" => Ok(OutputFormat::Compact),
-            _ => anyhow::bail!("Invalid output format: {}. Use 'pretty', 'json', or 'compact'", s),
+impl SolveArgs {
+    /// Fails if --search may run longer than `max_time`, as one `serve`
+    /// request may not
+    pub(crate) fn check_limits(&self, max_time: Duration) -> Result<()> {
+        if let Some(time) = self.max_time {
+            anyhow::ensure!(
+                time <= max_time,
+                "max_time may be at most {}s",
+                max_time.as_secs_f64()
+            );
         }
+        Ok(())
     }
 }
 
 /// Runs the solve command
-pub fn run(args: SolveArgs) -> Result<()> {
-    // Parse the hand
-    let cards = parse_hand(&args.hand)?;
+///
+/// Exits with 0 if the best play beats the blind (or no blind was given)
+/// and with 2 if it does not, so scripts can branch on the result. With
+/// --deck-remaining, the round is planned instead, and it exits with 0 if
+/// the blind is beaten in at least half the planned rounds.
+pub fn run(args: SolveArgs) -> Result<ExitCode> {
+    let beats_blind = run_with(&args, &mut Sink::Print(args.output))?;
+    Ok(match beats_blind {
+        Some(false) => ExitCode::from(EXIT_BLIND_NOT_BEATEN),
+        _ => ExitCode::SUCCESS,
+    })
+}
+
+/// Runs the solve command, sending its report to a sink
+///
+/// Returns whether the best play (or, with --deck-remaining, the planned
+/// round) beats the blind, if a blind was given (and a single hand was
+/// solved).
+pub fn run_with(args: &SolveArgs, sink: &mut Sink) -> Result<Option<bool>> {
+    let state = match &args.game_state {
+        Some(path) => Some(
+            GameState::from_file(path)
+                .with_context(|| format!("Failed to load game state from {}", path))?,
+        ),
+        None => None,
+    };
+    let run_state = state.as_ref().map(GameState::to_run_state).transpose()?;
+    let calculator = build_calculator(args, run_state.as_ref())?;
+    let blind_score =
+        required_score(args, calculator.boss_blind(), state.as_ref()).map(Score::from);
+
+    let mut constraints = SolveConstraints::new();
+    for card in &args.force_cards {
+        constraints = constraints.with_required_card(parse_card(card)?);
+    }
+    if let Some(count) = args.card_count {
+        constraints = constraints.with_card_count(count.into());
+    }
+
+    let mut solver = Solver::new(calculator)
+        .with_money_weight(args.money_weight)
+        .with_remaining(args.hands, args.discards)
+        .with_beam_width(args.beam_width)
+        .with_exhaustive(args.exhaustive)
+        .with_alternatives(args.show_alternatives)
+        .with_best_per_hand_type(args.per_hand_type)
+        .with_objective(args.objective)
+        .with_constraints(constraints)
+        .with_blind_score(blind_score);
+    if let Some(seed) = args.seed {
+        solver = solver.with_discard_sampling(DEFAULT_DISCARD_SAMPLES, seed);
+    }
+
+    if let Some(path) = &args.batch {
+        solve_batch(&solver, path, args.output)?;
+        return Ok(None);
+    }
+    if args.search {
+        search(args, run_state.unwrap_or_default(), solver.calculator(), sink)?;
+        return Ok(None);
+    }
 
+    // Parse the hand
+    let cards = parse_hand(args.hand.as_deref().unwrap_or_default())?;
     if cards.is_empty() {
         anyhow::bail!("Hand cannot be empty");
     }
 
-    // Load deck config if provided
-    if let Some(deck_path) = &args.deck {
-        let _deck_config = DeckConfig::from_file(deck_path)
-            .with_context(|| format!("Failed to load deck config from {}", deck_path))?;
-        // TODO: Use deck config to modify cards based on enhancements/editions
+    let consumables: Vec<Consumable> = match &state {
+        Some(state) if args.consumables.is_empty() => state.consumable_cards(),
+        _ => args
+            .consumables
+            .iter()
+            .map(|name| name.parse().with_context(|| format!("Invalid consumable: {}", name)))
+            .collect::<Result<_>>()?,
+    };
+
+    // Solve for the best play, after any consumables worth using
+    let (result, uses) = if consumables.is_empty() {
+        (solver.solve(&cards), vec![])
+    } else {
+        let plan = solver.solve_with_consumables(&cards, &consumables);
+        if let Some(ratio) = plan.score_ratio().filter(|_| !plan.uses.is_empty())
+            && matches!(args.output, OutputFormat::Pretty)
+            && !args.quiet
+        {
+            outln!("🧪 Use first ({:.1}x the score):", ratio);
+            for consumable_use in &plan.uses {
+                outln!("  {}", consumable_use);
+            }
+            outln!();
+        }
+        (plan.result, plan.uses)
+    };
+
+    // Weigh discarding against playing, from the cards left to draw, and
+    // plan the rest of the round against the blind
+    let deck_remaining = match &args.deck_remaining {
+        Some(path) => Some(
+            DeckConfig::from_file(path)
+                .with_context(|| format!("Failed to load remaining deck from {}", path))?
+                .to_cards()?,
+        ),
+        None => None,
+    };
+    let advice = deck_remaining
+        .as_ref()
+        .map(|deck_remaining| solver.solve_discard(&cards, deck_remaining, args.discards));
+    let plan = deck_remaining.as_ref().zip(blind_score).map(|(deck_remaining, blind_score)| {
+        RoundPlanner::new(solver.clone())
+            .with_rollouts(DEFAULT_ROLLOUTS, args.seed.unwrap_or_default())
+            .plan(&cards, deck_remaining, blind_score)
+    });
+
+    if args.quiet {
+        let score = result.best_score.as_ref().map_or(Score::ZERO, |score| score.score);
+        outln!("{}", score.value().floor());
+    } else {
+        let advice = advice.as_ref();
+        let report = SolveReport::new(&result, &uses, advice, plan.as_ref(), args, blind_score);
+        sink.emit(&report)?;
+    }
+    Ok(blind_score.map(|blind_score| match &plan {
+        Some(plan) => plan.best.success_probability >= ROUND_BEAT_CHANCE,
+        None => result.best_score.as_ref().is_some_and(|score| score.score >= blind_score),
+    }))
+}
+
+/// One play, as printed by the structured output formats
+#[derive(Debug, Serialize)]
+struct PlayReport {
+    cards: Vec<String>,
+    hand_type: HandType,
+    score: Score,
+    chips: u32,
+    mult: f64,
+    money: f64,
+    /// Chance of beating the blind, if a blind score was given
+    beat_probability: Option<f64>,
+}
+
+impl PlayReport {
+    fn new(hand: &Hand, score: &ScoreResult, beat_probability: Option<f64>) -> Self {
+        Self {
+            cards: hand.cards.iter().map(Card::to_string).collect(),
+            hand_type: score.hand_type,
+            score: score.score,
+            chips: score.chips,
+            mult: score.mult,
+            money: score.money_delta,
+            beat_probability,
+        }
+    }
+}
+
+/// Whether to play now or discard and redraw, as printed by the
+/// structured output formats
+#[derive(Debug, Serialize)]
+struct DiscardAdvice {
+    /// "play" or "discard"
+    action: &'static str,
+    discard: Vec<String>,
+    /// Value of the best play from the hand as it is
+    play_value: f64,
+    /// Expected value of the best play after the best discard, recommended
+    /// or not
+    discard_value: f64,
+    /// Whether every redraw was scored rather than sampled
+    exact: bool,
+}
+
+/// The solve command's result
+#[derive(Serialize)]
+struct SolveReport<'a> {
+    best_play: Option<PlayReport>,
+    held: Vec<String>,
+    debuffed: Vec<String>,
+    consumables_used: Vec<String>,
+    blind_score: Option<Score>,
+    hands_remaining: u32,
+    discards_remaining: u32,
+    warnings: Vec<String>,
+    alternatives: Vec<PlayReport>,
+    discard_advice: Option<DiscardAdvice>,
+    /// Chance of beating the blind over the rest of the round, planned
+    /// from --deck-remaining
+    round_beat_chance: Option<f64>,
+    #[serde(skip)]
+    result: &'a SolverResult,
+    #[serde(skip)]
+    advice: Option<&'a DiscardResult>,
+    #[serde(skip)]
+    plan: Option<&'a RoundPlan>,
+    #[serde(skip)]
+    args: &'a SolveArgs,
+}
+
+impl<'a> SolveReport<'a> {
+    fn new(
+        result: &'a SolverResult,
+        uses: &[ConsumableUse],
+        advice: Option<&'a DiscardResult>,
+        plan: Option<&'a RoundPlan>,
+        args: &'a SolveArgs,
+        blind_score: Option<Score>,
+    ) -> Self {
+        let alternatives = result.alternatives.iter().enumerate().map(|(i, (hand, score))| {
+            PlayReport::new(hand, score, result.alternative_beat_probabilities.get(i).copied())
+        });
+        Self {
+            best_play: result
+                .best_score
+                .as_ref()
+                .map(|score| PlayReport::new(&result.best_hand, score, result.beat_probability)),
+            held: result.held_cards.iter().map(Card::to_string).collect(),
+            debuffed: result.debuffed_cards.iter().map(Card::to_string).collect(),
+            consumables_used: uses.iter().map(ConsumableUse::to_string).collect(),
+            blind_score,
+            hands_remaining: result.hands_remaining,
+            discards_remaining: result.discards_remaining,
+            warnings: result.warnings.clone(),
+            alternatives: alternatives.collect(),
+            discard_advice: advice.map(|advice| DiscardAdvice {
+                action: if advice.should_discard() { "discard" } else { "play" },
+                discard: advice.discard.iter().map(Card::to_string).collect(),
+                play_value: advice.current_score,
+                discard_value: advice.expected_score,
+                exact: advice.exact,
+            }),
+            round_beat_chance: plan.map(|plan| plan.best.success_probability),
+            result,
+            advice,
+            plan,
+            args,
+        }
     }
+}
 
-    // Parse jokers
-    let jokers = parse_jokers(&args.jokers)?;
+impl Report for SolveReport<'_> {
+    fn print_pretty(&self) {
+        display_pretty(self.result, self.args, self.blind_score, self.plan);
+        if let Some(advice) = self.advice {
+            display_discard_advice(self.result, advice);
+        }
+    }
 
-    // Create score calculator and solver
-    let calculator = ScoreCalculator::new(jokers);
-    let solver = Solver::new(calculator);
+    fn print_compact(&self) {
+        display_compact(self.result);
+        if let Some(advice) = self.advice {
+            outln!(
+                "{} | Play EV: {:.0} | Discard {} EV: {:.0}",
+                if advice.should_discard() { "Discard" } else { "Play" },
+                advice.current_score,
+                format_cards(&advice.discard),
+                advice.expected_score
+            );
+        }
+    }
 
-    // Solve for the best play
-    let result = solver.solve(&cards);
+    /// One row per play, the best first
+    fn records(&self) -> Result<Vec<serde_json::Value>> {
+        #[derive(Serialize)]
+        struct PlayRecord<'a> {
+            rank: usize,
+            #[serde(flatten)]
+            play: &'a PlayReport,
+        }
 
-    // Display results based on output format
-    match args.output {
-        OutputFormat::Pretty => display_pretty(&result, &args),
-        OutputFormat::Json => display_json(&result)?,
-        OutputFormat::Compact => display_compact(&result),
+        self.best_play
+            .iter()
+            .chain(&self.alternatives)
+            .enumerate()
+            .map(|(i, play)| Ok(serde_json::to_value(PlayRecord { rank: i + 1, play })?))
+            .collect()
     }
+}
+
+/// Solves one hand per line of a file, or of stdin for "-"
+///
+/// Blank lines and lines starting with '#' are skipped. A line that fails
+/// to parse is reported in its row rather than stopping the batch.
+fn solve_batch(solver: &Solver, path: &str, output: OutputFormat) -> Result<()> {
+    use std::io::BufRead;
 
+    let reader: Box<dyn BufRead> = match path {
+        "-" => Box::new(std::io::stdin().lock()),
+        _ => Box::new(std::io::BufReader::new(
+            std::fs::File::open(path).with_context(|| format!("Failed to open {}", path))?,
+        )),
+    };
+
+    let mut records = RecordWriter::new(output);
+    if !output.is_structured() {
+        outln!("{:>5}  {:<28} {:<16} {:<20} {:>12}", "Line", "Hand", "Hand Type", "Play", "Score");
+    }
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let hand = line.trim();
+        if hand.is_empty() || hand.starts_with('#') {
+            continue;
+        }
+
+        let result = parse_hand(hand).map(|cards| solver.solve(&cards));
+        if output.is_structured() {
+            records.write(&BatchRecord::new(index + 1, hand, &result))?;
+            continue;
+        }
+        match &result {
+            Ok(SolverResult { best_score: Some(score), best_hand, .. }) => outln!(
+                "{:>5}  {:<28} {:<16} {:<20} {:>12}",
+                index + 1,
+                hand,
+                format!("{:?}", score.hand_type),
+                format_cards(&best_hand.cards),
+                score.score.to_string()
+            ),
+            Ok(_) => outln!("{:>5}  {:<28} No valid plays", index + 1, hand),
+            Err(e) => outln!("{:>5}  {:<28} ❌ {}", index + 1, hand, e),
+        }
+    }
     Ok(())
 }
 
-/// Parses a hand string into a vector of cards
-fn parse_hand(hand_str: &str) -> Result<Vec<Card>> {
-    let tokens: Vec<&str> = hand_str.split_whitespace().collect();
-    let mut cards = Vec::new();
+/// Searches the run's whole deck for the highest-scoring hand
+///
+/// The run is the game state's, if given, with the jokers, hand levels,
+/// boss blind, deck, and consumables given by flag taking precedence.
+fn search(
+    args: &SolveArgs,
+    run: GameRunState,
+    calculator: &ScoreCalculator,
+    sink: &mut Sink,
+) -> Result<()> {
+    let mut run = run
+        .with_jokers(calculator.jokers().to_vec())
+        .with_hand_levels(calculator.hand_levels().clone())
+        .with_boss_blind(calculator.boss_blind());
+    if let Some(deck) = calculator.deck() {
+        run.deck = deck.to_vec();
+    }
+    if !args.consumables.is_empty() {
+        for name in &args.consumables {
+            name.parse::<Consumable>()
+                .with_context(|| format!("Invalid consumable: {}", name))?;
+        }
+        run.consumables = args.consumables.clone();
+    }
 
-    for token in tokens {
-        let card = parse_card(token)?;
-        cards.push(card);
+    let mut search =
+        MaxScoreSearch::new(run).with_time_limit(args.max_time.unwrap_or(DEFAULT_TIME_LIMIT));
+    if let Some(width) = args.beam_width {
+        search = search.with_beam_width(width);
     }
+    let result = search.search();
 
-    Ok(cards)
+    if args.quiet {
+        let score = result.score.as_ref().map_or(Score::ZERO, |score| score.score);
+        outln!("{}", score.value().floor());
+        return Ok(());
+    }
+    sink.emit(&SearchReport::new(&result))
 }
 
-/// Parses a single card string (e.g., "AH", "10D", "KS")
-fn parse_card(card_str: &str) -> Result<Card> {
-    if card_str.len() < 2 {
-        anyhow::bail!("Invalid card format: {}", card_str);
+/// The highest-scoring hand found by --search
+#[derive(Serialize)]
+struct SearchReport<'a> {
+    best_play: Option<PlayReport>,
+    held: Vec<String>,
+    joker_order: Vec<&'static str>,
+    consumables_used: Vec<String>,
+    /// False if --max-time cut the search short
+    complete: bool,
+    #[serde(skip)]
+    result: &'a MaxScoreResult,
+}
+
+impl<'a> SearchReport<'a> {
+    fn new(result: &'a MaxScoreResult) -> Self {
+        Self {
+            best_play: result
+                .score
+                .as_ref()
+                .map(|score| PlayReport::new(&result.play, score, None)),
+            held: result.held_cards.iter().map(Card::to_string).collect(),
+            joker_order: result.joker_order.iter().map(|joker| joker.kind.name()).collect(),
+            consumables_used: result.consumables.iter().map(ConsumableUse::to_string).collect(),
+            complete: result.complete,
+            result,
+        }
+    }
+}
+
+impl Report for SearchReport<'_> {
+    fn print_pretty(&self) {
+        let Some(score) = &self.result.score else {
+            outln!("No cards in the deck to search");
+            return;
+        };
+        outln!("🔭 Highest-Scoring Hand:");
+        for consumable_use in &self.consumables_used {
+            outln!("  Use first: {}", consumable_use);
+        }
+        outln!("  Hand Type: {:?}", score.hand_type);
+        outln!("  Cards: {}", format_cards(&self.result.play.cards));
+        if !self.held.is_empty() {
+            outln!("  Held: {}", format_cards(&self.result.held_cards));
+        }
+        if self.joker_order.len() > 1 {
+            outln!("  Joker Order: {}", self.joker_order.join(", "));
+        }
+        outln!("  Chips: {} × Mult: {} = {}", score.chips, score.mult, score.score);
+        if !self.complete {
+            outln!("  ⏱️  Time limit reached; this is the best found so far");
+        }
     }
 
-    // Split into rank and suit
-    let (rank_str, suit_str) = if card_str.starts_with("10") {
-        ("10", &card_str[2..])
+    fn print_compact(&self) {
+        match &self.result.score {
+            Some(score) => outln!("{:?} | {} | Score: {}{}",
+                score.hand_type,
+                format_cards(&self.result.play.cards),
+                score.score,
+                if self.complete { "" } else { " (time limit reached)" }
+            ),
+            None => outln!("No valid plays"),
+        }
+    }
+}
+
+/// One batch line's result, as printed by the structured output formats
+///
+/// Every field is present on every line, empty where the line had no play
+/// or did not parse, so the records line up as csv rows.
+#[derive(Debug, Serialize)]
+struct BatchRecord {
+    line: usize,
+    hand: String,
+    play: Vec<String>,
+    hand_type: Option<HandType>,
+    score: Option<Score>,
+    chips: Option<u32>,
+    mult: Option<f64>,
+    beat_probability: Option<f64>,
+    error: Option<String>,
+}
+
+impl BatchRecord {
+    fn new(line: usize, hand: &str, result: &Result<SolverResult>) -> Self {
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        let score = result.and_then(|result| result.best_score.as_ref());
+        Self {
+            line,
+            hand: hand.to_string(),
+            play: result.map_or_else(Vec::new, |result| {
+                result.best_hand.cards.iter().map(Card::to_string).collect()
+            }),
+            hand_type: score.map(|score| score.hand_type),
+            score: score.map(|score| score.score),
+            chips: score.map(|score| score.chips),
+            mult: score.map(|score| score.mult),
+            beat_probability: result.and_then(|result| result.beat_probability),
+            error,
+        }
+    }
+}
+
+/// Creates the score calculator for the jokers, hand levels, deck, and
+/// boss blind
+///
+/// The game state's boss blind applies unless a boss or blind is given by
+/// flag.
+fn build_calculator(args: &SolveArgs, run: Option<&GameRunState>) -> Result<ScoreCalculator> {
+    let calculator =
+        calculator_from_flags(run, &args.jokers, &args.hand_levels, args.deck.as_deref())?;
+    Ok(match args.boss.is_some() || args.blind.is_some() || args.blind_score.is_some() {
+        true => calculator.with_boss_blind(args.boss),
+        false => calculator,
+    })
+}
+
+/// Creates a score calculator from the `--jokers`, `--hand-levels`, and
+/// `--deck` flags and a game state's run
+///
+/// Each comes from its flag if given, or else from the run, whose boss
+/// blind applies too. Hand levels from flags are set on top of the run's.
+pub(crate) fn calculator_from_flags(
+    run: Option<&GameRunState>,
+    jokers: &[String],
+    hand_levels: &[String],
+    deck: Option<&str>,
+) -> Result<ScoreCalculator> {
+    let jokers = match run {
+        Some(run) if jokers.is_empty() => run.jokers.clone(),
+        _ => parse_jokers(jokers)?,
+    };
+    let mut levels = run.map_or_else(HandLevels::new, |run| run.hand_levels.clone());
+    levels.set_entries(hand_levels)?;
+
+    let mut calculator = match run {
+        Some(run) => run.calculator().with_jokers(jokers),
+        None => ScoreCalculator::new(jokers),
+    }
+    .with_hand_levels(levels);
+    if let Some(deck_path) = deck {
+        let deck_config = DeckConfig::from_file(deck_path)
+            .with_context(|| format!("Failed to load deck config from {}", deck_path))?;
+        calculator = calculator.with_deck(deck_config.to_cards()?);
+    }
+    Ok(calculator)
+}
+
+/// Parses a hand string into a vector of cards
+///
+/// Cards may be separated by spaces, commas, semicolons, or pipes, so hands
+/// pasted in the usual shorthand (e.g., "Ah Kh Qh Jh Th", "AS,KD,5c") parse
+/// as they are.
+pub(crate) fn parse_hand(hand_str: &str) -> Result<Vec<Card>> {
+    hand_str
+        .split(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | '|'))
+        .filter(|token| !token.is_empty())
+        .map(parse_card)
+        .collect()
+}
+
+/// Parses a single card string (e.g., "AH", "10D", "KS"), optionally
+/// followed by modifiers after colons (e.g., "AH:gold", "KS:steel:foil",
+/// "10D:wild:red_seal"), or "stone" for a Stone card
+fn parse_card(card_str: &str) -> Result<Card> {
+    let mut parts = card_str.split(':');
+    let base = parts.next().unwrap_or_default();
+    let mut card = if base.eq_ignore_ascii_case("stone") {
+        // Stone cards have no rank or suit; any will do
+        Card::new(Rank::Ace, Suit::Spades).with_enhancement(Enhancement::Stone)
     } else {
-        (&card_str[..card_str.len() - 1], &card_str[card_str.len() - 1..])
+        parse_plain_card(base)?
+    };
+    for modifier in parts {
+        card = apply_modifier(card, modifier)
+            .with_context(|| format!("Invalid card: {}", card_str))?;
+    }
+    Ok(card)
+}
+
+/// Applies a card modifier: an enhancement (e.g., "gold"), an edition
+/// (e.g., "foil"), or a seal (e.g., "red_seal")
+fn apply_modifier(card: Card, modifier: &str) -> Result<Card> {
+    if modifier.to_lowercase().ends_with("seal") {
+        return Ok(card.with_seal(modifier.parse()?));
+    }
+    if let Ok(enhancement) = modifier.parse::<Enhancement>() {
+        return Ok(card.with_enhancement(enhancement));
+    }
+    if let Ok(edition) = modifier.parse::<Edition>() {
+        return Ok(card.with_edition(edition));
+    }
+    anyhow::bail!(
+        "Unknown card modifier: {}. Use an enhancement (e.g., gold), an edition (e.g., foil), \
+         or a seal (e.g., red_seal)",
+        modifier
+    )
+}
+
+/// Parses a card's rank and suit (e.g., "AH", "10D", "Th", "A♥"), in any
+/// case
+fn parse_plain_card(card_str: &str) -> Result<Card> {
+    // The suit is the last character and the rank everything before it
+    let (rank_str, suit_str) = match card_str.char_indices().last() {
+        Some((index, _)) if index > 0 => card_str.split_at(index),
+        _ => anyhow::bail!("Invalid card format: {}", card_str),
     };
 
     let rank = parse_rank(rank_str)?;
@@ -129,9 +764,9 @@ fn parse_card(card_str: &str) -> Result<Card> {
     Ok(Card::new(rank, suit))
 }
 
-/// Parses a rank string
+/// Parses a rank string, with "T" for 10
 fn parse_rank(s: &str) -> Result<Rank> {
-    match s {
+    match s.to_uppercase().as_str() {
         "2" => Ok(Rank::Two),
         "3" => Ok(Rank::Three),
         "4" => Ok(Rank::Four),
@@ -140,7 +775,7 @@ fn parse_rank(s: &str) -> Result<Rank> {
         "7" => Ok(Rank::Seven),
         "8" => Ok(Rank::Eight),
         "9" => Ok(Rank::Nine),
-        "10" => Ok(Rank::Ten),
+        "10" | "T" => Ok(Rank::Ten),
         "J" => Ok(Rank::Jack),
         "Q" => Ok(Rank::Queen),
         "K" => Ok(Rank::King),
@@ -149,131 +784,186 @@ fn parse_rank(s: &str) -> Result<Rank> {
     }
 }
 
-/// Parses a suit string
+/// Parses a suit string: a letter or a suit symbol (e.g., "♥")
 fn parse_suit(s: &str) -> Result<Suit> {
     match s.to_uppercase().as_str() {
-        "H" => Ok(Suit::Hearts),
-        "D" => Ok(Suit::Diamonds),
-        "C" => Ok(Suit::Clubs),
-        "S" => Ok(Suit::Spades),
+        "H" | "♥" | "♡" => Ok(Suit::Hearts),
+        "D" | "♦" | "♢" => Ok(Suit::Diamonds),
+        "C" | "♣" | "♧" => Ok(Suit::Clubs),
+        "S" | "♠" | "♤" => Ok(Suit::Spades),
         _ => anyhow::bail!("Invalid suit: {}", s),
     }
 }
 
 /// Parses joker names into Joker objects
-fn parse_jokers(_joker_names: &[String]) -> Result<Vec<Joker>> {
-    // TODO: Implement proper joker name parsing
-    // For now, return empty vector
-    Ok(Vec::new())
+///
+/// An `@name` entry stands for the joker set of that name in the user
+/// configuration.
+pub(crate) fn parse_jokers(joker_names: &[String]) -> Result<Vec<Joker>> {
+    if joker_names.iter().any(|name| name.trim().starts_with('@')) {
+        let expanded = super::config::user_config()?.expand_jokers(joker_names)?;
+        return expanded.iter().map(|name| name.parse()).collect();
+    }
+    joker_names
+        .iter()
+        .map(|name| name.parse())
+        .collect()
 }
 
 /// Displays results in pretty format
-fn display_pretty(result: &crate::core::solver::SolverResult, args: &SolveArgs) {
+fn display_pretty(
+    result: &SolverResult,
+    args: &SolveArgs,
+    blind_score: Option<Score>,
+    plan: Option<&RoundPlan>,
+) {
     if let Some(score_result) = &result.best_score {
-        println!("🃏 Best Play:");
-        println!("  Hand Type: {:?}", score_result.hand_type);
-        println!("  Cards: {}", format_cards(&result.best_hand.cards));
-        println!("  Score: {}", score_result.score);
-        println!("  Chips: {} × Mult: {} = {}",
+        outln!("🃏 Best Play:");
+        outln!("  Hand Type: {:?}", score_result.hand_type);
+        outln!("  Cards: {}", format_cards(&result.best_hand.cards));
+        if !result.held_cards.is_empty() {
+            outln!("  Held: {}", format_cards(&result.held_cards));
+        }
+        if !result.debuffed_cards.is_empty() {
+            outln!("  Debuffed: {}", format_cards(&result.debuffed_cards));
+        }
+        outln!("  Score: {}", score_result.score);
+        outln!("  Chips: {} × Mult: {} = {}",
             score_result.chips,
             score_result.mult,
             score_result.score
         );
+        outln!("  Breakdown:");
+        for event in &score_result.breakdown.events {
+            outln!("    {}", event);
+        }
+        if score_result.money_delta != 0.0 {
+            outln!("  Money: ${:.2}", score_result.money_delta);
+        }
+        for warning in &result.warnings {
+            outln!("  ⚠️  {}", warning);
+        }
 
-        if let Some(blind_score) = args.blind_score {
+        if let Some(blind_score) = blind_score {
             if score_result.score >= blind_score {
-                println!("  ✅ Beats blind (required: {})", blind_score);
+                outln!("  ✅ Beats blind (required: {})", blind_score);
             } else {
-                println!("  ❌ Does not beat blind (required: {}, short by: {})",
+                outln!("  ❌ Does not beat blind (required: {}, short by: {})",
                     blind_score,
                     blind_score - score_result.score
                 );
+                // Later hands are drawn, not dealt this play again, so
+                // this is only an upper bound without a deck to plan from
+                if result.hands_remaining > 1 && plan.is_none() {
+                    let needed = result
+                        .hands_needed(blind_score)
+                        .map_or("no number of".to_string(), |hands| hands.to_string());
+                    outln!("  📈 At best, plays like this need {} hands ({} hands, {} discards \
+                        left; pass --deck-remaining to plan the round)",
+                        needed,
+                        result.hands_remaining,
+                        result.discards_remaining
+                    );
+                }
+            }
+            if let Some(plan) = plan {
+                outln!("  🗺️  Chance to beat blind this round: {:.1}% ({} hands, {} discards left)",
+                    plan.best.success_probability * 100.0,
+                    result.hands_remaining,
+                    result.discards_remaining
+                );
+            }
+            if let Some(probability) = result.beat_probability {
+                outln!("  🎲 Chance to beat blind: {:.1}%", probability * 100.0);
             }
         }
 
+        if let Some(comparison) = result.explain() {
+            outln!("  💡 Why: {}", comparison);
+        }
+
         // Show alternatives
         if args.show_alternatives > 0 && !result.alternatives.is_empty() {
-            println!("\n📋 Alternative Plays:");
-            for (i, (hand, score)) in result.alternatives.iter().take(args.show_alternatives).enumerate() {
-                println!("  {}. {:?} - {} - Score: {}",
+            outln!("\n📋 Alternative Plays:");
+            for (i, (hand, score)) in result.alternatives.iter().enumerate() {
+                let chance = result
+                    .alternative_beat_probabilities
+                    .get(i)
+                    .map_or(String::new(), |p| format!(" - Beats blind: {:.1}%", p * 100.0));
+                outln!("  {}. {:?} - {} - Score: {}{}",
                     i + 1,
                     score.hand_type,
                     format_cards(&hand.cards),
-                    score.score
+                    score.score,
+                    chance
                 );
             }
         }
     } else {
-        println!("No valid plays found");
-    }
-}
-
-/// Displays results in JSON format
-fn display_json(result: &crate::core::solver::SolverResult) -> Result<()> {
-    let json = serde_json::json!({
-        "best_hand": {
-            "cards": result.best_hand.cards.len(),
-            "score": result.best_score.as_ref().map(|s| s.score),
-            "hand_type": result.best_score.as_ref().map(|s| format!("{:?}", s.hand_type)),
-            "chips": result.best_score.as_ref().map(|s| s.chips),
-            "mult": result.best_score.as_ref().map(|s| s.mult),
-        },
-        "alternatives": result.alternatives.iter().map(|(_, score)| {
-            serde_json::json!({
-                "score": score.score,
-                "hand_type": format!("{:?}", score.hand_type),
-            })
-        }).collect::<Vec<_>>(),
-    });
+        outln!("No valid plays found");
+    }
+}
 
-    println!("{}", serde_json::to_string_pretty(&json)?);
-    Ok(())
+/// Displays whether to play the best hand now or discard and redraw, with
+/// the expected value of both lines
+fn display_discard_advice(result: &SolverResult, advice: &DiscardResult) {
+    let sampled = if advice.exact { "" } else { " (sampled)" };
+    outln!("\n🔄 Play or Discard:");
+    outln!(
+        "  {} Play {}: {:.0}",
+        if advice.should_discard() { "  " } else { "👉" },
+        format_cards(&result.best_hand.cards),
+        advice.current_score
+    );
+    outln!(
+        "  {} Discard {} and redraw: {:.0} expected{} ({:+.0})",
+        if advice.should_discard() { "👉" } else { "  " },
+        format_cards(&advice.discard),
+        advice.expected_score,
+        sampled,
+        advice.expected_gain()
+    );
+    if advice.discards_left == 0 {
+        outln!("     No discards left");
+    }
+}
+
+/// Returns the score needed to beat the blind, from --blind-score or
+/// --blind, or else from the game state's blind
+///
+/// `boss` is the boss blind in play, `None` if a joker such as Chicot
+/// disables it, which leaves a boss blind its standard size.
+fn required_score(
+    args: &SolveArgs,
+    boss: Option<BossBlind>,
+    state: Option<&GameState>,
+) -> Option<u64> {
+    args.blind_score
+        .or_else(|| {
+            args.blind.map(|blind| match (blind, boss) {
+                (BlindType::Boss, Some(boss)) => boss_requirement(args.ante, boss, args.stake),
+                _ => blind_requirement(args.ante, blind, args.stake),
+            })
+        })
+        .or_else(|| state?.blind.as_ref().map(|blind| blind.score_required))
 }
 
 /// Displays results in compact format
-fn display_compact(result: &crate::core::solver::SolverResult) {
+fn display_compact(result: &SolverResult) {
     if let Some(score_result) = &result.best_score {
-        println!("{:?} | {} | Score: {}",
+        outln!("{:?} | {} | Score: {}",
             score_result.hand_type,
             format_cards(&result.best_hand.cards),
             score_result.score
         );
     } else {
-        println!("No valid plays");
+        outln!("No valid plays");
     }
 }
 
 /// Formats cards for display
-fn format_cards(cards: &[Card]) -> String {
-    cards.iter().map(|c| format_card(c)).collect::<Vec<_>>().join(" ")
-}
-
-/// Formats a single card for display
-fn format_card(card: &Card) -> String {
-    let rank = match card.rank {
-        Rank::Two => "2",
-        Rank::Three => "3",
-        Rank::Four => "4",
-        Rank::Five => "5",
-        Rank::Six => "6",
-        Rank::Seven => "7",
-        Rank::Eight => "8",
-        Rank::Nine => "9",
-        Rank::Ten => "10",
-        Rank::Jack => "J",
-        Rank::Queen => "Q",
-        Rank::King => "K",
-        Rank::Ace => "A",
-    };
-
-    let suit = match card.suit {
-        Suit::Hearts => "♥",
-        Suit::Diamonds => "♦",
-        Suit::Clubs => "♣",
-        Suit::Spades => "♠",
-    };
-
-    format!("{}{}", rank, suit)
+pub(crate) fn format_cards(cards: &[Card]) -> String {
+    cards.iter().map(Card::to_string).collect::<Vec<_>>().join(" ")
 }
 
 #[cfg(test)]
@@ -299,9 +989,244 @@ mod tests {
         assert_eq!(cards[4].rank, Rank::Ten);
     }
 
+    #[test]
+    fn test_parse_hand_shorthand() {
+        let expected = parse_hand("AH KH QH JH 10H").unwrap();
+        for hand in ["Ah Kh Qh Jh Th", "ah,kh,qh,jh,th", "AH, KH; QH | JH\t10h", "A♥ K♥ Q♥ J♥ T♥"] {
+            assert_eq!(parse_hand(hand).unwrap(), expected, "{}", hand);
+        }
+
+        let cards = parse_hand("AS,KD,5c").unwrap();
+        let cards: Vec<_> = cards.iter().map(|card| (card.rank, card.suit)).collect();
+        assert_eq!(
+            cards,
+            [(Rank::Ace, Suit::Spades), (Rank::King, Suit::Diamonds), (Rank::Five, Suit::Clubs)]
+        );
+        assert_eq!(parse_hand("th:gold").unwrap()[0].enhancement, Enhancement::Gold);
+        assert!(parse_hand("1H").is_err());
+        assert!(parse_hand("H").is_err());
+        assert!(parse_hand("♥").is_err());
+        assert!(parse_hand("AX").is_err());
+    }
+
+    #[test]
+    fn test_required_score_from_ante() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            solve: SolveArgs,
+        }
+
+        let required = |args: &[&str]| {
+            let cli = Cli::parse_from([&["jimbo", "--hand", "AH"], args].concat());
+            let calculator = build_calculator(&cli.solve, None).unwrap();
+            (required_score(&cli.solve, calculator.boss_blind(), None), calculator.boss_blind())
+        };
+
+        assert_eq!(required(&["--ante", "4", "--blind", "boss"]).0, Some(10_000));
+        assert_eq!(required(&["--blind-score", "500"]).0, Some(500));
+
+        // The Wall is twice the size of other boss blinds, unless Chicot
+        // disables it
+        let wall = ["--ante", "4", "--blind", "boss", "--boss", "wall"];
+        assert_eq!(required(&wall), (Some(20_000), Some(BossBlind::TheWall)));
+        assert_eq!(required(&[&wall[..], &["--jokers", "Chicot"]].concat()), (Some(10_000), None));
+    }
+
+    #[test]
+    fn test_game_state_merges_with_flags() {
+        use crate::config::game_state::BlindConfig;
+        use crate::core::joker::JokerKind;
+        use crate::core::{BossBlind, HandType};
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            solve: SolveArgs,
+        }
+
+        let state = GameState {
+            jokers: vec!["Joker".to_string(), "Baron".to_string()],
+            hand_levels: HandLevels::from_entries(["Flush=3", "Pair=2"]).unwrap(),
+            blind: Some(BlindConfig::boss("The Psychic".to_string(), 900)),
+            ..GameState::default()
+        };
+        let run = state.to_run_state().unwrap();
+
+        let cli = Cli::parse_from(["jimbo", "--hand", "AH"]);
+        let calculator = build_calculator(&cli.solve, Some(&run)).unwrap();
+        assert_eq!(calculator.jokers().len(), 2);
+        assert_eq!(calculator.hand_levels().level(HandType::Flush), 3);
+        assert_eq!(calculator.boss_blind(), Some(BossBlind::ThePsychic));
+        assert_eq!(required_score(&cli.solve, calculator.boss_blind(), Some(&state)), Some(900));
+
+        let cli = Cli::parse_from([
+            "jimbo", "--hand", "AH", "--jokers", "Scholar", "--hand-levels", "Pair=5",
+            "--blind-score", "300",
+        ]);
+        let calculator = build_calculator(&cli.solve, Some(&run)).unwrap();
+        assert_eq!(calculator.jokers()[0].kind, JokerKind::Scholar);
+        assert_eq!(calculator.hand_levels().level(HandType::Flush), 3);
+        assert_eq!(calculator.hand_levels().level(HandType::Pair), 5);
+        assert_eq!(calculator.boss_blind(), None);
+        assert_eq!(required_score(&cli.solve, calculator.boss_blind(), Some(&state)), Some(300));
+    }
+
+    #[test]
+    fn test_discard_advice_output() {
+        use crate::config::deck::CardDefinition;
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            solve: SolveArgs,
+        }
+
+        let dir = std::env::temp_dir().join(format!("jimbo-discard-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let remaining = dir.join("remaining.json");
+        let mut deck = DeckConfig::new();
+        deck.cards = vec![CardDefinition::new("2", "C"), CardDefinition::new("3", "D")];
+        deck.to_file(&remaining).unwrap();
+
+        // Both lines are valued, whichever is recommended
+        let advice = |flags: &[&str]| {
+            let mut argv = vec!["jimbo", "--hand", "AH AS KD", "--output", "json"];
+            argv.extend(["--deck-remaining", remaining.to_str().unwrap()]);
+            argv.extend(flags);
+            let mut sink = Sink::Capture(None);
+            run_with(&Cli::parse_from(argv).solve, &mut sink).unwrap();
+            sink.into_value().unwrap()["discard_advice"].clone()
+        };
+        for flags in [&["--discards-left", "2"][..], &["--discards-left", "0"]] {
+            let advice = advice(flags);
+            assert_eq!(advice["action"], "play");
+            assert_eq!(advice["play_value"].as_f64(), Some(64.0));
+            assert!(advice["discard_value"].as_f64().is_some_and(|value| value <= 64.0));
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let conflict = ["jimbo", "--batch", "-", "--deck-remaining", "remaining.json"];
+        assert!(Cli::try_parse_from(conflict).is_err());
+    }
+
+    #[test]
+    fn test_beats_blind_for_exit_code() {
+        use crate::config::deck::CardDefinition;
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            solve: SolveArgs,
+        }
+
+        let beats_blind = |flags: &[&str]| {
+            let mut argv = vec!["jimbo", "--hand", "AH AS", "--output", "json"];
+            argv.extend(flags);
+            let cli = Cli::parse_from(argv);
+            run_with(&cli.solve, &mut Sink::Capture(None)).unwrap()
+        };
+        // A pair of aces scores (10 + 22) × 2 = 64
+        assert_eq!(beats_blind(&["--blind-score", "64"]), Some(true));
+        assert_eq!(beats_blind(&["--blind-score", "65"]), Some(false));
+        // Repeating the play is only an upper bound, so more hands do not
+        // count without a deck to draw them from
+        assert_eq!(beats_blind(&["--blind-score", "65", "--hands", "2"]), Some(false));
+        assert_eq!(beats_blind(&[]), None);
+
+        // From a deck of 2C and 3D, the round adds a High Card 3 (8) and
+        // then a High Card 2 (7) before running out of cards
+        let dir = std::env::temp_dir().join(format!("jimbo-beats-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let remaining = dir.join("remaining.json");
+        let mut deck = DeckConfig::new();
+        deck.cards = vec![CardDefinition::new("2", "C"), CardDefinition::new("3", "D")];
+        deck.to_file(&remaining).unwrap();
+        let planned = |blind_score: &str| {
+            let deck = ["--deck-remaining", remaining.to_str().unwrap()];
+            beats_blind(&[&deck[..], &["--blind-score", blind_score, "--hands", "4"]].concat())
+        };
+        assert_eq!(planned("79"), Some(true));
+        assert_eq!(planned("100"), Some(false));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(Cli::try_parse_from(["jimbo", "--hand", "AH", "-q"]).unwrap().solve.quiet);
+        assert!(Cli::try_parse_from(["jimbo", "--hand", "AH", "-q", "--output", "csv"]).is_err());
+    }
+
+    #[test]
+    fn test_search_output() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            solve: SolveArgs,
+        }
+
+        let mut argv = vec!["jimbo", "--search", "--jokers", "Baron", "--max-time", "60"];
+        argv.extend(["--output", "json"]);
+        let mut sink = Sink::Capture(None);
+        assert_eq!(run_with(&Cli::parse_from(argv).solve, &mut sink).unwrap(), None);
+        let json = sink.into_value().unwrap();
+        assert_eq!(json["best_play"]["hand_type"], "FourOfAKind");
+        assert_eq!(json["held"].as_array().unwrap().len(), 4);
+        assert_eq!(json["complete"], true);
+
+        assert!(Cli::try_parse_from(["jimbo", "--search", "--hand", "AH"]).is_err());
+        assert!(Cli::try_parse_from(["jimbo", "--hand", "AH", "--max-time", "1"]).is_err());
+        assert!(Cli::try_parse_from(["jimbo", "--search", "--max-time", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_batch_records() {
+        let solver = Solver::new(ScoreCalculator::new(vec![]));
+        let result = parse_hand("AH AS 2C").map(|cards| solver.solve(&cards));
+        let json = serde_json::to_value(BatchRecord::new(3, "AH AS 2C", &result)).unwrap();
+        assert_eq!(json["line"], 3);
+        assert_eq!(json["hand_type"], "Pair");
+        assert_eq!(json["play"], serde_json::json!(["A♥", "A♠"]));
+        assert!(json["error"].is_null());
+
+        let result = parse_hand("ZZ").map(|cards| solver.solve(&cards));
+        let json = serde_json::to_value(BatchRecord::new(4, "ZZ", &result)).unwrap();
+        assert!(json["error"].is_string() && json["score"].is_null());
+    }
+
     #[test]
     fn test_invalid_card() {
         assert!(parse_card("XX").is_err());
         assert!(parse_card("1H").is_err());
+        assert!(parse_card("AH:shiny").is_err());
+        assert!(parse_card("AH:green_seal").is_err());
+    }
+
+    #[test]
+    fn test_parse_card_modifiers() {
+        use crate::core::card::Seal;
+
+        assert_eq!(parse_card("AH:gold").unwrap().enhancement, Enhancement::Gold);
+
+        let card = parse_card("KS:steel:foil").unwrap();
+        assert_eq!((card.rank, card.suit), (Rank::King, Suit::Spades));
+        assert_eq!((card.enhancement, card.edition), (Enhancement::Steel, Edition::Foil));
+
+        let card = parse_card("10D:wild:red_seal").unwrap();
+        assert_eq!((card.rank, card.enhancement), (Rank::Ten, Enhancement::Wild));
+        assert_eq!(card.seal, Some(Seal::Red));
+        assert_eq!(parse_card("2C:gold_seal").unwrap().seal, Some(Seal::Gold));
+
+        let stone = parse_card("stone").unwrap();
+        assert!(!stone.has_rank());
+        assert_eq!(parse_card("Stone:polychrome").unwrap().edition, Edition::Polychrome);
+
+        let cards = parse_hand("AH:glass stone KH").unwrap();
+        assert_eq!(cards.len(), 3);
+        assert_eq!(cards[0].enhancement, Enhancement::Glass);
     }
 }
@@ -0,0 +1,134 @@
+//! Stats command implementation
+//!
+//! This module implements the `stats` command which reads the runs a
+//! simulation exported with `simulate --export` and recomputes their
+//! statistics, percentiles, and histogram without simulating again.
+
+use super::output::{self, OutputFormat, Report};
+use super::simulate::{display_compact, display_statistics};
+use crate::core::{RawFormat, RunOutcome, Score, SimulationResult};
+use anyhow::{Context, Result};
+use clap::Args;
+use serde::Serialize;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+/// Arguments for the stats command
+#[derive(Debug, Args)]
+pub struct StatsArgs {
+    /// Runs exported with `simulate --export`: a .csv, .jsonl, or .ndjson
+    /// file
+    file: PathBuf,
+
+    /// Score a run must reach to clear the blind; reports the clear rate
+    /// (hands to clear count every hand a run played unless it was
+    /// simulated with the same --blind-score)
+    #[arg(long)]
+    blind_score: Option<u64>,
+
+    /// Report the chance of a run scoring at least this much
+    #[arg(long)]
+    target: Option<u64>,
+
+    /// Output format: pretty (default), json, yaml, csv, compact
+    #[arg(long, default_value = "pretty")]
+    output: OutputFormat,
+}
+
+/// Runs the stats command
+pub fn run(args: StatsArgs) -> Result<()> {
+    let runs = read_runs(&args.file)?;
+    let result = SimulationResult::from_runs(
+        &runs,
+        args.blind_score.map(Score::from),
+        args.target.map(Score::from),
+    );
+    let report = StatsReport {
+        result,
+        file: args.file.display().to_string(),
+    };
+    output::print(&report, args.output)
+}
+
+/// Reads the runs in an exported file, its extension giving the format
+fn read_runs(path: &Path) -> Result<Vec<RunOutcome>> {
+    let format = RawFormat::from_path(path)?;
+    let file = File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let runs = RunOutcome::read_raw(BufReader::new(file), format)
+        .with_context(|| format!("Failed to read runs from {}", path.display()))?;
+    anyhow::ensure!(
+        !runs.is_empty(),
+        "No runs in {}; export them with `simulate --export`",
+        path.display()
+    );
+    Ok(runs)
+}
+
+/// Statistics of exported runs, shaped as `simulate` prints them
+#[derive(Serialize)]
+#[serde(transparent)]
+struct StatsReport {
+    result: SimulationResult,
+    #[serde(skip)]
+    file: String,
+}
+
+impl Report for StatsReport {
+    fn print_pretty(&self) {
+        outln!(
+            "📊 Statistics for {} ({} runs):",
+            self.file,
+            self.result.num_runs
+        );
+        display_statistics(&self.result, "Blind");
+    }
+
+    fn print_compact(&self) {
+        display_compact(&self.result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        args: StatsArgs,
+    }
+
+    #[test]
+    fn test_stats_args() {
+        let cli = Cli::parse_from(["stats", "runs.csv", "--target", "500", "--output", "json"]);
+        assert_eq!(cli.args.file, PathBuf::from("runs.csv"));
+        assert_eq!(cli.args.target, Some(500));
+        assert_eq!(cli.args.blind_score, None);
+        assert_eq!(cli.args.output, OutputFormat::Json);
+    }
+
+    #[test]
+    fn test_read_runs() {
+        let dir = std::env::temp_dir().join(format!("jimbo-stats-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let csv = dir.join("runs.csv");
+        let rows = "run,seed,score,hands_played,hand_types,cards_played,money_delta\n\
+                    0,7,120,1,Pair,A♥ A♠,0\n\
+                    1,8,340,1,Flush,2♥ 5♥ 7♥ 9♥ J♥,1.5\n";
+        std::fs::write(&csv, rows).unwrap();
+        let runs = read_runs(&csv).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[1].score, Score::from(340));
+        assert_eq!(runs[1].money_delta, 1.5);
+
+        let empty = dir.join("empty.jsonl");
+        std::fs::write(&empty, "").unwrap();
+        assert!(read_runs(&empty).is_err());
+        assert!(read_runs(&dir.join("missing.csv")).is_err());
+        assert!(read_runs(&dir.join("runs.txt")).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,289 @@
+//! Terminal styling for human-readable output
+//!
+//! Commands write their pretty and compact output with emoji and Unicode
+//! symbols (card suits, ×, box drawing). Every such line goes through
+//! [`render`], which colors status lines (✅ green, ❌ red, ⚠️ yellow) and
+//! bolds headings when colors are on, and swaps the symbols for ASCII
+//! (e.g., "A♥" becomes "AH") when the terminal may not show them.
+//!
+//! Colors are on for terminals unless `NO_COLOR` is set; symbols are on
+//! unless the locale is not UTF-8 or `TERM` is "dumb". `--color always`
+//! and `--color never` turn both on or off. Structured output (json, yaml,
+//! csv) never goes through this layer.
+
+use anyhow::Result;
+use std::io::IsTerminal;
+use std::sync::OnceLock;
+
+/// Prints a line of human-readable output through [`render`]
+macro_rules! outln {
+    () => {
+        println!()
+    };
+    ($($arg:tt)*) => {
+        println!("{}", $crate::cli::style::render(&format!($($arg)*)))
+    };
+}
+
+/// Prints human-readable output through [`render`], without a newline
+macro_rules! out {
+    ($($arg:tt)*) => {
+        print!("{}", $crate::cli::style::render(&format!($($arg)*)))
+    };
+}
+
+/// Prints a line of human-readable output to stderr through [`render`]
+macro_rules! eoutln {
+    () => {
+        eprintln!()
+    };
+    ($($arg:tt)*) => {
+        eprintln!("{}", $crate::cli::style::render(&format!($($arg)*)))
+    };
+}
+
+/// Prints human-readable output to stderr through [`render`], without a
+/// newline
+macro_rules! eout {
+    ($($arg:tt)*) => {
+        eprint!("{}", $crate::cli::style::render(&format!($($arg)*)))
+    };
+}
+
+/// ASCII stand-ins for the symbols commands print
+///
+/// Status icons become bracketed tags, and symbols a column wide stay a
+/// column wide so tables and bars keep their alignment. Other emoji are
+/// decorative and are dropped, with the space after them.
+const ASCII_SYMBOLS: [(char, &str); 21] = [
+    ('✅', "[+]"),
+    ('❌', "[-]"),
+    ('⚠', "[!]"),
+    ('👉', "->"),
+    ('♥', "H"),
+    ('♠', "S"),
+    ('♦', "D"),
+    ('♣', "C"),
+    ('×', "x"),
+    ('→', "->"),
+    ('≥', ">="),
+    ('–', "-"),
+    ('═', "="),
+    ('█', "#"),
+    ('▉', "#"),
+    ('▊', "+"),
+    ('▋', "+"),
+    ('▌', "+"),
+    ('▍', "+"),
+    ('▎', "+"),
+    ('▏', "+"),
+];
+
+/// Emoji variation selector, which follows symbols such as ⚠ to draw them
+/// as emoji
+const EMOJI_VARIATION: char = '\u{fe0f}';
+
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const YELLOW: &str = "\x1b[33m";
+const CYAN: &str = "\x1b[36m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// When to style output, selected with --color
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// Colors on terminals, symbols where the locale allows
+    #[default]
+    Auto,
+    /// Colors and symbols, even when piped
+    Always,
+    /// Plain ASCII text
+    Never,
+}
+
+impl std::str::FromStr for ColorChoice {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            _ => anyhow::bail!("Invalid color choice: {}. Use 'auto', 'always', or 'never'", s),
+        }
+    }
+}
+
+/// How human-readable output is printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Style {
+    /// Whether to color status lines and headings
+    pub color: bool,
+    /// Whether to keep emoji and Unicode symbols
+    pub unicode: bool,
+}
+
+impl Style {
+    /// Works out the style for stdout and the environment
+    pub fn detect(choice: ColorChoice) -> Self {
+        let terminal = std::io::stdout().is_terminal();
+        Self::from_env(choice, terminal, |name| std::env::var(name).ok())
+    }
+
+    /// Works out the style from whether stdout is a terminal and the
+    /// environment variables `var` looks up
+    fn from_env(choice: ColorChoice, terminal: bool, var: impl Fn(&str) -> Option<String>) -> Self {
+        let dumb = var("TERM").as_deref() == Some("dumb");
+        // NO_COLOR counts when it is set to anything but an empty string
+        let no_color = var("NO_COLOR").is_some_and(|value| !value.is_empty());
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .into_iter()
+            .find_map(|name| var(name).filter(|value| !value.is_empty()));
+        let utf8 = locale.is_none_or(|locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        });
+
+        match choice {
+            ColorChoice::Always => Self { color: true, unicode: true },
+            ColorChoice::Never => Self { color: false, unicode: false },
+            ColorChoice::Auto => {
+                Self { color: terminal && !no_color && !dumb, unicode: utf8 && !dumb }
+            }
+        }
+    }
+
+    /// Styles text, one line at a time
+    pub fn render(&self, text: &str) -> String {
+        if !self.color && self.unicode {
+            return text.to_string();
+        }
+        let lines: Vec<String> = text.split('\n').map(|line| self.render_line(line)).collect();
+        lines.join("\n")
+    }
+
+    fn render_line(&self, line: &str) -> String {
+        let color = if self.color { line_color(line) } else { None };
+        let line = match self.unicode {
+            true => line.to_string(),
+            false => to_ascii(line),
+        };
+        match color {
+            // Keep a leading carriage return (progress bars) outside the color
+            Some(color) => match line.strip_prefix('\r') {
+                Some(rest) => format!("\r{}{}{}", color, rest, RESET),
+                None => format!("{}{}{}", color, line, RESET),
+            },
+            None => line,
+        }
+    }
+}
+
+/// Replaces a line's symbols with ASCII
+fn to_ascii(line: &str) -> String {
+    let mut ascii = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        if let Some((_, replacement)) = ASCII_SYMBOLS.iter().find(|(symbol, _)| *symbol == c) {
+            ascii.push_str(replacement);
+        } else if is_emoji(c) {
+            chars.next_if_eq(&EMOJI_VARIATION);
+            chars.next_if_eq(&' ');
+        } else if c != EMOJI_VARIATION {
+            ascii.push(c);
+        }
+    }
+    ascii
+}
+
+/// Returns whether a character is a pictograph (e.g., 🃏, ⏱, ⭐)
+fn is_emoji(c: char) -> bool {
+    matches!(c, '\u{1f000}'..='\u{1faff}' | '\u{2600}'..='\u{27bf}' | '\u{23e9}'..='\u{23fa}')
+        || c == '⭐'
+}
+
+/// Returns the color of a line from the symbol it starts with: status
+/// icons color their line, and an emoji at the start of an unindented
+/// line marks a heading
+fn line_color(line: &str) -> Option<&'static str> {
+    let text = line.trim_start_matches('\r');
+    let trimmed = text.trim_start();
+    let first = trimmed.chars().next()?;
+    match first {
+        '✅' => Some(GREEN),
+        '❌' => Some(RED),
+        '⚠' => Some(YELLOW),
+        '👉' => Some(CYAN),
+        _ if trimmed.len() == text.len() && !first.is_ascii() && !first.is_alphanumeric() => {
+            Some(BOLD)
+        }
+        _ => None,
+    }
+}
+
+static STYLE: OnceLock<Style> = OnceLock::new();
+
+/// Sets the style for the rest of the process, from --color
+///
+/// Has no effect once the style is set; output printed before this uses
+/// [`ColorChoice::Auto`].
+pub fn init(choice: ColorChoice) {
+    let _ = STYLE.set(Style::detect(choice));
+}
+
+/// Styles text with the process's style
+pub fn render(text: &str) -> String {
+    STYLE.get_or_init(|| Style::detect(ColorChoice::Auto)).render(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn style(choice: ColorChoice, terminal: bool, vars: &[(&str, &str)]) -> Style {
+        Style::from_env(choice, terminal, |name| {
+            vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+        })
+    }
+
+    #[test]
+    fn test_detect_style() {
+        let utf8 = [("LANG", "en_US.UTF-8")];
+        assert_eq!(style(ColorChoice::Auto, true, &utf8), Style { color: true, unicode: true });
+        assert_eq!(style(ColorChoice::Auto, false, &utf8), Style { color: false, unicode: true });
+        let no_color = [("LANG", "en_US.UTF-8"), ("NO_COLOR", "1")];
+        assert!(!style(ColorChoice::Auto, true, &no_color).color);
+        assert!(style(ColorChoice::Auto, true, &[("NO_COLOR", "")]).color);
+        assert!(!style(ColorChoice::Auto, true, &[("LC_ALL", "C"), ("LANG", "C.UTF-8")]).unicode);
+        assert!(!style(ColorChoice::Auto, true, &[("TERM", "dumb")]).color);
+        let always = style(ColorChoice::Always, false, &no_color);
+        assert_eq!(always, Style { color: true, unicode: true });
+        assert_eq!(style(ColorChoice::Never, true, &utf8), Style { color: false, unicode: false });
+
+        assert_eq!("NEVER".parse::<ColorChoice>().unwrap(), ColorChoice::Never);
+        assert!("sometimes".parse::<ColorChoice>().is_err());
+    }
+
+    #[test]
+    fn test_render() {
+        let plain = Style { color: false, unicode: false };
+        assert_eq!(plain.render("🃏 Best Play:"), "Best Play:");
+        assert_eq!(plain.render("  Cards: A♥ 10♠ K♦ 2♣"), "  Cards: AH 10S KD 2C");
+        let status = plain.render("  ✅ Beats blind\n  ⚠️  Warning");
+        assert_eq!(status, "  [+] Beats blind\n  [!]  Warning");
+        assert_eq!(plain.render("  30 × 4 → 120"), "  30 x 4 -> 120");
+        assert_eq!(plain.render("\n  ⏱️ Time: 2s 🎲"), "\n  Time: 2s ");
+        assert_eq!(plain.render("  ▉▊ 3"), "  #+ 3");
+
+        let unicode = Style { color: false, unicode: true };
+        assert_eq!(unicode.render("🃏 A♥"), "🃏 A♥");
+
+        let color = Style { color: true, unicode: true };
+        assert_eq!(color.render("  ❌ Short"), format!("{}  ❌ Short{}", RED, RESET));
+        assert_eq!(color.render("🃏 Best Play:"), format!("{}🃏 Best Play:{}", BOLD, RESET));
+        assert_eq!(color.render("  Score: 120"), "  Score: 120");
+        assert_eq!(color.render("\r⏱️ 50%"), format!("\r{}⏱️ 50%{}", BOLD, RESET));
+        assert_eq!(color.render(""), "");
+    }
+}
@@ -0,0 +1,275 @@
+//! Balatro save files
+//!
+//! Balatro keeps the run in progress in `save.jkr`: a Lua table literal
+//! compressed with raw DEFLATE. This module reads one into a game state and
+//! a deck configuration, so a run can be analyzed without retyping it.
+//!
+//! The deck is every playing card the run owns, wherever it is (draw pile,
+//! hand, or discards). Jokers jimbo does not know are left out and listed
+//! in `skipped`.
+
+use super::deck::CardDefinition;
+use super::lua::{self, LuaValue};
+use super::{DeckConfig, GameState};
+use crate::core::blind::{BlindConfig, BlindType};
+use crate::core::card::{Card, Edition, Enhancement, Rank, Suit};
+use crate::core::deck::DeckType;
+use crate::core::hand::HandType;
+use crate::core::joker::{Joker, JokerKind};
+use crate::core::pools::voucher_name;
+use crate::core::stake::Stake;
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Card areas holding the run's playing cards
+const PLAYING_CARD_AREAS: [&str; 4] = ["deck", "hand", "play", "discard"];
+
+/// A run read from a Balatro save file
+#[derive(Debug, Clone)]
+pub struct BalatroSave {
+    /// Jokers, consumables, vouchers, hand levels, blind, money, ante, and
+    /// stake (`deck_path` is left unset)
+    pub game_state: GameState,
+    /// Every playing card the run owns
+    pub deck: DeckConfig,
+    /// The run's seed as the game shows it (e.g., "7LB2WVPK")
+    pub seed: Option<String>,
+    /// Items that could not be imported, e.g. jokers jimbo does not model
+    pub skipped: Vec<String>,
+}
+
+impl BalatroSave {
+    /// Reads a save file (usually `save.jkr`)
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes =
+            std::fs::read(path).with_context(|| format!("Failed to read save file {:?}", path))?;
+        Self::from_bytes(&bytes).with_context(|| format!("Failed to import {:?}", path))
+    }
+
+    /// Reads a save from its compressed bytes, or from the Lua text itself
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let text = match miniz_oxide::inflate::decompress_to_vec(bytes) {
+            Ok(text) => text,
+            // Already decompressed, e.g. by another tool
+            Err(_) if bytes.trim_ascii_start().starts_with(b"return") => bytes.to_vec(),
+            Err(e) => anyhow::bail!("Not a Balatro save file: {:?}", e),
+        };
+        let save = lua::parse(&String::from_utf8_lossy(&text))?;
+        Self::from_lua(&save)
+    }
+
+    /// Reads a save from its parsed table
+    fn from_lua(save: &LuaValue) -> Result<Self> {
+        let game = save.get("GAME").context("The save has no GAME table")?;
+        let areas = save.get("cardAreas").context("The save has no cardAreas table")?;
+        let mut skipped = Vec::new();
+
+        let mut deck = DeckConfig::new();
+        for area in PLAYING_CARD_AREAS {
+            for card in cards_in(areas, area) {
+                deck.cards.push(CardDefinition::from_card(&playing_card(card)?));
+            }
+        }
+        deck.deck_type = save
+            .path(&["BACK", "name"])
+            .and_then(LuaValue::as_str)
+            .and_then(|name| name.parse::<DeckType>().ok());
+
+        let mut game_state = GameState::new();
+        for card in cards_in(areas, "jokers") {
+            match joker(card, game) {
+                Ok(joker) => game_state.jokers.push(joker),
+                Err(e) => skipped.push(e.to_string()),
+            }
+        }
+        game_state.consumables = cards_in(areas, "consumeables")
+            .filter_map(|card| card.path(&["ability", "name"]).and_then(LuaValue::as_str))
+            .map(str::to_string)
+            .collect();
+
+        let owned = |(_, value): &(&str, &LuaValue)| value.as_bool() == Some(true);
+        let used_vouchers = game.get("used_vouchers").map(LuaValue::fields);
+        for (key, _) in used_vouchers.into_iter().flatten().filter(owned) {
+            match voucher_name(key) {
+                Some(name) => game_state.vouchers.push(name.to_string()),
+                None => skipped.push(format!("Unknown voucher: {}", key)),
+            }
+        }
+
+        for (name, hand) in game.get("hands").map(LuaValue::fields).into_iter().flatten() {
+            let level = hand.get("level").and_then(LuaValue::as_f64).unwrap_or(1.0);
+            if let Ok(hand_type) = name.parse::<HandType>()
+                && level > 1.0
+            {
+                game_state.hand_levels =
+                    game_state.hand_levels.with_level(hand_type, level as u32);
+            }
+        }
+
+        game_state.blind = save.get("BLIND").and_then(blind);
+        game_state.money = number(game, &["dollars"]).unwrap_or(0.0).max(0.0) as u32;
+        game_state.ante = number(game, &["round_resets", "ante"]).unwrap_or(1.0).max(1.0) as u32;
+        if let Some(stake) = number(game, &["stake"]) {
+            game_state.stake = Stake::all()
+                .get((stake as usize).saturating_sub(1))
+                .copied()
+                .with_context(|| format!("Unknown stake: {}", stake))?;
+        }
+
+        let seed = game.path(&["pseudorandom", "seed"]).and_then(LuaValue::as_str);
+        Ok(Self { game_state, deck, seed: seed.map(str::to_string), skipped })
+    }
+}
+
+/// Returns the cards saved in a card area, in order
+fn cards_in<'a>(areas: &'a LuaValue, area: &str) -> impl Iterator<Item = &'a LuaValue> {
+    areas.path(&[area, "cards"]).map(LuaValue::items).unwrap_or_default().into_iter()
+}
+
+fn number(table: &LuaValue, keys: &[&str]) -> Option<f64> {
+    table.path(keys).and_then(LuaValue::as_f64)
+}
+
+fn text<'a>(table: &'a LuaValue, keys: &[&str]) -> Option<&'a str> {
+    table.path(keys).and_then(LuaValue::as_str)
+}
+
+/// Reads a playing card: its rank, suit, enhancement, edition, and seal
+fn playing_card(card: &LuaValue) -> Result<Card> {
+    let rank: Rank = text(card, &["base", "value"]).context("A card has no rank")?.parse()?;
+    let suit: Suit = text(card, &["base", "suit"]).context("A card has no suit")?.parse()?;
+    let mut card_out = Card::new(rank, suit);
+
+    // Enhanced cards are saved with their enhancement's key, e.g. "m_glass"
+    if let Some(key) = text(card, &["save_fields", "center"])
+        && let Some(name) = key.strip_prefix("m_")
+    {
+        card_out = card_out.with_enhancement(name.parse::<Enhancement>()?);
+    }
+    if let Some(edition) = card.get("edition").and_then(edition) {
+        card_out = card_out.with_edition(edition);
+    }
+    if let Some(seal) = text(card, &["seal"]) {
+        card_out = card_out.with_seal(seal.parse()?);
+    }
+    Ok(card_out)
+}
+
+/// Reads an edition table, e.g. `{type="foil", foil=true}`
+fn edition(edition: &LuaValue) -> Option<Edition> {
+    if let Some(name) = text(edition, &["type"]) {
+        return name.parse().ok();
+    }
+    edition
+        .fields()
+        .find(|(_, value)| value.as_bool() == Some(true))
+        .and_then(|(name, _)| name.parse().ok())
+}
+
+/// Reads a joker's name, with the card or suit the game picked this round
+/// for The Idol and Ancient Joker
+fn joker(card: &LuaValue, game: &LuaValue) -> Result<String> {
+    let name = text(card, &["ability", "name"])
+        .or_else(|| text(card, &["label"]))
+        .context("A joker has no name")?;
+    let kind: JokerKind = name.parse().map_err(|_| anyhow::anyhow!("Unknown joker: {}", name))?;
+
+    let target = match kind {
+        JokerKind::TheIdol => {
+            let rank = text(game, &["current_round", "idol_card", "rank"]);
+            let suit = text(game, &["current_round", "idol_card", "suit"]);
+            rank.zip(suit).and_then(|(rank, suit)| {
+                let card = Card::new(rank.parse().ok()?, suit.parse().ok()?);
+                let id = CardDefinition::from_card(&card);
+                Some(format!("{}{}", id.rank, id.suit))
+            })
+        }
+        JokerKind::AncientJoker => {
+            text(game, &["current_round", "ancient_card", "suit"]).map(str::to_string)
+        }
+        _ => None,
+    };
+    let joker = match target {
+        Some(target) => format!("{}:{}", kind.name(), target),
+        None => kind.name().to_string(),
+    };
+    // Catch a target the joker parser would not read back
+    joker.parse::<Joker>()?;
+    Ok(joker)
+}
+
+/// Reads the blind being played, if the run is in one
+fn blind(blind: &LuaValue) -> Option<BlindConfig> {
+    let name = text(blind, &["name"]).filter(|name| !name.is_empty())?;
+    let score_required = number(blind, &["chips"]).filter(|&chips| chips > 0.0)? as u64;
+    Some(match name {
+        "Small Blind" => BlindConfig::new(BlindType::Small, score_required),
+        "Big Blind" => BlindConfig::new(BlindType::Big, score_required),
+        _ => BlindConfig {
+            name: Some(name.to_string()),
+            ..BlindConfig::new(BlindType::Boss, score_required)
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::card::Seal;
+
+    const SAVE: &str = r#"return {["cardAreas"]={
+        ["deck"]={["cards"]={
+            [2]={["base"]={["suit"]="Spades",["value"]="King",},
+                ["save_fields"]={["center"]="m_glass",["card"]="S_K",},["seal"]="Red",},
+            [1]={["base"]={["suit"]="Hearts",["value"]="10",},
+                ["save_fields"]={["center"]="c_base",},
+                ["edition"]={["type"]="holo",["holo"]=true,},},
+        },},
+        ["hand"]={["cards"]={[1]={["base"]={["suit"]="Clubs",["value"]="Ace",},},},},
+        ["jokers"]={["cards"]={
+            [1]={["label"]="The Idol",["ability"]={["name"]="The Idol",},},
+            [2]={["ability"]={["name"]="Greedy Joker",},},
+            [3]={["ability"]={["name"]="Not A Joker",},},
+        },},
+        ["consumeables"]={["cards"]={[1]={["ability"]={["name"]="Jupiter",},},},},
+    },
+    ["GAME"]={["dollars"]=12,["stake"]=3,["round_resets"]={["ante"]=4,},
+        ["used_vouchers"]={["v_grabber"]=true,["v_nacho_tong"]=true,},
+        ["hands"]={["Flush"]={["level"]=3,},["Pair"]={["level"]=1,},},
+        ["pseudorandom"]={["seed"]="7LB2WVPK",},
+        ["current_round"]={["idol_card"]={["rank"]="Queen",["suit"]="Diamonds",},},
+    },
+    ["BLIND"]={["name"]="The Psychic",["chips"]=5000,},
+    ["BACK"]={["name"]="Plasma Deck",},
+    }"#;
+
+    #[test]
+    fn test_import_save() {
+        let compressed = miniz_oxide::deflate::compress_to_vec(SAVE.as_bytes(), 1);
+        let save = BalatroSave::from_bytes(&compressed).unwrap();
+
+        let cards = save.deck.to_cards().unwrap();
+        assert_eq!(cards.len(), 3);
+        assert_eq!((cards[0].rank, cards[0].edition), (Rank::Ten, Edition::Holographic));
+        assert_eq!(cards[1].enhancement, Enhancement::Glass);
+        assert_eq!(cards[1].seal, Some(Seal::Red));
+        assert_eq!(cards[2].suit, Suit::Clubs);
+        assert_eq!(save.deck.deck_type, Some(DeckType::Plasma));
+
+        let state = &save.game_state;
+        assert_eq!(state.jokers, vec!["The Idol:QD".to_string(), "Greedy Joker".to_string()]);
+        assert_eq!(save.skipped, vec!["Unknown joker: Not A Joker".to_string()]);
+        assert_eq!(state.consumables, vec!["Jupiter".to_string()]);
+        assert_eq!(state.vouchers, vec!["Grabber".to_string(), "Nacho Tong".to_string()]);
+        assert_eq!(state.hand_levels.level(HandType::Flush), 3);
+        assert_eq!((state.money, state.ante, state.stake), (12, 4, Stake::Green));
+        assert_eq!(state.boss_blind().map(|boss| boss.name()), Some("The Psychic"));
+        assert_eq!(save.seed.as_deref(), Some("7LB2WVPK"));
+        assert!(state.parse_jokers().is_ok());
+
+        // Decompressed saves are read as they are
+        assert_eq!(BalatroSave::from_bytes(SAVE.as_bytes()).unwrap().deck.cards.len(), 3);
+        assert!(BalatroSave::from_bytes(b"not a save").is_err());
+    }
+}
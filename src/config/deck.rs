@@ -1,13 +1,14 @@
 //! Deck configuration and serialization
 //!
-//! This module handles loading and saving deck configurations from JSON files,
-//! including card enhancements, editions, and seals.
+//! This module handles loading and saving deck configurations from JSON,
+//! YAML, or TOML files, including card enhancements, editions, and seals.
 
 use crate::core::card::{Card, Edition, Enhancement, Rank, Seal, Suit};
+use crate::core::deck::DeckType;
+use super::format::{self, ConfigFormat};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::fs;
 use std::path::Path;
 
 /// Represents a complete deck configuration
@@ -27,6 +28,10 @@ pub struct DeckConfig {
     /// Card seals mapped by card ID
     #[serde(default)]
     pub seals: HashMap<String, Seal>,
+
+    /// The official deck this configuration is based on, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deck_type: Option<DeckType>,
 }
 
 /// A card definition in the configuration
@@ -34,6 +39,50 @@ pub struct DeckConfig {
 pub struct CardDefinition {
     pub rank: String,
     pub suit: String,
+
+    /// Unique ID for this card, used as the lookup key into the
+    /// enhancement/edition/seal maps instead of the shared "AH"-style ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+
+    /// Enhancement for this card, overriding the lookup map
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enhancement: Option<Enhancement>,
+
+    /// Edition for this card, overriding the lookup map
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edition: Option<Edition>,
+
+    /// Seal for this card, overriding the lookup map
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seal: Option<Seal>,
+}
+
+impl CardDefinition {
+    /// Creates a plain card definition
+    pub fn new(rank: &str, suit: &str) -> Self {
+        Self {
+            rank: rank.to_string(),
+            suit: suit.to_string(),
+            id: None,
+            enhancement: None,
+            edition: None,
+            seal: None,
+        }
+    }
+
+    /// Creates a card definition from a card, with its enhancement,
+    /// edition, and seal set inline
+    pub fn from_card(card: &Card) -> Self {
+        let id = DeckConfig::make_card_id(card.rank, card.suit);
+        let (rank, suit) = id.split_at(id.len() - 1);
+        Self {
+            enhancement: Some(card.enhancement).filter(|&e| e != Enhancement::None),
+            edition: Some(card.edition).filter(|&e| e != Edition::None),
+            seal: card.seal,
+            ..Self::new(rank, suit)
+        }
+    }
 }
 
 impl DeckConfig {
@@ -44,6 +93,7 @@ impl DeckConfig {
             enhancements: HashMap::new(),
             editions: HashMap::new(),
             seals: HashMap::new(),
+            deck_type: None,
         }
     }
 
@@ -55,10 +105,7 @@ impl DeckConfig {
         let mut cards = Vec::new();
         for suit in suits {
             for rank in ranks {
-                cards.push(CardDefinition {
-                    rank: rank.to_string(),
-                    suit: suit.to_string(),
-                });
+                cards.push(CardDefinition::new(rank, suit));
             }
         }
 
@@ -67,32 +114,51 @@ impl DeckConfig {
             enhancements: HashMap::new(),
             editions: HashMap::new(),
             seals: HashMap::new(),
+            deck_type: None,
         }
     }
 
-    /// Loads a deck configuration from a JSON file
+    /// Creates the starting deck of an official deck archetype
+    ///
+    /// The seed is only used by the Erratic deck.
+    pub fn from_deck_type(deck_type: DeckType, seed: u64) -> Self {
+        let cards = deck_type
+            .create_cards(seed)
+            .iter()
+            .map(CardDefinition::from_card)
+            .collect();
+
+        Self {
+            cards,
+            deck_type: Some(deck_type),
+            ..Self::new()
+        }
+    }
+
+    /// Loads a deck configuration from a JSON, YAML, or TOML file, going by
+    /// its extension
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let contents = fs::read_to_string(&path)
-            .with_context(|| format!("Failed to read deck config from {:?}", path.as_ref()))?;
+        Self::from_file_as(&path, ConfigFormat::from_path(&path))
+    }
 
-        let config: DeckConfig = serde_json::from_str(&contents)
-            .context("Failed to parse deck config JSON")?;
+    /// Loads a deck configuration from a file in the given format
+    pub fn from_file_as<P: AsRef<Path>>(path: P, format: ConfigFormat) -> Result<Self> {
+        let config: DeckConfig = format::read_file_as(path.as_ref(), format, "deck config")?;
 
         config.validate()?;
         Ok(config)
     }
 
-    /// Saves a deck configuration to a JSON file
+    /// Saves a deck configuration to a JSON, YAML, or TOML file, going by
+    /// its extension
     pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
-        self.validate()?;
-
-        let json = serde_json::to_string_pretty(self)
-            .context("Failed to serialize deck config")?;
-
-        fs::write(&path, json)
-            .with_context(|| format!("Failed to write deck config to {:?}", path.as_ref()))?;
+        self.to_file_as(&path, ConfigFormat::from_path(&path))
+    }
 
-        Ok(())
+    /// Saves a deck configuration to a file in the given format
+    pub fn to_file_as<P: AsRef<Path>>(&self, path: P, format: ConfigFormat) -> Result<()> {
+        self.validate()?;
+        format::write_file_as(path.as_ref(), format, self, "deck config")
     }
 
     /// Validates the deck configuration
@@ -101,11 +167,15 @@ impl DeckConfig {
         anyhow::ensure!(!self.cards.is_empty(), "Deck must contain at least one card");
 
         // Validate each card definition
+        let mut ids = std::collections::HashSet::new();
         for card_def in &self.cards {
             Self::parse_rank(&card_def.rank)
                 .with_context(|| format!("Invalid rank: {}", card_def.rank))?;
             Self::parse_suit(&card_def.suit)
                 .with_context(|| format!("Invalid suit: {}", card_def.suit))?;
+            if let Some(id) = &card_def.id {
+                anyhow::ensure!(ids.insert(id), "Duplicate card id: {}", id);
+            }
         }
 
         Ok(())
@@ -118,23 +188,27 @@ impl DeckConfig {
         for card_def in &self.cards {
             let rank = Self::parse_rank(&card_def.rank)?;
             let suit = Self::parse_suit(&card_def.suit)?;
-            let card_id = Self::make_card_id(rank, suit);
+            let card_id = card_def
+                .id
+                .clone()
+                .unwrap_or_else(|| Self::make_card_id(rank, suit));
 
             let mut card = Card::new(rank, suit);
 
-            // Apply enhancements
-            if let Some(enhancement) = self.enhancements.get(&card_id) {
-                card = card.with_enhancement(*enhancement);
+            // Apply enhancements (inline first, then by ID)
+            let enhancement = card_def.enhancement.or(self.enhancements.get(&card_id).copied());
+            if let Some(enhancement) = enhancement {
+                card = card.with_enhancement(enhancement);
             }
 
             // Apply editions
-            if let Some(edition) = self.editions.get(&card_id) {
-                card = card.with_edition(*edition);
+            if let Some(edition) = card_def.edition.or(self.editions.get(&card_id).copied()) {
+                card = card.with_edition(edition);
             }
 
             // Apply seals
-            if let Some(seal) = self.seals.get(&card_id) {
-                card = card.with_seal(*seal);
+            if let Some(seal) = card_def.seal.or(self.seals.get(&card_id).copied()) {
+                card = card.with_seal(seal);
             }
 
             cards.push(card);
@@ -231,4 +305,64 @@ mod tests {
         let cards = deck.to_cards().unwrap();
         assert_eq!(cards.len(), 52);
     }
+
+    #[test]
+    fn test_from_deck_type() {
+        let deck = DeckConfig::from_deck_type(DeckType::Abandoned, 0);
+        assert_eq!(deck.deck_type, Some(DeckType::Abandoned));
+        assert_eq!(deck.to_cards().unwrap().len(), 40);
+    }
+
+    #[test]
+    fn test_card_definition_from_card() {
+        let card = Card::new(Rank::Ten, Suit::Clubs).with_enhancement(Enhancement::Glass);
+        let definition = CardDefinition::from_card(&card);
+        assert_eq!((definition.rank.as_str(), definition.suit.as_str()), ("10", "C"));
+        assert_eq!(definition.enhancement, Some(Enhancement::Glass));
+        assert_eq!((definition.edition, definition.seal), (None, None));
+    }
+
+    #[test]
+    fn test_duplicate_cards_with_ids() {
+        let json = r#"{
+            "cards": [
+                {"rank": "A", "suit": "H", "id": "AH-1"},
+                {"rank": "A", "suit": "H", "id": "AH-2", "enhancement": "Steel"},
+                {"rank": "A", "suit": "H"}
+            ],
+            "enhancements": {"AH-1": "Glass", "AH": "Bonus"}
+        }"#;
+        let deck: DeckConfig = serde_json::from_str(json).unwrap();
+        let cards = deck.to_cards().unwrap();
+
+        assert_eq!(cards[0].enhancement, Enhancement::Glass);
+        assert_eq!(cards[1].enhancement, Enhancement::Steel);
+        assert_eq!(cards[2].enhancement, Enhancement::Bonus);
+    }
+
+    #[test]
+    fn test_round_trips_every_format() {
+        use crate::config::ConfigFormat;
+
+        let mut deck = DeckConfig::from_deck_type(DeckType::Checkered, 0);
+        deck.enhancements.insert("AS".to_string(), Enhancement::Glass);
+        deck.seals.insert("KS".to_string(), Seal::Red);
+        deck.cards[0].id = Some("first".to_string());
+        deck.cards[0].edition = Some(Edition::Polychrome);
+
+        let original = serde_json::to_value(&deck).unwrap();
+        for format in [ConfigFormat::Json, ConfigFormat::Yaml, ConfigFormat::Toml] {
+            let text = format.to_string_lossless(&deck).unwrap();
+            let parsed: DeckConfig = format.parse(&text).unwrap();
+            assert_eq!(serde_json::to_value(&parsed).unwrap(), original, "{}", format);
+        }
+    }
+
+    #[test]
+    fn test_duplicate_ids_rejected() {
+        let mut deck = DeckConfig::standard();
+        deck.cards[0].id = Some("dup".to_string());
+        deck.cards[1].id = Some("dup".to_string());
+        assert!(deck.validate().is_err());
+    }
 }
@@ -0,0 +1,256 @@
+//! Differences between configurations
+//!
+//! This module compares two deck configurations or two game states and
+//! lists what changed between them: cards added, removed, or modified,
+//! jokers and other items gained or lost, and settings that changed value.
+
+use super::{DeckConfig, GameState};
+use crate::core::card::{Card, Edition, Enhancement};
+use crate::core::hand::HandType;
+use anyhow::Result;
+use serde::Serialize;
+
+/// How a field differs between two configurations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// One difference between two configurations
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConfigChange {
+    /// What changed (e.g., "jokers", "money", or a card such as "K♠")
+    pub field: String,
+    pub kind: ChangeKind,
+    /// The value in the first configuration (`None` if added)
+    pub before: Option<String>,
+    /// The value in the second configuration (`None` if removed)
+    pub after: Option<String>,
+}
+
+impl ConfigChange {
+    fn added(field: &str, value: String) -> Self {
+        Self { field: field.to_string(), kind: ChangeKind::Added, before: None, after: Some(value) }
+    }
+
+    fn removed(field: &str, value: String) -> Self {
+        Self {
+            field: field.to_string(),
+            kind: ChangeKind::Removed,
+            before: Some(value),
+            after: None,
+        }
+    }
+
+    fn changed(field: &str, before: String, after: String) -> Self {
+        Self {
+            field: field.to_string(),
+            kind: ChangeKind::Changed,
+            before: Some(before),
+            after: Some(after),
+        }
+    }
+}
+
+/// Lists the differences between two deck configurations
+///
+/// Cards are compared after their enhancements, editions, and seals are
+/// applied. A card of the same rank and suit in both decks but with other
+/// modifiers is reported as changed rather than removed and added.
+pub fn diff_decks(before: &DeckConfig, after: &DeckConfig) -> Result<Vec<ConfigChange>> {
+    let mut changes = Vec::new();
+    let deck_type = |deck: &DeckConfig| deck.deck_type.map(|deck_type| format!("{:?}", deck_type));
+    push_if_changed(&mut changes, "deck_type", deck_type(before), deck_type(after));
+
+    let mut removed = before.to_cards()?;
+    let mut added = after.to_cards()?;
+    removed.retain(|card| match added.iter().position(|other| other == card) {
+        Some(index) => {
+            added.remove(index);
+            false
+        }
+        None => true,
+    });
+
+    for card in removed {
+        let same_card = |other: &Card| other.rank == card.rank && other.suit == card.suit;
+        match added.iter().position(same_card) {
+            Some(index) => {
+                let other = added.remove(index);
+                changes.push(ConfigChange::changed(
+                    &Card::new(card.rank, card.suit).to_string(),
+                    describe_modifiers(&card),
+                    describe_modifiers(&other),
+                ));
+            }
+            None => changes.push(ConfigChange::removed("cards", describe_card(&card))),
+        }
+    }
+    changes.extend(added.iter().map(|card| ConfigChange::added("cards", describe_card(card))));
+    Ok(changes)
+}
+
+/// Lists the differences between two game states
+///
+/// Jokers, consumables, and vouchers are compared as they are written, so
+/// a joker that moved to another slot is not a change.
+pub fn diff_game_states(before: &GameState, after: &GameState) -> Vec<ConfigChange> {
+    let mut changes = Vec::new();
+    push_if_changed(&mut changes, "deck_path", before.deck_path.clone(), after.deck_path.clone());
+    push_list_changes(&mut changes, "jokers", &before.jokers, &after.jokers);
+    push_list_changes(&mut changes, "consumables", &before.consumables, &after.consumables);
+    push_list_changes(&mut changes, "vouchers", &before.vouchers, &after.vouchers);
+
+    for hand_type in HandType::all() {
+        let (old, new) = (before.hand_levels.level(hand_type), after.hand_levels.level(hand_type));
+        if old != new {
+            let field = format!("{:?} level", hand_type);
+            changes.push(ConfigChange::changed(&field, old.to_string(), new.to_string()));
+        }
+    }
+
+    let blind = |state: &GameState| {
+        state.blind.as_ref().map(|blind| match &blind.name {
+            Some(name) => format!("{} ({})", name, blind.score_required),
+            None => format!("{:?} ({})", blind.blind_type, blind.score_required),
+        })
+    };
+    let seed = |state: &GameState| state.seed.map(|seed| seed.to_string());
+    let money = |state: &GameState| Some(state.money.to_string());
+    let ante = |state: &GameState| Some(state.ante.to_string());
+    let stake = |state: &GameState| Some(format!("{:?}", state.stake));
+    push_if_changed(&mut changes, "blind", blind(before), blind(after));
+    push_if_changed(&mut changes, "seed", seed(before), seed(after));
+    push_if_changed(&mut changes, "money", money(before), money(after));
+    push_if_changed(&mut changes, "ante", ante(before), ante(after));
+    push_if_changed(&mut changes, "stake", stake(before), stake(after));
+    changes
+}
+
+/// Records a setting whose value differs, as added or removed when it is
+/// only set in one of the two
+fn push_if_changed(
+    changes: &mut Vec<ConfigChange>,
+    field: &str,
+    before: Option<String>,
+    after: Option<String>,
+) {
+    match (before, after) {
+        (Some(old), Some(new)) if old != new => {
+            changes.push(ConfigChange::changed(field, old, new))
+        }
+        (None, Some(new)) => changes.push(ConfigChange::added(field, new)),
+        (Some(old), None) => changes.push(ConfigChange::removed(field, old)),
+        _ => {}
+    }
+}
+
+/// Records the entries of a list that are only in one of the two, counting
+/// repeats
+fn push_list_changes(
+    changes: &mut Vec<ConfigChange>,
+    field: &str,
+    before: &[String],
+    after: &[String],
+) {
+    let mut added: Vec<&String> = after.iter().collect();
+    for item in before {
+        match added.iter().position(|other| *other == item) {
+            Some(index) => {
+                added.remove(index);
+            }
+            None => changes.push(ConfigChange::removed(field, item.clone())),
+        }
+    }
+    changes.extend(added.into_iter().map(|item| ConfigChange::added(field, item.clone())));
+}
+
+/// Describes a card with its modifiers, e.g. "K♠ (Glass, Red seal)"
+fn describe_card(card: &Card) -> String {
+    let name = Card::new(card.rank, card.suit).to_string();
+    match describe_modifiers(card).as_str() {
+        "plain" => name,
+        modifiers => format!("{} ({})", name, modifiers),
+    }
+}
+
+/// Lists a card's enhancement, edition, and seal, or "plain" if it has none
+fn describe_modifiers(card: &Card) -> String {
+    let mut modifiers = Vec::new();
+    if card.enhancement != Enhancement::None {
+        modifiers.push(format!("{:?}", card.enhancement));
+    }
+    if card.edition != Edition::None {
+        modifiers.push(format!("{:?}", card.edition));
+    }
+    if let Some(seal) = card.seal {
+        modifiers.push(format!("{:?} seal", seal));
+    }
+    match modifiers.is_empty() {
+        true => "plain".to_string(),
+        false => modifiers.join(", "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::deck::CardDefinition;
+    use crate::core::card::Seal;
+    use crate::core::stake::Stake;
+
+    #[test]
+    fn test_diff_decks() {
+        let before = DeckConfig::standard();
+        let mut after = DeckConfig::standard();
+        after.cards.retain(|card| card.rank != "2" || card.suit != "Clubs");
+        after.cards.push(CardDefinition::new("A", "Spades"));
+        after.enhancements.insert("KS".to_string(), Enhancement::Glass);
+        after.seals.insert("KS".to_string(), Seal::Red);
+
+        let changes = diff_decks(&before, &after).unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                ConfigChange::removed("cards", "2♣".to_string()),
+                ConfigChange::changed("K♠", "plain".to_string(), "Glass, Red seal".to_string()),
+                ConfigChange::added("cards", "A♠".to_string()),
+            ]
+        );
+        assert!(diff_decks(&before, &DeckConfig::standard()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_diff_game_states() {
+        let before = GameState {
+            jokers: vec!["Joker".to_string(), "Baron".to_string()],
+            money: 10,
+            ..Default::default()
+        };
+        let after = GameState {
+            jokers: vec!["Baron".to_string(), "Blueprint".to_string()],
+            hand_levels: before.hand_levels.clone().with_level(HandType::Flush, 3),
+            seed: Some(7),
+            money: 25,
+            stake: Stake::Gold,
+            ..Default::default()
+        };
+
+        let changes = diff_game_states(&before, &after);
+        assert_eq!(
+            changes,
+            vec![
+                ConfigChange::removed("jokers", "Joker".to_string()),
+                ConfigChange::added("jokers", "Blueprint".to_string()),
+                ConfigChange::changed("Flush level", "1".to_string(), "3".to_string()),
+                ConfigChange::added("seed", "7".to_string()),
+                ConfigChange::changed("money", "10".to_string(), "25".to_string()),
+                ConfigChange::changed("stake", "White".to_string(), "Gold".to_string()),
+            ]
+        );
+        assert!(diff_game_states(&before, &before).is_empty());
+    }
+}
@@ -0,0 +1,175 @@
+//! Configuration file formats
+//!
+//! Deck configurations and game states can be written as JSON, YAML, or
+//! TOML. The format is picked from the file extension (.json, .yaml/.yml,
+//! .toml), and files with any other extension are read as JSON unless a
+//! format is given (e.g., with `config init --format`).
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A serialization format for configuration files
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConfigFormat {
+    #[default]
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl ConfigFormat {
+    /// Picks the format from a file's extension, defaulting to JSON
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        let extension = path.as_ref().extension().and_then(|e| e.to_str()).unwrap_or_default();
+        match extension.to_lowercase().as_str() {
+            "yaml" | "yml" => ConfigFormat::Yaml,
+            "toml" => ConfigFormat::Toml,
+            _ => ConfigFormat::Json,
+        }
+    }
+
+    /// Returns the usual file extension for the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ConfigFormat::Json => "json",
+            ConfigFormat::Yaml => "yaml",
+            ConfigFormat::Toml => "toml",
+        }
+    }
+
+    /// Returns the path with its extension replaced by this format's
+    pub fn path_for<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        path.as_ref().with_extension(self.extension())
+    }
+
+    /// Parses a configuration from text in this format
+    pub fn parse<T: DeserializeOwned>(&self, contents: &str) -> Result<T> {
+        Ok(match self {
+            ConfigFormat::Json => serde_json::from_str(contents)?,
+            ConfigFormat::Yaml => serde_yaml::from_str(contents)?,
+            ConfigFormat::Toml => toml::from_str(contents)?,
+        })
+    }
+
+    /// Serializes a configuration as text in this format
+    pub fn to_string<T: Serialize>(&self, value: &T) -> Result<String> {
+        Ok(match self {
+            ConfigFormat::Json => serde_json::to_string_pretty(value)?,
+            ConfigFormat::Yaml => serde_yaml::to_string(value)?,
+            ConfigFormat::Toml => toml::to_string_pretty(value)?,
+        })
+    }
+
+    /// Serializes a configuration, checking that parsing the text back
+    /// gives the same configuration
+    ///
+    /// Fails if the format cannot hold a value, e.g. a seed above
+    /// `i64::MAX` in TOML.
+    pub fn to_string_lossless<T: Serialize + DeserializeOwned>(&self, value: &T) -> Result<String> {
+        let text = self
+            .to_string(value)
+            .with_context(|| format!("Failed to write the configuration as {}", self))?;
+        let parsed: T = self.parse(&text)?;
+        anyhow::ensure!(
+            serde_json::to_value(&parsed)? == serde_json::to_value(value)?,
+            "The configuration would change when written as {}",
+            self
+        );
+        Ok(text)
+    }
+}
+
+impl fmt::Display for ConfigFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigFormat::Json => write!(f, "JSON"),
+            ConfigFormat::Yaml => write!(f, "YAML"),
+            ConfigFormat::Toml => write!(f, "TOML"),
+        }
+    }
+}
+
+impl std::str::FromStr for ConfigFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "json" => Ok(ConfigFormat::Json),
+            "yaml" | "yml" => Ok(ConfigFormat::Yaml),
+            "toml" => Ok(ConfigFormat::Toml),
+            _ => anyhow::bail!("Invalid config format: {}. Use 'json', 'yaml', or 'toml'", s),
+        }
+    }
+}
+
+/// Reads a configuration file in the format its extension names
+///
+/// `what` names the configuration in errors (e.g., "deck config").
+pub(crate) fn read_file<T: DeserializeOwned>(path: &Path, what: &str) -> Result<T> {
+    read_file_as(path, ConfigFormat::from_path(path), what)
+}
+
+/// Reads a configuration file in the given format, whatever its extension
+pub(crate) fn read_file_as<T: DeserializeOwned>(
+    path: &Path,
+    format: ConfigFormat,
+    what: &str,
+) -> Result<T> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {} from {:?}", what, path))?;
+    format
+        .parse(&contents)
+        .with_context(|| format!("Failed to parse {} {}", what, format))
+}
+
+/// Writes a configuration file in the format its extension names
+pub(crate) fn write_file<T: Serialize>(path: &Path, value: &T, what: &str) -> Result<()> {
+    write_file_as(path, ConfigFormat::from_path(path), value, what)
+}
+
+/// Writes a configuration file in the given format, whatever its extension
+pub(crate) fn write_file_as<T: Serialize>(
+    path: &Path,
+    format: ConfigFormat,
+    value: &T,
+    what: &str,
+) -> Result<()> {
+    let contents = format
+        .to_string(value)
+        .with_context(|| format!("Failed to serialize {}", what))?;
+    fs::write(path, contents).with_context(|| format!("Failed to write {} to {:?}", what, path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(ConfigFormat::from_path("deck.TOML"), ConfigFormat::Toml);
+        assert_eq!(ConfigFormat::from_path("state.yml"), ConfigFormat::Yaml);
+        assert_eq!(ConfigFormat::from_path("deck.json"), ConfigFormat::Json);
+        assert_eq!(ConfigFormat::from_path("deck"), ConfigFormat::Json);
+        assert_eq!(ConfigFormat::Toml.path_for("decks/deck.json"), Path::new("decks/deck.toml"));
+        assert_eq!("YML".parse::<ConfigFormat>().unwrap(), ConfigFormat::Yaml);
+        assert!("xml".parse::<ConfigFormat>().is_err());
+    }
+
+    #[test]
+    fn test_lossless_check() {
+        #[derive(Serialize, serde::Deserialize)]
+        struct Seeded {
+            seed: u64,
+        }
+
+        let small = Seeded { seed: 42 };
+        assert!(ConfigFormat::Toml.to_string_lossless(&small).is_ok());
+        let large = Seeded { seed: u64::MAX };
+        assert!(ConfigFormat::Yaml.to_string_lossless(&large).is_ok());
+        assert!(ConfigFormat::Toml.to_string_lossless(&large).is_err());
+    }
+}
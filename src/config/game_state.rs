@@ -3,10 +3,20 @@
 //! This module handles loading and saving complete game state configurations,
 //! including decks, jokers, consumables, vouchers, and blind conditions.
 
+use super::format::{self, ConfigFormat};
+use super::DeckConfig;
+pub use crate::core::blind::{BlindConfig, BlindType};
+use crate::core::blind::BossBlind;
+use crate::core::consumable::{Consumable, Planet};
+use crate::core::deck::DeckType;
+use crate::core::hand_levels::HandLevels;
 use crate::core::joker::Joker;
+use crate::core::names::unknown_name;
+use crate::core::pools;
+use crate::core::run::GameRunState;
+use crate::core::stake::Stake;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::fs;
 use std::path::Path;
 
 /// Represents a complete game state
@@ -28,6 +38,10 @@ pub struct GameState {
     #[serde(default)]
     pub vouchers: Vec<String>,
 
+    /// Hand levels from Planet upgrades (e.g., {"Flush": 5})
+    #[serde(default, skip_serializing_if = "HandLevels::is_empty")]
+    pub hand_levels: HandLevels,
+
     /// Current blind configuration
     #[serde(skip_serializing_if = "Option::is_none")]
     pub blind: Option<BlindConfig>,
@@ -43,39 +57,16 @@ pub struct GameState {
     /// Current ante/round
     #[serde(default = "default_ante")]
     pub ante: u32,
+
+    /// Stake difficulty (e.g., "gold")
+    #[serde(default)]
+    pub stake: Stake,
 }
 
 fn default_ante() -> u32 {
     1
 }
 
-/// Configuration for a blind
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct BlindConfig {
-    /// Type of blind (small, big, boss)
-    pub blind_type: BlindType,
-
-    /// Name of the blind (for boss blinds)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub name: Option<String>,
-
-    /// Required score to beat the blind
-    pub score_required: u64,
-
-    /// Special ability description (for boss blinds)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub ability: Option<String>,
-}
-
-/// Type of blind
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
-pub enum BlindType {
-    Small,
-    Big,
-    Boss,
-}
-
 impl GameState {
     /// Creates a new empty game state
     pub fn new() -> Self {
@@ -84,84 +75,142 @@ impl GameState {
             jokers: Vec::new(),
             consumables: Vec::new(),
             vouchers: Vec::new(),
+            hand_levels: HandLevels::new(),
             blind: None,
             seed: None,
             money: 0,
             ante: 1,
+            stake: Stake::White,
         }
     }
 
-    /// Loads a game state from a JSON file
+    /// Loads a game state from a JSON, YAML, or TOML file, going by its
+    /// extension
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let contents = fs::read_to_string(&path)
-            .with_context(|| format!("Failed to read game state from {:?}", path.as_ref()))?;
-
-        let state: GameState = serde_json::from_str(&contents)
-            .context("Failed to parse game state JSON")?;
+        format::read_file(path.as_ref(), "game state")
+    }
 
-        Ok(state)
+    /// Loads a game state from a file in the given format
+    pub fn from_file_as<P: AsRef<Path>>(path: P, format: ConfigFormat) -> Result<Self> {
+        format::read_file_as(path.as_ref(), format, "game state")
     }
 
-    /// Saves a game state to a JSON file
+    /// Saves a game state to a JSON, YAML, or TOML file, going by its
+    /// extension
     pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
-        let json = serde_json::to_string_pretty(self)
-            .context("Failed to serialize game state")?;
+        format::write_file(path.as_ref(), self, "game state")
+    }
 
-        fs::write(&path, json)
-            .with_context(|| format!("Failed to write game state to {:?}", path.as_ref()))?;
+    /// Saves a game state to a file in the given format
+    pub fn to_file_as<P: AsRef<Path>>(&self, path: P, format: ConfigFormat) -> Result<()> {
+        format::write_file_as(path.as_ref(), format, self, "game state")
+    }
 
-        Ok(())
+    /// Parses the Planet cards among the consumables
+    ///
+    /// Consumables that are not planets (tarots, spectrals) are skipped.
+    pub fn planets(&self) -> Vec<Planet> {
+        self.consumables
+            .iter()
+            .filter_map(|name| name.parse().ok())
+            .collect()
+    }
+
+    /// Parses the consumables the solver can use before playing
+    ///
+    /// Consumables whose effect is not modeled are skipped.
+    pub fn consumable_cards(&self) -> Vec<Consumable> {
+        self.consumables
+            .iter()
+            .filter_map(|name| name.parse().ok())
+            .collect()
     }
 
     /// Parses joker names into Joker objects
+    ///
+    /// Jokers with a per-round target can pin it after a colon, e.g.
+    /// "The Idol:KH" or "Ancient Joker:Spades".
     pub fn parse_jokers(&self) -> Result<Vec<Joker>> {
-        // TODO: Implement joker name parsing
-        // For now, return empty vector
-        Ok(Vec::new())
+        self.jokers
+            .iter()
+            .map(|name| name.parse())
+            .collect()
     }
-}
 
-impl Default for GameState {
-    fn default() -> Self {
-        Self::new()
+    /// Parses the boss blind named in the blind configuration
+    ///
+    /// Boss blinds whose effect is not modeled (e.g., The Hook) are skipped.
+    pub fn boss_blind(&self) -> Option<BossBlind> {
+        self.blind.as_ref().and_then(BlindConfig::boss_blind)
     }
-}
 
-impl BlindConfig {
-    /// Creates a new blind configuration
-    pub fn new(blind_type: BlindType, score_required: u64) -> Self {
-        Self {
-            blind_type,
-            name: None,
-            score_required,
-            ability: None,
+    /// Validates the jokers, vouchers, and boss blind names
+    ///
+    /// Every voucher and boss blind in the game is accepted, including those
+    /// whose effect is not modeled; a misspelled name is reported with the
+    /// closest names.
+    pub fn validate(&self) -> Result<()> {
+        self.parse_jokers()?;
+
+        let normalize = |name: &str| -> String {
+            name.chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+                .to_lowercase()
+        };
+        let known = |names: &[&str], name: &str| {
+            names.iter().any(|known| normalize(known) == normalize(name))
+        };
+
+        let vouchers: Vec<&str> = pools::VOUCHERS.iter().map(|voucher| voucher.name).collect();
+        if let Some(voucher) = self.vouchers.iter().find(|voucher| !known(&vouchers, voucher)) {
+            return Err(unknown_name("voucher", voucher, &vouchers, normalize));
         }
-    }
 
-    /// Creates a small blind with the given score requirement
-    pub fn small(score_required: u64) -> Self {
-        Self::new(BlindType::Small, score_required)
+        let bosses: Vec<&str> = pools::BOSSES.iter().map(|boss| boss.name).collect();
+        if let Some(BlindConfig { blind_type: BlindType::Boss, name: Some(name), .. }) = &self.blind
+            && !known(&bosses, name)
+            && name.parse::<BossBlind>().is_err()
+        {
+            return Err(unknown_name("boss blind", name, &bosses, normalize));
+        }
+        Ok(())
     }
 
-    /// Creates a big blind with the given score requirement
-    pub fn big(score_required: u64) -> Self {
-        Self::new(BlindType::Big, score_required)
-    }
+    /// Builds the run state this configuration describes
+    ///
+    /// The deck is loaded from `deck_path` if set, otherwise a standard deck
+    /// is used. Hands and discards start at the deck's and stake's defaults.
+    pub fn to_run_state(&self) -> Result<GameRunState> {
+        self.validate()?;
+        let (deck_type, cards) = match &self.deck_path {
+            Some(deck_path) => {
+                let deck_config = DeckConfig::from_file(deck_path)
+                    .with_context(|| format!("Failed to load deck config from {}", deck_path))?;
+                (deck_config.deck_type.unwrap_or_default(), Some(deck_config.to_cards()?))
+            }
+            None => (DeckType::default(), None),
+        };
 
-    /// Creates a boss blind with the given name and score requirement
-    pub fn boss(name: String, score_required: u64) -> Self {
-        Self {
-            blind_type: BlindType::Boss,
-            name: Some(name),
-            score_required,
-            ability: None,
+        let mut run = GameRunState::new(deck_type, self.stake)
+            .with_jokers(self.parse_jokers()?)
+            .with_hand_levels(self.hand_levels.clone());
+        if let Some(cards) = cards {
+            run = run.with_deck(cards);
         }
+        run.consumables = self.consumables.clone();
+        run.vouchers = self.vouchers.clone();
+        run.money = self.money;
+        run.ante = self.ante;
+        run.boss_blind = self.boss_blind();
+
+        Ok(run)
     }
+}
 
-    /// Adds an ability description to this blind
-    pub fn with_ability(mut self, ability: String) -> Self {
-        self.ability = Some(ability);
-        self
+impl Default for GameState {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -206,4 +255,134 @@ mod tests {
         assert_eq!(deserialized.jokers.len(), 2);
         assert_eq!(deserialized.seed, Some(12345));
     }
+
+    #[test]
+    fn test_round_trips_every_format() {
+        use crate::config::ConfigFormat;
+
+        let json = r#"{
+            "deck_path": "decks/plasma.toml",
+            "jokers": ["Joker", "The Idol:QS"],
+            "hand_levels": {"Flush": 3},
+            "blind": {"blind_type": "boss", "name": "The Wall", "score_required": 8000},
+            "seed": 7,
+            "money": 25,
+            "ante": 4,
+            "stake": "gold"
+        }"#;
+        let state: GameState = serde_json::from_str(json).unwrap();
+
+        let original = serde_json::to_value(&state).unwrap();
+        for format in [ConfigFormat::Json, ConfigFormat::Yaml, ConfigFormat::Toml] {
+            let text = format.to_string_lossless(&state).unwrap();
+            let parsed: GameState = format.parse(&text).unwrap();
+            assert_eq!(serde_json::to_value(&parsed).unwrap(), original, "{}", format);
+        }
+    }
+
+    #[test]
+    fn test_planet_consumables() {
+        let state = GameState {
+            consumables: vec!["Jupiter".to_string(), "The_Fool".to_string(), "Planet_X".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(state.planets(), vec![Planet::Jupiter, Planet::PlanetX]);
+        assert_eq!(state.consumable_cards().len(), 2); // The Fool is not modeled
+    }
+
+    #[test]
+    fn test_hand_levels_deserialization() {
+        use crate::core::hand::HandType;
+
+        let json = r#"{"hand_levels": {"Flush": 5}}"#;
+        let state: GameState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.hand_levels.level(HandType::Flush), 5);
+        assert_eq!(state.hand_levels.level(HandType::Pair), 1);
+    }
+
+    #[test]
+    fn test_parse_jokers() {
+        use crate::core::card::Rank;
+        use crate::core::joker::JokerKind;
+
+        let state = GameState {
+            jokers: vec!["Greedy_Joker".to_string(), "The Idol:QS".to_string()],
+            ..Default::default()
+        };
+        let jokers = state.parse_jokers().unwrap();
+        assert_eq!(jokers[0].kind, JokerKind::GreedyJoker);
+        assert_eq!(jokers[1].target_rank, Some(Rank::Queen));
+
+        let state = GameState {
+            jokers: vec!["Not A Joker".to_string()],
+            ..Default::default()
+        };
+        assert!(state.parse_jokers().is_err());
+    }
+
+    #[test]
+    fn test_validate_names() {
+        let state = GameState {
+            jokers: vec!["Joker".to_string()],
+            vouchers: vec!["Tarot_Merchant".to_string(), "Overstock Plus".to_string()],
+            blind: Some(BlindConfig::boss("The Hook".to_string(), 2_000)),
+            ..Default::default()
+        };
+        assert!(state.validate().is_ok());
+
+        let error = |state: GameState| state.validate().unwrap_err().to_string();
+        let joker = GameState { jokers: vec!["Greedy Jokr".to_string()], ..Default::default() };
+        assert_eq!(error(joker), "Unknown joker: Greedy Jokr (did you mean Greedy Joker?)");
+        let voucher = GameState { vouchers: vec!["Overstok".to_string()], ..Default::default() };
+        assert_eq!(error(voucher), "Unknown voucher: Overstok (did you mean Overstock?)");
+        let boss = GameState {
+            blind: Some(BlindConfig::boss("The Hok".to_string(), 2_000)),
+            ..Default::default()
+        };
+        assert!(error(boss.clone()).starts_with("Unknown boss blind: The Hok (did you mean"));
+        assert!(boss.to_run_state().is_err());
+    }
+
+    #[test]
+    fn test_to_run_state() {
+        use crate::core::hand::HandType;
+
+        let json = r#"{
+            "jokers": ["Joker", "Baron"],
+            "vouchers": ["Overstock"],
+            "hand_levels": {"Flush": 3},
+            "money": 25,
+            "ante": 4,
+            "stake": "blue"
+        }"#;
+        let state: GameState = serde_json::from_str(json).unwrap();
+        let run = state.to_run_state().unwrap();
+
+        assert_eq!(run.jokers.len(), 2);
+        assert_eq!(run.deck.len(), 52);
+        assert_eq!(run.hand_levels.level(HandType::Flush), 3);
+        assert_eq!(run.vouchers, vec!["Overstock".to_string()]);
+        assert_eq!((run.money, run.ante, run.stake), (25, 4, Stake::Blue));
+        assert_eq!(run.discards_remaining, 3);
+
+        let state = GameState {
+            blind: Some(BlindConfig::boss("The Wall".to_string(), 8_000)),
+            ..Default::default()
+        };
+        assert_eq!(state.to_run_state().unwrap().boss_blind, Some(BossBlind::TheWall));
+
+        let state = GameState {
+            deck_path: Some("does/not/exist.json".to_string()),
+            ..Default::default()
+        };
+        assert!(state.to_run_state().is_err());
+    }
+
+    #[test]
+    fn test_stake_deserialization() {
+        let json = r#"{"stake": "gold"}"#;
+        let state: GameState = serde_json::from_str(json).unwrap();
+        assert_eq!(state.stake, Stake::Gold);
+        assert_eq!(GameState::new().stake, Stake::White);
+    }
 }
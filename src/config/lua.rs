@@ -0,0 +1,309 @@
+//! Lua table literals
+//!
+//! Balatro saves its runs as a Lua chunk that returns one table literal,
+//! e.g. `return {["GAME"]={["dollars"]=4,},}`. This module parses such a
+//! chunk into plain values. Only literals are understood: no expressions,
+//! function calls, or variables other than `inf` and `nan`.
+
+use anyhow::Result;
+
+/// A value in a Lua table literal
+#[derive(Debug, Clone, PartialEq)]
+pub enum LuaValue {
+    Nil,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    /// Entries in the order they are written
+    Table(Vec<(LuaValue, LuaValue)>),
+}
+
+impl LuaValue {
+    /// Looks up a string key in a table
+    pub fn get(&self, key: &str) -> Option<&LuaValue> {
+        match self {
+            LuaValue::Table(entries) => entries
+                .iter()
+                .find(|(k, _)| matches!(k, LuaValue::String(k) if k == key))
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Follows a path of string keys through nested tables
+    pub fn path(&self, keys: &[&str]) -> Option<&LuaValue> {
+        keys.iter().try_fold(self, |value, key| value.get(key))
+    }
+
+    /// Returns a table's entries with string keys
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &LuaValue)> {
+        let entries = match self {
+            LuaValue::Table(entries) => entries.as_slice(),
+            _ => &[],
+        };
+        entries.iter().filter_map(|(key, value)| match key {
+            LuaValue::String(key) => Some((key.as_str(), value)),
+            _ => None,
+        })
+    }
+
+    /// Returns a table's entries with number keys, in key order (Lua
+    /// writes a list's entries in no particular order)
+    pub fn items(&self) -> Vec<&LuaValue> {
+        let mut items: Vec<(f64, &LuaValue)> = match self {
+            LuaValue::Table(entries) => entries
+                .iter()
+                .filter_map(|(key, value)| match key {
+                    LuaValue::Number(index) => Some((*index, value)),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        items.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+        items.into_iter().map(|(_, value)| value).collect()
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            LuaValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            LuaValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            LuaValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+}
+
+/// Parses a chunk of the form `return <value>` (the `return` is optional)
+pub fn parse(chunk: &str) -> Result<LuaValue> {
+    let mut parser = Parser { bytes: chunk.as_bytes(), pos: 0 };
+    parser.skip_whitespace();
+    if parser.bytes[parser.pos..].starts_with(b"return") {
+        parser.pos += "return".len();
+    }
+    let value = parser.value()?;
+    parser.skip_whitespace();
+    anyhow::ensure!(
+        parser.pos == parser.bytes.len(),
+        "Unexpected text after the table at byte {}",
+        parser.pos
+    );
+    Ok(value)
+}
+
+/// Reads values from the bytes of a chunk
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    /// Consumes `expected`, after any whitespace
+    fn expect(&mut self, expected: u8) -> Result<()> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b) if b == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(b) => anyhow::bail!(
+                "Expected '{}' at byte {}, found '{}'",
+                expected as char,
+                self.pos,
+                b as char
+            ),
+            None => anyhow::bail!("Expected '{}', found the end of the file", expected as char),
+        }
+    }
+
+    fn value(&mut self) -> Result<LuaValue> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(b'{') => self.table(),
+            Some(b'"' | b'\'') => Ok(LuaValue::String(self.string()?)),
+            Some(b) if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_') => {
+                let word = self.word();
+                match word {
+                    "true" => Ok(LuaValue::Bool(true)),
+                    "false" => Ok(LuaValue::Bool(false)),
+                    "nil" => Ok(LuaValue::Nil),
+                    // Lua writes infinities and NaN as "inf", "-nan", etc.
+                    _ => word
+                        .parse()
+                        .map(LuaValue::Number)
+                        .map_err(|_| anyhow::anyhow!("Invalid value: {}", word)),
+                }
+            }
+            Some(b) => anyhow::bail!("Unexpected '{}' at byte {}", b as char, self.pos),
+            None => anyhow::bail!("Expected a value, found the end of the file"),
+        }
+    }
+
+    /// Reads a run of characters that can make up a number or a name
+    fn word(&mut self) -> &str {
+        let start = self.pos;
+        while self
+            .peek()
+            .is_some_and(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'+' | b'.' | b'_'))
+        {
+            self.pos += 1;
+        }
+        // Only ASCII was consumed
+        std::str::from_utf8(&self.bytes[start..self.pos]).unwrap_or_default()
+    }
+
+    fn table(&mut self) -> Result<LuaValue> {
+        self.expect(b'{')?;
+        let mut entries = Vec::new();
+        let mut next_index = 1.0;
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(b'}') {
+                self.pos += 1;
+                return Ok(LuaValue::Table(entries));
+            }
+
+            let entry = if self.peek() == Some(b'[') {
+                self.pos += 1;
+                let key = self.value()?;
+                self.expect(b']')?;
+                self.expect(b'=')?;
+                (key, self.value()?)
+            } else if let Some(name) = self.name_key() {
+                (LuaValue::String(name), self.value()?)
+            } else {
+                let key = LuaValue::Number(next_index);
+                next_index += 1.0;
+                (key, self.value()?)
+            };
+            entries.push(entry);
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',' | b';') => self.pos += 1,
+                Some(b'}') => {}
+                _ => self.expect(b'}')?,
+            }
+        }
+    }
+
+    /// Reads a bare name key and its '=' (`name = value`), leaving the
+    /// position unchanged if there is none
+    fn name_key(&mut self) -> Option<String> {
+        let start = self.pos;
+        let word = self.word().to_string();
+        self.skip_whitespace();
+        let is_name = word.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && word.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if is_name && self.peek() == Some(b'=') {
+            self.pos += 1;
+            return Some(word);
+        }
+        self.pos = start;
+        None
+    }
+
+    /// Reads a quoted string, with Lua's escapes
+    fn string(&mut self) -> Result<String> {
+        let quote = self.bytes[self.pos];
+        self.pos += 1;
+        let mut bytes = Vec::new();
+        loop {
+            let Some(b) = self.peek() else {
+                anyhow::bail!("Unterminated string");
+            };
+            self.pos += 1;
+            match b {
+                b if b == quote => break,
+                b'\\' => {
+                    let Some(escaped) = self.peek() else {
+                        anyhow::bail!("Unterminated string");
+                    };
+                    self.pos += 1;
+                    match escaped {
+                        b'n' | b'\n' => bytes.push(b'\n'),
+                        b't' => bytes.push(b'\t'),
+                        b'r' => bytes.push(b'\r'),
+                        b'a' => bytes.push(0x07),
+                        b'b' => bytes.push(0x08),
+                        b'f' => bytes.push(0x0c),
+                        b'v' => bytes.push(0x0b),
+                        b'0'..=b'9' => {
+                            // Up to three decimal digits
+                            let mut code = u32::from(escaped - b'0');
+                            for _ in 0..2 {
+                                match self.peek() {
+                                    Some(digit @ b'0'..=b'9') => {
+                                        code = code * 10 + u32::from(digit - b'0');
+                                        self.pos += 1;
+                                    }
+                                    _ => break,
+                                }
+                            }
+                            let byte = u8::try_from(code)
+                                .map_err(|_| anyhow::anyhow!("Invalid escape: \\{}", code))?;
+                            bytes.push(byte);
+                        }
+                        other => bytes.push(other),
+                    }
+                }
+                b => bytes.push(b),
+            }
+        }
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_table() {
+        let table = parse(
+            r#"return {["name"]="Say \"hi\"\10",["list"]={[2]="b",[1]="a",},[3]=-1.5e2,
+                flag=true, ["none"]=nil, ["big"]=inf, "implicit"}"#,
+        )
+        .unwrap();
+        assert_eq!(table.get("name").and_then(LuaValue::as_str), Some("Say \"hi\"\n"));
+        let list: Vec<&str> =
+            table.get("list").unwrap().items().iter().filter_map(|v| v.as_str()).collect();
+        assert_eq!(list, vec!["a", "b"]);
+        assert_eq!(table.items()[0].as_str(), Some("implicit"));
+        assert_eq!(table.items()[1].as_f64(), Some(-150.0));
+        assert_eq!(table.get("flag").and_then(LuaValue::as_bool), Some(true));
+        assert_eq!(table.get("none"), Some(&LuaValue::Nil));
+        assert_eq!(table.get("big").and_then(LuaValue::as_f64), Some(f64::INFINITY));
+        assert_eq!(table.path(&["list", "missing"]), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse("return {[\"a\"]=}").is_err());
+        assert!(parse("return {\"unterminated}").is_err());
+        assert!(parse("return {} extra").is_err());
+        assert!(parse("return {[1]=os.exit()}").is_err());
+    }
+}
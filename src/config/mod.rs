@@ -1,11 +1,21 @@
 //! Configuration file handling
 //!
 //! This module handles loading, saving, and validating configuration files
-//! for decks, game states, and presets.
+//! for decks, game states, and presets, and the user configuration of
+//! command defaults.
 
+pub mod balatro_save;
 pub mod deck;
+pub mod diff;
+pub mod format;
 pub mod game_state;
+mod lua;
+pub mod user;
 
 // Re-export commonly used types
+pub use balatro_save::BalatroSave;
 pub use deck::DeckConfig;
+pub use diff::{diff_decks, diff_game_states, ChangeKind, ConfigChange};
+pub use format::ConfigFormat;
 pub use game_state::GameState;
+pub use user::UserConfig;
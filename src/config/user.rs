@@ -0,0 +1,147 @@
+//! User configuration
+//!
+//! The user configuration, `~/.config/jimbo/config.toml`, holds defaults for
+//! the flags many commands share (--runs, --hand-size, --output, --threads)
+//! and named joker sets that a --jokers list can use as `@name`. Flags given
+//! on the command line always win over these defaults.
+
+use super::format;
+use crate::core::names::unknown_name;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming the user configuration file, overriding the
+/// default location
+pub const CONFIG_VAR: &str = "JIMBO_CONFIG";
+
+/// Defaults and joker sets from the user configuration
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UserConfig {
+    /// Default for --runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runs: Option<usize>,
+
+    /// Default for --hand-size
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hand_size: Option<usize>,
+
+    /// Default for --output where it picks a report format (e.g., "json")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+
+    /// Default for --threads
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threads: Option<u16>,
+
+    /// Joker sets by name, e.g. `flush = ["Droll Joker", "Four Fingers"]`
+    /// for `@flush`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub jokers: BTreeMap<String, Vec<String>>,
+}
+
+impl UserConfig {
+    /// Returns where the user configuration lives: $JIMBO_CONFIG, else
+    /// `jimbo/config.toml` under $XDG_CONFIG_HOME or `~/.config`
+    ///
+    /// Returns `None` when none of these variables is set.
+    pub fn path() -> Option<PathBuf> {
+        let var = |name| std::env::var_os(name).filter(|value| !value.is_empty());
+        if let Some(path) = var(CONFIG_VAR) {
+            return Some(PathBuf::from(path));
+        }
+        let config_home = var("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| var("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+        Some(config_home.join("jimbo").join("config.toml"))
+    }
+
+    /// Loads the user configuration, empty if there is none
+    pub fn load() -> Result<Self> {
+        Self::path().map_or_else(|| Ok(Self::default()), Self::load_from)
+    }
+
+    /// Loads a user configuration file, empty if the file doesn't exist
+    pub fn load_from<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        format::read_file(path, "user config")
+    }
+
+    /// Saves the user configuration, creating its directory if needed
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create directory {:?}", dir))?;
+        }
+        format::write_file(path, self, "user config")
+    }
+
+    /// Returns the jokers in a named set
+    pub fn joker_set(&self, name: &str) -> Result<&[String]> {
+        match self.jokers.get(name) {
+            Some(jokers) => Ok(jokers),
+            None => {
+                let names: Vec<&str> = self.jokers.keys().map(String::as_str).collect();
+                Err(unknown_name("joker set", &format!("@{}", name), &names, |name| {
+                    name.trim_start_matches('@').to_lowercase()
+                }))
+            }
+        }
+    }
+
+    /// Replaces each `@name` in a joker list with the jokers of that set
+    pub fn expand_jokers(&self, names: &[String]) -> Result<Vec<String>> {
+        let mut jokers = Vec::new();
+        for name in names {
+            match name.trim().strip_prefix('@') {
+                Some(set) => jokers.extend_from_slice(self.joker_set(set)?),
+                None => jokers.push(name.clone()),
+            }
+        }
+        Ok(jokers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_jokers() {
+        let mut config = UserConfig::default();
+        let flush = vec!["Droll Joker".to_string(), "Four Fingers".to_string()];
+        config.jokers.insert("flush".into(), flush);
+        let names = ["Joker".to_string(), " @flush".to_string()];
+        let jokers = config.expand_jokers(&names).unwrap();
+        assert_eq!(jokers, ["Joker", "Droll Joker", "Four Fingers"]);
+
+        let error = config.expand_jokers(&["@flsh".to_string()]).unwrap_err();
+        assert_eq!(error.to_string(), "Unknown joker set: @flsh (did you mean flush?)");
+    }
+
+    #[test]
+    fn test_user_config_file() {
+        let dir = std::env::temp_dir().join(format!("jimbo-user-config-{}", std::process::id()));
+        let path = dir.join("jimbo").join("config.toml");
+        assert_eq!(UserConfig::load_from(&path).unwrap(), UserConfig::default());
+
+        let mut config = UserConfig { runs: Some(5000), ..UserConfig::default() };
+        config.output = Some("json".into());
+        config.jokers.insert("mult".into(), vec!["Joker".into()]);
+        config.to_file(&path).unwrap();
+        let text = std::fs::read_to_string(&path).unwrap();
+        assert!(text.contains("runs = 5000") && !text.contains("threads"), "{}", text);
+        assert_eq!(UserConfig::load_from(&path).unwrap(), config);
+
+        std::fs::write(&path, "hand-size = 9\n").unwrap();
+        assert!(UserConfig::load_from(&path).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
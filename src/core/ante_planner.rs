@@ -0,0 +1,406 @@
+//! Ante planner
+//!
+//! This module looks ahead over the three blinds of an ante from its start:
+//! how the build scores against each blind, how to play the boss, and
+//! whether skipping the Small or Big blind for its tag leaves the run
+//! better off. Each blind is simulated as a round of its own; skips are
+//! rated by playing out the rest of the run both ways, with the shop, on
+//! the same seeds.
+
+use super::blind::{BlindType, BossBlind};
+use super::card::Card;
+use super::hand::HandType;
+use super::planner::{RoundPlan, RoundPlanner, DEFAULT_ROLLOUTS};
+use super::run::GameRunState;
+use super::score::Score;
+use super::shop::ShopModel;
+use super::simulator::{PairedDifference, PlayOut, SimulationResult, Simulator, TargetProbability};
+use super::tag::Tag;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Default number of rounds simulated per blind, and of run play-outs per
+/// skip decision and choice
+pub const DEFAULT_PLAN_RUNS: usize = 500;
+
+/// How the build is expected to fare against one blind of the ante
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlindOutlook {
+    pub blind_type: BlindType,
+    /// The boss blind, for a Boss blind whose boss is known
+    pub boss: Option<BossBlind>,
+    pub score_required: u64,
+    /// Money paid for beating the blind
+    pub reward: u32,
+    /// Mean score of a round playing every hand
+    pub mean_score: f64,
+    pub median_score: Score,
+    /// Chance of reaching the blind's requirement
+    pub clear: TargetProbability,
+}
+
+/// How to play the boss blind
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BossAdvice {
+    /// The boss, if known
+    pub boss: Option<BossBlind>,
+    /// Hand type played most in the simulated rounds that beat the boss,
+    /// and its share of those rounds' hands (`None` if no round did)
+    pub aim_for: Option<(HandType, f64)>,
+    /// Cards in the deck the boss debuffs; they score nothing, so they are
+    /// the first to discard
+    pub debuffed_cards: usize,
+    /// What to do with the hand dealt against the boss, if one was given
+    pub next_action: Option<RoundPlan>,
+}
+
+/// How runs fared from one choice at a blind
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RunLine {
+    /// Fraction of runs that beat the ante
+    pub ante_clear_rate: f64,
+    /// Fraction of runs that beat the final ante
+    pub win_rate: f64,
+    /// Mean blinds beaten or skipped from the start of the ante
+    pub mean_blinds_passed: f64,
+}
+
+impl RunLine {
+    /// Gathers the play-outs of one choice
+    fn new(ante: u32, play_outs: &[PlayOut]) -> Self {
+        let n = play_outs.len().max(1) as f64;
+        let count = |passed: &dyn Fn(&PlayOut) -> bool| {
+            play_outs.iter().filter(|play_out| passed(play_out)).count() as f64 / n
+        };
+        Self {
+            ante_clear_rate: count(&|play_out| play_out.lost_at.is_none_or(|lost| lost > ante)),
+            win_rate: count(&|play_out| play_out.lost_at.is_none()),
+            mean_blinds_passed: play_outs.iter().map(|p| p.blinds_passed as f64).sum::<f64>() / n,
+        }
+    }
+}
+
+/// What the play-outs say about skipping a blind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkipVerdict {
+    /// Skipped runs got significantly further
+    Skip,
+    /// Played runs got significantly further
+    Play,
+    /// Neither choice got significantly further
+    Either,
+}
+
+/// Whether to skip a Small or Big blind for its tag
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkipOutlook {
+    pub blind_type: BlindType,
+    /// The tag offered for skipping, if given
+    pub tag: Option<Tag>,
+    /// Runs that played the blind
+    pub play: RunLine,
+    /// Runs that skipped it and took the tag
+    pub skip: RunLine,
+    /// Blinds passed by skipping minus by playing, run by run
+    pub difference: PairedDifference,
+    pub verdict: SkipVerdict,
+}
+
+/// The plan for an ante
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AntePlan {
+    pub ante: u32,
+    /// The Small, Big, and Boss blinds, in order
+    pub blinds: Vec<BlindOutlook>,
+    /// Chance of beating all three blinds, each dealt from a fresh deck
+    pub ante_clear_rate: f64,
+    pub boss: BossAdvice,
+    /// Skipping the Small blind, then skipping the Big blind
+    pub skips: Vec<SkipOutlook>,
+    /// Rounds simulated per blind, and play-outs per choice
+    pub runs: usize,
+}
+
+/// Plans an ante from its start
+///
+/// Blinds are simulated with the run's deck, hands, and discards at the
+/// deck type's hand size; a boss that isn't known is planned as a plain
+/// Boss blind. Skips are rated with the game's shop, so the money and shop
+/// a skip gives up count against it.
+pub struct AntePlanner {
+    run: GameRunState,
+    runs: usize,
+    seed: u64,
+    small_tag: Option<Tag>,
+    big_tag: Option<Tag>,
+    boss_hand: Option<Vec<Card>>,
+    shop: ShopModel,
+}
+
+impl AntePlanner {
+    /// Creates a planner for the run's current ante
+    pub fn new(run: GameRunState) -> Self {
+        Self {
+            run,
+            runs: DEFAULT_PLAN_RUNS,
+            seed: 0,
+            small_tag: None,
+            big_tag: None,
+            boss_hand: None,
+            shop: ShopModel::new(),
+        }
+    }
+
+    /// Sets the rounds simulated per blind (and play-outs per choice) and
+    /// the seed they are dealt from
+    pub fn with_runs(mut self, runs: usize, seed: u64) -> Self {
+        self.runs = runs.max(1);
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the tags the Small and Big blinds offer for skipping them
+    pub fn with_tags(mut self, small: Option<Tag>, big: Option<Tag>) -> Self {
+        self.small_tag = small;
+        self.big_tag = big;
+        self
+    }
+
+    /// Sets the hand dealt against the boss, to recommend what to do with
+    pub fn with_boss_hand(mut self, hand: Vec<Card>) -> Self {
+        self.boss_hand = Some(hand);
+        self
+    }
+
+    /// Sets the shop model skip play-outs buy from
+    pub fn with_shop(mut self, shop: ShopModel) -> Self {
+        self.shop = shop;
+        self
+    }
+
+    /// Plans the ante
+    pub fn plan(&self) -> AntePlan {
+        let mut blinds = Vec::with_capacity(3);
+        let mut boss_rounds = None;
+        for blind_type in [BlindType::Small, BlindType::Big, BlindType::Boss] {
+            let (outlook, rounds) = self.outlook(blind_type);
+            blinds.push(outlook);
+            if blind_type == BlindType::Boss {
+                boss_rounds = Some(rounds);
+            }
+        }
+        let ante_clear_rate = blinds.iter().map(|blind| blind.clear.probability).product();
+        let boss_rounds = boss_rounds.expect("the Boss blind is planned");
+        let boss_required = blinds[2].score_required;
+
+        AntePlan {
+            ante: self.run.ante,
+            ante_clear_rate,
+            boss: self.boss_advice(&boss_rounds, boss_required),
+            skips: vec![
+                self.skip_outlook(BlindType::Small, self.small_tag),
+                self.skip_outlook(BlindType::Big, self.big_tag),
+            ],
+            blinds,
+            runs: self.runs,
+        }
+    }
+
+    /// Returns the run as it plays a blind: with its boss for the Boss
+    /// blind, and without one otherwise
+    fn run_for(&self, blind_type: BlindType) -> GameRunState {
+        let boss = self.run.boss_blind.filter(|_| blind_type == BlindType::Boss);
+        self.run.clone().with_boss_blind(boss)
+    }
+
+    /// Simulates rounds against a blind, playing every hand
+    fn outlook(&self, blind_type: BlindType) -> (BlindOutlook, SimulationResult) {
+        let run = self.run_for(blind_type);
+        let score_required = run.blind_requirement(blind_type);
+        let mut config = run.simulation_config(self.runs, Some(self.seed));
+        config.target_score = Some(Score::from(score_required));
+        config.record_runs = blind_type == BlindType::Boss;
+        let result = Simulator::new(run.solver()).simulate(config);
+
+        let outlook = BlindOutlook {
+            blind_type,
+            boss: run.boss_blind,
+            score_required,
+            reward: blind_type.reward(run.stake),
+            mean_score: result.mean_score,
+            median_score: result.median_score,
+            clear: result.target.expect("a target score was set"),
+        };
+        (outlook, result)
+    }
+
+    /// Advises on the boss from the simulated boss rounds
+    fn boss_advice(&self, rounds: &SimulationResult, score_required: u64) -> BossAdvice {
+        let boss = self.run.boss_blind;
+        let required = Score::from(score_required);
+
+        let mut counts: Vec<(HandType, usize)> = Vec::new();
+        let winning = rounds.runs.iter().filter(|round| round.score >= required);
+        for play in winning.flat_map(|round| &round.plays) {
+            match counts.iter_mut().find(|(hand_type, _)| *hand_type == play.hand_type) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((play.hand_type, 1)),
+            }
+        }
+        let total: usize = counts.iter().map(|(_, count)| count).sum();
+        let aim_for = counts
+            .iter()
+            .max_by_key(|(hand_type, count)| (*count, *hand_type))
+            .map(|&(hand_type, count)| (hand_type, count as f64 / total as f64));
+
+        let next_action = self.boss_hand.as_ref().map(|hand| {
+            let mut deck_remaining = self.run.deck.clone();
+            for card in hand {
+                if let Some(pos) = deck_remaining.iter().position(|c| c == card) {
+                    deck_remaining.remove(pos);
+                }
+            }
+            RoundPlanner::new(self.run_for(BlindType::Boss).solver())
+                .with_rollouts(DEFAULT_ROLLOUTS, self.seed)
+                .plan(hand, &deck_remaining, required)
+        });
+
+        BossAdvice {
+            boss,
+            aim_for,
+            debuffed_cards: boss.map_or(0, |boss| {
+                self.run.deck.iter().filter(|card| boss.debuffs(card)).count()
+            }),
+            next_action,
+        }
+    }
+
+    /// Plays out the run from a blind both ways, on the same seeds
+    fn skip_outlook(&self, blind_type: BlindType, tag: Option<Tag>) -> SkipOutlook {
+        let next = match blind_type {
+            BlindType::Small => BlindType::Big,
+            _ => BlindType::Boss,
+        };
+        let mut skipped = self.run.clone();
+        if let Some(tag) = tag {
+            tag.apply(&mut skipped);
+        }
+
+        let simulator = Simulator::new(self.run.solver()).with_shop(self.shop.clone());
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed ^ next as u64);
+        let seeds: Vec<u64> = (0..self.runs).map(|_| rng.next_u64()).collect();
+        let (played, skips): (Vec<PlayOut>, Vec<PlayOut>) = seeds
+            .into_par_iter()
+            .map(|seed| {
+                let play = simulator.play_out(&self.run, blind_type, 0, &mut rng_for(seed));
+                let skip = simulator.play_out(&skipped, next, 0, &mut rng_for(seed));
+                (play, skip)
+            })
+            .unzip();
+
+        let passed = |play_outs: &[PlayOut]| -> Vec<f64> {
+            play_outs.iter().map(|play_out| play_out.blinds_passed as f64).collect()
+        };
+        let difference = PairedDifference::from_values(&passed(&skips), &passed(&played));
+        let verdict = match (difference.lower > 0.0, difference.upper < 0.0) {
+            (true, _) => SkipVerdict::Skip,
+            (_, true) => SkipVerdict::Play,
+            _ => SkipVerdict::Either,
+        };
+
+        SkipOutlook {
+            blind_type,
+            tag,
+            play: RunLine::new(self.run.ante, &played),
+            skip: RunLine::new(self.run.ante, &skips),
+            difference,
+            verdict,
+        }
+    }
+}
+
+/// Creates the RNG a play-out is dealt from
+fn rng_for(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::card::{Rank, Suit};
+    use crate::core::joker::{Joker, JokerKind};
+
+    #[test]
+    fn test_plan_blinds() {
+        let run = GameRunState::default()
+            .with_jokers(vec![Joker::new(JokerKind::Joker)])
+            .with_boss_blind(Some(BossBlind::TheClub));
+        let plan = AntePlanner::new(run).with_runs(12, 7).plan();
+
+        let required: Vec<u64> = plan.blinds.iter().map(|blind| blind.score_required).collect();
+        assert_eq!(required, [300, 450, 600]);
+        assert_eq!(plan.blinds[2].boss, Some(BossBlind::TheClub));
+        assert_eq!(plan.blinds[0].boss, None);
+        assert!(plan.blinds[0].clear.probability >= plan.blinds[2].clear.probability);
+        let product: f64 = plan.blinds.iter().map(|blind| blind.clear.probability).product();
+        assert_eq!(plan.ante_clear_rate, product);
+
+        assert_eq!(plan.boss.debuffed_cards, 13);
+        assert!(plan.boss.aim_for.is_some_and(|(_, share)| share > 0.0 && share <= 1.0));
+        assert_eq!(plan.boss.next_action, None);
+        assert_eq!(plan.skips.len(), 2);
+        assert_eq!(plan.skips[1].blind_type, BlindType::Big);
+    }
+
+    #[test]
+    fn test_skip_outlook() {
+        // Eight unconnected cards of alternating suits make nothing better
+        // than a High Card, which beats no blind
+        let deck = [
+            (Rank::Two, Suit::Spades),
+            (Rank::Three, Suit::Hearts),
+            (Rank::Five, Suit::Diamonds),
+            (Rank::Seven, Suit::Clubs),
+            (Rank::Nine, Suit::Spades),
+            (Rank::Jack, Suit::Hearts),
+            (Rank::King, Suit::Diamonds),
+            (Rank::Ace, Suit::Clubs),
+        ]
+        .map(|(rank, suit)| Card::new(rank, suit));
+        let run = GameRunState::default().with_deck(deck.to_vec());
+        let planner = AntePlanner::new(run).with_runs(20, 3).with_tags(Some(Tag::Economy), None);
+        let skip = planner.skip_outlook(BlindType::Small, Some(Tag::Economy));
+        assert_eq!(skip.tag, Some(Tag::Economy));
+
+        // Both lines lose their first blind, so skipping is a blind ahead
+        assert_eq!(skip.play.mean_blinds_passed, 0.0);
+        assert_eq!(skip.skip.mean_blinds_passed, 1.0);
+        assert!(skip.difference.lower > 0.0);
+        assert_eq!(skip.verdict, SkipVerdict::Skip);
+    }
+
+    #[test]
+    fn test_boss_hand_advice() {
+        let hand = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+            Card::new(Rank::King, Suit::Hearts),
+            Card::new(Rank::Two, Suit::Spades),
+        ];
+        // A small deck keeps the discard search short
+        let mut deck = hand.clone();
+        deck.extend([Card::new(Rank::King, Suit::Clubs), Card::new(Rank::Nine, Suit::Spades)]);
+        let run = GameRunState::default()
+            .with_deck(deck)
+            .with_boss_blind(Some(BossBlind::TheGoad));
+        let planner = AntePlanner::new(run).with_runs(10, 1).with_boss_hand(hand);
+        let (_, rounds) = planner.outlook(BlindType::Boss);
+        let advice = planner.boss_advice(&rounds, 600);
+        let next = advice.next_action.expect("a boss hand was given");
+        assert!(!next.candidates.is_empty());
+        assert_eq!(advice.debuffed_cards, 3);
+    }
+}
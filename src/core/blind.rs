@@ -0,0 +1,376 @@
+//! Blinds and their score requirements
+//!
+//! Each ante has a Small, Big, and Boss blind. The required score grows
+//! with the ante and faster on higher stakes.
+
+use super::card::{Card, Suit};
+use super::hand::HandType;
+use super::names::unknown_name;
+use super::stake::Stake;
+use serde::{Deserialize, Serialize};
+
+/// Base blind chips for antes 1-8, indexed by stake scaling tier
+const ANTE_BASE_CHIPS: [[u64; 8]; 3] = [
+    [300, 800, 2_000, 5_000, 11_000, 20_000, 35_000, 50_000],
+    [300, 900, 2_600, 8_000, 20_000, 36_000, 60_000, 100_000],
+    [300, 1_000, 3_200, 9_000, 25_000, 60_000, 110_000, 200_000],
+];
+
+/// Type of blind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BlindType {
+    Small,
+    Big,
+    Boss,
+}
+
+impl BlindType {
+    /// Money awarded for beating the blind
+    pub fn reward(&self, stake: Stake) -> u32 {
+        match self {
+            BlindType::Small => stake.small_blind_reward(),
+            BlindType::Big => 4,
+            BlindType::Boss => 5,
+        }
+    }
+
+    /// Multiplier applied to the ante's base chips
+    pub fn multiplier(&self) -> f64 {
+        match self {
+            BlindType::Small => 1.0,
+            BlindType::Big => 1.5,
+            BlindType::Boss => 2.0,
+        }
+    }
+}
+
+impl std::str::FromStr for BlindType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "small" => Ok(BlindType::Small),
+            "big" => Ok(BlindType::Big),
+            "boss" => Ok(BlindType::Boss),
+            _ => anyhow::bail!("Invalid blind type: {}. Use 'small', 'big', or 'boss'", s),
+        }
+    }
+}
+
+/// A boss blind whose effect changes which plays are allowed or how they score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum BossBlind {
+    ThePsychic,   // Must play 5 cards
+    TheEye,       // No repeat hand types this round
+    TheMouth,     // Play only 1 hand type this round
+    TheArm,       // Decrease level of played poker hand
+    TheClub,      // All Club cards are debuffed
+    TheGoad,      // All Spade cards are debuffed
+    TheWindow,    // All Diamond cards are debuffed
+    TheHead,      // All Heart cards are debuffed
+    TheFlint,     // Base chips and mult are halved
+    TheWall,      // Extra large blind (x4 base)
+    VioletVessel, // Very large blind (x6 base)
+}
+
+impl BossBlind {
+    /// Returns every supported boss blind
+    pub fn all() -> Vec<BossBlind> {
+        vec![
+            BossBlind::ThePsychic,
+            BossBlind::TheEye,
+            BossBlind::TheMouth,
+            BossBlind::TheArm,
+            BossBlind::TheClub,
+            BossBlind::TheGoad,
+            BossBlind::TheWindow,
+            BossBlind::TheHead,
+            BossBlind::TheFlint,
+            BossBlind::TheWall,
+            BossBlind::VioletVessel,
+        ]
+    }
+
+    /// Returns the boss blind's name as shown in the game
+    pub fn name(&self) -> &'static str {
+        match self {
+            BossBlind::ThePsychic => "The Psychic",
+            BossBlind::TheEye => "The Eye",
+            BossBlind::TheMouth => "The Mouth",
+            BossBlind::TheArm => "The Arm",
+            BossBlind::TheClub => "The Club",
+            BossBlind::TheGoad => "The Goad",
+            BossBlind::TheWindow => "The Window",
+            BossBlind::TheHead => "The Head",
+            BossBlind::TheFlint => "The Flint",
+            BossBlind::TheWall => "The Wall",
+            BossBlind::VioletVessel => "Violet Vessel",
+        }
+    }
+
+    /// Returns whether a play is allowed under this boss blind
+    ///
+    /// `played_hand_types` holds the hand types already played this round,
+    /// in order.
+    pub fn allows(
+        &self,
+        hand_type: HandType,
+        cards_played: usize,
+        played_hand_types: &[HandType],
+    ) -> bool {
+        match self {
+            BossBlind::ThePsychic => cards_played == 5,
+            BossBlind::TheEye => !played_hand_types.contains(&hand_type),
+            BossBlind::TheMouth => played_hand_types.first().is_none_or(|&first| first == hand_type),
+            _ => true,
+        }
+    }
+
+    /// Returns the suit this boss blind debuffs, if any
+    pub fn debuffed_suit(&self) -> Option<Suit> {
+        match self {
+            BossBlind::TheClub => Some(Suit::Clubs),
+            BossBlind::TheGoad => Some(Suit::Spades),
+            BossBlind::TheWindow => Some(Suit::Diamonds),
+            BossBlind::TheHead => Some(Suit::Hearts),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the card is debuffed by this boss blind
+    ///
+    /// Wild cards count as every suit, so every suit debuff applies to them.
+    pub fn debuffs(&self, card: &Card) -> bool {
+        self.debuffed_suit().is_some_and(|suit| card.is_suit(suit))
+    }
+
+    /// Multiplier applied to the ante's base chips for this boss blind
+    pub fn size_multiplier(&self) -> f64 {
+        match self {
+            BossBlind::TheWall => 4.0,
+            BossBlind::VioletVessel => 6.0,
+            _ => BlindType::Boss.multiplier(),
+        }
+    }
+
+    /// Returns whether the hand's base chips and mult are halved (The Flint)
+    pub fn halves_base(&self) -> bool {
+        *self == BossBlind::TheFlint
+    }
+
+    /// Returns how many levels the played hand loses before it scores
+    pub fn level_penalty(&self) -> u32 {
+        match self {
+            BossBlind::TheArm => 1,
+            _ => 0,
+        }
+    }
+}
+
+impl std::str::FromStr for BossBlind {
+    type Err = anyhow::Error;
+
+    /// Parses a boss blind name, with or without "The", ignoring case,
+    /// spaces, dashes, and underscores (e.g., "The Psychic", "the_eye", "arm")
+    ///
+    /// Part of a name is enough if only one boss blind has it (e.g.,
+    /// "psy", "vessel"). An unknown name is reported with the closest boss
+    /// blinds' names.
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let normalize = |name: &str| -> String {
+            let name: String = name
+                .chars()
+                .filter(|c| !matches!(c, ' ' | '_' | '-'))
+                .collect::<String>()
+                .to_lowercase();
+            name.strip_prefix("the").map(str::to_string).unwrap_or(name)
+        };
+        let normalized = normalize(s);
+
+        if let Some(boss) = BossBlind::all()
+            .into_iter()
+            .find(|boss| normalize(boss.name()) == normalized)
+        {
+            return Ok(boss);
+        }
+
+        let partial: Vec<BossBlind> = BossBlind::all()
+            .into_iter()
+            .filter(|boss| !normalized.is_empty() && normalize(boss.name()).contains(&normalized))
+            .collect();
+        match partial[..] {
+            [boss] => return Ok(boss),
+            [_, _, ..] => {
+                let names: Vec<&str> = partial.iter().map(BossBlind::name).collect();
+                anyhow::bail!("Ambiguous boss blind: {} (could be {})", s, names.join(", "))
+            }
+            [] => {}
+        }
+
+        let names: Vec<&str> = BossBlind::all().iter().map(BossBlind::name).collect();
+        Err(unknown_name("boss blind", s, &names, normalize))
+    }
+}
+
+/// Configuration for a blind
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlindConfig {
+    /// Type of blind (small, big, boss)
+    pub blind_type: BlindType,
+
+    /// Name of the blind (for boss blinds)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Required score to beat the blind
+    pub score_required: u64,
+
+    /// Special ability description (for boss blinds)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ability: Option<String>,
+}
+
+impl BlindConfig {
+    /// Creates a new blind configuration
+    pub fn new(blind_type: BlindType, score_required: u64) -> Self {
+        Self {
+            blind_type,
+            name: None,
+            score_required,
+            ability: None,
+        }
+    }
+
+    /// Creates a small blind with the given score requirement
+    pub fn small(score_required: u64) -> Self {
+        Self::new(BlindType::Small, score_required)
+    }
+
+    /// Creates a big blind with the given score requirement
+    pub fn big(score_required: u64) -> Self {
+        Self::new(BlindType::Big, score_required)
+    }
+
+    /// Creates a boss blind with the given name and score requirement
+    pub fn boss(name: String, score_required: u64) -> Self {
+        Self {
+            blind_type: BlindType::Boss,
+            name: Some(name),
+            score_required,
+            ability: None,
+        }
+    }
+
+    /// Creates a boss blind with its name and its requirement in the run's
+    /// current ante
+    pub fn for_boss(boss: BossBlind, score_required: u64) -> Self {
+        Self::boss(boss.name().to_string(), score_required)
+    }
+
+    /// Parses the boss blind this blind names, if it is a Boss blind
+    ///
+    /// Boss blinds whose effect is not modeled (e.g., The Hook) give `None`.
+    pub fn boss_blind(&self) -> Option<BossBlind> {
+        match (self.blind_type, &self.name) {
+            (BlindType::Boss, Some(name)) => name.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// Adds an ability description to this blind
+    pub fn with_ability(mut self, ability: String) -> Self {
+        self.ability = Some(ability);
+        self
+    }
+}
+
+/// Returns the base blind chips for an ante at the given stake
+///
+/// Antes past 8 grow exponentially from the ante 8 amount, rounded down to
+/// two significant digits as in the game.
+pub fn ante_base_chips(ante: u32, stake: Stake) -> u64 {
+    let amounts = &ANTE_BASE_CHIPS[stake.blind_scaling() as usize - 1];
+
+    match ante {
+        0 => 100,
+        1..=8 => amounts[ante as usize - 1],
+        _ => {
+            let extra = (ante - 8) as f64;
+            let growth = (1.6 + (0.75 * extra).powf(1.0 + 0.2 * extra)).powf(extra);
+            let amount = (amounts[7] as f64 * growth).floor();
+            let step = 10f64.powf((amount.log10() - 1.0).floor());
+            (amount - amount % step) as u64
+        }
+    }
+}
+
+/// Returns the score required to beat a blind
+pub fn blind_requirement(ante: u32, blind_type: BlindType, stake: Stake) -> u64 {
+    (ante_base_chips(ante, stake) as f64 * blind_type.multiplier()) as u64
+}
+
+/// Returns the score required to beat a specific boss blind
+///
+/// Most bosses use the standard x2 size; The Wall and Violet Vessel are
+/// larger.
+pub fn boss_requirement(ante: u32, boss: BossBlind, stake: Stake) -> u64 {
+    (ante_base_chips(ante, stake) as f64 * boss.size_multiplier()) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blind_requirement() {
+        assert_eq!(blind_requirement(1, BlindType::Small, Stake::White), 300);
+        assert_eq!(blind_requirement(1, BlindType::Big, Stake::White), 450);
+        assert_eq!(blind_requirement(4, BlindType::Boss, Stake::White), 10_000);
+        assert_eq!(blind_requirement(8, BlindType::Boss, Stake::Green), 200_000);
+        assert_eq!(blind_requirement(8, BlindType::Small, Stake::Gold), 200_000);
+    }
+
+    #[test]
+    fn test_boss_blind_constraints() {
+        assert!(!BossBlind::ThePsychic.allows(HandType::Pair, 2, &[]));
+        assert!(BossBlind::ThePsychic.allows(HandType::Pair, 5, &[]));
+        assert!(!BossBlind::TheEye.allows(HandType::Pair, 2, &[HandType::Pair]));
+        assert!(BossBlind::TheMouth.allows(HandType::Flush, 5, &[]));
+        assert!(!BossBlind::TheMouth.allows(HandType::Flush, 5, &[HandType::Pair]));
+
+        assert_eq!("the_psychic".parse::<BossBlind>().unwrap(), BossBlind::ThePsychic);
+        assert_eq!("Arm".parse::<BossBlind>().unwrap(), BossBlind::TheArm);
+        assert!(BossBlind::TheClub.debuffs(&Card::new(crate::core::Rank::Two, Suit::Clubs)));
+        assert!(!BossBlind::TheHead.debuffs(&Card::new(crate::core::Rank::Two, Suit::Clubs)));
+        assert!("The Needle".parse::<BossBlind>().is_err());
+    }
+
+    #[test]
+    fn test_large_boss_requirements() {
+        assert_eq!(boss_requirement(2, BossBlind::TheHead, Stake::White), 1_600);
+        assert_eq!(boss_requirement(2, BossBlind::TheWall, Stake::White), 3_200);
+        assert_eq!(boss_requirement(2, BossBlind::VioletVessel, Stake::White), 4_800);
+        assert_eq!("violet_vessel".parse::<BossBlind>().unwrap(), BossBlind::VioletVessel);
+    }
+
+    #[test]
+    fn test_boss_blind_fuzzy_matching() {
+        assert_eq!("psy".parse::<BossBlind>().unwrap(), BossBlind::ThePsychic);
+        assert_eq!("Vessel".parse::<BossBlind>().unwrap(), BossBlind::VioletVessel);
+
+        let ambiguous = "w".parse::<BossBlind>().unwrap_err().to_string();
+        assert!(ambiguous.contains("The Window") && ambiguous.contains("The Wall"));
+        let typo = "The Pyschic".parse::<BossBlind>().unwrap_err().to_string();
+        assert!(typo.contains("did you mean The Psychic?"));
+        // Close to The Mouth, but a different boss: suggested, never chosen
+        assert!("The Tooth".parse::<BossBlind>().is_err());
+        assert!("".parse::<BossBlind>().is_err());
+    }
+
+    #[test]
+    fn test_endless_antes_grow() {
+        assert_eq!(ante_base_chips(9, Stake::White), 110_000);
+        assert!(ante_base_chips(12, Stake::White) > ante_base_chips(11, Stake::White));
+    }
+}
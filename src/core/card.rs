@@ -3,6 +3,7 @@
 //! This module defines the core Card type along with its enhancements,
 //! editions, ranks, and suits as they appear in Balatro.
 
+use super::rules::ScoringRules;
 use serde::{Deserialize, Serialize};
 
 /// Represents a playing card rank
@@ -33,7 +34,7 @@ pub enum Suit {
 }
 
 /// Card enhancements that modify scoring
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Enhancement {
     None,
     Bonus,      // +30 chips
@@ -47,7 +48,7 @@ pub enum Enhancement {
 }
 
 /// Card editions that provide special effects
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Edition {
     None,
     Foil,        // +50 chips
@@ -57,7 +58,7 @@ pub enum Edition {
 }
 
 /// Represents a single playing card with optional modifications
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Card {
     pub rank: Rank,
     pub suit: Suit,
@@ -67,7 +68,7 @@ pub struct Card {
 }
 
 /// Card seals that trigger special effects
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Seal {
     Gold,   // +$3 when played
     Red,    // Retrigger card
@@ -105,8 +106,39 @@ impl Card {
         self
     }
 
+    /// Returns true if the card counts as the given suit
+    ///
+    /// Wild cards count as every suit; Stone cards count as none.
+    pub fn is_suit(&self, suit: Suit) -> bool {
+        match self.enhancement {
+            Enhancement::Wild => true,
+            Enhancement::Stone => false,
+            _ => self.suit == suit,
+        }
+    }
+
+    /// Returns true if the card has a rank (Stone cards do not)
+    pub fn has_rank(&self) -> bool {
+        self.enhancement != Enhancement::Stone
+    }
+
+    /// Returns true if the card counts as a face card (Jack, Queen, King)
+    ///
+    /// With Pareidolia every card with a rank is a face card.
+    pub fn is_face_card(&self, rules: &ScoringRules) -> bool {
+        if !self.has_rank() {
+            return false;
+        }
+        rules.pareidolia || matches!(self.rank, Rank::Jack | Rank::Queen | Rank::King)
+    }
+
     /// Returns the base chip value of the card
+    ///
+    /// Stone cards have no rank, so they contribute no rank chips.
     pub fn base_chips(&self) -> u32 {
+        if !self.has_rank() {
+            return 0;
+        }
         match self.rank {
             Rank::Two => 2,
             Rank::Three => 3,
@@ -122,6 +154,40 @@ impl Card {
     }
 }
 
+impl std::fmt::Display for Card {
+    /// Formats the card as its rank and suit symbol (e.g., "A♥", "10♦"), or
+    /// "Stone" for a Stone card, which has neither
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if !self.has_rank() {
+            return write!(f, "Stone");
+        }
+        let rank = match self.rank {
+            Rank::Two => "2",
+            Rank::Three => "3",
+            Rank::Four => "4",
+            Rank::Five => "5",
+            Rank::Six => "6",
+            Rank::Seven => "7",
+            Rank::Eight => "8",
+            Rank::Nine => "9",
+            Rank::Ten => "10",
+            Rank::Jack => "J",
+            Rank::Queen => "Q",
+            Rank::King => "K",
+            Rank::Ace => "A",
+        };
+
+        let suit = match self.suit {
+            Suit::Hearts => "♥",
+            Suit::Diamonds => "♦",
+            Suit::Clubs => "♣",
+            Suit::Spades => "♠",
+        };
+
+        write!(f, "{}{}", rank, suit)
+    }
+}
+
 impl Rank {
     /// Returns numeric value for rank comparison (for straights)
     pub fn value(&self) -> u8 {
@@ -141,6 +207,139 @@ impl Rank {
             Rank::Ace => 14,
         }
     }
+
+    /// Returns the next rank up, wrapping from Ace to Two (Strength)
+    pub fn next(&self) -> Rank {
+        match self {
+            Rank::Two => Rank::Three,
+            Rank::Three => Rank::Four,
+            Rank::Four => Rank::Five,
+            Rank::Five => Rank::Six,
+            Rank::Six => Rank::Seven,
+            Rank::Seven => Rank::Eight,
+            Rank::Eight => Rank::Nine,
+            Rank::Nine => Rank::Ten,
+            Rank::Ten => Rank::Jack,
+            Rank::Jack => Rank::Queen,
+            Rank::Queen => Rank::King,
+            Rank::King => Rank::Ace,
+            Rank::Ace => Rank::Two,
+        }
+    }
+
+    /// Returns true for even number ranks (10, 8, 6, 4, 2)
+    ///
+    /// Face cards are neither even nor odd.
+    pub fn is_even(&self) -> bool {
+        matches!(self, Rank::Two | Rank::Four | Rank::Six | Rank::Eight | Rank::Ten)
+    }
+
+    /// Returns true for odd ranks (A, 9, 7, 5, 3); Aces count as odd
+    pub fn is_odd(&self) -> bool {
+        matches!(self, Rank::Ace | Rank::Three | Rank::Five | Rank::Seven | Rank::Nine)
+    }
+
+    /// Returns true for ranks in the Fibonacci sequence (A, 2, 3, 5, 8)
+    pub fn is_fibonacci(&self) -> bool {
+        matches!(self, Rank::Ace | Rank::Two | Rank::Three | Rank::Five | Rank::Eight)
+    }
+}
+
+impl std::str::FromStr for Rank {
+    type Err = anyhow::Error;
+
+    /// Parses a rank symbol ("2"-"10", "J", "Q", "K", "A") or name ("King")
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "2" | "two" => Ok(Rank::Two),
+            "3" | "three" => Ok(Rank::Three),
+            "4" | "four" => Ok(Rank::Four),
+            "5" | "five" => Ok(Rank::Five),
+            "6" | "six" => Ok(Rank::Six),
+            "7" | "seven" => Ok(Rank::Seven),
+            "8" | "eight" => Ok(Rank::Eight),
+            "9" | "nine" => Ok(Rank::Nine),
+            "10" | "ten" => Ok(Rank::Ten),
+            "j" | "jack" => Ok(Rank::Jack),
+            "q" | "queen" => Ok(Rank::Queen),
+            "k" | "king" => Ok(Rank::King),
+            "a" | "ace" => Ok(Rank::Ace),
+            _ => anyhow::bail!("Invalid rank: {}", s),
+        }
+    }
+}
+
+impl std::str::FromStr for Suit {
+    type Err = anyhow::Error;
+
+    /// Parses a suit letter ("H", "D", "C", "S") or name ("Hearts")
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "h" | "hearts" | "heart" => Ok(Suit::Hearts),
+            "d" | "diamonds" | "diamond" => Ok(Suit::Diamonds),
+            "c" | "clubs" | "club" => Ok(Suit::Clubs),
+            "s" | "spades" | "spade" => Ok(Suit::Spades),
+            _ => anyhow::bail!("Invalid suit: {}", s),
+        }
+    }
+}
+
+/// Lowercases a modifier name and strips spaces, underscores, and hyphens
+fn normalize_name(s: &str) -> String {
+    s.chars()
+        .filter(|c| !matches!(c, ' ' | '_' | '-'))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+impl std::str::FromStr for Enhancement {
+    type Err = anyhow::Error;
+
+    /// Parses an enhancement name (e.g., "Gold", "steel")
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match normalize_name(s).as_str() {
+            "bonus" => Ok(Enhancement::Bonus),
+            "mult" => Ok(Enhancement::Mult),
+            "wild" => Ok(Enhancement::Wild),
+            "glass" => Ok(Enhancement::Glass),
+            "steel" => Ok(Enhancement::Steel),
+            "stone" => Ok(Enhancement::Stone),
+            "gold" => Ok(Enhancement::Gold),
+            "lucky" => Ok(Enhancement::Lucky),
+            _ => anyhow::bail!("Invalid enhancement: {}", s),
+        }
+    }
+}
+
+impl std::str::FromStr for Edition {
+    type Err = anyhow::Error;
+
+    /// Parses an edition name (e.g., "Foil", "holo", "polychrome")
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match normalize_name(s).as_str() {
+            "foil" => Ok(Edition::Foil),
+            "holographic" | "holo" => Ok(Edition::Holographic),
+            "polychrome" | "poly" => Ok(Edition::Polychrome),
+            "negative" => Ok(Edition::Negative),
+            _ => anyhow::bail!("Invalid edition: {}", s),
+        }
+    }
+}
+
+impl std::str::FromStr for Seal {
+    type Err = anyhow::Error;
+
+    /// Parses a seal color, with or without "seal" (e.g., "Red", "gold_seal")
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let normalized = normalize_name(s);
+        match normalized.strip_suffix("seal").unwrap_or(&normalized) {
+            "gold" => Ok(Seal::Gold),
+            "red" => Ok(Seal::Red),
+            "blue" => Ok(Seal::Blue),
+            "purple" => Ok(Seal::Purple),
+            _ => anyhow::bail!("Invalid seal: {}", s),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -161,4 +360,55 @@ mod tests {
         assert_eq!(Card::new(Rank::King, Suit::Spades).base_chips(), 10);
         assert_eq!(Card::new(Rank::Five, Suit::Diamonds).base_chips(), 5);
     }
+
+    #[test]
+    fn test_wild_card_is_every_suit() {
+        let wild = Card::new(Rank::Two, Suit::Clubs).with_enhancement(Enhancement::Wild);
+        assert!(wild.is_suit(Suit::Hearts));
+        assert!(wild.is_suit(Suit::Clubs));
+        assert!(!Card::new(Rank::Two, Suit::Clubs).is_suit(Suit::Hearts));
+    }
+
+    #[test]
+    fn test_stone_card_has_no_rank_or_suit() {
+        let stone = Card::new(Rank::Ace, Suit::Hearts).with_enhancement(Enhancement::Stone);
+        assert!(!stone.has_rank());
+        assert!(!stone.is_suit(Suit::Hearts));
+        assert_eq!(stone.base_chips(), 0);
+    }
+
+    #[test]
+    fn test_rank_parity() {
+        assert!(Rank::Ace.is_odd());
+        assert!(!Rank::Ace.is_even());
+        assert!(Rank::Ten.is_even());
+        assert!(!Rank::King.is_even() && !Rank::King.is_odd());
+        assert!(Rank::Eight.is_fibonacci());
+        assert!(!Rank::Four.is_fibonacci());
+    }
+
+    #[test]
+    fn test_face_cards_with_pareidolia() {
+        let rules = ScoringRules::default();
+        let pareidolia = ScoringRules {
+            pareidolia: true,
+            ..Default::default()
+        };
+        let king = Card::new(Rank::King, Suit::Hearts);
+        let two = Card::new(Rank::Two, Suit::Hearts);
+
+        assert!(king.is_face_card(&rules));
+        assert!(!two.is_face_card(&rules));
+        assert!(two.is_face_card(&pareidolia));
+    }
+
+    #[test]
+    fn test_parse_modifiers() {
+        assert_eq!("Gold".parse::<Enhancement>().unwrap(), Enhancement::Gold);
+        assert_eq!("holo".parse::<Edition>().unwrap(), Edition::Holographic);
+        assert_eq!("red_seal".parse::<Seal>().unwrap(), Seal::Red);
+        assert_eq!("Purple".parse::<Seal>().unwrap(), Seal::Purple);
+        assert!("foil".parse::<Enhancement>().is_err());
+        assert!("green_seal".parse::<Seal>().is_err());
+    }
 }
@@ -0,0 +1,428 @@
+//! Consumable cards (Planets and Tarots)
+//!
+//! Planet cards each level up one poker hand type when used. Tarot cards
+//! change up to a few selected cards in hand. Blue and Purple seals create
+//! consumables, reported as [`ConsumableEvent`]s.
+
+use super::card::{Card, Enhancement, Seal, Suit};
+use super::hand::HandType;
+use super::hand_levels::HandLevels;
+use super::names::unknown_name;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// A Planet card, which upgrades the level of one hand type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Planet {
+    Pluto,   // High Card
+    Mercury, // Pair
+    Uranus,  // Two Pair
+    Venus,   // Three of a Kind
+    Saturn,  // Straight
+    Jupiter, // Flush
+    Earth,   // Full House
+    Mars,    // Four of a Kind
+    Neptune, // Straight Flush
+    PlanetX, // Five of a Kind
+    Ceres,   // Flush House
+    Eris,    // Flush Five
+}
+
+impl Planet {
+    /// Returns every Planet card
+    pub fn all() -> [Planet; 12] {
+        [
+            Planet::Pluto,
+            Planet::Mercury,
+            Planet::Uranus,
+            Planet::Venus,
+            Planet::Saturn,
+            Planet::Jupiter,
+            Planet::Earth,
+            Planet::Mars,
+            Planet::Neptune,
+            Planet::PlanetX,
+            Planet::Ceres,
+            Planet::Eris,
+        ]
+    }
+
+    /// Returns the hand type this planet upgrades
+    pub fn hand_type(&self) -> HandType {
+        match self {
+            Planet::Pluto => HandType::HighCard,
+            Planet::Mercury => HandType::Pair,
+            Planet::Uranus => HandType::TwoPair,
+            Planet::Venus => HandType::ThreeOfAKind,
+            Planet::Saturn => HandType::Straight,
+            Planet::Jupiter => HandType::Flush,
+            Planet::Earth => HandType::FullHouse,
+            Planet::Mars => HandType::FourOfAKind,
+            Planet::Neptune => HandType::StraightFlush,
+            Planet::PlanetX => HandType::FiveOfAKind,
+            Planet::Ceres => HandType::FlushHouse,
+            Planet::Eris => HandType::FlushFive,
+        }
+    }
+
+    /// Returns the planet that upgrades the given hand type
+    pub fn for_hand_type(hand_type: HandType) -> Planet {
+        Planet::all()
+            .into_iter()
+            .find(|planet| planet.hand_type() == hand_type)
+            .expect("every hand type has a planet")
+    }
+
+    /// Uses this planet, raising its hand type by one level
+    pub fn apply(&self, levels: &mut HandLevels) {
+        levels.upgrade(self.hand_type(), 1);
+    }
+}
+
+impl std::str::FromStr for Planet {
+    type Err = anyhow::Error;
+
+    /// Parses a planet name, ignoring case, spaces, dashes, and underscores
+    /// (e.g., "Jupiter", "planet_x", "Planet X")
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let normalized = normalize_name(s);
+
+        Planet::all()
+            .into_iter()
+            .find(|planet| format!("{:?}", planet).to_lowercase() == normalized)
+            .ok_or_else(|| {
+                let names = Planet::all().map(|planet| Consumable::Planet(planet).name());
+                let names: Vec<&str> = names.iter().map(String::as_str).collect();
+                unknown_name("planet", s, &names, normalize_name)
+            })
+    }
+}
+
+/// A Tarot card that changes selected cards in hand
+///
+/// Only Tarots that modify the cards they target are modeled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Tarot {
+    TheMagician,   // Up to 2 cards become Lucky
+    TheEmpress,    // Up to 2 cards become Mult
+    TheHierophant, // Up to 2 cards become Bonus
+    TheLovers,     // 1 card becomes Wild
+    TheChariot,    // 1 card becomes Steel
+    Justice,       // 1 card becomes Glass
+    TheDevil,      // 1 card becomes Gold
+    TheTower,      // 1 card becomes Stone
+    Strength,      // Up to 2 cards increase rank by 1
+    TheStar,       // Up to 3 cards become Diamonds
+    TheMoon,       // Up to 3 cards become Clubs
+    TheSun,        // Up to 3 cards become Hearts
+    TheWorld,      // Up to 3 cards become Spades
+}
+
+impl Tarot {
+    /// Returns every modeled Tarot card
+    pub fn all() -> [Tarot; 13] {
+        [
+            Tarot::TheMagician,
+            Tarot::TheEmpress,
+            Tarot::TheHierophant,
+            Tarot::TheLovers,
+            Tarot::TheChariot,
+            Tarot::Justice,
+            Tarot::TheDevil,
+            Tarot::TheTower,
+            Tarot::Strength,
+            Tarot::TheStar,
+            Tarot::TheMoon,
+            Tarot::TheSun,
+            Tarot::TheWorld,
+        ]
+    }
+
+    /// Returns the Tarot's name as shown in the game
+    pub fn name(&self) -> &'static str {
+        match self {
+            Tarot::TheMagician => "The Magician",
+            Tarot::TheEmpress => "The Empress",
+            Tarot::TheHierophant => "The Hierophant",
+            Tarot::TheLovers => "The Lovers",
+            Tarot::TheChariot => "The Chariot",
+            Tarot::Justice => "Justice",
+            Tarot::TheDevil => "The Devil",
+            Tarot::TheTower => "The Tower",
+            Tarot::Strength => "Strength",
+            Tarot::TheStar => "The Star",
+            Tarot::TheMoon => "The Moon",
+            Tarot::TheSun => "The Sun",
+            Tarot::TheWorld => "The World",
+        }
+    }
+
+    /// Returns the most cards this Tarot can target
+    pub fn max_targets(&self) -> usize {
+        match self {
+            Tarot::TheMagician | Tarot::TheEmpress | Tarot::TheHierophant | Tarot::Strength => 2,
+            Tarot::TheStar | Tarot::TheMoon | Tarot::TheSun | Tarot::TheWorld => 3,
+            _ => 1,
+        }
+    }
+
+    /// Applies this Tarot to one targeted card
+    pub fn apply(&self, card: &mut Card) {
+        match self {
+            Tarot::TheMagician => card.enhancement = Enhancement::Lucky,
+            Tarot::TheEmpress => card.enhancement = Enhancement::Mult,
+            Tarot::TheHierophant => card.enhancement = Enhancement::Bonus,
+            Tarot::TheLovers => card.enhancement = Enhancement::Wild,
+            Tarot::TheChariot => card.enhancement = Enhancement::Steel,
+            Tarot::Justice => card.enhancement = Enhancement::Glass,
+            Tarot::TheDevil => card.enhancement = Enhancement::Gold,
+            Tarot::TheTower => card.enhancement = Enhancement::Stone,
+            Tarot::Strength => card.rank = card.rank.next(),
+            Tarot::TheStar => card.suit = Suit::Diamonds,
+            Tarot::TheMoon => card.suit = Suit::Clubs,
+            Tarot::TheSun => card.suit = Suit::Hearts,
+            Tarot::TheWorld => card.suit = Suit::Spades,
+        }
+    }
+}
+
+impl std::str::FromStr for Tarot {
+    type Err = anyhow::Error;
+
+    /// Parses a Tarot name, ignoring case, spaces, dashes, and underscores
+    /// (e.g., "The Star", "the_magician", "Justice")
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let normalized = normalize_name(s);
+
+        Tarot::all()
+            .into_iter()
+            .find(|tarot| normalize_name(tarot.name()) == normalized)
+            .ok_or_else(|| {
+                let names = Tarot::all().map(|tarot| tarot.name());
+                unknown_name("tarot", s, &names, normalize_name)
+            })
+    }
+}
+
+/// A consumable card that can be used before playing a hand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Consumable {
+    Planet(Planet),
+    Tarot(Tarot),
+}
+
+impl Consumable {
+    /// Returns the consumable's name as shown in the game
+    pub fn name(&self) -> String {
+        match self {
+            Consumable::Planet(Planet::PlanetX) => "Planet X".to_string(),
+            Consumable::Planet(planet) => format!("{:?}", planet),
+            Consumable::Tarot(tarot) => tarot.name().to_string(),
+        }
+    }
+
+    /// Returns the most cards this consumable can target (0 for Planets)
+    pub fn max_targets(&self) -> usize {
+        match self {
+            Consumable::Planet(_) => 0,
+            Consumable::Tarot(tarot) => tarot.max_targets(),
+        }
+    }
+
+    /// Uses the consumable on the cards at `targets`, or on the hand levels
+    pub fn apply(&self, cards: &mut [Card], targets: &[usize], levels: &mut HandLevels) {
+        match self {
+            Consumable::Planet(planet) => planet.apply(levels),
+            Consumable::Tarot(tarot) => {
+                for &i in targets {
+                    tarot.apply(&mut cards[i]);
+                }
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for Consumable {
+    type Err = anyhow::Error;
+
+    /// Parses a Planet or Tarot name
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        s.parse()
+            .map(Consumable::Planet)
+            .or_else(|_| s.parse().map(Consumable::Tarot))
+            .map_err(|_| {
+                let planets = Planet::all().map(Consumable::Planet);
+                let tarots = Tarot::all().map(Consumable::Tarot);
+                let names: Vec<String> = planets.iter().chain(&tarots).map(|c| c.name()).collect();
+                let names: Vec<&str> = names.iter().map(String::as_str).collect();
+                unknown_name("consumable", s, &names, normalize_name)
+            })
+    }
+}
+
+/// Normalizes a Planet or Tarot name for matching: lowercase, without
+/// spaces, dashes, or underscores
+fn normalize_name(name: &str) -> String {
+    name.chars()
+        .filter(|c| !matches!(c, ' ' | '_' | '-'))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// A consumable created during play by a card seal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConsumableEvent {
+    /// A Blue seal held at end of round created the Planet for the played hand
+    Planet(Planet),
+    /// A Purple seal was discarded and created a Tarot card
+    Tarot,
+}
+
+/// Returns the consumables created by Blue seals held at end of round
+///
+/// Each held Blue seal creates the Planet for the final hand type played.
+pub fn held_events(held: &[Card], hand_type: HandType) -> Vec<ConsumableEvent> {
+    held.iter()
+        .filter(|card| card.seal == Some(Seal::Blue))
+        .map(|_| ConsumableEvent::Planet(Planet::for_hand_type(hand_type)))
+        .collect()
+}
+
+/// Returns the consumables created by discarding the given cards
+///
+/// Each discarded Purple seal creates a Tarot card.
+pub fn discard_events(discarded: &[Card]) -> Vec<ConsumableEvent> {
+    discarded
+        .iter()
+        .filter(|card| card.seal == Some(Seal::Purple))
+        .map(|_| ConsumableEvent::Tarot)
+        .collect()
+}
+
+/// Consumables collected over the course of a run
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsumableInventory {
+    /// Planets created, in the order they were received
+    pub planets: Vec<Planet>,
+    /// Number of Tarot cards created
+    pub tarots: u32,
+}
+
+impl ConsumableInventory {
+    /// Adds the consumables created by the given events
+    pub fn receive(&mut self, events: &[ConsumableEvent]) {
+        for event in events {
+            match event {
+                ConsumableEvent::Planet(planet) => self.planets.push(*planet),
+                ConsumableEvent::Tarot => self.tarots += 1,
+            }
+        }
+    }
+
+    /// Duplicates one consumable chosen at random (Perkeo)
+    ///
+    /// Perkeo's copies are Negative, so they never need a free slot.
+    pub fn duplicate_random<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        let total = self.planets.len() + self.tarots as usize;
+        if total == 0 {
+            return;
+        }
+
+        let choice = rng.gen_range(0..total);
+        match self.planets.get(choice) {
+            Some(planet) => self.planets.push(*planet),
+            None => self.tarots += 1,
+        }
+    }
+}
+
+impl HandLevels {
+    /// Uses each planet in order, upgrading the corresponding hand levels
+    pub fn apply_planets(&mut self, planets: &[Planet]) {
+        for planet in planets {
+            planet.apply(self);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_planet_hand_types() {
+        assert_eq!(Planet::Jupiter.hand_type(), HandType::Flush);
+        assert_eq!(Planet::for_hand_type(HandType::Pair), Planet::Mercury);
+    }
+
+    #[test]
+    fn test_apply_planets() {
+        let mut levels = HandLevels::new();
+        levels.apply_planets(&[Planet::Jupiter, Planet::Jupiter, Planet::Pluto]);
+        assert_eq!(levels.level(HandType::Flush), 3);
+        assert_eq!(levels.level(HandType::HighCard), 2);
+    }
+
+    #[test]
+    fn test_parse_planet() {
+        assert_eq!("Planet X".parse::<Planet>().unwrap(), Planet::PlanetX);
+        assert_eq!("ceres".parse::<Planet>().unwrap(), Planet::Ceres);
+        assert!("The Fool".parse::<Planet>().is_err());
+    }
+
+    #[test]
+    fn test_tarots_modify_cards() {
+        use crate::core::card::Rank;
+
+        let mut cards = vec![
+            Card::new(Rank::Two, Suit::Clubs),
+            Card::new(Rank::Ace, Suit::Clubs),
+        ];
+        let mut levels = HandLevels::new();
+
+        let star: Consumable = "The Star".parse().unwrap();
+        star.apply(&mut cards, &[0], &mut levels);
+        assert_eq!(cards[0].suit, Suit::Diamonds);
+        assert_eq!(cards[1].suit, Suit::Clubs);
+
+        Tarot::Strength.apply(&mut cards[1]);
+        assert_eq!(cards[1].rank, Rank::Two);
+
+        let jupiter: Consumable = "jupiter".parse().unwrap();
+        jupiter.apply(&mut cards, &[], &mut levels);
+        assert_eq!(levels.level(HandType::Flush), 2);
+        assert!("The Fool".parse::<Consumable>().is_err());
+    }
+
+    #[test]
+    fn test_seal_events() {
+        use crate::core::card::Rank;
+
+        let held = vec![
+            Card::new(Rank::Two, Suit::Hearts).with_seal(Seal::Blue),
+            Card::new(Rank::Three, Suit::Hearts).with_seal(Seal::Purple),
+        ];
+        let events = held_events(&held, HandType::Flush);
+        assert_eq!(events, vec![ConsumableEvent::Planet(Planet::Jupiter)]);
+
+        let mut inventory = ConsumableInventory::default();
+        inventory.receive(&events);
+        inventory.receive(&discard_events(&held));
+        assert_eq!(inventory.planets, vec![Planet::Jupiter]);
+        assert_eq!(inventory.tarots, 1);
+    }
+
+    #[test]
+    fn test_perkeo_duplicates_consumable() {
+        use rand::SeedableRng;
+
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(3);
+        let mut inventory = ConsumableInventory::default();
+        inventory.duplicate_random(&mut rng);
+        assert_eq!(inventory, ConsumableInventory::default());
+
+        inventory.receive(&[ConsumableEvent::Planet(Planet::Mars)]);
+        inventory.duplicate_random(&mut rng);
+        assert_eq!(inventory.planets, vec![Planet::Mars, Planet::Mars]);
+    }
+}
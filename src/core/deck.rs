@@ -0,0 +1,591 @@
+//! Official deck archetypes and run-level deck state
+//!
+//! Each Balatro deck starts from a different set of cards and/or changes
+//! the starting resources of the run (hands, discards, money, slots).
+//! `DeckState` tracks how the deck and jokers change over a run, through
+//! hooks called as blinds are selected, hands played, cards discarded, and
+//! rounds end.
+
+use super::blind::BlindType;
+use super::card::{Card, Enhancement, Rank, Suit};
+use super::hand::Hand;
+use super::joker::{Joker, JokerKind};
+use super::names::unknown_name;
+use super::rules::ScoringRules;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// A starting deck
+///
+/// Serialized by its lowercase name ("plasma"), and read back from any
+/// name [`FromStr`](std::str::FromStr) accepts, so a misspelled deck in a
+/// configuration file is reported with the closest decks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeckType {
+    #[default]
+    Red,       // +1 discard every round
+    Blue,      // +1 hand every round
+    Yellow,    // Start with extra $10
+    Green,     // $2 per remaining hand, $1 per remaining discard, no interest
+    Black,     // +1 joker slot, -1 hand every round
+    Magic,     // Start with Crystal Ball voucher and 2 copies of The Fool
+    Nebula,    // Start with Telescope voucher, -1 consumable slot
+    Ghost,     // Spectral cards may appear in the shop, start with Hex
+    Abandoned, // Start with no face cards
+    Checkered, // Start with 26 Spades and 26 Hearts
+    Zodiac,    // Start with Tarot Merchant, Planet Merchant, and Overstock
+    Painted,   // +2 hand size, -1 joker slot
+    Anaglyph,  // Gain a Double Tag after each Boss Blind
+    Plasma,    // Balance chips and mult when scoring, X2 base blind size
+    Erratic,   // All ranks and suits are randomized
+}
+
+/// Starting resources and rule changes for a deck
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeckModifiers {
+    pub hands: u32,
+    pub discards: u32,
+    pub hand_size: usize,
+    pub joker_slots: u32,
+    pub consumable_slots: u32,
+    pub starting_money: u32,
+    /// Money earned per hand left unplayed at end of round (Green)
+    pub money_per_remaining_hand: u32,
+    /// Money earned per discard left unused at end of round (Green)
+    pub money_per_remaining_discard: u32,
+    /// Whether money earns interest at end of round
+    pub earns_interest: bool,
+    /// Chips and mult are averaged before the final multiplication (Plasma)
+    pub plasma_balance: bool,
+    /// Multiplier applied to every blind's score requirement
+    pub blind_size_multiplier: f64,
+    /// Vouchers owned at the start of the run
+    pub starting_vouchers: Vec<String>,
+    /// Consumables owned at the start of the run
+    pub starting_consumables: Vec<String>,
+}
+
+impl Default for DeckModifiers {
+    fn default() -> Self {
+        Self {
+            hands: 4,
+            discards: 3,
+            hand_size: 8,
+            joker_slots: 5,
+            consumable_slots: 2,
+            starting_money: 4,
+            money_per_remaining_hand: 1,
+            money_per_remaining_discard: 0,
+            earns_interest: true,
+            plasma_balance: false,
+            blind_size_multiplier: 1.0,
+            starting_vouchers: Vec::new(),
+            starting_consumables: Vec::new(),
+        }
+    }
+}
+
+impl DeckType {
+    /// Returns every deck
+    pub fn all() -> [DeckType; 15] {
+        [
+            DeckType::Red,
+            DeckType::Blue,
+            DeckType::Yellow,
+            DeckType::Green,
+            DeckType::Black,
+            DeckType::Magic,
+            DeckType::Nebula,
+            DeckType::Ghost,
+            DeckType::Abandoned,
+            DeckType::Checkered,
+            DeckType::Zodiac,
+            DeckType::Painted,
+            DeckType::Anaglyph,
+            DeckType::Plasma,
+            DeckType::Erratic,
+        ]
+    }
+
+    /// Returns the deck's name as shown in the game
+    pub fn name(&self) -> &'static str {
+        match self {
+            DeckType::Red => "Red Deck",
+            DeckType::Blue => "Blue Deck",
+            DeckType::Yellow => "Yellow Deck",
+            DeckType::Green => "Green Deck",
+            DeckType::Black => "Black Deck",
+            DeckType::Magic => "Magic Deck",
+            DeckType::Nebula => "Nebula Deck",
+            DeckType::Ghost => "Ghost Deck",
+            DeckType::Abandoned => "Abandoned Deck",
+            DeckType::Checkered => "Checkered Deck",
+            DeckType::Zodiac => "Zodiac Deck",
+            DeckType::Painted => "Painted Deck",
+            DeckType::Anaglyph => "Anaglyph Deck",
+            DeckType::Plasma => "Plasma Deck",
+            DeckType::Erratic => "Erratic Deck",
+        }
+    }
+
+    /// Returns the starting resources and rule changes of this deck
+    pub fn modifiers(&self) -> DeckModifiers {
+        let names = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        let base = DeckModifiers::default();
+
+        match self {
+            DeckType::Red => DeckModifiers { discards: base.discards + 1, ..base },
+            DeckType::Blue => DeckModifiers { hands: base.hands + 1, ..base },
+            DeckType::Yellow => DeckModifiers {
+                starting_money: base.starting_money + 10,
+                ..base
+            },
+            DeckType::Green => DeckModifiers {
+                money_per_remaining_hand: 2,
+                money_per_remaining_discard: 1,
+                earns_interest: false,
+                ..base
+            },
+            DeckType::Black => DeckModifiers {
+                joker_slots: base.joker_slots + 1,
+                hands: base.hands - 1,
+                ..base
+            },
+            DeckType::Magic => DeckModifiers {
+                starting_vouchers: names(&["Crystal Ball"]),
+                starting_consumables: names(&["The Fool", "The Fool"]),
+                ..base
+            },
+            DeckType::Nebula => DeckModifiers {
+                starting_vouchers: names(&["Telescope"]),
+                consumable_slots: base.consumable_slots - 1,
+                ..base
+            },
+            DeckType::Ghost => DeckModifiers {
+                starting_consumables: names(&["Hex"]),
+                ..base
+            },
+            DeckType::Zodiac => DeckModifiers {
+                starting_vouchers: names(&["Tarot Merchant", "Planet Merchant", "Overstock"]),
+                ..base
+            },
+            DeckType::Painted => DeckModifiers {
+                hand_size: base.hand_size + 2,
+                joker_slots: base.joker_slots - 1,
+                ..base
+            },
+            DeckType::Plasma => DeckModifiers {
+                plasma_balance: true,
+                blind_size_multiplier: 2.0,
+                ..base
+            },
+            DeckType::Abandoned | DeckType::Checkered | DeckType::Anaglyph | DeckType::Erratic => {
+                base
+            }
+        }
+    }
+
+    /// Creates the starting cards of this deck
+    ///
+    /// The seed is only used by the Erratic deck, whose ranks and suits are
+    /// drawn at random.
+    pub fn create_cards(&self, seed: u64) -> Vec<Card> {
+        let standard = super::simulator::create_standard_deck();
+
+        match self {
+            DeckType::Abandoned => standard
+                .into_iter()
+                .filter(|card| !matches!(card.rank, Rank::Jack | Rank::Queen | Rank::King))
+                .collect(),
+            DeckType::Checkered => standard
+                .into_iter()
+                .map(|card| {
+                    let suit = match card.suit {
+                        Suit::Hearts | Suit::Diamonds => Suit::Hearts,
+                        Suit::Clubs | Suit::Spades => Suit::Spades,
+                    };
+                    Card::new(card.rank, suit)
+                })
+                .collect(),
+            DeckType::Erratic => {
+                let mut rng = ChaCha8Rng::seed_from_u64(seed);
+                let ranks: Vec<Rank> = standard.iter().map(|card| card.rank).collect();
+                let suits = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades];
+
+                (0..standard.len())
+                    .map(|_| {
+                        let rank = *ranks.choose(&mut rng).expect("standard deck is not empty");
+                        let suit = *suits.choose(&mut rng).expect("suits are not empty");
+                        Card::new(rank, suit)
+                    })
+                    .collect()
+            }
+            _ => standard,
+        }
+    }
+}
+
+/// The deck and jokers of a run, as mutated by play
+///
+/// Some jokers change the cards in the deck when a hand is played (Midas
+/// Mask, Vampire, DNA) or grow when the deck changes (Hologram). Playing
+/// each hand through `play_hand` keeps those changes across hands.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeckState {
+    pub cards: Vec<Card>,
+    pub jokers: Vec<Joker>,
+}
+
+impl DeckState {
+    /// Creates a deck state from the starting cards and jokers
+    pub fn new(cards: Vec<Card>, jokers: Vec<Joker>) -> Self {
+        Self { cards, jokers }
+    }
+
+    /// Applies the deck-mutating joker effects of playing a hand
+    ///
+    /// Runs before scoring, so the played cards in `hand` are updated in
+    /// place (Gold from Midas Mask, enhancements removed by Vampire) along
+    /// with the matching cards in the deck. Returns the number of cards
+    /// added to the deck.
+    pub fn play_hand(&mut self, hand: &mut Hand, first_hand_of_round: bool) -> usize {
+        let rules = ScoringRules::from_jokers(&self.jokers);
+        let scoring = hand.scoring_indices_with(&rules);
+        let original = hand.cards.clone();
+        let mut added = 0;
+
+        for index in 0..self.jokers.len() {
+            match self.jokers[index].kind {
+                JokerKind::Dna if first_hand_of_round && hand.cards.len() == 1 => {
+                    self.cards.push(hand.cards[0].clone());
+                    added += 1;
+                }
+                JokerKind::MidasMask => {
+                    for &i in &scoring {
+                        if hand.cards[i].is_face_card(&rules) {
+                            hand.cards[i].enhancement = Enhancement::Gold;
+                        }
+                    }
+                }
+                JokerKind::Vampire => {
+                    for &i in &scoring {
+                        if hand.cards[i].enhancement != Enhancement::None {
+                            hand.cards[i].enhancement = Enhancement::None;
+                            self.jokers[index].counter += 1;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for joker in &mut self.jokers {
+            if joker.kind == JokerKind::Hologram {
+                joker.counter += added as u32;
+            }
+        }
+
+        for (before, after) in original.iter().zip(&hand.cards) {
+            if before != after
+                && let Some(card) = self.cards.iter_mut().find(|card| *card == before)
+            {
+                *card = after.clone();
+            }
+        }
+
+        added
+    }
+
+    /// Applies joker effects triggered when a blind is selected
+    ///
+    /// Ceremonial Dagger destroys the joker to its right and gains double
+    /// its sell value as mult; Madness (Small and Big Blinds only) gains
+    /// x0.5 mult and destroys another joker at random.
+    pub fn on_blind_selected<R: Rng + ?Sized>(&mut self, blind_type: BlindType, rng: &mut R) {
+        let mut index = 0;
+        while index < self.jokers.len() {
+            match self.jokers[index].kind {
+                JokerKind::CeremonialDagger if index + 1 < self.jokers.len() => {
+                    let victim = self.jokers.remove(index + 1);
+                    self.jokers[index].counter += 2 * victim.kind.sell_value();
+                }
+                JokerKind::Madness if blind_type != BlindType::Boss => {
+                    self.jokers[index].counter += 1;
+                    let others: Vec<usize> =
+                        (0..self.jokers.len()).filter(|&i| i != index).collect();
+                    if let Some(&victim) = others.choose(rng) {
+                        self.jokers.remove(victim);
+                        if victim < index {
+                            index -= 1;
+                        }
+                    }
+                }
+                _ => {}
+            }
+            index += 1;
+        }
+    }
+
+    /// Applies joker effects triggered after a hand is scored
+    ///
+    /// Ice Cream loses 5 chips per hand and melts once it reaches 0.
+    pub fn on_hand_played(&mut self) {
+        for joker in &mut self.jokers {
+            if joker.kind == JokerKind::IceCream {
+                joker.counter += 1;
+            }
+        }
+        self.jokers
+            .retain(|joker| !(joker.kind == JokerKind::IceCream && joker.counter >= 20));
+    }
+
+    /// Applies joker effects triggered at the end of a round
+    ///
+    /// Popcorn loses 4 mult per round and is eaten once it reaches 0. The
+    /// Idol picks a new card from the deck and Ancient Joker a new suit.
+    pub fn on_round_end<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        for joker in &mut self.jokers {
+            match joker.kind {
+                JokerKind::Popcorn => joker.counter += 1,
+                JokerKind::TheIdol | JokerKind::AncientJoker => {
+                    Self::reroll_target(joker, &self.cards, rng)
+                }
+                _ => {}
+            }
+        }
+        self.jokers
+            .retain(|joker| !(joker.kind == JokerKind::Popcorn && joker.counter >= 5));
+    }
+
+    /// Picks targets for The Idol and Ancient Joker where none is pinned,
+    /// as the game does when a run starts
+    pub fn pick_missing_targets<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        for joker in &mut self.jokers {
+            let missing = match joker.kind {
+                JokerKind::TheIdol => joker.target_rank.is_none() || joker.target_suit.is_none(),
+                JokerKind::AncientJoker => joker.target_suit.is_none(),
+                _ => false,
+            };
+            if missing {
+                Self::reroll_target(joker, &self.cards, rng);
+            }
+        }
+    }
+
+    /// Picks a new target for The Idol (a card in the deck) or Ancient
+    /// Joker (a suit other than its current one)
+    fn reroll_target<R: Rng + ?Sized>(joker: &mut Joker, cards: &[Card], rng: &mut R) {
+        let suits = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades];
+        match joker.kind {
+            JokerKind::TheIdol => {
+                if let Some(card) = cards.iter().filter(|c| c.has_rank()).choose(rng) {
+                    joker.target_rank = Some(card.rank);
+                    joker.target_suit = Some(card.suit);
+                }
+            }
+            JokerKind::AncientJoker => {
+                let current = joker.target_suit;
+                let choices = suits.iter().filter(|&&suit| Some(suit) != current);
+                joker.target_suit = choices.copied().choose(rng);
+            }
+            _ => {}
+        }
+    }
+
+    /// Records discarded cards, which feed Yorick's discard counter
+    pub fn on_discard(&mut self, cards: &[Card]) {
+        for joker in &mut self.jokers {
+            if joker.kind == JokerKind::Yorick {
+                joker.counter += cards.len() as u32;
+            }
+        }
+    }
+
+    /// Removes destroyed cards from the deck
+    ///
+    /// Destroyed face cards feed Canio and destroyed Glass cards feed Glass
+    /// Joker.
+    pub fn destroy(&mut self, cards: &[Card]) {
+        let rules = ScoringRules::from_jokers(&self.jokers);
+        let faces = cards.iter().filter(|card| card.is_face_card(&rules)).count() as u32;
+        let glass = cards
+            .iter()
+            .filter(|card| card.enhancement == Enhancement::Glass)
+            .count() as u32;
+
+        for card in cards {
+            if let Some(position) = self.cards.iter().position(|c| c == card) {
+                self.cards.remove(position);
+            }
+        }
+
+        for joker in &mut self.jokers {
+            match joker.kind {
+                JokerKind::Canio => joker.counter += faces,
+                JokerKind::GlassJoker => joker.counter += glass,
+                _ => {}
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for DeckType {
+    type Err = anyhow::Error;
+
+    /// Parses a deck name, ignoring case and an optional " Deck" suffix
+    /// (e.g., "plasma", "Plasma Deck")
+    ///
+    /// An unknown name is reported with the closest decks' names.
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let normalize = |name: &str| -> String {
+            let name = name.trim().to_lowercase();
+            name.strip_suffix("deck").unwrap_or(&name).trim().to_string()
+        };
+        let name = normalize(s);
+
+        DeckType::all()
+            .into_iter()
+            .find(|deck| format!("{:?}", deck).to_lowercase() == name)
+            .ok_or_else(|| {
+                let names = DeckType::all().map(|deck| deck.name());
+                unknown_name("deck", s, &names, normalize)
+            })
+    }
+}
+
+impl<'de> Deserialize<'de> for DeckType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deck_cards() {
+        let abandoned = DeckType::Abandoned.create_cards(0);
+        assert_eq!(abandoned.len(), 40);
+
+        let checkered = DeckType::Checkered.create_cards(0);
+        assert_eq!(checkered.iter().filter(|c| c.suit == Suit::Spades).count(), 26);
+        assert_eq!(checkered.iter().filter(|c| c.suit == Suit::Hearts).count(), 26);
+
+        let erratic = DeckType::Erratic.create_cards(7);
+        assert_eq!(erratic.len(), 52);
+        assert_eq!(erratic, DeckType::Erratic.create_cards(7));
+    }
+
+    #[test]
+    fn test_deck_modifiers() {
+        assert_eq!(DeckType::Red.modifiers().discards, 4);
+        assert_eq!(DeckType::Black.modifiers().hands, 3);
+        assert_eq!(DeckType::Painted.modifiers().hand_size, 10);
+        assert!(DeckType::Plasma.modifiers().plasma_balance);
+        assert!(!DeckType::Green.modifiers().earns_interest);
+    }
+
+    #[test]
+    fn test_deck_state_mutations() {
+        let king = Card::new(Rank::King, Suit::Hearts).with_enhancement(Enhancement::Bonus);
+        let jokers = vec![
+            Joker::new(JokerKind::MidasMask),
+            Joker::new(JokerKind::Vampire),
+            Joker::new(JokerKind::Dna),
+            Joker::new(JokerKind::Hologram),
+        ];
+        let mut state = DeckState::new(vec![king.clone()], jokers);
+
+        // Midas Mask turns the King Gold, then Vampire eats the enhancement
+        let mut hand = Hand::new(vec![king]);
+        let added = state.play_hand(&mut hand, true);
+
+        assert_eq!(added, 1);
+        assert_eq!(hand.cards[0].enhancement, Enhancement::None);
+        assert_eq!(state.jokers[1].counter, 1);
+        assert_eq!(state.jokers[3].counter, 1);
+        assert_eq!(state.cards.len(), 2);
+        assert!(state.cards.iter().all(|card| card.enhancement == Enhancement::None));
+    }
+
+    #[test]
+    fn test_discard_and_destroy_counters() {
+        let queen = Card::new(Rank::Queen, Suit::Hearts).with_enhancement(Enhancement::Glass);
+        let jokers = vec![
+            Joker::new(JokerKind::Yorick),
+            Joker::new(JokerKind::Canio),
+            Joker::new(JokerKind::GlassJoker),
+        ];
+        let mut state = DeckState::new(vec![queen.clone()], jokers);
+
+        state.on_discard(&[queen.clone(), queen.clone()]);
+        state.destroy(&[queen]);
+
+        assert_eq!(state.jokers[0].counter, 2);
+        assert_eq!(state.jokers[1].counter, 1);
+        assert_eq!(state.jokers[2].counter, 1);
+        assert!(state.cards.is_empty());
+    }
+
+    #[test]
+    fn test_lifecycle_hooks() {
+        let mut rng = ChaCha8Rng::seed_from_u64(1);
+        let jokers = vec![
+            Joker::new(JokerKind::CeremonialDagger),
+            Joker::new(JokerKind::Blueprint),
+            Joker::new(JokerKind::IceCream).with_counter(19),
+            Joker::new(JokerKind::Popcorn),
+        ];
+        let mut state = DeckState::new(Vec::new(), jokers);
+
+        state.on_blind_selected(BlindType::Boss, &mut rng);
+        assert_eq!(state.jokers.len(), 3);
+        assert_eq!(state.jokers[0].counter, 10); // Blueprint sells for $5
+
+        state.on_hand_played();
+        assert_eq!(state.jokers.len(), 2); // Ice Cream melted
+
+        state.on_round_end(&mut rng);
+        assert_eq!(state.jokers[1].counter, 1);
+
+        let jokers = vec![Joker::new(JokerKind::Joker), Joker::new(JokerKind::Madness)];
+        let mut state = DeckState::new(Vec::new(), jokers);
+        state.on_blind_selected(BlindType::Small, &mut rng);
+        assert_eq!(state.jokers, vec![Joker::new(JokerKind::Madness).with_counter(1)]);
+    }
+
+    #[test]
+    fn test_round_end_rerolls_targets() {
+        let mut rng = ChaCha8Rng::seed_from_u64(9);
+        let jokers = vec![
+            Joker::new(JokerKind::TheIdol),
+            Joker::new(JokerKind::AncientJoker).with_target(None, Some(Suit::Hearts)),
+        ];
+        let mut state = DeckState::new(vec![Card::new(Rank::Five, Suit::Clubs)], jokers);
+
+        state.on_round_end(&mut rng);
+
+        assert_eq!(state.jokers[0].target_rank, Some(Rank::Five));
+        assert_eq!(state.jokers[0].target_suit, Some(Suit::Clubs));
+        assert!(state.jokers[1].target_suit.is_some_and(|suit| suit != Suit::Hearts));
+    }
+
+    #[test]
+    fn test_parse_deck() {
+        assert_eq!("Plasma Deck".parse::<DeckType>().unwrap(), DeckType::Plasma);
+        assert_eq!("erratic".parse::<DeckType>().unwrap(), DeckType::Erratic);
+        assert!("Purple".parse::<DeckType>().is_err());
+        let typo = "Plsama Deck".parse::<DeckType>().unwrap_err().to_string();
+        assert_eq!(typo, "Unknown deck: Plsama Deck (did you mean Plasma Deck?)");
+
+        let deck: DeckType = serde_json::from_str(r#""Plasma Deck""#).unwrap();
+        assert_eq!(deck, DeckType::Plasma);
+        assert_eq!(serde_json::to_string(&deck).unwrap(), r#""plasma""#);
+        let error = serde_json::from_str::<DeckType>(r#""abandonned""#).unwrap_err();
+        assert!(error.to_string().contains("did you mean Abandoned Deck?"));
+    }
+}
@@ -0,0 +1,274 @@
+//! Deck composition statistics
+//!
+//! Summarizes what a deck is made of (suits, ranks, face cards, and
+//! modifiers) and how likely a freshly dealt hand is to hold a flush or a
+//! straight. The draw odds are exact: they count the hands of each make-up
+//! instead of sampling them.
+
+use super::card::{Card, Edition, Enhancement, Rank, Suit};
+use serde::{Serialize, Serializer};
+use std::fmt::Debug;
+
+/// Ranks from lowest to highest
+const RANKS: [Rank; 13] = [
+    Rank::Two, Rank::Three, Rank::Four, Rank::Five, Rank::Six, Rank::Seven, Rank::Eight,
+    Rank::Nine, Rank::Ten, Rank::Jack, Rank::Queen, Rank::King, Rank::Ace,
+];
+
+/// Ranks as written on the cards, in the order of `RANKS`
+const RANK_NAMES: [&str; 13] = ["2", "3", "4", "5", "6", "7", "8", "9", "10", "J", "Q", "K", "A"];
+
+const SUITS: [Suit; 4] = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades];
+
+/// Cards needed for a flush or a straight
+const HAND_CARDS: usize = 5;
+
+/// Composition and draw odds of a deck
+#[derive(Debug, Clone, Serialize)]
+pub struct DeckStats {
+    pub cards: usize,
+    /// Cards dealt for the draw odds
+    pub hand_size: usize,
+    /// Cards of each suit, Stone cards excluded and Wild cards by their
+    /// printed suit
+    #[serde(serialize_with = "as_map")]
+    pub suits: Vec<(String, usize)>,
+    /// Cards of each rank, lowest first, Stone cards excluded
+    #[serde(serialize_with = "as_map")]
+    pub ranks: Vec<(String, usize)>,
+    /// Jacks, Queens, and Kings
+    pub face_cards: usize,
+    /// Fraction of the deck that is face cards
+    pub face_ratio: f64,
+    /// Enhanced cards by enhancement (only those in the deck)
+    #[serde(serialize_with = "as_map")]
+    pub enhancements: Vec<(String, usize)>,
+    /// Cards by edition (only those in the deck)
+    #[serde(serialize_with = "as_map")]
+    pub editions: Vec<(String, usize)>,
+    /// Sealed cards by seal (only those in the deck)
+    #[serde(serialize_with = "as_map")]
+    pub seals: Vec<(String, usize)>,
+    /// Chance a dealt hand holds five cards of one suit (Wild cards count
+    /// as every suit)
+    pub flush_probability: f64,
+    /// Chance a dealt hand holds five ranks in a row (Ace high or low)
+    pub straight_probability: f64,
+}
+
+impl DeckStats {
+    /// Summarizes a deck, with the draw odds for a hand of `hand_size`
+    /// cards (or the whole deck, if smaller)
+    pub fn new(deck: &[Card], hand_size: usize) -> Self {
+        let ranked: Vec<&Card> = deck.iter().filter(|card| card.has_rank()).collect();
+        let face_cards = ranked
+            .iter()
+            .filter(|card| matches!(card.rank, Rank::Jack | Rank::Queen | Rank::King))
+            .count();
+
+        Self {
+            cards: deck.len(),
+            hand_size,
+            suits: SUITS
+                .iter()
+                .map(|&suit| {
+                    let count = ranked.iter().filter(|card| card.suit == suit).count();
+                    (format!("{:?}", suit), count)
+                })
+                .collect(),
+            ranks: RANKS
+                .iter()
+                .zip(RANK_NAMES)
+                .map(|(&rank, name)| {
+                    (name.to_string(), ranked.iter().filter(|card| card.rank == rank).count())
+                })
+                .collect(),
+            face_cards,
+            face_ratio: match deck.len() {
+                0 => 0.0,
+                cards => face_cards as f64 / cards as f64,
+            },
+            enhancements: count_each(
+                deck.iter().map(|card| card.enhancement).filter(|&e| e != Enhancement::None),
+            ),
+            editions: count_each(
+                deck.iter().map(|card| card.edition).filter(|&e| e != Edition::None),
+            ),
+            seals: count_each(deck.iter().filter_map(|card| card.seal)),
+            flush_probability: flush_probability(deck, hand_size),
+            straight_probability: straight_probability(deck, hand_size),
+        }
+    }
+}
+
+/// Serializes (name, count) pairs as a map, so csv gives a column per name
+fn as_map<S: Serializer>(counts: &[(String, usize)], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(counts.iter().map(|(name, count)| (name, count)))
+}
+
+/// Counts each value, in the order first seen
+fn count_each<T: PartialEq + Debug>(values: impl Iterator<Item = T>) -> Vec<(String, usize)> {
+    let mut counts: Vec<(T, usize)> = Vec::new();
+    for value in values {
+        match counts.iter_mut().find(|(seen, _)| *seen == value) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((value, 1)),
+        }
+    }
+    counts.into_iter().map(|(value, count)| (format!("{:?}", value), count)).collect()
+}
+
+/// Returns the number of ways to choose `k` of `n` items
+pub(crate) fn choose(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    let k = k.min(n - k);
+    (0..k).fold(1.0, |ways, i| ways * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// Chance that `hand_size` cards dealt from the deck hold a flush
+///
+/// Sums the hands by how many cards of each suit, Wild cards, and Stone
+/// cards they hold.
+fn flush_probability(deck: &[Card], hand_size: usize) -> f64 {
+    let hand_size = hand_size.min(deck.len());
+    let wild = deck.iter().filter(|card| card.enhancement == Enhancement::Wild).count();
+    let natural = |suit: Suit| {
+        deck.iter()
+            .filter(|card| card.suit == suit)
+            .filter(|card| !matches!(card.enhancement, Enhancement::Wild | Enhancement::Stone))
+            .count()
+    };
+    let suits = SUITS.map(natural);
+    let stone = deck.len() - wild - suits.iter().sum::<usize>();
+
+    /// Ways to deal `left` more cards from `suits` and the Wild and Stone
+    /// cards that make a flush, given the most cards of one suit
+    /// dealt so far
+    fn flushes(suits: &[usize], left: usize, best: usize, (wild, stone): (usize, usize)) -> f64 {
+        let Some((&count, rest)) = suits.split_first() else {
+            // Deal the rest as Wild and Stone cards
+            return (0..=left.min(wild))
+                .filter(|dealt| best + dealt >= HAND_CARDS)
+                .map(|dealt| choose(wild, dealt) * choose(stone, left - dealt))
+                .sum();
+        };
+        (0..=left.min(count))
+            .map(|dealt| {
+                choose(count, dealt) * flushes(rest, left - dealt, best.max(dealt), (wild, stone))
+            })
+            .sum()
+    }
+
+    flushes(&suits, hand_size, 0, (wild, stone)) / choose(deck.len(), hand_size)
+}
+
+/// Chance that `hand_size` cards dealt from the deck hold a straight
+///
+/// Walks the ranks from Two to King counting the hands by cards dealt and
+/// the length of the current run of ranks, with the number of Aces chosen
+/// first so they can end a run (10-A) or start one (A-5).
+fn straight_probability(deck: &[Card], hand_size: usize) -> f64 {
+    let hand_size = hand_size.min(deck.len());
+    let counts: Vec<usize> = RANKS
+        .iter()
+        .map(|rank| deck.iter().filter(|card| card.has_rank() && card.rank == *rank).count())
+        .collect();
+    let stone = deck.len() - counts.iter().sum::<usize>();
+    let (aces, others) = counts.split_last().expect("thirteen ranks");
+
+    let mut straights = 0.0;
+    for dealt_aces in 0..=hand_size.min(*aces) {
+        // ways[dealt][run][straight]: hands so far by cards dealt, current
+        // run length (capped at five), and whether a straight was made
+        let mut ways = vec![[[0.0; 2]; HAND_CARDS + 1]; hand_size + 1];
+        ways[dealt_aces][usize::from(dealt_aces > 0)][0] = choose(*aces, dealt_aces);
+
+        for &count in others {
+            let mut next = vec![[[0.0; 2]; HAND_CARDS + 1]; hand_size + 1];
+            for (dealt, runs) in ways.iter().enumerate() {
+                for (run, by_made) in runs.iter().enumerate() {
+                    for (made, &hands) in by_made.iter().enumerate() {
+                        if hands == 0.0 {
+                            continue;
+                        }
+                        for more in 0..=count.min(hand_size - dealt) {
+                            let run = if more > 0 { (run + 1).min(HAND_CARDS) } else { 0 };
+                            let made = usize::from(made == 1 || run == HAND_CARDS);
+                            next[dealt + more][run][made] += hands * choose(count, more);
+                        }
+                    }
+                }
+            }
+            ways = next;
+        }
+
+        for (dealt, runs) in ways.iter().enumerate() {
+            for (run, hands) in runs.iter().enumerate() {
+                let ace_high = dealt_aces > 0 && run >= HAND_CARDS - 1;
+                let made = hands[1] + if ace_high { hands[0] } else { 0.0 };
+                straights += made * choose(stone, hand_size - dealt);
+            }
+        }
+    }
+
+    straights / choose(deck.len(), hand_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::simulator::create_standard_deck;
+
+    #[test]
+    fn test_standard_deck_stats() {
+        let stats = DeckStats::new(&create_standard_deck(), 5);
+        assert_eq!(stats.cards, 52);
+        assert_eq!(stats.suits[0], ("Hearts".to_string(), 13));
+        assert_eq!(stats.ranks[8], ("10".to_string(), 4));
+        assert_eq!(stats.face_cards, 12);
+        assert!(stats.enhancements.is_empty());
+
+        // 5,148 flushes and 10,240 straights (straight flushes included)
+        // among the 2,598,960 five-card hands
+        assert!((stats.flush_probability - 5_148.0 / 2_598_960.0).abs() < 1e-12);
+        assert!((stats.straight_probability - 10_240.0 / 2_598_960.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_modified_deck_stats() {
+        let mut deck: Vec<Card> = create_standard_deck()
+            .into_iter()
+            .filter(|card| card.suit == Suit::Hearts)
+            .collect();
+        deck[0] = deck[0].clone().with_enhancement(Enhancement::Stone);
+        deck[1] = deck[1].clone().with_enhancement(Enhancement::Wild).with_edition(Edition::Foil);
+
+        let stats = DeckStats::new(&deck, 5);
+        assert_eq!(stats.suits[0], ("Hearts".to_string(), 12));
+        assert_eq!(stats.ranks[0], ("2".to_string(), 0));
+        assert_eq!(
+            stats.enhancements,
+            vec![("Stone".to_string(), 1), ("Wild".to_string(), 1)]
+        );
+        assert_eq!(stats.editions, vec![("Foil".to_string(), 1)]);
+        // Every five cards but those with the Stone card are a flush
+        let with_stone = choose(12, 4) / choose(13, 5);
+        assert!((stats.flush_probability - (1.0 - with_stone)).abs() < 1e-12);
+
+        let thin = DeckStats::new(&deck[..3], 8);
+        assert_eq!(thin.flush_probability, 0.0);
+        assert_eq!(DeckStats::new(&[], 8).straight_probability, 0.0);
+    }
+
+    #[test]
+    fn test_more_cards_more_straights() {
+        let deck = create_standard_deck();
+        let five = straight_probability(&deck, 5);
+        let eight = straight_probability(&deck, 8);
+        assert!(eight > five && eight < 1.0);
+        assert!(straight_probability(&deck, 52) == 1.0);
+        assert!((flush_probability(&deck, 17) - 1.0).abs() < 1e-12);
+    }
+}
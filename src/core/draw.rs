@@ -0,0 +1,148 @@
+//! Draw piles
+//!
+//! A simulated round deals a handful of cards from a much larger deck. A
+//! [`DrawPile`] deals them one at a time in a random order by a partial
+//! Fisher–Yates shuffle over positions in the deck, so a round pays only
+//! for the cards it draws instead of copying and shuffling the whole deck.
+//!
+//! For variance reduction, a pile can be reversed, dealing the cards a pile
+//! from the same seed would deal last first, or made to deal a chosen card
+//! first.
+
+use super::card::Card;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+
+/// The undrawn cards of a deck, dealt in a random order
+pub struct DrawPile<'a> {
+    deck: &'a [Card],
+    /// Positions in `deck` of the cards not yet drawn
+    remaining: Vec<usize>,
+    rng: ChaCha8Rng,
+    /// Deals from the end of `remaining`, in order, instead of at random
+    reversed: bool,
+    /// Position dealt by the next draw, instead of a random one
+    first: Option<usize>,
+}
+
+impl<'a> DrawPile<'a> {
+    /// Creates a pile of the whole deck, dealt in an order drawn from the seed
+    pub fn new(deck: &'a [Card], seed: u64) -> Self {
+        Self {
+            deck,
+            remaining: (0..deck.len()).collect(),
+            rng: ChaCha8Rng::seed_from_u64(seed),
+            reversed: false,
+            first: None,
+        }
+    }
+
+    /// Deals the cards in the reverse of the order a pile from the same
+    /// seed would, so the two piles' first cards never overlap
+    pub fn reversed(mut self) -> Self {
+        // Play out the whole forward order, which is dealt back to front
+        let mut forward = Self::new(self.deck, 0);
+        forward.rng = self.rng.clone();
+        self.remaining = std::iter::from_fn(|| forward.draw_position()).collect();
+        self.reversed = true;
+        self
+    }
+
+    /// Deals the card at `position` first (ignored if out of range)
+    pub fn with_first(mut self, position: usize) -> Self {
+        self.first = (position < self.deck.len()).then_some(position);
+        self
+    }
+
+    /// Returns the number of cards left to draw
+    pub fn len(&self) -> usize {
+        self.remaining.len()
+    }
+
+    /// Returns true if every card has been drawn
+    pub fn is_empty(&self) -> bool {
+        self.remaining.is_empty()
+    }
+
+    /// Draws a random card, or `None` if the pile is empty
+    pub fn draw(&mut self) -> Option<&'a Card> {
+        self.draw_position().map(|position| &self.deck[position])
+    }
+
+    /// Draws the position in the deck of the next card
+    fn draw_position(&mut self) -> Option<usize> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        if let Some(position) = self.first.take() {
+            let i = self.remaining.iter().position(|&p| p == position)?;
+            return Some(match self.reversed {
+                true => self.remaining.remove(i),
+                false => self.remaining.swap_remove(i),
+            });
+        }
+        match self.reversed {
+            true => self.remaining.pop(),
+            false => {
+                let i = self.rng.gen_range(0..self.remaining.len());
+                Some(self.remaining.swap_remove(i))
+            }
+        }
+    }
+
+    /// Draws until the hand holds `hand_size` cards or the pile is empty
+    pub fn draw_to(&mut self, hand: &mut Vec<Card>, hand_size: usize) {
+        while hand.len() < hand_size
+            && let Some(card) = self.draw()
+        {
+            hand.push(card.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::simulator::create_standard_deck;
+
+    #[test]
+    fn test_draws_every_card_once() {
+        let deck = create_standard_deck();
+        let mut pile = DrawPile::new(&deck, 7);
+
+        let mut hand = Vec::new();
+        pile.draw_to(&mut hand, 8);
+        assert_eq!((hand.len(), pile.len()), (8, 44));
+
+        hand.extend(std::iter::from_fn(|| pile.draw().cloned()));
+        assert!(pile.is_empty());
+        assert_eq!(hand.len(), 52);
+        assert!(deck.iter().all(|card| hand.contains(card)));
+        assert_ne!(hand[..8], deck[..8]);
+
+        let mut again = Vec::new();
+        DrawPile::new(&deck, 7).draw_to(&mut again, 8);
+        assert_eq!(again, hand[..8]);
+    }
+
+    #[test]
+    fn test_reversed_and_first_card() {
+        let deck = create_standard_deck();
+        let forward: Vec<Card> = {
+            let mut pile = DrawPile::new(&deck, 3);
+            std::iter::from_fn(|| pile.draw().cloned()).collect()
+        };
+        let mut pile = DrawPile::new(&deck, 3).reversed();
+        assert_eq!(pile.len(), 52);
+        let mut reversed: Vec<Card> = std::iter::from_fn(|| pile.draw().cloned()).collect();
+        reversed.reverse();
+        assert_eq!(reversed, forward);
+
+        let mut pile = DrawPile::new(&deck, 3).with_first(20);
+        assert_eq!(pile.draw(), Some(&deck[20]));
+        let mut rest: Vec<Card> = std::iter::from_fn(|| pile.draw().cloned()).collect();
+        rest.push(deck[20].clone());
+        assert_eq!(rest.len(), 52);
+        assert!(deck.iter().all(|card| rest.contains(card)));
+    }
+}
@@ -0,0 +1,347 @@
+//! Explanations of how plays score
+//!
+//! Compares the score events of two plays source by source (hand base,
+//! cards, enhancements, jokers), so the differences that decide between
+//! them can be shown, e.g. "Flush base (+35 chips, +4 mult) and Droll Joker
+//! (+10 mult) outweigh Pair base and Jolly Joker". A single play's events
+//! can also be replayed as a trace of the running chips and mult.
+
+use super::hand::HandType;
+use super::score::Score;
+use super::scoring::{ScoreEffect, ScoreEvent, ScoreResult};
+use serde::{Deserialize, Serialize};
+
+/// Number of sources named on each side of a summary
+const SUMMARY_SOURCES: usize = 2;
+
+/// Everything one source added to a play
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Contribution {
+    pub chips: i32,
+    pub mult: f64,
+    /// Product of every x-mult applied (1.0 if none)
+    pub x_mult: f64,
+}
+
+impl Default for Contribution {
+    fn default() -> Self {
+        Self {
+            chips: 0,
+            mult: 0.0,
+            x_mult: 1.0,
+        }
+    }
+}
+
+impl Contribution {
+    /// Adds one event's effect
+    fn add(&mut self, effect: ScoreEffect) {
+        match effect {
+            ScoreEffect::Chips(chips) => self.chips += chips,
+            ScoreEffect::Mult(mult) => self.mult += mult,
+            ScoreEffect::XMult(multiplier) => self.x_mult *= multiplier,
+        }
+    }
+
+    /// Estimates how much this contribution raises a play's score, relative
+    /// to the play's total chips and added mult
+    fn impact(&self, result: &ScoreResult, added_mult: f64) -> f64 {
+        self.chips as f64 / (result.chips as f64).max(1.0)
+            + self.mult / added_mult.max(1.0)
+            + self.x_mult.ln()
+    }
+}
+
+impl std::fmt::Display for Contribution {
+    /// Formats the non-zero parts, e.g. "+35 chips, +4 mult, x1.5"
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = Vec::new();
+        if self.chips != 0 {
+            parts.push(format!("{:+} chips", self.chips));
+        }
+        if self.mult != 0.0 {
+            parts.push(format!("{:+} mult", self.mult));
+        }
+        if self.x_mult != 1.0 {
+            parts.push(format!("x{}", self.x_mult));
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+/// How one source contributed to each of the two plays compared
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceDiff {
+    /// The joker, enhancement, or base the contributions came from, without
+    /// the card it triggered on (e.g., "Greedy Joker", "Flush base")
+    pub source: String,
+    pub best: Contribution,
+    pub runner_up: Contribution,
+    /// Estimated effect on the difference in score: positive when the
+    /// source favors the best play
+    pub impact: f64,
+}
+
+impl SourceDiff {
+    /// Returns what the source added to the best play beyond what it added
+    /// to the runner-up
+    pub fn net(&self) -> Contribution {
+        Contribution {
+            chips: self.best.chips - self.runner_up.chips,
+            mult: self.best.mult - self.runner_up.mult,
+            x_mult: self.best.x_mult / self.runner_up.x_mult,
+        }
+    }
+}
+
+/// Why the best play scores more than the runner-up
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayComparison {
+    pub best_hand_type: HandType,
+    pub runner_up_hand_type: HandType,
+    pub best_score: Score,
+    pub runner_up_score: Score,
+    /// Sources that contributed differently, most in favor of the best play
+    /// first
+    pub differences: Vec<SourceDiff>,
+}
+
+impl PlayComparison {
+    /// Compares the score events of two plays
+    pub fn new(best: &ScoreResult, runner_up: &ScoreResult) -> Self {
+        let best_sources = contributions(&best.breakdown.events);
+        let runner_up_sources = contributions(&runner_up.breakdown.events);
+        let added_mult = |sources: &[(String, Contribution)]| -> f64 {
+            sources.iter().map(|(_, contribution)| contribution.mult).sum()
+        };
+        let (best_mult, runner_up_mult) = (added_mult(&best_sources), added_mult(&runner_up_sources));
+
+        let mut names: Vec<&String> = best_sources.iter().map(|(name, _)| name).collect();
+        for (name, _) in &runner_up_sources {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        let find = |sources: &[(String, Contribution)], name: &str| {
+            sources
+                .iter()
+                .find(|(source, _)| source == name)
+                .map(|(_, contribution)| *contribution)
+                .unwrap_or_default()
+        };
+        let mut differences: Vec<SourceDiff> = names
+            .into_iter()
+            .map(|name| {
+                let best_part = find(&best_sources, name);
+                let runner_up_part = find(&runner_up_sources, name);
+                SourceDiff {
+                    source: name.clone(),
+                    impact: best_part.impact(best, best_mult)
+                        - runner_up_part.impact(runner_up, runner_up_mult),
+                    best: best_part,
+                    runner_up: runner_up_part,
+                }
+            })
+            .filter(|diff| diff.best != diff.runner_up)
+            .collect();
+        differences.sort_by(|a, b| b.impact.total_cmp(&a.impact));
+
+        Self {
+            best_hand_type: best.hand_type,
+            runner_up_hand_type: runner_up.hand_type,
+            best_score: best.score,
+            runner_up_score: runner_up.score,
+            differences,
+        }
+    }
+
+    /// Returns the sources favoring the best play, strongest first
+    pub fn favoring_best(&self) -> impl Iterator<Item = &SourceDiff> {
+        self.differences.iter().filter(|diff| diff.impact > 0.0)
+    }
+
+    /// Returns the sources favoring the runner-up, strongest first
+    pub fn favoring_runner_up(&self) -> impl Iterator<Item = &SourceDiff> {
+        self.differences.iter().rev().filter(|diff| diff.impact < 0.0)
+    }
+}
+
+impl std::fmt::Display for PlayComparison {
+    /// Summarizes the deciding sources, e.g. "Flush base (+35 chips, +4
+    /// mult) outweighs Pair base and Jolly Joker"
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let winners: Vec<String> = self
+            .favoring_best()
+            .take(SUMMARY_SOURCES)
+            .map(|diff| format!("{} ({})", diff.source, diff.net()))
+            .collect();
+        let losers: Vec<&str> = self
+            .favoring_runner_up()
+            .take(SUMMARY_SOURCES)
+            .map(|diff| diff.source.as_str())
+            .collect();
+
+        if winners.is_empty() {
+            return write!(f, "{:?} ties {:?}", self.best_hand_type, self.runner_up_hand_type);
+        }
+        let verb = if winners.len() == 1 { "outweighs" } else { "outweigh" };
+        match losers.is_empty() {
+            true => write!(f, "{} beat {:?}", winners.join(" and "), self.runner_up_hand_type),
+            false => write!(f, "{} {} {}", winners.join(" and "), verb, losers.join(" and ")),
+        }
+    }
+}
+
+/// A score event and the running chips and mult once it has applied
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TraceStep {
+    pub event: ScoreEvent,
+    pub chips: i32,
+    pub mult: f64,
+}
+
+/// Replays a play's score events in the order they applied
+///
+/// The last step holds the play's chips and mult, before any Plasma Deck
+/// balancing.
+pub fn score_trace(result: &ScoreResult) -> Vec<TraceStep> {
+    let (mut chips, mut mult) = (0, 0.0);
+    result
+        .breakdown
+        .events
+        .iter()
+        .map(|event| {
+            match event.effect {
+                ScoreEffect::Chips(added) => chips += added,
+                ScoreEffect::Mult(added) => mult += added,
+                ScoreEffect::XMult(multiplier) => mult *= multiplier,
+            }
+            TraceStep {
+                event: event.clone(),
+                chips,
+                mult,
+            }
+        })
+        .collect()
+}
+
+/// Sums events by source, in the order sources first appear
+///
+/// Events triggered on a card ("Greedy Joker on 7♦") count toward their
+/// source ("Greedy Joker").
+fn contributions(events: &[ScoreEvent]) -> Vec<(String, Contribution)> {
+    let mut sources: Vec<(String, Contribution)> = Vec::new();
+    for event in events {
+        let name = event.source.split(" on ").next().unwrap_or(&event.source);
+        match sources.iter_mut().find(|(source, _)| source == name) {
+            Some((_, contribution)) => contribution.add(event.effect),
+            None => {
+                let mut contribution = Contribution::default();
+                contribution.add(event.effect);
+                sources.push((name.to_string(), contribution));
+            }
+        }
+    }
+    sources
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::card::{Card, Rank, Suit};
+    use crate::core::hand::Hand;
+    use crate::core::joker::{Joker, JokerKind};
+    use crate::core::card::{Edition, Seal};
+    use crate::core::scoring::{ScoreCalculator, ScoreStage};
+
+    #[test]
+    fn test_flush_beats_pair() {
+        let calculator = ScoreCalculator::new(vec![
+            Joker::new(JokerKind::DrollJoker),
+            Joker::new(JokerKind::JollyJoker),
+        ]);
+        let flush = Hand::new(vec![
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Five, Suit::Hearts),
+            Card::new(Rank::Seven, Suit::Hearts),
+            Card::new(Rank::Nine, Suit::Hearts),
+            Card::new(Rank::Jack, Suit::Hearts),
+        ]);
+        let pair = Hand::new(vec![
+            Card::new(Rank::Jack, Suit::Hearts),
+            Card::new(Rank::Jack, Suit::Spades),
+        ]);
+
+        let comparison =
+            PlayComparison::new(&calculator.calculate(&flush, &[]), &calculator.calculate(&pair, &[]));
+
+        assert_eq!(comparison.differences[0].source, "Flush base");
+        let droll = comparison.favoring_best().find(|d| d.source == "Droll Joker").unwrap();
+        assert_eq!(droll.net().mult, 10.0);
+        // Both plays score the J♥, so it is not a difference
+        assert!(comparison.differences.iter().all(|d| d.source != "J♥ base"));
+        assert_eq!(
+            comparison.to_string(),
+            "Flush base (+35 chips, +4 mult) and Droll Joker (+10 mult) outweigh Jolly Joker and Pair base"
+        );
+    }
+
+    #[test]
+    fn test_trace_replays_to_final_score() {
+        let calculator = ScoreCalculator::new(vec![
+            Joker::new(JokerKind::Joker),
+            Joker::new(JokerKind::SockAndBuskin),
+            Joker::new(JokerKind::Baron),
+        ]);
+        let hand = Hand::new(vec![
+            Card::new(Rank::King, Suit::Hearts).with_seal(Seal::Red),
+            Card::new(Rank::King, Suit::Spades).with_edition(Edition::Polychrome),
+        ]);
+        let held = [Card::new(Rank::King, Suit::Clubs)];
+
+        let result = calculator.calculate(&hand, &held);
+        let trace = score_trace(&result);
+        let last = trace.last().unwrap();
+        assert_eq!((last.chips as u32, last.mult), (result.chips, result.mult));
+
+        // The Red seal and Sock and Buskin each retrigger the first King
+        let triggers: Vec<u32> = trace
+            .iter()
+            .filter_map(|step| match &step.event.stage {
+                ScoreStage::Played { position: 0, trigger, .. } => Some(*trigger),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(triggers.iter().max(), Some(&2));
+        assert!(trace.iter().any(|step| step.event.stage == ScoreStage::Held));
+        assert_eq!(trace[0].event.stage, ScoreStage::Hand);
+        assert_eq!(last.event.stage, ScoreStage::Jokers);
+    }
+
+    #[test]
+    fn test_contributions_group_by_source() {
+        let events = vec![
+            ScoreEvent {
+                source: "Greedy Joker on 7♦".to_string(),
+                effect: ScoreEffect::Mult(3.0),
+                stage: ScoreStage::Jokers,
+            },
+            ScoreEvent {
+                source: "Greedy Joker on 2♦".to_string(),
+                effect: ScoreEffect::Mult(3.0),
+                stage: ScoreStage::Jokers,
+            },
+            ScoreEvent {
+                source: "Glass card on 2♦".to_string(),
+                effect: ScoreEffect::XMult(2.0),
+                stage: ScoreStage::Jokers,
+            },
+        ];
+
+        let sources = contributions(&events);
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0].1.mult, 6.0);
+        assert_eq!(sources[1].1.to_string(), "x2");
+    }
+}
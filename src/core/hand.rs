@@ -3,12 +3,13 @@
 //! This module handles evaluating collections of cards to determine
 //! poker hand types and their base scoring values.
 
-use super::card::{Card, Rank};
+use super::card::{Card, Rank, Suit};
+use super::names::unknown_name;
+use super::rules::ScoringRules;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
 
 /// Represents the type of poker hand
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum HandType {
     HighCard,
     Pair,
@@ -60,6 +61,103 @@ impl HandType {
             HandType::FlushFive => 16,
         }
     }
+
+    /// Returns the chips gained per level above 1 (from Planet cards)
+    pub fn level_chips(&self) -> u32 {
+        match self {
+            HandType::HighCard => 10,
+            HandType::Pair => 15,
+            HandType::TwoPair => 20,
+            HandType::ThreeOfAKind => 20,
+            HandType::Straight => 30,
+            HandType::Flush => 15,
+            HandType::FullHouse => 25,
+            HandType::FourOfAKind => 30,
+            HandType::StraightFlush => 40,
+            HandType::FiveOfAKind => 35,
+            HandType::FlushHouse => 40,
+            HandType::FlushFive => 50,
+        }
+    }
+
+    /// Returns the mult gained per level above 1 (from Planet cards)
+    pub fn level_mult(&self) -> u32 {
+        match self {
+            HandType::HighCard => 1,
+            HandType::Pair => 1,
+            HandType::TwoPair => 1,
+            HandType::ThreeOfAKind => 2,
+            HandType::Straight => 3,
+            HandType::Flush => 2,
+            HandType::FullHouse => 2,
+            HandType::FourOfAKind => 3,
+            HandType::StraightFlush => 4,
+            HandType::FiveOfAKind => 3,
+            HandType::FlushHouse => 4,
+            HandType::FlushFive => 3,
+        }
+    }
+
+    /// Returns every hand type, from weakest to strongest
+    pub fn all() -> [HandType; 12] {
+        [
+            HandType::HighCard,
+            HandType::Pair,
+            HandType::TwoPair,
+            HandType::ThreeOfAKind,
+            HandType::Straight,
+            HandType::Flush,
+            HandType::FullHouse,
+            HandType::FourOfAKind,
+            HandType::StraightFlush,
+            HandType::FiveOfAKind,
+            HandType::FlushHouse,
+            HandType::FlushFive,
+        ]
+    }
+
+    /// Returns the hand's name as shown in the game
+    pub fn name(&self) -> &'static str {
+        match self {
+            HandType::HighCard => "High Card",
+            HandType::Pair => "Pair",
+            HandType::TwoPair => "Two Pair",
+            HandType::ThreeOfAKind => "Three of a Kind",
+            HandType::Straight => "Straight",
+            HandType::Flush => "Flush",
+            HandType::FullHouse => "Full House",
+            HandType::FourOfAKind => "Four of a Kind",
+            HandType::StraightFlush => "Straight Flush",
+            HandType::FiveOfAKind => "Five of a Kind",
+            HandType::FlushHouse => "Flush House",
+            HandType::FlushFive => "Flush Five",
+        }
+    }
+}
+
+impl std::str::FromStr for HandType {
+    type Err = anyhow::Error;
+
+    /// Parses a hand type name, ignoring case, spaces, dashes, and underscores
+    /// (e.g., "Flush", "full_house", "Two Pair")
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let normalize = |name: &str| -> String {
+            name.chars()
+                .filter(|c| !matches!(c, ' ' | '_' | '-'))
+                .collect::<String>()
+                .to_lowercase()
+        };
+        let normalized = normalize(s);
+
+        HandType::all()
+            .into_iter()
+            .find(|hand_type| format!("{:?}", hand_type).to_lowercase() == normalized)
+            .ok_or_else(|| {
+                let names = HandType::all().map(|hand_type| format!("{:?}", hand_type));
+                let names: Vec<&str> = names.iter().map(String::as_str).collect();
+                unknown_name("hand type", s, &names, normalize)
+            })
+    }
 }
 
 /// Represents a collection of cards that form a playable hand
@@ -76,27 +174,161 @@ impl Hand {
 
     /// Evaluates the hand to determine its type
     pub fn evaluate(&self) -> HandType {
+        self.evaluate_with(&ScoringRules::default())
+    }
+
+    /// Evaluates the hand under the given rule modifiers
+    pub fn evaluate_with(&self, rules: &ScoringRules) -> HandType {
+        self.hand_type_of(&self.shape(rules))
+    }
+
+    /// Evaluates the hand and finds its scoring cards in one pass, as
+    /// [`Hand::evaluate_with`] and [`Hand::scoring_cards_with`] would
+    pub fn evaluate_scoring(&self, rules: &ScoringRules) -> (HandType, Vec<&Card>) {
+        let shape = self.shape(rules);
+        let hand_type = self.hand_type_of(&shape);
+        (hand_type, self.scoring_cards_of(hand_type, &shape, rules))
+    }
+
+    /// Determines the hand type from the hand's shape
+    fn hand_type_of(&self, shape: &Shape) -> HandType {
         if self.cards.is_empty() {
             return HandType::HighCard;
         }
-
-        let is_flush = self.is_flush();
-        let is_straight = self.is_straight();
-        let rank_counts = self.rank_counts();
+        let (is_flush, is_straight) = (shape.flush.is_some(), shape.straight.is_some());
 
         // Check for special Balatro hands
-        if let Some(hand_type) = self.check_special_hands(&rank_counts, is_flush, is_straight) {
+        if let Some(hand_type) = self.check_special_hands(&shape.rank_counts, is_flush, is_straight)
+        {
             return hand_type;
         }
 
         // Check standard poker hands
-        self.check_standard_hands(&rank_counts, is_flush, is_straight)
+        self.check_standard_hands(&shape.rank_counts, is_flush, is_straight)
+    }
+
+    /// Returns the cards that score for this hand, in played order
+    ///
+    /// Only cards that form the hand type score (the pair in a Pair, the
+    /// highest card in a High Card, and so on). Stone cards always score.
+    pub fn scoring_cards(&self) -> Vec<&Card> {
+        self.scoring_cards_with(&ScoringRules::default())
+    }
+
+    /// Returns the cards that score under the given rule modifiers
+    ///
+    /// With Splash every played card scores.
+    pub fn scoring_cards_with(&self, rules: &ScoringRules) -> Vec<&Card> {
+        if rules.splash {
+            return self.cards.iter().collect();
+        }
+        self.evaluate_scoring(rules).1
+    }
+
+    /// Returns the cards that score for a hand of the given type and shape
+    fn scoring_cards_of(&self, hand_type: HandType, shape: &Shape, rules: &ScoringRules) -> Vec<&Card> {
+        if rules.splash {
+            return self.cards.iter().collect();
+        }
+
+        let in_straight =
+            |card: &Card| shape.straight.is_some_and(|ranks| Self::in_ranks(ranks, card));
+        let in_flush = |card: &Card| shape.flush.is_some_and(|suit| card.is_suit(suit));
+
+        let forms_hand = |card: &Card| -> bool {
+            let count = shape.rank_counts.get(card.rank);
+            match hand_type {
+                HandType::HighCard => false,
+                HandType::Pair | HandType::TwoPair => count == 2,
+                HandType::ThreeOfAKind => count == 3,
+                HandType::FourOfAKind => count == 4,
+                HandType::Straight => in_straight(card),
+                HandType::Flush => in_flush(card),
+                HandType::StraightFlush => in_straight(card) || in_flush(card),
+                _ => true,
+            }
+        };
+
+        let high_card = if hand_type == HandType::HighCard {
+            self.ranked_cards().max_by_key(|card| card.rank.value())
+        } else {
+            None
+        };
+
+        self.cards
+            .iter()
+            .filter(|card| {
+                if !card.has_rank() {
+                    return true;
+                }
+                match high_card {
+                    Some(high) => std::ptr::eq(*card, high),
+                    None => forms_hand(card),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the positions of the scoring cards within `cards`
+    pub fn scoring_indices_with(&self, rules: &ScoringRules) -> Vec<usize> {
+        let scoring = self.scoring_cards_with(rules);
+
+        (0..self.cards.len())
+            .filter(|&i| scoring.iter().any(|card| std::ptr::eq(*card, &self.cards[i])))
+            .collect()
+    }
+
+    /// Returns the cards forming a straight under the given rule modifiers,
+    /// in played order, or `None` if there is no straight
+    ///
+    /// Duplicate ranks within the straight are all included. An Ace is
+    /// included when it plays either high or low.
+    pub fn straight_cards_with(&self, rules: &ScoringRules) -> Option<Vec<&Card>> {
+        let straight = self.straight_ranks(rules)?;
+        Some(self.ranked_cards().filter(|card| Self::in_ranks(straight, card)).collect())
+    }
+
+    /// Returns the cards forming a flush under the given rule modifiers, in
+    /// played order, or `None` if there is no flush
+    ///
+    /// Wild cards are included as part of any flush.
+    pub fn flush_cards_with(&self, rules: &ScoringRules) -> Option<Vec<&Card>> {
+        let suit = self.flush_suit(rules)?;
+        Some(self.ranked_cards().filter(|card| card.is_suit(suit)).collect())
+    }
+
+    /// Returns true if the hand contains the given hand type
+    ///
+    /// A hand contains every hand type it is built from, e.g. a Full House
+    /// contains a Pair, a Two Pair, and a Three of a Kind.
+    pub fn contains(&self, hand_type: HandType, rules: &ScoringRules) -> bool {
+        let rank_counts = self.rank_counts();
+        let max_count = rank_counts.values().max().copied().unwrap_or(0);
+        let groups = |min: usize| rank_counts.values().filter(|&&count| count >= min).count();
+        let is_flush = || self.flush_suit(rules).is_some();
+        let is_straight = || self.straight_ranks(rules).is_some();
+        let is_full_house = max_count >= 3 && groups(2) >= 2;
+
+        match hand_type {
+            HandType::HighCard => !self.cards.is_empty(),
+            HandType::Pair => max_count >= 2,
+            HandType::TwoPair => groups(2) >= 2,
+            HandType::ThreeOfAKind => max_count >= 3,
+            HandType::Straight => is_straight(),
+            HandType::Flush => is_flush(),
+            HandType::FullHouse => is_full_house,
+            HandType::FourOfAKind => max_count >= 4,
+            HandType::StraightFlush => is_straight() && is_flush(),
+            HandType::FiveOfAKind => max_count >= 5,
+            HandType::FlushHouse => is_full_house && is_flush(),
+            HandType::FlushFive => max_count >= 5 && is_flush(),
+        }
     }
 
     /// Checks for special Balatro-specific hand types
     fn check_special_hands(
         &self,
-        rank_counts: &HashMap<Rank, usize>,
+        rank_counts: &RankCounts,
         is_flush: bool,
         _is_straight: bool,
     ) -> Option<HandType> {
@@ -123,7 +355,7 @@ impl Hand {
     /// Checks for standard poker hand types
     fn check_standard_hands(
         &self,
-        rank_counts: &HashMap<Rank, usize>,
+        rank_counts: &RankCounts,
         is_flush: bool,
         is_straight: bool,
     ) -> HandType {
@@ -174,66 +406,125 @@ impl Hand {
         HandType::HighCard
     }
 
-    /// Checks if all cards are the same suit
-    fn is_flush(&self) -> bool {
-        if self.cards.len() < 5 {
-            return false;
+    /// Finds the rank counts, flush, and straight the hand type depends on
+    fn shape(&self, rules: &ScoringRules) -> Shape {
+        Shape {
+            rank_counts: self.rank_counts(),
+            flush: self.flush_suit(rules),
+            straight: self.straight_ranks(rules),
         }
-        let first_suit = self.cards[0].suit;
-        self.cards.iter().all(|card| card.suit == first_suit)
     }
 
-    /// Checks if cards form a straight (consecutive ranks)
-    fn is_straight(&self) -> bool {
-        if self.cards.len() < 5 {
-            return false;
+    /// Returns the suit of the flush, treating Wild cards as any suit
+    ///
+    /// A flush needs 5 cards of one suit (4 with Four Fingers). Stone cards
+    /// have no suit and never count towards a flush.
+    fn flush_suit(&self, rules: &ScoringRules) -> Option<Suit> {
+        let min_len = rules.min_straight_flush_len();
+        if self.ranked_cards().count() < min_len {
+            return None;
         }
+        [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades]
+            .into_iter()
+            .find(|&suit| self.ranked_cards().filter(|card| card.is_suit(suit)).count() >= min_len)
+    }
 
-        let mut values: Vec<u8> = self.cards.iter().map(|card| card.rank.value()).collect();
-        values.sort_unstable();
-        values.dedup();
+    /// Returns the rank values forming a straight, if any, as a bit set
+    /// (bit `v` for value `v`)
+    ///
+    /// A straight needs 5 consecutive ranks (4 with Four Fingers, and with
+    /// Shortcut adjacent ranks may skip one). An Ace can play low, in which
+    /// case bit 1 is set instead of bit 14.
+    fn straight_ranks(&self, rules: &ScoringRules) -> Option<u16> {
+        let min_len = rules.min_straight_flush_len();
+        let max_gap = rules.max_straight_gap();
+        if self.cards.len() < min_len {
+            return None;
+        }
 
-        if values.len() < 5 {
-            return false;
+        let mut present = 0u16;
+        for card in self.ranked_cards() {
+            present |= 1 << card.rank.value();
+        }
+        if present & (1 << 14) != 0 {
+            present |= 1 << 1; // Ace-low
         }
 
-        // Check for consecutive values
-        for window in values.windows(5) {
-            if window[4] - window[0] == 4 {
-                return true;
+        // Find the longest (then highest) run of connected values
+        let mut best: Option<(u16, usize)> = None;
+        let (mut run, mut len, mut last) = (0u16, 0usize, 0u8);
+        for value in (1..=14u8).filter(|value| present & (1 << value) != 0) {
+            if len == 0 || value - last > max_gap {
+                (run, len) = (0, 0);
+            }
+            run |= 1 << value;
+            len += 1;
+            last = value;
+            if len >= min_len && best.is_none_or(|(_, best_len)| len >= best_len) {
+                best = Some((run, len));
             }
         }
 
-        // Check for Ace-low straight (A-2-3-4-5)
-        if values.contains(&14) && values.contains(&2) && values.contains(&3)
-            && values.contains(&4) && values.contains(&5) {
-            return true;
-        }
+        best.map(|(run, _)| run)
+    }
+
+    /// Returns true if the card's rank is in a bit set of rank values, an
+    /// Ace matching either bit 14 or bit 1
+    fn in_ranks(ranks: u16, card: &Card) -> bool {
+        let value = card.rank.value();
+        ranks & (1 << value) != 0 || (value == 14 && ranks & (1 << 1) != 0)
+    }
 
-        false
+    /// Returns the cards that have a rank and suit (everything but Stone cards)
+    fn ranked_cards(&self) -> impl Iterator<Item = &Card> {
+        self.cards.iter().filter(|card| card.has_rank())
     }
 
-    /// Counts occurrences of each rank
-    fn rank_counts(&self) -> HashMap<Rank, usize> {
-        let mut counts = HashMap::new();
-        for card in &self.cards {
-            *counts.entry(card.rank).or_insert(0) += 1;
+    /// Counts occurrences of each rank, ignoring Stone cards
+    fn rank_counts(&self) -> RankCounts {
+        let mut counts = RankCounts::default();
+        for card in self.ranked_cards() {
+            counts.0[card.rank.value() as usize] += 1;
         }
         counts
     }
 
     /// Checks if hand is a full house (three of a kind + pair)
-    fn is_full_house(&self, rank_counts: &HashMap<Rank, usize>) -> bool {
+    fn is_full_house(&self, rank_counts: &RankCounts) -> bool {
         let has_three = rank_counts.values().any(|&count| count == 3);
         let has_pair = rank_counts.values().any(|&count| count == 2);
         has_three && has_pair
     }
 }
 
+/// What a hand's type depends on
+struct Shape {
+    rank_counts: RankCounts,
+    flush: Option<Suit>,
+    /// Rank values of the straight, as from `Hand::straight_ranks`
+    straight: Option<u16>,
+}
+
+/// Number of cards of each rank, indexed by rank value
+#[derive(Debug, Default)]
+struct RankCounts([usize; 15]);
+
+impl RankCounts {
+    /// Returns the number of cards of a rank
+    fn get(&self, rank: Rank) -> usize {
+        self.0[rank.value() as usize]
+    }
+
+    /// Returns the counts of the ranks present
+    fn values(&self) -> impl Iterator<Item = &usize> {
+        self.0.iter().filter(|&&count| count > 0)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::card::Suit;
+    use crate::core::card::Enhancement;
 
     #[test]
     fn test_hand_type_values() {
@@ -266,4 +557,189 @@ mod tests {
         let hand = Hand::new(cards);
         assert_eq!(hand.evaluate(), HandType::Flush);
     }
+
+    #[test]
+    fn test_wild_card_completes_flush() {
+        let cards = vec![
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Five, Suit::Hearts),
+            Card::new(Rank::Seven, Suit::Spades).with_enhancement(Enhancement::Wild),
+            Card::new(Rank::Nine, Suit::Hearts),
+            Card::new(Rank::King, Suit::Hearts),
+        ];
+        assert_eq!(Hand::new(cards).evaluate(), HandType::Flush);
+    }
+
+    #[test]
+    fn test_wild_cards_form_flush_five_and_flush_house() {
+        let flush_five = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Clubs).with_enhancement(Enhancement::Wild),
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades).with_enhancement(Enhancement::Wild),
+        ];
+        assert_eq!(Hand::new(flush_five).evaluate(), HandType::FlushFive);
+
+        let flush_house = vec![
+            Card::new(Rank::King, Suit::Spades),
+            Card::new(Rank::King, Suit::Spades),
+            Card::new(Rank::King, Suit::Hearts).with_enhancement(Enhancement::Wild),
+            Card::new(Rank::Two, Suit::Spades),
+            Card::new(Rank::Two, Suit::Diamonds).with_enhancement(Enhancement::Wild),
+        ];
+        assert_eq!(Hand::new(flush_house).evaluate(), HandType::FlushHouse);
+    }
+
+    #[test]
+    fn test_stone_cards_do_not_pair_or_flush() {
+        let pair = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades).with_enhancement(Enhancement::Stone),
+        ];
+        assert_eq!(Hand::new(pair).evaluate(), HandType::HighCard);
+
+        let flush = vec![
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Five, Suit::Hearts),
+            Card::new(Rank::Seven, Suit::Hearts),
+            Card::new(Rank::Nine, Suit::Hearts),
+            Card::new(Rank::King, Suit::Hearts).with_enhancement(Enhancement::Stone),
+        ];
+        assert_eq!(Hand::new(flush).evaluate(), HandType::HighCard);
+
+        let straight = vec![
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Three, Suit::Clubs),
+            Card::new(Rank::Four, Suit::Hearts),
+            Card::new(Rank::Five, Suit::Spades),
+            Card::new(Rank::Six, Suit::Hearts).with_enhancement(Enhancement::Stone),
+        ];
+        assert_eq!(Hand::new(straight).evaluate(), HandType::HighCard);
+    }
+
+    #[test]
+    fn test_scoring_cards_subset() {
+        let pair = Hand::new(vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::King, Suit::Clubs),
+            Card::new(Rank::Ace, Suit::Spades),
+        ]);
+        let scoring = pair.scoring_cards();
+        assert_eq!(scoring.len(), 2);
+        assert!(scoring.iter().all(|card| card.rank == Rank::Ace));
+
+        let high_card = Hand::new(vec![
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Nine, Suit::Clubs),
+            Card::new(Rank::Four, Suit::Hearts).with_enhancement(Enhancement::Stone),
+        ]);
+        let scoring = high_card.scoring_cards();
+        assert_eq!(scoring.len(), 2);
+        assert_eq!(scoring[0].rank, Rank::Nine);
+        assert!(!scoring[1].has_rank());
+    }
+
+    #[test]
+    fn test_four_fingers_flush_and_straight() {
+        let rules = ScoringRules {
+            four_fingers: true,
+            ..Default::default()
+        };
+
+        let flush = Hand::new(vec![
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Five, Suit::Hearts),
+            Card::new(Rank::Seven, Suit::Hearts),
+            Card::new(Rank::Nine, Suit::Hearts),
+        ]);
+        assert_eq!(flush.evaluate(), HandType::HighCard);
+        assert_eq!(flush.evaluate_with(&rules), HandType::Flush);
+
+        let straight = Hand::new(vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Two, Suit::Clubs),
+            Card::new(Rank::Three, Suit::Hearts),
+            Card::new(Rank::Four, Suit::Spades),
+            Card::new(Rank::King, Suit::Spades),
+        ]);
+        assert_eq!(straight.evaluate_with(&rules), HandType::Straight);
+        let scoring = straight.scoring_cards_with(&rules);
+        assert_eq!(scoring.len(), 4); // The King is not part of the straight
+    }
+
+    #[test]
+    fn test_shortcut_gapped_straight() {
+        let rules = ScoringRules {
+            shortcut: true,
+            ..Default::default()
+        };
+
+        let hand = Hand::new(vec![
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Four, Suit::Clubs),
+            Card::new(Rank::Five, Suit::Hearts),
+            Card::new(Rank::Seven, Suit::Spades),
+            Card::new(Rank::Eight, Suit::Diamonds),
+        ]);
+        assert_eq!(hand.evaluate(), HandType::HighCard);
+        assert_eq!(hand.evaluate_with(&rules), HandType::Straight);
+
+        let too_wide = Hand::new(vec![
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Five, Suit::Clubs),
+            Card::new(Rank::Six, Suit::Hearts),
+            Card::new(Rank::Seven, Suit::Spades),
+            Card::new(Rank::Eight, Suit::Diamonds),
+        ]);
+        assert_eq!(too_wide.evaluate_with(&rules), HandType::HighCard);
+    }
+
+    #[test]
+    fn test_straight_and_flush_cards() {
+        let rules = ScoringRules {
+            four_fingers: true,
+            ..Default::default()
+        };
+
+        let ace_low = Hand::new(vec![
+            Card::new(Rank::King, Suit::Spades),
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Two, Suit::Clubs),
+            Card::new(Rank::Three, Suit::Hearts),
+            Card::new(Rank::Four, Suit::Spades),
+        ]);
+        let straight = ace_low.straight_cards_with(&rules).unwrap();
+        assert_eq!(straight.len(), 4);
+        assert!(straight.iter().all(|card| card.rank != Rank::King));
+        assert!(ace_low.flush_cards_with(&rules).is_none());
+
+        let duplicate = Hand::new(vec![
+            Card::new(Rank::Five, Suit::Hearts),
+            Card::new(Rank::Six, Suit::Hearts),
+            Card::new(Rank::Six, Suit::Clubs),
+            Card::new(Rank::Seven, Suit::Hearts),
+            Card::new(Rank::Eight, Suit::Hearts),
+        ]);
+        assert_eq!(duplicate.straight_cards_with(&rules).unwrap().len(), 5);
+        let flush = duplicate.flush_cards_with(&rules).unwrap();
+        assert_eq!(flush.len(), 4);
+        assert!(flush.iter().all(|card| card.suit == Suit::Hearts));
+    }
+
+    #[test]
+    fn test_contains_sub_hands() {
+        let rules = ScoringRules::default();
+        let full_house = Hand::new(vec![
+            Card::new(Rank::King, Suit::Hearts),
+            Card::new(Rank::King, Suit::Spades),
+            Card::new(Rank::King, Suit::Clubs),
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Two, Suit::Diamonds),
+        ]);
+        assert!(full_house.contains(HandType::Pair, &rules));
+        assert!(full_house.contains(HandType::TwoPair, &rules));
+        assert!(full_house.contains(HandType::ThreeOfAKind, &rules));
+        assert!(!full_house.contains(HandType::Flush, &rules));
+    }
 }
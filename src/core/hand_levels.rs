@@ -0,0 +1,142 @@
+//! Hand levels from Planet card upgrades
+//!
+//! Each poker hand starts at level 1. Every level above 1 adds the hand
+//! type's per-level chips and mult on top of its base values.
+
+use super::hand::HandType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Tracks the level of each hand type
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct HandLevels {
+    levels: HashMap<HandType, u32>,
+}
+
+impl HandLevels {
+    /// Creates hand levels with every hand at level 1
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the level of the given hand type (1 if never upgraded)
+    pub fn level(&self, hand_type: HandType) -> u32 {
+        self.levels.get(&hand_type).copied().unwrap_or(1).max(1)
+    }
+
+    /// Sets the level of the given hand type (minimum 1)
+    pub fn set_level(&mut self, hand_type: HandType, level: u32) {
+        self.levels.insert(hand_type, level.max(1));
+    }
+
+    /// Creates hand levels with the given hand type set to a level
+    pub fn with_level(mut self, hand_type: HandType, level: u32) -> Self {
+        self.set_level(hand_type, level);
+        self
+    }
+
+    /// Raises the level of the given hand type by `amount`
+    pub fn upgrade(&mut self, hand_type: HandType, amount: u32) {
+        let level = self.level(hand_type) + amount;
+        self.set_level(hand_type, level);
+    }
+
+    /// Returns the base chips for the hand type at its current level
+    pub fn chips(&self, hand_type: HandType) -> u32 {
+        hand_type.base_chips() + (self.level(hand_type) - 1) * hand_type.level_chips()
+    }
+
+    /// Returns the base mult for the hand type at its current level
+    pub fn mult(&self, hand_type: HandType) -> u32 {
+        hand_type.base_mult() + (self.level(hand_type) - 1) * hand_type.level_mult()
+    }
+
+    /// Parses `HAND=LEVEL` entries (e.g., `["Flush=5", "Pair=3"]`)
+    pub fn from_entries<I, S>(entries: I) -> anyhow::Result<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut levels = Self::new();
+        levels.set_entries(entries)?;
+        Ok(levels)
+    }
+
+    /// Sets levels from `HAND=LEVEL` entries, keeping the other hand
+    /// types' levels
+    ///
+    /// Blank entries (e.g., from a trailing comma) are skipped. Levels
+    /// start at 1, so a level of 0 is an error rather than being raised.
+    pub fn set_entries<I, S>(&mut self, entries: I) -> anyhow::Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for entry in entries {
+            let entry = entry.as_ref().trim();
+            if entry.is_empty() {
+                continue;
+            }
+            let (name, level) = entry
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("Invalid hand level (expected HAND=LEVEL): {}", entry))?;
+            let hand_type: HandType = name.trim().parse()?;
+            let level: u32 = level
+                .trim()
+                .parse()
+                .map_err(|_| anyhow::anyhow!("Invalid level for {}: {}", name.trim(), level.trim()))?;
+            if level == 0 {
+                anyhow::bail!("Invalid level for {}: hand levels start at 1", name.trim());
+            }
+            self.set_level(hand_type, level);
+        }
+        Ok(())
+    }
+
+    /// Returns true if every hand type is at level 1
+    pub fn is_empty(&self) -> bool {
+        self.levels.values().all(|&level| level <= 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_level_is_base() {
+        let levels = HandLevels::new();
+        assert_eq!(levels.level(HandType::Flush), 1);
+        assert_eq!(levels.chips(HandType::Flush), 35);
+        assert_eq!(levels.mult(HandType::Flush), 4);
+    }
+
+    #[test]
+    fn test_level_five_flush() {
+        let levels = HandLevels::new().with_level(HandType::Flush, 5);
+        assert_eq!(levels.chips(HandType::Flush), 35 + 4 * 15);
+        assert_eq!(levels.mult(HandType::Flush), 4 + 4 * 2);
+    }
+
+    #[test]
+    fn test_upgrade() {
+        let mut levels = HandLevels::new();
+        levels.upgrade(HandType::Pair, 2);
+        assert_eq!(levels.level(HandType::Pair), 3);
+        assert_eq!(levels.chips(HandType::Pair), 40);
+    }
+
+    #[test]
+    fn test_from_entries() {
+        let levels = HandLevels::from_entries(["Flush=5", "two_pair=3"]).unwrap();
+        assert_eq!(levels.level(HandType::Flush), 5);
+        assert_eq!(levels.level(HandType::TwoPair), 3);
+        assert!(HandLevels::from_entries(["Flush"]).is_err());
+        assert!(HandLevels::from_entries(["Nothing=2"]).is_err());
+        assert!(HandLevels::from_entries(["Flush=0"]).is_err());
+
+        let levels = HandLevels::from_entries([" Full House = 4 ", ""]).unwrap();
+        assert_eq!(levels.level(HandType::FullHouse), 4);
+    }
+}
@@ -4,6 +4,9 @@
 //! Each joker has unique effects that can modify chips, mult, or trigger
 //! special behaviors during scoring.
 
+use super::card::{Rank, Suit};
+use super::names::unknown_name;
+use anyhow::Context;
 use serde::{Deserialize, Serialize};
 
 /// Represents a joker and its current state
@@ -12,6 +15,16 @@ pub struct Joker {
     pub kind: JokerKind,
     pub edition: JokerEdition,
     pub rarity: JokerRarity,
+    /// Progress of jokers that grow during a run (e.g., enhancements eaten
+    /// by Vampire, cards added for Hologram)
+    #[serde(default)]
+    pub counter: u32,
+    /// Rank chosen this round (The Idol)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_rank: Option<Rank>,
+    /// Suit chosen this round (The Idol, Ancient Joker)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_suit: Option<Suit>,
 }
 
 /// The type of joker and its effect
@@ -31,8 +44,76 @@ pub enum JokerKind {
     CrazyJoker,         // +12 mult if played hand contains a Straight
     DrollJoker,         // +10 mult if played hand contains a Flush
 
-    // Multiplicative jokers
-    Baron,              // x1.5 mult for each King in hand
+    // Rank jokers
+    EvenSteven,         // Played cards with even rank give +4 mult when scored
+    OddTodd,            // Played cards with odd rank give +31 chips when scored
+    Fibonacci,          // Each played Ace, 2, 3, 5, or 8 gives +8 mult when scored
+    Scholar,            // Played Aces give +20 chips and +4 mult when scored
+    WalkieTalkie,       // Each played 10 or 4 gives +10 chips and +4 mult when scored
+
+    // Suit composition jokers
+    FlowerPot,          // x3 mult if scoring cards include a Diamond, Club, Heart, and Spade
+    SeeingDouble,       // x2 mult if scoring cards include a Club and a card of another suit
+    Blackboard,         // x3 mult if all cards held in hand are Spades or Clubs
+
+    // Deck composition jokers
+    SteelJoker,         // Gives x0.2 mult for each Steel card in the full deck
+    StoneJoker,         // Gives +25 chips for each Stone card in the full deck
+    GlassJoker,         // Gains x0.75 mult for every Glass card destroyed
+    DriversLicense,     // x3 mult if the full deck has at least 16 enhanced cards
+
+    // Chance-based jokers
+    EightBall,          // 1 in 4 chance for each played 8 to create a Tarot card when scored
+    SpaceJoker,         // 1 in 4 chance to upgrade level of played poker hand
+    Bloodstone,         // 1 in 2 chance for played Hearts to give x1.5 mult when scored
+
+    // Legendary jokers
+    Perkeo,             // Creates a Negative copy of 1 random consumable at end of shop
+    Triboulet,          // Played Kings and Queens each give x2 mult when scored
+    Yorick,             // Gains x1 mult every 23 cards discarded
+    Chicot,             // Disables effect of every Boss Blind
+    Canio,              // Gains x1 mult when a face card is destroyed
+
+    // Round-lifecycle jokers
+    CeremonialDagger,   // On Blind select, destroy Joker to the right for 2x its sell value in mult
+    Madness,            // On Small/Big Blind select, gain x0.5 mult and destroy a random Joker
+    IceCream,           // +100 chips, -5 chips for every hand played
+    Popcorn,            // +20 mult, -4 mult per round played
+
+    // Per-round target jokers
+    TheIdol,            // Each played card of the target rank and suit gives x2 mult when scored
+    AncientJoker,       // Each played card of the target suit gives x1.5 mult when scored
+
+    // Copying jokers
+    Blueprint,          // Copies ability of Joker to the right
+
+    // Face card jokers
+    ScaryFace,          // Played face cards give +30 chips when scored
+    SmileyFace,         // Played face cards give +5 mult when scored
+    Photograph,         // First played face card gives x2 mult when scored
+    SockAndBuskin,      // Retrigger all played face cards
+
+    // Rule-changing jokers
+    FourFingers,        // Flushes and Straights can be made with 4 cards
+    Shortcut,           // Straights can be made with gaps of 1 rank
+    Splash,             // Every played card counts in scoring
+    Pareidolia,         // All cards are considered face cards
+
+    // Held-in-hand jokers
+    Baron,              // Each King held in hand gives x1.5 mult
+    Mime,               // Retrigger all card held in hand abilities
+    ReservedParking,    // Each face card held in hand has 1 in 2 chance to give $1
+
+    // Economy jokers
+    GoldenJoker,        // Earn $4 at end of round
+    Rocket,             // Earn $1 at end of round
+    BusinessCard,       // Played face cards have a 1 in 2 chance to give $2 when scored
+
+    // Deck-mutating jokers
+    Dna,                // If first hand of round is a single card, add a copy of it to the deck
+    Vampire,            // Gains x0.1 mult per scoring enhanced card, removing the enhancement
+    MidasMask,          // All played face cards become Gold cards when scored
+    Hologram,           // Gains x0.25 mult every time a playing card is added to the deck
 
     // TODO: Add more jokers as they are implemented
     // This is a placeholder structure to be expanded
@@ -49,7 +130,7 @@ pub enum JokerEdition {
 }
 
 /// Joker rarity levels
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum JokerRarity {
     Common,
     Uncommon,
@@ -60,10 +141,15 @@ pub enum JokerRarity {
 impl Joker {
     /// Creates a new joker with the given kind
     pub fn new(kind: JokerKind) -> Self {
+        let rarity = kind.rarity();
+
         Self {
             kind,
             edition: JokerEdition::None,
-            rarity: JokerRarity::Common,
+            rarity,
+            counter: 0,
+            target_rank: None,
+            target_suit: None,
         }
     }
 
@@ -73,6 +159,19 @@ impl Joker {
         self
     }
 
+    /// Creates a joker with the given scaling progress
+    pub fn with_counter(mut self, counter: u32) -> Self {
+        self.counter = counter;
+        self
+    }
+
+    /// Creates a joker pinned to a target rank and/or suit
+    pub fn with_target(mut self, rank: Option<Rank>, suit: Option<Suit>) -> Self {
+        self.target_rank = rank;
+        self.target_suit = suit;
+        self
+    }
+
     /// Creates a joker with a specific rarity
     pub fn with_rarity(mut self, rarity: JokerRarity) -> Self {
         self.rarity = rarity;
@@ -81,13 +180,252 @@ impl Joker {
 }
 
 impl JokerKind {
-    /// Returns the base chip bonus for this joker (if any)
-    pub fn base_chips(&self) -> i32 {
+    /// Returns every joker kind
+    pub fn all() -> Vec<JokerKind> {
+        vec![
+            JokerKind::Joker,
+            JokerKind::GreedyJoker,
+            JokerKind::LustyJoker,
+            JokerKind::WrathfulJoker,
+            JokerKind::GluttonousJoker,
+            JokerKind::JollyJoker,
+            JokerKind::ZanyJoker,
+            JokerKind::MadJoker,
+            JokerKind::CrazyJoker,
+            JokerKind::DrollJoker,
+            JokerKind::EvenSteven,
+            JokerKind::OddTodd,
+            JokerKind::Fibonacci,
+            JokerKind::Scholar,
+            JokerKind::WalkieTalkie,
+            JokerKind::FlowerPot,
+            JokerKind::SeeingDouble,
+            JokerKind::Blackboard,
+            JokerKind::SteelJoker,
+            JokerKind::StoneJoker,
+            JokerKind::GlassJoker,
+            JokerKind::DriversLicense,
+            JokerKind::EightBall,
+            JokerKind::SpaceJoker,
+            JokerKind::Bloodstone,
+            JokerKind::Perkeo,
+            JokerKind::Triboulet,
+            JokerKind::Yorick,
+            JokerKind::Chicot,
+            JokerKind::Canio,
+            JokerKind::CeremonialDagger,
+            JokerKind::Madness,
+            JokerKind::IceCream,
+            JokerKind::Popcorn,
+            JokerKind::TheIdol,
+            JokerKind::AncientJoker,
+            JokerKind::Blueprint,
+            JokerKind::ScaryFace,
+            JokerKind::SmileyFace,
+            JokerKind::Photograph,
+            JokerKind::SockAndBuskin,
+            JokerKind::FourFingers,
+            JokerKind::Shortcut,
+            JokerKind::Splash,
+            JokerKind::Pareidolia,
+            JokerKind::Baron,
+            JokerKind::Mime,
+            JokerKind::ReservedParking,
+            JokerKind::GoldenJoker,
+            JokerKind::Rocket,
+            JokerKind::BusinessCard,
+            JokerKind::Dna,
+            JokerKind::Vampire,
+            JokerKind::MidasMask,
+            JokerKind::Hologram,
+        ]
+    }
+
+    /// Returns the joker's name as shown in the game
+    pub fn name(&self) -> &'static str {
         match self {
-            _ => 0, // Most jokers don't add flat chips
+            JokerKind::Joker => "Joker",
+            JokerKind::GreedyJoker => "Greedy Joker",
+            JokerKind::LustyJoker => "Lusty Joker",
+            JokerKind::WrathfulJoker => "Wrathful Joker",
+            JokerKind::GluttonousJoker => "Gluttonous Joker",
+            JokerKind::JollyJoker => "Jolly Joker",
+            JokerKind::ZanyJoker => "Zany Joker",
+            JokerKind::MadJoker => "Mad Joker",
+            JokerKind::CrazyJoker => "Crazy Joker",
+            JokerKind::DrollJoker => "Droll Joker",
+            JokerKind::EvenSteven => "Even Steven",
+            JokerKind::OddTodd => "Odd Todd",
+            JokerKind::Fibonacci => "Fibonacci",
+            JokerKind::Scholar => "Scholar",
+            JokerKind::WalkieTalkie => "Walkie Talkie",
+            JokerKind::FlowerPot => "Flower Pot",
+            JokerKind::SeeingDouble => "Seeing Double",
+            JokerKind::Blackboard => "Blackboard",
+            JokerKind::SteelJoker => "Steel Joker",
+            JokerKind::StoneJoker => "Stone Joker",
+            JokerKind::GlassJoker => "Glass Joker",
+            JokerKind::DriversLicense => "Driver's License",
+            JokerKind::EightBall => "8 Ball",
+            JokerKind::SpaceJoker => "Space Joker",
+            JokerKind::Bloodstone => "Bloodstone",
+            JokerKind::Perkeo => "Perkeo",
+            JokerKind::Triboulet => "Triboulet",
+            JokerKind::Yorick => "Yorick",
+            JokerKind::Chicot => "Chicot",
+            JokerKind::Canio => "Canio",
+            JokerKind::CeremonialDagger => "Ceremonial Dagger",
+            JokerKind::Madness => "Madness",
+            JokerKind::IceCream => "Ice Cream",
+            JokerKind::Popcorn => "Popcorn",
+            JokerKind::TheIdol => "The Idol",
+            JokerKind::AncientJoker => "Ancient Joker",
+            JokerKind::Blueprint => "Blueprint",
+            JokerKind::ScaryFace => "Scary Face",
+            JokerKind::SmileyFace => "Smiley Face",
+            JokerKind::Photograph => "Photograph",
+            JokerKind::SockAndBuskin => "Sock and Buskin",
+            JokerKind::FourFingers => "Four Fingers",
+            JokerKind::Shortcut => "Shortcut",
+            JokerKind::Splash => "Splash",
+            JokerKind::Pareidolia => "Pareidolia",
+            JokerKind::Baron => "Baron",
+            JokerKind::Mime => "Mime",
+            JokerKind::ReservedParking => "Reserved Parking",
+            JokerKind::GoldenJoker => "Golden Joker",
+            JokerKind::Rocket => "Rocket",
+            JokerKind::BusinessCard => "Business Card",
+            JokerKind::Dna => "DNA",
+            JokerKind::Vampire => "Vampire",
+            JokerKind::MidasMask => "Midas Mask",
+            JokerKind::Hologram => "Hologram",
         }
     }
 
+    /// Returns the joker's effect as described in the game
+    pub fn description(&self) -> &'static str {
+        match self {
+            JokerKind::Joker => "+4 Mult",
+            JokerKind::GreedyJoker => "Played cards with Diamond suit give +3 Mult when scored",
+            JokerKind::LustyJoker => "Played cards with Heart suit give +3 Mult when scored",
+            JokerKind::WrathfulJoker => "Played cards with Spade suit give +3 Mult when scored",
+            JokerKind::GluttonousJoker => "Played cards with Club suit give +3 Mult when scored",
+            JokerKind::JollyJoker => "+8 Mult if played hand contains a Pair",
+            JokerKind::ZanyJoker => "+12 Mult if played hand contains a Three of a Kind",
+            JokerKind::MadJoker => "+10 Mult if played hand contains a Two Pair",
+            JokerKind::CrazyJoker => "+12 Mult if played hand contains a Straight",
+            JokerKind::DrollJoker => "+10 Mult if played hand contains a Flush",
+            JokerKind::EvenSteven => "Played cards with even rank give +4 Mult when scored",
+            JokerKind::OddTodd => "Played cards with odd rank give +31 Chips when scored",
+            JokerKind::Fibonacci => "Each played Ace, 2, 3, 5, or 8 gives +8 Mult when scored",
+            JokerKind::Scholar => "Played Aces give +20 Chips and +4 Mult when scored",
+            JokerKind::WalkieTalkie => {
+                "Each played 10 or 4 gives +10 Chips and +4 Mult when scored"
+            }
+            JokerKind::FlowerPot => {
+                "X3 Mult if scoring cards include a Diamond, Club, Heart, and Spade"
+            }
+            JokerKind::SeeingDouble => {
+                "X2 Mult if scoring cards include a Club and a card of any other suit"
+            }
+            JokerKind::Blackboard => "X3 Mult if all cards held in hand are Spades or Clubs",
+            JokerKind::SteelJoker => "Gives X0.2 Mult for each Steel card in your full deck",
+            JokerKind::StoneJoker => "Gives +25 Chips for each Stone card in your full deck",
+            JokerKind::GlassJoker => "Gains X0.75 Mult for every Glass card that is destroyed",
+            JokerKind::DriversLicense => {
+                "X3 Mult if you have at least 16 enhanced cards in your deck"
+            }
+            JokerKind::EightBall => "1 in 4 chance for each played 8 to create a Tarot card",
+            JokerKind::SpaceJoker => "1 in 4 chance to upgrade level of played poker hand",
+            JokerKind::Bloodstone => {
+                "1 in 2 chance for played Hearts to give X1.5 Mult when scored"
+            }
+            JokerKind::Perkeo => {
+                "Creates a Negative copy of 1 random consumable in your possession at the end of \
+                 the shop"
+            }
+            JokerKind::Triboulet => "Played Kings and Queens each give X2 Mult when scored",
+            JokerKind::Yorick => "Gains X1 Mult every 23 cards discarded",
+            JokerKind::Chicot => "Disables effect of every Boss Blind",
+            JokerKind::Canio => "Gains X1 Mult when a face card is destroyed",
+            JokerKind::CeremonialDagger => {
+                "When Blind is selected, destroy Joker to the right and permanently add double \
+                 its sell value to this Mult"
+            }
+            JokerKind::Madness => {
+                "When Small Blind or Big Blind is selected, gain X0.5 Mult and destroy a random \
+                 Joker"
+            }
+            JokerKind::IceCream => "+100 Chips, -5 Chips for every hand played",
+            JokerKind::Popcorn => "+20 Mult, -4 Mult per round played",
+            JokerKind::TheIdol => {
+                "Each played card of the round's rank and suit gives X2 Mult when scored"
+            }
+            JokerKind::AncientJoker => {
+                "Each played card of the round's suit gives X1.5 Mult when scored"
+            }
+            JokerKind::Blueprint => "Copies ability of Joker to the right",
+            JokerKind::ScaryFace => "Played face cards give +30 Chips when scored",
+            JokerKind::SmileyFace => "Played face cards give +5 Mult when scored",
+            JokerKind::Photograph => "First played face card gives X2 Mult when scored",
+            JokerKind::SockAndBuskin => "Retrigger all played face cards",
+            JokerKind::FourFingers => "All Flushes and Straights can be made with 4 cards",
+            JokerKind::Shortcut => "Allows Straights to be made with gaps of 1 rank",
+            JokerKind::Splash => "Every played card counts in scoring",
+            JokerKind::Pareidolia => "All cards are considered face cards",
+            JokerKind::Baron => "Each King held in hand gives X1.5 Mult",
+            JokerKind::Mime => "Retrigger all card held in hand abilities",
+            JokerKind::ReservedParking => {
+                "Each face card held in hand has a 1 in 2 chance to give $1"
+            }
+            JokerKind::GoldenJoker => "Earn $4 at end of round",
+            JokerKind::Rocket => {
+                "Earn $1 at end of round. Payout increases by $2 when Boss Blind is defeated"
+            }
+            JokerKind::BusinessCard => {
+                "Played face cards have a 1 in 2 chance to give $2 when scored"
+            }
+            JokerKind::Dna => {
+                "If first hand of round has only 1 card, add a permanent copy to deck and draw it \
+                 to hand"
+            }
+            JokerKind::Vampire => {
+                "Gains X0.1 Mult per scoring enhanced card played, removes card enhancement"
+            }
+            JokerKind::MidasMask => "All played face cards become Gold cards when scored",
+            JokerKind::Hologram => {
+                "Gains X0.25 Mult every time a playing card is added to your deck"
+            }
+        }
+    }
+
+    /// Describes the part of the joker's effect jimbo does not model yet,
+    /// or returns `None` if it is modeled in full
+    pub fn modeling_gap(&self) -> Option<&'static str> {
+        match self {
+            JokerKind::EightBall => Some("Tarot cards are counted but never used"),
+            JokerKind::Perkeo => Some("Only Planet cards bought in the shop are copied"),
+            JokerKind::Rocket => Some("The payout stays at $1 after Boss Blinds are defeated"),
+            JokerKind::Dna => Some("The copy joins the deck but is not drawn to hand"),
+            JokerKind::Vampire => {
+                Some("In solve, the played hand's enhancements are neither removed nor counted")
+            }
+            JokerKind::MidasMask => Some("In solve, face cards keep their enhancements"),
+            JokerKind::Hologram => Some("Grows only from the cards DNA copies into the deck"),
+            _ => None,
+        }
+    }
+
+    /// Returns true if jimbo models the joker's whole effect
+    pub fn is_fully_modeled(&self) -> bool {
+        self.modeling_gap().is_none()
+    }
+
+    /// Returns the base chip bonus for this joker (if any)
+    pub fn base_chips(&self) -> i32 {
+        0 // No implemented joker adds flat chips unconditionally
+    }
+
     /// Returns the base mult bonus for this joker (if any)
     pub fn base_mult(&self) -> i32 {
         match self {
@@ -95,6 +433,227 @@ impl JokerKind {
             _ => 0, // Most jokers have conditional effects
         }
     }
+
+    /// Returns true for the five Legendary jokers
+    pub fn is_legendary(&self) -> bool {
+        matches!(
+            self,
+            JokerKind::Perkeo
+                | JokerKind::Triboulet
+                | JokerKind::Yorick
+                | JokerKind::Chicot
+                | JokerKind::Canio
+        )
+    }
+
+    /// Returns how rarely the joker appears in the shop
+    pub fn rarity(&self) -> JokerRarity {
+        match self {
+            JokerKind::Perkeo
+            | JokerKind::Triboulet
+            | JokerKind::Yorick
+            | JokerKind::Chicot
+            | JokerKind::Canio => JokerRarity::Legendary,
+            JokerKind::DriversLicense
+            | JokerKind::AncientJoker
+            | JokerKind::Blueprint
+            | JokerKind::Baron
+            | JokerKind::Dna => JokerRarity::Rare,
+            JokerKind::Fibonacci
+            | JokerKind::FlowerPot
+            | JokerKind::SeeingDouble
+            | JokerKind::Blackboard
+            | JokerKind::SteelJoker
+            | JokerKind::StoneJoker
+            | JokerKind::GlassJoker
+            | JokerKind::SpaceJoker
+            | JokerKind::Bloodstone
+            | JokerKind::CeremonialDagger
+            | JokerKind::Madness
+            | JokerKind::TheIdol
+            | JokerKind::SockAndBuskin
+            | JokerKind::FourFingers
+            | JokerKind::Shortcut
+            | JokerKind::Pareidolia
+            | JokerKind::Mime
+            | JokerKind::Rocket
+            | JokerKind::Vampire
+            | JokerKind::MidasMask
+            | JokerKind::Hologram => JokerRarity::Uncommon,
+            _ => JokerRarity::Common,
+        }
+    }
+
+    /// Returns true if this joker targets a card or suit chosen each round
+    pub fn needs_target(&self) -> bool {
+        matches!(self, JokerKind::TheIdol | JokerKind::AncientJoker)
+    }
+
+    /// Returns true if this joker disables Boss Blind effects
+    pub fn disables_boss_blind(&self) -> bool {
+        *self == JokerKind::Chicot
+    }
+
+    /// Returns true if this joker's effect depends on the full deck
+    pub fn needs_deck(&self) -> bool {
+        matches!(
+            self,
+            JokerKind::SteelJoker | JokerKind::StoneJoker | JokerKind::DriversLicense
+        )
+    }
+
+    /// Returns the shop price of this joker
+    pub fn cost(&self) -> u32 {
+        match self {
+            JokerKind::Joker => 2,
+            JokerKind::JollyJoker | JokerKind::Splash => 3,
+            JokerKind::ZanyJoker
+            | JokerKind::MadJoker
+            | JokerKind::CrazyJoker
+            | JokerKind::DrollJoker
+            | JokerKind::ScaryFace
+            | JokerKind::SmileyFace
+            | JokerKind::BusinessCard
+            | JokerKind::EvenSteven
+            | JokerKind::OddTodd
+            | JokerKind::Scholar
+            | JokerKind::WalkieTalkie => 4,
+            JokerKind::GreedyJoker
+            | JokerKind::LustyJoker
+            | JokerKind::WrathfulJoker
+            | JokerKind::GluttonousJoker
+            | JokerKind::Photograph
+            | JokerKind::Pareidolia
+            | JokerKind::Mime
+            | JokerKind::EightBall
+            | JokerKind::SpaceJoker
+            | JokerKind::IceCream
+            | JokerKind::Popcorn => 5,
+            JokerKind::SockAndBuskin
+            | JokerKind::ReservedParking
+            | JokerKind::GoldenJoker
+            | JokerKind::Rocket
+            | JokerKind::FlowerPot
+            | JokerKind::SeeingDouble
+            | JokerKind::Blackboard
+            | JokerKind::StoneJoker
+            | JokerKind::GlassJoker
+            | JokerKind::CeremonialDagger
+            | JokerKind::TheIdol => 6,
+            JokerKind::FourFingers
+            | JokerKind::Shortcut
+            | JokerKind::Vampire
+            | JokerKind::MidasMask
+            | JokerKind::Hologram
+            | JokerKind::SteelJoker
+            | JokerKind::DriversLicense
+            | JokerKind::Bloodstone
+            | JokerKind::Madness => 7,
+            JokerKind::Baron | JokerKind::Dna | JokerKind::Fibonacci | JokerKind::AncientJoker => 8,
+            JokerKind::Blueprint => 10,
+            JokerKind::Perkeo
+            | JokerKind::Triboulet
+            | JokerKind::Yorick
+            | JokerKind::Chicot
+            | JokerKind::Canio => 20,
+        }
+    }
+
+    /// Returns the money received for selling this joker (half its cost)
+    pub fn sell_value(&self) -> u32 {
+        (self.cost() / 2).max(1)
+    }
+
+    /// Returns the money this joker pays out at the end of the round (if any)
+    pub fn end_of_round_money(&self) -> u32 {
+        match self {
+            JokerKind::GoldenJoker => 4,
+            JokerKind::Rocket => 1,
+            _ => 0,
+        }
+    }
+}
+
+/// Lowercases a name and drops spaces, dashes, underscores, and apostrophes
+fn normalize_name(s: &str) -> String {
+    s.chars()
+        .filter(|c| !matches!(c, ' ' | '_' | '-' | '\''))
+        .collect::<String>()
+        .to_lowercase()
+}
+
+impl std::str::FromStr for JokerKind {
+    type Err = anyhow::Error;
+
+    /// Parses a joker name, ignoring case, spaces, dashes, underscores, and
+    /// apostrophes (e.g., "GreedyJoker", "greedy_joker", "Driver's License")
+    ///
+    /// An unknown name is reported with the closest jokers' names.
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let normalized = normalize_name(s);
+
+        JokerKind::all()
+            .into_iter()
+            .find(|kind| {
+                normalize_name(&format!("{:?}", kind)) == normalized
+                    || normalize_name(kind.name()) == normalized
+            })
+            .ok_or_else(|| {
+                let names: Vec<&str> = JokerKind::all().iter().map(JokerKind::name).collect();
+                unknown_name("joker", s, &names, normalize_name)
+            })
+    }
+}
+
+impl std::str::FromStr for JokerRarity {
+    type Err = anyhow::Error;
+
+    /// Parses a rarity name, ignoring case (e.g., "uncommon")
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "common" => Ok(JokerRarity::Common),
+            "uncommon" => Ok(JokerRarity::Uncommon),
+            "rare" => Ok(JokerRarity::Rare),
+            "legendary" => Ok(JokerRarity::Legendary),
+            _ => anyhow::bail!(
+                "Invalid rarity: {}. Use 'common', 'uncommon', 'rare', or 'legendary'",
+                s
+            ),
+        }
+    }
+}
+
+impl std::str::FromStr for Joker {
+    type Err = anyhow::Error;
+
+    /// Parses a joker name with an optional target after a colon: a card
+    /// for The Idol ("The Idol:KH") or a suit for Ancient Joker
+    /// ("Ancient Joker:Hearts")
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let (name, target) = match s.split_once(':') {
+            Some((name, target)) => (name, Some(target.trim())),
+            None => (s, None),
+        };
+        let joker = Joker::new(name.parse()?);
+
+        let Some(target) = target else {
+            return Ok(joker);
+        };
+        let name = joker.kind.name();
+        let invalid = || format!("Invalid target for {}: {}", name, target);
+        match joker.kind {
+            JokerKind::TheIdol => {
+                let suit_at = target.char_indices().last().map_or(0, |(i, _)| i);
+                let rank = target[..suit_at].parse().with_context(invalid)?;
+                let suit = target[suit_at..].parse().with_context(invalid)?;
+                Ok(joker.with_target(Some(rank), Some(suit)))
+            }
+            JokerKind::AncientJoker => {
+                Ok(joker.with_target(None, Some(target.parse().with_context(invalid)?)))
+            }
+            _ => anyhow::bail!("{} does not take a target: {}", joker.kind.name(), s),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -108,6 +667,57 @@ mod tests {
         assert_eq!(joker.edition, JokerEdition::None);
     }
 
+    #[test]
+    fn test_legendary_rarity() {
+        assert_eq!(Joker::new(JokerKind::Canio).rarity, JokerRarity::Legendary);
+        assert_eq!(Joker::new(JokerKind::Joker).rarity, JokerRarity::Common);
+        assert_eq!(Joker::new(JokerKind::Baron).rarity, JokerRarity::Rare);
+        assert_eq!(JokerKind::Blackboard.rarity(), JokerRarity::Uncommon);
+        assert!(JokerKind::all()
+            .iter()
+            .all(|kind| kind.is_legendary() == (kind.rarity() == JokerRarity::Legendary)));
+
+        assert_eq!(" Uncommon".parse::<JokerRarity>().unwrap(), JokerRarity::Uncommon);
+        assert!("mythic".parse::<JokerRarity>().is_err());
+    }
+
+    #[test]
+    fn test_parse_joker() {
+        assert_eq!("greedy_joker".parse::<JokerKind>().unwrap(), JokerKind::GreedyJoker);
+        assert_eq!("Driver's License".parse::<JokerKind>().unwrap(), JokerKind::DriversLicense);
+        assert_eq!("8 Ball".parse::<JokerKind>().unwrap(), JokerKind::EightBall);
+        assert!("Jimbo the Great".parse::<JokerKind>().is_err());
+        let typo = "Blueprnt".parse::<JokerKind>().unwrap_err().to_string();
+        assert_eq!(typo, "Unknown joker: Blueprnt (did you mean Blueprint?)");
+
+        let idol: Joker = "The Idol:KH".parse().unwrap();
+        assert_eq!(idol.target_rank, Some(Rank::King));
+        assert_eq!(idol.target_suit, Some(Suit::Hearts));
+        let ancient: Joker = "ancient_joker:spades".parse().unwrap();
+        assert_eq!(ancient.target_suit, Some(Suit::Spades));
+        assert!("Joker:KH".parse::<Joker>().is_err());
+        let target = "The Idol:XH".parse::<Joker>().unwrap_err().to_string();
+        assert_eq!(target, "Invalid target for The Idol: XH");
+    }
+
+    #[test]
+    fn test_all_jokers_have_unique_names() {
+        let all = JokerKind::all();
+        for kind in &all {
+            assert_eq!(&kind.name().parse::<JokerKind>().unwrap(), kind);
+        }
+    }
+
+    #[test]
+    fn test_descriptions_and_modeling() {
+        assert!(JokerKind::all().iter().all(|kind| !kind.description().is_empty()));
+        assert!(JokerKind::Baron.is_fully_modeled());
+        assert!(JokerKind::Chicot.is_fully_modeled());
+        assert!(JokerKind::Perkeo.modeling_gap().is_some());
+        assert!(JokerKind::IceCream.is_fully_modeled());
+        assert!(JokerKind::Hologram.modeling_gap().is_some());
+    }
+
     #[test]
     fn test_base_joker_mult() {
         assert_eq!(JokerKind::Joker.base_mult(), 4);
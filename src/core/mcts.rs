@@ -0,0 +1,497 @@
+//! Monte Carlo tree search over run decisions (experimental)
+//!
+//! Between blinds a run decides what to buy in the shop, which held Planets
+//! to use, and whether to skip the next blind. This module searches those
+//! decisions with UCT: each node of the tree is the run after a sequence of
+//! decisions, and each new node is valued by playing out the rest of the
+//! run with the simulator, whose shop model buys in the shops that follow.
+//! A rollout's reward is the fraction of the blinds left at the root
+//! decision it beats or skips, so skipping and playing share one scale.
+
+use super::blind::BlindType;
+use super::consumable::{Consumable, Planet};
+use super::joker::JokerEdition;
+use super::run::GameRunState;
+use super::shop::{ShopModel, ShopOffer};
+use super::simulator::{Simulator, FINAL_ANTE};
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+/// Default number of search iterations, one rollout each
+pub const DEFAULT_ITERATIONS: usize = 400;
+
+/// Default UCT exploration constant
+pub const DEFAULT_EXPLORATION: f64 = std::f64::consts::SQRT_2;
+
+/// A decision taken between blinds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RunAction {
+    /// Buy an offer from the shop, taking `choice` from a pack
+    Buy {
+        offer: ShopOffer,
+        choice: Option<ShopOffer>,
+    },
+    /// Use a held Planet card
+    UsePlanet(Planet),
+    /// Skip the next blind, forgoing its reward and the shop after it
+    /// (tags are not modeled)
+    Skip,
+    /// Leave the shop and play the next blind, ending the decisions
+    Play,
+}
+
+impl std::fmt::Display for RunAction {
+    /// Describes the action, e.g. "Buy Baron ($8)" or "Skip the blind"
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunAction::Buy {
+                offer,
+                choice: Some(choice),
+            } => write!(f, "Buy {}, taking {}", offer, choice.name()),
+            RunAction::Buy { offer, .. } => write!(f, "Buy {}", offer),
+            RunAction::UsePlanet(planet) => {
+                write!(f, "Use {}", Consumable::Planet(*planet).name())
+            }
+            RunAction::Skip => write!(f, "Skip the blind"),
+            RunAction::Play => write!(f, "Play the blind"),
+        }
+    }
+}
+
+/// The point in a run a decision is taken at
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunDecision {
+    /// The run, whose ante is the one being played
+    pub run: GameRunState,
+    /// The blind played next
+    pub next_blind: BlindType,
+    /// What the shop has for sale (empty outside the shop)
+    pub offers: Vec<ShopOffer>,
+}
+
+impl RunDecision {
+    /// Creates a decision before `next_blind` of the run's ante, outside
+    /// the shop
+    pub fn new(run: GameRunState, next_blind: BlindType) -> Self {
+        Self {
+            run,
+            next_blind,
+            offers: Vec::new(),
+        }
+    }
+
+    /// Sets what the shop has for sale
+    pub fn with_offers(mut self, offers: Vec<ShopOffer>) -> Self {
+        self.offers = offers;
+        self
+    }
+
+    /// Returns every action that can be taken, ending with Play
+    ///
+    /// Offers must be affordable, and jokers need a free slot (unless
+    /// Negative). Jokers whose target has to be chosen are left out, as the
+    /// shop model leaves them.
+    pub fn actions(&self) -> Vec<RunAction> {
+        let run = &self.run;
+        let fits = |offer: &ShopOffer| match offer {
+            ShopOffer::Joker(joker) => {
+                !joker.kind.needs_target()
+                    && (ShopModel::has_free_slot(run)
+                        || joker.edition == JokerEdition::Negative)
+            }
+            _ => true,
+        };
+
+        let mut actions = Vec::new();
+        for offer in self.offers.iter().filter(|offer| offer.cost() <= run.money) {
+            match offer {
+                ShopOffer::Pack { choices, .. } => {
+                    actions.extend(choices.iter().filter(|choice| fits(choice)).map(|choice| {
+                        RunAction::Buy {
+                            offer: offer.clone(),
+                            choice: Some(choice.clone()),
+                        }
+                    }));
+                }
+                _ if fits(offer) => actions.push(RunAction::Buy {
+                    offer: offer.clone(),
+                    choice: None,
+                }),
+                _ => {}
+            }
+        }
+
+        let mut planets: Vec<Planet> = Vec::new();
+        for name in &run.consumables {
+            if let Ok(Consumable::Planet(planet)) = name.parse()
+                && !planets.contains(&planet)
+            {
+                planets.push(planet);
+            }
+        }
+        actions.extend(planets.into_iter().map(RunAction::UsePlanet));
+
+        if self.next_blind != BlindType::Boss {
+            actions.push(RunAction::Skip);
+        }
+        actions.push(RunAction::Play);
+        actions
+    }
+
+    /// Returns the decision after taking an action (Play leaves it as is)
+    ///
+    /// Skipping leaves the shop, so the offers are cleared.
+    pub fn apply(&self, action: &RunAction) -> RunDecision {
+        let mut next = self.clone();
+        match action {
+            RunAction::Buy { offer, choice } => {
+                if let Some(index) = next.offers.iter().position(|o| o == offer) {
+                    next.offers.remove(index);
+                }
+                next.run.money = next.run.money.saturating_sub(offer.cost());
+                choice.as_ref().unwrap_or(offer).apply(&mut next.run);
+            }
+            RunAction::UsePlanet(planet) => {
+                let used = next.run.consumables.iter().position(|name| {
+                    matches!(name.parse(), Ok(Consumable::Planet(held)) if held == *planet)
+                });
+                if let Some(index) = used {
+                    next.run.consumables.remove(index);
+                    planet.apply(&mut next.run.hand_levels);
+                }
+            }
+            RunAction::Skip => {
+                next.offers.clear();
+                next.next_blind = match self.next_blind {
+                    BlindType::Small => BlindType::Big,
+                    _ => BlindType::Boss,
+                };
+            }
+            RunAction::Play => {}
+        }
+        next
+    }
+}
+
+/// How the search rated one action from the root decision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionStats {
+    pub action: RunAction,
+    /// Rollouts made through the action
+    pub visits: usize,
+    /// Mean reward of those rollouts: the fraction of the blinds left at
+    /// the root decision beaten or skipped
+    pub q: f64,
+}
+
+/// The search's recommended decision
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RunRecommendation {
+    /// The most visited action
+    pub best: ActionStats,
+    /// Every action from the root, most visited first
+    pub actions: Vec<ActionStats>,
+    /// Rollouts made
+    pub iterations: usize,
+}
+
+/// Searches run decisions with Monte Carlo tree search
+///
+/// Experimental: rollouts follow the shop model and the simulator's
+/// default strategy, so decisions are rated against that way of playing.
+pub struct RunSearch {
+    shop: ShopModel,
+    iterations: usize,
+    seed: u64,
+    exploration: f64,
+}
+
+/// A decision reached in the search tree
+struct Node {
+    decision: RunDecision,
+    /// Hand size added by vouchers bought on the way to this node
+    extra_hand_size: usize,
+    untried: Vec<RunAction>,
+    children: Vec<(RunAction, usize)>,
+    visits: usize,
+    total_reward: f64,
+}
+
+impl Node {
+    /// Creates a node; one reached by Play is `open: false`, as Play ends
+    /// the decisions
+    fn new(decision: RunDecision, extra_hand_size: usize, open: bool) -> Self {
+        let mut untried = if open { decision.actions() } else { Vec::new() };
+        // Actions are tried from the end, so try Play first
+        untried.reverse();
+        Self {
+            decision,
+            extra_hand_size,
+            untried,
+            children: Vec::new(),
+            visits: 0,
+            total_reward: 0.0,
+        }
+    }
+
+    fn q(&self) -> f64 {
+        if self.visits == 0 { 0.0 } else { self.total_reward / self.visits as f64 }
+    }
+}
+
+impl RunSearch {
+    /// Creates a search with the game's shop and the default iterations
+    pub fn new() -> Self {
+        Self {
+            shop: ShopModel::new(),
+            iterations: DEFAULT_ITERATIONS,
+            seed: 0,
+            exploration: DEFAULT_EXPLORATION,
+        }
+    }
+
+    /// Sets the shop model rollouts buy from
+    pub fn with_shop(mut self, shop: ShopModel) -> Self {
+        self.shop = shop;
+        self
+    }
+
+    /// Sets the number of iterations and the seed rollouts are dealt from
+    pub fn with_iterations(mut self, iterations: usize, seed: u64) -> Self {
+        self.iterations = iterations.max(1);
+        self.seed = seed;
+        self
+    }
+
+    /// Sets the UCT exploration constant (higher tries rarer actions more)
+    pub fn with_exploration(mut self, exploration: f64) -> Self {
+        self.exploration = exploration;
+        self
+    }
+
+    /// Searches the decisions from `root`, recommending the first action
+    pub fn search(&self, root: &RunDecision) -> RunRecommendation {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        let mut nodes = vec![Node::new(root.clone(), 0, true)];
+        let blinds = Self::blinds_left(root);
+
+        for _ in 0..self.iterations {
+            // Select down the tree, then expand one untried action
+            let mut path = vec![0];
+            let mut current = 0;
+            loop {
+                if let Some(action) = nodes[current].untried.pop() {
+                    let child = self.expand(&nodes[current], &action);
+                    nodes.push(child);
+                    let index = nodes.len() - 1;
+                    nodes[current].children.push((action, index));
+                    path.push(index);
+                    break;
+                }
+                match self.select(&nodes, current) {
+                    Some(child) => {
+                        current = child;
+                        path.push(child);
+                    }
+                    None => break,
+                }
+            }
+
+            let leaf = &nodes[*path.last().expect("the path starts at the root")];
+            let reward = self.rollout(leaf, root.next_blind, blinds, &mut rng);
+            for index in path {
+                nodes[index].visits += 1;
+                nodes[index].total_reward += reward;
+            }
+        }
+
+        let mut actions: Vec<ActionStats> = nodes[0]
+            .children
+            .iter()
+            .map(|(action, index)| ActionStats {
+                action: action.clone(),
+                visits: nodes[*index].visits,
+                q: nodes[*index].q(),
+            })
+            .collect();
+        actions.sort_by(|a, b| b.visits.cmp(&a.visits).then(b.q.total_cmp(&a.q)));
+        RunRecommendation {
+            best: actions.first().cloned().expect("Play is always an action"),
+            actions,
+            iterations: self.iterations,
+        }
+    }
+
+    /// Creates the node reached by taking an action
+    fn expand(&self, parent: &Node, action: &RunAction) -> Node {
+        let bonus = match action {
+            RunAction::Buy { offer, choice } => choice.as_ref().unwrap_or(offer).hand_size_bonus(),
+            _ => 0,
+        };
+        let open = *action != RunAction::Play;
+        Node::new(parent.decision.apply(action), parent.extra_hand_size + bonus, open)
+    }
+
+    /// Picks the child with the highest upper confidence bound, or `None`
+    /// if the node has no children
+    fn select(&self, nodes: &[Node], parent: usize) -> Option<usize> {
+        let log_visits = (nodes[parent].visits.max(1) as f64).ln();
+        nodes[parent]
+            .children
+            .iter()
+            .map(|(_, index)| {
+                let child = &nodes[*index];
+                let bonus = self.exploration * (log_visits / child.visits.max(1) as f64).sqrt();
+                (*index, child.q() + bonus)
+            })
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(index, _)| index)
+    }
+
+    /// Plays out the rest of the run from a node, playing the next blind
+    /// straight away, and returns the fraction of the root's `blinds` left
+    /// (from `root_blind`) passed
+    fn rollout(
+        &self,
+        node: &Node,
+        root_blind: BlindType,
+        blinds: usize,
+        rng: &mut ChaCha8Rng,
+    ) -> f64 {
+        if blinds == 0 {
+            return 1.0;
+        }
+        let decision = &node.decision;
+        let simulator = Simulator::new(decision.run.solver()).with_shop(self.shop.clone());
+        let rng = &mut ChaCha8Rng::seed_from_u64(rng.next_u64());
+        let play_out =
+            simulator.play_out(&decision.run, decision.next_blind, node.extra_hand_size, rng);
+        // Blinds passed count from the start of the ante, and skipped
+        // blinds count as passed
+        let passed = play_out.blinds_passed - Simulator::blinds_before(root_blind);
+        passed as f64 / blinds as f64
+    }
+
+    /// Returns the number of blinds left in the run from a decision
+    fn blinds_left(decision: &RunDecision) -> usize {
+        let antes_left = (FINAL_ANTE + 1).saturating_sub(decision.run.ante) as usize;
+        (antes_left * 3).saturating_sub(Simulator::blinds_before(decision.next_blind))
+    }
+}
+
+impl Default for RunSearch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::joker::{Joker, JokerKind};
+    use crate::core::shop::Voucher;
+
+    #[test]
+    fn test_actions_and_apply() {
+        let run = GameRunState {
+            money: 9,
+            consumables: vec!["Jupiter".to_string(), "The Fool".to_string()],
+            ..GameRunState::default()
+        };
+        let offers = vec![
+            ShopOffer::Joker(Joker::new(JokerKind::Joker)),
+            ShopOffer::Voucher(Voucher::Grabber),
+            ShopOffer::Joker(Joker::new(JokerKind::Baron)),
+            ShopOffer::Pack {
+                name: "Celestial Pack".to_string(),
+                cost: 4,
+                choices: vec![ShopOffer::Planet(Planet::Saturn), ShopOffer::Planet(Planet::Mars)],
+            },
+        ];
+        let decision = RunDecision::new(run, BlindType::Boss).with_offers(offers.clone());
+
+        let actions = decision.actions();
+        // Grabber is $10, the pack offers two choices, and a Boss can't be skipped
+        assert_eq!(actions.len(), 6);
+        assert!(actions.contains(&RunAction::UsePlanet(Planet::Jupiter)));
+        assert!(!actions.contains(&RunAction::Skip));
+        assert_eq!(actions.last(), Some(&RunAction::Play));
+
+        let buy = RunAction::Buy {
+            offer: offers[2].clone(),
+            choice: None,
+        };
+        assert_eq!(buy.to_string(), "Buy Baron ($8)");
+        let bought = decision.apply(&buy);
+        assert_eq!((bought.run.money, bought.run.jokers.len()), (1, 1));
+        assert_eq!(bought.offers.len(), 3);
+        assert_eq!(bought.actions(), vec![RunAction::UsePlanet(Planet::Jupiter), RunAction::Play]);
+
+        let used = decision.apply(&RunAction::UsePlanet(Planet::Jupiter));
+        assert_eq!(used.run.consumables, vec!["The Fool".to_string()]);
+        assert_eq!(used.run.hand_levels.level(crate::core::hand::HandType::Flush), 2);
+
+        let skipped = RunDecision::new(GameRunState::default(), BlindType::Small)
+            .with_offers(offers)
+            .apply(&RunAction::Skip);
+        assert_eq!((skipped.next_blind, skipped.offers.len()), (BlindType::Big, 0));
+    }
+
+    #[test]
+    fn test_search_prefers_useful_purchase() {
+        let run = GameRunState {
+            money: 6,
+            ..GameRunState::default()
+        };
+        let decision = RunDecision::new(run, BlindType::Big).with_offers(vec![
+            ShopOffer::Joker(Joker::new(JokerKind::Joker)),
+            ShopOffer::Joker(Joker::new(JokerKind::GoldenJoker)),
+        ]);
+        let recommendation = RunSearch::new()
+            .with_shop(ShopModel::new().with_reserve(1_000))
+            .with_iterations(60, 3)
+            .search(&decision);
+
+        assert_eq!(recommendation.iterations, 60);
+        assert_eq!(recommendation.actions.len(), 4);
+        assert_eq!(recommendation.actions.iter().map(|a| a.visits).sum::<usize>(), 60);
+        assert_eq!(
+            recommendation.best.action,
+            RunAction::Buy {
+                offer: ShopOffer::Joker(Joker::new(JokerKind::Joker)),
+                choice: None,
+            }
+        );
+        assert!(recommendation.actions.iter().all(|a| (0.0..=1.0).contains(&a.q)));
+    }
+
+    #[test]
+    fn test_search_prefers_skip_when_blinds_are_lost() {
+        use crate::core::card::{Card, Rank, Suit};
+
+        // Eight unconnected cards of alternating suits make nothing better
+        // than a High Card, which beats no blind, so every skip is a blind
+        // passed that playing would not pass
+        let deck = [
+            (Rank::Two, Suit::Spades),
+            (Rank::Three, Suit::Hearts),
+            (Rank::Five, Suit::Diamonds),
+            (Rank::Seven, Suit::Clubs),
+            (Rank::Nine, Suit::Spades),
+            (Rank::Jack, Suit::Hearts),
+            (Rank::King, Suit::Diamonds),
+            (Rank::Ace, Suit::Clubs),
+        ]
+        .map(|(rank, suit)| Card::new(rank, suit));
+        let run = GameRunState::default().with_deck(deck.to_vec());
+        let recommendation = RunSearch::new()
+            .with_iterations(20, 5)
+            .search(&RunDecision::new(run, BlindType::Small));
+
+        assert_eq!(recommendation.best.action, RunAction::Skip);
+        let play = recommendation.actions.iter().find(|a| a.action == RunAction::Play).unwrap();
+        assert_eq!(play.q, 0.0);
+        assert!(recommendation.best.q >= 1.0 / 24.0);
+    }
+}
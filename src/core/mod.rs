@@ -3,17 +3,78 @@
 //! This module contains the fundamental data structures and algorithms
 //! for representing and evaluating Balatro game states.
 
+pub mod ante_planner;
+pub mod blind;
 pub mod card;
+pub mod consumable;
+pub mod deck;
+pub mod deck_stats;
+pub mod draw;
+pub mod explain;
 pub mod hand;
+pub mod hand_levels;
 pub mod joker;
+pub mod mcts;
+pub mod names;
+pub mod optimizer;
+pub mod planner;
+pub mod pools;
+pub mod rules;
+pub mod run;
+pub mod score;
 pub mod scoring;
+pub mod search;
+pub mod seed;
+pub mod sensitivity;
+pub mod shop;
 pub mod simulator;
 pub mod solver;
+pub mod stake;
+pub mod strategy;
+pub mod sweep;
+pub mod tag;
 
 // Re-export commonly used types
+pub use ante_planner::{
+    AntePlan, AntePlanner, BlindOutlook, BossAdvice, RunLine, SkipOutlook, SkipVerdict,
+};
+pub use blind::{blind_requirement, boss_requirement, BlindConfig, BlindType, BossBlind};
 pub use card::{Card, Enhancement, Edition, Rank, Suit};
+pub use consumable::{Consumable, ConsumableEvent, ConsumableInventory, Planet, Tarot};
+pub use deck::{DeckModifiers, DeckState, DeckType};
+pub use deck_stats::DeckStats;
+pub use draw::DrawPile;
+pub use explain::{score_trace, Contribution, PlayComparison, SourceDiff, TraceStep};
 pub use hand::{Hand, HandType};
+pub use hand_levels::HandLevels;
 pub use joker::Joker;
-pub use scoring::{ScoreCalculator, ScoreResult};
-pub use simulator::{create_standard_deck, SimulationConfig, SimulationResult, Simulator};
-pub use solver::Solver;
+pub use mcts::{ActionStats, RunAction, RunDecision, RunRecommendation, RunSearch};
+pub use optimizer::{BuildOptimizer, OptimizeObjective, OptimizedBuild};
+pub use planner::{RoundAction, RoundPlan, RoundPlanner};
+pub use rules::ScoringRules;
+pub use run::GameRunState;
+pub use score::Score;
+pub use scoring::{
+    MultStep, ProbabilityMode, ScoreCalculator, ScoreEffect, ScoreEvent, ScoreResult, ScoreStage,
+};
+pub use search::{MaxScoreResult, MaxScoreSearch};
+pub use seed::{Archetype, SeedPrediction, SeedRating};
+pub use sensitivity::{DeckPerturbation, DeckSensitivity};
+pub use shop::{PackContents, PackWeight, ShopModel, ShopOffer, ShopVisit, ShopWeights, Voucher};
+pub use simulator::{
+    create_standard_deck, AnteStats, BuildComparison, BuildConfig, BuildResult, Convergence,
+    HistogramBin, PairedDifference, PlayedHand, ProgressCallback, RawFormat, RunFailure,
+    RunOutcome, RunSimulationResult, ShopStats, SimProgress, SimulationConfig, SimulationResult,
+    Simulator, TargetProbability, VarianceReduction,
+};
+pub use solver::{
+    ConsumablePlan, ConsumableUse, DiscardResult, JokerOrderResult, Objective, ShopEvaluation,
+    SolveCache, SolveConstraints, Solver,
+};
+pub use stake::Stake;
+pub use strategy::{
+    BeamSearch, Exhaustive, FlushChaser, Greedy, HighCardPreserver, PairSpammer, Play, PlayContext,
+    PlayStrategy, Random,
+};
+pub use sweep::{ParameterSweep, SweepAxis, SweepParameter, SweepResult};
+pub use tag::Tag;
@@ -0,0 +1,117 @@
+//! Suggestions for names that don't parse
+//!
+//! Jokers, boss blinds, vouchers, decks, and the other named inputs report
+//! an unknown name with the closest real names, by edit distance, so a typo
+//! like "Blueprnt" comes back as "did you mean Blueprint?".
+
+/// The most names suggested for an unknown name
+const MAX_SUGGESTIONS: usize = 3;
+
+/// Returns the error for an unknown name, e.g. "Unknown joker: Blueprnt (did
+/// you mean Blueprint?)"
+///
+/// `what` names the kind of thing ("joker"), and `names` are every name it
+/// could have been. Names are compared after `normalize`, the same
+/// normalization the caller parses with.
+pub fn unknown_name(
+    what: &str,
+    input: &str,
+    names: &[&str],
+    normalize: impl Fn(&str) -> String,
+) -> anyhow::Error {
+    let suggestions = closest(input, names, normalize);
+    match suggestions.as_slice() {
+        [] => anyhow::anyhow!("Unknown {}: {}", what, input),
+        [name] => anyhow::anyhow!("Unknown {}: {} (did you mean {}?)", what, input, name),
+        [first, second] => {
+            anyhow::anyhow!("Unknown {}: {} (did you mean {} or {}?)", what, input, first, second)
+        }
+        [rest @ .., last] => anyhow::anyhow!(
+            "Unknown {}: {} (did you mean {}, or {}?)",
+            what,
+            input,
+            rest.join(", "),
+            last
+        ),
+    }
+}
+
+/// Returns up to three names closest to the input, closest first
+///
+/// A name is close if it is a few edits away (two, or a third of the
+/// input's length for longer inputs), or if it contains the input (e.g.,
+/// "greedy" for Greedy Joker).
+pub fn closest<'a>(
+    input: &str,
+    names: &[&'a str],
+    normalize: impl Fn(&str) -> String,
+) -> Vec<&'a str> {
+    let input = normalize(input);
+    if input.is_empty() {
+        return Vec::new();
+    }
+    let max_distance = (input.chars().count() / 3).max(2);
+
+    let mut matches: Vec<(usize, &str)> = names
+        .iter()
+        .filter_map(|&name| {
+            let normalized = normalize(name);
+            let distance = edit_distance(&input, &normalized);
+            let contains = input.chars().count() >= 3 && normalized.contains(&input);
+            (distance <= max_distance || contains).then_some((distance, name))
+        })
+        .collect();
+    // Stable, so names as close as each other keep their order
+    matches.sort_by_key(|(distance, _)| *distance);
+    matches.into_iter().take(MAX_SUGGESTIONS).map(|(_, name)| name).collect()
+}
+
+/// Returns the number of single-character insertions, deletions, and
+/// substitutions turning one string into the other
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lowercase(name: &str) -> String {
+        name.replace(' ', "").to_lowercase()
+    }
+
+    #[test]
+    fn test_closest_names() {
+        let names = ["Blueprint", "Brainstorm", "Greedy Joker", "Lusty Joker", "Joker"];
+        assert_eq!(closest("blueprnt", &names, lowercase), ["Blueprint"]);
+        assert_eq!(closest("greedy", &names, lowercase), ["Greedy Joker"]);
+        assert_eq!(closest("joker", &names, lowercase), ["Joker", "Lusty Joker", "Greedy Joker"]);
+        assert!(closest("xyz", &names, lowercase).is_empty());
+        assert!(closest("", &names, lowercase).is_empty());
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_unknown_name() {
+        let names = ["Red", "Blue", "Black", "Plasma"];
+        let error = |input| unknown_name("deck", input, &names, lowercase).to_string();
+        assert_eq!(error("plsma"), "Unknown deck: plsma (did you mean Plasma?)");
+        assert_eq!(error("Bled"), "Unknown deck: Bled (did you mean Red or Blue?)");
+        assert_eq!(error("Sapphire"), "Unknown deck: Sapphire");
+
+        let names = ["Joker", "Greedy Joker", "Lusty Joker"];
+        let error = unknown_name("joker", "joke", &names, lowercase).to_string();
+        let expected = "Unknown joker: joke (did you mean Joker, Lusty Joker, or Greedy Joker?)";
+        assert_eq!(error, expected);
+    }
+}
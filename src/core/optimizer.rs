@@ -0,0 +1,302 @@
+//! Joker build optimizer
+//!
+//! Searches the joker space for the combination that scores best with a
+//! run's deck and hand levels. Every candidate build is simulated on the
+//! same hands, and the search hill-climbs from a greedy build and from a
+//! few random ones, swapping one joker at a time while that improves the
+//! objective.
+
+use super::blind::BlindType;
+use super::joker::{Joker, JokerKind, JokerRarity};
+use super::run::GameRunState;
+use super::score::Score;
+use super::simulator::Simulator;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Default simulated rounds per candidate build
+pub const DEFAULT_OPTIMIZER_RUNS: usize = 100;
+
+/// Default number of random builds hill-climbed besides the greedy one
+pub const DEFAULT_RESTARTS: usize = 2;
+
+/// Most improving swaps made from one starting build
+const MAX_CLIMB_STEPS: usize = 20;
+
+/// What a build optimizer maximizes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OptimizeObjective {
+    /// Mean score of a round
+    #[default]
+    MeanScore,
+    /// Chance a round reaches the ante's Boss blind requirement
+    ClearRate,
+}
+
+impl std::str::FromStr for OptimizeObjective {
+    type Err = anyhow::Error;
+
+    /// Parses "mean" or "clear-rate" (also "clear")
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let normalized = s.trim().to_lowercase().replace(['_', ' '], "-");
+        match normalized.as_str() {
+            "mean" | "mean-score" => Ok(OptimizeObjective::MeanScore),
+            "clear" | "clear-rate" => Ok(OptimizeObjective::ClearRate),
+            _ => anyhow::bail!("Invalid objective: {}. Use 'mean' or 'clear-rate'", s),
+        }
+    }
+}
+
+/// A joker combination and how it did
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizedBuild {
+    pub jokers: Vec<Joker>,
+    /// Mean score of a simulated round
+    pub mean_score: f64,
+    /// Fraction of simulated rounds reaching the Boss blind requirement
+    pub clear_rate: f64,
+}
+
+impl OptimizedBuild {
+    /// Returns the value of the objective for this build
+    pub fn value(&self, objective: OptimizeObjective) -> f64 {
+        match objective {
+            OptimizeObjective::MeanScore => self.mean_score,
+            OptimizeObjective::ClearRate => self.clear_rate,
+        }
+    }
+}
+
+/// Searches for the joker combination that maximizes an objective
+///
+/// The run supplies the deck, hand levels, hands, discards, and ante (whose
+/// Boss blind requirement is the clear-rate target). Its own jokers are
+/// ignored.
+pub struct BuildOptimizer {
+    run: GameRunState,
+    slots: usize,
+    pool: Vec<JokerKind>,
+    objective: OptimizeObjective,
+    num_runs: usize,
+    seed: u64,
+    restarts: usize,
+}
+
+impl BuildOptimizer {
+    /// Creates an optimizer filling the deck's joker slots from every
+    /// non-Legendary joker
+    pub fn new(run: GameRunState) -> Self {
+        Self {
+            slots: run.deck_type.modifiers().joker_slots as usize,
+            run,
+            pool: JokerKind::all()
+                .into_iter()
+                .filter(|kind| kind.rarity() != JokerRarity::Legendary)
+                .collect(),
+            objective: OptimizeObjective::default(),
+            num_runs: DEFAULT_OPTIMIZER_RUNS,
+            seed: 0,
+            restarts: DEFAULT_RESTARTS,
+        }
+    }
+
+    /// Sets the number of jokers in a build
+    pub fn with_slots(mut self, slots: usize) -> Self {
+        self.slots = slots;
+        self
+    }
+
+    /// Sets the jokers to choose from
+    pub fn with_pool(mut self, pool: Vec<JokerKind>) -> Self {
+        self.pool = pool;
+        self
+    }
+
+    /// Limits the pool to jokers of the given rarities
+    pub fn with_rarities(mut self, rarities: &[JokerRarity]) -> Self {
+        self.pool.retain(|kind| rarities.contains(&kind.rarity()));
+        self
+    }
+
+    /// Sets what to maximize
+    pub fn with_objective(mut self, objective: OptimizeObjective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    /// Sets the rounds simulated per candidate and the seed they are dealt
+    /// from
+    pub fn with_runs(mut self, num_runs: usize, seed: u64) -> Self {
+        self.num_runs = num_runs.max(1);
+        self.seed = seed;
+        self
+    }
+
+    /// Sets how many random builds are hill-climbed besides the greedy one
+    pub fn with_restarts(mut self, restarts: usize) -> Self {
+        self.restarts = restarts;
+        self
+    }
+
+    /// Runs the search, returning every full build evaluated, best first
+    pub fn optimize(&self) -> Vec<OptimizedBuild> {
+        let slots = self.slots.min(self.pool.len());
+        let mut search = Search {
+            optimizer: self,
+            target: Score::from(self.run.blind_requirement(BlindType::Boss)),
+            evaluated: HashMap::new(),
+        };
+
+        let mut starts = vec![search.greedy(slots)];
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        for _ in 0..self.restarts {
+            starts.push(self.pool.choose_multiple(&mut rng, slots).cloned().collect());
+        }
+        for start in starts {
+            search.climb(start);
+        }
+
+        let mut builds: Vec<OptimizedBuild> = search
+            .evaluated
+            .into_values()
+            .filter(|build| build.jokers.len() == slots)
+            .collect();
+        builds.sort_by(|a, b| {
+            b.value(self.objective)
+                .total_cmp(&a.value(self.objective))
+                .then_with(|| b.mean_score.total_cmp(&a.mean_score))
+        });
+        builds
+    }
+}
+
+/// The state of one optimization
+struct Search<'a> {
+    optimizer: &'a BuildOptimizer,
+    /// Score a round must reach to count as clearing the blind
+    target: Score,
+    /// Builds simulated so far, keyed by their sorted joker names
+    evaluated: HashMap<Vec<&'static str>, OptimizedBuild>,
+}
+
+impl Search<'_> {
+    /// Simulates a build, or returns its earlier result
+    fn evaluate(&mut self, kinds: &[JokerKind]) -> OptimizedBuild {
+        let mut key: Vec<&'static str> = kinds.iter().map(JokerKind::name).collect();
+        key.sort_unstable();
+        if let Some(build) = self.evaluated.get(&key) {
+            return build.clone();
+        }
+
+        let optimizer = self.optimizer;
+        let jokers: Vec<Joker> = kinds.iter().cloned().map(Joker::new).collect();
+        let run = optimizer.run.clone().with_jokers(jokers.clone());
+        let mut config = run.simulation_config(optimizer.num_runs, Some(optimizer.seed));
+        config.target_score = Some(self.target);
+        let result = Simulator::new(run.solver()).simulate(config);
+
+        let build = OptimizedBuild {
+            jokers,
+            mean_score: result.mean_score,
+            clear_rate: result.target.map_or(0.0, |target| target.probability),
+        };
+        self.evaluated.insert(key, build.clone());
+        build
+    }
+
+    /// Adds the joker that helps most, one slot at a time
+    fn greedy(&mut self, slots: usize) -> Vec<JokerKind> {
+        let objective = self.optimizer.objective;
+        let mut build: Vec<JokerKind> = Vec::with_capacity(slots);
+        while build.len() < slots {
+            let mut best: Option<(JokerKind, f64)> = None;
+            for kind in &self.optimizer.pool {
+                if build.contains(kind) {
+                    continue;
+                }
+                let mut candidate = build.clone();
+                candidate.push(kind.clone());
+                let value = self.evaluate(&candidate).value(objective);
+                if best.as_ref().is_none_or(|(_, best)| value > *best) {
+                    best = Some((kind.clone(), value));
+                }
+            }
+            let Some((kind, _)) = best else {
+                break;
+            };
+            build.push(kind);
+        }
+        build
+    }
+
+    /// Swaps one joker for one from the pool while that improves the
+    /// objective, taking the best swap each step
+    fn climb(&mut self, mut build: Vec<JokerKind>) {
+        let objective = self.optimizer.objective;
+        let mut value = self.evaluate(&build).value(objective);
+
+        for _ in 0..MAX_CLIMB_STEPS {
+            let mut best: Option<(Vec<JokerKind>, f64)> = None;
+            for slot in 0..build.len() {
+                for kind in &self.optimizer.pool {
+                    if build.contains(kind) {
+                        continue;
+                    }
+                    let mut candidate = build.clone();
+                    candidate[slot] = kind.clone();
+                    let candidate_value = self.evaluate(&candidate).value(objective);
+                    if candidate_value > best.as_ref().map_or(value, |(_, best)| *best) {
+                        best = Some((candidate, candidate_value));
+                    }
+                }
+            }
+            let Some((candidate, candidate_value)) = best else {
+                return;
+            };
+            build = candidate;
+            value = candidate_value;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_optimizer_picks_strongest_jokers() {
+        let pool = vec![
+            JokerKind::Joker,
+            JokerKind::Blackboard,
+            JokerKind::GoldenJoker,
+            JokerKind::Rocket,
+        ];
+        let optimizer = BuildOptimizer::new(GameRunState::default())
+            .with_pool(pool)
+            .with_slots(2)
+            .with_runs(20, 1)
+            .with_restarts(1);
+
+        let builds = optimizer.optimize();
+        // Golden Joker and Rocket only pay money
+        let best: Vec<&JokerKind> = builds[0].jokers.iter().map(|joker| &joker.kind).collect();
+        assert!(best.contains(&&JokerKind::Joker));
+        assert!(best.contains(&&JokerKind::Blackboard));
+        assert!(builds.iter().all(|build| build.jokers.len() == 2));
+        assert!(builds.windows(2).all(|pair| pair[0].mean_score >= pair[1].mean_score));
+    }
+
+    #[test]
+    fn test_pool_filters_and_objective_parsing() {
+        let optimizer = BuildOptimizer::new(GameRunState::default())
+            .with_rarities(&[JokerRarity::Rare]);
+        assert!(optimizer.pool.contains(&JokerKind::Baron));
+        assert!(optimizer.pool.iter().all(|kind| kind.rarity() == JokerRarity::Rare));
+
+        assert_eq!("clear_rate".parse::<OptimizeObjective>().unwrap(), OptimizeObjective::ClearRate);
+        assert_eq!("Mean".parse::<OptimizeObjective>().unwrap(), OptimizeObjective::MeanScore);
+        assert!("max".parse::<OptimizeObjective>().is_err());
+    }
+}
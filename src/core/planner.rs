@@ -0,0 +1,248 @@
+//! Round planner
+//!
+//! This module sequences plays and discards over the rest of a round. The
+//! solver finds the best single play; the planner instead picks the next
+//! action that gives the best chance of reaching the blind's target with
+//! the hands and discards left.
+
+use super::card::Card;
+use super::draw::DrawPile;
+use super::hand::Hand;
+use super::score::Score;
+use super::simulator::Simulator;
+use super::solver::Solver;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+/// Default number of rollouts used to score each candidate action
+pub const DEFAULT_ROLLOUTS: usize = 64;
+
+/// An action taken with the current hand
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RoundAction {
+    /// Play these cards
+    Play(Vec<Card>),
+    /// Discard these cards and draw replacements
+    Discard(Vec<Card>),
+}
+
+/// A candidate action and its estimated chance of beating the blind
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlannedAction {
+    pub action: RoundAction,
+    /// Fraction of rollouts that reached the target after this action
+    pub success_probability: f64,
+}
+
+/// The planner's recommendation for the next action of the round
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoundPlan {
+    /// The action with the highest chance of beating the blind
+    pub best: PlannedAction,
+    /// Every candidate considered, most likely to succeed first
+    pub candidates: Vec<PlannedAction>,
+}
+
+/// Plans the rest of a round against a blind target
+///
+/// Each candidate action (the solver's top plays, and its best discard if a
+/// discard is left) is followed by rollouts that draw from a shuffled copy
+/// of the remaining deck and finish the round greedily: play the best hand,
+/// discarding first while the best hand is behind the pace the target needs.
+pub struct RoundPlanner {
+    solver: Solver,
+    rollouts: usize,
+    seed: u64,
+}
+
+impl RoundPlanner {
+    /// Creates a planner that uses the solver's hands and discards remaining
+    pub fn new(solver: Solver) -> Self {
+        Self {
+            solver,
+            rollouts: DEFAULT_ROLLOUTS,
+            seed: 0,
+        }
+    }
+
+    /// Sets the number of rollouts per candidate and the seed used to shuffle
+    pub fn with_rollouts(mut self, rollouts: usize, seed: u64) -> Self {
+        self.rollouts = rollouts.max(1);
+        self.seed = seed;
+        self
+    }
+
+    /// Picks the next action that is most likely to reach `target`
+    ///
+    /// `target` is the score still needed this round and `deck_remaining`
+    /// the cards left to draw. Ties keep the solver's order, so with no
+    /// pressure from the target the best-scoring play is recommended.
+    pub fn plan(&self, hand: &[Card], deck_remaining: &[Card], target: Score) -> RoundPlan {
+        let hands = self.solver.hands_remaining();
+        let discards = self.solver.discards_remaining();
+        let result = self.solver.solve(hand);
+
+        let mut actions: Vec<RoundAction> = std::iter::once(&result.best_hand)
+            .chain(result.alternatives.iter().map(|(hand, _)| hand))
+            .filter(|hand| !hand.cards.is_empty())
+            .map(|hand| RoundAction::Play(hand.cards.clone()))
+            .collect();
+        if discards > 0 && !deck_remaining.is_empty() {
+            let discard = self.solver.solve_discard(hand, deck_remaining, discards);
+            if discard.should_discard() {
+                actions.push(RoundAction::Discard(discard.discard));
+            }
+        }
+
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        let mut candidates: Vec<PlannedAction> = actions
+            .into_iter()
+            .map(|action| {
+                let successes = (0..self.rollouts)
+                    .filter(|_| {
+                        let round = Rollout {
+                            hand: hand.to_vec(),
+                            hand_size: hand.len(),
+                            draw_pile: DrawPile::new(deck_remaining, rng.next_u64()),
+                            needed: target.value(),
+                            hands,
+                            discards,
+                        };
+                        self.rollout(round, &action, &mut rng)
+                    })
+                    .count();
+                PlannedAction {
+                    action,
+                    success_probability: successes as f64 / self.rollouts as f64,
+                }
+            })
+            .collect();
+
+        candidates.sort_by(|a, b| b.success_probability.total_cmp(&a.success_probability));
+        let best = candidates.first().cloned().unwrap_or(PlannedAction {
+            action: RoundAction::Play(vec![]),
+            success_probability: 0.0,
+        });
+
+        RoundPlan { best, candidates }
+    }
+
+    /// Applies `first` and finishes the round greedily, returning whether the
+    /// target was reached
+    fn rollout(&self, mut round: Rollout, first: &RoundAction, rng: &mut ChaCha8Rng) -> bool {
+        let mut action = Some(first.clone());
+
+        while round.needed > 0.0 && round.hands > 0 && !round.hand.is_empty() {
+            let next = match action.take() {
+                Some(action) => action,
+                None => self.greedy_action(&round),
+            };
+
+            match next {
+                RoundAction::Discard(cards) => {
+                    round.remove(&cards);
+                    round.discards = round.discards.saturating_sub(1);
+                }
+                RoundAction::Play(cards) => {
+                    round.remove(&cards);
+                    let result = self.solver.calculator().calculate_sampled(
+                        &Hand::new(cards),
+                        &round.hand,
+                        rng,
+                    );
+                    round.needed -= result.score.value();
+                    round.hands -= 1;
+                }
+            }
+            round.draw_pile.draw_to(&mut round.hand, round.hand_size);
+        }
+
+        round.needed <= 0.0
+    }
+
+    /// Plays the best hand, or discards while it falls behind the pace
+    /// needed to reach the target with the hands left
+    fn greedy_action(&self, round: &Rollout) -> RoundAction {
+        let result = self.solver.solve(&round.hand);
+        let score = result.best_score.as_ref().map_or(0.0, |s| s.score.value());
+        let behind = score < round.needed && score * (round.hands as f64) < round.needed;
+
+        if behind
+            && round.discards > 0
+            && !round.draw_pile.is_empty()
+            && let Some(discard) = Simulator::choose_discard(&result.held_cards)
+        {
+            return RoundAction::Discard(discard);
+        }
+        RoundAction::Play(result.best_hand.cards)
+    }
+}
+
+/// The state of a round during a rollout
+struct Rollout<'a> {
+    hand: Vec<Card>,
+    hand_size: usize,
+    draw_pile: DrawPile<'a>,
+    /// Score still needed to beat the blind
+    needed: f64,
+    hands: u32,
+    discards: u32,
+}
+
+impl Rollout<'_> {
+    /// Removes one copy of each of the given cards from the hand
+    fn remove(&mut self, cards: &[Card]) {
+        for card in cards {
+            if let Some(pos) = self.hand.iter().position(|c| c == card) {
+                self.hand.remove(pos);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::card::{Rank, Suit};
+    use crate::core::scoring::ScoreCalculator;
+
+    #[test]
+    fn test_plan_plays_when_target_is_reachable() {
+        let hand = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+            Card::new(Rank::Two, Suit::Clubs),
+        ];
+        let deck = vec![Card::new(Rank::Three, Suit::Diamonds)];
+
+        let planner = RoundPlanner::new(Solver::new(ScoreCalculator::new(vec![])));
+        let plan = planner.plan(&hand, &deck, Score::from(50u64));
+
+        assert_eq!(plan.best.success_probability, 1.0);
+        assert!(matches!(plan.best.action, RoundAction::Play(_)));
+    }
+
+    #[test]
+    fn test_plan_discards_towards_flush() {
+        let hand = vec![
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Five, Suit::Hearts),
+            Card::new(Rank::Seven, Suit::Hearts),
+            Card::new(Rank::Nine, Suit::Hearts),
+            Card::new(Rank::Three, Suit::Clubs),
+        ];
+        let deck = vec![Card::new(Rank::Four, Suit::Hearts)];
+
+        // High card scores 14, a flush scores (35 + 27) x 4 = 248
+        let solver = Solver::new(ScoreCalculator::new(vec![])).with_remaining(1, 1);
+        let planner = RoundPlanner::new(solver).with_rollouts(8, 1);
+        let plan = planner.plan(&hand, &deck, Score::from(200u64));
+
+        assert_eq!(plan.best.success_probability, 1.0);
+        assert_eq!(
+            plan.best.action,
+            RoundAction::Discard(vec![Card::new(Rank::Three, Suit::Clubs)])
+        );
+    }
+}
@@ -0,0 +1,364 @@
+//! The game's item pools
+//!
+//! Every joker, voucher, Tarot, Planet, and boss blind by the key the game
+//! saves it under, in the order the game keeps its pools. Picking from a
+//! pool with the game's random numbers only gives the game's results if the
+//! pool is in this same order, so seed analysis and save imports share
+//! these tables rather than jimbo's own (partial) lists.
+
+use super::joker::JokerRarity;
+
+/// A joker in the game's pools
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JokerEntry {
+    pub key: &'static str,
+    pub name: &'static str,
+    pub rarity: JokerRarity,
+}
+
+/// A voucher in the game's pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VoucherEntry {
+    pub key: &'static str,
+    pub name: &'static str,
+    /// The voucher that must be redeemed before this one can appear
+    pub requires: Option<&'static str>,
+}
+
+/// A boss blind in the game's pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BossEntry {
+    pub key: &'static str,
+    pub name: &'static str,
+    /// First ante the boss can appear in
+    pub min_ante: u32,
+    /// Whether the boss only appears in the final ante of each eight
+    pub showdown: bool,
+}
+
+const fn joker(key: &'static str, name: &'static str, rarity: JokerRarity) -> JokerEntry {
+    JokerEntry { key, name, rarity }
+}
+
+const fn voucher(
+    key: &'static str,
+    name: &'static str,
+    requires: Option<&'static str>,
+) -> VoucherEntry {
+    VoucherEntry { key, name, requires }
+}
+
+const fn boss(key: &'static str, name: &'static str, min_ante: u32) -> BossEntry {
+    BossEntry { key, name, min_ante, showdown: false }
+}
+
+const fn showdown(key: &'static str, name: &'static str) -> BossEntry {
+    BossEntry { key, name, min_ante: 8, showdown: true }
+}
+
+use JokerRarity::{Common as C, Legendary as L, Rare as R, Uncommon as U};
+
+/// Every joker, in the game's order
+pub const JOKERS: [JokerEntry; 150] = [
+    joker("j_joker", "Joker", C),
+    joker("j_greedy_joker", "Greedy Joker", C),
+    joker("j_lusty_joker", "Lusty Joker", C),
+    joker("j_wrathful_joker", "Wrathful Joker", C),
+    joker("j_gluttenous_joker", "Gluttonous Joker", C),
+    joker("j_jolly", "Jolly Joker", C),
+    joker("j_zany", "Zany Joker", C),
+    joker("j_mad", "Mad Joker", C),
+    joker("j_crazy", "Crazy Joker", C),
+    joker("j_droll", "Droll Joker", C),
+    joker("j_sly", "Sly Joker", C),
+    joker("j_wily", "Wily Joker", C),
+    joker("j_clever", "Clever Joker", C),
+    joker("j_devious", "Devious Joker", C),
+    joker("j_crafty", "Crafty Joker", C),
+    joker("j_half", "Half Joker", C),
+    joker("j_stencil", "Joker Stencil", U),
+    joker("j_four_fingers", "Four Fingers", U),
+    joker("j_mime", "Mime", U),
+    joker("j_credit_card", "Credit Card", C),
+    joker("j_ceremonial", "Ceremonial Dagger", U),
+    joker("j_banner", "Banner", C),
+    joker("j_mystic_summit", "Mystic Summit", C),
+    joker("j_marble", "Marble Joker", U),
+    joker("j_loyalty_card", "Loyalty Card", U),
+    joker("j_8_ball", "8 Ball", C),
+    joker("j_misprint", "Misprint", C),
+    joker("j_dusk", "Dusk", U),
+    joker("j_raised_fist", "Raised Fist", C),
+    joker("j_chaos", "Chaos the Clown", C),
+    joker("j_fibonacci", "Fibonacci", U),
+    joker("j_steel_joker", "Steel Joker", U),
+    joker("j_scary_face", "Scary Face", C),
+    joker("j_abstract", "Abstract Joker", C),
+    joker("j_delayed_grat", "Delayed Gratification", C),
+    joker("j_hack", "Hack", U),
+    joker("j_pareidolia", "Pareidolia", U),
+    joker("j_gros_michel", "Gros Michel", C),
+    joker("j_even_steven", "Even Steven", C),
+    joker("j_odd_todd", "Odd Todd", C),
+    joker("j_scholar", "Scholar", C),
+    joker("j_business", "Business Card", C),
+    joker("j_supernova", "Supernova", C),
+    joker("j_ride_the_bus", "Ride the Bus", C),
+    joker("j_space", "Space Joker", U),
+    joker("j_egg", "Egg", C),
+    joker("j_burglar", "Burglar", U),
+    joker("j_blackboard", "Blackboard", U),
+    joker("j_runner", "Runner", C),
+    joker("j_ice_cream", "Ice Cream", C),
+    joker("j_dna", "DNA", R),
+    joker("j_splash", "Splash", C),
+    joker("j_blue_joker", "Blue Joker", C),
+    joker("j_sixth_sense", "Sixth Sense", U),
+    joker("j_constellation", "Constellation", U),
+    joker("j_hiker", "Hiker", U),
+    joker("j_faceless", "Faceless Joker", C),
+    joker("j_green_joker", "Green Joker", C),
+    joker("j_superposition", "Superposition", C),
+    joker("j_todo_list", "To Do List", C),
+    joker("j_cavendish", "Cavendish", C),
+    joker("j_card_sharp", "Card Sharp", U),
+    joker("j_red_card", "Red Card", C),
+    joker("j_madness", "Madness", U),
+    joker("j_square", "Square Joker", C),
+    joker("j_seance", "Seance", U),
+    joker("j_riff_raff", "Riff-raff", C),
+    joker("j_vampire", "Vampire", U),
+    joker("j_shortcut", "Shortcut", U),
+    joker("j_hologram", "Hologram", U),
+    joker("j_vagabond", "Vagabond", R),
+    joker("j_baron", "Baron", R),
+    joker("j_cloud_9", "Cloud 9", U),
+    joker("j_rocket", "Rocket", U),
+    joker("j_obelisk", "Obelisk", R),
+    joker("j_midas_mask", "Midas Mask", U),
+    joker("j_luchador", "Luchador", U),
+    joker("j_photograph", "Photograph", C),
+    joker("j_gift", "Gift Card", U),
+    joker("j_turtle_bean", "Turtle Bean", U),
+    joker("j_erosion", "Erosion", U),
+    joker("j_reserved_parking", "Reserved Parking", C),
+    joker("j_mail", "Mail-In Rebate", C),
+    joker("j_to_the_moon", "To the Moon", U),
+    joker("j_hallucination", "Hallucination", C),
+    joker("j_fortune_teller", "Fortune Teller", C),
+    joker("j_juggler", "Juggler", C),
+    joker("j_drunkard", "Drunkard", C),
+    joker("j_stone", "Stone Joker", U),
+    joker("j_golden", "Golden Joker", C),
+    joker("j_lucky_cat", "Lucky Cat", U),
+    joker("j_baseball", "Baseball Card", R),
+    joker("j_bull", "Bull", U),
+    joker("j_diet_cola", "Diet Cola", U),
+    joker("j_trading", "Trading Card", U),
+    joker("j_flash", "Flash Card", U),
+    joker("j_popcorn", "Popcorn", C),
+    joker("j_trousers", "Spare Trousers", U),
+    joker("j_ancient", "Ancient Joker", R),
+    joker("j_ramen", "Ramen", U),
+    joker("j_walkie_talkie", "Walkie Talkie", C),
+    joker("j_selzer", "Seltzer", U),
+    joker("j_castle", "Castle", U),
+    joker("j_smiley", "Smiley Face", C),
+    joker("j_campfire", "Campfire", R),
+    joker("j_ticket", "Golden Ticket", C),
+    joker("j_mr_bones", "Mr. Bones", U),
+    joker("j_acrobat", "Acrobat", U),
+    joker("j_sock_and_buskin", "Sock and Buskin", U),
+    joker("j_swashbuckler", "Swashbuckler", C),
+    joker("j_troubadour", "Troubadour", U),
+    joker("j_certificate", "Certificate", U),
+    joker("j_smeared", "Smeared Joker", U),
+    joker("j_throwback", "Throwback", U),
+    joker("j_hanging_chad", "Hanging Chad", C),
+    joker("j_rough_gem", "Rough Gem", U),
+    joker("j_bloodstone", "Bloodstone", U),
+    joker("j_arrowhead", "Arrowhead", U),
+    joker("j_onyx_agate", "Onyx Agate", U),
+    joker("j_glass", "Glass Joker", U),
+    joker("j_ring_master", "Showman", U),
+    joker("j_flower_pot", "Flower Pot", U),
+    joker("j_blueprint", "Blueprint", R),
+    joker("j_wee", "Wee Joker", R),
+    joker("j_merry_andy", "Merry Andy", U),
+    joker("j_oops", "Oops! All 6s", U),
+    joker("j_idol", "The Idol", U),
+    joker("j_seeing_double", "Seeing Double", U),
+    joker("j_matador", "Matador", U),
+    joker("j_hit_the_road", "Hit the Road", R),
+    joker("j_duo", "The Duo", R),
+    joker("j_trio", "The Trio", R),
+    joker("j_family", "The Family", R),
+    joker("j_order", "The Order", R),
+    joker("j_tribe", "The Tribe", R),
+    joker("j_stuntman", "Stuntman", R),
+    joker("j_invisible", "Invisible Joker", R),
+    joker("j_brainstorm", "Brainstorm", R),
+    joker("j_satellite", "Satellite", U),
+    joker("j_shoot_the_moon", "Shoot the Moon", C),
+    joker("j_drivers_license", "Driver's License", R),
+    joker("j_cartomancer", "Cartomancer", U),
+    joker("j_astronomer", "Astronomer", U),
+    joker("j_burnt", "Burnt Joker", R),
+    joker("j_bootstraps", "Bootstraps", U),
+    joker("j_caino", "Canio", L),
+    joker("j_triboulet", "Triboulet", L),
+    joker("j_yorick", "Yorick", L),
+    joker("j_chicot", "Chicot", L),
+    joker("j_perkeo", "Perkeo", L),
+];
+
+/// Jokers kept out of the shop at the start of a run: those that need an
+/// enhanced card in the deck, and Cavendish until Gros Michel goes extinct
+pub const GATED_JOKERS: [&str; 6] = [
+    "j_steel_joker",
+    "j_stone",
+    "j_lucky_cat",
+    "j_glass",
+    "j_ticket",
+    "j_cavendish",
+];
+
+/// Every voucher, in the game's order (each base voucher before its upgrade)
+pub const VOUCHERS: [VoucherEntry; 32] = [
+    voucher("v_overstock_norm", "Overstock", None),
+    voucher("v_overstock_plus", "Overstock Plus", Some("v_overstock_norm")),
+    voucher("v_clearance_sale", "Clearance Sale", None),
+    voucher("v_liquidation", "Liquidation", Some("v_clearance_sale")),
+    voucher("v_hone", "Hone", None),
+    voucher("v_glow_up", "Glow Up", Some("v_hone")),
+    voucher("v_reroll_surplus", "Reroll Surplus", None),
+    voucher("v_reroll_glut", "Reroll Glut", Some("v_reroll_surplus")),
+    voucher("v_crystal_ball", "Crystal Ball", None),
+    voucher("v_omen_globe", "Omen Globe", Some("v_crystal_ball")),
+    voucher("v_telescope", "Telescope", None),
+    voucher("v_observatory", "Observatory", Some("v_telescope")),
+    voucher("v_grabber", "Grabber", None),
+    voucher("v_nacho_tong", "Nacho Tong", Some("v_grabber")),
+    voucher("v_wasteful", "Wasteful", None),
+    voucher("v_recyclomancy", "Recyclomancy", Some("v_wasteful")),
+    voucher("v_tarot_merchant", "Tarot Merchant", None),
+    voucher("v_tarot_tycoon", "Tarot Tycoon", Some("v_tarot_merchant")),
+    voucher("v_planet_merchant", "Planet Merchant", None),
+    voucher("v_planet_tycoon", "Planet Tycoon", Some("v_planet_merchant")),
+    voucher("v_seed_money", "Seed Money", None),
+    voucher("v_money_tree", "Money Tree", Some("v_seed_money")),
+    voucher("v_blank", "Blank", None),
+    voucher("v_antimatter", "Antimatter", Some("v_blank")),
+    voucher("v_magic_trick", "Magic Trick", None),
+    voucher("v_illusion", "Illusion", Some("v_magic_trick")),
+    voucher("v_hieroglyph", "Hieroglyph", None),
+    voucher("v_petroglyph", "Petroglyph", Some("v_hieroglyph")),
+    voucher("v_directors_cut", "Director's Cut", None),
+    voucher("v_retcon", "Retcon", Some("v_directors_cut")),
+    voucher("v_paint_brush", "Paint Brush", None),
+    voucher("v_palette", "Palette", Some("v_paint_brush")),
+];
+
+/// Every Tarot card, in the game's order
+pub const TAROTS: [(&str, &str); 22] = [
+    ("c_fool", "The Fool"),
+    ("c_magician", "The Magician"),
+    ("c_high_priestess", "The High Priestess"),
+    ("c_empress", "The Empress"),
+    ("c_emperor", "The Emperor"),
+    ("c_heirophant", "The Hierophant"),
+    ("c_lovers", "The Lovers"),
+    ("c_chariot", "The Chariot"),
+    ("c_justice", "Justice"),
+    ("c_hermit", "The Hermit"),
+    ("c_wheel_of_fortune", "The Wheel of Fortune"),
+    ("c_strength", "Strength"),
+    ("c_hanged_man", "The Hanged Man"),
+    ("c_death", "Death"),
+    ("c_temperance", "Temperance"),
+    ("c_devil", "The Devil"),
+    ("c_tower", "The Tower"),
+    ("c_star", "The Star"),
+    ("c_moon", "The Moon"),
+    ("c_sun", "The Sun"),
+    ("c_judgement", "Judgement"),
+    ("c_world", "The World"),
+];
+
+/// Every Planet card, in the game's order, and whether it stays out of
+/// the shop until its hand has been played
+pub const PLANETS: [(&str, &str, bool); 12] = [
+    ("c_mercury", "Mercury", false),
+    ("c_venus", "Venus", false),
+    ("c_earth", "Earth", false),
+    ("c_mars", "Mars", false),
+    ("c_jupiter", "Jupiter", false),
+    ("c_saturn", "Saturn", false),
+    ("c_uranus", "Uranus", false),
+    ("c_neptune", "Neptune", false),
+    ("c_pluto", "Pluto", false),
+    ("c_planet_x", "Planet X", true),
+    ("c_ceres", "Ceres", true),
+    ("c_eris", "Eris", true),
+];
+
+/// Every boss blind, sorted by key as the game sorts them before picking
+pub const BOSSES: [BossEntry; 28] = [
+    boss("bl_arm", "The Arm", 2),
+    boss("bl_club", "The Club", 1),
+    boss("bl_eye", "The Eye", 3),
+    showdown("bl_final_acorn", "Amber Acorn"),
+    showdown("bl_final_bell", "Cerulean Bell"),
+    showdown("bl_final_heart", "Crimson Heart"),
+    showdown("bl_final_leaf", "Verdant Leaf"),
+    showdown("bl_final_vessel", "Violet Vessel"),
+    boss("bl_fish", "The Fish", 2),
+    boss("bl_flint", "The Flint", 2),
+    boss("bl_goad", "The Goad", 1),
+    boss("bl_head", "The Head", 1),
+    boss("bl_hook", "The Hook", 1),
+    boss("bl_house", "The House", 2),
+    boss("bl_manacle", "The Manacle", 1),
+    boss("bl_mark", "The Mark", 2),
+    boss("bl_mouth", "The Mouth", 2),
+    boss("bl_needle", "The Needle", 2),
+    boss("bl_ox", "The Ox", 6),
+    boss("bl_pillar", "The Pillar", 1),
+    boss("bl_plant", "The Plant", 4),
+    boss("bl_psychic", "The Psychic", 1),
+    boss("bl_serpent", "The Serpent", 5),
+    boss("bl_tooth", "The Tooth", 3),
+    boss("bl_wall", "The Wall", 2),
+    boss("bl_water", "The Water", 2),
+    boss("bl_wheel", "The Wheel", 2),
+    boss("bl_window", "The Window", 1),
+];
+
+/// Looks up a voucher's name by its key
+pub fn voucher_name(key: &str) -> Option<&'static str> {
+    VOUCHERS.iter().find(|voucher| voucher.key == key).map(|voucher| voucher.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::joker::JokerKind;
+
+    #[test]
+    fn test_pools() {
+        let count = |rarity| JOKERS.iter().filter(|joker| joker.rarity == rarity).count();
+        assert_eq!((count(C), count(U), count(R), count(L)), (61, 64, 20, 5));
+        assert!(BOSSES.windows(2).all(|pair| pair[0].key < pair[1].key));
+        for key in GATED_JOKERS {
+            assert!(JOKERS.iter().any(|joker| joker.key == key), "{}", key);
+        }
+        for required in VOUCHERS.iter().filter_map(|voucher| voucher.requires) {
+            assert!(voucher_name(required).is_some(), "{}", required);
+        }
+
+        // Every joker jimbo models is in the pool under its own name
+        for kind in JokerKind::all() {
+            assert!(JOKERS.iter().any(|joker| joker.name == kind.name()), "{}", kind.name());
+        }
+    }
+}
@@ -0,0 +1,60 @@
+//! Rule modifiers for hand evaluation and scoring
+//!
+//! Some jokers change the rules of the game rather than adding chips or
+//! mult (e.g., Four Fingers allows 4-card flushes). These are collected
+//! into a `ScoringRules` context that hand evaluation and scoring consult.
+
+use super::joker::{Joker, JokerKind};
+use serde::{Deserialize, Serialize};
+
+/// Rule changes that affect how hands are evaluated and scored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScoringRules {
+    /// Flushes and straights can be made with 4 cards (Four Fingers)
+    pub four_fingers: bool,
+    /// Straights can skip one rank between cards, e.g. 2-4-5-7-8 (Shortcut)
+    pub shortcut: bool,
+    /// Every played card counts in scoring (Splash)
+    pub splash: bool,
+    /// Every card is considered a face card (Pareidolia)
+    pub pareidolia: bool,
+}
+
+impl ScoringRules {
+    /// Derives the active rules from a set of jokers
+    pub fn from_jokers(jokers: &[Joker]) -> Self {
+        let has = |kind: JokerKind| jokers.iter().any(|joker| joker.kind == kind);
+
+        Self {
+            four_fingers: has(JokerKind::FourFingers),
+            shortcut: has(JokerKind::Shortcut),
+            splash: has(JokerKind::Splash),
+            pareidolia: has(JokerKind::Pareidolia),
+        }
+    }
+
+    /// Returns the largest allowed rank step between adjacent straight cards
+    pub fn max_straight_gap(&self) -> u8 {
+        if self.shortcut { 2 } else { 1 }
+    }
+
+    /// Returns the number of cards needed for a straight or flush
+    pub fn min_straight_flush_len(&self) -> usize {
+        if self.four_fingers { 4 } else { 5 }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_from_jokers() {
+        let rules = ScoringRules::from_jokers(&[Joker::new(JokerKind::FourFingers)]);
+        assert!(rules.four_fingers);
+        assert!(!rules.splash);
+        assert!(!rules.shortcut);
+        assert_eq!(rules.min_straight_flush_len(), 4);
+        assert_eq!(ScoringRules::default().min_straight_flush_len(), 5);
+    }
+}
@@ -0,0 +1,204 @@
+//! Full run state
+//!
+//! `GameRunState` gathers everything about a run in progress that affects
+//! solving and simulation, so callers pass one value instead of separate
+//! jokers, hand levels, and decks.
+
+use super::blind::{blind_requirement, boss_requirement, BlindType, BossBlind};
+use super::card::Card;
+use super::deck::DeckType;
+use super::hand_levels::HandLevels;
+use super::joker::Joker;
+use super::scoring::ScoreCalculator;
+use super::simulator::SimulationConfig;
+use super::solver::Solver;
+use super::stake::Stake;
+use serde::{Deserialize, Serialize};
+
+/// The state of a run in progress
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameRunState {
+    /// Every card in the full deck
+    pub deck: Vec<Card>,
+    /// The deck archetype the run started with
+    pub deck_type: DeckType,
+    /// Current hand levels
+    pub hand_levels: HandLevels,
+    /// Jokers in scoring order
+    pub jokers: Vec<Joker>,
+    /// Consumables held (tarots, planets, spectrals)
+    pub consumables: Vec<String>,
+    /// Vouchers purchased
+    pub vouchers: Vec<String>,
+    pub money: u32,
+    pub ante: u32,
+    pub hands_remaining: u32,
+    pub discards_remaining: u32,
+    pub stake: Stake,
+    /// The boss blind of the current ante, if known
+    #[serde(default)]
+    pub boss_blind: Option<BossBlind>,
+}
+
+impl GameRunState {
+    /// Creates the state at the start of a run with the given deck and stake
+    pub fn new(deck_type: DeckType, stake: Stake) -> Self {
+        let modifiers = deck_type.modifiers();
+
+        Self {
+            deck: deck_type.create_cards(0),
+            deck_type,
+            hand_levels: HandLevels::new(),
+            jokers: Vec::new(),
+            consumables: modifiers.starting_consumables,
+            vouchers: modifiers.starting_vouchers,
+            money: modifiers.starting_money,
+            ante: 1,
+            hands_remaining: modifiers.hands,
+            discards_remaining: modifiers.discards.saturating_add_signed(stake.discard_modifier()),
+            stake,
+            boss_blind: None,
+        }
+    }
+
+    /// Replaces the full deck
+    pub fn with_deck(mut self, deck: Vec<Card>) -> Self {
+        self.deck = deck;
+        self
+    }
+
+    /// Replaces the jokers (in scoring order)
+    pub fn with_jokers(mut self, jokers: Vec<Joker>) -> Self {
+        self.jokers = jokers;
+        self
+    }
+
+    /// Replaces the hand levels
+    pub fn with_hand_levels(mut self, hand_levels: HandLevels) -> Self {
+        self.hand_levels = hand_levels;
+        self
+    }
+
+    /// Sets the boss blind being played
+    pub fn with_boss_blind(mut self, boss_blind: Option<BossBlind>) -> Self {
+        self.boss_blind = boss_blind;
+        self
+    }
+
+    /// Creates a score calculator for this run's jokers, hand levels, deck,
+    /// and boss blind
+    pub fn calculator(&self) -> ScoreCalculator {
+        ScoreCalculator::new(self.jokers.clone())
+            .with_hand_levels(self.hand_levels.clone())
+            .with_deck(self.deck.clone())
+            .with_plasma_balance(self.deck_type.modifiers().plasma_balance)
+            .with_boss_blind(self.boss_blind)
+    }
+
+    /// Creates a solver for this run that knows the hands and discards left
+    pub fn solver(&self) -> Solver {
+        Solver::new(self.calculator()).with_remaining(self.hands_remaining, self.discards_remaining)
+    }
+
+    /// Creates a simulation config that draws from this run's deck
+    ///
+    /// Hands are drawn at the deck type's hand size, and each run plays the
+    /// hands and discards remaining.
+    pub fn simulation_config(&self, num_runs: usize, seed: Option<u64>) -> SimulationConfig {
+        SimulationConfig {
+            deck: self.deck.clone(),
+            hand_size: self.deck_type.modifiers().hand_size,
+            num_runs,
+            seed,
+            hands_remaining: self.hands_remaining,
+            discards_remaining: self.discards_remaining,
+            blind_score: None,
+            blind: None,
+            target_score: None,
+            convergence: None,
+            variance_reduction: None,
+            max_duration: None,
+            record_runs: false,
+            parallelism: None,
+            progress: None,
+        }
+    }
+
+    /// Returns the score required to beat a blind in the current ante
+    ///
+    /// Includes the deck's blind size multiplier (x2 for Plasma Deck), and
+    /// the boss blind's size for the Boss blind (The Wall, Violet Vessel)
+    /// unless a joker disables the boss (Chicot).
+    pub fn blind_requirement(&self, blind_type: BlindType) -> u64 {
+        let boss = self
+            .boss_blind
+            .filter(|_| !self.jokers.iter().any(|joker| joker.kind.disables_boss_blind()));
+        let base = match (blind_type, boss) {
+            (BlindType::Boss, Some(boss)) => boss_requirement(self.ante, boss, self.stake),
+            _ => blind_requirement(self.ante, blind_type, self.stake),
+        };
+        (base as f64 * self.deck_type.modifiers().blind_size_multiplier) as u64
+    }
+}
+
+impl Default for GameRunState {
+    fn default() -> Self {
+        Self::new(DeckType::default(), Stake::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::card::{Rank, Suit};
+    use crate::core::hand::Hand;
+    use crate::core::joker::JokerKind;
+
+    #[test]
+    fn test_new_run_state() {
+        let run = GameRunState::new(DeckType::Red, Stake::Blue);
+        assert_eq!(run.deck.len(), 52);
+        assert_eq!(run.hands_remaining, 4);
+        assert_eq!(run.discards_remaining, 3); // Red +1, Blue Stake -1
+        assert_eq!(run.money, 4);
+
+        let plasma = GameRunState::new(DeckType::Plasma, Stake::White);
+        assert_eq!(plasma.blind_requirement(BlindType::Small), 600);
+        assert_eq!(plasma.simulation_config(10, None).hand_size, 8);
+    }
+
+    #[test]
+    fn test_boss_blind_scales_requirement_and_scoring() {
+        let run = GameRunState::default().with_boss_blind(Some(BossBlind::TheWall));
+        assert_eq!(run.blind_requirement(BlindType::Boss), 1_200);
+        assert_eq!(run.blind_requirement(BlindType::Small), 300);
+
+        let run = run.with_boss_blind(Some(BossBlind::TheFlint));
+        assert_eq!(run.blind_requirement(BlindType::Boss), 600);
+        let hand = Hand::new(vec![Card::new(Rank::Ace, Suit::Hearts)]);
+        assert_eq!(run.calculator().calculate(&hand, &[]).breakdown.base_chips, 3);
+    }
+
+    #[test]
+    fn test_chicot_disables_boss_blind() {
+        let run = GameRunState::default()
+            .with_boss_blind(Some(BossBlind::TheWall))
+            .with_jokers(vec![Joker::new(JokerKind::Chicot)]);
+        assert_eq!(run.blind_requirement(BlindType::Boss), 600);
+
+        let run = run.with_boss_blind(Some(BossBlind::TheFlint));
+        assert_eq!(run.calculator().boss_blind(), None);
+        let hand = Hand::new(vec![Card::new(Rank::Ace, Suit::Hearts)]);
+        assert_eq!(run.calculator().calculate(&hand, &[]).breakdown.base_chips, 5);
+    }
+
+    #[test]
+    fn test_run_state_calculator() {
+        let run = GameRunState::default().with_jokers(vec![Joker::new(JokerKind::Joker)]);
+        let hand = Hand::new(vec![Card::new(Rank::Ace, Suit::Hearts)]);
+
+        let result = run.calculator().calculate(&hand, &[]);
+
+        assert_eq!(result.breakdown.joker_mult, 4);
+    }
+}
@@ -0,0 +1,163 @@
+//! Overflow-safe score values
+//!
+//! Endless-mode builds easily exceed `u64`, so scores are stored as `f64`.
+//! Anything that overflows (or becomes NaN) is capped at infinity, which
+//! Balatro displays as "naneinf".
+
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Sub};
+
+/// Scores at or above this value are displayed in e-notation
+pub const E_NOTATION_THRESHOLD: f64 = 1e11;
+
+/// A non-negative score that saturates at infinity instead of overflowing
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Score(f64);
+
+impl Score {
+    /// A score of zero
+    pub const ZERO: Score = Score(0.0);
+
+    /// The "naneinf" cap reached when a score overflows
+    pub const NANEINF: Score = Score(f64::INFINITY);
+
+    /// Creates a score, rounding down and capping overflow/NaN at infinity
+    pub fn new(value: f64) -> Self {
+        if value.is_nan() || value == f64::INFINITY {
+            Self::NANEINF
+        } else {
+            Self(value.max(0.0).floor())
+        }
+    }
+
+    /// Returns the raw score value
+    pub fn value(&self) -> f64 {
+        self.0
+    }
+
+    /// Returns true if the score overflowed to the "naneinf" cap
+    pub fn is_naneinf(&self) -> bool {
+        self.0.is_infinite()
+    }
+
+    /// Converts to `u64`, saturating at `u64::MAX`
+    pub fn to_u64(&self) -> u64 {
+        self.0 as u64
+    }
+}
+
+impl From<u64> for Score {
+    fn from(value: u64) -> Self {
+        Self::new(value as f64)
+    }
+}
+
+impl PartialEq for Score {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Score {}
+
+impl PartialOrd for Score {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Score {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Add for Score {
+    type Output = Score;
+
+    fn add(self, rhs: Score) -> Score {
+        Score::new(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Score {
+    type Output = Score;
+
+    /// Subtracts, clamping at zero
+    fn sub(self, rhs: Score) -> Score {
+        if self.is_naneinf() {
+            return self;
+        }
+        Score::new(self.0 - rhs.0)
+    }
+}
+
+impl fmt::Display for Score {
+    /// Formats like Balatro: `1,234,567` below 1e11, `1.23e11` above,
+    /// and `naneinf` once the score has overflowed
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&format_score(self.0))
+    }
+}
+
+/// Formats a score value the way Balatro displays it
+pub fn format_score(value: f64) -> String {
+    if !value.is_finite() {
+        return "naneinf".to_string();
+    }
+
+    if value >= E_NOTATION_THRESHOLD {
+        let mut exponent = value.log10().floor() as i32;
+        let mut mantissa = value / 10f64.powi(exponent);
+        // Rounding can carry the mantissa to 10.00
+        if (mantissa * 100.0).round() >= 1000.0 {
+            mantissa /= 10.0;
+            exponent += 1;
+        }
+        return format!("{:.2}e{}", mantissa, exponent);
+    }
+
+    let digits = format!("{}", value.floor() as u64);
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_score() {
+        assert_eq!(format_score(0.0), "0");
+        assert_eq!(format_score(1536.0), "1,536");
+        assert_eq!(format_score(1_234_567.0), "1,234,567");
+        assert_eq!(format_score(1.234e11), "1.23e11");
+        assert_eq!(format_score(9.999e12), "1.00e13");
+        assert_eq!(format_score(f64::INFINITY), "naneinf");
+    }
+
+    #[test]
+    fn test_score_saturates() {
+        assert!(Score::new(f64::MAX * 10.0).is_naneinf());
+        assert!(Score::new(f64::NAN).is_naneinf());
+        assert_eq!(Score::new(12.7), Score::from(12));
+        assert_eq!(Score::NANEINF - Score::from(5), Score::NANEINF);
+        assert_eq!(Score::from(3) - Score::from(5), Score::ZERO);
+    }
+
+    #[test]
+    fn test_score_ordering() {
+        let mut scores = vec![Score::from(5), Score::NANEINF, Score::from(1)];
+        scores.sort();
+        assert_eq!(scores, vec![Score::from(1), Score::from(5), Score::NANEINF]);
+    }
+}
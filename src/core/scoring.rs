@@ -3,19 +3,112 @@
 //! This module handles the complex scoring logic for Balatro,
 //! including base hand values, card bonuses, and joker effects.
 
-use super::card::Card;
+use super::blind::BossBlind;
+use super::card::{Card, Edition, Enhancement, Rank, Seal, Suit};
+use super::consumable::{self, ConsumableEvent};
 use super::hand::{Hand, HandType};
-use super::joker::Joker;
+use super::hand_levels::HandLevels;
+use super::joker::{Joker, JokerEdition, JokerKind};
+use super::rules::ScoringRules;
+use super::score::Score;
+use rand::{Rng, RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Chance that a scored Glass card shatters after the hand (1 in 4)
+pub const GLASS_BREAK_CHANCE: f64 = 0.25;
+
+/// Chance that a scored Lucky card gives +20 mult (1 in 5)
+pub const LUCKY_MULT_CHANCE: f64 = 1.0 / 5.0;
+
+/// Chance that a scored Lucky card gives $20 (1 in 15)
+pub const LUCKY_MONEY_CHANCE: f64 = 1.0 / 15.0;
+
+/// Chance that Business Card pays $2 for a scored face card (1 in 2)
+pub const BUSINESS_CARD_CHANCE: f64 = 0.5;
+
+/// Chance that 8 Ball creates a Tarot card for a scored 8 (1 in 4)
+pub const EIGHT_BALL_CHANCE: f64 = 0.25;
+
+/// Chance that Space Joker upgrades the played hand (1 in 4)
+pub const SPACE_JOKER_CHANCE: f64 = 0.25;
+
+/// Chance that Bloodstone gives x1.5 mult for a scored Heart (1 in 2)
+pub const BLOODSTONE_CHANCE: f64 = 0.5;
+
+/// Chance that Reserved Parking pays $1 for a held face card (1 in 2)
+pub const RESERVED_PARKING_CHANCE: f64 = 0.5;
+
+/// How chance-based effects (Lucky cards, etc.) are resolved during scoring
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProbabilityMode {
+    /// Each effect contributes its value weighted by its probability
+    #[default]
+    Expected,
+    /// Each effect is rolled with a ChaCha RNG seeded with the given value
+    Sampled(u64),
+    /// Every effect triggers
+    Optimistic,
+    /// No effect triggers
+    Pessimistic,
+}
+
+/// Resolves chance-based effects according to a probability mode
+struct Roller<'a> {
+    mode: ProbabilityMode,
+    rng: Option<&'a mut dyn RngCore>,
+}
+
+impl Roller<'_> {
+    /// Returns the weight (0.0 to 1.0) an effect with chance `p` contributes
+    fn weight(&mut self, p: f64) -> f64 {
+        match self.mode {
+            ProbabilityMode::Expected => p,
+            ProbabilityMode::Optimistic => 1.0,
+            ProbabilityMode::Pessimistic => 0.0,
+            ProbabilityMode::Sampled(_) => match self.rng.as_mut() {
+                Some(rng) => f64::from(u8::from(rng.gen_bool(p))),
+                None => 0.0,
+            },
+        }
+    }
+}
 
 /// Result of a scoring calculation
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ScoreResult {
     pub hand_type: HandType,
     pub chips: u32,
-    pub mult: u32,
-    pub score: u64,
+    pub mult: f64,
+    pub score: Score,
     pub breakdown: ScoreBreakdown,
+    /// Number of scored Glass cards, each of which may shatter
+    pub glass_cards_scored: u32,
+    /// Money earned by the play and by the cards and jokers at end of round
+    pub money_delta: f64,
+    /// The part of `money_delta` paid at end of round (held Gold cards,
+    /// Golden Joker, Rocket), which a round earns once, after its last play
+    pub round_money: f64,
+    /// Consumables created by Blue seals held at end of round
+    pub consumable_events: Vec<ConsumableEvent>,
+    /// Tarot cards created while scoring (8 Ball), weighted by probability
+    /// unless chance effects are sampled
+    pub tarots_created: f64,
+}
+
+impl ScoreResult {
+    /// Expected number of Glass cards destroyed by this play
+    pub fn expected_glass_breaks(&self) -> f64 {
+        self.glass_cards_scored as f64 * GLASS_BREAK_CHANCE
+    }
+
+    /// Rolls the 1-in-4 destruction chance for each scored Glass card
+    pub fn sample_glass_breaks<R: Rng + ?Sized>(&self, rng: &mut R) -> u32 {
+        (0..self.glass_cards_scored)
+            .filter(|_| rng.gen_bool(GLASS_BREAK_CHANCE))
+            .count() as u32
+    }
 }
 
 /// Detailed breakdown of how the score was calculated
@@ -25,46 +118,539 @@ pub struct ScoreBreakdown {
     pub base_mult: u32,
     pub card_chips: u32,
     pub card_mult: u32,
+    pub card_mult_multiplier: f64,
+    pub held_mult_multiplier: f64,
     pub joker_chips: i32,
     pub joker_mult: i32,
-    pub joker_mult_multiplier: f32,
+    pub joker_mult_multiplier: f64,
+    /// Every x-mult applied, in the order it was applied
+    pub mult_steps: Vec<MultStep>,
+    /// Every chip, mult, and x-mult contribution, in the order it was applied
+    pub events: Vec<ScoreEvent>,
+}
+
+/// A single contribution to the score (e.g., "+3 mult: Greedy Joker on 7♦")
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScoreEvent {
+    /// What made the contribution (e.g., "A♥ base", "Polychrome joker")
+    pub source: String,
+    /// The contribution made
+    pub effect: ScoreEffect,
+    /// The part of the scoring pipeline that made it
+    #[serde(default)]
+    pub stage: ScoreStage,
+}
+
+/// A part of the scoring pipeline, in the order they apply
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum ScoreStage {
+    /// The hand type's base chips and mult
+    #[default]
+    Hand,
+    /// One trigger of the scoring card at a position among the scoring
+    /// cards; `trigger` is 0 for the first and counts retriggers after it
+    Played { position: usize, card: Card, trigger: u32 },
+    /// Cards held in hand
+    Held,
+    /// Jokers, left to right
+    Jokers,
+}
+
+/// The kind and amount of a score contribution
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ScoreEffect {
+    /// Chips added (negative for chip penalties)
+    Chips(i32),
+    /// Mult added
+    Mult(f64),
+    /// Factor the running mult was multiplied by
+    XMult(f64),
+}
+
+impl std::fmt::Display for ScoreEvent {
+    /// Formats the event as "+11 chips: A♥ base" or "x1.5: Polychrome card"
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.effect {
+            ScoreEffect::Chips(chips) => write!(f, "{:+} chips: {}", chips, self.source),
+            ScoreEffect::Mult(mult) => write!(f, "{:+} mult: {}", mult, self.source),
+            ScoreEffect::XMult(multiplier) => write!(f, "x{}: {}", multiplier, self.source),
+        }
+    }
+}
+
+/// The breakdown's x-mult steps and event log, kept only when traced
+///
+/// Naming every contribution allocates, so plays scored only to be ranked
+/// skip it.
+#[derive(Default)]
+struct Trace {
+    enabled: bool,
+    /// Stage new events are recorded in
+    stage: ScoreStage,
+    steps: Vec<MultStep>,
+    events: Vec<ScoreEvent>,
+}
+
+impl Trace {
+    fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Self::default()
+        }
+    }
+
+    /// Records a non-zero contribution, naming its source only if traced
+    fn record(&mut self, source: impl FnOnce() -> String, effect: ScoreEffect) {
+        let is_zero = match effect {
+            ScoreEffect::Chips(chips) => chips == 0,
+            ScoreEffect::Mult(mult) => mult == 0.0,
+            ScoreEffect::XMult(multiplier) => multiplier == 1.0,
+        };
+        if self.enabled && !is_zero {
+            self.events.push(ScoreEvent {
+                source: source(),
+                effect,
+                stage: self.stage.clone(),
+            });
+        }
+    }
+
+    /// Records an x-mult step
+    fn step(&mut self, source: &str, multiplier: f64) {
+        if self.enabled {
+            self.steps.push(MultStep {
+                source: source.to_string(),
+                multiplier,
+            });
+        }
+    }
+
+    /// Records later events in the given stage, built only if traced
+    fn enter(&mut self, stage: impl FnOnce() -> ScoreStage) {
+        if self.enabled {
+            self.stage = stage();
+        }
+    }
+
+    /// Appends another trace's steps and events
+    fn extend(&mut self, other: Trace) {
+        self.steps.extend(other.steps);
+        self.events.extend(other.events);
+    }
+}
+
+/// A single multiplicative step applied to the running mult
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MultStep {
+    /// What applied the x-mult (e.g., "Glass card", "Baron")
+    pub source: String,
+    /// The factor the mult was multiplied by
+    pub multiplier: f64,
+}
+
+/// Accumulated contributions from the played cards
+struct CardBonuses {
+    /// Chips added by the cards
+    chips: u32,
+    /// Total +mult added by the cards
+    mult: u32,
+    /// Product of all x-mult applied by the cards
+    mult_multiplier: f64,
+    /// Mult after the cards, including the hand's base mult
+    running_mult: f64,
+    /// Chips added by jokers triggering on scored cards
+    joker_chips: u32,
+    /// Mult added by jokers triggering on scored cards
+    joker_mult: u32,
+    /// X-mult steps and contributions made by the cards, in order
+    trace: Trace,
+    /// Money earned while scoring the cards
+    money: f64,
+    /// Tarot cards created while scoring the cards
+    tarots: f64,
+}
+
+impl CardBonuses {
+    /// Adds chips from the card itself
+    fn add_chips(&mut self, source: &str, card: &Card, chips: u32) {
+        self.chips += chips;
+        let source = || format!("{} on {}", source, card);
+        self.trace.record(source, ScoreEffect::Chips(chips as i32));
+    }
+
+    /// Adds flat mult
+    fn add_mult(&mut self, source: &str, card: &Card, mult: u32) {
+        self.mult += mult;
+        self.running_mult += mult as f64;
+        let source = || format!("{} on {}", source, card);
+        self.trace.record(source, ScoreEffect::Mult(mult as f64));
+    }
+
+    /// Multiplies the running mult, recording the step
+    fn times_mult(&mut self, source: &str, card: &Card, multiplier: f64) {
+        self.mult_multiplier *= multiplier;
+        self.running_mult *= multiplier;
+        self.trace.step(source, multiplier);
+        let source = || format!("{} on {}", source, card);
+        self.trace.record(source, ScoreEffect::XMult(multiplier));
+    }
+
+    /// Adds chips from a joker triggering on a scored card
+    fn add_joker_chips(&mut self, source: &str, card: &Card, chips: u32) {
+        self.joker_chips += chips;
+        let source = || format!("{} on {}", source, card);
+        self.trace.record(source, ScoreEffect::Chips(chips as i32));
+    }
+
+    /// Adds mult from a joker triggering on a scored card
+    fn add_joker_mult(&mut self, source: &str, card: &Card, mult: u32) {
+        self.joker_mult += mult;
+        self.running_mult += mult as f64;
+        let source = || format!("{} on {}", source, card);
+        self.trace.record(source, ScoreEffect::Mult(mult as f64));
+    }
+}
+
+/// Accumulated contributions from the joker pass
+struct JokerBonuses {
+    /// Chips added by jokers
+    chips: i32,
+    /// Total +mult added by jokers
+    mult: i32,
+    /// Product of all x-mult applied by jokers
+    mult_multiplier: f64,
+    /// Mult after the jokers, carried over from the earlier passes
+    running_mult: f64,
+    /// X-mult steps and contributions made by jokers, in order
+    trace: Trace,
+}
+
+impl JokerBonuses {
+    /// Adds flat chips
+    fn add_chips(&mut self, source: &str, chips: i32) {
+        self.chips += chips;
+        self.trace.record(|| source.to_string(), ScoreEffect::Chips(chips));
+    }
+
+    /// Adds flat mult
+    fn add_mult(&mut self, source: &str, mult: i32) {
+        self.mult += mult;
+        self.running_mult += mult as f64;
+        self.trace.record(|| source.to_string(), ScoreEffect::Mult(mult as f64));
+    }
+
+    /// Multiplies the running mult, recording the step
+    fn times_mult(&mut self, source: &str, multiplier: f64) {
+        self.mult_multiplier *= multiplier;
+        self.running_mult *= multiplier;
+        self.trace.step(source, multiplier);
+        self.trace.record(|| source.to_string(), ScoreEffect::XMult(multiplier));
+    }
+}
+
+/// The cards of a play, as seen by jokers that depend on the whole hand
+struct Play<'a> {
+    /// Every played card
+    hand: &'a Hand,
+    /// The played cards that score
+    scoring: &'a [&'a Card],
+    /// The cards remaining in hand
+    held: &'a [Card],
 }
 
 /// The main scoring calculator
 pub struct ScoreCalculator {
     jokers: Vec<Joker>,
+    rules: ScoringRules,
+    hand_levels: HandLevels,
+    probability_mode: ProbabilityMode,
+    plasma_balance: bool,
+    boss_blind: Option<BossBlind>,
+    deck: Option<Vec<Card>>,
+    /// Behind a mutex so calculators can be shared between threads; the
+    /// simulator reseeds it from each run's seed so runs roll their own
+    rng: Mutex<ChaCha8Rng>,
+}
+
+impl Clone for ScoreCalculator {
+    fn clone(&self) -> Self {
+        Self {
+            jokers: self.jokers.clone(),
+            rules: self.rules,
+            hand_levels: self.hand_levels.clone(),
+            probability_mode: self.probability_mode,
+            plasma_balance: self.plasma_balance,
+            boss_blind: self.boss_blind,
+            deck: self.deck.clone(),
+            rng: Mutex::new(self.rng.lock().expect("rng lock poisoned").clone()),
+        }
+    }
 }
 
 impl ScoreCalculator {
     /// Creates a new score calculator with the given jokers
     pub fn new(jokers: Vec<Joker>) -> Self {
-        Self { jokers }
+        Self {
+            rules: ScoringRules::from_jokers(&jokers),
+            jokers,
+            hand_levels: HandLevels::new(),
+            probability_mode: ProbabilityMode::Expected,
+            plasma_balance: false,
+            boss_blind: None,
+            deck: None,
+            rng: Mutex::new(ChaCha8Rng::seed_from_u64(0)),
+        }
+    }
+
+    /// Returns the jokers in scoring order
+    pub fn jokers(&self) -> &[Joker] {
+        &self.jokers
+    }
+
+    /// Replaces the jokers (in scoring order), re-deriving the rule modifiers
+    pub fn with_jokers(mut self, jokers: Vec<Joker>) -> Self {
+        self.rules = ScoringRules::from_jokers(&jokers);
+        self.jokers = jokers;
+        self
+    }
+
+    /// Overrides the rule modifiers derived from the jokers
+    pub fn with_rules(mut self, rules: ScoringRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Returns the rule modifiers used for evaluation and scoring
+    pub fn rules(&self) -> &ScoringRules {
+        &self.rules
+    }
+
+    /// Sets the hand levels used for base chips and mult
+    pub fn with_hand_levels(mut self, hand_levels: HandLevels) -> Self {
+        self.hand_levels = hand_levels;
+        self
+    }
+
+    /// Returns the hand levels used for base chips and mult
+    pub fn hand_levels(&self) -> &HandLevels {
+        &self.hand_levels
+    }
+
+    /// Sets how chance-based effects are resolved
+    pub fn with_probability_mode(mut self, mode: ProbabilityMode) -> Self {
+        if let ProbabilityMode::Sampled(seed) = mode {
+            self.rng = Mutex::new(ChaCha8Rng::seed_from_u64(seed));
+        }
+        self.probability_mode = mode;
+        self
+    }
+
+    /// Returns the probability mode used by this calculator
+    pub fn probability_mode(&self) -> ProbabilityMode {
+        self.probability_mode
+    }
+
+    /// Sets the full deck, used by jokers that count cards in the deck
+    pub fn with_deck(mut self, deck: Vec<Card>) -> Self {
+        self.deck = Some(deck);
+        self
+    }
+
+    /// Returns the full deck, if one was provided
+    pub fn deck(&self) -> Option<&[Card]> {
+        self.deck.as_deref()
+    }
+
+    /// Returns the jokers whose effect depends on the deck when no deck is set
+    ///
+    /// These jokers score as if the deck had no enhanced cards.
+    pub fn jokers_missing_deck(&self) -> Vec<&Joker> {
+        if self.deck.is_some() {
+            return Vec::new();
+        }
+        self.jokers.iter().filter(|joker| joker.kind.needs_deck()).collect()
+    }
+
+    /// Returns the jokers that need a per-round target but have none set
+    ///
+    /// These jokers have no effect until a target is pinned.
+    pub fn jokers_missing_target(&self) -> Vec<&Joker> {
+        self.jokers
+            .iter()
+            .filter(|joker| {
+                joker.kind.needs_target()
+                    && (joker.target_suit.is_none()
+                        || (joker.kind == JokerKind::TheIdol && joker.target_rank.is_none()))
+            })
+            .collect()
+    }
+
+    /// Averages chips and mult before the final multiplication (Plasma Deck)
+    pub fn with_plasma_balance(mut self, plasma_balance: bool) -> Self {
+        self.plasma_balance = plasma_balance;
+        self
+    }
+
+    /// Sets the active boss blind, whose scoring effects are applied
+    pub fn with_boss_blind(mut self, boss_blind: Option<BossBlind>) -> Self {
+        self.boss_blind = boss_blind;
+        self
+    }
+
+    /// Returns the active boss blind, if any: none while a joker disables
+    /// it (Chicot)
+    pub fn boss_blind(&self) -> Option<BossBlind> {
+        self.boss_blind
+            .filter(|_| !self.jokers.iter().any(|joker| joker.kind.disables_boss_blind()))
+    }
+
+    /// Returns whether the active boss blind debuffs the card
+    pub fn is_debuffed(&self, card: &Card) -> bool {
+        self.boss_blind().is_some_and(|boss| boss.debuffs(card))
     }
 
     /// Calculates the score for a given hand
-    pub fn calculate(&self, hand: &Hand) -> ScoreResult {
-        let hand_type = hand.evaluate();
+    ///
+    /// `held` contains the cards that remain in hand after the play, which
+    /// drive held-in-hand effects such as Steel cards and Baron.
+    pub fn calculate(&self, hand: &Hand, held: &[Card]) -> ScoreResult {
+        self.calculate_traced(hand, held, true)
+    }
+
+    /// Calculates the score without the breakdown's mult steps and events
+    ///
+    /// Every other field matches [`ScoreCalculator::calculate`]. Much
+    /// faster when many plays are scored only to be ranked.
+    pub fn calculate_quick(&self, hand: &Hand, held: &[Card]) -> ScoreResult {
+        self.calculate_traced(hand, held, false)
+    }
+
+    /// Calculates the score with the calculator's probability mode
+    fn calculate_traced(&self, hand: &Hand, held: &[Card], traced: bool) -> ScoreResult {
+        match self.probability_mode {
+            ProbabilityMode::Sampled(_) => {
+                let mut rng = self.rng.lock().expect("rng lock poisoned");
+                let mut roller = Roller {
+                    mode: self.probability_mode,
+                    rng: Some(&mut *rng),
+                };
+                self.score(hand, held, &mut roller, traced)
+            }
+            mode => self.score(hand, held, &mut Roller { mode, rng: None }, traced),
+        }
+    }
+
+    /// Calculates the score, sampling chance-based effects with the given RNG
+    ///
+    /// Used by the simulator to roll actual outcomes for a play that was
+    /// chosen by expected value.
+    pub fn calculate_sampled<R: RngCore>(
+        &self,
+        hand: &Hand,
+        held: &[Card],
+        rng: &mut R,
+    ) -> ScoreResult {
+        let mut roller = Roller {
+            mode: ProbabilityMode::Sampled(0),
+            rng: Some(rng),
+        };
+        self.score(hand, held, &mut roller, true)
+    }
+
+    /// Runs the scoring pipeline with the given chance resolver, recording
+    /// the breakdown's steps and events if `traced`
+    fn score(&self, hand: &Hand, held: &[Card], roller: &mut Roller, traced: bool) -> ScoreResult {
+        // Only cards that form the hand score, unless Splash is active
+        let (hand_type, scoring_cards) = hand.evaluate_scoring(&self.rules);
 
-        // Base values from hand type
-        let base_chips = hand_type.base_chips();
-        let base_mult = hand_type.base_mult();
+        // Base values from hand type at its current level, less any levels
+        // The Arm takes, plus any levels Space Joker adds before scoring
+        let mut hand_levels = std::borrow::Cow::Borrowed(&self.hand_levels);
+        let penalty = self.boss_blind().map_or(0, |boss| boss.level_penalty());
+        if penalty > 0 {
+            let level = hand_levels.level(hand_type).saturating_sub(penalty);
+            hand_levels.to_mut().set_level(hand_type, level);
+        }
+        let levels_gained: f64 = (0..self.count_abilities(&JokerKind::SpaceJoker))
+            .map(|_| roller.weight(SPACE_JOKER_CHANCE))
+            .sum();
+        let mut base_chips = hand_levels.chips(hand_type)
+            + (hand_type.level_chips() as f64 * levels_gained).round() as u32;
+        let mut base_mult =
+            hand_levels.mult(hand_type) as f64 + hand_type.level_mult() as f64 * levels_gained;
+
+        // The Flint halves base chips and mult, rounding half up (mult at least 1)
+        if self.boss_blind().is_some_and(|boss| boss.halves_base()) {
+            base_chips = base_chips.div_ceil(2);
+            base_mult = (base_mult / 2.0 + 0.5).floor().max(1.0);
+        }
+        let mut trace = Trace::new(traced);
+        let hand_name = || format!("{:?} base", hand_type);
+        trace.record(hand_name, ScoreEffect::Chips(base_chips as i32));
+        trace.record(hand_name, ScoreEffect::Mult(base_mult));
 
         // Calculate card contributions
-        let (card_chips, card_mult) = self.calculate_card_bonuses(&hand.cards);
+        let CardBonuses {
+            chips: card_chips,
+            mult: card_mult,
+            mult_multiplier: card_mult_multiplier,
+            running_mult: card_total_mult,
+            joker_chips: scored_joker_chips,
+            joker_mult: scored_joker_mult,
+            trace: card_trace,
+            money: card_money,
+            tarots: tarots_created,
+        } = self.calculate_card_bonuses(&scoring_cards, base_mult, roller, traced);
+
+        // Calculate held-in-hand contributions (applied after played cards)
+        trace.extend(card_trace);
+        trace.enter(|| ScoreStage::Held);
+        let held_mult_multiplier = self.calculate_held_multiplier(held, &mut trace);
 
-        // Calculate joker contributions
-        let (joker_chips, joker_mult, joker_mult_multiplier) =
-            self.calculate_joker_bonuses(hand, hand_type);
+        // Calculate joker contributions, left to right
+        let held_mult = card_total_mult * held_mult_multiplier;
+        let JokerBonuses {
+            chips: independent_chips,
+            mult: independent_mult,
+            mult_multiplier: joker_mult_multiplier,
+            running_mult: joker_total_mult,
+            trace: joker_trace,
+        } = self.calculate_joker_bonuses(
+            &Play {
+                hand,
+                scoring: &scoring_cards,
+                held,
+            },
+            held_mult,
+            traced,
+        );
+        trace.extend(joker_trace);
 
         // Apply all modifiers
-        let total_chips = (base_chips + card_chips).saturating_add_signed(joker_chips);
-        let total_mult = (base_mult + card_mult).saturating_add_signed(joker_mult);
+        let mut total_chips = (base_chips + card_chips + scored_joker_chips)
+            .saturating_add_signed(independent_chips);
+        let mut final_mult = joker_total_mult.max(0.0);
 
-        // Apply multiplicative joker effects
-        let final_mult = (total_mult as f32 * joker_mult_multiplier) as u32;
+        // Plasma Deck: chips and mult are both set to their average
+        if self.plasma_balance {
+            let balanced = (total_chips as f64 + final_mult) / 2.0;
+            total_chips = balanced as u32;
+            final_mult = balanced;
+        }
+        let joker_chips = scored_joker_chips as i32 + independent_chips;
+        let joker_mult = scored_joker_mult as i32 + independent_mult;
+
+        // Final score: chips * mult, rounded down and capped at naneinf
+        let score = if self.plasma_balance {
+            Score::new(final_mult * final_mult)
+        } else {
+            Score::new(total_chips as f64 * final_mult)
+        };
 
-        // Final score: chips * mult
-        let score = (total_chips as u64) * (final_mult as u64);
+        // Money from scored cards, held cards, and end-of-round jokers
+        let (held_money, held_round_money) = self.calculate_held_money(held, roller);
+        let round_money = held_round_money + self.joker_money();
+        let money_delta = card_money + held_money + round_money;
 
         ScoreResult {
             hand_type,
@@ -73,73 +659,488 @@ impl ScoreCalculator {
             score,
             breakdown: ScoreBreakdown {
                 base_chips,
-                base_mult,
+                base_mult: base_mult.round() as u32,
                 card_chips,
                 card_mult,
+                card_mult_multiplier,
+                held_mult_multiplier,
                 joker_chips,
                 joker_mult,
                 joker_mult_multiplier,
+                mult_steps: trace.steps,
+                events: trace.events,
             },
+            glass_cards_scored: scoring_cards
+                .iter()
+                .filter(|card| card.enhancement == Enhancement::Glass && !self.is_debuffed(card))
+                .count() as u32,
+            money_delta,
+            round_money,
+            consumable_events: consumable::held_events(held, hand_type),
+            tarots_created,
         }
     }
 
-    /// Calculates chip and mult bonuses from cards
-    fn calculate_card_bonuses(&self, cards: &[Card]) -> (u32, u32) {
-        let mut chips = 0u32;
-        let mut mult = 0u32;
+    /// Scores played cards left to right, starting from the hand's base mult
+    ///
+    /// Each card applies its chips, then +mult, then x-mult (Glass), then its
+    /// edition (Foil, Holographic, Polychrome), so x-mult only compounds the
+    /// mult accumulated so far. Retriggered cards (Red seal, Sock and Buskin)
+    /// repeat their whole scoring step, including on-scored jokers.
+    fn calculate_card_bonuses(
+        &self,
+        cards: &[&Card],
+        base_mult: f64,
+        roller: &mut Roller,
+        traced: bool,
+    ) -> CardBonuses {
+        let mut bonuses = CardBonuses {
+            chips: 0,
+            mult: 0,
+            mult_multiplier: 1.0,
+            running_mult: base_mult,
+            joker_chips: 0,
+            joker_mult: 0,
+            trace: Trace::new(traced),
+            money: 0.0,
+            tarots: 0.0,
+        };
+        let sock_and_buskin = self.count_abilities(&JokerKind::SockAndBuskin);
+        let first_face = cards
+            .iter()
+            .position(|card| card.is_face_card(&self.rules) && !self.is_debuffed(card));
 
-        for card in cards {
-            // Base card value
-            chips += card.base_chips();
+        for (position, card) in cards.iter().enumerate() {
+            // Debuffed cards still form the hand but add nothing
+            if self.is_debuffed(card) {
+                continue;
+            }
+            let is_face = card.is_face_card(&self.rules);
+            let red_seal = usize::from(card.seal == Some(Seal::Red));
+            let triggers = 1 + red_seal + if is_face { sock_and_buskin } else { 0 };
 
-            // Enhancement bonuses
-            match card.enhancement {
-                super::card::Enhancement::Bonus => chips += 30,
-                super::card::Enhancement::Mult => mult += 4,
-                super::card::Enhancement::Stone => chips += 50,
-                _ => {} // Other enhancements handled elsewhere
+            for trigger in 0..triggers {
+                bonuses.trace.enter(|| ScoreStage::Played {
+                    position,
+                    card: (*card).clone(),
+                    trigger: trigger as u32,
+                });
+                self.score_card(card, first_face == Some(position), &mut bonuses, roller);
             }
+        }
+
+        bonuses
+    }
 
-            // Edition bonuses
-            match card.edition {
-                super::card::Edition::Foil => chips += 50,
-                super::card::Edition::Holographic => mult += 10,
-                _ => {} // Polychrome is multiplicative, handled separately
+    /// Applies one trigger of a scored card, followed by on-scored jokers
+    fn score_card(
+        &self,
+        card: &Card,
+        is_first_face: bool,
+        bonuses: &mut CardBonuses,
+        roller: &mut Roller,
+    ) {
+        // Base card value
+        bonuses.chips += card.base_chips();
+        let base = || format!("{} base", card);
+        bonuses.trace.record(base, ScoreEffect::Chips(card.base_chips() as i32));
+
+        // Enhancement bonuses
+        match card.enhancement {
+            Enhancement::Bonus => bonuses.add_chips("Bonus card", card, 30),
+            Enhancement::Mult => bonuses.add_mult("Mult card", card, 4),
+            Enhancement::Stone => bonuses.add_chips("Stone card", card, 50),
+            Enhancement::Glass => bonuses.times_mult("Glass card", card, 2.0),
+            Enhancement::Lucky => {
+                let mult = (20.0 * roller.weight(LUCKY_MULT_CHANCE)).round() as u32;
+                bonuses.add_mult("Lucky card", card, mult);
+                bonuses.money += 20.0 * roller.weight(LUCKY_MONEY_CHANCE);
             }
+            _ => {} // Other enhancements handled elsewhere
+        }
+
+        // Edition bonuses
+        match card.edition {
+            Edition::Foil => bonuses.add_chips("Foil card", card, 50),
+            Edition::Holographic => bonuses.add_mult("Holographic card", card, 10),
+            Edition::Polychrome => bonuses.times_mult("Polychrome card", card, 1.5),
+            _ => {}
+        }
+
+        if card.seal == Some(Seal::Gold) {
+            bonuses.money += 3.0;
+        }
+
+        // Jokers that trigger on each scored card
+        for index in 0..self.jokers.len() {
+            self.apply_on_scored(index, card, is_first_face, bonuses, roller);
         }
+    }
+
+    /// Applies a joker's effect for a single scored card
+    fn apply_on_scored(
+        &self,
+        index: usize,
+        card: &Card,
+        is_first_face: bool,
+        bonuses: &mut CardBonuses,
+        roller: &mut Roller,
+    ) {
+        let Some(ability) = self.resolve_ability(index) else {
+            return;
+        };
+        let is_face = card.is_face_card(&self.rules);
+        let rank = card.has_rank().then_some(card.rank);
+        let is_rank = |ranks: &[Rank]| rank.is_some_and(|rank| ranks.contains(&rank));
+        let name = ability.kind.name();
 
-        (chips, mult)
+        match ability.kind {
+            JokerKind::GreedyJoker if card.is_suit(Suit::Diamonds) => {
+                bonuses.add_joker_mult(name, card, 3);
+            }
+            JokerKind::LustyJoker if card.is_suit(Suit::Hearts) => {
+                bonuses.add_joker_mult(name, card, 3);
+            }
+            JokerKind::WrathfulJoker if card.is_suit(Suit::Spades) => {
+                bonuses.add_joker_mult(name, card, 3);
+            }
+            JokerKind::GluttonousJoker if card.is_suit(Suit::Clubs) => {
+                bonuses.add_joker_mult(name, card, 3);
+            }
+            JokerKind::ScaryFace if is_face => bonuses.add_joker_chips(name, card, 30),
+            JokerKind::SmileyFace if is_face => bonuses.add_joker_mult(name, card, 5),
+            JokerKind::Photograph if is_first_face => bonuses.times_mult(name, card, 2.0),
+            JokerKind::EvenSteven if rank.is_some_and(|r| r.is_even()) => {
+                bonuses.add_joker_mult(name, card, 4);
+            }
+            JokerKind::OddTodd if rank.is_some_and(|r| r.is_odd()) => {
+                bonuses.add_joker_chips(name, card, 31);
+            }
+            JokerKind::Fibonacci if rank.is_some_and(|r| r.is_fibonacci()) => {
+                bonuses.add_joker_mult(name, card, 8);
+            }
+            JokerKind::Scholar if is_rank(&[Rank::Ace]) => {
+                bonuses.add_joker_chips(name, card, 20);
+                bonuses.add_joker_mult(name, card, 4);
+            }
+            JokerKind::WalkieTalkie if is_rank(&[Rank::Ten, Rank::Four]) => {
+                bonuses.add_joker_chips(name, card, 10);
+                bonuses.add_joker_mult(name, card, 4);
+            }
+            JokerKind::Triboulet if is_rank(&[Rank::King, Rank::Queen]) => {
+                bonuses.times_mult(name, card, 2.0);
+            }
+            JokerKind::TheIdol
+                if rank.is_some()
+                    && rank == ability.target_rank
+                    && ability.target_suit.is_some_and(|suit| card.is_suit(suit)) =>
+            {
+                bonuses.times_mult(name, card, 2.0);
+            }
+            JokerKind::AncientJoker
+                if ability.target_suit.is_some_and(|suit| card.is_suit(suit)) =>
+            {
+                bonuses.times_mult(name, card, 1.5);
+            }
+            JokerKind::EightBall if is_rank(&[Rank::Eight]) => {
+                bonuses.tarots += roller.weight(EIGHT_BALL_CHANCE);
+            }
+            JokerKind::Bloodstone if card.is_suit(Suit::Hearts) => {
+                let factor = 1.0 + 0.5 * roller.weight(BLOODSTONE_CHANCE);
+                if factor != 1.0 {
+                    bonuses.times_mult(name, card, factor);
+                }
+            }
+            JokerKind::BusinessCard if is_face => {
+                bonuses.money += 2.0 * roller.weight(BUSINESS_CARD_CHANCE);
+            }
+            _ => {}
+        }
     }
 
-    /// Calculates bonuses from jokers
+    /// Returns the ability a joker slot uses, following Blueprint copies
+    ///
+    /// Blueprint copies the joker to its right; a Blueprint at the end of
+    /// the list (or copying only Blueprints) has no ability.
+    fn resolve_ability(&self, index: usize) -> Option<&Joker> {
+        self.jokers[index..]
+            .iter()
+            .find(|joker| joker.kind != JokerKind::Blueprint)
+    }
+
+    /// Calculates the multiplicative bonus from cards held in hand
+    ///
+    /// Each held card triggers once, plus once more for a Red seal and once
+    /// per Mime. Every trigger applies Steel (x1.5) and, for Kings, each
+    /// Baron (x1.5).
+    fn calculate_held_multiplier(&self, held: &[Card], trace: &mut Trace) -> f64 {
+        let mime_count = self.count_abilities(&JokerKind::Mime);
+        let baron_count = self.count_abilities(&JokerKind::Baron);
+        let mut multiplier = 1.0f64;
+        let mut apply = |source: &str, card: &Card| {
+            multiplier *= 1.5;
+            trace.step(source, 1.5);
+            trace.record(|| format!("{} on {}", source, card), ScoreEffect::XMult(1.5));
+        };
+
+        for card in held.iter().filter(|card| !self.is_debuffed(card)) {
+            let red_seal = usize::from(card.seal == Some(Seal::Red));
+            let triggers = 1 + red_seal + mime_count;
+
+            for _ in 0..triggers {
+                if card.enhancement == Enhancement::Steel {
+                    apply("Steel card (held)", card);
+                }
+                if card.rank == Rank::King && card.has_rank() {
+                    for _ in 0..baron_count {
+                        apply("Baron", card);
+                    }
+                }
+            }
+        }
+
+        multiplier
+    }
+
+    /// Returns true if holding the card can change the result of a play
+    ///
+    /// Plays that differ only in held cards for which this is false score
+    /// the same, so their results can be reused.
+    pub fn held_card_matters(&self, card: &Card) -> bool {
+        let breaks_blackboard = self.count_abilities(&JokerKind::Blackboard) > 0
+            && !(card.is_suit(Suit::Spades) || card.is_suit(Suit::Clubs));
+        if breaks_blackboard || card.seal == Some(Seal::Blue) {
+            return true;
+        }
+
+        !self.is_debuffed(card)
+            && (matches!(card.enhancement, Enhancement::Steel | Enhancement::Gold)
+                || (card.rank == Rank::King
+                    && card.has_rank()
+                    && self.count_abilities(&JokerKind::Baron) > 0)
+                || (card.is_face_card(&self.rules)
+                    && self.count_abilities(&JokerKind::ReservedParking) > 0))
+    }
+
+    /// Returns true if the jokers that look for a hand type in the played
+    /// hand (Jolly Joker, Droll Joker, ...) find the same in both hands
+    pub(crate) fn same_contained_types(&self, a: &Hand, b: &Hand) -> bool {
+        const CONTAINED: [(JokerKind, HandType); 5] = [
+            (JokerKind::JollyJoker, HandType::Pair),
+            (JokerKind::ZanyJoker, HandType::ThreeOfAKind),
+            (JokerKind::MadJoker, HandType::TwoPair),
+            (JokerKind::CrazyJoker, HandType::Straight),
+            (JokerKind::DrollJoker, HandType::Flush),
+        ];
+        CONTAINED.iter().all(|(kind, hand_type)| {
+            self.count_abilities(kind) == 0
+                || a.contains(*hand_type, &self.rules) == b.contains(*hand_type, &self.rules)
+        })
+    }
+
+    /// Calculates the money earned by cards held in hand, as paid when the
+    /// hand is played and paid at end of round
+    ///
+    /// Reserved Parking pays $1 per held face card (1 in 2) and Gold cards
+    /// pay $3 at end of round; both are retriggered by Red seals and Mime.
+    fn calculate_held_money(&self, held: &[Card], roller: &mut Roller) -> (f64, f64) {
+        let mime_count = self.count_abilities(&JokerKind::Mime);
+        let parking_count = self.count_abilities(&JokerKind::ReservedParking);
+        let mut money = 0.0;
+        let mut round_money = 0.0;
+
+        for card in held.iter().filter(|card| !self.is_debuffed(card)) {
+            let red_seal = usize::from(card.seal == Some(Seal::Red));
+            let triggers = 1 + red_seal + mime_count;
+
+            for _ in 0..triggers {
+                if card.enhancement == Enhancement::Gold {
+                    round_money += 3.0;
+                }
+                if card.is_face_card(&self.rules) {
+                    for _ in 0..parking_count {
+                        money += roller.weight(RESERVED_PARKING_CHANCE);
+                    }
+                }
+            }
+        }
+
+        (money, round_money)
+    }
+
+    /// Money paid out by jokers at the end of the round
+    fn joker_money(&self) -> f64 {
+        self.jokers
+            .iter()
+            .map(|joker| joker.kind.end_of_round_money() as f64)
+            .sum()
+    }
+
+    /// Counts the cards in the full deck with the given enhancement
+    fn deck_count(&self, enhancement: Enhancement) -> usize {
+        self.deck()
+            .unwrap_or_default()
+            .iter()
+            .filter(|card| card.enhancement == enhancement)
+            .count()
+    }
+
+    /// Counts the enhanced cards in the full deck
+    fn enhanced_deck_count(&self) -> usize {
+        self.deck()
+            .unwrap_or_default()
+            .iter()
+            .filter(|card| card.enhancement != Enhancement::None)
+            .count()
+    }
+
+    /// Counts the joker slots using the given ability, including Blueprint copies
+    fn count_abilities(&self, kind: &JokerKind) -> usize {
+        (0..self.jokers.len())
+            .filter(|&index| self.resolve_ability(index).is_some_and(|joker| &joker.kind == kind))
+            .count()
+    }
+
+    /// Applies joker effects left to right on top of the running mult
+    ///
+    /// Each joker applies its Foil/Holographic edition first, then its own
+    /// ability, then its Polychrome edition, so the order of +mult and
+    /// x-mult jokers changes the final score.
     fn calculate_joker_bonuses(
         &self,
-        _hand: &Hand,
-        _hand_type: HandType,
-    ) -> (i32, i32, f32) {
-        let mut chips = 0i32;
-        let mut mult = 0i32;
-        let mut mult_multiplier = 1.0f32;
-
-        for joker in &self.jokers {
-            // Base joker effects
-            chips += joker.kind.base_chips();
-            mult += joker.kind.base_mult();
-
-            // Joker edition effects
+        play: &Play,
+        running_mult: f64,
+        traced: bool,
+    ) -> JokerBonuses {
+        let mut bonuses = JokerBonuses {
+            chips: 0,
+            mult: 0,
+            mult_multiplier: 1.0,
+            running_mult,
+            trace: Trace::new(traced),
+        };
+        bonuses.trace.enter(|| ScoreStage::Jokers);
+
+        for (index, joker) in self.jokers.iter().enumerate() {
+            // Additive joker edition effects
             match joker.edition {
-                super::joker::JokerEdition::Foil => chips += 50,
-                super::joker::JokerEdition::Holographic => mult += 10,
-                super::joker::JokerEdition::Polychrome => mult_multiplier *= 1.5,
+                JokerEdition::Foil => bonuses.add_chips("Foil joker", 50),
+                JokerEdition::Holographic => bonuses.add_mult("Holographic joker", 10),
                 _ => {}
             }
 
-            // TODO: Implement conditional joker effects based on hand composition
-            // This will be expanded as more jokers are implemented
+            if let Some(ability) = self.resolve_ability(index) {
+                self.apply_independent(ability, play, &mut bonuses);
+            }
+
+            // Multiplicative joker edition effects
+            if joker.edition == JokerEdition::Polychrome {
+                bonuses.times_mult("Polychrome joker", 1.5);
+            }
         }
 
-        (chips, mult, mult_multiplier)
+        bonuses
     }
+
+    /// Applies a joker ability that depends on the hand as a whole
+    fn apply_independent(&self, ability: &Joker, play: &Play, bonuses: &mut JokerBonuses) {
+        let contains = |hand_type: HandType| play.hand.contains(hand_type, &self.rules);
+        let kind = &ability.kind;
+        let name = kind.name();
+
+        bonuses.add_chips(name, kind.base_chips());
+        bonuses.add_mult(name, kind.base_mult());
+
+        match kind {
+            JokerKind::JollyJoker if contains(HandType::Pair) => bonuses.add_mult(name, 8),
+            JokerKind::ZanyJoker if contains(HandType::ThreeOfAKind) => bonuses.add_mult(name, 12),
+            JokerKind::MadJoker if contains(HandType::TwoPair) => bonuses.add_mult(name, 10),
+            JokerKind::CrazyJoker if contains(HandType::Straight) => bonuses.add_mult(name, 12),
+            JokerKind::DrollJoker if contains(HandType::Flush) => bonuses.add_mult(name, 10),
+            JokerKind::FlowerPot if has_every_suit(play.scoring) => {
+                bonuses.times_mult(name, 3.0);
+            }
+            JokerKind::SeeingDouble if has_club_and_other_suit(play.scoring) => {
+                bonuses.times_mult(name, 2.0);
+            }
+            JokerKind::Blackboard
+                if play.held.iter().all(|c| c.is_suit(Suit::Spades) || c.is_suit(Suit::Clubs)) =>
+            {
+                bonuses.times_mult(name, 3.0);
+            }
+            JokerKind::SteelJoker if self.deck_count(Enhancement::Steel) > 0 => {
+                let steel = self.deck_count(Enhancement::Steel) as f64;
+                bonuses.times_mult(name, 1.0 + 0.2 * steel);
+            }
+            JokerKind::StoneJoker => {
+                bonuses.add_chips(name, 25 * self.deck_count(Enhancement::Stone) as i32);
+            }
+            JokerKind::GlassJoker if ability.counter > 0 => {
+                bonuses.times_mult(name, 1.0 + 0.75 * ability.counter as f64);
+            }
+            JokerKind::DriversLicense if self.enhanced_deck_count() >= 16 => {
+                bonuses.times_mult(name, 3.0);
+            }
+            JokerKind::CeremonialDagger => bonuses.add_mult(name, ability.counter as i32),
+            JokerKind::IceCream => {
+                bonuses.add_chips(name, 100u32.saturating_sub(5 * ability.counter) as i32);
+            }
+            JokerKind::Popcorn => {
+                bonuses.add_mult(name, 20u32.saturating_sub(4 * ability.counter) as i32);
+            }
+            JokerKind::Madness if ability.counter > 0 => {
+                bonuses.times_mult(name, 1.0 + 0.5 * ability.counter as f64);
+            }
+            JokerKind::Yorick if ability.counter >= 23 => {
+                bonuses.times_mult(name, 1.0 + (ability.counter / 23) as f64);
+            }
+            JokerKind::Canio if ability.counter > 0 => {
+                bonuses.times_mult(name, 1.0 + ability.counter as f64);
+            }
+            JokerKind::Vampire if ability.counter > 0 => {
+                bonuses.times_mult(name, 1.0 + 0.1 * ability.counter as f64);
+            }
+            JokerKind::Hologram if ability.counter > 0 => {
+                bonuses.times_mult(name, 1.0 + 0.25 * ability.counter as f64);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Returns true if the cards cover all four suits, one suit per card
+///
+/// Wild cards fill whichever suits the other cards are missing.
+fn has_every_suit(cards: &[&Card]) -> bool {
+    let wilds = cards.iter().filter(|c| c.enhancement == Enhancement::Wild).count();
+    let suits = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades];
+    let covered = suits
+        .iter()
+        .filter(|&&suit| {
+            cards
+                .iter()
+                .any(|c| c.enhancement != Enhancement::Wild && c.is_suit(suit))
+        })
+        .count();
+
+    covered + wilds >= suits.len()
+}
+
+/// Returns true if one card is a Club and a different card is another suit
+fn has_club_and_other_suit(cards: &[&Card]) -> bool {
+    let is_other = |card: &Card| {
+        [Suit::Hearts, Suit::Diamonds, Suit::Spades]
+            .iter()
+            .any(|&suit| card.is_suit(suit))
+    };
+
+    cards.iter().enumerate().any(|(i, club)| {
+        club.is_suit(Suit::Clubs)
+            && cards
+                .iter()
+                .enumerate()
+                .any(|(j, other)| i != j && is_other(other))
+    })
 }
 
 #[cfg(test)]
@@ -157,7 +1158,7 @@ mod tests {
         let hand = Hand::new(cards);
         let calculator = ScoreCalculator::new(vec![]);
 
-        let result = calculator.calculate(&hand);
+        let result = calculator.calculate(&hand, &[]);
 
         assert_eq!(result.hand_type, HandType::Pair);
         assert_eq!(result.breakdown.base_chips, 10);
@@ -165,6 +1166,42 @@ mod tests {
         assert_eq!(result.breakdown.card_chips, 22); // Two aces: 11 + 11
     }
 
+    #[test]
+    fn test_hand_level_scoring() {
+        let cards = vec![
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Five, Suit::Hearts),
+            Card::new(Rank::Seven, Suit::Hearts),
+            Card::new(Rank::Nine, Suit::Hearts),
+            Card::new(Rank::King, Suit::Hearts),
+        ];
+        let levels = HandLevels::new().with_level(HandType::Flush, 5);
+        let calculator = ScoreCalculator::new(vec![]).with_hand_levels(levels);
+
+        let result = calculator.calculate(&Hand::new(cards), &[]);
+
+        assert_eq!(result.breakdown.base_chips, 95);
+        assert_eq!(result.breakdown.base_mult, 12);
+        assert_eq!(result.score, Score::from((95 + 33) * 12));
+    }
+
+    #[test]
+    fn test_only_scoring_cards_add_chips() {
+        let cards = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+            Card::new(Rank::King, Suit::Clubs),
+        ];
+        let hand = Hand::new(cards);
+
+        let result = ScoreCalculator::new(vec![]).calculate(&hand, &[]);
+        assert_eq!(result.breakdown.card_chips, 22); // King does not score
+
+        let splash = ScoreCalculator::new(vec![Joker::new(JokerKind::Splash)]);
+        let result = splash.calculate(&hand, &[]);
+        assert_eq!(result.breakdown.card_chips, 32); // Splash scores every card
+    }
+
     #[test]
     fn test_scoring_with_joker() {
         let cards = vec![
@@ -175,8 +1212,589 @@ mod tests {
         let jokers = vec![Joker::new(JokerKind::Joker)];
         let calculator = ScoreCalculator::new(jokers);
 
-        let result = calculator.calculate(&hand);
+        let result = calculator.calculate(&hand, &[]);
 
         assert_eq!(result.breakdown.joker_mult, 4); // Basic Joker gives +4 mult
     }
+
+    #[test]
+    fn test_score_events_trace_contributions() {
+        let calculator = ScoreCalculator::new(vec![
+            Joker::new(JokerKind::GreedyJoker),
+            Joker::new(JokerKind::Joker).with_edition(JokerEdition::Polychrome),
+        ]);
+        let hand = Hand::new(vec![
+            Card::new(Rank::Seven, Suit::Diamonds),
+            Card::new(Rank::Seven, Suit::Hearts).with_edition(Edition::Polychrome),
+        ]);
+
+        let result = calculator.calculate(&hand, &[]);
+        let events: Vec<String> = result.breakdown.events.iter().map(|e| e.to_string()).collect();
+        assert_eq!(
+            events,
+            vec![
+                "+10 chips: Pair base",
+                "+2 mult: Pair base",
+                "+7 chips: 7♦ base",
+                "+3 mult: Greedy Joker on 7♦",
+                "+7 chips: 7♥ base",
+                "x1.5: Polychrome card on 7♥",
+                "+4 mult: Joker",
+                "x1.5: Polychrome joker",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_the_arm_lowers_played_hand_level() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+        ]);
+        let levels = HandLevels::new().with_level(HandType::Pair, 3);
+        let calculator = ScoreCalculator::new(vec![]).with_hand_levels(levels);
+
+        let arm = calculator.clone().with_boss_blind(Some(BossBlind::TheArm));
+        assert_eq!(arm.calculate(&hand, &[]).breakdown.base_chips, 25);
+        assert_eq!(calculator.calculate(&hand, &[]).breakdown.base_chips, 40);
+
+        // Level 1 hands cannot go lower
+        let arm = ScoreCalculator::new(vec![]).with_boss_blind(Some(BossBlind::TheArm));
+        assert_eq!(arm.calculate(&hand, &[]).breakdown.base_chips, 10);
+    }
+
+    #[test]
+    fn test_the_flint_halves_base_values() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+            Card::new(Rank::Ace, Suit::Clubs),
+        ]);
+        let calculator = ScoreCalculator::new(vec![]).with_boss_blind(Some(BossBlind::TheFlint));
+
+        // Three of a Kind: 30 chips x 3 mult becomes 15 x 2
+        let result = calculator.calculate(&hand, &[]);
+        assert_eq!(result.breakdown.base_chips, 15);
+        assert_eq!(result.breakdown.base_mult, 2);
+        assert_eq!(result.score, Score::from((15u64 + 33) * 2));
+    }
+
+    #[test]
+    fn test_debuffed_cards_add_nothing() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::Ace, Suit::Clubs).with_edition(Edition::Holographic),
+            Card::new(Rank::Ace, Suit::Spades),
+        ]);
+        let held = vec![Card::new(Rank::King, Suit::Clubs).with_enhancement(Enhancement::Steel)];
+        let calculator = ScoreCalculator::new(vec![Joker::new(JokerKind::GluttonousJoker)])
+            .with_boss_blind(Some(BossBlind::TheClub));
+
+        let result = calculator.calculate(&hand, &held);
+        assert_eq!(result.hand_type, HandType::Pair); // Still forms the pair
+        assert_eq!(result.breakdown.card_chips, 11);
+        assert_eq!(result.breakdown.card_mult, 0);
+        assert_eq!(result.breakdown.joker_mult, 0);
+        assert_eq!(result.breakdown.held_mult_multiplier, 1.0);
+    }
+
+    #[test]
+    fn test_held_steel_and_baron() {
+        let hand = Hand::new(vec![Card::new(Rank::Two, Suit::Hearts)]);
+        let held = vec![
+            Card::new(Rank::King, Suit::Spades),
+            Card::new(Rank::Five, Suit::Clubs).with_enhancement(Enhancement::Steel),
+        ];
+        let calculator = ScoreCalculator::new(vec![Joker::new(JokerKind::Baron)]);
+
+        let result = calculator.calculate(&hand, &held);
+
+        // Baron on the held King (x1.5) and the Steel card (x1.5)
+        assert_eq!(result.breakdown.held_mult_multiplier, 2.25);
+        assert_eq!(result.mult, 2.25);
+        assert_eq!(result.breakdown.mult_steps.len(), 2);
+    }
+
+    #[test]
+    fn test_glass_card_doubles_mult() {
+        let cards = vec![
+            Card::new(Rank::Ace, Suit::Hearts).with_enhancement(Enhancement::Glass),
+            Card::new(Rank::Ace, Suit::Spades),
+        ];
+        let calculator = ScoreCalculator::new(vec![]);
+
+        let result = calculator.calculate(&Hand::new(cards), &[]);
+
+        assert_eq!(result.mult, 4.0); // Pair base 2 x2
+        assert_eq!(result.glass_cards_scored, 1);
+        assert_eq!(result.expected_glass_breaks(), 0.25);
+    }
+
+    #[test]
+    fn test_lucky_card_probability_modes() {
+        let cards = vec![Card::new(Rank::Ace, Suit::Hearts).with_enhancement(Enhancement::Lucky)];
+        let hand = Hand::new(cards);
+
+        let expected = ScoreCalculator::new(vec![]).calculate(&hand, &[]);
+        assert_eq!(expected.breakdown.card_mult, 4); // 20 * 1/5
+
+        let optimistic = ScoreCalculator::new(vec![])
+            .with_probability_mode(ProbabilityMode::Optimistic)
+            .calculate(&hand, &[]);
+        assert_eq!(optimistic.breakdown.card_mult, 20);
+
+        let pessimistic = ScoreCalculator::new(vec![])
+            .with_probability_mode(ProbabilityMode::Pessimistic)
+            .calculate(&hand, &[]);
+        assert_eq!(pessimistic.breakdown.card_mult, 0);
+
+        let sampled = ScoreCalculator::new(vec![])
+            .with_probability_mode(ProbabilityMode::Sampled(1))
+            .calculate(&hand, &[]);
+        assert!(sampled.breakdown.card_mult == 0 || sampled.breakdown.card_mult == 20);
+    }
+
+    #[test]
+    fn test_mime_retriggers_held_cards() {
+        let hand = Hand::new(vec![Card::new(Rank::Two, Suit::Hearts)]);
+        let held = vec![Card::new(Rank::Five, Suit::Clubs).with_enhancement(Enhancement::Steel)];
+        let calculator = ScoreCalculator::new(vec![Joker::new(JokerKind::Mime)]);
+
+        let result = calculator.calculate(&hand, &held);
+
+        assert_eq!(result.breakdown.held_mult_multiplier, 2.25);
+    }
+
+    #[test]
+    fn test_stone_card_scores_flat_chips() {
+        let cards = vec![
+            Card::new(Rank::Ace, Suit::Hearts).with_enhancement(Enhancement::Stone),
+        ];
+        let calculator = ScoreCalculator::new(vec![]);
+
+        let result = calculator.calculate(&Hand::new(cards), &[]);
+
+        assert_eq!(result.hand_type, HandType::HighCard);
+        assert_eq!(result.breakdown.card_chips, 50);
+    }
+
+    #[test]
+    fn test_polychrome_applies_in_card_order() {
+        let cards = vec![
+            Card::new(Rank::Ace, Suit::Hearts).with_edition(Edition::Polychrome),
+            Card::new(Rank::Ace, Suit::Spades).with_enhancement(Enhancement::Mult),
+        ];
+        let calculator = ScoreCalculator::new(vec![]);
+
+        let result = calculator.calculate(&Hand::new(cards), &[]);
+
+        // Pair base 2, x1.5 from the first card, then +4 from the second
+        assert_eq!(result.breakdown.card_mult_multiplier, 1.5);
+        assert_eq!(result.mult, 7.0);
+    }
+
+    #[test]
+    fn test_face_card_jokers_with_pareidolia() {
+        let cards = vec![
+            Card::new(Rank::King, Suit::Hearts),
+            Card::new(Rank::King, Suit::Spades),
+        ];
+        let hand = Hand::new(cards);
+
+        let jokers = vec![Joker::new(JokerKind::ScaryFace), Joker::new(JokerKind::SmileyFace)];
+        let result = ScoreCalculator::new(jokers).calculate(&hand, &[]);
+        assert_eq!(result.breakdown.joker_chips, 60);
+        assert_eq!(result.breakdown.joker_mult, 10);
+
+        let twos = Hand::new(vec![
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Two, Suit::Spades),
+        ]);
+        let jokers = vec![Joker::new(JokerKind::ScaryFace)];
+        let result = ScoreCalculator::new(jokers.clone()).calculate(&twos, &[]);
+        assert_eq!(result.breakdown.joker_chips, 0);
+
+        let mut jokers = jokers;
+        jokers.push(Joker::new(JokerKind::Pareidolia));
+        let result = ScoreCalculator::new(jokers).calculate(&twos, &[]);
+        assert_eq!(result.breakdown.joker_chips, 60);
+    }
+
+    #[test]
+    fn test_joker_order_matters() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+        ]);
+        let polychrome = Joker::new(JokerKind::Joker).with_edition(JokerEdition::Polychrome);
+        let jolly = Joker::new(JokerKind::JollyJoker);
+
+        // (2 + 4) * 1.5 + 8 = 17
+        let x_first = ScoreCalculator::new(vec![polychrome.clone(), jolly.clone()]);
+        assert_eq!(x_first.calculate(&hand, &[]).mult, 17.0);
+
+        // (2 + 8 + 4) * 1.5 = 21
+        let x_last = ScoreCalculator::new(vec![jolly, polychrome]);
+        assert_eq!(x_last.calculate(&hand, &[]).mult, 21.0);
+    }
+
+    #[test]
+    fn test_blueprint_copies_right_neighbor() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+        ]);
+        let jokers = vec![
+            Joker::new(JokerKind::Blueprint),
+            Joker::new(JokerKind::JollyJoker),
+        ];
+        let result = ScoreCalculator::new(jokers).calculate(&hand, &[]);
+        assert_eq!(result.breakdown.joker_mult, 16);
+
+        let jokers = vec![
+            Joker::new(JokerKind::JollyJoker),
+            Joker::new(JokerKind::Blueprint),
+        ];
+        let result = ScoreCalculator::new(jokers).calculate(&hand, &[]);
+        assert_eq!(result.breakdown.joker_mult, 8);
+
+        // Copies of held-card jokers trigger on held cards too
+        let held = vec![Card::new(Rank::King, Suit::Clubs).with_enhancement(Enhancement::Gold)];
+        let jokers = vec![Joker::new(JokerKind::Blueprint), Joker::new(JokerKind::Baron)];
+        let calculator = ScoreCalculator::new(jokers);
+        let result = calculator.calculate(&hand, &held);
+        assert_eq!(result.breakdown.held_mult_multiplier, 2.25);
+        assert!(calculator.held_card_matters(&held[0]));
+
+        let jokers = vec![Joker::new(JokerKind::Blueprint), Joker::new(JokerKind::Mime)];
+        let result = ScoreCalculator::new(jokers).calculate(&hand, &held);
+        // The held Gold card pays $3, then again for Mime and its copy
+        assert_eq!(result.round_money, 9.0);
+    }
+
+    #[test]
+    fn test_suit_jokers_trigger_per_scored_card() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::Ace, Suit::Diamonds),
+            Card::new(Rank::Ace, Suit::Spades),
+        ]);
+        let jokers = vec![Joker::new(JokerKind::GreedyJoker)];
+        let result = ScoreCalculator::new(jokers).calculate(&hand, &[]);
+        assert_eq!(result.breakdown.joker_mult, 3);
+    }
+
+    #[test]
+    fn test_stacked_x_mult_is_exact() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::Ace, Suit::Hearts)
+                .with_enhancement(Enhancement::Glass)
+                .with_edition(Edition::Polychrome),
+            Card::new(Rank::Ace, Suit::Spades).with_enhancement(Enhancement::Glass),
+        ]);
+        let jokers = vec![Joker::new(JokerKind::Joker).with_edition(JokerEdition::Polychrome)];
+
+        let result = ScoreCalculator::new(jokers).calculate(&hand, &[]);
+
+        // ((2 x2 x1.5 x2) + 4) x1.5 = 24
+        assert_eq!(result.mult, 24.0);
+        let factors: Vec<f64> = result.breakdown.mult_steps.iter().map(|s| s.multiplier).collect();
+        assert_eq!(factors, vec![2.0, 1.5, 2.0, 1.5]);
+        assert_eq!(result.score, Score::from((10 + 22) * 24));
+    }
+
+    #[test]
+    fn test_money_delta() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::King, Suit::Hearts).with_seal(Seal::Gold),
+            Card::new(Rank::King, Suit::Spades).with_enhancement(Enhancement::Lucky),
+        ]);
+        let held = vec![
+            Card::new(Rank::Two, Suit::Clubs).with_enhancement(Enhancement::Gold),
+            Card::new(Rank::Queen, Suit::Clubs),
+        ];
+
+        let result = ScoreCalculator::new(vec![]).calculate(&hand, &held);
+        // Gold seal $3 + Lucky $20/15 + held Gold card $3
+        assert!((result.money_delta - (6.0 + 20.0 / 15.0)).abs() < 1e-9);
+
+        let jokers = vec![
+            Joker::new(JokerKind::GoldenJoker),
+            Joker::new(JokerKind::BusinessCard),
+            Joker::new(JokerKind::ReservedParking),
+        ];
+        let result = ScoreCalculator::new(jokers)
+            .with_probability_mode(ProbabilityMode::Optimistic)
+            .calculate(&hand, &held);
+        // $3 + $20 + $3, Golden Joker $4, Business Card 2 x $2, Reserved Parking $1
+        assert_eq!(result.money_delta, 35.0);
+        // Of which the held Gold card and Golden Joker pay at end of round
+        assert_eq!(result.round_money, 7.0);
+    }
+
+    #[test]
+    fn test_plasma_balance() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+        ]);
+
+        let result = ScoreCalculator::new(vec![])
+            .with_plasma_balance(true)
+            .calculate(&hand, &[]);
+
+        // (32 chips + 2 mult) / 2 = 17, squared
+        assert_eq!(result.mult, 17.0);
+        assert_eq!(result.score, Score::from(289));
+    }
+
+    #[test]
+    fn test_scaling_x_mult_jokers() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+        ]);
+        let jokers = vec![
+            Joker::new(JokerKind::Vampire).with_counter(5),
+            Joker::new(JokerKind::Hologram).with_counter(2),
+        ];
+
+        let result = ScoreCalculator::new(jokers).calculate(&hand, &[]);
+
+        // Pair base 2 x1.5 (Vampire) x1.5 (Hologram)
+        assert_eq!(result.mult, 4.5);
+    }
+
+    #[test]
+    fn test_rank_jokers() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+            Card::new(Rank::Ten, Suit::Hearts),
+            Card::new(Rank::Ten, Suit::Spades),
+        ]);
+        let jokers = vec![
+            Joker::new(JokerKind::EvenSteven),
+            Joker::new(JokerKind::OddTodd),
+            Joker::new(JokerKind::Fibonacci),
+            Joker::new(JokerKind::Scholar),
+            Joker::new(JokerKind::WalkieTalkie),
+        ];
+
+        let result = ScoreCalculator::new(jokers).calculate(&hand, &[]);
+
+        // Aces: Odd Todd 31 + Scholar 20 chips, Fibonacci 8 + Scholar 4 mult
+        // Tens: Walkie Talkie 10 chips, Even Steven 4 + Walkie Talkie 4 mult
+        assert_eq!(result.breakdown.joker_chips, 2 * (31 + 20 + 10));
+        assert_eq!(result.breakdown.joker_mult, 2 * (8 + 4 + 4 + 4));
+    }
+
+    #[test]
+    fn test_triboulet_ignores_stone_kings() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::King, Suit::Hearts),
+            Card::new(Rank::King, Suit::Spades).with_enhancement(Enhancement::Stone),
+        ]);
+        let jokers = vec![Joker::new(JokerKind::Triboulet)];
+
+        let result = ScoreCalculator::new(jokers).calculate(&hand, &[]);
+
+        // High Card base 1, x2 for the ranked King only
+        assert_eq!(result.mult, 2.0);
+    }
+
+    #[test]
+    fn test_face_card_retriggers() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Two, Suit::Spades),
+            Card::new(Rank::King, Suit::Hearts),
+            Card::new(Rank::King, Suit::Spades),
+        ]);
+        let jokers = vec![
+            Joker::new(JokerKind::Photograph),
+            Joker::new(JokerKind::SockAndBuskin),
+        ];
+
+        let result = ScoreCalculator::new(jokers.clone()).calculate(&hand, &[]);
+
+        // Two Pair base 2; the first King triggers twice, each x2 from Photograph
+        assert_eq!(result.breakdown.card_chips, 2 + 2 + 4 * 10);
+        assert_eq!(result.mult, 8.0);
+
+        // With Pareidolia the first Two is the first face card
+        let mut jokers = jokers;
+        jokers.push(Joker::new(JokerKind::Pareidolia));
+        let result = ScoreCalculator::new(jokers).calculate(&hand, &[]);
+        assert_eq!(result.breakdown.card_chips, 2 * (2 + 2 + 10 + 10));
+        assert_eq!(result.mult, 8.0);
+    }
+
+    #[test]
+    fn test_red_seal_retriggers_played_card() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::Ace, Suit::Hearts).with_seal(Seal::Red),
+            Card::new(Rank::Ace, Suit::Spades),
+        ]);
+
+        let result = ScoreCalculator::new(vec![]).calculate(&hand, &[]);
+
+        assert_eq!(result.breakdown.card_chips, 33);
+    }
+
+    #[test]
+    fn test_suit_composition_jokers() {
+        let rainbow = Hand::new(vec![
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Two, Suit::Diamonds),
+            Card::new(Rank::Two, Suit::Clubs),
+            Card::new(Rank::Two, Suit::Spades).with_enhancement(Enhancement::Wild),
+        ]);
+        let flower_pot = ScoreCalculator::new(vec![Joker::new(JokerKind::FlowerPot)]);
+        assert_eq!(flower_pot.calculate(&rainbow, &[]).mult, 21.0); // Four of a Kind 7 x3
+
+        let clubs = Hand::new(vec![
+            Card::new(Rank::Two, Suit::Clubs),
+            Card::new(Rank::Two, Suit::Clubs).with_enhancement(Enhancement::Wild),
+        ]);
+        let seeing_double = ScoreCalculator::new(vec![Joker::new(JokerKind::SeeingDouble)]);
+        assert_eq!(seeing_double.calculate(&clubs, &[]).mult, 4.0);
+        assert_eq!(flower_pot.calculate(&clubs, &[]).mult, 2.0);
+
+        let blackboard = ScoreCalculator::new(vec![Joker::new(JokerKind::Blackboard)]);
+        let dark = vec![Card::new(Rank::Five, Suit::Spades), Card::new(Rank::Six, Suit::Clubs)];
+        let light = vec![Card::new(Rank::Five, Suit::Spades), Card::new(Rank::Six, Suit::Hearts)];
+        assert_eq!(blackboard.calculate(&clubs, &dark).mult, 6.0);
+        assert_eq!(blackboard.calculate(&clubs, &light).mult, 2.0);
+    }
+
+    #[test]
+    fn test_deck_composition_jokers() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+        ]);
+        let two = Card::new(Rank::Two, Suit::Clubs);
+        let mut deck = vec![two.clone().with_enhancement(Enhancement::Steel); 5];
+        deck.extend(vec![two.with_enhancement(Enhancement::Stone); 11]);
+        let jokers = vec![
+            Joker::new(JokerKind::StoneJoker),
+            Joker::new(JokerKind::SteelJoker),
+            Joker::new(JokerKind::DriversLicense),
+        ];
+
+        let without_deck = ScoreCalculator::new(jokers.clone());
+        assert_eq!(without_deck.jokers_missing_deck().len(), 3);
+        assert_eq!(without_deck.calculate(&hand, &[]).mult, 2.0);
+
+        let with_deck = ScoreCalculator::new(jokers).with_deck(deck);
+        let result = with_deck.calculate(&hand, &[]);
+        assert!(with_deck.jokers_missing_deck().is_empty());
+        assert_eq!(result.breakdown.joker_chips, 25 * 11);
+        // Pair base 2 x2 (five Steel cards) x3 (16 enhanced cards)
+        assert_eq!(result.mult, 12.0);
+    }
+
+    #[test]
+    fn test_probabilistic_jokers() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::Eight, Suit::Hearts),
+            Card::new(Rank::Eight, Suit::Spades),
+        ]);
+        let jokers = vec![
+            Joker::new(JokerKind::EightBall),
+            Joker::new(JokerKind::SpaceJoker),
+            Joker::new(JokerKind::Bloodstone),
+        ];
+
+        let expected = ScoreCalculator::new(jokers.clone()).calculate(&hand, &[]);
+        assert_eq!(expected.tarots_created, 0.5);
+        // Pair base 2 + a quarter level (+0.25), x1.25 from Bloodstone
+        assert_eq!(expected.mult, 2.8125);
+
+        let optimistic = ScoreCalculator::new(jokers.clone())
+            .with_probability_mode(ProbabilityMode::Optimistic)
+            .calculate(&hand, &[]);
+        assert_eq!(optimistic.tarots_created, 2.0);
+        assert_eq!(optimistic.breakdown.base_chips, 25);
+        assert_eq!(optimistic.mult, 4.5);
+
+        let pessimistic = ScoreCalculator::new(jokers)
+            .with_probability_mode(ProbabilityMode::Pessimistic)
+            .calculate(&hand, &[]);
+        assert_eq!(pessimistic.tarots_created, 0.0);
+        assert_eq!(pessimistic.mult, 2.0);
+    }
+
+    #[test]
+    fn test_legendary_counters() {
+        let hand = Hand::new(vec![Card::new(Rank::Ace, Suit::Hearts)]);
+        let jokers = vec![
+            Joker::new(JokerKind::Yorick).with_counter(50),
+            Joker::new(JokerKind::Canio).with_counter(1),
+        ];
+
+        let result = ScoreCalculator::new(jokers).calculate(&hand, &[]);
+
+        // High Card base 1, x3 (two sets of 23 discards) x2 (one face card destroyed)
+        assert_eq!(result.mult, 6.0);
+    }
+
+    #[test]
+    fn test_decaying_jokers() {
+        let hand = Hand::new(vec![Card::new(Rank::Ace, Suit::Hearts)]);
+        let jokers = vec![
+            Joker::new(JokerKind::IceCream).with_counter(4),
+            Joker::new(JokerKind::Popcorn).with_counter(1),
+            Joker::new(JokerKind::CeremonialDagger).with_counter(6),
+        ];
+
+        let result = ScoreCalculator::new(jokers).calculate(&hand, &[]);
+
+        assert_eq!(result.breakdown.joker_chips, 80);
+        assert_eq!(result.breakdown.joker_mult, 16 + 6);
+    }
+
+    #[test]
+    fn test_target_jokers() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::King, Suit::Hearts),
+            Card::new(Rank::King, Suit::Spades),
+        ]);
+        let jokers = vec![
+            Joker::new(JokerKind::TheIdol).with_target(Some(Rank::King), Some(Suit::Hearts)),
+            Joker::new(JokerKind::AncientJoker).with_target(None, Some(Suit::Spades)),
+        ];
+
+        let result = ScoreCalculator::new(jokers).calculate(&hand, &[]);
+
+        // Pair base 2, x2 for the King of Hearts, x1.5 for the King of Spades
+        assert_eq!(result.mult, 6.0);
+
+        let unpinned = ScoreCalculator::new(vec![Joker::new(JokerKind::TheIdol)]);
+        assert_eq!(unpinned.jokers_missing_target().len(), 1);
+        assert_eq!(unpinned.calculate(&hand, &[]).mult, 2.0);
+    }
+
+    #[test]
+    fn test_quick_matches_traced() {
+        let hand = Hand::new(vec![
+            Card::new(Rank::King, Suit::Hearts),
+            Card::new(Rank::King, Suit::Spades),
+            Card::new(Rank::Seven, Suit::Diamonds),
+        ]);
+        let held = vec![Card::new(Rank::King, Suit::Clubs)];
+        let calculator = ScoreCalculator::new(vec![
+            Joker::new(JokerKind::GreedyJoker),
+            Joker::new(JokerKind::Baron),
+            Joker::new(JokerKind::Joker).with_edition(JokerEdition::Polychrome),
+        ]);
+
+        let traced = calculator.calculate(&hand, &held);
+        let mut quick = calculator.calculate_quick(&hand, &held);
+        assert!(quick.breakdown.mult_steps.is_empty() && quick.breakdown.events.is_empty());
+        assert!(!traced.breakdown.events.is_empty());
+
+        quick.breakdown.mult_steps = traced.breakdown.mult_steps.clone();
+        quick.breakdown.events = traced.breakdown.events.clone();
+        assert_eq!(quick, traced);
+    }
 }
@@ -0,0 +1,271 @@
+//! Maximum score search
+//!
+//! In endless runs the question is no longer whether a blind can be beaten
+//! but how high one hand can score. This module searches a run's whole deck
+//! for the hand to aim for: the play, the cards held beside it, the joker
+//! order, and the consumables to use first.
+
+use super::card::Card;
+use super::consumable::Consumable;
+use super::hand::Hand;
+use super::joker::Joker;
+use super::run::GameRunState;
+use super::score::Score;
+use super::scoring::{ScoreCalculator, ScoreResult};
+use super::solver::{ConsumableUse, Solver, MAX_REORDERED_JOKERS};
+use std::time::{Duration, Instant};
+
+/// Default time allowed for a search
+pub const DEFAULT_TIME_LIMIT: Duration = Duration::from_secs(5);
+
+/// Default beam width used to find the starting play
+pub const DEFAULT_SEARCH_BEAM_WIDTH: usize = 32;
+
+/// Searches a run's deck for the highest-scoring hand
+///
+/// A beam search over the whole deck finds a starting play. Local search
+/// then fills the rest of the hand with cards worth holding and swaps
+/// cards in and out while that raises the score. Finally every joker order
+/// is tried and the run's consumables are used where they help.
+pub struct MaxScoreSearch {
+    run: GameRunState,
+    time_limit: Duration,
+    beam_width: usize,
+}
+
+/// The highest-scoring hand found
+#[derive(Debug, Clone)]
+pub struct MaxScoreResult {
+    /// Every card to hold in hand before using consumables
+    pub hand: Vec<Card>,
+    /// The play to make
+    pub play: Hand,
+    /// The cards left in hand beside the play
+    pub held_cards: Vec<Card>,
+    /// The joker order to score with
+    pub joker_order: Vec<Joker>,
+    /// Consumables to use before playing, in order
+    pub consumables: Vec<ConsumableUse>,
+    /// The score of the play (`None` if the deck is empty)
+    pub score: Option<ScoreResult>,
+    /// False if the time limit cut the search short
+    pub complete: bool,
+}
+
+impl MaxScoreSearch {
+    /// Creates a search over the run's deck, jokers, hand levels, and
+    /// consumables
+    pub fn new(run: GameRunState) -> Self {
+        Self {
+            run,
+            time_limit: DEFAULT_TIME_LIMIT,
+            beam_width: DEFAULT_SEARCH_BEAM_WIDTH,
+        }
+    }
+
+    /// Sets how long the search may run before returning its best so far
+    pub fn with_time_limit(mut self, time_limit: Duration) -> Self {
+        self.time_limit = time_limit;
+        self
+    }
+
+    /// Sets the beam width used to find the starting play
+    pub fn with_beam_width(mut self, beam_width: usize) -> Self {
+        self.beam_width = beam_width.max(1);
+        self
+    }
+
+    /// Runs the search
+    pub fn search(&self) -> MaxScoreResult {
+        let deadline = Instant::now() + self.time_limit;
+        let hand_size = self.run.deck_type.modifiers().hand_size;
+        let mut calculator = self.run.calculator();
+
+        let solver = Solver::new(calculator.clone()).with_deadline(deadline);
+        let start = solver.rank(solver.beam_search(&self.run.deck, self.beam_width));
+        let Some((play, _, _)) = start.into_iter().next() else {
+            return MaxScoreResult {
+                hand: vec![],
+                play: Hand::new(vec![]),
+                held_cards: vec![],
+                joker_order: self.run.jokers.clone(),
+                consumables: vec![],
+                score: None,
+                complete: !solver.past_deadline(),
+            };
+        };
+
+        let mut pool = self.run.deck.clone();
+        for card in &play.cards {
+            if let Some(i) = pool.iter().position(|c| c == card) {
+                pool.swap_remove(i);
+            }
+        }
+        let mut hand = HandSearch {
+            played: play.cards,
+            held: vec![],
+            pool,
+        };
+        let mut complete = !solver.past_deadline();
+        complete &= hand.improve(&calculator, hand_size, deadline);
+
+        let mut joker_order = self.run.jokers.clone();
+        if joker_order.len() <= MAX_REORDERED_JOKERS {
+            let mut best = hand.score(&calculator);
+            // The first permutation is the current order, already scored
+            for order in Solver::permutations(&self.run.jokers).skip(1) {
+                if Instant::now() > deadline {
+                    complete = false;
+                    break;
+                }
+                let reordered = calculator.clone().with_jokers(order.clone());
+                let score = hand.score(&reordered);
+                if score > best {
+                    best = score;
+                    joker_order = order;
+                }
+            }
+            calculator = calculator.with_jokers(joker_order.clone());
+        }
+
+        let consumables: Vec<Consumable> = self
+            .run
+            .consumables
+            .iter()
+            .filter_map(|name| name.parse().ok())
+            .collect();
+        let cards: Vec<Card> = hand.played.iter().chain(&hand.held).cloned().collect();
+        let solver = Solver::new(calculator).with_deadline(deadline);
+        let plan = solver.solve_with_consumables(&cards, &consumables);
+        complete &= !solver.past_deadline();
+
+        MaxScoreResult {
+            hand: cards,
+            play: plan.result.best_hand,
+            held_cards: plan.result.held_cards,
+            joker_order,
+            consumables: plan.uses,
+            score: plan.result.best_score,
+            complete,
+        }
+    }
+}
+
+/// A hand being improved by local search
+struct HandSearch {
+    played: Vec<Card>,
+    held: Vec<Card>,
+    /// Deck cards not in the hand
+    pool: Vec<Card>,
+}
+
+impl HandSearch {
+    /// Scores the play with the held cards
+    fn score(&self, calculator: &ScoreCalculator) -> Score {
+        calculator
+            .calculate(&Hand::new(self.played.clone()), &self.held)
+            .score
+    }
+
+    /// Adds and swaps cards while that raises the score, returning false if
+    /// the deadline passed first
+    ///
+    /// Each pass takes the first improving move: playing another card (up to
+    /// five), holding another card (up to the hand size), or exchanging a
+    /// played or held card for one from the pool.
+    fn improve(&mut self, calculator: &ScoreCalculator, hand_size: usize, deadline: Instant) -> bool {
+        let mut best = self.score(calculator);
+
+        loop {
+            if Instant::now() > deadline {
+                return false;
+            }
+
+            let has_room = self.played.len() + self.held.len() < hand_size;
+            let mut improved = false;
+            'moves: for j in 0..self.pool.len() {
+                if has_room {
+                    for played in [true, false] {
+                        if played && self.played.len() >= 5 {
+                            continue;
+                        }
+                        let card = self.pool.swap_remove(j);
+                        self.slots(played).push(card);
+                        let score = self.score(calculator);
+                        if score > best {
+                            best = score;
+                            improved = true;
+                            break 'moves;
+                        }
+                        let card = self.slots(played).pop().expect("card was just added");
+                        self.pool.push(card);
+                        let last = self.pool.len() - 1;
+                        self.pool.swap(j, last);
+                    }
+                }
+
+                for played in [true, false] {
+                    for i in 0..self.slots(played).len() {
+                        self.exchange(played, i, j);
+                        let score = self.score(calculator);
+                        if score > best {
+                            best = score;
+                            improved = true;
+                            break 'moves;
+                        }
+                        self.exchange(played, i, j);
+                    }
+                }
+            }
+
+            if !improved {
+                return true;
+            }
+        }
+    }
+
+    /// Exchanges a played or held card with a pool card
+    fn exchange(&mut self, played: bool, i: usize, j: usize) {
+        let slots = if played { &mut self.played } else { &mut self.held };
+        std::mem::swap(&mut slots[i], &mut self.pool[j]);
+    }
+
+    /// Returns the played or held cards
+    fn slots(&mut self, played: bool) -> &mut Vec<Card> {
+        if played { &mut self.played } else { &mut self.held }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::card::Rank;
+    use crate::core::hand::HandType;
+    use crate::core::joker::JokerKind;
+
+    #[test]
+    fn test_search_holds_kings_for_baron() {
+        let run = GameRunState::default().with_jokers(vec![Joker::new(JokerKind::Baron)]);
+
+        let result = MaxScoreSearch::new(run).with_time_limit(Duration::from_secs(60)).search();
+
+        assert!(result.complete);
+        // Four Aces played with all four Kings held beats a Straight Flush
+        assert_eq!(result.score.unwrap().hand_type, HandType::FourOfAKind);
+        assert_eq!(result.held_cards.len(), 4);
+        assert!(result.held_cards.iter().all(|card| card.rank == Rank::King));
+    }
+
+    #[test]
+    fn test_search_respects_time_limit() {
+        let result = MaxScoreSearch::new(GameRunState::default())
+            .with_time_limit(Duration::ZERO)
+            .search();
+
+        assert!(!result.complete);
+        assert!(result.score.is_some());
+
+        let empty = MaxScoreSearch::new(GameRunState::default().with_deck(vec![])).search();
+        assert!(empty.score.is_none());
+    }
+}
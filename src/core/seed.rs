@@ -0,0 +1,822 @@
+//! Seed analysis
+//!
+//! Balatro draws every random outcome from named streams derived from the
+//! run's seed string: the stream's state is hashed from its name and the
+//! seed, stepped on each use, and fed to LuaJIT's `math.randomseed` before
+//! `math.random` picks from a pool. [`SeedRng`] reproduces those streams
+//! and [`LuaRandom`] LuaJIT's generator, so a seed's early bosses,
+//! vouchers, and shop cards can be predicted before playing it.
+//!
+//! Predictions assume a Red Deck on White Stake with every item unlocked,
+//! no rerolls, skipped tags, or Showman, and that each ante's voucher is
+//! redeemed (which is what lets upgraded vouchers appear) in the ante's
+//! first shop, after that visit's cards are dealt. From then on its
+//! changes to the shop apply: Tarot and Planet Merchant and Tycoon odds,
+//! Magic Trick's playing cards, Hone and Glow Up's editions, and
+//! Overstock's extra cards. Illusion's enhancements, editions, and seals
+//! on playing cards are not predicted.
+
+use super::joker::{JokerEdition, JokerRarity};
+use super::pools::{self, BOSSES, GATED_JOKERS, JOKERS, PLANETS, TAROTS, VOUCHERS};
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::{E, PI};
+
+/// Longest seed the game accepts
+pub const SEED_LENGTH: usize = 8;
+
+/// Ante whose boss is a showdown boss (every eighth)
+const WIN_ANTE: u32 = 8;
+
+/// Every playing card's game key, in the order the game picks from them
+/// (sorted by key: suit, then rank with digits before letters)
+const PLAYING_CARDS: [&str; 52] = [
+    "C_2", "C_3", "C_4", "C_5", "C_6", "C_7", "C_8", "C_9", "C_A", "C_J", "C_K", "C_Q", "C_T",
+    "D_2", "D_3", "D_4", "D_5", "D_6", "D_7", "D_8", "D_9", "D_A", "D_J", "D_K", "D_Q", "D_T",
+    "H_2", "H_3", "H_4", "H_5", "H_6", "H_7", "H_8", "H_9", "H_A", "H_J", "H_K", "H_Q", "H_T",
+    "S_2", "S_3", "S_4", "S_5", "S_6", "S_7", "S_8", "S_9", "S_A", "S_J", "S_K", "S_Q", "S_T",
+];
+
+/// Hashes a string to a number in [0, 1) the way the game does
+pub fn pseudohash(text: &str) -> f64 {
+    let mut num = 1.0;
+    for (i, byte) in text.bytes().enumerate().rev() {
+        let value = (1.1239285023 / num) * f64::from(byte) * PI + PI * (i + 1) as f64;
+        num = value - value.floor();
+    }
+    num
+}
+
+/// LuaJIT's `math.random` generator (four combined Tausworthe generators)
+#[derive(Debug, Clone)]
+pub struct LuaRandom {
+    state: [u64; 4],
+}
+
+impl LuaRandom {
+    /// Seeds the generator as `math.randomseed(seed)` does
+    pub fn new(seed: f64) -> Self {
+        let mut state = [0; 4];
+        let mut d = seed;
+        for (i, shift) in [1u32, 6, 9, 17].into_iter().enumerate() {
+            d = d * PI + E;
+            let bits = d.to_bits();
+            // Keep the bits each generator needs from being all zero
+            let min = 1u64 << shift;
+            state[i] = if bits < min { bits + min } else { bits };
+        }
+        let mut random = Self { state };
+        for _ in 0..10 {
+            random.step();
+        }
+        random
+    }
+
+    /// Advances the generators, returning a double's bits in [1, 2)
+    fn step(&mut self) -> u64 {
+        let mut result = 0;
+        for (i, (k, q, s)) in [(63, 31, 18), (58, 19, 28), (55, 24, 7), (47, 21, 8)]
+            .into_iter()
+            .enumerate()
+        {
+            let z = self.state[i];
+            let z = (((z << q) ^ z) >> (k - s)) ^ ((z & (u64::MAX << (64 - k))) << s);
+            result ^= z;
+            self.state[i] = z;
+        }
+        (result & 0x000f_ffff_ffff_ffff) | 0x3ff0_0000_0000_0000
+    }
+
+    /// Returns a number in [0, 1), as `math.random()`
+    pub fn random(&mut self) -> f64 {
+        f64::from_bits(self.step()) - 1.0
+    }
+
+    /// Returns an integer in [min, max], as `math.random(min, max)`
+    pub fn range(&mut self, min: usize, max: usize) -> usize {
+        let d = self.random();
+        (d * (max - min + 1) as f64).floor() as usize + min
+    }
+}
+
+/// The game's named random streams for one seed
+#[derive(Debug, Clone)]
+pub struct SeedRng {
+    seed: String,
+    hashed_seed: f64,
+    streams: HashMap<String, f64>,
+}
+
+impl SeedRng {
+    /// Creates the streams of a seed (e.g., "7LB2WVPK")
+    pub fn new(seed: &str) -> Self {
+        Self { seed: seed.to_string(), hashed_seed: pseudohash(seed), streams: HashMap::new() }
+    }
+
+    /// Steps a stream, returning the number the game seeds `math.random`
+    /// with (`pseudoseed`)
+    pub fn pseudoseed(&mut self, key: &str) -> f64 {
+        let seed = &self.seed;
+        let state = self
+            .streams
+            .entry(key.to_string())
+            .or_insert_with(|| pseudohash(&format!("{}{}", key, seed)));
+        let next = (2.134453429141 + *state * 1.72431234) % 1.0;
+        // The game rounds each state to 13 decimals by printing it
+        *state = format!("{:.13}", next).parse::<f64>().unwrap_or(next).abs();
+        (*state + self.hashed_seed) / 2.0
+    }
+
+    /// Returns a number in [0, 1) from a stream (`pseudorandom`)
+    pub fn random(&mut self, key: &str) -> f64 {
+        LuaRandom::new(self.pseudoseed(key)).random()
+    }
+
+    /// Picks an index into a list of `len` items from a stream
+    /// (`pseudorandom_element`)
+    pub fn index(&mut self, key: &str, len: usize) -> usize {
+        LuaRandom::new(self.pseudoseed(key)).range(1, len) - 1
+    }
+
+    /// Picks from a pool the way the game does, drawing again from
+    /// "<key>_resample<n>" while the pick is unavailable
+    ///
+    /// Returns `None` if nothing in the pool is available.
+    fn pick<'a>(&mut self, key: &str, pool: &[(&'a str, bool)]) -> Option<&'a str> {
+        if !pool.iter().any(|(_, available)| *available) {
+            return None;
+        }
+        let mut index = self.index(key, pool.len());
+        let mut attempt = 1;
+        while !pool[index].1 {
+            attempt += 1;
+            index = self.index(&format!("{}_resample{}", key, attempt), pool.len());
+        }
+        Some(pool[index].0)
+    }
+}
+
+/// Normalizes a seed as the game's seed box does: upper case, letters and
+/// digits, at most eight characters
+pub fn normalize_seed(seed: &str) -> Result<String> {
+    let seed = seed.trim().to_uppercase();
+    anyhow::ensure!(
+        !seed.is_empty()
+            && seed.len() <= SEED_LENGTH
+            && seed.chars().all(|c| c.is_ascii_alphanumeric()),
+        "Invalid seed: {}. Seeds are up to {} letters and digits (e.g., 7LB2WVPK)",
+        seed,
+        SEED_LENGTH
+    );
+    Ok(seed)
+}
+
+/// What kind of card a shop slot holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShopCardKind {
+    Joker,
+    Tarot,
+    Planet,
+    /// Sold once Magic Trick is redeemed
+    PlayingCard,
+}
+
+/// The shop's odds and size, as the vouchers redeemed so far leave them
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ShopOdds {
+    /// Weight of each kind of card, in the order the game polls them
+    rates: [(ShopCardKind, f64); 4],
+    /// Multiplier on the chance of a joker edition other than Negative
+    edition_rate: f64,
+    /// Cards dealt on each shop visit
+    slots: usize,
+}
+
+impl Default for ShopOdds {
+    fn default() -> Self {
+        Self {
+            rates: [
+                (ShopCardKind::Joker, 20.0),
+                (ShopCardKind::Tarot, 4.0),
+                (ShopCardKind::Planet, 4.0),
+                (ShopCardKind::PlayingCard, 0.0),
+            ],
+            edition_rate: 1.0,
+            slots: 2,
+        }
+    }
+}
+
+impl ShopOdds {
+    /// Applies a redeemed voucher's change to the shop, if it makes one
+    fn redeem(&mut self, voucher: &str) {
+        let mut set_rate = |kind: ShopCardKind, rate: f64| {
+            if let Some(entry) = self.rates.iter_mut().find(|(k, _)| *k == kind) {
+                entry.1 = rate;
+            }
+        };
+        match voucher {
+            "v_tarot_merchant" => set_rate(ShopCardKind::Tarot, 9.6),
+            "v_tarot_tycoon" => set_rate(ShopCardKind::Tarot, 32.0),
+            "v_planet_merchant" => set_rate(ShopCardKind::Planet, 9.6),
+            "v_planet_tycoon" => set_rate(ShopCardKind::Planet, 32.0),
+            "v_magic_trick" => set_rate(ShopCardKind::PlayingCard, 4.0),
+            "v_hone" => self.edition_rate = 2.0,
+            "v_glow_up" => self.edition_rate = 4.0,
+            "v_overstock_norm" | "v_overstock_plus" => self.slots += 1,
+            _ => {}
+        }
+    }
+}
+
+/// A card predicted to be for sale in the shop
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ShopCard {
+    pub kind: ShopCardKind,
+    pub name: String,
+    /// The game's key for the card (e.g., "j_blueprint")
+    #[serde(skip)]
+    pub key: &'static str,
+    /// Edition of a joker, if it has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edition: Option<JokerEdition>,
+}
+
+/// What a seed holds for one ante
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AntePrediction {
+    pub ante: u32,
+    pub boss: String,
+    /// The game's key for the boss (e.g., "bl_hook")
+    #[serde(skip)]
+    pub boss_key: &'static str,
+    pub voucher: String,
+    #[serde(skip)]
+    pub voucher_key: &'static str,
+    /// Cards dealt on each of the ante's shop visits, once its voucher is
+    /// redeemed (two, more with Overstock)
+    pub shop_slots: usize,
+    /// The ante's shop cards in the order they are dealt (`shop_slots` per
+    /// visit, more with rerolls)
+    pub shop: Vec<ShopCard>,
+}
+
+/// A seed's early bosses, vouchers, and shop cards
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SeedPrediction {
+    pub seed: String,
+    pub antes: Vec<AntePrediction>,
+}
+
+impl SeedPrediction {
+    /// Predicts the first `antes` antes of a seed, with `shop_cards` shop
+    /// cards for each
+    pub fn new(seed: &str, antes: u32, shop_cards: usize) -> Result<Self> {
+        let seed = normalize_seed(seed)?;
+        let mut rng = SeedRng::new(&seed);
+        let mut seen: HashSet<&str> = HashSet::new();
+        let mut redeemed: HashSet<&str> = HashSet::new();
+        let mut bosses_used: HashMap<&str, u32> = HashMap::new();
+        let mut odds = ShopOdds::default();
+
+        let predictions = (1..=antes)
+            .map(|ante| {
+                let voucher = next_voucher(&mut rng, ante, &redeemed);
+                redeemed.insert(voucher.key);
+                let boss = next_boss(&mut rng, ante, &mut bosses_used);
+
+                // The voucher is bought once the first visit's cards are out
+                let first_visit = shop_cards.min(odds.slots);
+                let mut shop: Vec<ShopCard> = (0..first_visit)
+                    .map(|_| shop_card(&mut rng, ante, &odds, &mut seen))
+                    .collect();
+                odds.redeem(voucher.key);
+                shop.extend(
+                    (first_visit..shop_cards).map(|_| shop_card(&mut rng, ante, &odds, &mut seen)),
+                );
+                AntePrediction {
+                    ante,
+                    boss: boss.name.to_string(),
+                    boss_key: boss.key,
+                    voucher: voucher.name.to_string(),
+                    voucher_key: voucher.key,
+                    shop_slots: odds.slots,
+                    shop,
+                }
+            })
+            .collect();
+        Ok(Self { seed, antes: predictions })
+    }
+
+    /// Rates how well the seed's early shops and bosses suit an archetype
+    pub fn rate(&self, archetype: Archetype) -> SeedRating {
+        let mut points = 0.0;
+        let mut highlights = Vec::new();
+        for ante in &self.antes {
+            // Help arriving sooner is worth more
+            let weight = 1.0 / f64::from(ante.ante);
+            for card in &ante.shop {
+                let value = if archetype.jokers().contains(&card.key) {
+                    3.0
+                } else if STRONG_JOKERS.contains(&card.key) {
+                    2.0
+                } else if archetype.planet() == Some(card.key) {
+                    1.0
+                } else {
+                    continue;
+                };
+                points += value * weight;
+                highlights.push(format!("+ {} in ante {}'s shop", card.name, ante.ante));
+            }
+            let helps_planets = archetype.planet().is_some() && ante.voucher_key == "v_telescope";
+            if USEFUL_VOUCHERS.contains(&ante.voucher_key) || helps_planets {
+                points += 1.5 * weight;
+                highlights.push(format!("+ {} voucher in ante {}", ante.voucher, ante.ante));
+            }
+            if archetype.counters().contains(&ante.boss_key) {
+                points -= 2.0 * weight;
+                highlights.push(format!("- {} as ante {}'s boss", ante.boss, ante.ante));
+            }
+        }
+
+        // Diminishing returns: a few good finds already make a good seed
+        let score = 10.0 * (1.0 - (-points.max(0.0) / 6.0).exp());
+        SeedRating { archetype, score: (score * 10.0).round() / 10.0, highlights }
+    }
+}
+
+/// How well a seed suits an archetype
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SeedRating {
+    pub archetype: Archetype,
+    /// From 0 (nothing for the archetype) to 10
+    pub score: f64,
+    /// What raised or lowered the score, e.g. "+ Droll Joker in ante 1's
+    /// shop"
+    pub highlights: Vec<String>,
+}
+
+/// A build to rate seeds for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Archetype {
+    Flush,
+    Straight,
+    Pairs,
+    FaceCards,
+    HighCard,
+}
+
+/// Jokers that carry any build
+const STRONG_JOKERS: [&str; 12] = [
+    "j_blueprint",
+    "j_brainstorm",
+    "j_stencil",
+    "j_loyalty_card",
+    "j_gros_michel",
+    "j_campfire",
+    "j_acrobat",
+    "j_baseball",
+    "j_hologram",
+    "j_obelisk",
+    "j_throwback",
+    "j_madness",
+];
+
+/// Vouchers that help any build early
+const USEFUL_VOUCHERS: [&str; 5] =
+    ["v_grabber", "v_wasteful", "v_paint_brush", "v_clearance_sale", "v_overstock_norm"];
+
+impl Archetype {
+    /// Returns every archetype
+    pub fn all() -> [Archetype; 5] {
+        [
+            Archetype::Flush,
+            Archetype::Straight,
+            Archetype::Pairs,
+            Archetype::FaceCards,
+            Archetype::HighCard,
+        ]
+    }
+
+    /// Returns the archetype's name, e.g. for headings
+    pub fn name(&self) -> &'static str {
+        match self {
+            Archetype::Flush => "Flush",
+            Archetype::Straight => "Straight",
+            Archetype::Pairs => "Pairs",
+            Archetype::FaceCards => "Face Cards",
+            Archetype::HighCard => "High Card",
+        }
+    }
+
+    /// Jokers the archetype is built around
+    fn jokers(&self) -> &'static [&'static str] {
+        match self {
+            Archetype::Flush => &[
+                "j_droll",
+                "j_crafty",
+                "j_tribe",
+                "j_four_fingers",
+                "j_smeared",
+                "j_greedy_joker",
+                "j_lusty_joker",
+                "j_wrathful_joker",
+                "j_gluttenous_joker",
+                "j_rough_gem",
+                "j_bloodstone",
+                "j_arrowhead",
+                "j_onyx_agate",
+                "j_ancient",
+            ],
+            Archetype::Straight => &[
+                "j_crazy",
+                "j_devious",
+                "j_order",
+                "j_four_fingers",
+                "j_shortcut",
+                "j_runner",
+                "j_superposition",
+            ],
+            Archetype::Pairs => &[
+                "j_jolly",
+                "j_zany",
+                "j_mad",
+                "j_sly",
+                "j_wily",
+                "j_clever",
+                "j_duo",
+                "j_trio",
+                "j_family",
+                "j_trousers",
+                "j_card_sharp",
+            ],
+            Archetype::FaceCards => &[
+                "j_scary_face",
+                "j_smiley",
+                "j_pareidolia",
+                "j_sock_and_buskin",
+                "j_photograph",
+                "j_business",
+                "j_midas_mask",
+                "j_reserved_parking",
+            ],
+            Archetype::HighCard => &[
+                "j_raised_fist",
+                "j_supernova",
+                "j_ride_the_bus",
+                "j_green_joker",
+                "j_mime",
+                "j_baron",
+            ],
+        }
+    }
+
+    /// The Planet card that levels the archetype's hand, if it has one
+    fn planet(&self) -> Option<&'static str> {
+        match self {
+            Archetype::Flush => Some("c_jupiter"),
+            Archetype::Straight => Some("c_saturn"),
+            Archetype::Pairs => Some("c_mercury"),
+            Archetype::FaceCards => None,
+            Archetype::HighCard => Some("c_pluto"),
+        }
+    }
+
+    /// Boss blinds that get in the archetype's way
+    fn counters(&self) -> &'static [&'static str] {
+        match self {
+            Archetype::Flush => &["bl_club", "bl_goad", "bl_window", "bl_head", "bl_eye"],
+            Archetype::Straight => &["bl_eye", "bl_needle"],
+            Archetype::Pairs => &["bl_psychic", "bl_eye"],
+            Archetype::FaceCards => &["bl_plant"],
+            Archetype::HighCard => &["bl_psychic", "bl_eye"],
+        }
+    }
+}
+
+impl std::str::FromStr for Archetype {
+    type Err = anyhow::Error;
+
+    /// Parses an archetype name, ignoring case, spaces, dashes, and
+    /// underscores (e.g., "flush", "face_cards", "High Card")
+    fn from_str(s: &str) -> Result<Self> {
+        let normalized: String = s
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '_' | '-'))
+            .collect::<String>()
+            .to_lowercase();
+        match normalized.as_str() {
+            "pair" => return Ok(Archetype::Pairs),
+            "face" => return Ok(Archetype::FaceCards),
+            _ => {}
+        }
+        Archetype::all()
+            .into_iter()
+            .find(|archetype| format!("{:?}", archetype).to_lowercase() == normalized)
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Unknown archetype: {}. Use flush, straight, pairs, face_cards, or high_card",
+                    s
+                )
+            })
+    }
+}
+
+/// Draws the ante's voucher, given those redeemed so far
+fn next_voucher(
+    rng: &mut SeedRng,
+    ante: u32,
+    redeemed: &HashSet<&str>,
+) -> &'static pools::VoucherEntry {
+    let pool: Vec<(&str, bool)> = VOUCHERS
+        .iter()
+        .map(|voucher| {
+            let unlocked = voucher.requires.is_none_or(|base| redeemed.contains(base));
+            (voucher.key, unlocked && !redeemed.contains(voucher.key))
+        })
+        .collect();
+    let key = rng.pick(&format!("Voucher{}", ante), &pool).unwrap_or("v_blank");
+    VOUCHERS.iter().find(|voucher| voucher.key == key).expect("picked from the pool")
+}
+
+/// Draws the ante's boss from those eligible that have been used least
+fn next_boss(
+    rng: &mut SeedRng,
+    ante: u32,
+    bosses_used: &mut HashMap<&'static str, u32>,
+) -> &'static pools::BossEntry {
+    let showdown_ante = ante >= 2 && ante.is_multiple_of(WIN_ANTE);
+    let eligible: Vec<&pools::BossEntry> = BOSSES
+        .iter()
+        .filter(|boss| match boss.showdown {
+            true => showdown_ante,
+            false => !showdown_ante && boss.min_ante <= ante.max(1),
+        })
+        .collect();
+    let uses = |boss: &pools::BossEntry| bosses_used.get(boss.key).copied().unwrap_or(0);
+    let least = eligible.iter().map(|boss| uses(boss)).min().unwrap_or(0);
+    let pool: Vec<&pools::BossEntry> =
+        eligible.into_iter().filter(|boss| uses(boss) == least).collect();
+
+    let boss = pool[rng.index("boss", pool.len())];
+    *bosses_used.entry(boss.key).or_default() += 1;
+    boss
+}
+
+/// Deals the next shop card of an ante, keeping cards already dealt out
+/// of later pools as the game does
+fn shop_card(
+    rng: &mut SeedRng,
+    ante: u32,
+    odds: &ShopOdds,
+    seen: &mut HashSet<&'static str>,
+) -> ShopCard {
+    let total: f64 = odds.rates.iter().map(|(_, rate)| rate).sum();
+    let polled = rng.random(&format!("cdt{}", ante)) * total;
+    let mut threshold = 0.0;
+    let kind = odds
+        .rates
+        .iter()
+        .find(|(_, rate)| {
+            threshold += rate;
+            *rate > 0.0 && polled <= threshold
+        })
+        .map_or(ShopCardKind::Joker, |(kind, _)| *kind);
+
+    let (key, name, edition) = match kind {
+        ShopCardKind::Joker => {
+            let rarity = rng.random(&format!("rarity{}sho", ante));
+            let (rarity, level) = match rarity {
+                r if r > 0.95 => (JokerRarity::Rare, 3),
+                r if r > 0.7 => (JokerRarity::Uncommon, 2),
+                _ => (JokerRarity::Common, 1),
+            };
+            let pool: Vec<(&str, bool)> = JOKERS
+                .iter()
+                .filter(|joker| joker.rarity == rarity)
+                .map(|joker| {
+                    let available = !seen.contains(joker.key) && !GATED_JOKERS.contains(&joker.key);
+                    (joker.key, available)
+                })
+                .collect();
+            let key = rng.pick(&format!("Joker{}sho{}", level, ante), &pool).unwrap_or("j_joker");
+            let name = JOKERS.iter().find(|joker| joker.key == key).map(|joker| joker.name);
+            let edition = poll_edition(rng, &format!("edisho{}", ante), odds.edition_rate);
+            (key, name, edition)
+        }
+        ShopCardKind::Tarot => {
+            let pool: Vec<(&str, bool)> =
+                TAROTS.iter().map(|(key, _)| (*key, !seen.contains(key))).collect();
+            let key = rng.pick(&format!("Tarotsho{}", ante), &pool).unwrap_or("c_strength");
+            (key, TAROTS.iter().find(|(k, _)| *k == key).map(|(_, name)| *name), None)
+        }
+        ShopCardKind::Planet => {
+            let pool: Vec<(&str, bool)> = PLANETS
+                .iter()
+                .map(|(key, _, softlock)| (*key, !softlock && !seen.contains(key)))
+                .collect();
+            let key = rng.pick(&format!("Planetsho{}", ante), &pool).unwrap_or("c_pluto");
+            (key, PLANETS.iter().find(|(k, _, _)| *k == key).map(|(_, name, _)| *name), None)
+        }
+        ShopCardKind::PlayingCard => {
+            // Playing cards are never kept out of later shops
+            let key = PLAYING_CARDS[rng.index(&format!("frontsho{}", ante), PLAYING_CARDS.len())];
+            return ShopCard { kind, name: playing_card_name(key), key, edition: None };
+        }
+    };
+
+    seen.insert(key);
+    ShopCard { kind, name: name.unwrap_or(key).to_string(), key, edition }
+}
+
+/// Names a playing card from its game key (e.g., "H_K" is "King of Hearts")
+fn playing_card_name(key: &str) -> String {
+    let (suit, rank) = key.split_once('_').unwrap_or_default();
+    let rank = match rank {
+        "A" => "Ace",
+        "K" => "King",
+        "Q" => "Queen",
+        "J" => "Jack",
+        "T" => "10",
+        rank => rank,
+    };
+    let suit = match suit {
+        "C" => "Clubs",
+        "D" => "Diamonds",
+        "H" => "Hearts",
+        _ => "Spades",
+    };
+    format!("{} of {}", rank, suit)
+}
+
+/// Rolls a shop joker's edition, with the chance of each but Negative
+/// scaled by Hone or Glow Up's edition rate
+fn poll_edition(rng: &mut SeedRng, key: &str, edition_rate: f64) -> Option<JokerEdition> {
+    match rng.random(key) {
+        poll if poll > 1.0 - 0.003 => Some(JokerEdition::Negative),
+        poll if poll > 1.0 - 0.006 * edition_rate => Some(JokerEdition::Polychrome),
+        poll if poll > 1.0 - 0.02 * edition_rate => Some(JokerEdition::Holographic),
+        poll if poll > 1.0 - 0.04 * edition_rate => Some(JokerEdition::Foil),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pseudohash() {
+        for text in ["", "A", "7LB2WVPK", "boss7LB2WVPK"] {
+            let hash = pseudohash(text);
+            assert!((0.0..1.0).contains(&hash) || text.is_empty(), "{}", text);
+        }
+        assert_eq!(pseudohash(""), 1.0);
+        assert_ne!(pseudohash("AB"), pseudohash("BA"));
+    }
+
+    #[test]
+    fn test_lua_random() {
+        let mut random = LuaRandom::new(0.5);
+        let numbers: Vec<f64> = (0..1000).map(|_| random.random()).collect();
+        assert!(numbers.iter().all(|n| (0.0..1.0).contains(n)));
+        let mean = numbers.iter().sum::<f64>() / numbers.len() as f64;
+        assert!((mean - 0.5).abs() < 0.05);
+
+        // Reseeding repeats the sequence
+        let mut again = LuaRandom::new(0.5);
+        assert_eq!(again.random(), numbers[0]);
+        let rolls: Vec<usize> = (0..200).map(|_| again.range(1, 6)).collect();
+        assert!(rolls.iter().all(|roll| (1..=6).contains(roll)));
+        assert!((1..=6).all(|face| rolls.contains(&face)));
+    }
+
+    #[test]
+    fn test_streams_are_independent() {
+        let mut rng = SeedRng::new("7LB2WVPK");
+        let first = rng.pseudoseed("boss");
+        assert_ne!(rng.pseudoseed("boss"), first);
+
+        // Drawing from another stream does not move this one
+        let mut other = SeedRng::new("7LB2WVPK");
+        other.pseudoseed("Voucher1");
+        assert_eq!(other.pseudoseed("boss"), first);
+    }
+
+    #[test]
+    fn test_predict_seed() {
+        let prediction = SeedPrediction::new("7lb2wvpk", 8, 6).unwrap();
+        assert_eq!(prediction.seed, "7LB2WVPK");
+        assert_eq!(prediction, SeedPrediction::new("7LB2WVPK", 8, 6).unwrap());
+        assert_ne!(prediction, SeedPrediction::new("7LB2WVPL", 8, 6).unwrap());
+
+        let bosses: Vec<&pools::BossEntry> = prediction
+            .antes
+            .iter()
+            .map(|ante| BOSSES.iter().find(|boss| boss.key == ante.boss_key).unwrap())
+            .collect();
+        for (ante, boss) in (1..).zip(&bosses) {
+            assert_eq!(boss.showdown, ante == 8, "{}", boss.name);
+            assert!(boss.min_ante <= ante, "{} in ante {}", boss.name, ante);
+        }
+        // Bosses repeat only once every eligible one has appeared
+        let regular: HashSet<&str> = bosses[..7].iter().map(|boss| boss.key).collect();
+        assert_eq!(regular.len(), 7);
+
+        // Each voucher is redeemed, so none repeats, and upgrades follow
+        // their base
+        let vouchers: Vec<&str> = prediction.antes.iter().map(|ante| ante.voucher_key).collect();
+        for (i, key) in vouchers.iter().enumerate() {
+            assert!(!vouchers[..i].contains(key));
+            let entry = VOUCHERS.iter().find(|voucher| voucher.key == *key).unwrap();
+            if let Some(base) = entry.requires {
+                assert!(vouchers[..i].contains(&base), "{} before {}", key, base);
+            }
+        }
+
+        // No card is dealt twice, and gated jokers stay out
+        let shop: Vec<&ShopCard> = prediction.antes.iter().flat_map(|ante| &ante.shop).collect();
+        let keys: HashSet<&str> = shop.iter().map(|card| card.key).collect();
+        assert_eq!(keys.len(), shop.len());
+        assert!(shop.iter().all(|card| !GATED_JOKERS.contains(&card.key)));
+        assert!(shop.iter().any(|card| card.kind == ShopCardKind::Joker));
+
+        assert!(SeedPrediction::new("TOO-LONG-SEED", 1, 1).is_err());
+    }
+
+    #[test]
+    fn test_vouchers_change_shop_odds() {
+        let mut odds = ShopOdds::default();
+        for voucher in ["v_tarot_tycoon", "v_magic_trick", "v_glow_up", "v_overstock_plus"] {
+            odds.redeem(voucher);
+        }
+        odds.redeem("v_overstock_norm");
+        assert_eq!(odds.rates[1], (ShopCardKind::Tarot, 32.0));
+        assert_eq!(odds.rates[3], (ShopCardKind::PlayingCard, 4.0));
+        assert_eq!(odds.edition_rate, 4.0);
+        assert_eq!(odds.slots, 4);
+
+        // Magic Trick is bought in ante 1's first shop, after its two cards
+        let prediction = SeedPrediction::new("2", 2, 6).unwrap();
+        assert_eq!(prediction.antes[0].voucher_key, "v_magic_trick");
+        let kinds: Vec<ShopCardKind> =
+            prediction.antes[0].shop.iter().map(|card| card.kind).collect();
+        assert!(!kinds[..2].contains(&ShopCardKind::PlayingCard));
+        assert!(kinds[2..].contains(&ShopCardKind::PlayingCard));
+        assert_eq!(playing_card_name("H_T"), "10 of Hearts");
+    }
+
+    #[test]
+    fn test_seed_golden() {
+        // Pinned predictions for one seed, so a change to the streams,
+        // pools, or voucher odds shows up here
+        let prediction = SeedPrediction::new("2", 2, 6).unwrap();
+        let antes: Vec<(&str, &str, Vec<&str>)> = prediction
+            .antes
+            .iter()
+            .map(|ante| {
+                let shop = ante.shop.iter().map(|card| card.name.as_str()).collect();
+                (ante.boss.as_str(), ante.voucher.as_str(), shop)
+            })
+            .collect();
+        assert_eq!(
+            antes,
+            vec![
+                (
+                    "The Pillar",
+                    "Magic Trick",
+                    vec!["Uranus", "Drunkard", "Saturn", "Egg", "5 of Diamonds", "The Fool"],
+                ),
+                (
+                    "The Fish",
+                    "Blank",
+                    vec![
+                        "Hologram",
+                        "Crafty Joker",
+                        "Droll Joker",
+                        "7 of Spades",
+                        "Constellation",
+                        "Walkie Talkie",
+                    ],
+                ),
+            ]
+        );
+        assert_eq!(prediction.antes[1].shop[1].edition, Some(JokerEdition::Holographic));
+    }
+
+    #[test]
+    fn test_rate_seed() {
+        let prediction = SeedPrediction::new("7LB2WVPK", 4, 6).unwrap();
+        for archetype in Archetype::all() {
+            let rating = prediction.rate(archetype);
+            assert!((0.0..=10.0).contains(&rating.score));
+        }
+        assert_eq!("face_cards".parse::<Archetype>().unwrap(), Archetype::FaceCards);
+        assert_eq!("High Card".parse::<Archetype>().unwrap(), Archetype::HighCard);
+        assert_eq!("pair".parse::<Archetype>().unwrap(), Archetype::Pairs);
+        assert!("mult".parse::<Archetype>().is_err());
+    }
+}
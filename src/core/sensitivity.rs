@@ -0,0 +1,150 @@
+//! Deck sensitivity analysis
+//!
+//! Tarot cards, Spectral cards, and packs change the deck a few cards at a
+//! time. This module describes such changes as perturbations, so the
+//! simulator can measure how much each one moves the score and show which
+//! purchases are worth making.
+
+use super::card::{Card, Enhancement, Rank, Suit};
+use super::simulator::PairedDifference;
+use serde::{Deserialize, Serialize};
+
+/// A change to the deck
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DeckPerturbation {
+    /// Removes every card ranked below the given rank (Stone cards stay)
+    RemoveBelow(Rank),
+    /// Adds a full 13-card suit
+    AddSuit(Suit),
+    /// Adds copies of a card
+    AddCards { card: Card, count: usize },
+    /// Enhances the first `count` unenhanced cards of the given rank
+    Enhance {
+        rank: Rank,
+        enhancement: Enhancement,
+        count: usize,
+    },
+}
+
+impl DeckPerturbation {
+    /// Returns the perturbations analyzed by default: trimming 2–5s, an
+    /// extra suit of each kind, four Steel Kings, and two Glass Aces
+    pub fn defaults() -> Vec<DeckPerturbation> {
+        let mut perturbations = vec![DeckPerturbation::RemoveBelow(Rank::Six)];
+        perturbations.extend(
+            [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades].map(DeckPerturbation::AddSuit),
+        );
+        perturbations.push(DeckPerturbation::AddCards {
+            card: Card::new(Rank::King, Suit::Spades).with_enhancement(Enhancement::Steel),
+            count: 4,
+        });
+        perturbations.push(DeckPerturbation::Enhance {
+            rank: Rank::Ace,
+            enhancement: Enhancement::Glass,
+            count: 2,
+        });
+        perturbations
+    }
+
+    /// Returns the deck with the change applied
+    pub fn apply(&self, deck: &[Card]) -> Vec<Card> {
+        let mut deck = deck.to_vec();
+        match self {
+            DeckPerturbation::RemoveBelow(rank) => {
+                deck.retain(|card| !card.has_rank() || card.rank.value() >= rank.value());
+            }
+            DeckPerturbation::AddSuit(suit) => {
+                let mut rank = Rank::Two;
+                for _ in 0..13 {
+                    deck.push(Card::new(rank, *suit));
+                    rank = rank.next();
+                }
+            }
+            DeckPerturbation::AddCards { card, count } => {
+                deck.extend(std::iter::repeat_n(card.clone(), *count));
+            }
+            DeckPerturbation::Enhance {
+                rank,
+                enhancement,
+                count,
+            } => {
+                deck.iter_mut()
+                    .filter(|card| card.rank == *rank && card.enhancement == Enhancement::None)
+                    .take(*count)
+                    .for_each(|card| card.enhancement = *enhancement);
+            }
+        }
+        deck
+    }
+}
+
+impl std::fmt::Display for DeckPerturbation {
+    /// Describes the change, e.g. "Remove cards below 6" or "Add 4 Steel K♠"
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeckPerturbation::RemoveBelow(rank) => {
+                let below = Card::new(*rank, Suit::Spades).to_string();
+                write!(f, "Remove cards below {}", below.trim_end_matches('♠'))
+            }
+            DeckPerturbation::AddSuit(suit) => write!(f, "Add 13 {:?}", suit),
+            DeckPerturbation::AddCards { card, count } => match card.enhancement {
+                Enhancement::None => write!(f, "Add {} {}", count, card),
+                enhancement => write!(f, "Add {} {:?} {}", count, enhancement, card),
+            },
+            DeckPerturbation::Enhance {
+                rank,
+                enhancement,
+                count,
+            } => {
+                let card = Card::new(*rank, Suit::Spades).to_string();
+                write!(f, "Make {} {}s {:?}", count, card.trim_end_matches('♠'), enhancement)
+            }
+        }
+    }
+}
+
+/// The score impact of one deck perturbation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeckSensitivity {
+    pub perturbation: DeckPerturbation,
+    /// Mean score with the perturbed deck
+    pub mean_score: f64,
+    /// Run-by-run change from the unchanged deck
+    pub impact: PairedDifference,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::simulator::create_standard_deck;
+
+    #[test]
+    fn test_apply_perturbations() {
+        let deck = create_standard_deck();
+
+        let trimmed = DeckPerturbation::RemoveBelow(Rank::Six).apply(&deck);
+        assert_eq!(trimmed.len(), 36);
+        assert!(trimmed.iter().all(|card| card.rank.value() >= 6));
+
+        let hearts = DeckPerturbation::AddSuit(Suit::Hearts).apply(&deck);
+        assert_eq!(hearts.iter().filter(|card| card.suit == Suit::Hearts).count(), 26);
+
+        let steel_king = Card::new(Rank::King, Suit::Spades).with_enhancement(Enhancement::Steel);
+        let kings = DeckPerturbation::AddCards {
+            card: steel_king.clone(),
+            count: 4,
+        };
+        assert_eq!(kings.apply(&deck).iter().filter(|card| **card == steel_king).count(), 4);
+        assert_eq!(kings.to_string(), "Add 4 Steel K♠");
+
+        let glass = DeckPerturbation::Enhance {
+            rank: Rank::Ace,
+            enhancement: Enhancement::Glass,
+            count: 2,
+        };
+        let glass_deck = glass.apply(&deck);
+        assert_eq!(glass_deck.iter().filter(|c| c.enhancement == Enhancement::Glass).count(), 2);
+        assert_eq!(glass.to_string(), "Make 2 As Glass");
+        assert_eq!(DeckPerturbation::RemoveBelow(Rank::Ten).to_string(), "Remove cards below 10");
+    }
+}
@@ -0,0 +1,755 @@
+//! Shop offers
+//!
+//! Jokers, vouchers, planets, and booster packs that can be bought between
+//! blinds. Each offer knows its price and how it changes a run, so the
+//! solver can compare purchases by simulating the run with and without them.
+//!
+//! A [`ShopModel`] rolls shops the way the game does, from weights that can
+//! be loaded from a file as the game patches, and buys from them like a
+//! simple player, so full-run simulations can spend their money.
+
+use super::consumable::Planet;
+use super::hand::HandType;
+use super::joker::{Joker, JokerEdition, JokerKind, JokerRarity};
+use super::names::unknown_name;
+use super::run::GameRunState;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Price of every voucher in the shop
+pub const VOUCHER_COST: u32 = 10;
+
+/// Price of a Planet card in the shop
+pub const PLANET_COST: u32 = 3;
+
+/// A voucher whose effect on scoring is modeled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Voucher {
+    Grabber,      // +1 hand per round
+    NachoTong,    // +1 hand per round
+    Wasteful,     // +1 discard per round
+    Recyclomancy, // +1 discard per round
+    PaintBrush,   // +1 hand size
+    Palette,      // +1 hand size
+}
+
+impl Voucher {
+    /// Returns every modeled voucher
+    pub fn all() -> [Voucher; 6] {
+        [
+            Voucher::Grabber,
+            Voucher::NachoTong,
+            Voucher::Wasteful,
+            Voucher::Recyclomancy,
+            Voucher::PaintBrush,
+            Voucher::Palette,
+        ]
+    }
+
+    /// Returns the voucher's name as shown in the game
+    pub fn name(&self) -> &'static str {
+        match self {
+            Voucher::Grabber => "Grabber",
+            Voucher::NachoTong => "Nacho Tong",
+            Voucher::Wasteful => "Wasteful",
+            Voucher::Recyclomancy => "Recyclomancy",
+            Voucher::PaintBrush => "Paint Brush",
+            Voucher::Palette => "Palette",
+        }
+    }
+
+    /// Returns the extra cards held in hand with this voucher
+    pub fn hand_size_bonus(&self) -> usize {
+        match self {
+            Voucher::PaintBrush | Voucher::Palette => 1,
+            _ => 0,
+        }
+    }
+
+    /// Returns the voucher that must be redeemed before this one can appear
+    pub fn requires(&self) -> Option<Voucher> {
+        match self {
+            Voucher::NachoTong => Some(Voucher::Grabber),
+            Voucher::Recyclomancy => Some(Voucher::Wasteful),
+            Voucher::Palette => Some(Voucher::PaintBrush),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for Voucher {
+    type Err = anyhow::Error;
+
+    /// Parses a voucher name, ignoring case, spaces, dashes, and underscores
+    /// (e.g., "Nacho Tong", "paint_brush")
+    ///
+    /// An unknown name is reported with the closest vouchers' names.
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let normalize = |name: &str| -> String {
+            name.chars()
+                .filter(|c| !matches!(c, ' ' | '_' | '-'))
+                .collect::<String>()
+                .to_lowercase()
+        };
+        let normalized = normalize(s);
+
+        Voucher::all()
+            .into_iter()
+            .find(|voucher| normalize(voucher.name()) == normalized)
+            .ok_or_else(|| {
+                let names = Voucher::all().map(|voucher| voucher.name());
+                unknown_name("voucher", s, &names, normalize)
+            })
+    }
+}
+
+/// Something for sale in the shop
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ShopOffer {
+    Joker(Joker),
+    Voucher(Voucher),
+    Planet(Planet),
+    /// A booster pack, from which the best of `choices` is taken
+    Pack {
+        name: String,
+        cost: u32,
+        choices: Vec<ShopOffer>,
+    },
+}
+
+impl ShopOffer {
+    /// Returns the offer's name as shown in the game
+    pub fn name(&self) -> String {
+        match self {
+            ShopOffer::Joker(joker) => joker.kind.name().to_string(),
+            ShopOffer::Voucher(voucher) => voucher.name().to_string(),
+            ShopOffer::Planet(Planet::PlanetX) => "Planet X".to_string(),
+            ShopOffer::Planet(planet) => format!("{:?}", planet),
+            ShopOffer::Pack { name, .. } => name.clone(),
+        }
+    }
+
+    /// Returns the shop price
+    ///
+    /// Joker editions add to the base price: $2 for Foil, $3 for
+    /// Holographic, and $5 for Polychrome or Negative.
+    pub fn cost(&self) -> u32 {
+        match self {
+            ShopOffer::Joker(joker) => {
+                let edition = match joker.edition {
+                    JokerEdition::None => 0,
+                    JokerEdition::Foil => 2,
+                    JokerEdition::Holographic => 3,
+                    JokerEdition::Polychrome | JokerEdition::Negative => 5,
+                };
+                joker.kind.cost() + edition
+            }
+            ShopOffer::Voucher(_) => VOUCHER_COST,
+            ShopOffer::Planet(_) => PLANET_COST,
+            ShopOffer::Pack { cost, .. } => *cost,
+        }
+    }
+
+    /// Returns the extra cards held in hand after buying this offer
+    pub fn hand_size_bonus(&self) -> usize {
+        match self {
+            ShopOffer::Voucher(voucher) => voucher.hand_size_bonus(),
+            _ => 0,
+        }
+    }
+
+    /// Applies the purchase to the run, without paying for it
+    ///
+    /// Jokers go to the rightmost slot and planets are used immediately. A
+    /// pack has no effect of its own; apply one of its choices instead.
+    pub fn apply(&self, run: &mut GameRunState) {
+        match self {
+            ShopOffer::Joker(joker) => run.jokers.push(joker.clone()),
+            ShopOffer::Voucher(voucher) => {
+                match voucher {
+                    Voucher::Grabber | Voucher::NachoTong => run.hands_remaining += 1,
+                    Voucher::Wasteful | Voucher::Recyclomancy => run.discards_remaining += 1,
+                    Voucher::PaintBrush | Voucher::Palette => {}
+                }
+                run.vouchers.push(voucher.name().to_string());
+            }
+            ShopOffer::Planet(planet) => planet.apply(&mut run.hand_levels),
+            ShopOffer::Pack { .. } => {}
+        }
+    }
+}
+
+impl std::fmt::Display for ShopOffer {
+    /// Formats the offer as its name and price (e.g., "Baron ($8)")
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (${})", self.name(), self.cost())
+    }
+}
+
+/// Shop slots filled with booster packs
+const PACK_SLOTS: usize = 2;
+
+/// Planet cards offered before their secret hand has been played
+const COMMON_PLANETS: [Planet; 9] = [
+    Planet::Pluto,
+    Planet::Mercury,
+    Planet::Uranus,
+    Planet::Venus,
+    Planet::Saturn,
+    Planet::Jupiter,
+    Planet::Earth,
+    Planet::Mars,
+    Planet::Neptune,
+];
+
+/// What a booster pack holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PackContents {
+    /// Jokers (Buffoon packs)
+    Jokers,
+    /// Planet cards (Celestial packs)
+    Planets,
+}
+
+/// A booster pack the shop can offer, and how often
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackWeight {
+    pub name: String,
+    pub contents: PackContents,
+    /// Cards shown when the pack is opened, one of which is taken
+    pub choices: usize,
+    pub cost: u32,
+    /// Relative chance of the pack filling a pack slot
+    pub weight: f64,
+}
+
+impl PackWeight {
+    fn new(name: &str, contents: PackContents, choices: usize, cost: u32, weight: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            contents,
+            choices,
+            cost,
+            weight,
+        }
+    }
+}
+
+/// The odds and prices a shop is rolled from
+///
+/// The defaults are the game's. Every field can be overridden from JSON,
+/// and fields left out keep their defaults.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShopWeights {
+    /// Card slots in each shop
+    pub card_slots: usize,
+    /// Relative chance of a card slot holding a joker
+    pub joker_rate: f64,
+    /// Relative chance of a card slot holding a Tarot card, which is not
+    /// offered since Tarot cards are not simulated
+    pub tarot_rate: f64,
+    /// Relative chance of a card slot holding a Planet card
+    pub planet_rate: f64,
+    /// Chance of a shop joker being Common
+    pub common_rate: f64,
+    /// Chance of a shop joker being Uncommon
+    pub uncommon_rate: f64,
+    /// Chance of a shop joker being Rare
+    pub rare_rate: f64,
+    /// Chance of a shop joker being Foil
+    pub foil_rate: f64,
+    /// Chance of a shop joker being Holographic
+    pub holographic_rate: f64,
+    /// Chance of a shop joker being Polychrome
+    pub polychrome_rate: f64,
+    /// Chance of a shop joker being Negative
+    pub negative_rate: f64,
+    /// Packs whose contents are simulated
+    pub packs: Vec<PackWeight>,
+    /// Relative chance of a pack slot holding a pack that is not simulated
+    /// (Arcana, Standard, Spectral, and Mega packs)
+    pub other_pack_weight: f64,
+    /// Price of the first reroll in a shop
+    pub reroll_cost: u32,
+    /// Price added by each further reroll in the same shop
+    pub reroll_increase: u32,
+    /// Chance of an ante's voucher being one whose effect is simulated
+    pub modeled_voucher_rate: f64,
+}
+
+impl Default for ShopWeights {
+    fn default() -> Self {
+        Self {
+            card_slots: 2,
+            joker_rate: 20.0,
+            tarot_rate: 4.0,
+            planet_rate: 4.0,
+            common_rate: 0.70,
+            uncommon_rate: 0.25,
+            rare_rate: 0.05,
+            foil_rate: 0.02,
+            holographic_rate: 0.014,
+            polychrome_rate: 0.003,
+            negative_rate: 0.003,
+            packs: vec![
+                PackWeight::new("Buffoon Pack", PackContents::Jokers, 2, 4, 1.2),
+                PackWeight::new("Jumbo Buffoon Pack", PackContents::Jokers, 4, 6, 0.6),
+                PackWeight::new("Celestial Pack", PackContents::Planets, 3, 4, 4.0),
+                PackWeight::new("Jumbo Celestial Pack", PackContents::Planets, 5, 6, 2.0),
+            ],
+            other_pack_weight: 14.12,
+            reroll_cost: 5,
+            reroll_increase: 1,
+            // Grabber, Wasteful, and Paint Brush of the 16 base vouchers
+            modeled_voucher_rate: 3.0 / 16.0,
+        }
+    }
+}
+
+/// Picks one of the options with chance proportional to its weight
+fn pick<'a, T, R: Rng + ?Sized>(options: &'a [(T, f64)], rng: &mut R) -> Option<&'a T> {
+    let total: f64 = options.iter().map(|(_, weight)| weight.max(0.0)).sum();
+    let mut roll = rng.gen_range(0.0..1.0) * total;
+    for (option, weight) in options {
+        roll -= weight.max(0.0);
+        if roll < 0.0 {
+            return Some(option);
+        }
+    }
+    None
+}
+
+/// Rolls shops for a run and buys from them like a simple player
+///
+/// Jokers are bought while there are free joker slots, rarest and priciest
+/// first; jokers whose target is chosen each round are passed over. A
+/// voucher is bought whenever it is affordable. Planets (and Celestial
+/// packs) are bought for the hand the run plays most. Once nothing else is
+/// worth buying, the shop is rerolled while a joker slot is free. Money
+/// below the reserve is never spent.
+#[derive(Debug, Clone, Default)]
+pub struct ShopModel {
+    weights: ShopWeights,
+    reserve: u32,
+    max_rerolls: u32,
+}
+
+/// What one shop visit bought
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShopVisit {
+    /// Offers bought, with the choice taken in place of each pack
+    pub bought: Vec<ShopOffer>,
+    /// Money spent, including rerolls
+    pub spent: u32,
+    pub rerolls: u32,
+}
+
+impl ShopModel {
+    /// Creates a model with the game's weights that spends all its money
+    /// and never rerolls
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the odds and prices shops are rolled from
+    pub fn with_weights(mut self, weights: ShopWeights) -> Self {
+        self.weights = weights;
+        self
+    }
+
+    /// Sets the money kept back from spending (e.g., for interest)
+    pub fn with_reserve(mut self, reserve: u32) -> Self {
+        self.reserve = reserve;
+        self
+    }
+
+    /// Sets the most rerolls bought in one shop
+    pub fn with_max_rerolls(mut self, max_rerolls: u32) -> Self {
+        self.max_rerolls = max_rerolls;
+        self
+    }
+
+    /// Returns the odds and prices shops are rolled from
+    pub fn weights(&self) -> &ShopWeights {
+        &self.weights
+    }
+
+    /// Returns the price of the next reroll after `rerolls` in this shop
+    pub fn reroll_cost(&self, rerolls: u32) -> u32 {
+        self.weights.reroll_cost + rerolls * self.weights.reroll_increase
+    }
+
+    /// Rolls a joker the run does not own, with a random edition
+    pub fn roll_joker<R: Rng + ?Sized>(&self, run: &GameRunState, rng: &mut R) -> Option<Joker> {
+        let weights = &self.weights;
+        let rarities = [
+            (JokerRarity::Common, weights.common_rate),
+            (JokerRarity::Uncommon, weights.uncommon_rate),
+            (JokerRarity::Rare, weights.rare_rate),
+        ];
+        let rarity = *pick(&rarities, rng)?;
+        let pool: Vec<JokerKind> = JokerKind::all()
+            .into_iter()
+            .filter(|kind| kind.rarity() == rarity)
+            .filter(|kind| run.jokers.iter().all(|joker| joker.kind != *kind))
+            .collect();
+        let kind = pool.choose(rng)?.clone();
+
+        let editions = [
+            (JokerEdition::Foil, weights.foil_rate),
+            (JokerEdition::Holographic, weights.holographic_rate),
+            (JokerEdition::Polychrome, weights.polychrome_rate),
+            (JokerEdition::Negative, weights.negative_rate),
+        ];
+        let roll: f64 = rng.gen_range(0.0..1.0);
+        let mut threshold = 0.0;
+        let edition = editions
+            .into_iter()
+            .find(|(_, rate)| {
+                threshold += rate;
+                roll < threshold
+            })
+            .map_or(JokerEdition::None, |(edition, _)| edition);
+
+        Some(Joker::new(kind).with_edition(edition))
+    }
+
+    /// Rolls the offers in the shop's card slots
+    ///
+    /// A slot rolled as a Tarot card is left empty.
+    pub fn roll_cards<R: Rng + ?Sized>(&self, run: &GameRunState, rng: &mut R) -> Vec<ShopOffer> {
+        #[derive(Clone, Copy)]
+        enum Slot {
+            Joker,
+            Tarot,
+            Planet,
+        }
+        let weights = &self.weights;
+        let slots = [
+            (Slot::Joker, weights.joker_rate),
+            (Slot::Tarot, weights.tarot_rate),
+            (Slot::Planet, weights.planet_rate),
+        ];
+
+        (0..weights.card_slots)
+            .filter_map(|_| match pick(&slots, rng)? {
+                Slot::Joker => self.roll_joker(run, rng).map(ShopOffer::Joker),
+                Slot::Tarot => None,
+                Slot::Planet => Some(ShopOffer::Planet(*COMMON_PLANETS.choose(rng)?)),
+            })
+            .collect()
+    }
+
+    /// Rolls the packs in the shop's two pack slots
+    ///
+    /// A slot rolled as a pack that is not simulated is left empty.
+    pub fn roll_packs<R: Rng + ?Sized>(&self, run: &GameRunState, rng: &mut R) -> Vec<ShopOffer> {
+        let mut packs: Vec<(Option<&PackWeight>, f64)> =
+            self.weights.packs.iter().map(|pack| (Some(pack), pack.weight)).collect();
+        packs.push((None, self.weights.other_pack_weight));
+
+        let mut offers = Vec::with_capacity(PACK_SLOTS);
+        for _ in 0..PACK_SLOTS {
+            let Some(&Some(pack)) = pick(&packs, rng) else {
+                continue;
+            };
+            let choices = match pack.contents {
+                PackContents::Jokers => {
+                    let mut choices: Vec<ShopOffer> = Vec::with_capacity(pack.choices);
+                    // Each joker in a pack is different
+                    let mut pool = run.clone();
+                    while choices.len() < pack.choices
+                        && let Some(joker) = self.roll_joker(&pool, rng)
+                    {
+                        pool.jokers.push(joker.clone());
+                        choices.push(ShopOffer::Joker(joker));
+                    }
+                    choices
+                }
+                PackContents::Planets => COMMON_PLANETS
+                    .choose_multiple(rng, pack.choices)
+                    .map(|planet| ShopOffer::Planet(*planet))
+                    .collect(),
+            };
+            offers.push(ShopOffer::Pack {
+                name: pack.name.clone(),
+                cost: pack.cost,
+                choices,
+            });
+        }
+        offers
+    }
+
+    /// Rolls the voucher offered for an ante, or `None` if it is one whose
+    /// effect is not simulated
+    ///
+    /// Vouchers already redeemed, and upgrades whose base voucher is not,
+    /// are not offered.
+    pub fn roll_voucher<R: Rng + ?Sized>(&self, run: &GameRunState, rng: &mut R) -> Option<Voucher> {
+        if !rng.gen_bool(self.weights.modeled_voucher_rate.clamp(0.0, 1.0)) {
+            return None;
+        }
+        let owned = |voucher: Voucher| run.vouchers.iter().any(|name| name == voucher.name());
+        let available: Vec<Voucher> = Voucher::all()
+            .into_iter()
+            .filter(|voucher| !owned(*voucher))
+            .filter(|voucher| voucher.requires().is_none_or(owned))
+            .collect();
+        available.choose(rng).copied()
+    }
+
+    /// Visits a shop: rolls it, buys from it, and rerolls while worthwhile
+    ///
+    /// Purchases are paid for and applied to the run. The ante's voucher is
+    /// taken from `voucher` if bought. `favorite` is the hand type Planets
+    /// are bought for.
+    pub fn visit<R: Rng + ?Sized>(
+        &self,
+        run: &mut GameRunState,
+        voucher: &mut Option<Voucher>,
+        favorite: Option<HandType>,
+        rng: &mut R,
+    ) -> ShopVisit {
+        let mut visit = ShopVisit::default();
+        let mut offers = self.roll_cards(run, rng);
+        offers.extend(self.roll_packs(run, rng));
+
+        loop {
+            if let Some(offer) = voucher.map(ShopOffer::Voucher)
+                && self.can_afford(run, offer.cost())
+            {
+                self.buy(run, &offer, None, &mut visit);
+                *voucher = None;
+            }
+            while let Some((index, choice)) = self.best_offer(run, &offers, favorite) {
+                let offer = offers.remove(index);
+                self.buy(run, &offer, choice, &mut visit);
+            }
+
+            let cost = self.reroll_cost(visit.rerolls);
+            if visit.rerolls >= self.max_rerolls
+                || !Self::has_free_slot(run)
+                || !self.can_afford(run, cost)
+            {
+                return visit;
+            }
+            run.money -= cost;
+            visit.spent += cost;
+            visit.rerolls += 1;
+            // Rerolls replace the cards but not the packs
+            offers.retain(|offer| matches!(offer, ShopOffer::Pack { .. }));
+            offers.extend(self.roll_cards(run, rng));
+        }
+    }
+
+    /// Pays for an offer and applies it, or the pack choice taken from it
+    fn buy(
+        &self,
+        run: &mut GameRunState,
+        offer: &ShopOffer,
+        choice: Option<ShopOffer>,
+        visit: &mut ShopVisit,
+    ) {
+        run.money -= offer.cost();
+        visit.spent += offer.cost();
+        let bought = choice.unwrap_or_else(|| offer.clone());
+        bought.apply(run);
+        visit.bought.push(bought);
+    }
+
+    /// Returns the offer most worth buying and, for a pack, the choice to
+    /// take from it, or `None` if nothing affordable is worth buying
+    fn best_offer(
+        &self,
+        run: &GameRunState,
+        offers: &[ShopOffer],
+        favorite: Option<HandType>,
+    ) -> Option<(usize, Option<ShopOffer>)> {
+        offers
+            .iter()
+            .enumerate()
+            .filter(|(_, offer)| self.can_afford(run, offer.cost()))
+            .filter_map(|(index, offer)| {
+                let (value, choice) = match offer {
+                    ShopOffer::Pack { choices, .. } => {
+                        let (value, choice) = choices
+                            .iter()
+                            .filter_map(|choice| Some((Self::value(run, choice, favorite)?, choice)))
+                            .max_by_key(|(value, _)| *value)?;
+                        // A pack is worth a little less than its best card
+                        (value - 1, Some(choice.clone()))
+                    }
+                    _ => (Self::value(run, offer, favorite)?, None),
+                };
+                Some((value, index, choice))
+            })
+            .max_by_key(|(value, index, _)| (*value, std::cmp::Reverse(*index)))
+            .map(|(_, index, choice)| (index, choice))
+    }
+
+    /// Ranks an offer for buying, higher first, or `None` if it is not
+    /// worth buying
+    fn value(run: &GameRunState, offer: &ShopOffer, favorite: Option<HandType>) -> Option<u32> {
+        match offer {
+            ShopOffer::Joker(joker) => {
+                let fits = Self::has_free_slot(run) || joker.edition == JokerEdition::Negative;
+                let rarity = match joker.kind.rarity() {
+                    JokerRarity::Common => 1,
+                    JokerRarity::Uncommon => 2,
+                    JokerRarity::Rare => 3,
+                    JokerRarity::Legendary => 4,
+                };
+                (fits && !joker.kind.needs_target()).then(|| 1_000 + rarity * 100 + offer.cost())
+            }
+            ShopOffer::Planet(planet) => (Some(planet.hand_type()) == favorite).then_some(500),
+            ShopOffer::Voucher(_) => Some(2_000),
+            ShopOffer::Pack { .. } => None,
+        }
+    }
+
+    /// Returns true if the run has a joker slot free
+    pub(crate) fn has_free_slot(run: &GameRunState) -> bool {
+        let negatives = run
+            .jokers
+            .iter()
+            .filter(|joker| joker.edition == JokerEdition::Negative)
+            .count();
+        run.jokers.len() < run.deck_type.modifiers().joker_slots as usize + negatives
+    }
+
+    /// Returns true if the run can pay `cost` and keep its reserve
+    fn can_afford(&self, run: &GameRunState, cost: u32) -> bool {
+        run.money >= cost + self.reserve
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::hand::HandType;
+    use crate::core::joker::JokerKind;
+
+    #[test]
+    fn test_offer_costs() {
+        assert_eq!(ShopOffer::Joker(Joker::new(JokerKind::Baron)).cost(), 8);
+        let polychrome = Joker::new(JokerKind::Joker).with_edition(JokerEdition::Polychrome);
+        assert_eq!(ShopOffer::Joker(polychrome).cost(), 7);
+        assert_eq!(ShopOffer::Voucher(Voucher::Grabber).cost(), 10);
+        assert_eq!(ShopOffer::Planet(Planet::Jupiter).to_string(), "Jupiter ($3)");
+    }
+
+    #[test]
+    fn test_apply_offers() {
+        let mut run = GameRunState::default();
+        ShopOffer::Voucher(Voucher::Grabber).apply(&mut run);
+        ShopOffer::Planet(Planet::Jupiter).apply(&mut run);
+        ShopOffer::Joker(Joker::new(JokerKind::Joker)).apply(&mut run);
+
+        assert_eq!(run.hands_remaining, 5);
+        assert_eq!(run.vouchers, vec!["Grabber".to_string()]);
+        assert_eq!(run.hand_levels.level(HandType::Flush), 2);
+        assert_eq!(run.jokers.len(), 1);
+        assert_eq!("paint_brush".parse::<Voucher>().unwrap().hand_size_bonus(), 1);
+        let typo = "Nacho Tongs".parse::<Voucher>().unwrap_err().to_string();
+        assert_eq!(typo, "Unknown voucher: Nacho Tongs (did you mean Nacho Tong?)");
+    }
+
+    #[test]
+    fn test_rolls_follow_weights() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(1);
+        let run = GameRunState::default().with_jokers(vec![Joker::new(JokerKind::Joker)]);
+        let model = ShopModel::new();
+
+        let jokers: Vec<Joker> =
+            (0..1_000).filter_map(|_| model.roll_joker(&run, &mut rng)).collect();
+        assert_eq!(jokers.len(), 1_000);
+        assert!(jokers.iter().all(|joker| joker.kind != JokerKind::Joker));
+        assert!(jokers.iter().all(|joker| joker.rarity != JokerRarity::Legendary));
+        let common = jokers.iter().filter(|joker| joker.rarity == JokerRarity::Common).count();
+        assert!((600..800).contains(&common));
+        let editions = jokers.iter().filter(|joker| joker.edition != JokerEdition::None).count();
+        assert!(editions < 100);
+
+        let planets_only = ShopWeights {
+            joker_rate: 0.0,
+            tarot_rate: 0.0,
+            other_pack_weight: 0.0,
+            ..ShopWeights::default()
+        };
+        let model = ShopModel::new().with_weights(planets_only);
+        let cards = model.roll_cards(&run, &mut rng);
+        assert_eq!(cards.len(), 2);
+        assert!(cards.iter().all(|card| matches!(card, ShopOffer::Planet(_))));
+        for pack in model.roll_packs(&run, &mut rng) {
+            let ShopOffer::Pack { name, choices, .. } = pack else {
+                panic!("pack slots hold packs");
+            };
+            let expected = model.weights().packs.iter().find(|p| p.name == name).unwrap();
+            assert_eq!(choices.len(), expected.choices);
+        }
+        assert_eq!((model.reroll_cost(0), model.reroll_cost(2)), (5, 7));
+    }
+
+    #[test]
+    fn test_vouchers_need_their_base() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(2);
+        let weights = ShopWeights {
+            modeled_voucher_rate: 1.0,
+            ..ShopWeights::default()
+        };
+        let model = ShopModel::new().with_weights(weights);
+
+        let mut run = GameRunState::default();
+        for _ in 0..50 {
+            let voucher = model.roll_voucher(&run, &mut rng).unwrap();
+            assert!(voucher.requires().is_none());
+        }
+        ShopOffer::Voucher(Voucher::Grabber).apply(&mut run);
+        let rolled: Vec<Voucher> =
+            (0..50).filter_map(|_| model.roll_voucher(&run, &mut rng)).collect();
+        assert!(rolled.contains(&Voucher::NachoTong));
+        assert!(!rolled.contains(&Voucher::Grabber));
+    }
+
+    #[test]
+    fn test_visit_buys_jokers_within_budget() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(3);
+        let jokers_only = ShopWeights {
+            tarot_rate: 0.0,
+            planet_rate: 0.0,
+            other_pack_weight: 0.0,
+            packs: Vec::new(),
+            ..ShopWeights::default()
+        };
+        let model = ShopModel::new().with_weights(jokers_only).with_max_rerolls(3);
+
+        let mut run = GameRunState {
+            money: 40,
+            ..GameRunState::default()
+        };
+        let visit = model.visit(&mut run, &mut None, None, &mut rng);
+        assert!(!visit.bought.is_empty());
+        assert_eq!(run.jokers.len(), visit.bought.len());
+        assert_eq!(run.money, 40 - visit.spent);
+        assert!(visit.rerolls <= 3);
+
+        let mut poor = GameRunState {
+            money: 20,
+            ..GameRunState::default()
+        };
+        let model = model.with_reserve(20);
+        let mut voucher = Some(Voucher::Grabber);
+        assert_eq!(model.visit(&mut poor, &mut voucher, None, &mut rng), ShopVisit::default());
+        assert_eq!(voucher, Some(Voucher::Grabber));
+    }
+
+    #[test]
+    fn test_weights_from_partial_json() {
+        let weights: ShopWeights =
+            serde_json::from_str(r#"{"rare_rate": 0.1, "reroll_cost": 3}"#).unwrap();
+        assert_eq!(weights.rare_rate, 0.1);
+        assert_eq!(weights.reroll_cost, 3);
+        assert_eq!(weights.packs, ShopWeights::default().packs);
+    }
+}
@@ -3,11 +3,51 @@
 //! This module runs multiple simulations with random hands to evaluate
 //! the performance of different joker builds and deck configurations.
 
-use super::card::{Card, Rank, Suit};
+use super::blind::{BlindConfig, BlindType, BossBlind};
+use super::card::{Card, Enhancement, Rank, Suit};
+use super::consumable::{ConsumableInventory, Planet};
+use super::deck::DeckState;
+use super::draw::DrawPile;
+use super::hand::HandType;
+use super::hand_levels::HandLevels;
+use super::joker::{Joker, JokerKind};
+use super::run::GameRunState;
+use super::score::Score;
+use super::scoring::{ProbabilityMode, ScoreCalculator};
+use super::sensitivity::{DeckPerturbation, DeckSensitivity};
+use super::shop::{ShopModel, ShopOffer, ShopVisit};
 use super::solver::Solver;
+use super::strategy::{Exhaustive, Play, PlayContext, PlayStrategy};
+use anyhow::Context;
 use rand::prelude::*;
 use rand_chacha::ChaCha8Rng;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Ante a run must beat to win
+pub const FINAL_ANTE: u32 = 8;
+
+/// Single-hand scores whose reach is reported by full-run simulations
+pub const SCORE_MILESTONES: [u64; 4] = [1_000, 10_000, 100_000, 1_000_000];
+
+/// Most bars in a simulation's score histogram
+pub const HISTOGRAM_BINS: usize = 12;
+
+/// Normal quantile for the 95% confidence intervals reported
+const Z_95: f64 = 1.96;
+
+/// Runs simulated between convergence and time budget checks
+const SIMULATION_BATCH: usize = 100;
+
+/// Most progress reports made during one simulation
+const PROGRESS_REPORTS: usize = 100;
+
+/// Called with the progress of a running simulation
+pub type ProgressCallback = Box<dyn Fn(SimProgress) + Send + Sync>;
 
 /// Configuration for a simulation run
 pub struct SimulationConfig {
@@ -15,95 +55,1839 @@ pub struct SimulationConfig {
     pub hand_size: usize,
     pub num_runs: usize,
     pub seed: Option<u64>,
+    /// Hands played per run; each run's score is their total
+    pub hands_remaining: u32,
+    /// Discards available per run
+    pub discards_remaining: u32,
+    /// Score each round must reach to clear the blind (`None` plays every
+    /// hand)
+    ///
+    /// When set, a round ends as soon as its total reaches the target, and
+    /// the solver is told the score still needed before each hand.
+    pub blind_score: Option<Score>,
+    /// Blind every round is played against (`None` plays without one)
+    ///
+    /// A Boss blind's effect applies to every hand, and the blind's
+    /// requirement is the blind score unless `blind_score` is set, so the
+    /// clear rate is reported for that blind.
+    pub blind: Option<BlindConfig>,
+    /// When set, the result's `target` reports P(score ≥ target) over the
+    /// rounds with a 95% Wilson interval (see [`TargetProbability::new`]);
+    /// rounds are played the same either way
+    pub target_score: Option<Score>,
+    /// Stops before `num_runs` once the estimate is precise enough, making
+    /// `num_runs` a maximum
+    pub convergence: Option<Convergence>,
+    /// Deals runs so the estimates vary less than with independent
+    /// shuffles (`None` shuffles each run independently)
+    pub variance_reduction: Option<VarianceReduction>,
+    /// Stops starting runs once this much time has passed, making
+    /// `num_runs` a maximum (`usize::MAX` runs until the time is up)
+    ///
+    /// The budget is checked every [`SIMULATION_BATCH`] runs, so a slow
+    /// batch can overrun it.
+    pub max_duration: Option<Duration>,
+    /// Keeps every run's outcome in the result, for
+    /// [`SimulationResult::write_raw`]
+    pub record_runs: bool,
+    /// Threads to simulate on (`None` uses every core)
+    ///
+    /// Each run draws from its own RNG seeded from `seed`, so results are
+    /// the same for any number of threads.
+    pub parallelism: Option<usize>,
+    /// Called as runs complete, up to [`PROGRESS_REPORTS`] times (or about
+    /// that often over a time budget) and always once the simulation ends
+    pub progress: Option<ProgressCallback>,
+}
+
+impl Default for SimulationConfig {
+    /// 1,000 single-hand rounds from a standard deck with 8 cards in hand,
+    /// no discards, no blind, and every option off
+    fn default() -> Self {
+        Self {
+            deck: create_standard_deck(),
+            hand_size: 8,
+            num_runs: 1000,
+            seed: None,
+            hands_remaining: 1,
+            discards_remaining: 0,
+            blind_score: None,
+            blind: None,
+            target_score: None,
+            convergence: None,
+            variance_reduction: None,
+            max_duration: None,
+            record_runs: false,
+            parallelism: None,
+            progress: None,
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// Returns the score a round must reach to clear its blind: the blind
+    /// score, or else the blind's requirement
+    fn clear_score(&self) -> Option<Score> {
+        self.blind_score
+            .or_else(|| self.blind.as_ref().map(|blind| Score::from(blind.score_required)))
+    }
+}
+
+/// How runs are dealt to narrow the estimates from a given number of runs
+///
+/// Every run is still dealt a fair shuffle, so the estimates stay unbiased
+/// (for stratified sampling, exactly so when the runs are a multiple of
+/// the deck size). How much the spread shrinks depends on the build: most
+/// when the score hangs on which particular cards are drawn (e.g., Scholar
+/// or Fibonacci), little when it hangs on the hand type alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VarianceReduction {
+    /// Runs are played in pairs from one seed, the second dealt the first's
+    /// shuffle from the bottom up, so the pair's hands share no cards and a
+    /// lucky deal is offset by the cards it leaves behind
+    Antithetic,
+    /// Run `i` is dealt card `i` of the deck (wrapping around) first, so
+    /// every card opens the same number of rounds
+    Stratified,
+}
+
+impl std::str::FromStr for VarianceReduction {
+    type Err = anyhow::Error;
+
+    /// Parses "antithetic" or "stratified"
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "antithetic" => Ok(VarianceReduction::Antithetic),
+            "stratified" => Ok(VarianceReduction::Stratified),
+            _ => anyhow::bail!(
+                "Invalid variance reduction: {}. Use 'antithetic' or 'stratified'",
+                s
+            ),
+        }
+    }
+}
+
+/// Draws each run's seed from the master RNG, in run order
+///
+/// Antithetic pairs share the seed of their first run.
+struct RunSeeds {
+    master: ChaCha8Rng,
+    paired: bool,
+    last: u64,
+}
+
+impl RunSeeds {
+    fn new(master: ChaCha8Rng, variance_reduction: Option<VarianceReduction>) -> Self {
+        Self {
+            master,
+            paired: variance_reduction == Some(VarianceReduction::Antithetic),
+            last: 0,
+        }
+    }
+
+    /// Returns the seed of the run at `index`, called for each run in turn
+    fn next(&mut self, index: usize) -> u64 {
+        if !self.paired || index.is_multiple_of(2) {
+            self.last = self.master.next_u64();
+        }
+        self.last
+    }
+}
+
+/// When a simulation has run enough to stop early
+///
+/// Convergence is checked every [`SIMULATION_BATCH`] runs against 95%
+/// confidence intervals.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Convergence {
+    /// The interval of the mean score is narrower than this fraction of the
+    /// mean (e.g., 0.02 for ±1%)
+    Mean(f64),
+    /// The interval of the chance of reaching the target score is narrower
+    /// than this (e.g., 0.05 for ±2.5 points); needs a target score
+    Target(f64),
+}
+
+impl Convergence {
+    /// Returns true if the scores so far meet the tolerance
+    fn is_met(&self, scores: &[Score], target_score: Option<Score>) -> bool {
+        let n = scores.len();
+        if n < 2 {
+            return false;
+        }
+
+        match *self {
+            Convergence::Mean(tolerance) => {
+                let values = scores.iter().map(Score::value);
+                let mean = values.clone().sum::<f64>() / n as f64;
+                let variance = values.map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+                let width = 2.0 * Z_95 * (variance / n as f64).sqrt();
+                width <= tolerance * mean.abs()
+            }
+            Convergence::Target(tolerance) => target_score.is_some_and(|target| {
+                let hits = scores.iter().filter(|&&score| score >= target).count();
+                let probability = TargetProbability::new(target, hits, n);
+                probability.upper - probability.lower <= tolerance
+            }),
+        }
+    }
+}
+
+impl std::str::FromStr for Convergence {
+    type Err = anyhow::Error;
+
+    /// Parses "mean:<relative width>" or "target:<width>" (e.g.,
+    /// "mean:0.02", "target:0.05")
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let normalized = s.trim().to_lowercase();
+        let parsed = normalized.split_once(':').and_then(|(kind, tolerance)| {
+            let tolerance: f64 = tolerance.trim().parse().ok().filter(|t: &f64| *t > 0.0)?;
+            match kind.trim() {
+                "mean" => Some(Convergence::Mean(tolerance)),
+                "target" => Some(Convergence::Target(tolerance)),
+                _ => None,
+            }
+        });
+        parsed.ok_or_else(|| {
+            anyhow::anyhow!(
+                "Invalid convergence: {}. Use 'mean:<tolerance>' or 'target:<tolerance>'",
+                s
+            )
+        })
+    }
+}
+
+/// How far a simulation has got
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SimProgress {
+    /// Runs completed so far
+    pub completed: usize,
+    pub total: usize,
+    /// Time since the simulation started
+    pub elapsed: Duration,
+    /// Time the simulation may take, if it has a budget
+    pub budget: Option<Duration>,
+    /// Estimated time until every run is done (or the budget runs out),
+    /// from the pace so far
+    pub eta: Duration,
+    /// Mean score of the runs completed so far
+    pub mean_score: f64,
+    /// True for the last report, made once the simulation ends
+    pub finished: bool,
+}
+
+impl SimProgress {
+    /// Returns the fraction of the simulation done, from 0.0 to 1.0: the
+    /// share of runs completed or of the time budget used, whichever is
+    /// further along
+    pub fn fraction(&self) -> f64 {
+        if self.finished || self.total == 0 {
+            return 1.0;
+        }
+        let runs = self.completed as f64 / self.total as f64;
+        let time = self.budget.map_or(0.0, |budget| {
+            self.elapsed.as_secs_f64() / budget.as_secs_f64().max(f64::EPSILON)
+        });
+        runs.max(time).min(1.0)
+    }
+}
+
+/// Tracks completed runs and reports progress to the callback
+struct ProgressTracker<'a> {
+    callback: &'a ProgressCallback,
+    total: usize,
+    budget: Option<Duration>,
+    /// Runs completed between reports
+    interval: usize,
+    started: Instant,
+    state: Mutex<ProgressState>,
+}
+
+/// Progress so far, guarded by the tracker's lock
+#[derive(Default)]
+struct ProgressState {
+    completed: usize,
+    score_sum: f64,
+    /// When the last report was made
+    reported_at: Duration,
+}
+
+impl<'a> ProgressTracker<'a> {
+    fn new(callback: &'a ProgressCallback, total: usize, budget: Option<Duration>) -> Self {
+        Self {
+            callback,
+            total,
+            budget,
+            interval: total.div_ceil(PROGRESS_REPORTS).max(1),
+            started: Instant::now(),
+            state: Mutex::new(ProgressState::default()),
+        }
+    }
+
+    /// Records a completed run, reporting progress every `interval` runs
+    /// or, with a time budget, every hundredth of the budget
+    ///
+    /// The callback is called with the lock held, so reports arrive in order
+    /// even when runs complete on several threads.
+    fn record(&self, score: Score) {
+        let mut state = self.state.lock().expect("progress lock poisoned");
+        state.completed += 1;
+        state.score_sum += score.value();
+        let elapsed = self.started.elapsed();
+        let due = state.completed.is_multiple_of(self.interval)
+            || self.budget.is_some_and(|budget| {
+                elapsed >= state.reported_at + budget / PROGRESS_REPORTS as u32
+            });
+        if due || state.completed == self.total {
+            let finished = state.completed == self.total;
+            self.report(&mut state, elapsed, finished);
+        }
+    }
+
+    /// Makes the last report if the simulation stopped before `total` runs
+    fn finish(&self) {
+        let mut state = self.state.lock().expect("progress lock poisoned");
+        if state.completed < self.total {
+            self.report(&mut state, self.started.elapsed(), true);
+        }
+    }
+
+    fn report(&self, state: &mut ProgressState, elapsed: Duration, finished: bool) {
+        state.reported_at = elapsed;
+        let completed = state.completed;
+        // Runs left over a time budget can be all but unlimited
+        let remaining = (self.total - completed) as f64 / completed.max(1) as f64;
+        let mut eta = match finished {
+            true => Duration::ZERO,
+            false => Duration::try_from_secs_f64(elapsed.as_secs_f64() * remaining)
+                .unwrap_or(Duration::MAX),
+        };
+        if let Some(budget) = self.budget {
+            eta = eta.min(budget.saturating_sub(elapsed));
+        }
+
+        (self.callback)(SimProgress {
+            completed,
+            total: self.total,
+            elapsed,
+            budget: self.budget,
+            eta,
+            mean_score: if completed == 0 { 0.0 } else { state.score_sum / completed as f64 },
+            finished,
+        });
+    }
+}
+
+/// Statistics from a simulation run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationResult {
+    pub num_runs: usize,
+    pub mean_score: f64,
+    pub median_score: Score,
+    pub min_score: Score,
+    pub max_score: Score,
+    pub percentile_25: Score,
+    pub percentile_75: Score,
+    pub percentile_95: Score,
+    /// Total Glass cards destroyed across all runs (sampled)
+    pub glass_destroyed: u64,
+    /// Expected Glass cards destroyed per hand played
+    pub expected_glass_destroyed: f64,
+    /// Consumables created by seals across all runs
+    pub consumables: ConsumableInventory,
+    /// Fraction of rounds that reached the blind score, if one was set
+    pub blind_clear_rate: Option<f64>,
+    /// Mean hands played in rounds that cleared the blind (`None` if none
+    /// did, or no blind score was set)
+    pub mean_hands_to_clear: Option<f64>,
+    /// Chance of reaching the target score, if one was set
+    pub target: Option<TargetProbability>,
+    /// Whether the convergence tolerance was met before `num_runs` ran out,
+    /// if one was set
+    pub converged: Option<bool>,
+    /// Whether the time budget ran out before `num_runs` did, if one was set
+    pub out_of_time: Option<bool>,
+    /// Every run's outcome, in run order, if the config asked to record them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub runs: Vec<RunOutcome>,
+    /// Score distribution in equal-width bins, lowest first
+    #[serde(default)]
+    pub histogram: Vec<HistogramBin>,
+}
+
+/// Runs whose score fell in a range, one bar of a score histogram
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HistogramBin {
+    /// Lowest score in the range
+    pub lower: Score,
+    /// Highest score in the range (naneinf in the last bin if any run
+    /// overflowed)
+    pub upper: Score,
+    /// Runs scoring in the range
+    pub count: usize,
+}
+
+/// File formats for raw per-run results
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawFormat {
+    /// One row per run, with a header
+    Csv,
+    /// One JSON object per line
+    JsonLines,
+}
+
+impl RawFormat {
+    /// Picks the format from a file extension: .csv, or .jsonl / .ndjson
+    pub fn from_path(path: &Path) -> anyhow::Result<Self> {
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or_default();
+        match extension.to_lowercase().as_str() {
+            "csv" => Ok(RawFormat::Csv),
+            "jsonl" | "ndjson" => Ok(RawFormat::JsonLines),
+            _ => anyhow::bail!(
+                "Unknown export format for {:?}. Use a .csv or .jsonl file",
+                path
+            ),
+        }
+    }
 }
 
-/// Statistics from a simulation run
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SimulationResult {
-    pub num_runs: usize,
-    pub mean_score: f64,
-    pub median_score: u64,
-    pub min_score: u64,
-    pub max_score: u64,
-    pub percentile_25: u64,
-    pub percentile_75: u64,
-    pub percentile_95: u64,
-}
+impl SimulationResult {
+    /// Computes the statistics of runs, in run order
+    ///
+    /// `blind_score` sets the blind clear rate and hands to clear, and
+    /// `target_score` the chance of reaching it. Merging in run order lists
+    /// consumables as a serial simulation would. The runs themselves are not
+    /// kept.
+    pub fn from_runs(
+        runs: &[RunOutcome],
+        blind_score: Option<Score>,
+        target_score: Option<Score>,
+    ) -> Self {
+        let mut scores: Vec<Score> = Vec::with_capacity(runs.len());
+        let mut hands_to_clear: Vec<u32> = Vec::new();
+        let mut attrition = GlassAttrition::default();
+        let mut consumables = ConsumableInventory::default();
+        for run in runs {
+            if blind_score.is_some_and(|target| run.score >= target) {
+                hands_to_clear.push(run.hands_played);
+            }
+            scores.push(run.score);
+            attrition.destroyed += run.glass_destroyed;
+            attrition.expected += run.expected_glass_destroyed;
+            consumables.planets.extend(&run.consumables.planets);
+            consumables.tarots += run.consumables.tarots;
+        }
+
+        let num_runs = scores.len();
+        let hits = target_score
+            .map(|target| (target, scores.iter().filter(|&&score| score >= target).count()));
+        let mut result = Self::calculate_statistics(scores, num_runs, attrition, consumables);
+        result.target = hits.map(|(target, hits)| TargetProbability::new(target, hits, num_runs));
+        if blind_score.is_some() {
+            result.blind_clear_rate = Some(if num_runs == 0 {
+                0.0
+            } else {
+                hands_to_clear.len() as f64 / num_runs as f64
+            });
+            result.mean_hands_to_clear = (!hands_to_clear.is_empty()).then(|| {
+                hands_to_clear.iter().sum::<u32>() as f64 / hands_to_clear.len() as f64
+            });
+        }
+        result
+    }
+
+    /// Writes the recorded runs, one per row or line
+    ///
+    /// CSV rows hold the run's seed, score, hands played, and money earned,
+    /// with each hand's type and cards separated by `|` (e.g., "Flush|Pair").
+    /// JSON lines hold every field of [`RunOutcome`]. Nothing but the CSV
+    /// header is written unless the config set `record_runs`.
+    pub fn write_raw(&self, writer: &mut impl Write, format: RawFormat) -> anyhow::Result<()> {
+        match format {
+            RawFormat::Csv => {
+                writeln!(writer, "run,seed,score,hands_played,hand_types,cards_played,money_delta")?;
+                for run in &self.runs {
+                    let hand_types: Vec<String> =
+                        run.plays.iter().map(|play| format!("{:?}", play.hand_type)).collect();
+                    let cards: Vec<String> = run
+                        .plays
+                        .iter()
+                        .map(|play| {
+                            let cards: Vec<String> = play.cards.iter().map(Card::to_string).collect();
+                            cards.join(" ")
+                        })
+                        .collect();
+                    writeln!(
+                        writer,
+                        "{},{},{},{},{},{},{}",
+                        run.index,
+                        run.seed,
+                        run.score.value(),
+                        run.hands_played,
+                        hand_types.join("|"),
+                        cards.join("|"),
+                        run.money_delta
+                    )?;
+                }
+            }
+            RawFormat::JsonLines => {
+                for run in &self.runs {
+                    serde_json::to_writer(&mut *writer, run)?;
+                    writeln!(writer)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Calculates statistics from collected scores
+    fn calculate_statistics(
+        mut scores: Vec<Score>,
+        num_runs: usize,
+        attrition: GlassAttrition,
+        consumables: ConsumableInventory,
+    ) -> SimulationResult {
+        scores.sort_unstable();
+
+        let mean_score = scores.iter().map(Score::value).sum::<f64>() / num_runs as f64;
+        let median_score = Self::percentile(&scores, 0.5);
+        let min_score = scores.first().copied().unwrap_or_default();
+        let max_score = scores.last().copied().unwrap_or_default();
+
+        SimulationResult {
+            num_runs,
+            mean_score,
+            median_score,
+            min_score,
+            max_score,
+            percentile_25: Self::percentile(&scores, 0.25),
+            percentile_75: Self::percentile(&scores, 0.75),
+            percentile_95: Self::percentile(&scores, 0.95),
+            glass_destroyed: attrition.destroyed,
+            expected_glass_destroyed: if num_runs > 0 {
+                attrition.expected / num_runs as f64
+            } else {
+                0.0
+            },
+            consumables,
+            blind_clear_rate: None,
+            mean_hands_to_clear: None,
+            target: None,
+            converged: None,
+            out_of_time: None,
+            runs: Vec::new(),
+            histogram: Self::histogram(&scores),
+        }
+    }
+
+    /// Bins sorted scores into at most `HISTOGRAM_BINS` equal-width ranges
+    ///
+    /// Scores are whole numbers, so narrow ranges get one bin per score.
+    /// Overflowed (naneinf) scores are counted in the last bin.
+    fn histogram(sorted_scores: &[Score]) -> Vec<HistogramBin> {
+        let overflowed = sorted_scores.iter().filter(|score| score.is_naneinf()).count();
+        let finite = &sorted_scores[..sorted_scores.len() - overflowed];
+        let (Some(min), Some(max)) = (finite.first(), finite.last()) else {
+            return match overflowed {
+                0 => Vec::new(),
+                count => vec![HistogramBin { lower: Score::NANEINF, upper: Score::NANEINF, count }],
+            };
+        };
+
+        let (min, max) = (min.value(), max.value());
+        let bins = HISTOGRAM_BINS.min((max - min) as usize + 1);
+        let width = (max - min + 1.0) / bins as f64;
+        let lower = |bin: usize| Score::new((min + bin as f64 * width).ceil());
+        let mut histogram: Vec<HistogramBin> = (0..bins)
+            .map(|bin| HistogramBin {
+                lower: lower(bin),
+                upper: match bin + 1 == bins {
+                    true => Score::new(max),
+                    false => Score::new(lower(bin + 1).value() - 1.0),
+                },
+                count: 0,
+            })
+            .collect();
+        for score in finite {
+            let bin = ((score.value() - min) / width) as usize;
+            histogram[bin.min(bins - 1)].count += 1;
+        }
+        if overflowed > 0 {
+            let last = histogram.last_mut().expect("at least one bin");
+            last.upper = Score::NANEINF;
+            last.count += overflowed;
+        }
+        histogram
+    }
+
+    /// Calculates a percentile from sorted scores
+    fn percentile(sorted_scores: &[Score], p: f64) -> Score {
+        if sorted_scores.is_empty() {
+            return Score::ZERO;
+        }
+
+        let index = ((sorted_scores.len() as f64 - 1.0) * p) as usize;
+        sorted_scores[index]
+    }
+}
+
+/// The chance a round's score reaches a target
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TargetProbability {
+    pub target: Score,
+    /// Fraction of runs that reached the target
+    pub probability: f64,
+    /// Lower bound of the 95% Wilson score interval
+    pub lower: f64,
+    /// Upper bound of the 95% Wilson score interval
+    pub upper: f64,
+}
+
+impl TargetProbability {
+    /// Estimates the chance of reaching `target` from `hits` of `trials`
+    /// runs
+    ///
+    /// The Wilson interval stays within [0, 1] and remains sensible when
+    /// every run (or none) reaches the target, unlike the normal
+    /// approximation.
+    pub fn new(target: Score, hits: usize, trials: usize) -> Self {
+        if trials == 0 {
+            return Self {
+                target,
+                probability: 0.0,
+                lower: 0.0,
+                upper: 1.0,
+            };
+        }
+
+        let n = trials as f64;
+        let p = hits as f64 / n;
+        let z2 = Z_95 * Z_95;
+        let denominator = 1.0 + z2 / n;
+        let center = (p + z2 / (2.0 * n)) / denominator;
+        let margin = Z_95 * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denominator;
+
+        Self {
+            target,
+            probability: p,
+            lower: (center - margin).max(0.0),
+            upper: (center + margin).min(1.0),
+        }
+    }
+}
+
+/// Statistics from simulating complete runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunSimulationResult {
+    pub num_runs: usize,
+    /// Fraction of runs that beat the final ante's boss blind
+    pub win_rate: f64,
+    /// Mean ante at which lost runs ended (`None` if every run won)
+    pub average_losing_ante: Option<f64>,
+    /// Mean last ante played, counting wins as [`FINAL_ANTE`]
+    #[serde(default)]
+    pub average_final_ante: f64,
+    /// The blinds that ended lost runs, most runs first
+    #[serde(default)]
+    pub failures: Vec<RunFailure>,
+    /// Fraction of runs that beat each ante, from the starting ante on
+    pub ante_survival: Vec<(u32, f64)>,
+    /// Fraction of runs whose best single hand reached each score in
+    /// [`SCORE_MILESTONES`]
+    pub milestones: Vec<(Score, f64)>,
+    /// Mean money held when runs ended
+    pub mean_final_money: f64,
+    /// Spending in the shop, if runs visited one
+    pub shop: Option<ShopStats>,
+    /// How runs fared in each ante, from the starting ante on
+    #[serde(default)]
+    pub antes: Vec<AnteStats>,
+}
+
+/// A blind that ended simulated runs
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RunFailure {
+    pub blind_type: BlindType,
+    /// The boss blind, for runs lost to a Boss blind
+    pub boss: Option<BossBlind>,
+    /// Runs lost to this blind
+    pub runs: usize,
+}
+
+/// How simulated runs fared in one ante
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AnteStats {
+    pub ante: u32,
+    /// Runs that reached the ante
+    pub runs: usize,
+    /// Fraction of the runs reaching the ante that beat it
+    pub clear_rate: f64,
+    /// Mean money held entering the ante
+    pub mean_money: f64,
+    /// Mean score margin of each blind played: the round's total over the
+    /// blind's requirement, less one (e.g., 0.5 for scoring 50% more than
+    /// needed, negative for lost blinds)
+    pub blind_margins: Vec<(BlindType, f64)>,
+    /// The boss blind that ended the most runs in this ante, and how many
+    /// (`None` if no run lost to the boss)
+    pub deadliest_boss: Option<(BossBlind, usize)>,
+}
+
+/// How simulated runs spent their money in the shop
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ShopStats {
+    /// Mean money spent per run, rerolls included
+    pub mean_spent: f64,
+    /// Mean rerolls bought per run
+    pub mean_rerolls: f64,
+    /// Mean purchases per run, counting a pack as the card taken from it
+    pub mean_purchases: f64,
+    /// Mean jokers held when runs ended
+    pub mean_final_jokers: f64,
+}
+
+/// How one simulated run went
+struct FullRunOutcome {
+    /// The first ante not beaten, or `None` if the run won
+    lost_at: Option<u32>,
+    best_hand: Score,
+    money: u32,
+    /// Jokers held when the run ended
+    jokers: Vec<Joker>,
+    /// Everything bought in the shop over the run
+    shopping: ShopVisit,
+    /// Each ante reached, in order
+    antes: Vec<AnteOutcome>,
+}
+
+/// How far a run played out from one of its blinds got
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct PlayOut {
+    /// The first ante not beaten, or `None` if the run won
+    pub lost_at: Option<u32>,
+    /// Blinds beaten or skipped from the start of the run's ante
+    pub blinds_passed: usize,
+}
+
+/// How one ante of a simulated run went
+struct AnteOutcome {
+    ante: u32,
+    /// Money held entering the ante
+    money: u32,
+    boss: BossBlind,
+    /// Each blind played and its score margin (see [`AnteStats`])
+    margins: Vec<(BlindType, f64)>,
+    /// The blind that ended the run, if one did
+    lost_to: Option<BlindType>,
+}
+
+/// How one blind of a simulated run went
+struct BlindOutcome {
+    /// Hands left once the target was reached (`None` if it was not)
+    hands_left: Option<u32>,
+    /// Best single hand
+    best: Score,
+    /// Total scored
+    total: Score,
+}
+
+/// How one simulated run (a single round) went
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunOutcome {
+    /// Position of the run in the simulation, from 0
+    pub index: usize,
+    /// Seed of the run's RNG, derived from the simulation's seed
+    pub seed: u64,
+    /// Total score of the hands played
+    pub score: Score,
+    pub hands_played: u32,
+    /// Glass cards destroyed (sampled)
+    pub glass_destroyed: u64,
+    /// Expected Glass cards destroyed
+    pub expected_glass_destroyed: f64,
+    /// Consumables created by seals
+    pub consumables: ConsumableInventory,
+    /// Each hand played, in order
+    pub plays: Vec<PlayedHand>,
+    /// Money earned or lost while scoring (e.g., Gold seals, Bull), and at
+    /// end of round (e.g., Golden Joker)
+    pub money_delta: f64,
+}
+
+impl RunOutcome {
+    /// Reads runs written by [`SimulationResult::write_raw`]
+    ///
+    /// JSON lines give back every field. CSV rows hold only the run, seed,
+    /// score, hands played, and money earned, so runs read from CSV have no
+    /// plays, Glass destroyed, or consumables.
+    pub fn read_raw(reader: impl BufRead, format: RawFormat) -> anyhow::Result<Vec<RunOutcome>> {
+        match format {
+            RawFormat::Csv => {
+                /// The columns of a CSV row that a run can be rebuilt from
+                #[derive(Deserialize)]
+                struct Row {
+                    run: usize,
+                    seed: u64,
+                    score: f64,
+                    hands_played: u32,
+                    money_delta: f64,
+                }
+
+                let mut csv = csv::Reader::from_reader(reader);
+                csv.deserialize()
+                    .enumerate()
+                    .map(|(row, record)| {
+                        // Row 1 is the header
+                        let row: Row = record.with_context(|| format!("Invalid row {}", row + 2))?;
+                        Ok(RunOutcome {
+                            index: row.run,
+                            seed: row.seed,
+                            score: Score::new(row.score),
+                            hands_played: row.hands_played,
+                            glass_destroyed: 0,
+                            expected_glass_destroyed: 0.0,
+                            consumables: ConsumableInventory::default(),
+                            plays: Vec::new(),
+                            money_delta: row.money_delta,
+                        })
+                    })
+                    .collect()
+            }
+            RawFormat::JsonLines => {
+                let mut runs = Vec::new();
+                for (line, text) in reader.lines().enumerate() {
+                    let text = text?;
+                    if text.trim().is_empty() {
+                        continue;
+                    }
+                    let run = serde_json::from_str(&text)
+                        .with_context(|| format!("Invalid run on line {}", line + 1))?;
+                    runs.push(run);
+                }
+                Ok(runs)
+            }
+        }
+    }
+}
+
+/// A hand played during a simulated run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PlayedHand {
+    pub hand_type: HandType,
+    pub cards: Vec<Card>,
+    pub score: Score,
+}
+
+/// Running totals of Glass card destruction during a simulation
+#[derive(Debug, Default)]
+struct GlassAttrition {
+    destroyed: u64,
+    expected: f64,
+}
+
+/// A joker, hand level, and deck configuration to compare with others
+#[derive(Debug, Clone)]
+pub struct BuildConfig {
+    pub name: String,
+    pub jokers: Vec<Joker>,
+    pub hand_levels: HandLevels,
+    /// Cards to draw from (`None` uses the simulation's deck)
+    pub deck: Option<Vec<Card>>,
+}
+
+impl BuildConfig {
+    /// Creates a build with the given jokers, base hand levels, and the
+    /// simulation's deck
+    pub fn new(name: impl Into<String>, jokers: Vec<Joker>) -> Self {
+        Self {
+            name: name.into(),
+            jokers,
+            hand_levels: HandLevels::new(),
+            deck: None,
+        }
+    }
+
+    /// Sets the hand levels
+    pub fn with_hand_levels(mut self, hand_levels: HandLevels) -> Self {
+        self.hand_levels = hand_levels;
+        self
+    }
+
+    /// Sets the cards to draw from
+    pub fn with_deck(mut self, deck: Vec<Card>) -> Self {
+        self.deck = Some(deck);
+        self
+    }
+}
+
+/// Statistics for several builds simulated on the same hands
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildComparison {
+    pub num_runs: usize,
+    /// One entry per build, in the order given; differences are relative to
+    /// the first build
+    pub builds: Vec<BuildResult>,
+}
+
+/// How one build did in a comparison
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildResult {
+    pub name: String,
+    pub stats: SimulationResult,
+    /// Difference from the first build (`None` for the first build itself)
+    pub difference: Option<PairedDifference>,
+}
+
+/// The run-by-run difference between a build's score and the baseline's
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PairedDifference {
+    /// Mean of the build's score minus the baseline's
+    pub mean: f64,
+    /// Lower bound of the 95% confidence interval of the mean
+    pub lower: f64,
+    /// Upper bound of the 95% confidence interval of the mean
+    pub upper: f64,
+    /// Fraction of runs where the build outscored the baseline
+    pub win_rate: f64,
+}
+
+impl PairedDifference {
+    /// Computes the paired difference of two builds' scores, run by run
+    fn new(scores: &[Score], baseline: &[Score]) -> Self {
+        let values: Vec<f64> = scores.iter().map(|score| score.value()).collect();
+        let baseline: Vec<f64> = baseline.iter().map(|score| score.value()).collect();
+        Self::from_values(&values, &baseline)
+    }
+
+    /// Computes the paired difference of two sets of values, pair by pair
+    pub(crate) fn from_values(values: &[f64], baseline: &[f64]) -> Self {
+        let n = values.len();
+        if n == 0 {
+            return Self {
+                mean: 0.0,
+                lower: 0.0,
+                upper: 0.0,
+                win_rate: 0.0,
+            };
+        }
+
+        let differences: Vec<f64> =
+            values.iter().zip(baseline).map(|(value, base)| value - base).collect();
+        let mean = differences.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            differences.iter().map(|d| (d - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+        let margin = Z_95 * (variance / n as f64).sqrt();
+        let wins = values.iter().zip(baseline).filter(|(value, base)| value > base).count();
+
+        Self {
+            mean,
+            lower: mean - margin,
+            upper: mean + margin,
+            win_rate: wins as f64 / n as f64,
+        }
+    }
+
+    /// Returns true if the confidence interval excludes zero
+    pub fn is_significant(&self) -> bool {
+        self.lower > 0.0 || self.upper < 0.0
+    }
+}
+
+/// The simulator runs multiple hands and collects statistics
+pub struct Simulator {
+    solver: Solver,
+    strategy: Box<dyn PlayStrategy>,
+    /// Shop visited between blinds of full runs, if any
+    shop: Option<ShopModel>,
+    /// Threads full runs are simulated on, or one per core if unset
+    parallelism: Option<usize>,
+}
+
+impl Simulator {
+    /// Creates a new simulator with the given solver, making the best play
+    /// every hand
+    pub fn new(solver: Solver) -> Self {
+        Self {
+            solver,
+            strategy: Box::new(Exhaustive),
+            shop: None,
+            parallelism: None,
+        }
+    }
+
+    /// Sets the strategy that chooses each play (default: [`Exhaustive`])
+    pub fn with_strategy(mut self, strategy: impl PlayStrategy + 'static) -> Self {
+        self.strategy = Box::new(strategy);
+        self
+    }
+
+    /// Sets the shop full runs visit after each blind (default: none, so
+    /// nothing is bought)
+    pub fn with_shop(mut self, shop: ShopModel) -> Self {
+        self.shop = Some(shop);
+        self
+    }
+
+    /// Sets the threads [`Simulator::simulate_runs`] plays runs on (default:
+    /// one per core); rounds take theirs from [`SimulationConfig`]
+    pub fn with_parallelism(mut self, parallelism: Option<usize>) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Runs a simulation with the given configuration
+    ///
+    /// With a convergence tolerance or a time budget, runs are simulated in
+    /// batches until the tolerance is met, the time is up, or `num_runs` is
+    /// reached; the result's `num_runs` is the number actually simulated.
+    pub fn simulate(&self, config: SimulationConfig) -> SimulationResult {
+        self.simulate_with(&self.solver, config)
+    }
+
+    /// Simulates like [`Simulator::simulate`], scoring with another
+    /// calculator but this simulator's solver settings and strategy
+    pub fn simulate_with_calculator(
+        &self,
+        calculator: ScoreCalculator,
+        config: SimulationConfig,
+    ) -> SimulationResult {
+        self.simulate_with(&self.solver.clone().with_calculator(calculator), config)
+    }
+
+    /// Returns the threads set by [`Simulator::with_parallelism`], if any
+    pub fn parallelism(&self) -> Option<usize> {
+        self.parallelism
+    }
+
+    /// Simulates rounds with the given solver
+    fn simulate_with(&self, solver: &Solver, config: SimulationConfig) -> SimulationResult {
+        let started = Instant::now();
+        let batch = match config.convergence.is_some() || config.max_duration.is_some() {
+            true => SIMULATION_BATCH,
+            false => config.num_runs,
+        };
+        let tracker = config
+            .progress
+            .as_ref()
+            .map(|callback| ProgressTracker::new(callback, config.num_runs, config.max_duration));
+
+        let mut seeds = RunSeeds::new(self.create_rng(config.seed), config.variance_reduction);
+        let capacity = match config.max_duration {
+            Some(_) => SIMULATION_BATCH,
+            None => config.num_runs,
+        };
+        let mut rounds: Vec<RunOutcome> = Vec::with_capacity(capacity);
+        let mut scores: Vec<Score> = Vec::with_capacity(capacity);
+        let mut converged = config.convergence.map(|_| false);
+        let mut out_of_time = config.max_duration.map(|_| false);
+        Self::in_pool(config.parallelism, || {
+            while rounds.len() < config.num_runs {
+                // Seeds are drawn in run order, so batches do not change them
+                let start = rounds.len();
+                let count = batch.min(config.num_runs - start);
+                let batch_seeds: Vec<u64> = (start..start + count).map(|i| seeds.next(i)).collect();
+                let batch_rounds: Vec<RunOutcome> = batch_seeds
+                    .par_iter()
+                    .enumerate()
+                    .map(|(i, &seed)| {
+                        let round =
+                            self.simulate_round(solver, &config.deck, &config, start + i, seed);
+                        if let Some(tracker) = &tracker {
+                            tracker.record(round.score);
+                        }
+                        round
+                    })
+                    .collect();
+                scores.extend(batch_rounds.iter().map(|round| round.score));
+                rounds.extend(batch_rounds);
+
+                if let Some(convergence) = config.convergence
+                    && convergence.is_met(&scores, config.target_score)
+                {
+                    converged = Some(true);
+                    break;
+                }
+                if config.max_duration.is_some_and(|budget| started.elapsed() >= budget) {
+                    out_of_time = Some(rounds.len() < config.num_runs);
+                    break;
+                }
+            }
+        });
+        if let Some(tracker) = &tracker {
+            tracker.finish();
+        }
+
+        let mut result = self.summarize(rounds, &config);
+        result.converged = converged;
+        result.out_of_time = out_of_time;
+        result
+    }
+
+    /// Simulates runs one at a time as the iterator is advanced
+    ///
+    /// Runs are simulated serially and not kept, so results can be consumed
+    /// as they arrive, piped elsewhere, or abandoned early. They match the
+    /// runs [`Simulator::simulate`] would play with the same config. The
+    /// progress callback is not called.
+    pub fn simulate_iter(&self, config: SimulationConfig) -> impl Iterator<Item = RunOutcome> + '_ {
+        let mut seeds = RunSeeds::new(self.create_rng(config.seed), config.variance_reduction);
+        (0..config.num_runs).map(move |index| {
+            let seed = seeds.next(index);
+            self.simulate_round(&self.solver, &config.deck, &config, index, seed)
+        })
+    }
+
+    /// Simulates several builds on the same hands and compares them with
+    /// the first
+    ///
+    /// Every build plays each run from the same seed (common random
+    /// numbers), so builds sharing a deck are dealt identical hands and the
+    /// differences between them come from the builds rather than the
+    /// shuffle. The simulator's solver settings and strategy are used, with
+    /// each build's jokers, hand levels, and deck (which jokers counting the
+    /// full deck see too). The config's deck is used by builds that do not
+    /// set their own.
+    pub fn compare(&self, builds: Vec<BuildConfig>, config: &SimulationConfig) -> BuildComparison {
+        let mut run_seeds = RunSeeds::new(self.create_rng(config.seed), config.variance_reduction);
+        let seeds: Vec<u64> = (0..config.num_runs).map(|index| run_seeds.next(index)).collect();
+        let results: Vec<(String, SimulationResult, Vec<Score>)> = builds
+            .into_iter()
+            .map(|build| {
+                let mut calculator = self
+                    .solver
+                    .calculator()
+                    .clone()
+                    .with_jokers(build.jokers)
+                    .with_hand_levels(build.hand_levels);
+                if let Some(deck) = &build.deck {
+                    calculator = calculator.with_deck(deck.clone());
+                }
+                let solver = self.solver.clone().with_calculator(calculator);
+                let deck = build.deck.as_ref().unwrap_or(&config.deck);
+                let rounds: Vec<RunOutcome> = Self::in_pool(config.parallelism, || {
+                    seeds
+                        .par_iter()
+                        .enumerate()
+                        .map(|(index, &seed)| self.simulate_round(&solver, deck, config, index, seed))
+                        .collect()
+                });
+                let scores: Vec<Score> = rounds.iter().map(|round| round.score).collect();
+                (build.name, self.summarize(rounds, config), scores)
+            })
+            .collect();
+
+        let baseline = results.first().map(|(_, _, scores)| scores.clone()).unwrap_or_default();
+        BuildComparison {
+            num_runs: config.num_runs,
+            builds: results
+                .into_iter()
+                .enumerate()
+                .map(|(i, (name, stats, scores))| BuildResult {
+                    name,
+                    stats,
+                    difference: (i > 0).then(|| PairedDifference::new(&scores, &baseline)),
+                })
+                .collect(),
+        }
+    }
+
+    /// Measures how each deck perturbation changes the mean score
+    ///
+    /// The unchanged deck and every perturbed deck are compared as builds
+    /// (see [`Simulator::compare`]) with this simulator's jokers and hand
+    /// levels. Results are sorted by impact, most helpful first.
+    pub fn deck_sensitivity(
+        &self,
+        config: &SimulationConfig,
+        perturbations: &[DeckPerturbation],
+    ) -> Vec<DeckSensitivity> {
+        let calculator = self.solver.calculator();
+        let build = |name: String, deck: Vec<Card>| {
+            BuildConfig::new(name, calculator.jokers().to_vec())
+                .with_hand_levels(calculator.hand_levels().clone())
+                .with_deck(deck)
+        };
+        let mut builds = vec![build("Current deck".to_string(), config.deck.clone())];
+        builds.extend(
+            perturbations
+                .iter()
+                .map(|perturbation| build(perturbation.to_string(), perturbation.apply(&config.deck))),
+        );
+
+        let comparison = self.compare(builds, config);
+        let mut sensitivities: Vec<DeckSensitivity> = perturbations
+            .iter()
+            .zip(comparison.builds.into_iter().skip(1))
+            .map(|(perturbation, result)| DeckSensitivity {
+                perturbation: perturbation.clone(),
+                mean_score: result.stats.mean_score,
+                impact: result.difference.expect("perturbed builds are compared to the first"),
+            })
+            .collect();
+        sensitivities.sort_by(|a, b| b.impact.mean.total_cmp(&a.impact.mean));
+        sensitivities
+    }
+
+    /// Collects per-round outcomes, in run order, into statistics, keeping
+    /// the outcomes if the config asked to record them
+    fn summarize(&self, rounds: Vec<RunOutcome>, config: &SimulationConfig) -> SimulationResult {
+        let mut result =
+            SimulationResult::from_runs(&rounds, config.clear_score(), config.target_score);
+        if config.record_runs {
+            result.runs = rounds;
+        }
+        result
+    }
+
+    /// Plays one round from a freshly shuffled deck with the run's seed
+    ///
+    /// Played and discarded cards are replaced from the draw pile. A discard
+    /// is spent whenever the best play uses fewer than five cards, throwing
+    /// away up to five of the cards it leaves in hand. The round stops early
+    /// once the blind score, if set, is reached. Under a boss blind, each
+    /// play must be one the boss allows after the hands already played.
+    /// Jokers that change the deck or themselves as hands are played (e.g.,
+    /// Midas Mask, Vampire) do so before each hand is scored, and those
+    /// that change after a hand or discard (e.g., Ice Cream, Yorick) after
+    /// it. The jokers are taken as they are once the blind was selected.
+    fn simulate_round(
+        &self,
+        solver: &Solver,
+        deck: &[Card],
+        config: &SimulationConfig,
+        index: usize,
+        seed: u64,
+    ) -> RunOutcome {
+        let mut solver = solver.clone();
+        if let Some(boss) = config.blind.as_ref().and_then(BlindConfig::boss_blind) {
+            solver = solver.with_boss_blind(Some(boss));
+        }
+        let boss = solver.calculator().boss_blind();
+        let mut state = DeckState::new(deck.to_vec(), solver.calculator().jokers().to_vec());
+        let blind_score = config.clear_score();
+
+        let rng = &mut ChaCha8Rng::seed_from_u64(seed);
+        let mut draw_pile = DrawPile::new(deck, rng.next_u64());
+        Self::seed_sampling(&mut solver, rng);
+        match config.variance_reduction {
+            Some(VarianceReduction::Antithetic) if !index.is_multiple_of(2) => {
+                draw_pile = draw_pile.reversed();
+            }
+            Some(VarianceReduction::Stratified) if !deck.is_empty() => {
+                draw_pile = draw_pile.with_first(index % deck.len());
+            }
+            _ => {}
+        }
+        let mut hand = Vec::with_capacity(config.hand_size);
+        draw_pile.draw_to(&mut hand, config.hand_size);
+
+        let mut total = Score::ZERO;
+        let mut hands_played = 0;
+        let mut discards = config.discards_remaining;
+        let mut attrition = GlassAttrition::default();
+        let mut consumables = ConsumableInventory::default();
+        let mut plays: Vec<PlayedHand> = Vec::new();
+        let mut money_delta = 0.0;
+        // Held Gold cards, end-of-round jokers, and Blue seals pay once,
+        // for the cards held after the last play
+        let mut round_money = 0.0;
+        let mut round_events = Vec::new();
+
+        for hands_left in (1..=config.hands_remaining).rev() {
+            let mut hand_solver = match blind_score {
+                Some(target) => solver
+                    .clone()
+                    .with_remaining(hands_left, discards)
+                    .with_blind_score(Some(target - total)),
+                None => solver.clone(),
+            };
+            if boss.is_some() {
+                let played = plays.iter().map(|play| play.hand_type).collect();
+                hand_solver = hand_solver.with_played_hand_types(played);
+            }
+            let play = self.play_with_discards(
+                &hand_solver,
+                &mut state,
+                &mut hand,
+                &mut draw_pile,
+                &mut discards,
+                config.hand_size,
+            );
+            if play.hand.cards.is_empty() {
+                break;
+            }
+            let mut played = play.hand;
+            state.play_hand(&mut played, hands_played == 0);
+            Self::follow_deck_state(&mut solver, &state);
+
+            // Plays are ranked by expected value; roll the actual outcome of
+            // the chosen play with the simulation RNG
+            let score_result =
+                solver.calculator().calculate_sampled(&played, &play.held_cards, rng);
+            attrition.destroyed += score_result.sample_glass_breaks(rng) as u64;
+            attrition.expected += score_result.expected_glass_breaks();
+            consumables.tarots += score_result.tarots_created as u32;
+            total = total + score_result.score;
+            money_delta += score_result.money_delta - score_result.round_money;
+            round_money = score_result.round_money;
+            round_events = score_result.consumable_events;
+            hands_played += 1;
+            state.on_hand_played();
+            Self::follow_deck_state(&mut solver, &state);
+            plays.push(PlayedHand {
+                hand_type: score_result.hand_type,
+                cards: played.cards,
+                score: score_result.score,
+            });
+            if blind_score.is_some_and(|target| total >= target) {
+                break;
+            }
+
+            hand = play.held_cards;
+            draw_pile.draw_to(&mut hand, config.hand_size);
+        }
+        money_delta += round_money;
+        consumables.receive(&round_events);
+
+        RunOutcome {
+            index,
+            seed,
+            score: total,
+            hands_played,
+            glass_destroyed: attrition.destroyed,
+            expected_glass_destroyed: attrition.expected,
+            consumables,
+            plays,
+            money_delta,
+        }
+    }
+
+    /// Simulates complete runs from the run's starting ante through
+    /// [`FINAL_ANTE`]
+    ///
+    /// Each ante plays the Small, Big, and Boss blinds in turn, with a
+    /// random boss blind (the run's own boss for its current ante, if set).
+    /// Every blind is a fresh round from the shuffled deck with the run's
+    /// hands and discards, played with this simulator's strategy until the
+    /// blind's score is reached; running out of hands ends the run. Beating
+    /// a blind pays its reward, $1 per hand left, interest ($1 per $5, up
+    /// to $5), and the jokers' end-of-round money. Nothing is bought unless
+    /// a shop is set (see [`Simulator::with_shop`]), in which case it is
+    /// visited after every blind but the last.
+    ///
+    /// Jokers change as the run goes on (see [`DeckState`]): when a blind
+    /// is selected (Ceremonial Dagger, Madness), as hands are played and
+    /// cards discarded (Ice Cream, Yorick), and when a round ends (Popcorn),
+    /// and cards changed by play (Midas Mask, DNA) stay changed. The Idol
+    /// and Ancient Joker pick new targets each round from the run's seed,
+    /// starting from their pinned targets, if any.
+    pub fn simulate_runs(
+        &self,
+        run: &GameRunState,
+        num_runs: usize,
+        seed: Option<u64>,
+    ) -> RunSimulationResult {
+        let seeds = self.run_seeds(seed, num_runs);
+        let outcomes: Vec<FullRunOutcome> = Self::in_pool(self.parallelism, || {
+            seeds
+                .into_par_iter()
+                .map(|seed| {
+                    let rng = &mut ChaCha8Rng::seed_from_u64(seed);
+                    self.simulate_run(run, BlindType::Small, 0, rng)
+                })
+                .collect()
+        });
+
+        let fraction = |count: usize| {
+            if num_runs == 0 { 0.0 } else { count as f64 / num_runs as f64 }
+        };
+        let mean = |value: &dyn Fn(&FullRunOutcome) -> f64| {
+            let total: f64 = outcomes.iter().map(value).sum();
+            if num_runs == 0 { 0.0 } else { total / num_runs as f64 }
+        };
+        let losing_antes: Vec<u32> = outcomes.iter().filter_map(|o| o.lost_at).collect();
+
+        let mut failures: Vec<RunFailure> = Vec::new();
+        for outcome in outcomes.iter().flat_map(|o| &o.antes) {
+            let Some(blind_type) = outcome.lost_to else {
+                continue;
+            };
+            let boss = (blind_type == BlindType::Boss).then_some(outcome.boss);
+            match failures.iter_mut().find(|f| f.blind_type == blind_type && f.boss == boss) {
+                Some(failure) => failure.runs += 1,
+                None => failures.push(RunFailure { blind_type, boss, runs: 1 }),
+            }
+        }
+        // Stable, so blinds first lost to come first among ties
+        failures.sort_by_key(|failure| std::cmp::Reverse(failure.runs));
+
+        RunSimulationResult {
+            num_runs,
+            win_rate: fraction(outcomes.len() - losing_antes.len()),
+            average_losing_ante: (!losing_antes.is_empty()).then(|| {
+                losing_antes.iter().sum::<u32>() as f64 / losing_antes.len() as f64
+            }),
+            average_final_ante: mean(&|o| o.lost_at.unwrap_or(FINAL_ANTE) as f64),
+            failures,
+            ante_survival: (run.ante..=FINAL_ANTE)
+                .map(|ante| {
+                    let survived = outcomes.iter().filter(|o| o.lost_at.is_none_or(|lost| lost > ante));
+                    (ante, fraction(survived.count()))
+                })
+                .collect(),
+            milestones: SCORE_MILESTONES
+                .iter()
+                .map(|&milestone| {
+                    let milestone = Score::from(milestone);
+                    let reached = outcomes.iter().filter(|o| o.best_hand >= milestone);
+                    (milestone, fraction(reached.count()))
+                })
+                .collect(),
+            mean_final_money: mean(&|o| o.money as f64),
+            shop: self.shop.as_ref().map(|_| ShopStats {
+                mean_spent: mean(&|o| o.shopping.spent as f64),
+                mean_rerolls: mean(&|o| o.shopping.rerolls as f64),
+                mean_purchases: mean(&|o| o.shopping.bought.len() as f64),
+                mean_final_jokers: mean(&|o| o.jokers.len() as f64),
+            }),
+            antes: (run.ante..=FINAL_ANTE)
+                .map(|ante| Self::ante_stats(ante, &outcomes))
+                .collect(),
+        }
+    }
+
+    /// Gathers how the runs that reached an ante fared in it
+    fn ante_stats(ante: u32, outcomes: &[FullRunOutcome]) -> AnteStats {
+        let reached: Vec<&AnteOutcome> = outcomes
+            .iter()
+            .filter_map(|o| o.antes.iter().find(|outcome| outcome.ante == ante))
+            .collect();
+        let runs = reached.len();
+        let average = |values: &[f64]| {
+            if values.is_empty() { 0.0 } else { values.iter().sum::<f64>() / values.len() as f64 }
+        };
+
+        let blind_margins = [BlindType::Small, BlindType::Big, BlindType::Boss]
+            .into_iter()
+            .filter_map(|blind_type| {
+                let margins: Vec<f64> = reached
+                    .iter()
+                    .flat_map(|outcome| &outcome.margins)
+                    .filter(|(played, _)| *played == blind_type)
+                    .map(|(_, margin)| *margin)
+                    .collect();
+                (!margins.is_empty()).then(|| (blind_type, average(&margins)))
+            })
+            .collect();
+
+        let mut losses: Vec<(BossBlind, usize)> = Vec::new();
+        for outcome in &reached {
+            if outcome.lost_to != Some(BlindType::Boss) {
+                continue;
+            }
+            match losses.iter_mut().find(|(boss, _)| *boss == outcome.boss) {
+                Some((_, count)) => *count += 1,
+                None => losses.push((outcome.boss, 1)),
+            }
+        }
+        // Bosses are listed as first lost to, so earlier ones win ties
+        let deadliest_boss = losses
+            .iter()
+            .enumerate()
+            .max_by_key(|(i, (_, count))| (*count, std::cmp::Reverse(*i)))
+            .map(|(_, loss)| *loss);
+
+        let money: Vec<f64> = reached.iter().map(|outcome| outcome.money as f64).collect();
+        AnteStats {
+            ante,
+            runs,
+            clear_rate: match runs {
+                0 => 0.0,
+                _ => reached.iter().filter(|o| o.lost_to.is_none()).count() as f64 / runs as f64,
+            },
+            mean_money: average(&money),
+            blind_margins,
+            deadliest_boss,
+        }
+    }
+
+    /// Plays out the rest of a run from `next_blind` of its ante, as
+    /// [`Simulator::simulate_runs`] plays each run, counting the blinds
+    /// before `next_blind` as passed
+    ///
+    /// `extra_hand_size` is added to the deck type's hand size (e.g., for
+    /// vouchers bought before the play-out).
+    pub(crate) fn play_out(
+        &self,
+        run: &GameRunState,
+        next_blind: BlindType,
+        extra_hand_size: usize,
+        rng: &mut ChaCha8Rng,
+    ) -> PlayOut {
+        let outcome = self.simulate_run(run, next_blind, extra_hand_size, rng);
+        PlayOut {
+            lost_at: outcome.lost_at,
+            blinds_passed: Self::blinds_before(next_blind) + Self::blinds_beaten(&outcome),
+        }
+    }
+
+    /// Returns the number of blinds a run beat
+    fn blinds_beaten(outcome: &FullRunOutcome) -> usize {
+        outcome
+            .antes
+            .iter()
+            .map(|ante| ante.margins.len() - usize::from(ante.lost_to.is_some()))
+            .sum()
+    }
+
+    /// Returns the number of blinds in an ante before `blind_type`
+    pub(crate) fn blinds_before(blind_type: BlindType) -> usize {
+        match blind_type {
+            BlindType::Small => 0,
+            BlindType::Big => 1,
+            BlindType::Boss => 2,
+        }
+    }
+
+    /// Plays one run, from `first_blind` of its ante, until it loses a
+    /// blind or beats the final ante
+    fn simulate_run(
+        &self,
+        run: &GameRunState,
+        first_blind: BlindType,
+        extra_hand_size: usize,
+        rng: &mut ChaCha8Rng,
+    ) -> FullRunOutcome {
+        let mut hand_size = run.deck_type.modifiers().hand_size + extra_hand_size;
+        let first_ante = run.ante;
+        let bosses = BossBlind::all();
+        let mut run = run.clone();
+        let mut solver = self.solver.clone();
+        if self.shop.is_some() {
+            // Purchases add to the jokers and hand levels being played with
+            run.jokers = solver.calculator().jokers().to_vec();
+            run.hand_levels = solver.calculator().hand_levels().clone();
+        }
+        // Cards and jokers as play changes them over the run
+        let mut state = DeckState::new(run.deck.clone(), solver.calculator().jokers().to_vec());
+        state.pick_missing_targets(rng);
+        Self::follow_deck_state(&mut solver, &state);
+        Self::seed_sampling(&mut solver, rng);
+        let mut best_hand = Score::ZERO;
+        let mut played = Vec::new();
+        let mut shopping = ShopVisit::default();
+        let mut antes = Vec::new();
+
+        for ante in run.ante..=FINAL_ANTE {
+            let boss = match run.boss_blind.take() {
+                Some(boss) if ante == run.ante => boss,
+                _ => *bosses.choose(rng).expect("there are boss blinds"),
+            };
+            let mut ante_run = run.clone().with_boss_blind(Some(boss));
+            ante_run.ante = ante;
+            // Starting partway into an ante, its voucher has already been offered
+            let skipped = if ante == first_ante { Self::blinds_before(first_blind) } else { 0 };
+            let mut voucher = match skipped {
+                0 => self.shop.as_ref().and_then(|shop| shop.roll_voucher(&run, rng)),
+                _ => None,
+            };
+            antes.push(AnteOutcome {
+                ante,
+                money: run.money,
+                boss,
+                margins: Vec::with_capacity(3),
+                lost_to: None,
+            });
+            let outcome = antes.last_mut().expect("the ante was just added");
+
+            for blind_type in [BlindType::Small, BlindType::Big, BlindType::Boss].into_iter().skip(skipped)
+            {
+                state.on_blind_selected(blind_type, rng);
+                Self::follow_deck_state(&mut solver, &state);
+                let blind_solver = solver
+                    .clone()
+                    .with_boss_blind((blind_type == BlindType::Boss).then_some(boss))
+                    .with_remaining(run.hands_remaining, run.discards_remaining);
+                // Chicot, if held, disables the boss, its size included
+                ante_run.jokers.clone_from(&state.jokers);
+                let target = Score::from(ante_run.blind_requirement(blind_type));
+                let blind =
+                    self.play_blind(&blind_solver, &mut state, hand_size, target, &mut played, rng);
+                Self::follow_deck_state(&mut solver, &state);
+                run.deck.clone_from(&state.cards);
+                best_hand = best_hand.max(blind.best);
+                let margin = blind.total.value() / target.value().max(1.0) - 1.0;
+                outcome.margins.push((blind_type, margin));
+
+                let Some(hands_left) = blind.hands_left else {
+                    outcome.lost_to = Some(blind_type);
+                    return FullRunOutcome {
+                        lost_at: Some(ante),
+                        best_hand,
+                        money: run.money,
+                        jokers: state.jokers,
+                        shopping,
+                        antes,
+                    };
+                };
+                state.on_round_end(rng);
+                Self::follow_deck_state(&mut solver, &state);
+                let end_of_round_money: u32 = solver
+                    .calculator()
+                    .jokers()
+                    .iter()
+                    .map(|joker| joker.kind.end_of_round_money())
+                    .sum();
+                let interest = (run.money / 5).min(5);
+                run.money += blind_type.reward(run.stake) + hands_left + interest + end_of_round_money;
+
+                let Some(shop) = &self.shop else { continue };
+                // There is no shop after the final Boss blind
+                if ante == FINAL_ANTE && blind_type == BlindType::Boss {
+                    continue;
+                }
+                run.jokers.clone_from(&state.jokers);
+                let visit = shop.visit(&mut run, &mut voucher, Self::most_played(&played), rng);
+                Self::copy_consumables(&mut run, &visit.bought, rng);
+                state.jokers.clone_from(&run.jokers);
+                if !visit.bought.is_empty() {
+                    let calculator = solver
+                        .calculator()
+                        .clone()
+                        .with_jokers(run.jokers.clone())
+                        .with_hand_levels(run.hand_levels.clone());
+                    solver = solver.with_calculator(calculator);
+                    hand_size += visit.bought.iter().map(ShopOffer::hand_size_bonus).sum::<usize>();
+                }
+                shopping.spent += visit.spent;
+                shopping.rerolls += visit.rerolls;
+                shopping.bought.extend(visit.bought);
+            }
+        }
+
+        FullRunOutcome {
+            lost_at: None,
+            best_hand,
+            money: run.money,
+            jokers: state.jokers,
+            shopping,
+            antes,
+        }
+    }
+
+    /// Copies a consumable for each Perkeo held as a shop ends
+    ///
+    /// The only consumables runs hold are the Planets just bought, which
+    /// are used as soon as they are bought, so each copy is one of them
+    /// chosen at random and is used straight away too. Returns the copies.
+    fn copy_consumables(
+        run: &mut GameRunState,
+        bought: &[ShopOffer],
+        rng: &mut ChaCha8Rng,
+    ) -> Vec<Planet> {
+        let perkeos = run.jokers.iter().filter(|joker| joker.kind == JokerKind::Perkeo).count();
+        let planets: Vec<Planet> = bought
+            .iter()
+            .filter_map(|offer| match offer {
+                ShopOffer::Planet(planet) => Some(*planet),
+                _ => None,
+            })
+            .collect();
+        if perkeos == 0 || planets.is_empty() {
+            return Vec::new();
+        }
 
-/// The simulator runs multiple hands and collects statistics
-pub struct Simulator {
-    solver: Solver,
-}
+        let mut held = ConsumableInventory { planets, tarots: 0 };
+        for _ in 0..perkeos {
+            held.duplicate_random(rng);
+        }
+        let copies = held.planets.split_off(held.planets.len() - perkeos);
+        run.hand_levels.apply_planets(&copies);
+        copies
+    }
 
-impl Simulator {
-    /// Creates a new simulator with the given solver
-    pub fn new(solver: Solver) -> Self {
-        Self { solver }
+    /// Returns the hand type played most often, the strongest on ties
+    fn most_played(played: &[HandType]) -> Option<HandType> {
+        HandType::all()
+            .into_iter()
+            .map(|hand_type| (played.iter().filter(|&&p| p == hand_type).count(), hand_type))
+            .filter(|(count, _)| *count > 0)
+            .max()
+            .map(|(_, hand_type)| hand_type)
     }
 
-    /// Runs a simulation with the given configuration
-    pub fn simulate(&self, config: SimulationConfig) -> SimulationResult {
-        let mut rng = self.create_rng(config.seed);
-        let mut scores: Vec<u64> = Vec::with_capacity(config.num_runs);
+    /// Plays a blind until `target` is reached or the hands run out
+    ///
+    /// The hand types played are added to `played`, and the changes play
+    /// makes to the cards and jokers to `state`.
+    fn play_blind(
+        &self,
+        solver: &Solver,
+        state: &mut DeckState,
+        hand_size: usize,
+        target: Score,
+        played: &mut Vec<HandType>,
+        rng: &mut ChaCha8Rng,
+    ) -> BlindOutcome {
+        let deck = state.cards.clone();
+        let mut draw_pile = DrawPile::new(&deck, rng.next_u64());
+        let mut solver = solver.clone();
+        let mut hand = Vec::with_capacity(hand_size);
+        draw_pile.draw_to(&mut hand, hand_size);
 
-        for _ in 0..config.num_runs {
-            let hand = self.draw_random_hand(&config.deck, config.hand_size, &mut rng);
-            let result = self.solver.solve(&hand);
+        let mut total = Score::ZERO;
+        let mut best = Score::ZERO;
+        let mut discards = solver.discards_remaining();
+        let mut played_hand_types = Vec::new();
 
-            if let Some(score_result) = result.best_score {
-                scores.push(score_result.score);
-            } else {
-                scores.push(0);
+        for hands_left in (0..solver.hands_remaining()).rev() {
+            let hand_solver = solver.clone().with_played_hand_types(played_hand_types.clone());
+            let play = self.play_with_discards(
+                &hand_solver,
+                state,
+                &mut hand,
+                &mut draw_pile,
+                &mut discards,
+                hand_size,
+            );
+            if play.hand.cards.is_empty() {
+                break;
             }
+            let mut played_hand = play.hand;
+            state.play_hand(&mut played_hand, played_hand_types.is_empty());
+            Self::follow_deck_state(&mut solver, state);
+
+            let result = solver.calculator().calculate_sampled(&played_hand, &play.held_cards, rng);
+            state.on_hand_played();
+            Self::follow_deck_state(&mut solver, state);
+            played_hand_types.push(result.hand_type);
+            played.push(result.hand_type);
+            best = best.max(result.score);
+            total = total + result.score;
+            if total >= target {
+                return BlindOutcome {
+                    hands_left: Some(hands_left),
+                    best,
+                    total,
+                };
+            }
+
+            hand = play.held_cards;
+            draw_pile.draw_to(&mut hand, hand_size);
         }
 
-        self.calculate_statistics(scores, config.num_runs)
+        BlindOutcome {
+            hands_left: None,
+            best,
+            total,
+        }
     }
 
-    /// Creates a deterministic or random RNG based on seed
-    fn create_rng(&self, seed: Option<u64>) -> ChaCha8Rng {
-        match seed {
-            Some(s) => ChaCha8Rng::seed_from_u64(s),
-            None => ChaCha8Rng::from_entropy(),
+    /// Asks the strategy for a play, first discarding what the strategy
+    /// chooses to while discards are left
+    ///
+    /// A discard throws away the cards chosen (by default up to five of the
+    /// cards the play leaves in hand, when it uses fewer than five) and
+    /// refills the hand from the draw pile. Discarded cards are recorded in
+    /// `state` (e.g., for Yorick).
+    fn play_with_discards(
+        &self,
+        solver: &Solver,
+        state: &mut DeckState,
+        hand: &mut Vec<Card>,
+        draw_pile: &mut DrawPile,
+        discards: &mut u32,
+        hand_size: usize,
+    ) -> Play {
+        let mut play = self.strategy.choose_play(&PlayContext { cards: hand, solver });
+
+        while *discards > 0
+            && !draw_pile.is_empty()
+            && let Some(discarded) =
+                self.strategy.choose_discard(&PlayContext { cards: hand, solver }, &play)
+        {
+            for card in &discarded {
+                if let Some(pos) = hand.iter().position(|c| c == card) {
+                    hand.remove(pos);
+                }
+            }
+            state.on_discard(&discarded);
+            draw_pile.draw_to(hand, hand_size);
+            *discards -= 1;
+            play = self.strategy.choose_play(&PlayContext { cards: hand, solver });
         }
+
+        play
     }
 
-    /// Draws a random hand from the deck
-    fn draw_random_hand(&self, deck: &[Card], hand_size: usize, rng: &mut ChaCha8Rng) -> Vec<Card> {
-        let mut deck_copy = deck.to_vec();
-        deck_copy.shuffle(rng);
-        deck_copy.into_iter().take(hand_size).collect()
+    /// Reseeds a calculator that samples chance effects from the run's RNG
+    ///
+    /// Each round or run then rolls its own effects, rather than taking
+    /// rolls from the calculator shared by every thread, so the results do
+    /// not depend on which thread plays which run.
+    fn seed_sampling(solver: &mut Solver, rng: &mut ChaCha8Rng) {
+        if let ProbabilityMode::Sampled(_) = solver.calculator().probability_mode() {
+            let mode = ProbabilityMode::Sampled(rng.next_u64());
+            let calculator = solver.calculator().clone().with_probability_mode(mode);
+            *solver = solver.clone().with_calculator(calculator);
+        }
     }
 
-    /// Calculates statistics from collected scores
-    fn calculate_statistics(&self, mut scores: Vec<u64>, num_runs: usize) -> SimulationResult {
-        scores.sort_unstable();
+    /// Points the solver at the cards and jokers as play has left them, if
+    /// play changed them
+    ///
+    /// The calculator's deck, if it has one, is replaced by the state's
+    /// cards, so jokers that count the deck see cards play has changed.
+    fn follow_deck_state(solver: &mut Solver, state: &DeckState) {
+        let calculator = solver.calculator();
+        let deck_changed = calculator.deck().is_some_and(|deck| deck != state.cards.as_slice());
+        if !deck_changed && calculator.jokers() == state.jokers.as_slice() {
+            return;
+        }
+        let mut calculator = calculator.clone().with_jokers(state.jokers.clone());
+        if calculator.deck().is_some() {
+            calculator = calculator.with_deck(state.cards.clone());
+        }
+        *solver = solver.clone().with_calculator(calculator);
+    }
 
-        let mean_score = scores.iter().sum::<u64>() as f64 / num_runs as f64;
-        let median_score = self.percentile(&scores, 0.5);
-        let min_score = *scores.first().unwrap_or(&0);
-        let max_score = *scores.last().unwrap_or(&0);
+    /// Picks up to five held cards to discard, unenhanced and low ranks first
+    pub(crate) fn choose_discard(held: &[Card]) -> Option<Vec<Card>> {
+        let mut candidates: Vec<Card> = held.to_vec();
+        candidates.sort_by_key(|card| (card.enhancement != Enhancement::None, card.rank.value()));
+        candidates.truncate(5);
+        (!candidates.is_empty()).then_some(candidates)
+    }
 
-        SimulationResult {
-            num_runs,
-            mean_score,
-            median_score,
-            min_score,
-            max_score,
-            percentile_25: self.percentile(&scores, 0.25),
-            percentile_75: self.percentile(&scores, 0.75),
-            percentile_95: self.percentile(&scores, 0.95),
+    /// Creates a deterministic or random RNG based on seed
+    fn create_rng(&self, seed: Option<u64>) -> ChaCha8Rng {
+        match seed {
+            Some(s) => ChaCha8Rng::seed_from_u64(s),
+            None => ChaCha8Rng::from_entropy(),
         }
     }
 
-    /// Calculates a percentile from sorted scores
-    fn percentile(&self, sorted_scores: &[u64], p: f64) -> u64 {
-        if sorted_scores.is_empty() {
-            return 0;
-        }
+    /// Derives one seed per run from the master seed
+    ///
+    /// Seeds are drawn up front, in run order, so each run's outcome does
+    /// not depend on which thread simulates it.
+    fn run_seeds(&self, seed: Option<u64>, num_runs: usize) -> Vec<u64> {
+        let mut rng = self.create_rng(seed);
+        (0..num_runs).map(|_| rng.next_u64()).collect()
+    }
 
-        let index = ((sorted_scores.len() as f64 - 1.0) * p) as usize;
-        sorted_scores[index]
+    /// Runs `job` on a pool of `parallelism` threads, or on the global pool
+    /// (one thread per core) if unset or the pool cannot be built
+    fn in_pool<T: Send>(parallelism: Option<usize>, job: impl FnOnce() -> T + Send) -> T {
+        let pool = parallelism
+            .and_then(|threads| rayon::ThreadPoolBuilder::new().num_threads(threads).build().ok());
+        match pool {
+            Some(pool) => pool.install(job),
+            None => job(),
+        }
     }
 }
 
@@ -149,10 +1933,758 @@ mod tests {
             hand_size: 5,
             num_runs: 10,
             seed: Some(42),
+            ..Default::default()
         };
 
         let result = simulator.simulate(config);
         assert_eq!(result.num_runs, 10);
         assert!(result.mean_score > 0.0);
+
+        let histogram = &result.histogram;
+        assert!(!histogram.is_empty() && histogram.len() <= HISTOGRAM_BINS);
+        assert_eq!(histogram.iter().map(|bin| bin.count).sum::<usize>(), 10);
+        assert_eq!(histogram[0].lower, result.min_score);
+        assert_eq!(histogram[histogram.len() - 1].upper, result.max_score);
+        for pair in histogram.windows(2) {
+            assert_eq!(pair[0].upper.value() + 1.0, pair[1].lower.value());
+        }
+    }
+
+    #[test]
+    fn test_histogram_bins() {
+        let scores: Vec<Score> = [10, 11, 11, 13].into_iter().map(Score::from).collect();
+        let counts: Vec<usize> =
+            SimulationResult::histogram(&scores).iter().map(|bin| bin.count).collect();
+        assert_eq!(counts, [1, 2, 0, 1]);
+
+        let mut scores: Vec<Score> = (0..1000).map(Score::from).collect();
+        scores.push(Score::NANEINF);
+        let histogram = SimulationResult::histogram(&scores);
+        assert_eq!(histogram.len(), HISTOGRAM_BINS);
+        assert_eq!(histogram.last().unwrap().upper, Score::NANEINF);
+        assert_eq!(histogram.iter().map(|bin| bin.count).sum::<usize>(), 1001);
+
+        assert!(SimulationResult::histogram(&[]).is_empty());
+        assert_eq!(SimulationResult::histogram(&[Score::NANEINF])[0].count, 1);
+    }
+
+    #[test]
+    fn test_variance_reduction() {
+        let simulator = Simulator::new(Solver::new(ScoreCalculator::new(vec![])));
+        let config = |variance_reduction, hand_size, num_runs| SimulationConfig {
+            variance_reduction,
+            hand_size,
+            record_runs: true,
+            hands_remaining: 1,
+            discards_remaining: 0,
+            ..GameRunState::default().simulation_config(num_runs, Some(4))
+        };
+
+        let antithetic = simulator.simulate(config(Some(VarianceReduction::Antithetic), 8, 400));
+        for pair in antithetic.runs.chunks(2) {
+            assert_eq!(pair[0].seed, pair[1].seed);
+            let cards = &pair[1].plays[0].cards;
+            assert!(pair[0].plays[0].cards.iter().all(|card| !cards.contains(card)));
+        }
+        let plain = simulator.simulate(config(None, 8, 400));
+        let relative = (antithetic.mean_score - plain.mean_score).abs() / plain.mean_score;
+        assert!(relative < 0.1, "antithetic mean is off by {:.1}%", relative * 100.0);
+
+        // Dealt one card, stratified rounds play every card equally often,
+        // so the mean is exact
+        let stratified = simulator.simulate(config(Some(VarianceReduction::Stratified), 1, 104));
+        let exact: f64 = create_standard_deck()
+            .iter()
+            .map(|card| 5.0 + card.base_chips() as f64)
+            .sum::<f64>()
+            / 52.0;
+        assert!((stratified.mean_score - exact).abs() < 1e-9);
+        assert!((simulator.simulate(config(None, 1, 104)).mean_score - exact).abs() > 1e-9);
+
+        assert_eq!("Antithetic".parse::<VarianceReduction>().unwrap(), VarianceReduction::Antithetic);
+        assert!("control-variates".parse::<VarianceReduction>().is_err());
+    }
+
+    #[test]
+    fn test_results_independent_of_thread_count() {
+        use crate::core::card::Seal;
+
+        let deck: Vec<Card> = create_standard_deck()
+            .into_iter()
+            .map(|card| card.with_enhancement(Enhancement::Glass).with_seal(Seal::Blue))
+            .collect();
+        let simulator = Simulator::new(Solver::new(ScoreCalculator::new(vec![])));
+        let config = |parallelism| SimulationConfig {
+            deck: deck.clone(),
+            hand_size: 8,
+            num_runs: 50,
+            seed: Some(11),
+            hands_remaining: 2,
+            discards_remaining: 1,
+            parallelism: Some(parallelism),
+            ..Default::default()
+        };
+
+        let serial = simulator.simulate(config(1));
+        let parallel = simulator.simulate(config(4));
+
+        assert_eq!(serial.mean_score, parallel.mean_score);
+        assert_eq!(serial.percentile_25, parallel.percentile_25);
+        assert_eq!(serial.glass_destroyed, parallel.glass_destroyed);
+        assert_eq!(serial.consumables.planets, parallel.consumables.planets);
+    }
+
+    #[test]
+    fn test_play_out_past_final_ante() {
+        // No blinds are left to play once the final ante is behind the run
+        let run = GameRunState { ante: FINAL_ANTE + 1, ..GameRunState::default() };
+        let simulator = Simulator::new(run.solver());
+        let rng = &mut ChaCha8Rng::seed_from_u64(1);
+        let play_out = simulator.play_out(&run, BlindType::Big, 0, rng);
+        assert_eq!(play_out, PlayOut { lost_at: None, blinds_passed: 1 });
+        assert_eq!(simulator.play_out(&run, BlindType::Boss, 0, rng).blinds_passed, 2);
+    }
+
+    #[test]
+    fn test_sampled_results_do_not_depend_on_threads() {
+        use crate::core::scoring::ProbabilityMode;
+
+        // Every Lucky card's roll comes from its own run's seed
+        let deck: Vec<Card> = create_standard_deck()
+            .into_iter()
+            .map(|card| card.with_enhancement(Enhancement::Lucky))
+            .collect();
+        let calculator =
+            ScoreCalculator::new(vec![]).with_probability_mode(ProbabilityMode::Sampled(3));
+        let simulator = |threads| {
+            Simulator::new(Solver::new(calculator.clone())).with_parallelism(Some(threads))
+        };
+        let config = |parallelism| SimulationConfig {
+            deck: deck.clone(),
+            hand_size: 8,
+            num_runs: 50,
+            seed: Some(11),
+            hands_remaining: 2,
+            discards_remaining: 1,
+            record_runs: true,
+            parallelism: Some(parallelism),
+            ..Default::default()
+        };
+        let scores = |result: SimulationResult| {
+            result.runs.iter().map(|run| run.score).collect::<Vec<_>>()
+        };
+        let serial = simulator(1).simulate(config(1));
+        assert_eq!(scores(serial), scores(simulator(1).simulate(config(4))));
+
+        let run = GameRunState::default().with_deck(deck.clone());
+        let serial = simulator(1).simulate_runs(&run, 8, Some(2));
+        let parallel = simulator(4).simulate_runs(&run, 8, Some(2));
+        assert_eq!(serial.milestones, parallel.milestones);
+        assert_eq!(serial.average_losing_ante, parallel.average_losing_ante);
+    }
+
+    #[test]
+    fn test_progress_reported() {
+        use std::sync::Arc;
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let received = Arc::clone(&reports);
+        let config = SimulationConfig {
+            deck: create_standard_deck(),
+            hand_size: 8,
+            num_runs: 250,
+            seed: Some(2),
+            parallelism: Some(4),
+            progress: Some(Box::new(move |progress| received.lock().unwrap().push(progress))),
+            ..Default::default()
+        };
+
+        let result = Simulator::new(Solver::new(ScoreCalculator::new(vec![]))).simulate(config);
+
+        let reports = reports.lock().unwrap();
+        assert!(reports.len() <= PROGRESS_REPORTS + 1);
+        assert!(reports.windows(2).all(|pair| pair[0].completed < pair[1].completed));
+        let last = reports.last().unwrap();
+        assert_eq!((last.completed, last.total), (250, 250));
+        assert_eq!(last.fraction(), 1.0);
+        assert_eq!(last.eta, Duration::ZERO);
+        assert!(last.finished);
+        assert!((last.mean_score - result.mean_score).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_round_scores_every_hand() {
+        let simulator = Simulator::new(Solver::new(ScoreCalculator::new(vec![])));
+        let config = |hands_remaining, discards_remaining| SimulationConfig {
+            deck: create_standard_deck(),
+            hand_size: 8,
+            num_runs: 100,
+            seed: Some(3),
+            hands_remaining,
+            discards_remaining,
+            ..Default::default()
+        };
+
+        let one_hand = simulator.simulate(config(1, 0));
+        let round = simulator.simulate(config(4, 0));
+        let with_discards = simulator.simulate(config(4, 3));
+
+        assert!(round.mean_score > 3.0 * one_hand.mean_score);
+        assert!(with_discards.mean_score > round.mean_score);
+    }
+
+    #[test]
+    fn test_round_money_paid_once() {
+        use crate::core::card::Seal;
+        use crate::core::joker::{Joker, JokerKind};
+
+        let jokers = vec![Joker::new(JokerKind::GoldenJoker)];
+        let simulator = Simulator::new(Solver::new(ScoreCalculator::new(jokers)));
+        let deck: Vec<Card> = create_standard_deck()
+            .into_iter()
+            .map(|card| card.with_seal(Seal::Blue))
+            .collect();
+        let config = SimulationConfig {
+            deck,
+            num_runs: 20,
+            seed: Some(1),
+            hands_remaining: 4,
+            record_runs: true,
+            ..Default::default()
+        };
+
+        // Golden Joker pays $4 a round, not $4 a hand, and each Blue seal
+        // held after the last play makes one Planet
+        let result = simulator.simulate(config);
+        for run in &result.runs {
+            assert_eq!(run.hands_played, 4);
+            assert_eq!(run.money_delta, 4.0);
+            assert_eq!(run.consumables.planets.len(), 8 - run.plays[3].cards.len());
+        }
+    }
+
+    #[test]
+    fn test_blind_clear_rate() {
+        let simulator = Simulator::new(Solver::new(ScoreCalculator::new(vec![])));
+        let config = |blind_score: Option<u64>| SimulationConfig {
+            deck: create_standard_deck(),
+            hand_size: 8,
+            num_runs: 100,
+            seed: Some(4),
+            hands_remaining: 4,
+            discards_remaining: 3,
+            blind_score: blind_score.map(Score::from),
+            ..Default::default()
+        };
+
+        let unbounded = simulator.simulate(config(None));
+        assert_eq!(unbounded.blind_clear_rate, None);
+
+        let easy = simulator.simulate(config(Some(300)));
+        assert!(easy.blind_clear_rate.unwrap() > 0.9);
+        // Rounds stop once the blind is cleared
+        assert!(easy.mean_score < unbounded.mean_score);
+        let hands = easy.mean_hands_to_clear.unwrap();
+        assert!((1.0..=4.0).contains(&hands));
+
+        let hard = simulator.simulate(config(Some(1_500)));
+        assert!(hard.blind_clear_rate.unwrap() < easy.blind_clear_rate.unwrap());
+
+        let impossible = simulator.simulate(config(Some(1_000_000)));
+        assert_eq!(impossible.blind_clear_rate, Some(0.0));
+        assert_eq!(impossible.mean_hands_to_clear, None);
+    }
+
+    #[test]
+    fn test_boss_blind_applies_every_round() {
+        let simulator = Simulator::new(Solver::new(ScoreCalculator::new(vec![])));
+        let config = |blind: Option<BlindConfig>| SimulationConfig {
+            deck: create_standard_deck(),
+            hand_size: 8,
+            num_runs: 100,
+            seed: Some(4),
+            hands_remaining: 4,
+            discards_remaining: 3,
+            blind,
+            record_runs: true,
+            ..Default::default()
+        };
+
+        let plain = simulator.simulate(config(Some(BlindConfig::big(600))));
+        let psychic =
+            simulator.simulate(config(Some(BlindConfig::for_boss(BossBlind::ThePsychic, 600))));
+        assert!(psychic.runs.iter().flat_map(|run| &run.plays).all(|play| play.cards.len() == 5));
+        assert!(psychic.blind_clear_rate.unwrap() < plain.blind_clear_rate.unwrap());
+
+        let eye = simulator.simulate(config(Some(BlindConfig::for_boss(BossBlind::TheEye, 600))));
+        for run in &eye.runs {
+            let mut hand_types: Vec<HandType> = run.plays.iter().map(|play| play.hand_type).collect();
+            hand_types.sort();
+            hand_types.dedup();
+            assert_eq!(hand_types.len(), run.plays.len());
+        }
+
+        // An unmodeled boss is played as a plain blind of its size
+        let hook = simulator.simulate(config(Some(BlindConfig::boss("The Hook".to_string(), 600))));
+        assert_eq!(hook.blind_clear_rate, plain.blind_clear_rate);
+    }
+
+    #[test]
+    fn test_target_probability() {
+        let config = SimulationConfig {
+            deck: create_standard_deck(),
+            hand_size: 8,
+            num_runs: 200,
+            seed: Some(6),
+            target_score: Some(Score::from(200)),
+            ..Default::default()
+        };
+
+        let result = Simulator::new(Solver::new(ScoreCalculator::new(vec![]))).simulate(config);
+        let target = result.target.unwrap();
+        assert!(target.probability > 0.0 && target.probability < 1.0);
+        assert!(target.lower < target.probability && target.probability < target.upper);
+        assert!(target.upper - target.lower < 0.2);
+    }
+
+    #[test]
+    fn test_early_stopping() {
+        let simulator = Simulator::new(Solver::new(ScoreCalculator::new(vec![])));
+        let config = |convergence| SimulationConfig {
+            deck: create_standard_deck(),
+            hand_size: 8,
+            num_runs: 5_000,
+            seed: Some(10),
+            target_score: Some(Score::from(200)),
+            convergence: Some(convergence),
+            ..Default::default()
+        };
+
+        let loose = simulator.simulate(config(Convergence::Mean(0.2)));
+        assert_eq!(loose.converged, Some(true));
+        assert_eq!(loose.num_runs % SIMULATION_BATCH, 0);
+        assert!(loose.num_runs < 5_000);
+
+        let target = simulator.simulate(config(Convergence::Target(0.15)));
+        assert_eq!(target.converged, Some(true));
+        let interval = target.target.unwrap();
+        assert!(interval.upper - interval.lower <= 0.15);
+
+        let strict = simulator.simulate(SimulationConfig {
+            num_runs: 150,
+            ..config(Convergence::Mean(0.0001))
+        });
+        assert_eq!((strict.num_runs, strict.converged), (150, Some(false)));
+
+        assert_eq!("mean:0.02".parse::<Convergence>().unwrap(), Convergence::Mean(0.02));
+        assert_eq!("Target:0.05".parse::<Convergence>().unwrap(), Convergence::Target(0.05));
+        assert!("mean".parse::<Convergence>().is_err());
+        assert!("mean:-1".parse::<Convergence>().is_err());
+    }
+
+    #[test]
+    fn test_time_budget() {
+        use std::sync::Arc;
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let received = Arc::clone(&reports);
+        let config = SimulationConfig {
+            deck: create_standard_deck(),
+            hand_size: 8,
+            num_runs: usize::MAX,
+            seed: Some(11),
+            max_duration: Some(Duration::from_millis(200)),
+            progress: Some(Box::new(move |progress| received.lock().unwrap().push(progress))),
+            ..Default::default()
+        };
+
+        let started = Instant::now();
+        let result = Simulator::new(Solver::new(ScoreCalculator::new(vec![]))).simulate(config);
+
+        assert!(started.elapsed() < Duration::from_secs(30));
+        assert_eq!(result.out_of_time, Some(true));
+        assert!(result.num_runs >= SIMULATION_BATCH);
+        assert_eq!(result.num_runs % SIMULATION_BATCH, 0);
+        let last = *reports.lock().unwrap().last().unwrap();
+        assert!(last.finished);
+        assert_eq!((last.completed, last.fraction()), (result.num_runs, 1.0));
+    }
+
+    #[test]
+    fn test_wilson_interval() {
+        let target = Score::from(100);
+        let all = TargetProbability::new(target, 20, 20);
+        assert_eq!((all.probability, all.upper), (1.0, 1.0));
+        assert!((all.lower - 0.839).abs() < 0.001);
+
+        let half = TargetProbability::new(target, 50, 100);
+        assert!((half.lower - 0.404).abs() < 0.001);
+        assert!((half.upper - 0.596).abs() < 0.001);
+
+        let none = TargetProbability::new(target, 0, 0);
+        assert_eq!((none.lower, none.upper), (0.0, 1.0));
+    }
+
+    #[test]
+    fn test_simulate_iter_matches_simulate() {
+        let simulator = Simulator::new(Solver::new(ScoreCalculator::new(vec![])));
+        let config = || SimulationConfig {
+            deck: create_standard_deck(),
+            hand_size: 8,
+            num_runs: 40,
+            seed: Some(9),
+            ..Default::default()
+        };
+
+        let outcomes: Vec<RunOutcome> = simulator.simulate_iter(config()).collect();
+        assert_eq!(outcomes.len(), 40);
+        assert!(outcomes.iter().enumerate().all(|(i, outcome)| outcome.index == i));
+        let mean = outcomes.iter().map(|o| o.score.value()).sum::<f64>() / 40.0;
+        assert_eq!(mean, simulator.simulate(config()).mean_score);
+
+        let first = simulator.simulate_iter(config()).next().unwrap();
+        assert_eq!(first.score, outcomes[0].score);
+    }
+
+    #[test]
+    fn test_write_raw_runs() {
+        let simulator = Simulator::new(Solver::new(ScoreCalculator::new(vec![])));
+        let config = |record_runs| SimulationConfig {
+            deck: create_standard_deck(),
+            hand_size: 8,
+            num_runs: 5,
+            seed: Some(12),
+            hands_remaining: 2,
+            record_runs,
+            ..Default::default()
+        };
+
+        assert!(simulator.simulate(config(false)).runs.is_empty());
+        let result = simulator.simulate(config(true));
+        assert_eq!(result.runs.len(), 5);
+        assert_eq!(result.runs[0].plays.len(), 2);
+
+        let mut csv = Vec::new();
+        result.write_raw(&mut csv, RawFormat::Csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].starts_with("run,seed,score"));
+        let row: Vec<&str> = lines[1].split(',').collect();
+        assert_eq!(row[1], result.runs[0].seed.to_string());
+        assert_eq!(row[4].split('|').count(), 2);
+
+        let mut jsonl = Vec::new();
+        result.write_raw(&mut jsonl, RawFormat::JsonLines).unwrap();
+        let text = std::str::from_utf8(&jsonl).unwrap();
+        let first: RunOutcome = serde_json::from_str(text.lines().next().unwrap()).unwrap();
+        assert_eq!(first.score, result.runs[0].score);
+
+        // Reading the runs back gives the same statistics
+        let exports = [(csv.as_bytes(), RawFormat::Csv), (&jsonl[..], RawFormat::JsonLines)];
+        for (raw, format) in exports {
+            let runs = RunOutcome::read_raw(raw, format).unwrap();
+            assert_eq!(runs.len(), 5);
+            assert_eq!(runs[4].seed, result.runs[4].seed);
+            let stats = SimulationResult::from_runs(&runs, None, Some(Score::from(100)));
+            assert_eq!(stats.num_runs, result.num_runs);
+            assert_eq!(stats.mean_score, result.mean_score);
+            assert_eq!(stats.percentile_75, result.percentile_75);
+            assert_eq!(stats.histogram, result.histogram);
+            assert!(stats.target.is_some() && stats.runs.is_empty());
+        }
+        let runs = RunOutcome::read_raw(&jsonl[..], RawFormat::JsonLines).unwrap();
+        assert_eq!(runs[0].plays.len(), 2);
+        assert!(RunOutcome::read_raw("run,seed\nx,1\n".as_bytes(), RawFormat::Csv).is_err());
+        assert!(RunOutcome::read_raw("{\n".as_bytes(), RawFormat::JsonLines).is_err());
+
+        assert_eq!(RawFormat::from_path(Path::new("runs.CSV")).unwrap(), RawFormat::Csv);
+        assert_eq!(RawFormat::from_path(Path::new("a.jsonl")).unwrap(), RawFormat::JsonLines);
+        assert!(RawFormat::from_path(Path::new("runs.txt")).is_err());
+    }
+
+    #[test]
+    fn test_deck_sensitivity() {
+        let simulator = Simulator::new(Solver::new(ScoreCalculator::new(vec![])));
+        let config = SimulationConfig {
+            deck: create_standard_deck(),
+            hand_size: 8,
+            num_runs: 100,
+            seed: Some(13),
+            ..Default::default()
+        };
+
+        let perturbations = DeckPerturbation::defaults();
+        let results = simulator.deck_sensitivity(&config, &perturbations);
+        assert_eq!(results.len(), perturbations.len());
+        assert!(results.windows(2).all(|pair| pair[0].impact.mean >= pair[1].impact.mean));
+
+        // Trimming low cards makes pairs of high cards far more likely
+        let trimmed = results
+            .iter()
+            .find(|r| r.perturbation == DeckPerturbation::RemoveBelow(Rank::Six))
+            .unwrap();
+        assert!(trimmed.impact.mean > 0.0);
+    }
+
+    #[test]
+    fn test_compare_builds() {
+        use crate::core::joker::JokerKind;
+
+        let simulator = Simulator::new(Solver::new(ScoreCalculator::new(vec![])));
+        let config = SimulationConfig {
+            deck: create_standard_deck(),
+            hand_size: 8,
+            num_runs: 100,
+            seed: Some(8),
+            ..Default::default()
+        };
+        let builds = vec![
+            BuildConfig::new("Plain", vec![]),
+            BuildConfig::new("Joker", vec![Joker::new(JokerKind::Joker)]),
+            BuildConfig::new("Plain again", vec![]),
+        ];
+
+        let comparison = simulator.compare(builds, &config);
+        assert_eq!(comparison.builds.len(), 3);
+        assert!(comparison.builds[0].difference.is_none());
+
+        // +4 mult never lowers a score, and the same hands are dealt to both
+        let joker = comparison.builds[1].difference.unwrap();
+        assert!(joker.mean > 0.0 && joker.is_significant());
+        assert_eq!(joker.win_rate, 1.0);
+
+        let same = comparison.builds[2].difference.unwrap();
+        assert_eq!((same.mean, same.lower, same.upper), (0.0, 0.0, 0.0));
+        assert!(!same.is_significant());
+    }
+
+    #[test]
+    fn test_strategy_changes_play() {
+        use crate::core::strategy::Random;
+
+        let solver = Solver::new(ScoreCalculator::new(vec![]));
+        let config = || SimulationConfig {
+            deck: create_standard_deck(),
+            hand_size: 8,
+            num_runs: 20,
+            seed: Some(5),
+            ..Default::default()
+        };
+
+        let optimal = Simulator::new(solver.clone()).simulate(config());
+        let random = Simulator::new(solver).with_strategy(Random::new(5)).simulate(config());
+
+        assert!(optimal.mean_score > random.mean_score);
+    }
+
+    #[test]
+    fn test_simulate_full_runs() {
+        use crate::core::joker::{Joker, JokerKind};
+
+        let run = GameRunState::default();
+        let weak = Simulator::new(run.solver()).simulate_runs(&run, 10, Some(1));
+        assert_eq!(weak.num_runs, 10);
+        assert_eq!(weak.win_rate, 0.0);
+        let losing_ante = weak.average_losing_ante.unwrap();
+        assert!((1.0..=FINAL_ANTE as f64).contains(&losing_ante));
+        assert_eq!(weak.ante_survival.len(), FINAL_ANTE as usize);
+        assert!(weak.ante_survival.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+        assert_eq!(weak.milestones.len(), SCORE_MILESTONES.len());
+
+        let strong = run.clone().with_jokers(vec![
+            Joker::new(JokerKind::Joker),
+            Joker::new(JokerKind::JollyJoker),
+            Joker::new(JokerKind::Bloodstone),
+        ]);
+        let strong = Simulator::new(strong.solver()).simulate_runs(&strong, 10, Some(1));
+        assert!(strong.ante_survival[1].1 >= weak.ante_survival[1].1);
+        assert!(strong.mean_final_money > 0.0);
+    }
+
+    #[test]
+    fn test_full_runs_visit_shop() {
+        let run = GameRunState::default();
+        let plain = Simulator::new(run.solver()).simulate_runs(&run, 10, Some(1));
+        assert_eq!(plain.shop, None);
+
+        let shopping = Simulator::new(run.solver())
+            .with_shop(ShopModel::new())
+            .simulate_runs(&run, 10, Some(1));
+        let shop = shopping.shop.unwrap();
+        assert!(shop.mean_spent > 0.0 && shop.mean_final_jokers > 0.0);
+        assert!(shopping.average_losing_ante.unwrap() > plain.average_losing_ante.unwrap());
+
+        // Money below the reserve is never spent
+        let saving = Simulator::new(run.solver())
+            .with_shop(ShopModel::new().with_reserve(1_000))
+            .simulate_runs(&run, 10, Some(1));
+        assert_eq!(saving.shop.unwrap().mean_spent, 0.0);
+        assert_eq!(saving.average_losing_ante, plain.average_losing_ante);
+    }
+
+    #[test]
+    fn test_per_ante_breakdown() {
+        let run = GameRunState::default();
+        let result = Simulator::new(run.solver())
+            .with_shop(ShopModel::new())
+            .simulate_runs(&run, 20, Some(3));
+        assert_eq!(result.antes.len(), 8);
+
+        let first = &result.antes[0];
+        assert_eq!((first.ante, first.runs), (1, 20));
+        assert_eq!(first.mean_money, run.money as f64);
+        assert_eq!(first.blind_margins[0].0, BlindType::Small);
+        // Runs only reach an ante by beating the previous one
+        for pair in result.antes.windows(2) {
+            let cleared = (pair[0].runs as f64 * pair[0].clear_rate).round() as usize;
+            assert_eq!(pair[1].runs, cleared);
+        }
+        for ante in &result.antes {
+            let boss_losses = ante.deadliest_boss.map_or(0, |(_, losses)| losses);
+            assert!(boss_losses <= ante.runs);
+            if ante.clear_rate == 1.0 {
+                assert!(ante.blind_margins.iter().all(|(_, margin)| *margin >= 0.0));
+            }
+        }
+
+        // Every lost run is put down to one blind
+        let lost = (result.num_runs as f64 * (1.0 - result.win_rate)).round() as usize;
+        assert_eq!(result.failures.iter().map(|failure| failure.runs).sum::<usize>(), lost);
+        assert!(result.failures.windows(2).all(|pair| pair[0].runs >= pair[1].runs));
+        assert!(result
+            .failures
+            .iter()
+            .all(|failure| failure.boss.is_some() == (failure.blind_type == BlindType::Boss)));
+        assert!(result.average_final_ante >= 1.0 && result.average_final_ante <= 8.0);
+    }
+
+    #[test]
+    fn test_glass_attrition_reported() {
+        use crate::core::card::Enhancement;
+
+        let deck: Vec<Card> = create_standard_deck()
+            .into_iter()
+            .map(|card| card.with_enhancement(Enhancement::Glass))
+            .collect();
+        let simulator = Simulator::new(Solver::new(ScoreCalculator::new(vec![])));
+
+        let config = SimulationConfig {
+            deck,
+            hand_size: 8,
+            num_runs: 200,
+            seed: Some(7),
+            ..Default::default()
+        };
+
+        let result = simulator.simulate(config);
+        assert!(result.expected_glass_destroyed > 0.0);
+        assert!(result.glass_destroyed > 0);
+    }
+
+    #[test]
+    fn test_deck_changes_carry_across_hands() {
+        use crate::core::joker::JokerKind;
+
+        // Midas Mask gilds the five Kings of each hand, so the 16 enhanced
+        // cards Driver's License needs are in the deck by the fourth hand
+        let deck = vec![Card::new(Rank::King, Suit::Hearts); 20];
+        let jokers = vec![Joker::new(JokerKind::MidasMask), Joker::new(JokerKind::DriversLicense)];
+        let calculator = ScoreCalculator::new(jokers).with_deck(deck.clone());
+        let config = SimulationConfig {
+            deck,
+            hand_size: 5,
+            num_runs: 1,
+            seed: Some(3),
+            hands_remaining: 4,
+            record_runs: true,
+            ..Default::default()
+        };
+
+        let result = Simulator::new(Solver::new(calculator)).simulate(config);
+        let plays = &result.runs[0].plays;
+        assert_eq!(plays.len(), 4);
+        assert!(plays[0].cards.iter().all(|card| card.enhancement == Enhancement::Gold));
+        assert!(plays[..3].iter().all(|play| play.score == plays[0].score));
+        assert_eq!(plays[3].score.value(), plays[0].score.value() * 3.0);
+    }
+
+    #[test]
+    fn test_jokers_decay_over_a_round() {
+        use crate::core::joker::JokerKind;
+
+        // Every hand is the same five Kings, so only Ice Cream's chips change
+        let deck = vec![Card::new(Rank::King, Suit::Hearts); 20];
+        let calculator = ScoreCalculator::new(vec![Joker::new(JokerKind::IceCream)]);
+        let config = SimulationConfig {
+            deck,
+            hand_size: 5,
+            num_runs: 1,
+            seed: Some(3),
+            hands_remaining: 4,
+            record_runs: true,
+            ..Default::default()
+        };
+
+        let result = Simulator::new(Solver::new(calculator)).simulate(config);
+        let plays = &result.runs[0].plays;
+        assert_eq!(plays.len(), 4);
+        assert!(plays.windows(2).all(|pair| pair[1].score < pair[0].score));
+    }
+
+    #[test]
+    fn test_jokers_sacrificed_when_blind_selected() {
+        use crate::core::joker::JokerKind;
+
+        // Ceremonial Dagger destroys the Joker to its right at the first blind
+        let jokers = vec![Joker::new(JokerKind::CeremonialDagger), Joker::new(JokerKind::Joker)];
+        let run = GameRunState::default().with_jokers(jokers);
+        let simulator = Simulator::new(Solver::new(run.calculator()));
+        let rng = &mut ChaCha8Rng::seed_from_u64(5);
+        let outcome = simulator.simulate_run(&run, BlindType::Small, 0, rng);
+        assert_eq!(outcome.jokers.len(), 1);
+    }
+
+    #[test]
+    fn test_joker_targets_rerolled_each_round() {
+        use crate::core::joker::JokerKind;
+
+        // The Idol starts pinned to a card the all-Hearts deck doesn't have
+        let deck: Vec<Card> =
+            create_standard_deck().into_iter().filter(|card| card.suit == Suit::Hearts).collect();
+        let jokers = vec![
+            Joker::new(JokerKind::TheIdol).with_target(Some(Rank::Ace), Some(Suit::Spades)),
+            Joker::new(JokerKind::AncientJoker),
+        ];
+        let run = GameRunState::default().with_deck(deck).with_jokers(jokers);
+        let simulator = Simulator::new(Solver::new(run.calculator()));
+        let play = |seed| {
+            let rng = &mut ChaCha8Rng::seed_from_u64(seed);
+            simulator.simulate_run(&run, BlindType::Small, 0, rng)
+        };
+
+        let outcome = play(8);
+        assert!(Simulator::blinds_beaten(&outcome) > 0);
+        let (idol, ancient) = (&outcome.jokers[0], &outcome.jokers[1]);
+        assert_eq!(idol.target_suit, Some(Suit::Hearts));
+        assert!(idol.target_rank.is_some());
+        assert!(ancient.target_suit.is_some());
+        assert_eq!(play(8).jokers, outcome.jokers);
+    }
+
+    #[test]
+    fn test_perkeo_copies_bought_planets() {
+        // Perkeo copies the only Planet bought, which levels Pair again
+        let bought = [ShopOffer::Planet(Planet::Mercury)];
+        let mut run = GameRunState::default().with_jokers(vec![Joker::new(JokerKind::Perkeo)]);
+        let rng = &mut ChaCha8Rng::seed_from_u64(3);
+        let copies = Simulator::copy_consumables(&mut run, &bought, rng);
+        assert_eq!(copies, vec![Planet::Mercury]);
+        assert_eq!(run.hand_levels.level(HandType::Pair), 2);
+
+        // Nothing is copied without Perkeo
+        let mut run = GameRunState::default();
+        assert!(Simulator::copy_consumables(&mut run, &bought, rng).is_empty());
+        assert_eq!(run.hand_levels.level(HandType::Pair), 1);
     }
 }
@@ -3,93 +3,1355 @@
 //! This module contains the algorithm for finding the highest-scoring
 //! combination of cards from a given hand.
 
+use super::blind::BossBlind;
 use super::card::Card;
-use super::hand::Hand;
-use super::scoring::{ScoreCalculator, ScoreResult};
+use super::consumable::Consumable;
+use super::deck_stats::choose;
+use super::explain::PlayComparison;
+use super::hand::{Hand, HandType};
+use super::joker::Joker;
+use super::run::GameRunState;
+use super::score::Score;
+use super::scoring::{ProbabilityMode, ScoreCalculator, ScoreResult};
+use super::shop::ShopOffer;
+use super::simulator::Simulator;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use std::collections::{HashMap, HashSet};
+use std::time::Instant;
+
+/// Default number of redraws sampled per discard when enumeration is too large
+pub const DEFAULT_DISCARD_SAMPLES: usize = 32;
+
+/// Number of sampled outcomes used to estimate the chance of beating a blind
+pub const BEAT_PROBABILITY_SAMPLES: usize = 256;
+
+/// Default number of alternative plays reported alongside the best play
+pub const DEFAULT_ALTERNATIVES: usize = 3;
+
+/// Number of simulated rounds used to compare shop offers
+pub const SHOP_SIMULATION_RUNS: usize = 64;
+
+/// Most jokers whose every order is tried (7! = 5040 orders)
+pub const MAX_REORDERED_JOKERS: usize = 7;
+
+/// What the solver maximizes when ranking plays
+///
+/// The objectives differ only for plays with chance-based effects (Lucky
+/// cards, Bloodstone, ...); plays without them rank by score under every
+/// objective.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Objective {
+    /// The expected score
+    #[default]
+    Expected,
+    /// The median score
+    Median,
+    /// The chance of reaching the blind score (expected score if none is set)
+    BeatBlind,
+    /// The score reached with the given probability or better, e.g. 0.1
+    /// for the score a play falls below only 10% of the time
+    Quantile(f64),
+}
+
+impl std::str::FromStr for Objective {
+    type Err = anyhow::Error;
+
+    /// Parses "expected", "median", "beat-blind", or a lower percentile such
+    /// as "p10"
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let normalized = s.trim().to_lowercase().replace(['_', ' '], "-");
+        if let Some(percentile) = normalized.strip_prefix('p')
+            && let Ok(percentile) = percentile.parse::<f64>()
+            && (0.0..=100.0).contains(&percentile)
+        {
+            return Ok(Objective::Quantile(percentile / 100.0));
+        }
+        match normalized.as_str() {
+            "expected" => Ok(Objective::Expected),
+            "median" => Ok(Objective::Median),
+            "beat-blind" | "beat" => Ok(Objective::BeatBlind),
+            _ => anyhow::bail!(
+                "Invalid objective: {}. Use 'expected', 'median', 'beat-blind', or 'p<percentile>'",
+                s
+            ),
+        }
+    }
+}
+
+/// Restrictions on which plays the solver may choose
+///
+/// Used for boss blinds that force a card to be played (Cerulean Bell) and
+/// for experiments such as "best 5-card play only".
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SolveConstraints {
+    /// Cards every play must include
+    pub required_cards: Vec<Card>,
+    /// Exact number of cards every play must have
+    pub card_count: Option<usize>,
+}
+
+impl SolveConstraints {
+    /// Creates constraints that allow every play
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires every play to include the given card
+    pub fn with_required_card(mut self, card: Card) -> Self {
+        self.required_cards.push(card);
+        self
+    }
+
+    /// Requires every play to have exactly `count` cards
+    pub fn with_card_count(mut self, count: usize) -> Self {
+        self.card_count = Some(count);
+        self
+    }
+
+    /// Returns true if the played cards meet the constraints
+    ///
+    /// A card required twice must be played twice.
+    pub fn allows(&self, played: &[Card]) -> bool {
+        if self.card_count.is_some_and(|count| count != played.len()) {
+            return false;
+        }
+
+        let mut unmatched: Vec<&Card> = played.iter().collect();
+        self.required_cards.iter().all(|required| {
+            match unmatched.iter().position(|card| *card == required) {
+                Some(i) => {
+                    unmatched.swap_remove(i);
+                    true
+                }
+                None => false,
+            }
+        })
+    }
+}
+
+/// A play with the cards left in hand and its score
+pub(crate) type ScoredPlay = (Hand, Vec<Card>, ScoreResult);
 
 /// The solver finds optimal plays from a given hand
+#[derive(Clone)]
 pub struct Solver {
     calculator: ScoreCalculator,
+    money_weight: f64,
+    hands_remaining: u32,
+    discards_remaining: u32,
+    discard_samples: usize,
+    seed: u64,
+    blind_score: Option<Score>,
+    beam_width: Option<usize>,
+    exhaustive: bool,
+    played_hand_types: Vec<HandType>,
+    alternatives: usize,
+    best_per_hand_type: bool,
+    objective: Objective,
+    constraints: SolveConstraints,
+    deadline: Option<Instant>,
 }
 
 impl Solver {
     /// Creates a new solver with the given score calculator
     pub fn new(calculator: ScoreCalculator) -> Self {
-        Self { calculator }
+        Self {
+            calculator,
+            money_weight: 0.0,
+            hands_remaining: 1,
+            discards_remaining: 0,
+            discard_samples: DEFAULT_DISCARD_SAMPLES,
+            seed: 0,
+            blind_score: None,
+            beam_width: None,
+            exhaustive: false,
+            played_hand_types: Vec::new(),
+            alternatives: DEFAULT_ALTERNATIVES,
+            best_per_hand_type: false,
+            objective: Objective::Expected,
+            constraints: SolveConstraints::default(),
+            deadline: None,
+        }
+    }
+
+    /// Sets how many points each dollar of `money_delta` is worth when
+    /// ranking plays (0 ranks by score alone)
+    pub fn with_money_weight(mut self, money_weight: f64) -> Self {
+        self.money_weight = money_weight;
+        self
+    }
+
+    /// Sets the hands and discards left in the round (default: 1 hand, no
+    /// discards)
+    pub fn with_remaining(mut self, hands_remaining: u32, discards_remaining: u32) -> Self {
+        self.hands_remaining = hands_remaining;
+        self.discards_remaining = discards_remaining;
+        self
+    }
+
+    /// Sets the score needed to beat the blind, so each reported play gets
+    /// its chance of reaching it
+    pub fn with_blind_score(mut self, blind_score: Option<Score>) -> Self {
+        self.blind_score = blind_score;
+        self
+    }
+
+    /// Limits the search to the `width` best plays of each size, extending
+    /// them one card at a time (default: every play is scored)
+    pub fn with_beam_width(mut self, beam_width: Option<usize>) -> Self {
+        self.beam_width = beam_width;
+        self
+    }
+
+    /// Scores every play, including duplicates of identical cards, and
+    /// ignores any beam width
+    pub fn with_exhaustive(mut self, exhaustive: bool) -> Self {
+        self.exhaustive = exhaustive;
+        self
+    }
+
+    /// Sets the hand types already played this round, in order, which The
+    /// Eye and The Mouth restrict
+    pub fn with_played_hand_types(mut self, played_hand_types: Vec<HandType>) -> Self {
+        self.played_hand_types = played_hand_types;
+        self
+    }
+
+    /// Sets how many alternative plays are reported after the best play
+    pub fn with_alternatives(mut self, alternatives: usize) -> Self {
+        self.alternatives = alternatives;
+        self
+    }
+
+    /// Reports only the best play of each hand type, so alternatives show
+    /// different hands rather than different cards for the same hand
+    pub fn with_best_per_hand_type(mut self, best_per_hand_type: bool) -> Self {
+        self.best_per_hand_type = best_per_hand_type;
+        self
+    }
+
+    /// Sets what plays are ranked by (default: expected score)
+    pub fn with_objective(mut self, objective: Objective) -> Self {
+        self.objective = objective;
+        self
+    }
+
+    /// Sets the boss blind being played
+    pub fn with_boss_blind(mut self, boss_blind: Option<BossBlind>) -> Self {
+        self.calculator = self.calculator.with_boss_blind(boss_blind);
+        self
+    }
+
+    /// Restricts the plays the solver may choose
+    pub fn with_constraints(mut self, constraints: SolveConstraints) -> Self {
+        self.constraints = constraints;
+        self
+    }
+
+    /// Sets a time after which beam search and consumable planning stop
+    /// early with the best they have found (default: none)
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Returns true if the deadline, if any, has passed
+    pub fn past_deadline(&self) -> bool {
+        self.deadline.is_some_and(|deadline| Instant::now() > deadline)
+    }
+
+    /// Returns the hands left in the round, including the one being solved
+    pub fn hands_remaining(&self) -> u32 {
+        self.hands_remaining
+    }
+
+    /// Returns the discards left in the round
+    pub fn discards_remaining(&self) -> u32 {
+        self.discards_remaining
+    }
+
+    /// Sets how many redraws are sampled per candidate discard, and the seed
+    /// used to sample them
+    ///
+    /// Redraws are enumerated exactly when there are no more of them than
+    /// `samples`.
+    pub fn with_discard_sampling(mut self, samples: usize, seed: u64) -> Self {
+        self.discard_samples = samples.max(1);
+        self.seed = seed;
+        self
+    }
+
+    /// Returns the value used to rank a play: its score plus weighted money
+    fn rank_value(&self, result: &ScoreResult) -> f64 {
+        result.score.value() + self.money_weight * result.money_delta
+    }
+
+    /// Replaces the score calculator, keeping the other settings
+    pub fn with_calculator(mut self, calculator: ScoreCalculator) -> Self {
+        self.calculator = calculator;
+        self
+    }
+
+    /// Returns the score calculator used by this solver
+    pub fn calculator(&self) -> &ScoreCalculator {
+        &self.calculator
     }
 
     /// Finds the best play from the given cards
     pub fn solve(&self, cards: &[Card]) -> SolverResult {
-        if cards.is_empty() {
-            return SolverResult {
+        self.report(match self.beam_width {
+            Some(width) if !self.exhaustive => self.beam_search(cards, width),
+            _ => self.enumerate(cards),
+        })
+    }
+
+    /// Finds the best play from the given cards, reusing plays scored by
+    /// earlier calls with the same cache
+    ///
+    /// Meant for hands edited one card at a time: after adding or removing
+    /// a card, only plays that include it, or that now hold a card affecting
+    /// the score (a Steel card, a King with Baron), are scored again. Every
+    /// play is considered, ignoring any beam width. A cache must only be used
+    /// with one solver.
+    pub fn solve_incremental(&self, cards: &[Card], cache: &mut SolveCache) -> SolverResult {
+        self.report(self.enumerate_cached(cards, cache))
+    }
+
+    /// Ranks scored plays and reports the best one with its alternatives
+    fn report(&self, plays: Vec<ScoredPlay>) -> SolverResult {
+        let results = self.rank(plays);
+
+        // Extract best and alternatives, skipping plays that score the same
+        // cards as a better one
+        let mut seen = Vec::new();
+        let results: Vec<ScoredPlay> = results
+            .into_iter()
+            .filter(|(hand, _, score)| {
+                let key = self.alternative_key(hand, score);
+                let new = !seen.contains(&key);
+                if new {
+                    seen.push(key);
+                }
+                new
+            })
+            .take(self.alternatives + 1)
+            .map(|(hand, held, score)| {
+                let score = self.with_breakdown(&hand, &held, score);
+                (hand, held, score)
+            })
+            .collect();
+        let probabilities: Vec<f64> = match self.blind_score {
+            Some(blind_score) => results
+                .iter()
+                .map(|(hand, held, _)| self.beat_probability(hand, held, blind_score))
+                .collect(),
+            None => vec![],
+        };
+        let mut results = results.into_iter();
+        let best = results.next();
+        let alternatives: Vec<_> = results.map(|(h, _, s)| (h, s)).collect();
+
+        match best {
+            Some((best_hand, held_cards, best_score)) => SolverResult {
+                debuffed_cards: best_hand
+                    .cards
+                    .iter()
+                    .filter(|card| self.calculator.is_debuffed(card))
+                    .cloned()
+                    .collect(),
+                best_hand,
+                best_score: Some(best_score),
+                held_cards,
+                alternatives,
+                beat_probability: probabilities.first().copied(),
+                alternative_beat_probabilities: probabilities.into_iter().skip(1).collect(),
+                warnings: self.warnings(),
+                hands_remaining: self.hands_remaining,
+                discards_remaining: self.discards_remaining,
+            },
+            None => SolverResult {
                 best_hand: Hand::new(vec![]),
                 best_score: None,
-                alternatives: vec![],
+                held_cards: vec![],
+                alternatives,
+                beat_probability: None,
+                alternative_beat_probabilities: vec![],
+                debuffed_cards: vec![],
+                warnings: self.warnings(),
+                hands_remaining: self.hands_remaining,
+                discards_remaining: self.discards_remaining,
+            },
+        }
+    }
+
+    /// Finds the discard that maximizes the expected best play after redrawing
+    ///
+    /// Every set of up to five cards from `hand` is considered. The cards
+    /// drawn to replace them come from `deck_remaining`: every possible draw
+    /// is scored when there are few enough, otherwise draws are sampled.
+    /// The best discard is found even when it is not recommended, so both
+    /// lines can be compared; keeping the hand is recommended when no
+    /// discard beats the current best play, or when no discards are left.
+    pub fn solve_discard(
+        &self,
+        hand: &[Card],
+        deck_remaining: &[Card],
+        discards_left: u32,
+    ) -> DiscardResult {
+        let current_score = self.best_value(hand);
+        let mut best = DiscardResult {
+            discard: vec![],
+            kept: hand.to_vec(),
+            expected_score: f64::NEG_INFINITY,
+            current_score,
+            exact: true,
+            discards_left,
+        };
+
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        for discard_size in 1..=5.min(hand.len()) {
+            let draw_size = discard_size.min(deck_remaining.len());
+            // Count the draws before listing them, which only exact
+            // discards need
+            let exact = choose(deck_remaining.len(), draw_size) <= self.discard_samples as f64;
+            let draws = match exact {
+                true => Self::generate_combinations(deck_remaining, draw_size),
+                false => Vec::new(),
+            };
+
+            for combo in Self::generate_combinations(hand, discard_size) {
+                let (discard, kept) = Self::split_cards(hand, &combo);
+                let redraw = |draw: &[usize]| -> f64 {
+                    let mut next = kept.clone();
+                    next.extend(draw.iter().map(|&i| deck_remaining[i].clone()));
+                    self.best_value(&next)
+                };
+
+                let expected_score = if exact {
+                    draws.iter().map(|draw| redraw(draw)).sum::<f64>() / draws.len() as f64
+                } else {
+                    (0..self.discard_samples)
+                        .map(|_| {
+                            let draw = rand::seq::index::sample(
+                                &mut rng,
+                                deck_remaining.len(),
+                                draw_size,
+                            );
+                            redraw(&draw.into_vec())
+                        })
+                        .sum::<f64>()
+                        / self.discard_samples as f64
+                };
+
+                if expected_score > best.expected_score {
+                    best = DiscardResult {
+                        discard,
+                        kept,
+                        expected_score,
+                        current_score,
+                        exact,
+                        discards_left,
+                    };
+                }
+            }
+        }
+
+        if best.discard.is_empty() {
+            // No cards to discard, so discarding leaves the hand as it is
+            best.expected_score = current_score;
+        }
+        best
+    }
+
+    /// Estimates the chance that playing `hand` scores at least `blind_score`
+    ///
+    /// Chance-based effects (Lucky cards, Bloodstone, Space Joker, ...) are
+    /// rolled independently for each sampled outcome, with a fixed seed so
+    /// repeated solves agree.
+    pub fn beat_probability(&self, hand: &Hand, held: &[Card], blind_score: Score) -> f64 {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        let beats = (0..BEAT_PROBABILITY_SAMPLES)
+            .filter(|_| self.calculator.calculate_sampled(hand, held, &mut rng).score >= blind_score)
+            .count();
+        beats as f64 / BEAT_PROBABILITY_SAMPLES as f64
+    }
+
+    /// Finds the consumables worth using before playing, and the play after
+    ///
+    /// Consumables are chosen greedily: each step tries every remaining
+    /// consumable on every set of cards it can target, keeps the use that
+    /// raises the best play the most, and stops once no use helps.
+    pub fn solve_with_consumables(
+        &self,
+        cards: &[Card],
+        consumables: &[Consumable],
+    ) -> ConsumablePlan {
+        let baseline = self.solve(cards);
+        let mut hand = cards.to_vec();
+        let mut solver = self.clone();
+        let mut remaining = consumables.to_vec();
+        let mut uses = Vec::new();
+        let mut best = baseline.clone();
+
+        while !self.past_deadline() {
+            let mut improved = None;
+            let mut best_value = Self::result_value(&solver, &best);
+
+            for (i, consumable) in remaining.iter().enumerate() {
+                let target_sets: Vec<Vec<usize>> = match consumable.max_targets() {
+                    0 => vec![vec![]],
+                    max => (1..=max.min(hand.len()))
+                        .flat_map(|size| Self::generate_combinations(&hand, size))
+                        .collect(),
+                };
+
+                for targets in target_sets {
+                    let (next_hand, next_solver) = solver.use_consumable(&hand, consumable, &targets);
+                    let result = next_solver.solve(&next_hand);
+                    let value = Self::result_value(&next_solver, &result);
+
+                    if value > best_value {
+                        best_value = value;
+                        improved = Some((i, targets, result));
+                    }
+                }
+            }
+
+            let Some((i, targets, result)) = improved else {
+                break;
             };
+            let (next_hand, next_solver) = solver.use_consumable(&hand, &remaining[i], &targets);
+            uses.push(ConsumableUse {
+                consumable: remaining.remove(i),
+                targets: targets.iter().map(|&t| hand[t].clone()).collect(),
+            });
+            hand = next_hand;
+            solver = next_solver;
+            best = result;
+        }
+
+        ConsumablePlan {
+            uses,
+            hand,
+            result: best,
+            baseline,
+        }
+    }
+
+    /// Returns the hand, and a solver with the hand levels, after using a
+    /// consumable on the cards at `targets`
+    fn use_consumable(
+        &self,
+        hand: &[Card],
+        consumable: &Consumable,
+        targets: &[usize],
+    ) -> (Vec<Card>, Solver) {
+        let mut next_hand = hand.to_vec();
+        let mut levels = self.calculator.hand_levels().clone();
+        consumable.apply(&mut next_hand, targets, &mut levels);
+
+        let mut next_solver = self.clone();
+        next_solver.calculator = next_solver.calculator.with_hand_levels(levels);
+        (next_hand, next_solver)
+    }
+
+    /// Returns the ranking value of a solver result's best play
+    fn result_value(solver: &Solver, result: &SolverResult) -> f64 {
+        result
+            .best_score
+            .as_ref()
+            .map_or(0.0, |score| solver.rank_value(score))
+    }
+
+    /// Returns the ranking value of the best play from the given cards
+    fn best_value(&self, cards: &[Card]) -> f64 {
+        self.solve(cards)
+            .best_score
+            .map_or(0.0, |result| self.rank_value(&result))
+    }
+
+    /// Drops plays the boss blind or constraints do not allow, and sorts the
+    /// rest best first by the objective
+    pub(crate) fn rank(&self, mut results: Vec<ScoredPlay>) -> Vec<ScoredPlay> {
+        if self.constraints != SolveConstraints::default() {
+            results.retain(|(hand, _, _)| self.constraints.allows(&hand.cards));
+        }
+        if let Some(boss) = self.calculator.boss_blind() {
+            results.retain(|(hand, _, score)| {
+                boss.allows(score.hand_type, hand.cards.len(), &self.played_hand_types)
+            });
+        }
+
+        // Sort positions rather than the plays themselves, which are large
+        let mut order: Vec<usize> = (0..results.len()).collect();
+        if self.money_weight == 0.0 {
+            order.sort_by_key(|&i| std::cmp::Reverse(results[i].2.score));
+        } else {
+            order.sort_by(|&a, &b| {
+                self.rank_value(&results[b].2).total_cmp(&self.rank_value(&results[a].2))
+            });
+        }
+        let mut slots: Vec<Option<ScoredPlay>> = results.into_iter().map(Some).collect();
+        results = order.into_iter().filter_map(|i| slots[i].take()).collect();
+
+        // Stable sort, so plays the objective ties keep their expected order
+        if self.objective != Objective::Expected {
+            let mut valued: Vec<(f64, ScoredPlay)> = results
+                .into_iter()
+                .map(|play| (self.objective_value(&play), play))
+                .collect();
+            valued.sort_by(|a, b| b.0.total_cmp(&a.0));
+            results = valued.into_iter().map(|(_, play)| play).collect();
+        }
+        results
+    }
+
+    /// Returns the value of a play under the solver's objective
+    ///
+    /// Plays whose outcome is certain (every chance-based effect triggering
+    /// or not gives the same result) are valued directly; others from
+    /// [`BEAT_PROBABILITY_SAMPLES`] sampled outcomes.
+    fn objective_value(&self, (hand, held, expected): &ScoredPlay) -> f64 {
+        let quantile = match (self.objective, self.blind_score) {
+            (Objective::Expected, _) | (Objective::BeatBlind, None) => {
+                return self.rank_value(expected);
+            }
+            (Objective::BeatBlind, Some(blind_score)) => {
+                return self.beat_probability(hand, held, blind_score);
+            }
+            (Objective::Median, _) => 0.5,
+            (Objective::Quantile(quantile), _) => quantile,
+        };
+
+        let bound = |mode| {
+            let calculator = self.calculator.clone().with_probability_mode(mode);
+            self.rank_value(&calculator.calculate(hand, held))
+        };
+        let lowest = bound(ProbabilityMode::Pessimistic);
+        if lowest == bound(ProbabilityMode::Optimistic) {
+            return lowest;
+        }
+
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        let mut outcomes: Vec<f64> = (0..BEAT_PROBABILITY_SAMPLES)
+            .map(|_| self.rank_value(&self.calculator.calculate_sampled(hand, held, &mut rng)))
+            .collect();
+        outcomes.sort_by(f64::total_cmp);
+        outcomes[((outcomes.len() - 1) as f64 * quantile.clamp(0.0, 1.0)) as usize]
+    }
+
+    /// Scores every play of 1 to 5 cards; the cards not played stay in hand
+    /// and feed held-in-hand effects
+    ///
+    /// Unless the search is exhaustive, plays that are the same cards as an
+    /// earlier play (duplicate cards in hand) are skipped, since they can
+    /// never score differently, and so are plays dominated by their scoring
+    /// cards alone (see [`Solver::is_dominated`]).
+    pub(crate) fn enumerate(&self, cards: &[Card]) -> Vec<ScoredPlay> {
+        let canonical = Self::canonical_indices(cards);
+        let prune = !self.exhaustive && Self::has_duplicates(&canonical);
+        let held_matters = self.kicker_pruning().then(|| self.held_matters(cards));
+        let mut seen = HashSet::new();
+        let mut results = Vec::with_capacity(Self::play_count(cards.len()));
+
+        for hand_size in 1..=5.min(cards.len()) {
+            Self::for_each_combination(cards.len(), hand_size, |combo| {
+                if prune && !seen.insert(Self::play_key(&canonical, combo)) {
+                    return;
+                }
+                let (played, held) = Self::split_cards(cards, combo);
+                let hand = Hand::new(played);
+                if held_matters.as_ref().is_some_and(|held_matters| {
+                    self.is_dominated(&hand, combo, held_matters)
+                }) {
+                    return;
+                }
+                let score = self.score_for_ranking(&hand, &held);
+                results.push((hand, held, score));
+            });
+        }
+
+        results
+    }
+
+    /// Returns true if plays dominated by their scoring cards may be
+    /// skipped: the search is not exhaustive, and no boss blind or
+    /// constraint asks for more cards than score
+    fn kicker_pruning(&self) -> bool {
+        !self.exhaustive
+            && self.calculator.boss_blind().is_none()
+            && self.constraints == SolveConstraints::default()
+    }
+
+    /// Returns whether holding each card can change a play's result
+    fn held_matters(&self, cards: &[Card]) -> Vec<bool> {
+        cards.iter().map(|card| self.calculator.held_card_matters(card)).collect()
+    }
+
+    /// Returns true if the play at `combo` has cards that do not score
+    /// (kickers) yet scores exactly as its scoring cards alone would, which
+    /// is a smaller play scored before it
+    ///
+    /// A kicker changes nothing unless holding it would (a Steel card, a
+    /// King with Baron, ...) or it adds a hand type the played hand
+    /// contains for a joker (e.g., a second pair for Mad Joker), so such
+    /// plays score no more than the smaller play and can be skipped.
+    fn is_dominated(&self, hand: &Hand, combo: &[usize], held_matters: &[bool]) -> bool {
+        let rules = self.calculator.rules();
+        let (hand_type, scoring) = hand.evaluate_scoring(rules);
+        if scoring.len() == hand.cards.len() {
+            return false;
+        }
+        let kicker_matters = hand.cards.iter().zip(combo).any(|(card, &i)| {
+            held_matters[i] && !scoring.iter().any(|scored| std::ptr::eq(*scored, card))
+        });
+        if kicker_matters {
+            return false;
+        }
+
+        let scoring = Hand::new(scoring.into_iter().cloned().collect());
+        scoring.evaluate_scoring(rules).0 == hand_type
+            && self.calculator.same_contained_types(hand, &scoring)
+    }
+
+    /// Returns the play [`Solver::rank`] would put first of those
+    /// [`Solver::enumerate`] scores, keeping only the best so far
+    ///
+    /// Plays are scored in buffers reused from one play to the next, so only
+    /// improvements allocate. Objectives other than the expected value need
+    /// every play, and fall back to ranking them all.
+    pub(crate) fn best_play(&self, cards: &[Card]) -> Option<ScoredPlay> {
+        if self.objective != Objective::Expected {
+            return self.rank(self.enumerate(cards)).into_iter().next();
         }
 
-        let mut results: Vec<(Hand, ScoreResult)> = Vec::new();
+        let check_constraints = self.constraints != SolveConstraints::default();
+        let boss = self.calculator.boss_blind();
+        let held_matters = self.kicker_pruning().then(|| self.held_matters(cards));
+        let mut hand = Hand::new(Vec::with_capacity(5));
+        let mut held = Vec::with_capacity(cards.len());
+        let mut best: Option<ScoredPlay> = None;
 
-        // Generate all possible hand combinations (1 to 5 cards)
         for hand_size in 1..=5.min(cards.len()) {
-            let combinations = Self::generate_combinations(cards, hand_size);
+            Self::for_each_combination(cards.len(), hand_size, |combo| {
+                Self::split_into(cards, combo, &mut hand.cards, &mut held);
+                if check_constraints && !self.constraints.allows(&hand.cards) {
+                    return;
+                }
+                if held_matters.as_ref().is_some_and(|held_matters| {
+                    self.is_dominated(&hand, combo, held_matters)
+                }) {
+                    return;
+                }
+                let score = self.score_for_ranking(&hand, &held);
+                if boss.is_some_and(|boss| {
+                    !boss.allows(score.hand_type, hand.cards.len(), &self.played_hand_types)
+                }) {
+                    return;
+                }
+
+                // Ranking is a stable sort, so the first of equal plays wins
+                let better = best.as_ref().is_none_or(|(_, _, best)| {
+                    if self.money_weight == 0.0 {
+                        score.score > best.score
+                    } else {
+                        self.rank_value(&score).total_cmp(&self.rank_value(best)).is_gt()
+                    }
+                });
+                if better {
+                    best = Some((hand.clone(), held.clone(), score));
+                }
+            });
+        }
+
+        best
+    }
+
+    /// Scores every play like [`Solver::enumerate`], looking each up in the
+    /// cache first
+    ///
+    /// Plays are cached by their played cards, in order, and the held cards
+    /// that can change their score. The cache keeps only the plays of this
+    /// hand afterwards.
+    fn enumerate_cached(&self, cards: &[Card], cache: &mut SolveCache) -> Vec<ScoredPlay> {
+        let canonical = Self::canonical_indices(cards);
+        let prune = !self.exhaustive && Self::has_duplicates(&canonical);
+        let held_matters = self.kicker_pruning().then(|| self.held_matters(cards));
+        let mut seen = HashSet::new();
+        let mut scores = HashMap::new();
+        let mut results = Vec::new();
+
+        for hand_size in 1..=5.min(cards.len()) {
+            Self::for_each_combination(cards.len(), hand_size, |combo| {
+                if prune && !seen.insert(Self::play_key(&canonical, combo)) {
+                    return;
+                }
+
+                let (played, held) = Self::split_cards(cards, combo);
+                if held_matters.as_ref().is_some_and(|held_matters| {
+                    self.is_dominated(&Hand::new(played.clone()), combo, held_matters)
+                }) {
+                    return;
+                }
+                let relevant_held = held
+                    .iter()
+                    .filter(|card| self.calculator.held_card_matters(card))
+                    .cloned()
+                    .collect();
+                let key = (played, relevant_held);
+                let hand = Hand::new(key.0.clone());
+                let score = match scores.get(&key).or(cache.scores.get(&key)) {
+                    Some(score) => score.clone(),
+                    None => self.score_for_ranking(&hand, &held),
+                };
+
+                scores.insert(key, score.clone());
+                results.push((hand, held, score));
+            });
+        }
+
+        cache.scores = scores;
+        results
+    }
+
+    /// Builds plays one card at a time, keeping only the `width` best plays
+    /// of each size as the starting points for the next size
+    ///
+    /// Much faster than enumeration for large hands, but a play whose
+    /// smaller subsets all score poorly (e.g., a straight) can be missed.
+    /// Past the deadline, if any, no larger plays are tried.
+    pub(crate) fn beam_search(&self, cards: &[Card], width: usize) -> Vec<ScoredPlay> {
+        let canonical = Self::canonical_indices(cards);
+        let mut seen = HashSet::new();
+        let mut frontier: Vec<Vec<usize>> = vec![vec![]];
+        let mut results = Vec::new();
+
+        for size in 1..=5.min(cards.len()) {
+            if size > 1 && self.past_deadline() {
+                break;
+            }
+            let mut layer: Vec<(Vec<usize>, ScoredPlay)> = Vec::new();
+            for combo in &frontier {
+                for i in (0..cards.len()).filter(|i| !combo.contains(i)) {
+                    let mut next = combo.clone();
+                    next.push(i);
+                    next.sort_unstable();
+                    if seen.insert(Self::play_key(&canonical, &next)) {
+                        let play = self.score_play(cards, &next);
+                        layer.push((next, play));
+                    }
+                }
+            }
+
+            layer.sort_by(|a, b| self.rank_value(&b.1.2).total_cmp(&self.rank_value(&a.1.2)));
+            layer.truncate(width.max(1));
+            frontier = layer.iter().map(|(combo, _)| combo.clone()).collect();
+            results.extend(layer.into_iter().map(|(_, play)| play));
+        }
+
+        results
+    }
+
+    /// Scores playing the cards at `combo`, holding the rest
+    fn score_play(&self, cards: &[Card], combo: &[usize]) -> ScoredPlay {
+        let (played, held) = Self::split_cards(cards, combo);
+        let hand = Hand::new(played);
+        let score = self.score_for_ranking(&hand, &held);
+        (hand, held, score)
+    }
+
+    /// Scores a play for ranking, leaving out the breakdown's steps and
+    /// events unless chance effects are sampled
+    fn score_for_ranking(&self, hand: &Hand, held: &[Card]) -> ScoreResult {
+        match self.calculator.probability_mode() {
+            ProbabilityMode::Sampled(_) => self.calculator.calculate(hand, held),
+            _ => self.calculator.calculate_quick(hand, held),
+        }
+    }
+
+    /// Fills in the breakdown of a play scored for ranking
+    ///
+    /// Sampled plays already have theirs, and scoring them again would roll
+    /// their chance effects again.
+    fn with_breakdown(&self, hand: &Hand, held: &[Card], score: ScoreResult) -> ScoreResult {
+        match self.calculator.probability_mode() {
+            ProbabilityMode::Sampled(_) => score,
+            _ => self.calculator.calculate(hand, held),
+        }
+    }
+
+    /// Maps each card to the index of the first identical card in `cards`
+    fn canonical_indices(cards: &[Card]) -> Vec<usize> {
+        cards
+            .iter()
+            .map(|card| cards.iter().position(|c| c == card).unwrap_or_default())
+            .collect()
+    }
+
+    /// Returns the number of plays of 1 to 5 cards from a hand of `len`
+    fn play_count(len: usize) -> usize {
+        let mut combinations = 1;
+        (1..=5.min(len))
+            .map(|size| {
+                combinations = combinations * (len + 1 - size) / size;
+                combinations
+            })
+            .sum()
+    }
+
+    /// Returns true if any card is identical to an earlier one
+    fn has_duplicates(canonical: &[usize]) -> bool {
+        canonical.iter().enumerate().any(|(i, &first)| first != i)
+    }
+
+    /// Returns a key that is equal for plays of identical cards
+    fn play_key(canonical: &[usize], combo: &[usize]) -> Vec<usize> {
+        let mut key: Vec<usize> = combo.iter().map(|&i| canonical[i]).collect();
+        key.sort_unstable();
+        key
+    }
+
+    /// Returns a key that is equal for plays reported as duplicates: the
+    /// same hand type scoring the same cards, or just the same hand type
+    /// when reporting the best play per hand type
+    fn alternative_key(&self, hand: &Hand, score: &ScoreResult) -> (HandType, Vec<String>) {
+        if self.best_per_hand_type {
+            return (score.hand_type, vec![]);
+        }
+        let mut cards: Vec<String> = hand
+            .scoring_cards_with(self.calculator.rules())
+            .into_iter()
+            .map(|card| format!("{:?}", card))
+            .collect();
+        cards.sort_unstable();
+        (score.hand_type, cards)
+    }
+
+    /// Returns warnings about inputs that make the scores inaccurate
+    fn warnings(&self) -> Vec<String> {
+        let missing_deck = self.calculator.jokers_missing_deck().into_iter().map(|joker| {
+            format!("{} depends on the full deck, but no deck was provided", joker.kind.name())
+        });
+        let missing_target = self.calculator.jokers_missing_target().into_iter().map(|joker| {
+            format!("{} has no target card or suit set, so it has no effect", joker.kind.name())
+        });
+
+        missing_deck.chain(missing_target).collect()
+    }
 
-            for combo in combinations {
-                let hand = Hand::new(combo);
-                let score = self.calculator.calculate(&hand);
-                results.push((hand, score));
+    /// Ranks shop offers by how much they raise the run's mean round score
+    ///
+    /// Uses [`SHOP_SIMULATION_RUNS`] simulated rounds with a fixed seed; see
+    /// [`Solver::evaluate_shop_sampled`].
+    pub fn evaluate_shop(run: &GameRunState, offers: &[ShopOffer]) -> Vec<ShopEvaluation> {
+        Self::evaluate_shop_sampled(run, offers, SHOP_SIMULATION_RUNS, 0)
+    }
+
+    /// Ranks shop offers by how much they raise the run's mean round score
+    ///
+    /// The run is simulated as-is and once with each offer bought, drawing
+    /// the same hands every time so differences come from the purchase and
+    /// not the shuffle. A pack is valued by its best choice. Offers are
+    /// sorted by score gain, best first.
+    pub fn evaluate_shop_sampled(
+        run: &GameRunState,
+        offers: &[ShopOffer],
+        num_runs: usize,
+        seed: u64,
+    ) -> Vec<ShopEvaluation> {
+        let mean_score = |offer: Option<&ShopOffer>| -> f64 {
+            let mut run = run.clone();
+            let mut hand_size_bonus = 0;
+            if let Some(offer) = offer {
+                offer.apply(&mut run);
+                hand_size_bonus = offer.hand_size_bonus();
+            }
+            let mut config = run.simulation_config(num_runs, Some(seed));
+            config.hand_size += hand_size_bonus;
+            Simulator::new(run.solver()).simulate(config).mean_score
+        };
+        let baseline = mean_score(None);
+
+        let mut evaluations: Vec<ShopEvaluation> = offers
+            .iter()
+            .map(|offer| {
+                let (choice, mean_score) = match offer {
+                    ShopOffer::Pack { choices, .. } => choices
+                        .iter()
+                        .map(|choice| (Some(choice.clone()), mean_score(Some(choice))))
+                        .max_by(|a, b| a.1.total_cmp(&b.1))
+                        .unwrap_or((None, baseline)),
+                    _ => (None, mean_score(Some(offer))),
+                };
+
+                ShopEvaluation {
+                    offer: offer.clone(),
+                    cost: offer.cost(),
+                    affordable: offer.cost() <= run.money,
+                    choice,
+                    mean_score,
+                    score_gain: mean_score - baseline,
+                }
+            })
+            .collect();
+
+        evaluations.sort_by(|a, b| b.score_gain.total_cmp(&a.score_gain));
+        evaluations
+    }
+
+    /// Finds the joker order that scores the given hand highest, with
+    /// `held` in hand (for Baron, Shoot the Moon, ...)
+    ///
+    /// Every permutation of `jokers` is scored with this solver's hand levels
+    /// and probability mode. Ties keep the current order, so a zero delta
+    /// means the current order is already optimal. Fails for more than
+    /// [`MAX_REORDERED_JOKERS`] jokers, whose orders are too many to try.
+    pub fn optimize_joker_order(
+        &self,
+        jokers: &[Joker],
+        hand: &Hand,
+        held: &[Card],
+    ) -> anyhow::Result<JokerOrderResult> {
+        anyhow::ensure!(
+            jokers.len() <= MAX_REORDERED_JOKERS,
+            "Can only reorder up to {} jokers, not {}",
+            MAX_REORDERED_JOKERS,
+            jokers.len()
+        );
+        let score_order = |order: &[Joker]| -> Score {
+            self.calculator
+                .clone()
+                .with_jokers(order.to_vec())
+                .calculate(hand, held)
+                .score
+        };
+
+        let current_score = score_order(jokers);
+        let mut best_order = jokers.to_vec();
+        let mut best_score = current_score;
+
+        // The first permutation is the current order
+        for order in Self::permutations(jokers).skip(1) {
+            let score = score_order(&order);
+            if score > best_score {
+                best_score = score;
+                best_order = order;
             }
         }
 
-        // Sort by score (descending)
-        results.sort_by(|a, b| b.1.score.cmp(&a.1.score));
+        Ok(JokerOrderResult {
+            order: best_order,
+            best_score,
+            current_score,
+            score_delta: best_score.value() - current_score.value(),
+        })
+    }
+
+    /// Generates every ordering of the given jokers, starting with the
+    /// order given
+    pub(crate) fn permutations(jokers: &[Joker]) -> Permutations {
+        Permutations {
+            current: jokers.to_vec(),
+            counters: vec![0; jokers.len()],
+            i: 0,
+        }
+    }
 
-        // Extract best and alternatives
-        let best = results.first().cloned();
-        let alternatives: Vec<_> = results.into_iter().skip(1).take(3).collect();
+    /// Splits cards into the played subset (by index) and the held remainder
+    fn split_cards(cards: &[Card], indices: &[usize]) -> (Vec<Card>, Vec<Card>) {
+        let mut played = Vec::with_capacity(indices.len());
+        let mut held = Vec::with_capacity(cards.len() - indices.len());
+        Self::split_into(cards, indices, &mut played, &mut held);
+        (played, held)
+    }
 
-        SolverResult {
-            best_hand: best.as_ref().map(|(h, _)| h.clone()).unwrap_or_else(|| Hand::new(vec![])),
-            best_score: best.map(|(_, s)| s),
-            alternatives,
+    /// Splits cards like [`Solver::split_cards`], reusing the given buffers
+    fn split_into(cards: &[Card], indices: &[usize], played: &mut Vec<Card>, held: &mut Vec<Card>) {
+        played.clear();
+        held.clear();
+        for (i, card) in cards.iter().enumerate() {
+            if indices.contains(&i) {
+                played.push(card.clone());
+            } else {
+                held.push(card.clone());
+            }
         }
     }
 
-    /// Generates all combinations of cards of a given size
-    fn generate_combinations(cards: &[Card], size: usize) -> Vec<Vec<Card>> {
+    /// Calls `f` with every combination of `size` indices below `len`, in
+    /// the order [`Solver::generate_combinations`] returns them, without
+    /// allocating each one
+    fn for_each_combination(len: usize, size: usize, mut f: impl FnMut(&[usize])) {
+        if size > len {
+            return;
+        }
+        let mut combo: Vec<usize> = (0..size).collect();
+        loop {
+            f(&combo);
+
+            // Advance the rightmost index that can still move right
+            let Some(i) = (0..size).rev().find(|&i| combo[i] < len - size + i) else {
+                return;
+            };
+            combo[i] += 1;
+            for j in i + 1..size {
+                combo[j] = combo[j - 1] + 1;
+            }
+        }
+    }
+
+    /// Generates all combinations of card indices of a given size
+    fn generate_combinations(cards: &[Card], size: usize) -> Vec<Vec<usize>> {
         let mut results = Vec::new();
         let mut current = Vec::new();
-        Self::generate_combinations_recursive(cards, size, 0, &mut current, &mut results);
+        Self::generate_combinations_recursive(cards.len(), size, 0, &mut current, &mut results);
         results
     }
 
     /// Recursive helper for generating combinations
     fn generate_combinations_recursive(
-        cards: &[Card],
+        len: usize,
         size: usize,
         start: usize,
-        current: &mut Vec<Card>,
-        results: &mut Vec<Vec<Card>>,
+        current: &mut Vec<usize>,
+        results: &mut Vec<Vec<usize>>,
     ) {
         if current.len() == size {
             results.push(current.clone());
             return;
         }
 
-        for i in start..cards.len() {
-            current.push(cards[i].clone());
-            Self::generate_combinations_recursive(cards, size, i + 1, current, results);
+        for i in start..len {
+            current.push(i);
+            Self::generate_combinations_recursive(len, size, i + 1, current, results);
             current.pop();
         }
     }
 }
 
+/// Scored plays kept between calls to [`Solver::solve_incremental`]
+#[derive(Debug, Clone, Default)]
+pub struct SolveCache {
+    scores: HashMap<(Vec<Card>, Vec<Card>), ScoreResult>,
+}
+
+impl SolveCache {
+    /// Creates an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of plays cached
+    pub fn len(&self) -> usize {
+        self.scores.len()
+    }
+
+    /// Returns true if no plays are cached
+    pub fn is_empty(&self) -> bool {
+        self.scores.is_empty()
+    }
+}
+
 /// Result from the solver containing the best play and alternatives
 #[derive(Debug, Clone)]
 pub struct SolverResult {
     pub best_hand: Hand,
     pub best_score: Option<ScoreResult>,
+    pub held_cards: Vec<Card>,
     pub alternatives: Vec<(Hand, ScoreResult)>,
+    /// Chance the best play reaches the blind score, if one was set
+    pub beat_probability: Option<f64>,
+    /// Chance each alternative reaches the blind score, in the same order
+    pub alternative_beat_probabilities: Vec<f64>,
+    /// Cards in the best play that the boss blind debuffs
+    pub debuffed_cards: Vec<Card>,
+    /// Problems with the inputs that may make the scores inaccurate
+    pub warnings: Vec<String>,
+    /// Hands left in the round, including the one being solved
+    pub hands_remaining: u32,
+    /// Discards left in the round
+    pub discards_remaining: u32,
+}
+
+impl SolverResult {
+    /// Returns how many hands scoring like the best play it takes to reach
+    /// `blind_score`, or `None` if the best play scores nothing
+    pub fn hands_needed(&self, blind_score: Score) -> Option<u32> {
+        let score = self.best_score.as_ref()?.score;
+        if score == Score::ZERO {
+            return None;
+        }
+        Some((blind_score.value() / score.value()).ceil().max(1.0) as u32)
+    }
+
+    /// Explains why the best play beats the first alternative, or returns
+    /// `None` if there is no alternative
+    pub fn explain(&self) -> Option<PlayComparison> {
+        let best = self.best_score.as_ref()?;
+        let (_, runner_up) = self.alternatives.first()?;
+        Some(PlayComparison::new(best, runner_up))
+    }
+
+    /// Returns whether repeating the best play with the hands remaining
+    /// reaches `blind_score`
+    ///
+    /// An upper bound: later hands are drawn rather than dealt this play
+    /// again, and discards are not considered. [`RoundPlanner`] plans the
+    /// round from the cards left to draw instead.
+    ///
+    /// [`RoundPlanner`]: super::planner::RoundPlanner
+    pub fn beats_blind_in_round(&self, blind_score: Score) -> bool {
+        self.hands_needed(blind_score)
+            .is_some_and(|hands| hands <= self.hands_remaining)
+    }
+}
+
+/// Result of the discard solver
+#[derive(Debug, Clone)]
+pub struct DiscardResult {
+    /// Cards the best discard throws away (empty only for an empty hand)
+    pub discard: Vec<Card>,
+    /// Cards kept in hand
+    pub kept: Vec<Card>,
+    /// Expected value of the best play after the best discard and redraw,
+    /// whether or not discarding is recommended
+    pub expected_score: f64,
+    /// Value of the best play from the hand as it is
+    pub current_score: f64,
+    /// Whether every possible redraw was scored (otherwise redraws were sampled)
+    pub exact: bool,
+    /// Discards left in the round, including this one
+    pub discards_left: u32,
+}
+
+impl DiscardResult {
+    /// Returns whether discarding is recommended over playing now: a
+    /// discard is left and the best one is expected to beat playing now
+    pub fn should_discard(&self) -> bool {
+        self.discards_left > 0 && !self.discard.is_empty() && self.expected_gain() > 0.0
+    }
+
+    /// Expected gain of the best discard over playing now, negative if
+    /// playing now is better
+    pub fn expected_gain(&self) -> f64 {
+        self.expected_score - self.current_score
+    }
+}
+
+/// A consumable used before playing, with the cards it was used on
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsumableUse {
+    pub consumable: Consumable,
+    /// The targeted cards, as they were before the consumable was used
+    pub targets: Vec<Card>,
+}
+
+impl std::fmt::Display for ConsumableUse {
+    /// Formats the use as "The Star on 2♣ 5♣" or "Jupiter"
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.consumable.name())?;
+        if !self.targets.is_empty() {
+            let targets: Vec<String> = self.targets.iter().map(Card::to_string).collect();
+            write!(f, " on {}", targets.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Result of solving with consumables used before the play
+#[derive(Debug, Clone)]
+pub struct ConsumablePlan {
+    /// Consumables to use, in order (empty if none improve the play)
+    pub uses: Vec<ConsumableUse>,
+    /// The hand after the consumables are used
+    pub hand: Vec<Card>,
+    /// The best play after the consumables are used
+    pub result: SolverResult,
+    /// The best play without using any consumables
+    pub baseline: SolverResult,
+}
+
+impl ConsumablePlan {
+    /// Returns how many times the baseline score the planned play scores,
+    /// or `None` if the baseline scores nothing
+    pub fn score_ratio(&self) -> Option<f64> {
+        let baseline = self.baseline.best_score.as_ref()?.score.value();
+        let planned = self.result.best_score.as_ref()?.score.value();
+        (baseline > 0.0).then(|| planned / baseline)
+    }
+}
+
+/// A shop offer valued by simulating the run with it bought
+#[derive(Debug, Clone)]
+pub struct ShopEvaluation {
+    pub offer: ShopOffer,
+    /// Price of the offer
+    pub cost: u32,
+    /// Whether the run has the money to buy it
+    pub affordable: bool,
+    /// The pack choice to take, for packs
+    pub choice: Option<ShopOffer>,
+    /// Mean round score with the offer bought
+    pub mean_score: f64,
+    /// Mean round score gained over not buying anything
+    pub score_gain: f64,
+}
+
+impl ShopEvaluation {
+    /// Returns the score gained per dollar spent
+    pub fn gain_per_dollar(&self) -> f64 {
+        if self.cost == 0 {
+            return self.score_gain;
+        }
+        self.score_gain / self.cost as f64
+    }
+}
+
+/// Every ordering of a list of jokers, generated one at a time by Heap's
+/// algorithm
+pub(crate) struct Permutations {
+    current: Vec<Joker>,
+    counters: Vec<usize>,
+    /// The position being permuted, or 0 before the first ordering
+    i: usize,
+}
+
+impl Iterator for Permutations {
+    type Item = Vec<Joker>;
+
+    fn next(&mut self) -> Option<Vec<Joker>> {
+        if self.i == 0 {
+            self.i = 1;
+            return Some(self.current.clone());
+        }
+        while self.i < self.current.len() {
+            let i = self.i;
+            if self.counters[i] < i {
+                let swap_with = if i.is_multiple_of(2) { 0 } else { self.counters[i] };
+                self.current.swap(swap_with, i);
+                self.counters[i] += 1;
+                self.i = 1;
+                return Some(self.current.clone());
+            }
+            self.counters[i] = 0;
+            self.i += 1;
+        }
+        None
+    }
+}
+
+/// Result of optimizing the order of jokers
+#[derive(Debug, Clone)]
+pub struct JokerOrderResult {
+    /// The best joker order found
+    pub order: Vec<Joker>,
+    /// Score with the best order
+    pub best_score: Score,
+    /// Score with the order as given
+    pub current_score: Score,
+    /// Improvement of the best order over the current order
+    pub score_delta: f64,
 }
 
 #[cfg(test)]
@@ -107,6 +1369,11 @@ mod tests {
 
         let combos = Solver::generate_combinations(&cards, 2);
         assert_eq!(combos.len(), 3); // C(3,2) = 3
+
+        let mut visited = Vec::new();
+        Solver::for_each_combination(cards.len(), 2, |combo| visited.push(combo.to_vec()));
+        assert_eq!(visited, combos);
+        Solver::for_each_combination(2, 3, |_| panic!("no combinations of 3 from 2"));
     }
 
     #[test]
@@ -125,4 +1392,639 @@ mod tests {
         // Should find a valid hand (pair would be 2 cards, but solver might find a better combination)
         assert!(!result.best_hand.cards.is_empty());
     }
+
+    #[test]
+    fn test_hands_needed_for_blind() {
+        let cards = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+        ];
+
+        // Pair of Aces: (10 + 22) x 2 = 64
+        let solver = Solver::new(ScoreCalculator::new(vec![])).with_remaining(3, 2);
+        let result = solver.solve(&cards);
+
+        assert_eq!(result.discards_remaining, 2);
+        assert_eq!(result.hands_needed(Score::from(64u64)), Some(1));
+        assert_eq!(result.hands_needed(Score::from(150u64)), Some(3));
+        assert!(result.beats_blind_in_round(Score::from(150u64)));
+        assert!(!result.beats_blind_in_round(Score::from(200u64)));
+    }
+
+    #[test]
+    fn test_beat_probability_with_lucky_cards() {
+        use crate::core::card::Enhancement;
+
+        let cards = vec![
+            Card::new(Rank::Ace, Suit::Hearts).with_enhancement(Enhancement::Lucky),
+            Card::new(Rank::Ace, Suit::Spades),
+        ];
+
+        // Pair of Aces: 64 without the Lucky mult, (10 + 22) x 22 = 704 with it
+        let solver = Solver::new(ScoreCalculator::new(vec![]))
+            .with_blind_score(Some(Score::from(500u64)));
+        let result = solver.solve(&cards);
+
+        let probability = result.beat_probability.unwrap();
+        assert!(probability > 0.1 && probability < 0.3, "{}", probability);
+        assert_eq!(result.alternative_beat_probabilities.len(), result.alternatives.len());
+
+        let certain = solver.with_blind_score(Some(Score::from(64u64))).solve(&cards);
+        assert_eq!(certain.beat_probability, Some(1.0));
+    }
+
+    #[test]
+    fn test_duplicate_plays_are_pruned() {
+        let cards = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::King, Suit::Clubs),
+        ];
+
+        let pruned = Solver::new(ScoreCalculator::new(vec![])).enumerate(&cards);
+        let exhaustive = Solver::new(ScoreCalculator::new(vec![]))
+            .with_exhaustive(true)
+            .enumerate(&cards);
+
+        assert_eq!(exhaustive.len(), 7);
+        assert_eq!(pruned.len(), 3); // A, K, AA; AK and AAK add a kicker
+    }
+
+    #[test]
+    fn test_dominated_plays_are_pruned() {
+        use crate::core::card::Enhancement;
+        use crate::core::joker::JokerKind;
+
+        // Returns the number of plays scored, checking none better is lost
+        let plays = |jokers: Vec<Joker>, cards: &[Card]| {
+            let solver = Solver::new(ScoreCalculator::new(jokers));
+            let pruned = solver.enumerate(cards);
+            let exhaustive = solver.clone().with_exhaustive(true).enumerate(cards);
+            let best = |plays: &[ScoredPlay]| plays.iter().map(|play| play.2.score).max();
+            assert_eq!(best(&pruned), best(&exhaustive));
+            pruned.len()
+        };
+        let aces = [Card::new(Rank::Ace, Suit::Hearts), Card::new(Rank::Ace, Suit::Spades)];
+        let with = |card: Card| [&aces[..], &[card]].concat();
+
+        // A, A, 7, and AA; the 7 adds nothing to AA or an Ace
+        assert_eq!(plays(vec![], &with(Card::new(Rank::Seven, Suit::Clubs))), 4);
+
+        // Baron wants the King held and the Steel card is better held, so
+        // none of the 7 plays is dominated
+        let king = with(Card::new(Rank::King, Suit::Clubs));
+        assert_eq!(plays(vec![Joker::new(JokerKind::Baron)], &king), 7);
+        let steel = Card::new(Rank::Two, Suit::Clubs).with_enhancement(Enhancement::Steel);
+        assert_eq!(plays(vec![], &with(steel)), 7);
+
+        // The 2♣ does not score in the four-card flush, but gives Jolly
+        // Joker its pair
+        let cards: Vec<Card> = [Rank::Two, Rank::Five, Rank::Eight, Rank::Jack, Rank::Two]
+            .into_iter()
+            .zip([Suit::Hearts, Suit::Hearts, Suit::Hearts, Suit::Hearts, Suit::Clubs])
+            .map(|(rank, suit)| Card::new(rank, suit))
+            .collect();
+        let jokers = vec![Joker::new(JokerKind::FourFingers), Joker::new(JokerKind::JollyJoker)];
+        let best = Solver::new(ScoreCalculator::new(jokers)).solve(&cards);
+        assert_eq!(best.best_hand.cards.len(), 5);
+    }
+
+    #[test]
+    fn test_alternatives_are_deduplicated() {
+        let cards = vec![
+            Card::new(Rank::King, Suit::Hearts),
+            Card::new(Rank::King, Suit::Clubs),
+            Card::new(Rank::Seven, Suit::Spades),
+            Card::new(Rank::Four, Suit::Diamonds),
+            Card::new(Rank::Three, Suit::Hearts),
+        ];
+        let solver = Solver::new(ScoreCalculator::new(vec![])).with_alternatives(5);
+
+        // KK with any kickers scores the same cards, so only one pair is reported
+        let result = solver.solve(&cards);
+        assert_eq!(result.best_score.as_ref().unwrap().hand_type, HandType::Pair);
+        assert_eq!(result.alternatives.len(), 5);
+        assert!(result.alternatives.iter().all(|(_, score)| score.hand_type == HandType::HighCard));
+
+        let result = solver.with_best_per_hand_type(true).solve(&cards);
+        assert_eq!(result.alternatives.len(), 1);
+        assert_eq!(result.alternatives[0].1.hand_type, HandType::HighCard);
+    }
+
+    #[test]
+    fn test_solve_incremental_matches_solve() {
+        use crate::core::card::Enhancement;
+        use crate::core::joker::JokerKind;
+
+        let solver = Solver::new(ScoreCalculator::new(vec![Joker::new(JokerKind::Baron)]));
+        let mut cache = SolveCache::new();
+        let mut cards = vec![
+            Card::new(Rank::Queen, Suit::Hearts),
+            Card::new(Rank::Queen, Suit::Clubs),
+            Card::new(Rank::Four, Suit::Spades),
+        ];
+        let score = |result: &SolverResult| result.best_score.as_ref().unwrap().score;
+
+        assert!(solver.solve_incremental(&[], &mut cache).best_score.is_none());
+        for card in [
+            Card::new(Rank::King, Suit::Spades),
+            Card::new(Rank::Nine, Suit::Hearts).with_enhancement(Enhancement::Steel),
+            Card::new(Rank::Queen, Suit::Spades),
+        ] {
+            let incremental = solver.solve_incremental(&cards, &mut cache);
+            assert_eq!(score(&incremental), score(&solver.solve(&cards)));
+            assert_eq!(incremental.best_hand, solver.solve(&cards).best_hand);
+            cards.insert(1, card);
+        }
+
+        cards.remove(0);
+        let incremental = solver.solve_incremental(&cards, &mut cache);
+        assert_eq!(score(&incremental), score(&solver.solve(&cards)));
+        assert_eq!(cache.len(), solver.enumerate(&cards).len());
+    }
+
+    #[test]
+    fn test_risk_averse_objective_avoids_lucky_cards() {
+        use crate::core::card::Enhancement;
+
+        // A Lucky 2 pays +20 mult 1 time in 5; a plain Ace is certain
+        let cards = vec![
+            Card::new(Rank::Two, Suit::Hearts).with_enhancement(Enhancement::Lucky),
+            Card::new(Rank::Ace, Suit::Clubs),
+        ];
+        let solver = Solver::new(ScoreCalculator::new(vec![])).with_alternatives(1);
+        let play = |objective| {
+            let result = solver.clone().with_objective(objective).solve(&cards);
+            result.best_hand.cards[0].rank
+        };
+
+        assert_eq!(play(Objective::Expected), Rank::Two);
+        assert_eq!(play(Objective::Median), Rank::Ace);
+        assert_eq!(play("p10".parse().unwrap()), Rank::Ace);
+        assert_eq!("beat-blind".parse::<Objective>().unwrap(), Objective::BeatBlind);
+        assert!("p200".parse::<Objective>().is_err());
+
+        // Only the Lucky card can reach 100 points
+        let result = solver
+            .with_objective(Objective::BeatBlind)
+            .with_blind_score(Some(Score::from(100u64)))
+            .solve(&cards);
+        assert_eq!(result.best_hand.cards[0].rank, Rank::Two);
+        assert!(result.beat_probability.unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_constraints_restrict_plays() {
+        let cards = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Clubs),
+            Card::new(Rank::Seven, Suit::Spades),
+            Card::new(Rank::Four, Suit::Diamonds),
+            Card::new(Rank::Three, Suit::Hearts),
+        ];
+        let solve = |constraints| {
+            Solver::new(ScoreCalculator::new(vec![]))
+                .with_constraints(constraints)
+                .solve(&cards)
+        };
+
+        // Cerulean Bell forcing the 4♦ still leaves the pair of Aces playable
+        let forced = solve(SolveConstraints::new().with_required_card(cards[3].clone()));
+        assert!(forced.best_hand.cards.contains(&cards[3]));
+        assert_eq!(forced.best_score.unwrap().hand_type, HandType::Pair);
+        assert!(forced.alternatives.iter().all(|(hand, _)| hand.cards.contains(&cards[3])));
+
+        let five = solve(SolveConstraints::new().with_card_count(5));
+        assert_eq!(five.best_hand.cards.len(), 5);
+        assert!(five.alternatives.is_empty());
+
+        let twice = SolveConstraints::new()
+            .with_required_card(cards[0].clone())
+            .with_required_card(cards[0].clone());
+        assert!(solve(twice).best_score.is_none());
+    }
+
+    #[test]
+    fn test_explain_best_play() {
+        let cards = vec![
+            Card::new(Rank::King, Suit::Hearts),
+            Card::new(Rank::King, Suit::Clubs),
+            Card::new(Rank::Four, Suit::Spades),
+        ];
+        let solver = Solver::new(ScoreCalculator::new(vec![]));
+
+        let comparison = solver.solve(&cards).explain().unwrap();
+        assert_eq!(comparison.best_hand_type, HandType::Pair);
+        assert_eq!(comparison.runner_up_hand_type, HandType::HighCard);
+        assert!(comparison.to_string().starts_with("Pair base"));
+
+        assert!(solver.solve(&cards[..1]).explain().is_none());
+    }
+
+    #[test]
+    fn test_beam_search_finds_best_play() {
+        use crate::core::hand::HandType;
+
+        let cards = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+            Card::new(Rank::Ace, Suit::Clubs),
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Seven, Suit::Diamonds),
+            Card::new(Rank::Nine, Suit::Spades),
+            Card::new(Rank::Jack, Suit::Clubs),
+            Card::new(Rank::Four, Suit::Diamonds),
+        ];
+
+        let solver = Solver::new(ScoreCalculator::new(vec![])).with_beam_width(Some(3));
+        let result = solver.solve(&cards);
+        assert_eq!(result.best_score.unwrap().hand_type, HandType::ThreeOfAKind);
+        assert!(solver.beam_search(&cards, 3).len() <= 15);
+    }
+
+    #[test]
+    fn test_boss_blind_constraints() {
+        use crate::core::blind::BossBlind;
+
+        let cards = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+            Card::new(Rank::King, Suit::Clubs),
+            Card::new(Rank::Nine, Suit::Clubs),
+            Card::new(Rank::Four, Suit::Diamonds),
+            Card::new(Rank::Two, Suit::Diamonds),
+        ];
+        let solver = |boss| Solver::new(ScoreCalculator::new(vec![]).with_boss_blind(Some(boss)));
+
+        let psychic = solver(BossBlind::ThePsychic).solve(&cards);
+        assert_eq!(psychic.best_hand.cards.len(), 5);
+
+        let eye = solver(BossBlind::TheEye)
+            .with_played_hand_types(vec![HandType::Pair])
+            .solve(&cards);
+        assert_eq!(eye.best_score.unwrap().hand_type, HandType::HighCard);
+
+        let mouth = solver(BossBlind::TheMouth)
+            .with_played_hand_types(vec![HandType::Flush])
+            .solve(&cards);
+        assert!(mouth.best_score.is_none());
+    }
+
+    #[test]
+    fn test_boss_constraints_skipped_when_disabled() {
+        use crate::core::blind::BossBlind;
+
+        use crate::core::joker::JokerKind;
+
+        // Chicot disables The Psychic, so the Pair needs no filler cards
+        let cards = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+            Card::new(Rank::King, Suit::Clubs),
+            Card::new(Rank::Nine, Suit::Clubs),
+            Card::new(Rank::Four, Suit::Diamonds),
+        ];
+        let calculator = ScoreCalculator::new(vec![Joker::new(JokerKind::Chicot)])
+            .with_boss_blind(Some(BossBlind::ThePsychic));
+        let result = Solver::new(calculator).solve(&cards);
+        assert_eq!(result.best_score.unwrap().hand_type, HandType::Pair);
+        assert_eq!(result.best_hand.cards.len(), 2);
+    }
+
+    #[test]
+    fn test_suit_debuff_reranks_plays() {
+        use crate::core::blind::BossBlind;
+
+        let cards = vec![
+            Card::new(Rank::King, Suit::Hearts),
+            Card::new(Rank::King, Suit::Diamonds),
+            Card::new(Rank::Three, Suit::Clubs),
+            Card::new(Rank::Three, Suit::Spades),
+            Card::new(Rank::Three, Suit::Diamonds),
+        ];
+        let calculator = ScoreCalculator::new(vec![]).with_boss_blind(Some(BossBlind::TheWindow));
+        let result = Solver::new(calculator).solve(&cards);
+
+        let best = result.best_score.unwrap();
+        assert_eq!(best.hand_type, HandType::FullHouse);
+        assert_eq!(result.debuffed_cards.len(), 2);
+        assert!(result.debuffed_cards.iter().all(|card| card.suit == Suit::Diamonds));
+    }
+
+    #[test]
+    fn test_consumables_used_before_play() {
+        use crate::core::consumable::{Planet, Tarot};
+
+        let cards = vec![
+            Card::new(Rank::Two, Suit::Diamonds),
+            Card::new(Rank::Five, Suit::Diamonds),
+            Card::new(Rank::Seven, Suit::Diamonds),
+            Card::new(Rank::Nine, Suit::Diamonds),
+            Card::new(Rank::Two, Suit::Clubs),
+        ];
+        let consumables = vec![
+            Consumable::Tarot(Tarot::TheStar),
+            Consumable::Planet(Planet::Jupiter),
+            Consumable::Planet(Planet::Neptune),
+        ];
+
+        let solver = Solver::new(ScoreCalculator::new(vec![]));
+        let plan = solver.solve_with_consumables(&cards, &consumables);
+
+        // The Star turns the 2♣ into a Diamond for a flush, then Jupiter levels it
+        assert_eq!(plan.uses.len(), 2);
+        assert_eq!(plan.uses[0].to_string(), "The Star on 2♣");
+        assert_eq!(plan.uses[1].to_string(), "Jupiter");
+        assert_eq!(plan.result.best_score.as_ref().unwrap().hand_type, HandType::Flush);
+        assert!(plan.score_ratio().unwrap() > 2.0);
+    }
+
+    #[test]
+    fn test_deadline_stops_search_early() {
+        use crate::core::consumable::Planet;
+
+        let cards = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+            Card::new(Rank::Ace, Suit::Clubs),
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Seven, Suit::Diamonds),
+        ];
+        let consumables = vec![Consumable::Planet(Planet::Venus)];
+        let solver = Solver::new(ScoreCalculator::new(vec![])).with_deadline(Instant::now());
+
+        // Past the deadline only single cards are tried and nothing is used
+        assert!(solver.past_deadline());
+        assert!(solver.beam_search(&cards, 3).iter().all(|(play, _, _)| play.cards.len() == 1));
+        let plan = solver.solve_with_consumables(&cards, &consumables);
+        assert!(plan.uses.is_empty());
+        assert_eq!(plan.result.best_score.unwrap().hand_type, HandType::ThreeOfAKind);
+    }
+
+    #[test]
+    fn test_evaluate_shop() {
+        use crate::core::consumable::Planet;
+        use crate::core::joker::JokerKind;
+        use crate::core::shop::{ShopOffer, Voucher};
+
+        let run = GameRunState {
+            money: 6,
+            ..Default::default()
+        };
+        let offers = vec![
+            ShopOffer::Planet(Planet::Eris),
+            ShopOffer::Joker(Joker::new(JokerKind::Joker)),
+            ShopOffer::Voucher(Voucher::Grabber),
+            ShopOffer::Pack {
+                name: "Buffoon Pack".to_string(),
+                cost: 4,
+                choices: vec![
+                    ShopOffer::Joker(Joker::new(JokerKind::Joker)),
+                    ShopOffer::Joker(Joker::new(JokerKind::Blueprint)),
+                ],
+            },
+        ];
+
+        let evaluations = Solver::evaluate_shop_sampled(&run, &offers, 4, 7);
+
+        // +4 mult on every hand beats a fifth hand this early
+        assert_eq!(evaluations[0].offer, offers[1]);
+        assert!(evaluations[0].affordable);
+        let grabber = evaluations.iter().find(|e| e.offer == offers[2]).unwrap();
+        assert!(grabber.score_gain > 0.0);
+        assert!(!grabber.affordable);
+        // Flush Five never comes up with a standard deck
+        let eris = evaluations.iter().find(|e| e.offer == offers[0]).unwrap();
+        assert_eq!(eris.score_gain, 0.0);
+        // Blueprint copies nothing on its own, so the pack's best pick is Joker
+        let pack = evaluations.iter().find(|e| e.offer == offers[3]).unwrap();
+        assert_eq!(pack.choice, Some(offers[1].clone()));
+        assert!(pack.gain_per_dollar() > 0.0);
+    }
+
+    #[test]
+    fn test_solver_holds_steel_cards() {
+        use crate::core::card::Enhancement;
+
+        let cards = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+            Card::new(Rank::King, Suit::Clubs).with_enhancement(Enhancement::Steel),
+        ];
+
+        let solver = Solver::new(ScoreCalculator::new(vec![]));
+        let result = solver.solve(&cards);
+
+        // Playing the pair and holding the Steel King beats playing all three
+        assert_eq!(result.best_hand.cards.len(), 2);
+        assert_eq!(result.held_cards.len(), 1);
+        assert_eq!(result.held_cards[0].enhancement, Enhancement::Steel);
+    }
+
+    #[test]
+    fn test_solver_finds_four_card_flush_with_four_fingers() {
+        use crate::core::hand::HandType;
+        use crate::core::joker::{Joker, JokerKind};
+
+        let cards = vec![
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Six, Suit::Hearts),
+            Card::new(Rank::Nine, Suit::Hearts),
+            Card::new(Rank::Jack, Suit::Hearts),
+            Card::new(Rank::Four, Suit::Clubs),
+            Card::new(Rank::Eight, Suit::Spades),
+        ];
+
+        let calculator = ScoreCalculator::new(vec![Joker::new(JokerKind::FourFingers)]);
+        let result = Solver::new(calculator).solve(&cards);
+
+        let best = result.best_score.unwrap();
+        assert_eq!(best.hand_type, HandType::Flush);
+        assert!(result.best_hand.cards.iter().filter(|c| c.suit == Suit::Hearts).count() >= 4);
+    }
+
+    #[test]
+    fn test_solver_ranks_gapped_straight_with_shortcut() {
+        use crate::core::hand::HandType;
+        use crate::core::joker::{Joker, JokerKind};
+
+        let cards = vec![
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Four, Suit::Clubs),
+            Card::new(Rank::Six, Suit::Hearts),
+            Card::new(Rank::Eight, Suit::Spades),
+            Card::new(Rank::Ten, Suit::Diamonds),
+            Card::new(Rank::King, Suit::Diamonds),
+        ];
+
+        let plain = Solver::new(ScoreCalculator::new(vec![])).solve(&cards);
+        assert_eq!(plain.best_score.unwrap().hand_type, HandType::HighCard);
+
+        let calculator = ScoreCalculator::new(vec![Joker::new(JokerKind::Shortcut)]);
+        let result = Solver::new(calculator).solve(&cards);
+        assert_eq!(result.best_score.unwrap().hand_type, HandType::Straight);
+    }
+
+    #[test]
+    fn test_solve_discard_chases_flush() {
+        let hand = vec![
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Five, Suit::Hearts),
+            Card::new(Rank::Seven, Suit::Hearts),
+            Card::new(Rank::Nine, Suit::Hearts),
+            Card::new(Rank::Three, Suit::Clubs),
+        ];
+        let deck = vec![Card::new(Rank::Four, Suit::Hearts)];
+
+        let solver = Solver::new(ScoreCalculator::new(vec![]));
+        let result = solver.solve_discard(&hand, &deck, 1);
+
+        // Drawing the last heart for the 3♣ completes the flush
+        assert!(result.exact);
+        assert_eq!(result.discard, vec![Card::new(Rank::Three, Suit::Clubs)]);
+        assert!(result.expected_gain() > 0.0);
+
+        // With no discards left, the discard is still weighed but not advised
+        let no_discards = solver.solve_discard(&hand, &deck, 0);
+        assert!(!no_discards.should_discard());
+        assert_eq!(no_discards.expected_score, result.expected_score);
+    }
+
+    #[test]
+    fn test_solve_discard_samples_large_decks() {
+        use crate::core::simulator::create_standard_deck;
+
+        let hand = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+            Card::new(Rank::Two, Suit::Clubs),
+        ];
+        let solver = Solver::new(ScoreCalculator::new(vec![])).with_discard_sampling(4, 1);
+        let result = solver.solve_discard(&hand, &create_standard_deck(), 2);
+
+        assert!(!result.exact);
+        assert!(result.expected_score > 0.0);
+
+        // Five possible redraws are enumerated once five samples would be
+        let deck = &create_standard_deck()[..5];
+        let sampled = |samples| {
+            Solver::new(ScoreCalculator::new(vec![]))
+                .with_discard_sampling(samples, 1)
+                .solve_discard(&hand[..1], deck, 1)
+                .exact
+        };
+        assert!(sampled(5));
+        assert!(!sampled(4));
+        assert_eq!(result.should_discard(), result.expected_gain() > 0.0);
+    }
+
+    #[test]
+    fn test_optimize_joker_order() {
+        use crate::core::joker::{JokerEdition, JokerKind};
+
+        let hand = Hand::new(vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+        ]);
+        let jokers = vec![
+            Joker::new(JokerKind::Joker).with_edition(JokerEdition::Polychrome),
+            Joker::new(JokerKind::JollyJoker),
+        ];
+
+        let solver = Solver::new(ScoreCalculator::new(vec![]));
+        let result = solver.optimize_joker_order(&jokers, &hand, &[]).unwrap();
+
+        assert_eq!(result.order[0].kind, JokerKind::JollyJoker);
+        assert!(result.score_delta > 0.0);
+        assert_eq!(result.best_score.value() - result.current_score.value(), result.score_delta);
+
+        // Blackboard's x3 goes last only while no red card is held
+        let jokers = vec![Joker::new(JokerKind::Blackboard), Joker::new(JokerKind::Joker)];
+        let result = solver.optimize_joker_order(&jokers, &hand, &[]).unwrap();
+        assert_eq!(result.order[0].kind, JokerKind::Joker);
+        let held = [Card::new(Rank::King, Suit::Hearts)];
+        let result = solver.optimize_joker_order(&jokers, &hand, &held).unwrap();
+        assert_eq!(result.score_delta, 0.0);
+
+        let many = vec![Joker::new(JokerKind::Joker); MAX_REORDERED_JOKERS + 1];
+        assert!(solver.optimize_joker_order(&many, &hand, &[]).is_err());
+    }
+
+    #[test]
+    fn test_permutation_count() {
+        use crate::core::joker::JokerKind;
+
+        let jokers = vec![
+            Joker::new(JokerKind::Joker),
+            Joker::new(JokerKind::JollyJoker),
+            Joker::new(JokerKind::Baron),
+        ];
+        let orders: Vec<Vec<Joker>> = Solver::permutations(&jokers).collect();
+        assert_eq!(orders.len(), 6);
+        assert_eq!(orders[0], jokers);
+        assert!(orders.iter().all(|order| orders.iter().filter(|o| *o == order).count() == 1));
+        assert_eq!(Solver::permutations(&[]).count(), 1);
+    }
+
+    #[test]
+    fn test_money_weight_prefers_gold_seal_play() {
+        use crate::core::card::Seal;
+
+        let cards = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Two, Suit::Spades).with_seal(Seal::Gold),
+        ];
+
+        let result = Solver::new(ScoreCalculator::new(vec![])).solve(&cards);
+        assert_eq!(result.best_score.unwrap().money_delta, 0.0);
+
+        let result = Solver::new(ScoreCalculator::new(vec![]))
+            .with_money_weight(10.0)
+            .solve(&cards);
+        assert_eq!(result.best_score.unwrap().money_delta, 3.0);
+    }
+
+    #[test]
+    fn test_warns_when_deck_missing() {
+        use crate::core::joker::{Joker, JokerKind};
+
+        let cards = vec![Card::new(Rank::Ace, Suit::Hearts)];
+        let calculator = ScoreCalculator::new(vec![Joker::new(JokerKind::SteelJoker)]);
+
+        let result = Solver::new(calculator.clone()).solve(&cards);
+        assert_eq!(result.warnings.len(), 1);
+
+        let result = Solver::new(calculator.with_deck(cards.clone())).solve(&cards);
+        assert!(result.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_best_play_matches_ranking() {
+        use crate::core::card::Seal;
+        use crate::core::joker::{Joker, JokerKind};
+
+        let cards = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Spades),
+            Card::new(Rank::Nine, Suit::Hearts),
+            Card::new(Rank::Seven, Suit::Hearts),
+            Card::new(Rank::Four, Suit::Hearts),
+            Card::new(Rank::Two, Suit::Hearts).with_seal(Seal::Gold),
+            Card::new(Rank::King, Suit::Clubs),
+            Card::new(Rank::King, Suit::Diamonds),
+        ];
+        let calculator = ScoreCalculator::new(vec![
+            Joker::new(JokerKind::GreedyJoker),
+            Joker::new(JokerKind::Baron),
+        ]);
+
+        for solver in [
+            Solver::new(calculator.clone()),
+            Solver::new(calculator.clone()).with_money_weight(50.0),
+        ] {
+            let best = solver.best_play(&cards).unwrap();
+            let ranked = solver.rank(solver.enumerate(&cards));
+            assert_eq!(best, ranked[0]);
+        }
+    }
 }
@@ -0,0 +1,132 @@
+//! Stake difficulty modifiers
+//!
+//! Stakes stack: each stake applies its own modifier plus every modifier
+//! of the stakes below it.
+
+use super::names::unknown_name;
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// A stake difficulty level, from White (easiest) to Gold (hardest)
+///
+/// Serialized by its lowercase name ("gold"), and read back from any name
+/// [`FromStr`](std::str::FromStr) accepts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stake {
+    #[default]
+    White,  // Base difficulty
+    Red,    // Small Blind gives no reward money
+    Green,  // Required score scales faster for each Ante
+    Black,  // Shop can have Eternal jokers
+    Blue,   // -1 discard
+    Purple, // Required score scales faster for each Ante
+    Orange, // Shop can have Perishable jokers
+    Gold,   // Shop can have Rental jokers
+}
+
+impl Stake {
+    /// Returns every stake, from easiest to hardest
+    pub fn all() -> [Stake; 8] {
+        [
+            Stake::White,
+            Stake::Red,
+            Stake::Green,
+            Stake::Black,
+            Stake::Blue,
+            Stake::Purple,
+            Stake::Orange,
+            Stake::Gold,
+        ]
+    }
+
+    /// Returns the blind scaling tier (1 = base, 2 = Green, 3 = Purple)
+    pub fn blind_scaling(&self) -> u8 {
+        if *self >= Stake::Purple {
+            3
+        } else if *self >= Stake::Green {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Money awarded for beating the Small Blind
+    pub fn small_blind_reward(&self) -> u32 {
+        if *self >= Stake::Red { 0 } else { 3 }
+    }
+
+    /// Change to the number of discards per round
+    pub fn discard_modifier(&self) -> i32 {
+        if *self >= Stake::Blue { -1 } else { 0 }
+    }
+
+    /// Returns true if shop jokers can be Eternal (cannot be sold or destroyed)
+    pub fn has_eternal_jokers(&self) -> bool {
+        *self >= Stake::Black
+    }
+
+    /// Returns true if shop jokers can be Perishable (debuffed after 5 rounds)
+    pub fn has_perishable_jokers(&self) -> bool {
+        *self >= Stake::Orange
+    }
+
+    /// Returns true if shop jokers can be Rental (cost $3 per round)
+    pub fn has_rental_jokers(&self) -> bool {
+        *self >= Stake::Gold
+    }
+}
+
+impl std::str::FromStr for Stake {
+    type Err = anyhow::Error;
+
+    /// Parses a stake name, ignoring case and an optional " Stake" suffix
+    /// (e.g., "gold", "Gold Stake")
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let normalize = |name: &str| -> String {
+            let name = name.trim().to_lowercase();
+            name.strip_suffix("stake").unwrap_or(&name).trim().to_string()
+        };
+        let name = normalize(s);
+
+        Stake::all()
+            .into_iter()
+            .find(|stake| format!("{:?}", stake).to_lowercase() == name)
+            .ok_or_else(|| {
+                let names = Stake::all().map(|stake| format!("{:?}", stake));
+                let names: Vec<&str> = names.iter().map(String::as_str).collect();
+                unknown_name("stake", s, &names, normalize)
+            })
+    }
+}
+
+impl<'de> Deserialize<'de> for Stake {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stake_modifiers_stack() {
+        assert_eq!(Stake::White.blind_scaling(), 1);
+        assert_eq!(Stake::Black.blind_scaling(), 2);
+        assert_eq!(Stake::Gold.blind_scaling(), 3);
+        assert_eq!(Stake::White.small_blind_reward(), 3);
+        assert_eq!(Stake::Gold.small_blind_reward(), 0);
+        assert_eq!(Stake::Green.discard_modifier(), 0);
+        assert_eq!(Stake::Blue.discard_modifier(), -1);
+        assert!(!Stake::Orange.has_rental_jokers());
+        assert!(Stake::Gold.has_eternal_jokers());
+    }
+
+    #[test]
+    fn test_parse_stake() {
+        assert_eq!("gold".parse::<Stake>().unwrap(), Stake::Gold);
+        assert_eq!("Purple Stake".parse::<Stake>().unwrap(), Stake::Purple);
+        assert!("Platinum".parse::<Stake>().is_err());
+    }
+}
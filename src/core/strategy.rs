@@ -0,0 +1,343 @@
+//! Play strategies
+//!
+//! A [`PlayStrategy`] decides which cards to play from a hand. The simulator
+//! asks its strategy for every play, so optimal search can be compared with
+//! simpler, more human ways of playing, and library users can plug in their
+//! own.
+
+use super::card::{Card, Suit};
+use super::hand::{Hand, HandType};
+use super::simulator::Simulator;
+use super::solver::{ScoredPlay, Solver};
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// What a strategy sees when choosing a play
+pub struct PlayContext<'a> {
+    /// The cards in hand
+    pub cards: &'a [Card],
+    /// The solver for the round, with its jokers, hand levels, and boss blind
+    pub solver: &'a Solver,
+}
+
+/// The cards a strategy chose to play
+#[derive(Debug, Clone, PartialEq)]
+pub struct Play {
+    /// The played cards (empty if there is nothing to play)
+    pub hand: Hand,
+    /// The cards left in hand
+    pub held_cards: Vec<Card>,
+}
+
+impl Play {
+    /// Creates a play from a scored play, or an empty play if there is none
+    fn from_scored(play: Option<ScoredPlay>) -> Self {
+        match play {
+            Some((hand, held_cards, _)) => Self { hand, held_cards },
+            None => Self {
+                hand: Hand::new(vec![]),
+                held_cards: vec![],
+            },
+        }
+    }
+}
+
+/// Chooses which cards to play from a hand
+///
+/// Strategies are shared by the simulator's worker threads, so they must be
+/// `Send + Sync`, and should not depend on the order hands are seen in if
+/// simulations are to be reproducible.
+pub trait PlayStrategy: Send + Sync {
+    /// Returns the play to make
+    fn choose_play(&self, ctx: &PlayContext) -> Play;
+
+    /// Returns the cards to discard instead of making `play`, or `None` to
+    /// make it
+    ///
+    /// Only asked while discards are left. By default, up to five held
+    /// cards are discarded, unenhanced and low ranks first, whenever the
+    /// play uses fewer than five cards.
+    fn choose_discard(&self, _ctx: &PlayContext, play: &Play) -> Option<Vec<Card>> {
+        if play.hand.cards.len() >= 5 {
+            return None;
+        }
+        Simulator::choose_discard(&play.held_cards)
+    }
+}
+
+/// Makes the best-ranked play of the hand types accepted, or the best play
+/// overall if none is
+fn best_of_types(ctx: &PlayContext, accept: impl Fn(HandType) -> bool) -> Play {
+    let plays = ctx.solver.rank(ctx.solver.enumerate(ctx.cards));
+    let index = plays.iter().position(|(_, _, score)| accept(score.hand_type)).unwrap_or(0);
+    Play::from_scored(plays.into_iter().nth(index))
+}
+
+/// Scores every play and makes the best one
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Exhaustive;
+
+impl PlayStrategy for Exhaustive {
+    fn choose_play(&self, ctx: &PlayContext) -> Play {
+        Play::from_scored(ctx.solver.best_play(ctx.cards))
+    }
+}
+
+/// Plays the highest poker hand it can make, like a player who ignores
+/// jokers and enhancements
+///
+/// Among plays of the same hand type the highest scoring one is made.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Greedy;
+
+impl PlayStrategy for Greedy {
+    fn choose_play(&self, ctx: &PlayContext) -> Play {
+        let plays = ctx.solver.rank(ctx.solver.enumerate(ctx.cards));
+        let best = plays.into_iter().enumerate().max_by_key(|(i, (_, _, score))| {
+            // Ranked best first, so earlier plays win ties
+            (score.hand_type, std::cmp::Reverse(*i))
+        });
+        Play::from_scored(best.map(|(_, play)| play))
+    }
+}
+
+/// Makes the best play found by a beam search of the given width
+///
+/// See [`Solver::with_beam_width`].
+#[derive(Debug, Clone, Copy)]
+pub struct BeamSearch {
+    pub width: usize,
+}
+
+impl PlayStrategy for BeamSearch {
+    fn choose_play(&self, ctx: &PlayContext) -> Play {
+        let plays = ctx.solver.rank(ctx.solver.beam_search(ctx.cards, self.width));
+        Play::from_scored(plays.into_iter().next())
+    }
+}
+
+/// Plays the best flush it can make, discarding off-suit cards to chase
+/// one
+///
+/// Without a flush in hand, it discards up to five cards not of the suit it
+/// holds most of (Wild cards are kept), lowest first, and once discards run
+/// out makes the best play it has.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FlushChaser;
+
+impl FlushChaser {
+    fn is_flush(hand_type: HandType) -> bool {
+        matches!(
+            hand_type,
+            HandType::Flush | HandType::StraightFlush | HandType::FlushHouse | HandType::FlushFive
+        )
+    }
+}
+
+impl PlayStrategy for FlushChaser {
+    fn choose_play(&self, ctx: &PlayContext) -> Play {
+        best_of_types(ctx, Self::is_flush)
+    }
+
+    fn choose_discard(&self, ctx: &PlayContext, play: &Play) -> Option<Vec<Card>> {
+        let rules = ctx.solver.calculator().rules();
+        if play.hand.cards.is_empty() || Self::is_flush(play.hand.evaluate_with(rules)) {
+            return None;
+        }
+        let suit = [Suit::Hearts, Suit::Diamonds, Suit::Clubs, Suit::Spades]
+            .into_iter()
+            .max_by_key(|&suit| ctx.cards.iter().filter(|card| card.is_suit(suit)).count())?;
+
+        let mut off_suit: Vec<Card> =
+            ctx.cards.iter().filter(|card| !card.is_suit(suit)).cloned().collect();
+        off_suit.sort_by_key(|card| card.rank.value());
+        off_suit.truncate(5);
+        (!off_suit.is_empty()).then_some(off_suit)
+    }
+}
+
+/// Plays the best pair it can make, every hand, like a player levelling
+/// Pair
+///
+/// Without a pair in hand, it discards up to five of its lowest cards to
+/// find one, and once discards run out makes the best play it has. With a
+/// pair it never discards.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PairSpammer;
+
+impl PlayStrategy for PairSpammer {
+    fn choose_play(&self, ctx: &PlayContext) -> Play {
+        best_of_types(ctx, |hand_type| hand_type == HandType::Pair)
+    }
+
+    fn choose_discard(&self, ctx: &PlayContext, play: &Play) -> Option<Vec<Card>> {
+        let rules = ctx.solver.calculator().rules();
+        if play.hand.cards.is_empty() || play.hand.evaluate_with(rules) == HandType::Pair {
+            return None;
+        }
+        Simulator::choose_discard(&play.held_cards)
+    }
+}
+
+/// Makes its best poker hand from its lowest cards, holding high cards
+/// back for later hands
+///
+/// Among the plays of the best hand type it can make, the one with the
+/// lowest total rank is made, the highest scoring on ties.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HighCardPreserver;
+
+impl PlayStrategy for HighCardPreserver {
+    fn choose_play(&self, ctx: &PlayContext) -> Play {
+        let plays = ctx.solver.rank(ctx.solver.enumerate(ctx.cards));
+        let Some(best_type) = plays.iter().map(|(_, _, score)| score.hand_type).max() else {
+            return Play::from_scored(None);
+        };
+        let rank_total =
+            |hand: &Hand| hand.cards.iter().map(|card| u32::from(card.rank.value())).sum::<u32>();
+        // Ranked best first, so earlier plays win ties
+        let best = plays
+            .into_iter()
+            .filter(|(_, _, score)| score.hand_type == best_type)
+            .enumerate()
+            .min_by_key(|(i, (hand, _, _))| (rank_total(hand), *i))
+            .map(|(_, play)| play);
+        Play::from_scored(best)
+    }
+}
+
+/// Makes a uniformly random play among those the boss blind allows
+///
+/// The choice is drawn from the seed and the cards in hand, so the same
+/// hand always gets the same play however many threads are simulating.
+#[derive(Debug, Clone, Copy)]
+pub struct Random {
+    seed: u64,
+}
+
+impl Random {
+    /// Creates a random strategy with a fixed seed
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+impl PlayStrategy for Random {
+    fn choose_play(&self, ctx: &PlayContext) -> Play {
+        let mut hasher = DefaultHasher::new();
+        (self.seed, ctx.cards).hash(&mut hasher);
+        let mut rng = ChaCha8Rng::seed_from_u64(hasher.finish());
+
+        let mut plays = ctx.solver.rank(ctx.solver.enumerate(ctx.cards));
+        let choice = (!plays.is_empty()).then(|| plays.swap_remove(rng.gen_range(0..plays.len())));
+        Play::from_scored(choice)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::card::{Rank, Suit};
+    use crate::core::hand::HandType;
+    use crate::core::scoring::ScoreCalculator;
+
+    fn cards() -> Vec<Card> {
+        vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Clubs),
+            Card::new(Rank::Ten, Suit::Spades),
+            Card::new(Rank::Ten, Suit::Diamonds),
+            Card::new(Rank::Three, Suit::Hearts),
+        ]
+    }
+
+    #[test]
+    fn test_strategies_choose_plays() {
+        let solver = Solver::new(ScoreCalculator::new(vec![]));
+        let cards = cards();
+        let ctx = PlayContext {
+            cards: &cards,
+            solver: &solver,
+        };
+
+        let exhaustive = Exhaustive.choose_play(&ctx);
+        assert_eq!(exhaustive.hand, solver.solve(&cards).best_hand);
+
+        let greedy = Greedy.choose_play(&ctx);
+        assert_eq!(greedy.hand.evaluate(), HandType::TwoPair);
+
+        let beam = BeamSearch { width: 4 }.choose_play(&ctx);
+        assert!(!beam.hand.cards.is_empty());
+    }
+
+    #[test]
+    fn test_player_presets() {
+        let solver = Solver::new(ScoreCalculator::new(vec![]));
+        let cards = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::King, Suit::Hearts),
+            Card::new(Rank::Nine, Suit::Hearts),
+            Card::new(Rank::Two, Suit::Hearts),
+            Card::new(Rank::Five, Suit::Hearts),
+            Card::new(Rank::Five, Suit::Clubs),
+            Card::new(Rank::Five, Suit::Diamonds),
+            Card::new(Rank::Five, Suit::Spades),
+        ];
+        let ctx = PlayContext {
+            cards: &cards,
+            solver: &solver,
+        };
+
+        assert_eq!(Exhaustive.choose_play(&ctx).hand.evaluate(), HandType::FourOfAKind);
+        assert_eq!(FlushChaser.choose_play(&ctx).hand.evaluate(), HandType::Flush);
+        let pair = PairSpammer.choose_play(&ctx);
+        assert_eq!(pair.hand.evaluate(), HandType::Pair);
+        assert_eq!(PairSpammer.choose_discard(&ctx, &pair), None);
+
+        let cards = vec![
+            Card::new(Rank::Ace, Suit::Hearts),
+            Card::new(Rank::Ace, Suit::Clubs),
+            Card::new(Rank::Ten, Suit::Spades),
+            Card::new(Rank::Ten, Suit::Diamonds),
+            Card::new(Rank::Three, Suit::Hearts),
+            Card::new(Rank::Three, Suit::Clubs),
+            Card::new(Rank::Seven, Suit::Hearts),
+        ];
+        let ctx = PlayContext {
+            cards: &cards,
+            solver: &solver,
+        };
+        let preserved = HighCardPreserver.choose_play(&ctx);
+        assert_eq!(preserved.hand.evaluate(), HandType::TwoPair);
+        assert_eq!(preserved.held_cards.iter().filter(|card| card.rank == Rank::Ace).count(), 2);
+
+        // Without a flush, off-suit cards are thrown away, lowest first
+        let play = FlushChaser.choose_play(&ctx);
+        let discard = FlushChaser.choose_discard(&ctx, &play).unwrap();
+        assert_eq!(discard.len(), 4);
+        assert!(discard.iter().all(|card| card.suit != Suit::Hearts));
+        assert_eq!(discard[0].rank, Rank::Three);
+    }
+
+    #[test]
+    fn test_random_strategy_is_seeded() {
+        let solver = Solver::new(ScoreCalculator::new(vec![]));
+        let cards = cards();
+        let ctx = PlayContext {
+            cards: &cards,
+            solver: &solver,
+        };
+
+        let plays: Vec<Play> = (0..5).map(|_| Random::new(3).choose_play(&ctx)).collect();
+        assert!(plays.windows(2).all(|pair| pair[0] == pair[1]));
+        let play = &plays[0];
+        assert_eq!(play.hand.cards.len() + play.held_cards.len(), cards.len());
+
+        let empty = Random::new(3).choose_play(&PlayContext {
+            cards: &[],
+            solver: &solver,
+        });
+        assert!(empty.hand.cards.is_empty());
+    }
+}
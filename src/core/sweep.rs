@@ -0,0 +1,283 @@
+//! Parameter sweeps
+//!
+//! Simulates a run over a range of one or two parameters (hand size, number
+//! of jokers, a hand's level), producing a matrix of results so scaling
+//! curves can be seen instead of single-point estimates.
+
+use super::hand::HandType;
+use super::run::GameRunState;
+use super::simulator::{SimulationResult, Simulator};
+use serde::{Deserialize, Serialize};
+
+/// Most values one sweep axis may take
+const MAX_AXIS_VALUES: u32 = 100;
+
+/// A run setting varied by a sweep
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SweepParameter {
+    /// Cards held in hand
+    HandSize,
+    /// Number of jokers, taking the run's jokers from the left
+    JokerSlots,
+    /// Level of one hand type
+    HandLevel(HandType),
+}
+
+impl SweepParameter {
+    /// Applies a value of the parameter to the run, returning the hand size
+    /// to simulate with
+    fn apply(&self, run: &mut GameRunState, hand_size: usize, value: u32) -> usize {
+        match self {
+            SweepParameter::HandSize => return value as usize,
+            SweepParameter::JokerSlots => run.jokers.truncate(value as usize),
+            SweepParameter::HandLevel(hand_type) => run.hand_levels.set_level(*hand_type, value),
+        }
+        hand_size
+    }
+}
+
+impl std::fmt::Display for SweepParameter {
+    /// Names the parameter, e.g. "hand size" or "Flush level"
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SweepParameter::HandSize => write!(f, "hand size"),
+            SweepParameter::JokerSlots => write!(f, "jokers"),
+            SweepParameter::HandLevel(hand_type) => write!(f, "{} level", hand_type.name()),
+        }
+    }
+}
+
+/// A parameter and the values a sweep takes it through
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SweepAxis {
+    pub parameter: SweepParameter,
+    pub values: Vec<u32>,
+}
+
+impl SweepAxis {
+    /// Creates an axis over an inclusive range of values
+    pub fn new(parameter: SweepParameter, values: std::ops::RangeInclusive<u32>) -> Self {
+        Self {
+            parameter,
+            values: values.collect(),
+        }
+    }
+}
+
+impl std::str::FromStr for SweepAxis {
+    type Err = anyhow::Error;
+
+    /// Parses "<parameter>=<first>..<last>", where the parameter is
+    /// "hand-size", "jokers", or a hand type's level (e.g., "hand-size=7..10",
+    /// "jokers=0..5", "flush=1..10")
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let (name, range) = s
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Invalid sweep: {}. Use '<parameter>=<first>..<last>'", s))?;
+        let (first, last) = range
+            .split_once("..")
+            .ok_or_else(|| anyhow::anyhow!("Invalid sweep range: {}. Use '<first>..<last>'", range))?;
+        let first: u32 = first.trim().parse()?;
+        let last: u32 = last.trim().parse()?;
+        if first > last {
+            anyhow::bail!("Invalid sweep range: {} is after {}", first, last);
+        }
+        if last - first >= MAX_AXIS_VALUES {
+            anyhow::bail!(
+                "Invalid sweep range: {} takes more than {} values",
+                range,
+                MAX_AXIS_VALUES
+            );
+        }
+
+        let normalized: String = name
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '_' | '-'))
+            .collect::<String>()
+            .to_lowercase();
+        let parameter = match normalized.as_str() {
+            "handsize" => SweepParameter::HandSize,
+            "jokers" | "jokerslots" => SweepParameter::JokerSlots,
+            _ => SweepParameter::HandLevel(name.parse().map_err(|_| {
+                anyhow::anyhow!(
+                    "Unknown sweep parameter: {}. Use 'hand-size', 'jokers', or a hand type",
+                    name
+                )
+            })?),
+        };
+        if parameter == SweepParameter::HandSize && first == 0 {
+            anyhow::bail!("Hand size must be at least 1");
+        }
+
+        Ok(Self::new(parameter, first..=last))
+    }
+}
+
+/// Results of a sweep: one row per value of the first axis and, for a
+/// two-axis sweep, one column per value of the second
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SweepResult {
+    pub rows: SweepAxis,
+    pub columns: Option<SweepAxis>,
+    /// `cells[row][column]`, with a single column for one-axis sweeps
+    pub cells: Vec<Vec<SimulationResult>>,
+}
+
+impl SweepResult {
+    /// Returns the mean score of every cell
+    pub fn mean_scores(&self) -> Vec<Vec<f64>> {
+        self.cells
+            .iter()
+            .map(|row| row.iter().map(|result| result.mean_score).collect())
+            .collect()
+    }
+}
+
+/// Simulates a run at every point of a one- or two-parameter grid
+///
+/// Each point is simulated from the same seed, so neighboring points are
+/// dealt the same hands wherever the deck and hand size allow.
+pub struct ParameterSweep {
+    run: GameRunState,
+    simulator: Simulator,
+    num_runs: usize,
+    seed: Option<u64>,
+    hand_size: Option<usize>,
+}
+
+impl ParameterSweep {
+    /// Creates a sweep of the run with 1,000 rounds per point, making the
+    /// best play every hand
+    pub fn new(run: GameRunState) -> Self {
+        Self {
+            simulator: Simulator::new(run.solver()),
+            run,
+            num_runs: 1000,
+            seed: None,
+            hand_size: None,
+        }
+    }
+
+    /// Sets the simulator that plays each point: its strategy, solver
+    /// settings, and threads are used, with the point's jokers and hand
+    /// levels
+    pub fn with_simulator(mut self, simulator: Simulator) -> Self {
+        self.simulator = simulator;
+        self
+    }
+
+    /// Sets the hand size used where the sweep does not vary it (default:
+    /// the deck type's)
+    pub fn with_hand_size(mut self, hand_size: usize) -> Self {
+        self.hand_size = Some(hand_size);
+        self
+    }
+
+    /// Sets the rounds simulated per point and the seed they are dealt from
+    pub fn with_runs(mut self, num_runs: usize, seed: Option<u64>) -> Self {
+        self.num_runs = num_runs;
+        self.seed = seed;
+        self
+    }
+
+    /// Simulates every combination of the axes' values
+    pub fn sweep(&self, rows: SweepAxis, columns: Option<SweepAxis>) -> SweepResult {
+        // Without a seed, pick one so every point is dealt the same hands
+        let seed = self.seed.unwrap_or_else(rand::random);
+        let column_values: Vec<Option<u32>> = match &columns {
+            Some(axis) => axis.values.iter().copied().map(Some).collect(),
+            None => vec![None],
+        };
+
+        let cells = rows
+            .values
+            .iter()
+            .map(|&row| {
+                column_values
+                    .iter()
+                    .map(|&column| {
+                        let mut run = self.run.clone();
+                        let base_hand_size =
+                            self.hand_size.unwrap_or(run.deck_type.modifiers().hand_size);
+                        let mut hand_size = rows.parameter.apply(&mut run, base_hand_size, row);
+                        if let (Some(axis), Some(value)) = (&columns, column) {
+                            hand_size = axis.parameter.apply(&mut run, hand_size, value);
+                        }
+
+                        let mut config = run.simulation_config(self.num_runs, Some(seed));
+                        config.hand_size = hand_size;
+                        config.parallelism = self.simulator.parallelism();
+                        self.simulator.simulate_with_calculator(run.calculator(), config)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        SweepResult {
+            rows,
+            columns,
+            cells,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::joker::{Joker, JokerKind};
+
+    #[test]
+    fn test_parse_axes() {
+        let axis: SweepAxis = "hand-size=7..10".parse().unwrap();
+        assert_eq!(axis.parameter, SweepParameter::HandSize);
+        assert_eq!(axis.values, vec![7, 8, 9, 10]);
+
+        let axis: SweepAxis = "Full House=1..3".parse().unwrap();
+        assert_eq!(axis.parameter, SweepParameter::HandLevel(HandType::FullHouse));
+        assert_eq!("jokers=0..2".parse::<SweepAxis>().unwrap().values, vec![0, 1, 2]);
+
+        assert!("hand-size=10..7".parse::<SweepAxis>().is_err());
+        assert!("hand-size=0..3".parse::<SweepAxis>().is_err());
+        assert!("flush=1..100".parse::<SweepAxis>().is_ok());
+        assert!("flush=1..4000000000".parse::<SweepAxis>().is_err());
+        assert!("wisdom=1..3".parse::<SweepAxis>().is_err());
+        assert!("jokers".parse::<SweepAxis>().is_err());
+    }
+
+    #[test]
+    fn test_two_axis_sweep() {
+        let run = GameRunState::default().with_jokers(vec![
+            Joker::new(JokerKind::Joker),
+            Joker::new(JokerKind::Joker),
+        ]);
+        let result = ParameterSweep::new(run).with_runs(30, Some(1)).sweep(
+            SweepAxis::new(SweepParameter::HandSize, 5..=8),
+            Some(SweepAxis::new(SweepParameter::JokerSlots, 0..=2)),
+        );
+
+        let means = result.mean_scores();
+        assert_eq!((means.len(), means[0].len()), (4, 3));
+        // More cards in hand and more jokers both help
+        assert!(means.iter().all(|row| row.windows(2).all(|pair| pair[0] < pair[1])));
+        assert!(means.windows(2).all(|pair| pair[0][2] <= pair[1][2]));
+    }
+
+    #[test]
+    fn test_sweep_uses_simulator_strategy() {
+        use crate::core::strategy::Random;
+
+        let run = GameRunState::default();
+        let axis = || SweepAxis::new(SweepParameter::HandLevel(HandType::FullHouse), 1..=2);
+        let best = ParameterSweep::new(run.clone()).with_runs(50, Some(1)).sweep(axis(), None);
+        let random = ParameterSweep::new(run.clone())
+            .with_simulator(Simulator::new(run.solver()).with_strategy(Random::new(1)))
+            .with_runs(50, Some(1))
+            .sweep(axis(), None);
+
+        // Random plays score less than the best play on the same hands
+        for (best, random) in best.mean_scores().iter().zip(random.mean_scores()) {
+            assert!(random[0] < best[0]);
+        }
+        assert_eq!(best.rows.parameter.to_string(), "Full House level");
+    }
+}
@@ -0,0 +1,192 @@
+//! Skip tags
+//!
+//! Skipping a Small or Big blind forgoes its reward and the shop after it
+//! for the tag the blind offers. Only tags that pay money or change the
+//! boss are modeled; the rest (free jokers, packs, vouchers, and so on)
+//! are known by name but give a skip nothing.
+
+use super::names::unknown_name;
+use super::run::GameRunState;
+use serde::{Deserialize, Serialize};
+
+/// Most money an Economy Tag pays
+const ECONOMY_TAG_MAX: u32 = 40;
+
+/// Money an Investment Tag pays after the boss
+const INVESTMENT_TAG_MONEY: u32 = 25;
+
+/// Money a Speed Tag pays per blind skipped
+const SPEED_TAG_MONEY: u32 = 5;
+
+/// A tag offered for skipping a blind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Tag {
+    Uncommon,
+    Rare,
+    Negative,
+    Foil,
+    Holographic,
+    Polychrome,
+    Investment,
+    Voucher,
+    Boss,
+    Standard,
+    Charm,
+    Meteor,
+    Buffoon,
+    Handy,
+    Garbage,
+    Ethereal,
+    Coupon,
+    Double,
+    Juggle,
+    D6,
+    TopUp,
+    Speed,
+    Orbital,
+    Economy,
+}
+
+impl Tag {
+    /// Returns every tag, in the game's order
+    pub fn all() -> Vec<Tag> {
+        vec![
+            Tag::Uncommon,
+            Tag::Rare,
+            Tag::Negative,
+            Tag::Foil,
+            Tag::Holographic,
+            Tag::Polychrome,
+            Tag::Investment,
+            Tag::Voucher,
+            Tag::Boss,
+            Tag::Standard,
+            Tag::Charm,
+            Tag::Meteor,
+            Tag::Buffoon,
+            Tag::Handy,
+            Tag::Garbage,
+            Tag::Ethereal,
+            Tag::Coupon,
+            Tag::Double,
+            Tag::Juggle,
+            Tag::D6,
+            Tag::TopUp,
+            Tag::Speed,
+            Tag::Orbital,
+            Tag::Economy,
+        ]
+    }
+
+    /// Returns the tag's name as shown in the game
+    pub fn name(&self) -> &'static str {
+        match self {
+            Tag::Uncommon => "Uncommon Tag",
+            Tag::Rare => "Rare Tag",
+            Tag::Negative => "Negative Tag",
+            Tag::Foil => "Foil Tag",
+            Tag::Holographic => "Holographic Tag",
+            Tag::Polychrome => "Polychrome Tag",
+            Tag::Investment => "Investment Tag",
+            Tag::Voucher => "Voucher Tag",
+            Tag::Boss => "Boss Tag",
+            Tag::Standard => "Standard Tag",
+            Tag::Charm => "Charm Tag",
+            Tag::Meteor => "Meteor Tag",
+            Tag::Buffoon => "Buffoon Tag",
+            Tag::Handy => "Handy Tag",
+            Tag::Garbage => "Garbage Tag",
+            Tag::Ethereal => "Ethereal Tag",
+            Tag::Coupon => "Coupon Tag",
+            Tag::Double => "Double Tag",
+            Tag::Juggle => "Juggle Tag",
+            Tag::D6 => "D6 Tag",
+            Tag::TopUp => "Top-up Tag",
+            Tag::Speed => "Speed Tag",
+            Tag::Orbital => "Orbital Tag",
+            Tag::Economy => "Economy Tag",
+        }
+    }
+
+    /// Returns whether taking the tag changes the run as it is modeled
+    pub fn is_modeled(&self) -> bool {
+        matches!(self, Tag::Investment | Tag::Boss | Tag::Speed | Tag::Economy)
+    }
+
+    /// Applies the tag to a run that just skipped a blind
+    ///
+    /// Economy doubles the money (up to $40 more), Investment pays its $25
+    /// straight away rather than after the boss, Speed pays $5 for this
+    /// skip alone (earlier skips aren't known), and Boss rerolls the boss,
+    /// leaving it unknown. Other tags change nothing.
+    pub fn apply(&self, run: &mut GameRunState) {
+        match self {
+            Tag::Economy => run.money += run.money.min(ECONOMY_TAG_MAX),
+            Tag::Investment => run.money += INVESTMENT_TAG_MONEY,
+            Tag::Speed => run.money += SPEED_TAG_MONEY,
+            Tag::Boss => run.boss_blind = None,
+            _ => {}
+        }
+    }
+}
+
+impl std::str::FromStr for Tag {
+    type Err = anyhow::Error;
+
+    /// Parses a tag name, with or without "Tag", ignoring case, spaces,
+    /// dashes, and underscores (e.g., "Economy Tag", "top_up", "d6")
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let normalize = |name: &str| -> String {
+            let name: String = name
+                .chars()
+                .filter(|c| c.is_alphanumeric())
+                .collect::<String>()
+                .to_lowercase();
+            name.strip_suffix("tag").map(str::to_string).unwrap_or(name)
+        };
+        let normalized = normalize(s);
+        if let Some(tag) = Tag::all().into_iter().find(|tag| normalize(tag.name()) == normalized) {
+            return Ok(tag);
+        }
+
+        let names: Vec<&str> = Tag::all().iter().map(Tag::name).collect();
+        Err(unknown_name("tag", s, &names, normalize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::blind::BossBlind;
+
+    #[test]
+    fn test_parse_tag() {
+        assert_eq!("Economy Tag".parse::<Tag>().unwrap(), Tag::Economy);
+        assert_eq!("top_up".parse::<Tag>().unwrap(), Tag::TopUp);
+        assert_eq!("D6".parse::<Tag>().unwrap(), Tag::D6);
+        let error = "Econmy".parse::<Tag>().unwrap_err();
+        assert_eq!(error.to_string(), "Unknown tag: Econmy (did you mean Economy Tag?)");
+    }
+
+    #[test]
+    fn test_apply_tag() {
+        let mut run = GameRunState {
+            money: 12,
+            boss_blind: Some(BossBlind::TheWall),
+            ..GameRunState::default()
+        };
+        Tag::Economy.apply(&mut run);
+        assert_eq!(run.money, 24);
+        Tag::Investment.apply(&mut run);
+        assert_eq!(run.money, 49);
+        Tag::Economy.apply(&mut run);
+        assert_eq!(run.money, 89);
+
+        Tag::Boss.apply(&mut run);
+        assert_eq!(run.boss_blind, None);
+        let before = run.clone();
+        Tag::Rare.apply(&mut run);
+        assert_eq!(run, before);
+        assert!(!Tag::Rare.is_modeled());
+    }
+}
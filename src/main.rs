@@ -1,4 +1,8 @@
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, FromArgMatches, Parser, Subcommand};
+use clap_complete::CompleteEnv;
+use jimbo::cli::config::{user_config, with_user_defaults};
+use jimbo::cli::style::{self, ColorChoice};
+use std::process::ExitCode;
 
 #[derive(Parser)]
 #[command(name = "jimbo")]
@@ -7,6 +11,15 @@ use clap::{Parser, Subcommand};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// When to use colors and emoji: auto (on terminals), always, or never
+    /// (plain ASCII); NO_COLOR turns colors off for auto
+    #[arg(long, global = true, default_value = "auto", value_name = "WHEN")]
+    color: ColorChoice,
+
+    /// Same as --color never
+    #[arg(long, global = true)]
+    no_color: bool,
 }
 
 #[derive(Subcommand)]
@@ -14,23 +27,106 @@ enum Commands {
     /// Analyzes your hand and finds the optimal play
     Solve(jimbo::cli::solve::SolveArgs),
 
+    /// Prints the card-by-card scoring trace of a play
+    Explain(jimbo::cli::explain::ExplainArgs),
+
     /// Runs multiple simulations to find average/best-case scores
     Simulate(jimbo::cli::simulate::SimulateArgs),
 
+    /// Recomputes the statistics of runs exported with `simulate --export`
+    Stats(jimbo::cli::stats::StatsArgs),
+
+    /// Simulates several builds on the same hands and compares them
+    Compare(jimbo::cli::compare::CompareArgs),
+
+    /// Searches for the joker combinations that score best with a deck
+    Optimize(jimbo::cli::optimize::OptimizeArgs),
+
+    /// Simulates complete runs from a game state through the final ante
+    Run(jimbo::cli::run::RunArgs),
+
+    /// Plans an ante: each blind's odds, how to play the boss, and whether
+    /// to skip for a tag
+    Plan(jimbo::cli::plan::PlanArgs),
+
     /// Launches the interactive terminal user interface
     Tui,
 
+    /// Starts an interactive shell that keeps the hand, jokers, and blind
+    /// between commands
+    Repl,
+
     /// Manage configuration files for decks and presets
     Config(jimbo::cli::config::ConfigArgs),
+
+    /// Serves solve, simulate, and explain as a local HTTP JSON API
+    Serve(jimbo::cli::serve::ServeArgs),
+
+    /// Imports the run in progress from a Balatro save file
+    Import(jimbo::cli::import::ImportArgs),
+
+    /// Predicts a seed's early bosses, vouchers, and shops, and rates it
+    /// for a build
+    Seed(jimbo::cli::seed::SeedArgs),
+
+    /// Analyzes a deck: its make-up and the odds of drawing flushes and
+    /// straights
+    Deck(jimbo::cli::deck::DeckArgs),
+
+    /// Looks up jokers: their effects, rarity, cost, and modeling status
+    Jokers(jimbo::cli::jokers::JokersArgs),
+
+    /// Prints the shell script that enables tab completion
+    Completions(jimbo::cli::completions::CompletionsArgs),
 }
 
-fn main() -> anyhow::Result<()> {
-    let cli = Cli::parse();
+fn main() -> anyhow::Result<ExitCode> {
+    // Answers the completion script's callbacks, then exits
+    CompleteEnv::with_factory(Cli::command)
+        .var(jimbo::cli::completions::COMPLETE_VAR)
+        .complete();
 
-    match cli.command {
-        Commands::Solve(args) => jimbo::cli::solve::run(args),
+    // Flags left out take their defaults from the user configuration
+    let user = user_config();
+    let command = match &user {
+        Ok(config) => with_user_defaults(Cli::command(), config),
+        Err(_) => Cli::command(),
+    };
+
+    // Usage errors exit with 1 like other errors, keeping 2 for `solve`'s
+    // "does not beat the blind"
+    let parsed = command.try_get_matches().and_then(|matches| Cli::from_arg_matches(&matches));
+    let cli = match parsed {
+        Ok(cli) => cli,
+        Err(e) => {
+            e.print()?;
+            return Ok(if e.use_stderr() { ExitCode::FAILURE } else { ExitCode::SUCCESS });
+        }
+    };
+
+    style::init(if cli.no_color { ColorChoice::Never } else { cli.color });
+    if let Err(e) = user {
+        eprintln!("{}", style::render(&format!("⚠️  {}; ignoring it", e)));
+    }
+
+    let result = match cli.command {
+        Commands::Solve(args) => return jimbo::cli::solve::run(args),
+        Commands::Explain(args) => jimbo::cli::explain::run(args),
         Commands::Simulate(args) => jimbo::cli::simulate::run(args),
+        Commands::Stats(args) => jimbo::cli::stats::run(args),
+        Commands::Compare(args) => jimbo::cli::compare::run(args),
+        Commands::Optimize(args) => jimbo::cli::optimize::run(args),
+        Commands::Run(args) => jimbo::cli::run::run(args),
+        Commands::Plan(args) => jimbo::cli::plan::run(args),
         Commands::Tui => jimbo::tui::run(),
+        Commands::Repl => jimbo::cli::repl::run(),
         Commands::Config(args) => jimbo::cli::config::run(args),
-    }
+        Commands::Serve(args) => jimbo::cli::serve::run(args),
+        Commands::Import(args) => jimbo::cli::import::run(args),
+        Commands::Seed(args) => jimbo::cli::seed::run(args),
+        Commands::Deck(args) => jimbo::cli::deck::run(args),
+        Commands::Jokers(args) => jimbo::cli::jokers::run(args),
+        Commands::Completions(args) => jimbo::cli::completions::run(args),
+    };
+    result.map(|()| ExitCode::SUCCESS)
 }
@@ -13,10 +13,10 @@ const POLL_TIMEOUT_MS: u64 = 100;
 /// `Ok(None)` if the timeout elapsed with no event,
 /// or an error if polling failed.
 pub fn poll_event() -> Result<Option<KeyEvent>> {
-    if event::poll(Duration::from_millis(POLL_TIMEOUT_MS))? {
-        if let Event::Key(key_event) = event::read()? {
-            return Ok(Some(key_event));
-        }
+    if event::poll(Duration::from_millis(POLL_TIMEOUT_MS))?
+        && let Event::Key(key_event) = event::read()?
+    {
+        return Ok(Some(key_event));
     }
     Ok(None)
 }
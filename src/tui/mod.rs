@@ -51,10 +51,10 @@ fn run_app<B: ratatui::backend::Backend>(
     loop {
         terminal.draw(|f| ui::draw(f, &app))?;
 
-        if let Some(event) = events::poll_event()? {
-            if !app.handle_event(event) {
-                break;
-            }
+        if let Some(event) = events::poll_event()?
+            && !app.handle_event(event)
+        {
+            break;
         }
     }
 